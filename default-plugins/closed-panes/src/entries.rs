@@ -0,0 +1,63 @@
+use zellij_tile::prelude::*;
+
+/// A one line description of what ran in the closed pane and how it ended
+pub fn entry_description(closed_pane: &ClosedPaneInfo) -> String {
+    let command = closed_pane.command.as_deref().unwrap_or("shell");
+    let mut description = match &closed_pane.cwd {
+        Some(cwd) => format!("{} in {}", command, cwd.display()),
+        None => command.to_owned(),
+    };
+    if let Some(exit_status) = closed_pane.exit_status {
+        description.push_str(&format!(" (exit code {})", exit_status));
+    }
+    description
+}
+
+/// The last lines of the closed pane's scrollback that fit in `max_rows`, each cut down to
+/// `max_width` characters
+pub fn preview_lines(scrollback: &[String], max_rows: usize, max_width: usize) -> Vec<String> {
+    scrollback
+        .iter()
+        .skip(scrollback.len().saturating_sub(max_rows))
+        .map(|line| line.chars().take(max_width).collect())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn closed_panes_are_described_by_their_command_and_exit_status() {
+        let shell = ClosedPaneInfo {
+            id: 1,
+            title: "Pane #1".to_owned(),
+            cwd: Some(PathBuf::from("/tmp")),
+            ..Default::default()
+        };
+        assert_eq!(entry_description(&shell), "shell in /tmp");
+        let command = ClosedPaneInfo {
+            id: 2,
+            title: "cargo test".to_owned(),
+            command: Some("cargo test".to_owned()),
+            exit_status: Some(101),
+            ..Default::default()
+        };
+        assert_eq!(entry_description(&command), "cargo test (exit code 101)");
+    }
+
+    #[test]
+    fn preview_shows_the_end_of_the_scrollback() {
+        let scrollback: Vec<String> = (0..5).map(|i| format!("line number {}", i)).collect();
+        assert_eq!(
+            preview_lines(&scrollback, 2, 6),
+            vec!["line n".to_owned(), "line n".to_owned()]
+        );
+        assert_eq!(
+            preview_lines(&scrollback, 2, 20),
+            vec!["line number 3".to_owned(), "line number 4".to_owned()]
+        );
+        assert_eq!(preview_lines(&scrollback, 10, 20).len(), 5);
+    }
+}
@@ -0,0 +1,173 @@
+mod entries;
+
+use std::collections::BTreeMap;
+use zellij_tile::prelude::*;
+
+use entries::{entry_description, preview_lines};
+
+#[derive(Default)]
+struct State {
+    closed_panes: Vec<ClosedPaneInfo>,
+    selected: usize,
+    preview: Vec<String>, // the scrollback of the selected pane
+    error: Option<String>,
+}
+
+register_plugin!(State);
+
+impl ZellijPlugin for State {
+    fn load(&mut self, _configuration: BTreeMap<String, String>) {
+        subscribe(&[EventType::Key]);
+        let own_plugin_id = get_plugin_ids().plugin_id;
+        rename_plugin_pane(own_plugin_id, "Reopen Closed Pane");
+        match list_closed_panes() {
+            Ok(closed_panes) => self.closed_panes = closed_panes,
+            Err(e) => self.error = Some(e),
+        }
+        self.load_preview();
+    }
+    fn update(&mut self, event: Event) -> bool {
+        match event {
+            Event::Key(key) => self.handle_key(key),
+            _ => false,
+        }
+    }
+    fn render(&mut self, rows: usize, cols: usize) {
+        self.render_title();
+        let list_y = 2;
+        let max_list_rows = (rows.saturating_sub(list_y + 2) / 2).max(1); // half the space, the rest for the preview
+        let list_rows = self.render_list(list_y, max_list_rows, cols);
+        let preview_y = list_y + list_rows + 1;
+        let max_preview_rows = rows.saturating_sub(preview_y + 2); // 2 for the help and its padding
+        self.render_preview(preview_y, max_preview_rows, cols);
+        self.render_help(rows.saturating_sub(1), cols);
+    }
+}
+
+impl State {
+    fn handle_key(&mut self, key: KeyWithModifier) -> bool {
+        match key.bare_key {
+            BareKey::Down if key.has_no_modifiers() => {
+                if !self.closed_panes.is_empty() {
+                    self.selected = (self.selected + 1) % self.closed_panes.len();
+                    self.load_preview();
+                }
+            },
+            BareKey::Up if key.has_no_modifiers() => {
+                if !self.closed_panes.is_empty() {
+                    self.selected = self
+                        .selected
+                        .checked_sub(1)
+                        .unwrap_or(self.closed_panes.len() - 1);
+                    self.load_preview();
+                }
+            },
+            BareKey::Enter if key.has_no_modifiers() => {
+                if let Some(closed_pane) = self.closed_panes.get(self.selected) {
+                    // closing first so that the pane is reopened where the user was before
+                    close_self();
+                    reopen_closed_pane(closed_pane.id);
+                }
+                return false;
+            },
+            BareKey::Esc if key.has_no_modifiers() => {
+                close_self();
+                return false;
+            },
+            BareKey::Char('c') if key.has_modifiers(&[KeyModifier::Ctrl]) => {
+                close_self();
+                return false;
+            },
+            _ => return false,
+        }
+        true
+    }
+    fn load_preview(&mut self) {
+        self.preview = match self.closed_panes.get(self.selected) {
+            Some(closed_pane) => match get_closed_pane_scrollback(closed_pane.id) {
+                Ok(scrollback) => scrollback,
+                Err(e) => {
+                    self.error = Some(e);
+                    vec![]
+                },
+            },
+            None => vec![],
+        };
+    }
+    fn render_title(&self) {
+        let text = if let Some(error) = &self.error {
+            Text::new(format!(" {}", error)).error_color_all()
+        } else if self.closed_panes.is_empty() {
+            Text::new(" No panes were closed in this session yet")
+        } else {
+            Text::new(format!(
+                " RECENTLY CLOSED PANES ({})",
+                self.closed_panes.len()
+            ))
+            .color_range(2, ..)
+        };
+        print_text_with_coordinates(text, 0, 0, None, None);
+    }
+    fn render_list(&self, y: usize, max_rows: usize, cols: usize) -> usize {
+        if self.closed_panes.is_empty() {
+            return 0;
+        }
+        let start = self
+            .selected
+            .saturating_sub(max_rows / 2)
+            .min(self.closed_panes.len().saturating_sub(max_rows));
+        let items: Vec<NestedListItem> = self
+            .closed_panes
+            .iter()
+            .enumerate()
+            .skip(start)
+            .take(max_rows)
+            .map(|(i, closed_pane)| {
+                let title_len = closed_pane.title.chars().count();
+                let item = NestedListItem::new(format!(
+                    "{} - {}",
+                    closed_pane.title,
+                    entry_description(closed_pane)
+                ))
+                .color_range(0, ..title_len);
+                if i == self.selected {
+                    item.selected()
+                } else {
+                    item
+                }
+            })
+            .collect();
+        let rendered_rows = items.len();
+        print_nested_list_with_coordinates(items, 0, y, Some(cols), None);
+        rendered_rows
+    }
+    fn render_preview(&self, y: usize, max_rows: usize, cols: usize) {
+        if self.closed_panes.get(self.selected).is_none() || max_rows < 2 {
+            return;
+        }
+        let title = Text::new(" Last output:").color_range(3, ..);
+        print_text_with_coordinates(title, 0, y, None, None);
+        let max_width = cols.saturating_sub(1); // 1 for the indentation
+        for (i, line) in preview_lines(&self.preview, max_rows - 1, max_width)
+            .into_iter()
+            .enumerate()
+        {
+            let text = Text::new(format!(" {}", line)).dim_range(..);
+            print_text_with_coordinates(text, 0, y + 1 + i, Some(cols), None);
+        }
+    }
+    fn render_help(&self, y: usize, cols: usize) {
+        let full_text = "Help: <↓↑> - Navigate, <ENTER> - Reopen, <ESC> - Close";
+        let short_text = "<↓↑/ENTER/ESC> - Navigate/Reopen/Close";
+        if cols >= full_text.chars().count() {
+            let text = Text::new(full_text)
+                .color_range(3, 6..=9)
+                .color_range(3, 23..=29)
+                .color_range(3, 41..=45);
+            print_text_with_coordinates(text, 0, y, Some(cols), None);
+        } else {
+            let text = Text::new(short_text).color_range(3, ..=13);
+            print_text_with_coordinates(text, 0, y, Some(cols), None);
+        }
+    }
+}
@@ -0,0 +1,776 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use chrono::{DateTime, FixedOffset, Utc};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+use zellij_tile::prelude::*;
+
+use crate::template::{parse_template, uses_placeholder, Segment, StyleAttribute};
+use crate::LinePart;
+
+pub const CONFIG_FORMAT: &str = "format";
+const CONFIG_TAB_FORMAT: &str = "tab_format";
+const CONFIG_TAB_ACTIVE_FORMAT: &str = "tab_active_format";
+const CONFIG_TAB_BELL_FORMAT: &str = "tab_bell_format";
+const CONFIG_CLOCK_FORMAT: &str = "clock_format";
+const CONFIG_COMMAND: &str = "command";
+const CONFIG_COMMAND_INTERVAL: &str = "command_interval";
+
+const DEFAULT_TAB_FORMAT: &str =
+    "#[fg=ribbon_unselected.base,bg=ribbon_unselected.background] {index}:{name}{flags} #[default] ";
+const DEFAULT_TAB_ACTIVE_FORMAT: &str =
+    "#[fg=ribbon_selected.base,bg=ribbon_selected.background,bold] {index}:{name}{flags} #[default] ";
+const DEFAULT_TAB_BELL_FORMAT: &str =
+    "#[fg=ribbon_unselected.emphasis_3,bg=ribbon_unselected.background,bold] {index}:{name}{flags} #[default] ";
+const DEFAULT_CLOCK_FORMAT: &str = "%H:%M";
+const DEFAULT_COMMAND_INTERVAL: f64 = 10.0;
+
+const TICK_SECONDS: f64 = 1.0;
+// listing clients has the server query the running command of every focused pane, so we don't
+// do it every tick
+const CLIENTS_REFRESH_TICKS: usize = 5;
+const COMMAND_CONTEXT: &str = "format_bar_command";
+// plugins can't read the host's time zone, so we ask the host for its UTC offset, and ask again
+// every hour so the clock follows daylight saving time changes
+const UTC_OFFSET_CONTEXT: &str = "format_bar_utc_offset";
+const UTC_OFFSET_REFRESH_TICKS: usize = 3600;
+
+/// What the bar is rendered from, owned by the rest of the plugin
+pub struct BarContext<'a> {
+    pub mode_info: &'a ModeInfo,
+    pub tabs: &'a [TabInfo],
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct RunStyle {
+    fg: Option<PaletteColor>,
+    bg: Option<PaletteColor>,
+    bold: bool,
+    dim: bool,
+    italic: bool,
+    underline: bool,
+    reverse: bool,
+}
+
+impl RunStyle {
+    fn apply(&mut self, attribute: &StyleAttribute, theme: &Styling) {
+        match attribute {
+            StyleAttribute::Fg(color) => self.fg = color.resolve(theme),
+            StyleAttribute::Bg(color) => self.bg = color.resolve(theme),
+            StyleAttribute::Bold(bold) => self.bold = *bold,
+            StyleAttribute::Dim(dim) => self.dim = *dim,
+            StyleAttribute::Italic(italic) => self.italic = *italic,
+            StyleAttribute::Underline(underline) => self.underline = *underline,
+            StyleAttribute::Reverse(reverse) => self.reverse = *reverse,
+            StyleAttribute::Default => *self = RunStyle::default(),
+            StyleAttribute::AlignLeft | StyleAttribute::AlignRight => {},
+        }
+    }
+    fn paint(&self, text: &str, theme: &Styling) -> String {
+        let to_ansi = |color: PaletteColor| match color {
+            PaletteColor::Rgb((r, g, b)) => ansi_term::Color::RGB(r, g, b),
+            PaletteColor::EightBit(color) => ansi_term::Color::Fixed(color),
+        };
+        let mut style = ansi_term::Style::new()
+            .fg(to_ansi(self.fg.unwrap_or(theme.text_unselected.base)))
+            .on(to_ansi(self.bg.unwrap_or(theme.text_unselected.background)));
+        if self.bold {
+            style = style.bold();
+        }
+        if self.dim {
+            style = style.dimmed();
+        }
+        if self.italic {
+            style = style.italic();
+        }
+        if self.underline {
+            style = style.underline();
+        }
+        if self.reverse {
+            style = style.reverse();
+        }
+        style.paint(text).to_string()
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Run {
+    text: String,
+    style: RunStyle,
+    tab_index: Option<usize>,
+}
+
+impl Run {
+    fn width(&self) -> usize {
+        self.text.width()
+    }
+}
+
+/// The runs of a rendered template, split by alignment
+#[derive(Debug, Default)]
+struct Line {
+    left: Vec<Run>,
+    right: Vec<Run>,
+    is_right_aligned: bool,
+    style: RunStyle,
+}
+
+impl Line {
+    fn push(&mut self, text: String, tab_index: Option<usize>) {
+        if text.is_empty() {
+            return;
+        }
+        let run = Run {
+            text,
+            style: self.style,
+            tab_index,
+        };
+        if self.is_right_aligned {
+            self.right.push(run);
+        } else {
+            self.left.push(run);
+        }
+    }
+}
+
+/// A bar rendered from user templates, as an alternative to the compact bar's fixed layout. It is
+/// used when the plugin is configured with a `format`, eg.
+/// `format " {session} #[fg=emphasis_0,bold]{mode}#[default] {tabs}#[align=right]{command} {clock} "`
+///
+/// Placeholders: `{session}`, `{mode}`, `{tabs}`, `{tab_count}`, `{swap_layout}` (suffixed with
+/// `*` once the layout was changed), `{clock}` (see `clock_format`), `{pane_command}`,
+/// `{pane_cwd}`, `{clients}` and `{command}`, the first line of the output of the `command`
+/// configuration, refreshed every `command_interval` seconds.
+///
+/// The clock is shown in the UTC offset reported by the host's `date +%z`, and in UTC if that
+/// can't be run. Since only the offset is known, `%Z` in `clock_format` shows it rather than the
+/// name of the time zone.
+///
+/// Each tab is rendered with `tab_format`, `tab_active_format` or `tab_bell_format`, which can
+/// also use `{index}`, `{name}` and `{flags}` (`Z` for fullscreen, `S` for synced panes and `!`
+/// for a bell).
+#[derive(Debug, Default)]
+pub struct FormatBar {
+    format: Vec<Segment>,
+    tab_format: Vec<Segment>,
+    tab_active_format: Vec<Segment>,
+    tab_bell_format: Vec<Segment>,
+    clock_format: String,
+    command: Option<String>,
+    command_interval: f64,
+
+    clock: String,
+    utc_offset: Option<FixedOffset>,
+    ticks_until_utc_offset_refresh: usize,
+    command_output: String,
+    command_is_running: bool,
+    seconds_until_command: f64,
+    ticks_until_clients_refresh: usize,
+    client_count: usize,
+    focused_pane: Option<PaneId>,
+    pane_command: String,
+    pane_cwd: String,
+}
+
+impl FormatBar {
+    pub fn from_config(config: &BTreeMap<String, String>) -> Option<Self> {
+        let format = parse_template(config.get(CONFIG_FORMAT)?);
+        let template = |key: &str, default: &str| {
+            parse_template(config.get(key).map(|t| t.as_str()).unwrap_or(default))
+        };
+        Some(FormatBar {
+            format,
+            tab_format: template(CONFIG_TAB_FORMAT, DEFAULT_TAB_FORMAT),
+            tab_active_format: template(CONFIG_TAB_ACTIVE_FORMAT, DEFAULT_TAB_ACTIVE_FORMAT),
+            tab_bell_format: template(CONFIG_TAB_BELL_FORMAT, DEFAULT_TAB_BELL_FORMAT),
+            clock_format: config
+                .get(CONFIG_CLOCK_FORMAT)
+                .cloned()
+                .unwrap_or_else(|| DEFAULT_CLOCK_FORMAT.to_owned()),
+            command: config.get(CONFIG_COMMAND).cloned(),
+            command_interval: config
+                .get(CONFIG_COMMAND_INTERVAL)
+                .and_then(|i| i.parse().ok())
+                .filter(|i: &f64| *i >= TICK_SECONDS)
+                .unwrap_or(DEFAULT_COMMAND_INTERVAL),
+            ..Default::default()
+        })
+    }
+    pub fn event_types(&self) -> Vec<EventType> {
+        vec![
+            EventType::Timer,
+            EventType::RunCommandResult,
+            EventType::ListClients,
+            EventType::CwdChanged,
+        ]
+    }
+    pub fn load(&mut self) {
+        self.update_clock();
+        self.refresh_utc_offset();
+        self.run_command();
+        self.refresh_clients();
+        if self.needs_ticks() {
+            set_timeout(TICK_SECONDS);
+        }
+    }
+    pub fn handle_timer(&mut self) -> bool {
+        if !self.needs_ticks() {
+            return false;
+        }
+        set_timeout(TICK_SECONDS);
+        self.seconds_until_command -= TICK_SECONDS;
+        if self.seconds_until_command <= 0.0 {
+            self.run_command();
+        }
+        self.ticks_until_clients_refresh = self.ticks_until_clients_refresh.saturating_sub(1);
+        if self.ticks_until_clients_refresh == 0 {
+            self.refresh_clients();
+        }
+        self.ticks_until_utc_offset_refresh = self.ticks_until_utc_offset_refresh.saturating_sub(1);
+        if self.ticks_until_utc_offset_refresh == 0 {
+            self.refresh_utc_offset();
+        }
+        self.update_clock()
+    }
+    pub fn handle_command_result(
+        &mut self,
+        exit_code: Option<i32>,
+        stdout: Vec<u8>,
+        context: BTreeMap<String, String>,
+    ) -> bool {
+        if context.contains_key(UTC_OFFSET_CONTEXT) {
+            if exit_code == Some(0) {
+                self.utc_offset = parse_utc_offset(&String::from_utf8_lossy(&stdout));
+            }
+            return self.update_clock();
+        }
+        if !context.contains_key(COMMAND_CONTEXT) {
+            return false;
+        }
+        self.command_is_running = false;
+        let output = if exit_code == Some(0) {
+            String::from_utf8_lossy(&stdout)
+                .lines()
+                .next()
+                .unwrap_or("")
+                .trim()
+                .to_owned()
+        } else {
+            String::new()
+        };
+        let should_render = output != self.command_output;
+        self.command_output = output;
+        should_render
+    }
+    pub fn handle_list_clients(&mut self, clients: Vec<ClientInfo>) -> bool {
+        let previous = (
+            self.client_count,
+            self.focused_pane,
+            self.pane_command.clone(),
+        );
+        self.client_count = clients.len();
+        if let Some(current_client) = clients.into_iter().find(|c| c.is_current_client) {
+            if self.focused_pane != Some(current_client.pane_id) {
+                self.focused_pane = Some(current_client.pane_id);
+                self.pane_cwd = self.read_pane_cwd(current_client.pane_id);
+            }
+            self.pane_command = current_client.running_command;
+        }
+        previous
+            != (
+                self.client_count,
+                self.focused_pane,
+                self.pane_command.clone(),
+            )
+    }
+    pub fn handle_cwd_changed(&mut self, pane_id: PaneId, cwd: std::path::PathBuf) -> bool {
+        if self.focused_pane != Some(pane_id) {
+            return false;
+        }
+        let cwd = cwd.display().to_string();
+        let should_render = cwd != self.pane_cwd;
+        self.pane_cwd = cwd;
+        should_render
+    }
+    /// Focus changes come with a pane update, which is when the focused pane's command and cwd
+    /// might have changed
+    pub fn handle_pane_update(&mut self, pane_manifest: &PaneManifest, tabs: &[TabInfo]) {
+        let focused_pane = tabs.iter().find(|t| t.active).and_then(|tab| {
+            let panes = pane_manifest.panes.get(&tab.position)?;
+            let focused_in_layer = |is_floating: bool| {
+                panes
+                    .iter()
+                    .find(|p| p.is_focused && p.is_floating == is_floating && !p.is_suppressed)
+            };
+            let focused = if tab.are_floating_panes_visible {
+                focused_in_layer(true).or_else(|| focused_in_layer(false))
+            } else {
+                focused_in_layer(false)
+            };
+            focused.map(|p| {
+                if p.is_plugin {
+                    PaneId::Plugin(p.id)
+                } else {
+                    PaneId::Terminal(p.id)
+                }
+            })
+        });
+        if focused_pane.is_some() && focused_pane != self.focused_pane {
+            self.refresh_clients();
+        }
+    }
+    pub fn render(&self, context: &BarContext, cols: usize) -> Vec<LinePart> {
+        let theme = &context.mode_info.style.colors;
+        let active_tab_index = context.tabs.iter().position(|t| t.active).unwrap_or(0);
+        let mut line = Line::default();
+        self.expand(&self.format, context, None, &mut line);
+        let mut left = line.left;
+        let mut right = line.right;
+
+        let total_width = |runs: &[Run]| runs.iter().map(|r| r.width()).sum::<usize>();
+        if total_width(&left) + total_width(&right) > cols {
+            let available = cols
+                .saturating_sub(total_width(&left) + total_width(&right) - tab_runs_width(&left));
+            fit_tabs(&mut left, active_tab_index, available);
+        }
+        let right_width = total_width(&right);
+        truncate_runs(&mut left, cols.saturating_sub(right_width));
+        let left_width = total_width(&left);
+        truncate_runs(&mut right, cols.saturating_sub(left_width));
+
+        let spacer_width = cols.saturating_sub(left_width + total_width(&right));
+        let spacer = Run {
+            text: " ".repeat(spacer_width),
+            style: RunStyle::default(),
+            tab_index: None,
+        };
+        left.into_iter()
+            .chain(std::iter::once(spacer))
+            .chain(right)
+            .filter(|run| !run.text.is_empty())
+            .map(|run| LinePart {
+                part: run.style.paint(&run.text, theme),
+                len: run.width(),
+                tab_index: run.tab_index,
+            })
+            .collect()
+    }
+    fn expand(
+        &self,
+        segments: &[Segment],
+        context: &BarContext,
+        tab: Option<&TabInfo>,
+        line: &mut Line,
+    ) {
+        let theme = &context.mode_info.style.colors;
+        let tab_index = tab.map(|t| t.position);
+        for segment in segments {
+            match segment {
+                Segment::Text(text) => line.push(text.clone(), tab_index),
+                Segment::Style(attributes) => {
+                    for attribute in attributes {
+                        match attribute {
+                            StyleAttribute::AlignLeft => line.is_right_aligned = false,
+                            StyleAttribute::AlignRight => line.is_right_aligned = true,
+                            _ => line.style.apply(attribute, theme),
+                        }
+                    }
+                },
+                Segment::Placeholder(name) if name == "tabs" && tab.is_none() => {
+                    let outer_style = line.style;
+                    for tab in context.tabs {
+                        line.style = outer_style;
+                        self.expand(self.tab_template(tab), context, Some(tab), line);
+                    }
+                    line.style = outer_style;
+                },
+                Segment::Placeholder(name) => {
+                    let value = self
+                        .placeholder_value(name, context, tab)
+                        .unwrap_or_else(|| format!("{{{}}}", name));
+                    line.push(value, tab_index);
+                },
+            }
+        }
+    }
+    fn tab_template(&self, tab: &TabInfo) -> &[Segment] {
+        if tab.is_flashing_bell || (tab.has_bell_notification && !tab.active) {
+            &self.tab_bell_format
+        } else if tab.active {
+            &self.tab_active_format
+        } else {
+            &self.tab_format
+        }
+    }
+    /// Returns `None` for unknown placeholders, which are then shown as they are so that typos
+    /// are easy to spot
+    fn placeholder_value(
+        &self,
+        name: &str,
+        context: &BarContext,
+        tab: Option<&TabInfo>,
+    ) -> Option<String> {
+        let active_tab = context.tabs.iter().find(|t| t.active);
+        let value = match name {
+            "session" => context.mode_info.session_name.clone().unwrap_or_default(),
+            "mode" => format!("{:?}", context.mode_info.mode).to_uppercase(),
+            "swap_layout" => match active_tab.and_then(|t| t.active_swap_layout_name.as_ref()) {
+                Some(name) if active_tab.map(|t| t.is_swap_layout_dirty) == Some(true) => {
+                    format!("{}*", name)
+                },
+                Some(name) => name.clone(),
+                None => String::new(),
+            },
+            "clock" => self.clock.clone(),
+            "pane_command" => self.pane_command.clone(),
+            "pane_cwd" => self.pane_cwd.clone(),
+            "clients" => self.client_count.to_string(),
+            "command" => self.command_output.clone(),
+            "tab_count" => context.tabs.len().to_string(),
+            "index" => (tab?.position + 1).to_string(),
+            "name" => tab?.name.clone(),
+            "flags" => tab_flags(tab?),
+            _ => return None,
+        };
+        Some(value)
+    }
+    fn needs_ticks(&self) -> bool {
+        self.command.is_some()
+            || uses_placeholder(&self.format, "clock")
+            || uses_placeholder(&self.format, "clients")
+            || uses_placeholder(&self.format, "pane_command")
+    }
+    fn update_clock(&mut self) -> bool {
+        let clock = format_clock(Utc::now(), self.utc_offset, &self.clock_format);
+        let should_render = clock != self.clock;
+        self.clock = clock;
+        should_render
+    }
+    fn refresh_utc_offset(&mut self) {
+        self.ticks_until_utc_offset_refresh = UTC_OFFSET_REFRESH_TICKS;
+        if !uses_placeholder(&self.format, "clock") {
+            return;
+        }
+        let mut context = BTreeMap::new();
+        context.insert(UTC_OFFSET_CONTEXT.to_owned(), String::new());
+        run_command(&["date", "+%z"], context);
+    }
+    fn run_command(&mut self) {
+        self.seconds_until_command = self.command_interval;
+        let command = match self.command.as_ref() {
+            Some(command) if !self.command_is_running => command,
+            _ => return,
+        };
+        self.command_is_running = true;
+        let mut context = BTreeMap::new();
+        context.insert(COMMAND_CONTEXT.to_owned(), String::new());
+        let args = ["sh", "-c", command.as_str()];
+        if self.pane_cwd.is_empty() {
+            run_command(&args, context);
+        } else {
+            run_command_with_env_variables_and_cwd(
+                &args,
+                BTreeMap::new(),
+                self.pane_cwd.clone().into(),
+                context,
+            );
+        }
+    }
+    fn refresh_clients(&mut self) {
+        self.ticks_until_clients_refresh = CLIENTS_REFRESH_TICKS;
+        list_clients();
+    }
+    fn read_pane_cwd(&self, pane_id: PaneId) -> String {
+        get_pane_cwd(pane_id)
+            .map(|cwd| cwd.display().to_string())
+            .unwrap_or_default()
+    }
+}
+
+/// Parses a UTC offset as printed by `date +%z`, eg. `+0530`
+fn parse_utc_offset(offset: &str) -> Option<FixedOffset> {
+    let offset = offset.trim();
+    let (sign, digits) = match offset.split_at_checked(1)? {
+        ("+", digits) => (1, digits),
+        ("-", digits) => (-1, digits),
+        _ => return None,
+    };
+    if digits.len() != 4 || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let hours: i32 = digits[..2].parse().ok()?;
+    let minutes: i32 = digits[2..].parse().ok()?;
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60))
+}
+
+fn format_clock(now: DateTime<Utc>, utc_offset: Option<FixedOffset>, clock_format: &str) -> String {
+    let now = now.with_timezone(&utc_offset.unwrap_or_else(|| FixedOffset::east_opt(0).unwrap()));
+    let mut clock = String::new();
+    if write!(clock, "{}", now.format(clock_format)).is_err() {
+        // an invalid format string
+        clock = clock_format.to_owned();
+    }
+    clock
+}
+
+fn tab_flags(tab: &TabInfo) -> String {
+    let mut flags = String::new();
+    if tab.is_fullscreen_active {
+        flags.push('Z');
+    }
+    if tab.is_sync_panes_active {
+        flags.push('S');
+    }
+    if tab.has_bell_notification || tab.is_flashing_bell {
+        flags.push('!');
+    }
+    if flags.is_empty() {
+        flags
+    } else {
+        format!(" {}", flags)
+    }
+}
+
+fn tab_runs_width(runs: &[Run]) -> usize {
+    runs.iter()
+        .filter(|r| r.tab_index.is_some())
+        .map(|r| r.width())
+        .sum()
+}
+
+/// Hides the tabs furthest from the active one until the rest fit in `available` columns,
+/// replacing them with a count that can be clicked to get to them
+fn fit_tabs(runs: &mut Vec<Run>, active_tab_index: usize, available: usize) {
+    let tab_width = |runs: &[Run], index: usize| -> usize {
+        runs.iter()
+            .filter(|r| r.tab_index == Some(index))
+            .map(|r| r.width())
+            .sum()
+    };
+    let tab_indices: Vec<usize> = {
+        let mut indices: Vec<usize> = runs.iter().filter_map(|r| r.tab_index).collect();
+        indices.dedup();
+        indices
+    };
+    let (mut first, mut last) = match (tab_indices.first(), tab_indices.last()) {
+        (Some(first), Some(last)) => (*first, *last),
+        _ => return,
+    };
+    let indicators_width = |first: usize, last: usize| {
+        let left = if first > tab_indices[0] {
+            format!(" ← +{} ", first - tab_indices[0]).width()
+        } else {
+            0
+        };
+        let right = if last < tab_indices[tab_indices.len() - 1] {
+            format!(" +{} → ", tab_indices[tab_indices.len() - 1] - last).width()
+        } else {
+            0
+        };
+        left + right
+    };
+    let mut visible_width = tab_runs_width(runs);
+    while visible_width + indicators_width(first, last) > available && first < last {
+        // drop the tab furthest from the active one
+        if active_tab_index.saturating_sub(first) >= last.saturating_sub(active_tab_index) {
+            visible_width -= tab_width(runs, first);
+            first += 1;
+        } else {
+            visible_width -= tab_width(runs, last);
+            last -= 1;
+        }
+    }
+    let hidden_before = first - tab_indices[0];
+    let hidden_after = tab_indices[tab_indices.len() - 1] - last;
+    let style = runs
+        .iter()
+        .find(|r| r.tab_index.is_some())
+        .map(|r| r.style)
+        .unwrap_or_default();
+    let Some(tabs_start) = runs.iter().position(|r| r.tab_index.is_some()) else {
+        return;
+    };
+    runs.retain(|r| match r.tab_index {
+        Some(index) => index >= first && index <= last,
+        None => true,
+    });
+    if hidden_after > 0 {
+        let insert_at = runs
+            .iter()
+            .rposition(|r| r.tab_index.is_some())
+            .map(|i| i + 1)
+            .unwrap_or(tabs_start);
+        runs.insert(
+            insert_at,
+            Run {
+                text: format!(" +{} → ", hidden_after),
+                style,
+                tab_index: Some(last + 1),
+            },
+        );
+    }
+    if hidden_before > 0 {
+        runs.insert(
+            tabs_start,
+            Run {
+                text: format!(" ← +{} ", hidden_before),
+                style,
+                tab_index: Some(first - 1),
+            },
+        );
+    }
+}
+
+/// Cuts the runs down to `cols` columns, dropping whatever doesn't fit
+fn truncate_runs(runs: &mut Vec<Run>, cols: usize) {
+    let mut remaining = cols;
+    for run in runs.iter_mut() {
+        if run.width() <= remaining {
+            remaining -= run.width();
+            continue;
+        }
+        let mut truncated = String::new();
+        for c in run.text.chars() {
+            let width = c.width().unwrap_or(0);
+            if width > remaining {
+                break;
+            }
+            remaining -= width;
+            truncated.push(c);
+        }
+        run.text = truncated;
+        remaining = 0;
+    }
+    runs.retain(|r| !r.text.is_empty());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tab(position: usize, name: &str, active: bool) -> TabInfo {
+        TabInfo {
+            position,
+            name: name.to_owned(),
+            active,
+            ..Default::default()
+        }
+    }
+
+    fn render_to_text(
+        bar: &FormatBar,
+        mode_info: &ModeInfo,
+        tabs: &[TabInfo],
+        cols: usize,
+    ) -> String {
+        let context = BarContext { mode_info, tabs };
+        bar.render(&context, cols)
+            .iter()
+            .map(|part| {
+                // strip the ansi styling, leaving only the text
+                let mut text = String::new();
+                let mut in_escape = false;
+                for c in part.part.chars() {
+                    match (in_escape, c) {
+                        (false, '\u{1b}') => in_escape = true,
+                        (true, 'm') => in_escape = false,
+                        (false, c) => text.push(c),
+                        _ => {},
+                    }
+                }
+                text
+            })
+            .collect()
+    }
+
+    #[test]
+    fn format_is_filled_in_and_aligned() {
+        let mut config = BTreeMap::new();
+        config.insert(
+            CONFIG_FORMAT.to_owned(),
+            "[{session}] {tabs}#[align=right]{mode} {nope}".to_owned(),
+        );
+        config.insert(CONFIG_TAB_FORMAT.to_owned(), "{index}:{name} ".to_owned());
+        config.insert(
+            CONFIG_TAB_ACTIVE_FORMAT.to_owned(),
+            "{index}*{name} ".to_owned(),
+        );
+        let bar = FormatBar::from_config(&config).unwrap();
+        let mode_info = ModeInfo {
+            session_name: Some("work".to_owned()),
+            ..Default::default()
+        };
+        let tabs = vec![
+            tab(0, "code", false),
+            tab(1, "logs", true),
+            tab(2, "notes", false),
+        ];
+        assert_eq!(
+            render_to_text(&bar, &mode_info, &tabs, 50),
+            "[work] 1:code 2*logs 3:notes         NORMAL {nope}"
+        );
+        // the tabs furthest from the active one make way first
+        assert_eq!(
+            render_to_text(&bar, &mode_info, &tabs, 40),
+            "[work]  ← +1 2*logs  +1 →  NORMAL {nope}"
+        );
+    }
+
+    #[test]
+    fn utc_offsets_are_parsed_from_date_output() {
+        assert_eq!(
+            parse_utc_offset("+0530\n"),
+            FixedOffset::east_opt(5 * 3600 + 30 * 60)
+        );
+        assert_eq!(parse_utc_offset("-0800"), FixedOffset::west_opt(8 * 3600));
+        assert_eq!(parse_utc_offset("+0000"), FixedOffset::east_opt(0));
+        assert_eq!(parse_utc_offset("CEST"), None);
+        assert_eq!(parse_utc_offset("+05:30"), None);
+        assert_eq!(parse_utc_offset(""), None);
+    }
+
+    #[test]
+    fn clock_is_shown_in_the_host_utc_offset() {
+        let now = DateTime::parse_from_rfc3339("2024-03-10T23:45:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        assert_eq!(format_clock(now, None, "%H:%M"), "23:45");
+        assert_eq!(
+            format_clock(now, parse_utc_offset("+0200"), "%a %H:%M"),
+            "Mon 01:45"
+        );
+        assert_eq!(
+            format_clock(now, parse_utc_offset("-0430"), "%H:%M"),
+            "19:15"
+        );
+        assert_eq!(
+            format_clock(now, None, "%Q"),
+            "%Q",
+            "an invalid format is shown as is"
+        );
+    }
+
+    #[test]
+    fn utc_offset_result_updates_the_clock() {
+        let mut config = BTreeMap::new();
+        config.insert(CONFIG_FORMAT.to_owned(), "{clock}".to_owned());
+        config.insert(CONFIG_CLOCK_FORMAT.to_owned(), "%z".to_owned());
+        let mut bar = FormatBar::from_config(&config).unwrap();
+        bar.update_clock();
+        assert_eq!(bar.clock, "+0000");
+
+        let mut context = BTreeMap::new();
+        context.insert(UTC_OFFSET_CONTEXT.to_owned(), String::new());
+        assert!(bar.handle_command_result(Some(0), b"+0100\n".to_vec(), context.clone()));
+        assert_eq!(bar.clock, "+0100");
+        assert_eq!(
+            bar.command_output, "",
+            "the offset isn't taken for the output of the user's command"
+        );
+        assert!(
+            !bar.handle_command_result(Some(1), vec![], context),
+            "a failed command keeps the last known offset"
+        );
+        assert_eq!(bar.clock, "+0100");
+    }
+}
@@ -1,8 +1,10 @@
 mod action_types;
 mod clipboard_utils;
+mod format_bar;
 mod keybind_utils;
 mod line;
 mod tab;
+mod template;
 mod tooltip;
 
 use std::cmp::{max, min};
@@ -13,6 +15,7 @@ use tab::get_tab_to_focus;
 use zellij_tile::prelude::*;
 
 use crate::clipboard_utils::{system_clipboard_error, text_copied_hint};
+use crate::format_bar::{BarContext, FormatBar};
 use crate::line::tab_line;
 use crate::tab::tab_style;
 use crate::tooltip::TooltipRenderer;
@@ -63,6 +66,9 @@ struct State {
 
     // Keybinding cache
     cached_keybinds: KeybindsVec,
+
+    // Set when the bar is rendered from a user template
+    format_bar: Option<FormatBar>,
 }
 
 struct TabRenderData {
@@ -82,6 +88,9 @@ impl ZellijPlugin for State {
         self.initialize_configuration(configuration);
         self.setup_subscriptions();
         self.configure_keybinds();
+        if let Some(format_bar) = self.format_bar.as_mut() {
+            format_bar.load();
+        }
     }
 
     fn update(&mut self, event: Event) -> bool {
@@ -105,6 +114,26 @@ impl ZellijPlugin for State {
             },
             Event::TabUpdate(tabs) => self.handle_tab_update(tabs),
             Event::PaneUpdate(pane_manifest) => self.handle_pane_update(pane_manifest),
+            Event::Timer(_) => self
+                .format_bar
+                .as_mut()
+                .map(|f| f.handle_timer())
+                .unwrap_or(false),
+            Event::RunCommandResult(exit_code, stdout, _stderr, context) => self
+                .format_bar
+                .as_mut()
+                .map(|f| f.handle_command_result(exit_code, stdout, context))
+                .unwrap_or(false),
+            Event::ListClients(clients) => self
+                .format_bar
+                .as_mut()
+                .map(|f| f.handle_list_clients(clients))
+                .unwrap_or(false),
+            Event::CwdChanged(pane_id, cwd, _focused_client_ids) => self
+                .format_bar
+                .as_mut()
+                .map(|f| f.handle_cwd_changed(pane_id, cwd))
+                .unwrap_or(false),
             Event::Mouse(mouse_event) => {
                 self.handle_mouse_event(mouse_event);
                 false
@@ -156,13 +185,15 @@ impl State {
 
         if self.is_tooltip {
             self.is_first_run = true;
+        } else {
+            self.format_bar = FormatBar::from_config(&configuration);
         }
     }
 
     fn setup_subscriptions(&self) {
         set_selectable(false);
 
-        let events = if self.is_tooltip {
+        let mut events = if self.is_tooltip {
             vec![
                 EventType::ModeUpdate,
                 EventType::TabUpdate,
@@ -180,6 +211,9 @@ impl State {
                 EventType::InitialKeybinds,
             ]
         };
+        if let Some(format_bar) = self.format_bar.as_ref() {
+            events.append(&mut format_bar.event_types());
+        }
 
         subscribe(&events);
     }
@@ -262,6 +296,9 @@ impl State {
     }
 
     fn handle_pane_update(&mut self, pane_manifest: PaneManifest) -> bool {
+        if let Some(format_bar) = self.format_bar.as_mut() {
+            format_bar.handle_pane_update(&pane_manifest, &self.tabs);
+        }
         if self.toggle_tooltip_key.is_some() {
             let previous_tooltip_state = self.tooltip_is_active;
             self.tooltip_is_active = self.detect_tooltip_presence(&pane_manifest);
@@ -513,14 +550,22 @@ impl State {
             return;
         }
 
-        let tab_data = self.prepare_tab_data();
-        self.tab_line = tab_line(
-            &self.mode_info,
-            tab_data,
-            cols,
-            self.toggle_tooltip_key.clone(),
-            self.tooltip_is_active,
-        );
+        if let Some(format_bar) = self.format_bar.as_ref() {
+            let context = BarContext {
+                mode_info: &self.mode_info,
+                tabs: &self.tabs,
+            };
+            self.tab_line = format_bar.render(&context, cols);
+        } else {
+            let tab_data = self.prepare_tab_data();
+            self.tab_line = tab_line(
+                &self.mode_info,
+                tab_data,
+                cols,
+                self.toggle_tooltip_key.clone(),
+                self.tooltip_is_active,
+            );
+        }
 
         let output = self
             .tab_line
@@ -0,0 +1,274 @@
+use zellij_tile::prelude::*;
+
+/// A piece of a bar template, in the spirit of tmux's `status-format`: literal text,
+/// `{placeholders}` that are replaced with session state and `#[styles]` that apply to everything
+/// after them
+#[derive(Debug, Clone, PartialEq)]
+pub enum Segment {
+    Text(String),
+    Placeholder(String),
+    Style(Vec<StyleAttribute>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StyleAttribute {
+    Fg(ColorSpec),
+    Bg(ColorSpec),
+    Bold(bool),
+    Dim(bool),
+    Italic(bool),
+    Underline(bool),
+    Reverse(bool),
+    /// Resets colors and attributes to the bar's defaults
+    Default,
+    AlignLeft,
+    AlignRight,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ColorSpec {
+    /// The bar's own foreground or background
+    Default,
+    Theme(ThemeComponent, ThemeField),
+    Rgb(u8, u8, u8),
+    EightBit(u8),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ThemeComponent {
+    TextUnselected,
+    TextSelected,
+    RibbonUnselected,
+    RibbonSelected,
+    TableTitle,
+    TableCellUnselected,
+    TableCellSelected,
+    ListUnselected,
+    ListSelected,
+    FrameSelected,
+    FrameHighlight,
+    ExitCodeSuccess,
+    ExitCodeError,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ThemeField {
+    Base,
+    Background,
+    Emphasis0,
+    Emphasis1,
+    Emphasis2,
+    Emphasis3,
+}
+
+impl ColorSpec {
+    /// Theme colors are named `<component>.<field>` after the theme's own sections (eg.
+    /// `ribbon_selected.background`), a bare field refers to `text_unselected`
+    fn parse(color: &str) -> Option<Self> {
+        if color == "default" {
+            return Some(ColorSpec::Default);
+        }
+        if let Some(hex) = color.strip_prefix('#') {
+            if hex.len() != 6 || !hex.is_ascii() {
+                return None;
+            }
+            let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+            return Some(ColorSpec::Rgb(channel(0)?, channel(2)?, channel(4)?));
+        }
+        if let Ok(color) = color.strip_prefix("colour").unwrap_or(color).parse::<u8>() {
+            return Some(ColorSpec::EightBit(color));
+        }
+        let (component, field) = match color.split_once('.') {
+            Some((component, field)) => (ThemeComponent::parse(component)?, field),
+            None => (ThemeComponent::TextUnselected, color),
+        };
+        Some(ColorSpec::Theme(component, ThemeField::parse(field)?))
+    }
+    /// Returns `None` for the bar's default color
+    pub fn resolve(&self, theme: &Styling) -> Option<PaletteColor> {
+        match self {
+            ColorSpec::Default => None,
+            ColorSpec::Theme(component, field) => {
+                let declaration = component.declaration(theme);
+                Some(match field {
+                    ThemeField::Base => declaration.base,
+                    ThemeField::Background => declaration.background,
+                    ThemeField::Emphasis0 => declaration.emphasis_0,
+                    ThemeField::Emphasis1 => declaration.emphasis_1,
+                    ThemeField::Emphasis2 => declaration.emphasis_2,
+                    ThemeField::Emphasis3 => declaration.emphasis_3,
+                })
+            },
+            ColorSpec::Rgb(r, g, b) => Some(PaletteColor::Rgb((*r, *g, *b))),
+            ColorSpec::EightBit(color) => Some(PaletteColor::EightBit(*color)),
+        }
+    }
+}
+
+impl ThemeComponent {
+    fn parse(component: &str) -> Option<Self> {
+        match component {
+            "text_unselected" => Some(ThemeComponent::TextUnselected),
+            "text_selected" => Some(ThemeComponent::TextSelected),
+            "ribbon_unselected" => Some(ThemeComponent::RibbonUnselected),
+            "ribbon_selected" => Some(ThemeComponent::RibbonSelected),
+            "table_title" => Some(ThemeComponent::TableTitle),
+            "table_cell_unselected" => Some(ThemeComponent::TableCellUnselected),
+            "table_cell_selected" => Some(ThemeComponent::TableCellSelected),
+            "list_unselected" => Some(ThemeComponent::ListUnselected),
+            "list_selected" => Some(ThemeComponent::ListSelected),
+            "frame_selected" => Some(ThemeComponent::FrameSelected),
+            "frame_highlight" => Some(ThemeComponent::FrameHighlight),
+            "exit_code_success" => Some(ThemeComponent::ExitCodeSuccess),
+            "exit_code_error" => Some(ThemeComponent::ExitCodeError),
+            _ => None,
+        }
+    }
+    fn declaration(&self, theme: &Styling) -> StyleDeclaration {
+        match self {
+            ThemeComponent::TextUnselected => theme.text_unselected,
+            ThemeComponent::TextSelected => theme.text_selected,
+            ThemeComponent::RibbonUnselected => theme.ribbon_unselected,
+            ThemeComponent::RibbonSelected => theme.ribbon_selected,
+            ThemeComponent::TableTitle => theme.table_title,
+            ThemeComponent::TableCellUnselected => theme.table_cell_unselected,
+            ThemeComponent::TableCellSelected => theme.table_cell_selected,
+            ThemeComponent::ListUnselected => theme.list_unselected,
+            ThemeComponent::ListSelected => theme.list_selected,
+            ThemeComponent::FrameSelected => theme.frame_selected,
+            ThemeComponent::FrameHighlight => theme.frame_highlight,
+            ThemeComponent::ExitCodeSuccess => theme.exit_code_success,
+            ThemeComponent::ExitCodeError => theme.exit_code_error,
+        }
+    }
+}
+
+impl ThemeField {
+    fn parse(field: &str) -> Option<Self> {
+        match field {
+            "base" => Some(ThemeField::Base),
+            "background" => Some(ThemeField::Background),
+            "emphasis_0" => Some(ThemeField::Emphasis0),
+            "emphasis_1" => Some(ThemeField::Emphasis1),
+            "emphasis_2" => Some(ThemeField::Emphasis2),
+            "emphasis_3" => Some(ThemeField::Emphasis3),
+            _ => None,
+        }
+    }
+}
+
+impl StyleAttribute {
+    fn parse(attribute: &str) -> Option<Self> {
+        let attribute = attribute.trim();
+        if let Some(color) = attribute.strip_prefix("fg=") {
+            return ColorSpec::parse(color).map(StyleAttribute::Fg);
+        }
+        if let Some(color) = attribute.strip_prefix("bg=") {
+            return ColorSpec::parse(color).map(StyleAttribute::Bg);
+        }
+        match attribute {
+            "default" | "none" => Some(StyleAttribute::Default),
+            "bold" => Some(StyleAttribute::Bold(true)),
+            "nobold" => Some(StyleAttribute::Bold(false)),
+            "dim" => Some(StyleAttribute::Dim(true)),
+            "nodim" => Some(StyleAttribute::Dim(false)),
+            "italics" => Some(StyleAttribute::Italic(true)),
+            "noitalics" => Some(StyleAttribute::Italic(false)),
+            "underscore" => Some(StyleAttribute::Underline(true)),
+            "nounderscore" => Some(StyleAttribute::Underline(false)),
+            "reverse" => Some(StyleAttribute::Reverse(true)),
+            "noreverse" => Some(StyleAttribute::Reverse(false)),
+            "align=left" => Some(StyleAttribute::AlignLeft),
+            "align=right" => Some(StyleAttribute::AlignRight),
+            _ => None,
+        }
+    }
+}
+
+/// Parses a template, `{{`, `}}` and `##` are literal braces and hashes. Unknown style
+/// attributes are ignored rather than failing the whole template, so that a typo only costs its
+/// own styling.
+pub fn parse_template(template: &str) -> Vec<Segment> {
+    let mut segments = vec![];
+    let mut text = String::new();
+    let mut chars = template.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (c, chars.peek()) {
+            ('{', Some('{')) | ('}', Some('}')) | ('#', Some('#')) => {
+                chars.next();
+                text.push(c);
+            },
+            ('{', _) => {
+                let placeholder: String = chars.by_ref().take_while(|c| *c != '}').collect();
+                flush_text(&mut text, &mut segments);
+                segments.push(Segment::Placeholder(placeholder.trim().to_owned()));
+            },
+            ('#', Some('[')) => {
+                chars.next();
+                let style: String = chars.by_ref().take_while(|c| *c != ']').collect();
+                flush_text(&mut text, &mut segments);
+                segments.push(Segment::Style(
+                    style.split(',').filter_map(StyleAttribute::parse).collect(),
+                ));
+            },
+            _ => text.push(c),
+        }
+    }
+    flush_text(&mut text, &mut segments);
+    segments
+}
+
+fn flush_text(text: &mut String, segments: &mut Vec<Segment>) {
+    if !text.is_empty() {
+        segments.push(Segment::Text(std::mem::take(text)));
+    }
+}
+
+/// Whether a template uses a placeholder, so that we only poll for what's shown
+pub fn uses_placeholder(segments: &[Segment], name: &str) -> bool {
+    segments
+        .iter()
+        .any(|s| matches!(s, Segment::Placeholder(p) if p == name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_template_with_placeholders_and_styles() {
+        let segments = parse_template(
+            "#[fg=ribbon_selected.base,bold,bogus] {session} {{x}} ##1#[align=right]{clock}",
+        );
+        assert_eq!(
+            segments,
+            vec![
+                Segment::Style(vec![
+                    StyleAttribute::Fg(ColorSpec::Theme(
+                        ThemeComponent::RibbonSelected,
+                        ThemeField::Base
+                    )),
+                    StyleAttribute::Bold(true),
+                ]),
+                Segment::Text(" ".to_owned()),
+                Segment::Placeholder("session".to_owned()),
+                Segment::Text(" {x} #1".to_owned()),
+                Segment::Style(vec![StyleAttribute::AlignRight]),
+                Segment::Placeholder("clock".to_owned()),
+            ]
+        );
+        assert_eq!(
+            ColorSpec::parse("emphasis_2"),
+            Some(ColorSpec::Theme(
+                ThemeComponent::TextUnselected,
+                ThemeField::Emphasis2
+            ))
+        );
+        assert_eq!(
+            ColorSpec::parse("#ff8000"),
+            Some(ColorSpec::Rgb(255, 128, 0))
+        );
+        assert_eq!(ColorSpec::parse("colour42"), Some(ColorSpec::EightBit(42)));
+        assert_eq!(ColorSpec::parse("ribbon_selected.nope"), None);
+    }
+}
@@ -75,6 +75,13 @@ keybinds clear-defaults=true {{
         bind "s" {{ SwitchToMode "Normal"; }}
         bind "e" {{ EditScrollback; SwitchToMode "Locked"; }}
         bind "f" {{ SwitchToMode "EnterSearch"; SearchInput 0; }}
+        bind "a" {{
+            LaunchOrFocusPlugin "zellij:pane-search" {{
+                floating true
+                move_to_focused_tab true
+            }};
+            SwitchToMode "Locked"
+        }}
         bind "Ctrl c" {{ ScrollToBottom; SwitchToMode "Locked"; }}
         bind "j" "Down" {{ ScrollDown; }}
         bind "k" "Up" {{ ScrollUp; }}
@@ -296,6 +303,13 @@ keybinds clear-defaults=true {{
         bind "{primary_modifier} s" {{ SwitchToMode "Normal"; }}
         bind "e" {{ EditScrollback; SwitchToMode "Normal"; }}
         bind "s" {{ SwitchToMode "EnterSearch"; SearchInput 0; }}
+        bind "a" {{
+            LaunchOrFocusPlugin "zellij:pane-search" {{
+                floating true
+                move_to_focused_tab true
+            }};
+            SwitchToMode "Normal"
+        }}
         bind "Ctrl c" {{ ScrollToBottom; SwitchToMode "Normal"; }}
         bind "j" "Down" {{ ScrollDown; }}
         bind "k" "Up" {{ ScrollUp; }}
@@ -525,6 +539,13 @@ keybinds clear-defaults=true {{
     scroll {{
         bind "e" {{ EditScrollback; SwitchToMode "Normal"; }}
         bind "s" {{ SwitchToMode "EnterSearch"; SearchInput 0; }}
+        bind "a" {{
+            LaunchOrFocusPlugin "zellij:pane-search" {{
+                floating true
+                move_to_focused_tab true
+            }};
+            SwitchToMode "Normal"
+        }}
         bind "j" "Down" {{ ScrollDown; }}
         bind "k" "Up" {{ ScrollUp; }}
         bind "d" {{ HalfPageScrollDown; }}
@@ -731,6 +752,13 @@ keybinds clear-defaults=true {{
         bind "{primary_modifier} s" {{ SwitchToMode "Normal"; }}
         bind "e" {{ EditScrollback; SwitchToMode "Normal"; }}
         bind "s" {{ SwitchToMode "EnterSearch"; SearchInput 0; }}
+        bind "a" {{
+            LaunchOrFocusPlugin "zellij:pane-search" {{
+                floating true
+                move_to_focused_tab true
+            }};
+            SwitchToMode "Normal"
+        }}
         bind "Ctrl c" {{ ScrollToBottom; SwitchToMode "Normal"; }}
         bind "j" "Down" {{ ScrollDown; }}
         bind "k" "Up" {{ ScrollUp; }}
@@ -938,6 +966,13 @@ keybinds clear-defaults=true {{
     scroll {{
         bind "e" {{ EditScrollback; SwitchToMode "Normal"; }}
         bind "s" {{ SwitchToMode "EnterSearch"; SearchInput 0; }}
+        bind "a" {{
+            LaunchOrFocusPlugin "zellij:pane-search" {{
+                floating true
+                move_to_focused_tab true
+            }};
+            SwitchToMode "Normal"
+        }}
         bind "Ctrl c" {{ ScrollToBottom; SwitchToMode "Normal"; }}
         bind "j" "Down" {{ ScrollDown; }}
         bind "k" "Up" {{ ScrollUp; }}
@@ -1126,6 +1161,13 @@ keybinds clear-defaults=true {{
         bind "{primary_modifier} s" {{ SwitchToMode "Normal"; }}
         bind "e" {{ EditScrollback; SwitchToMode "Normal"; }}
         bind "s" {{ SwitchToMode "EnterSearch"; SearchInput 0; }}
+        bind "a" {{
+            LaunchOrFocusPlugin "zellij:pane-search" {{
+                floating true
+                move_to_focused_tab true
+            }};
+            SwitchToMode "Normal"
+        }}
         bind "Ctrl c" {{ ScrollToBottom; SwitchToMode "Normal"; }}
         bind "j" "Down" {{ ScrollDown; }}
         bind "k" "Up" {{ ScrollUp; }}
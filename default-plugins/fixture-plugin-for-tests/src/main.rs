@@ -988,6 +988,27 @@ impl ZellijPlugin for State {
         should_render
     }
 
+    fn save_state(&self) -> Vec<u8> {
+        // only instances configured to save their state do, so that reloading the plugin in other
+        // tests renders the same as loading it
+        if self.configuration.get("save_state").map(|v| v == "true") != Some(true) {
+            return vec![];
+        }
+        let inputs_received = self
+            .received_events
+            .iter()
+            .filter(|e| matches!(e, Event::InputReceived))
+            .count();
+        inputs_received.to_string().into_bytes()
+    }
+
+    fn restore_state(&mut self, state: Vec<u8>) {
+        self.explicit_string_to_render = Some(format!(
+            "Restored inputs received: {}",
+            String::from_utf8_lossy(&state)
+        ));
+    }
+
     fn render(&mut self, rows: usize, cols: usize) {
         if let Some(payload) = self.received_payload.as_ref() {
             println!("Payload from worker: {:?}", payload);
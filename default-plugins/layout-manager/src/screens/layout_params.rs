@@ -0,0 +1,191 @@
+use super::{KeyResponse, LayoutListScreen, Screen};
+use crate::text_input::{InputAction, TextInput};
+use crate::ui::truncate_with_ellipsis_start;
+use std::collections::BTreeMap;
+use zellij_tile::prelude::*;
+
+/// What to do with the layout once all its params have values
+#[derive(Clone)]
+pub enum LayoutParamsTarget {
+    NewTabs {
+        rename_tab_to: Option<String>,
+    },
+    Override {
+        retain_terminal_panes: bool,
+        retain_plugin_panes: bool,
+        apply_only_to_active_tab: bool,
+    },
+}
+
+/// Prompts for the params declared in a layout one at a time, starting from their defaults
+#[derive(Clone)]
+pub struct LayoutParamsScreen {
+    layout_info: LayoutInfo,
+    params: Vec<LayoutParam>,
+    layout_vars: BTreeMap<String, String>,
+    current_param_index: usize,
+    value_input: TextInput,
+    target: LayoutParamsTarget,
+    selected_layout_index: usize,
+}
+
+impl LayoutParamsScreen {
+    pub fn new(
+        layout_info: LayoutInfo,
+        params: Vec<LayoutParam>,
+        target: LayoutParamsTarget,
+        selected_layout_index: usize,
+    ) -> Self {
+        let value_input = TextInput::new(
+            params
+                .first()
+                .map(|p| p.default.clone())
+                .unwrap_or_default(),
+        );
+        Self {
+            layout_info,
+            params,
+            layout_vars: BTreeMap::new(),
+            current_param_index: 0,
+            value_input,
+            target,
+            selected_layout_index,
+        }
+    }
+
+    pub fn handle_key(&mut self, key: KeyWithModifier) -> KeyResponse {
+        let action = self.value_input.handle_key(key);
+
+        match action {
+            InputAction::Continue => KeyResponse::render(),
+            InputAction::Submit => self.submit_current_value(),
+            InputAction::Cancel => KeyResponse::new_screen(Screen::LayoutList(
+                LayoutListScreen::with_selected_index(self.selected_layout_index),
+            )),
+            InputAction::Complete => KeyResponse::none(),
+            InputAction::NoAction => KeyResponse::none(),
+        }
+    }
+
+    fn submit_current_value(&mut self) -> KeyResponse {
+        if let Some(param) = self.params.get(self.current_param_index) {
+            self.layout_vars
+                .insert(param.name.clone(), self.value_input.drain_text());
+        }
+        self.current_param_index += 1;
+        match self.params.get(self.current_param_index) {
+            Some(next_param) => {
+                self.value_input = TextInput::new(next_param.default.clone());
+                KeyResponse::render()
+            },
+            None => {
+                self.open_layout();
+                KeyResponse::none()
+            },
+        }
+    }
+
+    fn open_layout(&self) {
+        match &self.target {
+            LayoutParamsTarget::NewTabs { rename_tab_to } => {
+                let tab_ids =
+                    new_tabs_with_layout_info_and_vars(&self.layout_info, self.layout_vars.clone());
+                if let (Some(tab_name), Some(&tab_id)) = (rename_tab_to, tab_ids.first()) {
+                    rename_tab_with_id(tab_id as u64, tab_name);
+                }
+            },
+            LayoutParamsTarget::Override {
+                retain_terminal_panes,
+                retain_plugin_panes,
+                apply_only_to_active_tab,
+            } => {
+                override_layout_with_vars(
+                    &self.layout_info,
+                    *retain_terminal_panes,
+                    *retain_plugin_panes,
+                    *apply_only_to_active_tab,
+                    Default::default(),
+                    self.layout_vars.clone(),
+                );
+            },
+        }
+        close_self();
+    }
+
+    fn value_line_text(&self, max_width: Option<usize>) -> (String, usize) {
+        // Returns (text, cursor_position_in_line)
+        let prompt = format!(
+            "{}: ",
+            self.params
+                .get(self.current_param_index)
+                .map(|p| p.name.as_str())
+                .unwrap_or_default()
+        );
+        let prompt_len = prompt.chars().count();
+
+        let input_text = self.value_input.get_text();
+        let cursor_pos = self.value_input.get_cursor_position();
+
+        let mut text = format!("{}{}", prompt, input_text);
+        let mut cursor_position_in_line = prompt_len + cursor_pos;
+
+        if let Some(max_width) = max_width {
+            if text.chars().count() > max_width {
+                let truncated_value =
+                    truncate_with_ellipsis_start(input_text, max_width.saturating_sub(prompt_len));
+                text = format!("{}{}", prompt, truncated_value);
+                let truncated_len = truncated_value.chars().count();
+                cursor_position_in_line = prompt_len + cursor_pos.min(truncated_len);
+            }
+        }
+
+        (text, cursor_position_in_line)
+    }
+
+    fn title_text(&self) -> String {
+        format!(
+            "Layout Parameters ({}/{})",
+            self.current_param_index + 1,
+            self.params.len()
+        )
+    }
+
+    fn help_text(&self) -> (&str, &[&str]) {
+        ("<Enter> - Next, <Esc> - Cancel", &["<Enter>", "<Esc>"])
+    }
+
+    fn render_help_text(&self, x: usize, y: usize) {
+        let (text, items_to_color) = self.help_text();
+        let mut text_obj = Text::new(text);
+        for item in items_to_color {
+            text_obj = text_obj.color_substring(3, item);
+        }
+        print_text_with_coordinates(text_obj, x, y, None, None);
+    }
+
+    pub fn render(&self, rows: usize, cols: usize) {
+        let desired_ui_width = std::cmp::max(
+            self.help_text().0.chars().count(),
+            self.value_line_text(None).0.chars().count(),
+        );
+
+        // Leave at least 4 columns margin (2 on each side) to prevent text from reaching screen edge
+        let max_allowed_width = cols.saturating_sub(4);
+        let actual_ui_width = std::cmp::min(desired_ui_width, max_allowed_width);
+
+        // title(1) + spacing(1) + value_line(1) + spacing(1) + help(1) = 5
+        let actual_ui_height = std::cmp::min(5, rows);
+
+        let base_y = rows.saturating_sub(actual_ui_height) / 2;
+        let base_x = cols.saturating_sub(actual_ui_width) / 2;
+
+        let value_line_y = base_y + 2;
+        let (value_line, cursor_position_in_line) = self.value_line_text(Some(actual_ui_width));
+        show_cursor(Some((base_x + cursor_position_in_line, value_line_y)));
+
+        let title = Text::new(self.title_text()).color_all(2);
+        print_text_with_coordinates(title, base_x, base_y, Some(actual_ui_width), None);
+        print_text_with_coordinates(Text::new(&value_line), base_x, value_line_y, None, None);
+        self.render_help_text(base_x, base_y + 4);
+    }
+}
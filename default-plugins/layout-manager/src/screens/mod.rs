@@ -1,5 +1,6 @@
 mod import_layout;
 mod layout_list;
+mod layout_params;
 mod new_layout_from_session;
 mod rename_layout;
 
@@ -7,6 +8,7 @@ use zellij_tile::prelude::LayoutMetadata;
 
 pub use import_layout::ImportLayoutScreen;
 pub use layout_list::LayoutListScreen;
+pub use layout_params::{LayoutParamsScreen, LayoutParamsTarget};
 pub use new_layout_from_session::NewLayoutFromCurrentSessionScreen;
 pub use rename_layout::RenameLayoutScreen;
 
@@ -19,6 +21,7 @@ pub enum Screen {
     NewLayoutFromSession(NewLayoutFromCurrentSessionScreen),
     ImportLayout(ImportLayoutScreen),
     RenameLayout(RenameLayoutScreen),
+    LayoutParams(LayoutParamsScreen),
     Error(ErrorScreen),
     ErrorDetail(ErrorDetailScreen),
 }
@@ -48,8 +48,10 @@ impl ZellijPlugin for App {
         if self.closing {
             return false;
         }
-        intercept_key_presses(); // we do this here so that all clients (even those connected after
-                                 // load) will have their keys intercepted
+        if !self.is_broadcasting() {
+            intercept_key_presses(); // we do this here so that all clients (even those connected after
+                                     // load) will have their keys intercepted
+        }
         match event {
             Event::ModeUpdate(mode_info) => self.handle_mode_update(mode_info),
             Event::PaneUpdate(pane_manifest) => self.handle_pane_update(pane_manifest),
@@ -65,6 +67,13 @@ impl ZellijPlugin for App {
 
         if self.grouped_panes_count == 0 {
             self.render_no_panes_message(rows, cols);
+        } else if self.is_broadcasting() {
+            let ui_width = self.calculate_ui_width();
+            self.update_baseline_ui_width(ui_width);
+            let base_x = cols.saturating_sub(self.baseline_ui_width) / 2;
+            let base_y = rows.saturating_sub(3) / 2;
+            print_text_with_coordinates(self.broadcasting_text().1, base_x, base_y, None, None);
+            self.render_controls(base_x, base_y + 2);
         } else {
             let ui_width = self.calculate_ui_width();
             self.update_baseline_ui_width(ui_width);
@@ -153,6 +162,21 @@ impl App {
         (Box::leak(count_text.into_boxed_str()), component)
     }
 
+    fn broadcasting_text(&self) -> (&'static str, Text) {
+        let text = if self.grouped_panes_count == 1 {
+            format!("TYPING INTO {} SELECTED PANE", self.grouped_panes_count)
+        } else {
+            format!("TYPING INTO {} SELECTED PANES", self.grouped_panes_count)
+        };
+
+        let component = Text::new(&text).color_all(2);
+        (Box::leak(text.into_boxed_str()), component)
+    }
+
+    fn is_broadcasting(&self) -> bool {
+        self.mode_info.broadcasting_to_pane_group.unwrap_or(false)
+    }
+
     fn shortcuts_line1_text() -> (&'static str, Text) {
         let text = "<b> - break out, <s> - stack, <c> - close";
         let component = Text::new(text)
@@ -180,7 +204,17 @@ impl App {
 
     fn handle_mode_update(&mut self, mode_info: ModeInfo) -> bool {
         if self.mode_info != mode_info {
+            let was_broadcasting = self.is_broadcasting();
             self.mode_info = mode_info;
+            if was_broadcasting != self.is_broadcasting() {
+                // while broadcasting, keys go to the grouped panes rather than to us
+                if self.is_broadcasting() {
+                    clear_key_presses_intercepts();
+                } else {
+                    intercept_key_presses();
+                }
+                self.baseline_ui_width = 0;
+            }
             let ui_width = self.calculate_ui_width();
             self.update_baseline_ui_width(ui_width);
             true
@@ -517,12 +551,14 @@ impl App {
 
 fn render_group_controls(mode_info: &ModeInfo, base_x: usize, base_y: usize) {
     let keymap = mode_info.get_mode_keybinds();
-    let (common_modifiers, pane_group_key, group_mark_key) = extract_key_bindings(&keymap);
+    let (common_modifiers, pane_group_key, group_mark_key, group_broadcast_key) =
+        extract_key_bindings(&keymap);
 
     let pane_group_bound = pane_group_key != "UNBOUND";
     let group_mark_bound = group_mark_key != "UNBOUND";
+    let group_broadcast_bound = group_broadcast_key != "UNBOUND";
 
-    if !pane_group_bound && !group_mark_bound {
+    if !pane_group_bound && !group_mark_bound && !group_broadcast_bound {
         return;
     }
 
@@ -535,16 +571,22 @@ fn render_group_controls(mode_info: &ModeInfo, base_x: usize, base_y: usize) {
     }
 
     if group_mark_bound {
-        render_follow_focus_ribbon(&group_mark_key, next_x, base_y, mode_info);
+        next_x = render_follow_focus_ribbon(&group_mark_key, next_x, base_y, mode_info);
+    }
+
+    if group_broadcast_bound {
+        render_broadcast_ribbon(&group_broadcast_key, next_x, base_y, mode_info);
     }
 }
 
 fn group_controls_length(mode_info: &ModeInfo) -> usize {
     let keymap = mode_info.get_mode_keybinds();
-    let (common_modifiers, pane_group_key, group_mark_key) = extract_key_bindings(&keymap);
+    let (common_modifiers, pane_group_key, group_mark_key, group_broadcast_key) =
+        extract_key_bindings(&keymap);
 
     let pane_group_bound = pane_group_key != "UNBOUND";
     let group_mark_bound = group_mark_key != "UNBOUND";
+    let group_broadcast_bound = group_broadcast_key != "UNBOUND";
 
     let mut length = 0;
 
@@ -570,26 +612,43 @@ fn group_controls_length(mode_info: &ModeInfo) -> usize {
         length += follow_text.chars().count() + 4;
     }
 
+    if group_broadcast_bound {
+        let broadcast_text = format!("<{}> Type Into All", group_broadcast_key);
+        length += broadcast_text.chars().count() + 4;
+    }
+
     length
 }
 
 fn extract_key_bindings(
     keymap: &[(KeyWithModifier, Vec<Action>)],
-) -> (Vec<KeyModifier>, String, String) {
+) -> (Vec<KeyModifier>, String, String, String) {
     let pane_group_keys = get_key_for_action(keymap, &[Action::TogglePaneInGroup]);
     let group_mark_keys = get_key_for_action(keymap, &[Action::ToggleGroupMarking]);
+    let group_broadcast_keys = get_key_for_action(keymap, &[Action::ToggleGroupBroadcast]);
 
-    let key_refs: Vec<&KeyWithModifier> = [pane_group_keys.first(), group_mark_keys.first()]
-        .into_iter()
-        .flatten()
-        .collect();
+    let key_refs: Vec<&KeyWithModifier> = [
+        pane_group_keys.first(),
+        group_mark_keys.first(),
+        group_broadcast_keys.first(),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
 
     let common_modifiers = get_common_modifiers(key_refs);
 
     let pane_group_key = format_key_without_modifiers(&pane_group_keys, &common_modifiers);
     let group_mark_key = format_key_without_modifiers(&group_mark_keys, &common_modifiers);
-
-    (common_modifiers, pane_group_key, group_mark_key)
+    let group_broadcast_key =
+        format_key_without_modifiers(&group_broadcast_keys, &common_modifiers);
+
+    (
+        common_modifiers,
+        pane_group_key,
+        group_mark_key,
+        group_broadcast_key,
+    )
 }
 
 fn format_key_without_modifiers(
@@ -666,7 +725,7 @@ fn render_follow_focus_ribbon(
     x_position: usize,
     base_y: usize,
     mode_info: &ModeInfo,
-) {
+) -> usize {
     let follow_text = format!("<{}> Follow Focus", group_mark_key);
     let key_highlight = format!("{}", group_mark_key);
 
@@ -677,6 +736,26 @@ fn render_follow_focus_ribbon(
     }
 
     print_ribbon_with_coordinates(ribbon, x_position, base_y, None, None);
+
+    x_position + follow_text.len() + 4
+}
+
+fn render_broadcast_ribbon(
+    group_broadcast_key: &str,
+    x_position: usize,
+    base_y: usize,
+    mode_info: &ModeInfo,
+) {
+    let broadcast_text = format!("<{}> Type Into All", group_broadcast_key);
+    let key_highlight = format!("{}", group_broadcast_key);
+
+    let mut ribbon = Text::new(&broadcast_text).color_substring(0, &key_highlight);
+
+    if mode_info.broadcasting_to_pane_group.unwrap_or(false) {
+        ribbon = ribbon.selected();
+    }
+
+    print_ribbon_with_coordinates(ribbon, x_position, base_y, None, None);
 }
 
 fn render_toggle_group_ribbon(pane_group_key: &str, base_x: usize, base_y: usize) -> usize {
@@ -0,0 +1,267 @@
+mod results;
+
+use std::collections::BTreeMap;
+use zellij_tile::prelude::*;
+
+use results::{result_rows, snippet, ResultRow};
+
+const PROMPT: &str = " SEARCH ALL PANES: ";
+// searching every pane is expensive, so we wait for a pause in typing before doing it
+const SEARCH_DELAY_SECS: f64 = 0.3;
+
+#[derive(Default)]
+struct State {
+    query: String,
+    case_sensitive: bool,
+    results: Vec<PaneSearchResult>,
+    rows: Vec<ResultRow>,
+    selected_row: Option<usize>,
+    error: Option<String>,
+    results_are_stale: bool,
+    pending_search_timers: usize,
+}
+
+register_plugin!(State);
+
+impl ZellijPlugin for State {
+    fn load(&mut self, _configuration: BTreeMap<String, String>) {
+        subscribe(&[EventType::Key, EventType::Timer]);
+        let own_plugin_id = get_plugin_ids().plugin_id;
+        rename_plugin_pane(own_plugin_id, "Search All Panes");
+    }
+    fn update(&mut self, event: Event) -> bool {
+        match event {
+            Event::Key(key) => self.handle_key(key),
+            Event::Timer(_) => {
+                self.pending_search_timers = self.pending_search_timers.saturating_sub(1);
+                if self.pending_search_timers == 0 && self.results_are_stale {
+                    self.search();
+                    true
+                } else {
+                    false
+                }
+            },
+            _ => false,
+        }
+    }
+    fn render(&mut self, rows: usize, cols: usize) {
+        self.render_prompt(cols);
+        self.render_summary();
+        let list_y = 3;
+        let max_list_rows = rows.saturating_sub(list_y + 2); // 2 for the help and its padding
+        self.render_results(list_y, max_list_rows, cols);
+        self.render_help(rows.saturating_sub(1), cols);
+    }
+}
+
+impl State {
+    fn handle_key(&mut self, key: KeyWithModifier) -> bool {
+        match key.bare_key {
+            BareKey::Char(character) if key.has_no_modifiers() => {
+                self.query.push(character);
+                self.search_after_delay();
+            },
+            BareKey::Backspace if key.has_no_modifiers() => {
+                self.query.pop();
+                self.search_after_delay();
+            },
+            BareKey::Tab if key.has_no_modifiers() => {
+                self.case_sensitive = !self.case_sensitive;
+                self.search_after_delay();
+            },
+            BareKey::Down if key.has_no_modifiers() => self.move_selection_down(),
+            BareKey::Up if key.has_no_modifiers() => self.move_selection_up(),
+            BareKey::Enter if key.has_no_modifiers() && self.results_are_stale => {
+                // don't jump to a match of a query we're no longer searching for
+                self.search();
+            },
+            BareKey::Enter if key.has_no_modifiers() => {
+                self.reveal_selected_match();
+                return false;
+            },
+            BareKey::Esc if key.has_no_modifiers() => {
+                close_self();
+                return false;
+            },
+            BareKey::Char('c') if key.has_modifiers(&[KeyModifier::Ctrl]) => {
+                close_self();
+                return false;
+            },
+            _ => return false,
+        }
+        true
+    }
+    fn search_after_delay(&mut self) {
+        self.results_are_stale = true;
+        self.pending_search_timers += 1;
+        set_timeout(SEARCH_DELAY_SECS);
+    }
+    fn search(&mut self) {
+        self.results_are_stale = false;
+        self.error = None;
+        self.results = if self.query.is_empty() {
+            vec![]
+        } else {
+            match search_all_panes(&self.query, self.case_sensitive) {
+                Ok(results) => results,
+                Err(e) => {
+                    self.error = Some(e);
+                    vec![]
+                },
+            }
+        };
+        self.rows = result_rows(&self.results);
+        self.selected_row = self.rows.iter().position(|r| r.is_match());
+    }
+    fn move_selection_down(&mut self) {
+        if let Some(selected_row) = self.selected_row {
+            let next = self
+                .rows
+                .iter()
+                .enumerate()
+                .skip(selected_row + 1)
+                .find(|(_, r)| r.is_match())
+                .or_else(|| self.rows.iter().enumerate().find(|(_, r)| r.is_match()));
+            self.selected_row = next.map(|(i, _)| i);
+        }
+    }
+    fn move_selection_up(&mut self) {
+        if let Some(selected_row) = self.selected_row {
+            let previous = self
+                .rows
+                .iter()
+                .enumerate()
+                .take(selected_row)
+                .rev()
+                .find(|(_, r)| r.is_match())
+                .or_else(|| {
+                    self.rows
+                        .iter()
+                        .enumerate()
+                        .rev()
+                        .find(|(_, r)| r.is_match())
+                });
+            self.selected_row = previous.map(|(i, _)| i);
+        }
+    }
+    fn reveal_selected_match(&self) {
+        let selected = self.selected_row.and_then(|i| self.rows.get(i));
+        if let Some(ResultRow::Match {
+            result_index,
+            match_index,
+        }) = selected
+        {
+            let result = &self.results[*result_index];
+            let search_match = &result.matches[*match_index];
+            reveal_search_match(
+                result.pane_id,
+                &self.query,
+                self.case_sensitive,
+                search_match.line_index,
+            );
+            // so that the search keys keep cycling through the matches in that pane
+            switch_to_input_mode(&InputMode::Search);
+            close_self();
+        }
+    }
+    fn render_prompt(&self, cols: usize) {
+        let prompt_len = PROMPT.chars().count();
+        let max_query_len = cols.saturating_sub(prompt_len + 1); // 1 for the cursor
+        let query_len = self.query.chars().count();
+        let query: String = if query_len > max_query_len {
+            self.query.chars().skip(query_len - max_query_len).collect()
+        } else {
+            self.query.clone()
+        };
+        let text = Text::new(format!("{}{}_", PROMPT, query)).color_range(3, prompt_len..);
+        print_text_with_coordinates(text, 0, 0, None, None);
+    }
+    fn render_summary(&self) {
+        let case = if self.case_sensitive {
+            "case sensitive"
+        } else {
+            "case insensitive"
+        };
+        let text = if let Some(error) = &self.error {
+            Text::new(format!(" {}", error)).error_color_all()
+        } else if self.query.is_empty() {
+            Text::new(format!(
+                " Type to search the scrollback of every terminal pane ({})",
+                case
+            ))
+        } else {
+            let match_count: usize = self.results.iter().map(|r| r.matches.len()).sum();
+            let summary = format!(" {} matches in {} panes", match_count, self.results.len());
+            let summary_len = summary.chars().count();
+            Text::new(format!("{} ({})", summary, case)).dim_range(summary_len..)
+        };
+        print_text_with_coordinates(text, 0, 1, None, None);
+    }
+    fn render_results(&self, y: usize, max_rows: usize, cols: usize) {
+        if self.rows.is_empty() || max_rows == 0 {
+            return;
+        }
+        let anchor = self.selected_row.unwrap_or(0);
+        let start = anchor
+            .saturating_sub(max_rows / 2)
+            .min(self.rows.len().saturating_sub(max_rows));
+        let items = self
+            .rows
+            .iter()
+            .enumerate()
+            .skip(start)
+            .take(max_rows)
+            .map(|(i, row)| self.render_row(row, Some(i) == self.selected_row, cols))
+            .collect();
+        print_nested_list_with_coordinates(items, 0, y, Some(cols), None);
+    }
+    fn render_row(&self, row: &ResultRow, is_selected: bool, cols: usize) -> NestedListItem {
+        match row {
+            ResultRow::Tab { position, name } => {
+                NestedListItem::new(format!("Tab #{} - {}", position + 1, name)).color_range(2, ..)
+            },
+            ResultRow::Pane { title, match_count } => {
+                let title_len = title.chars().count();
+                NestedListItem::new(format!("{} ({})", title, match_count))
+                    .indent(1)
+                    .color_range(0, ..title_len)
+            },
+            ResultRow::Match {
+                result_index,
+                match_index,
+            } => {
+                let search_match = &self.results[*result_index].matches[*match_index];
+                let line_number = format!("{}: ", search_match.line_index + 1);
+                let line_number_len = line_number.chars().count();
+                let max_width = cols.saturating_sub(line_number_len + 7); // 7 for the indentation and bullet
+                let (line, column) = snippet(&search_match.line, search_match.column, max_width);
+                let match_start = line_number_len + column;
+                let match_end = match_start + self.query.chars().count();
+                let item = NestedListItem::new(format!("{}{}", line_number, line))
+                    .indent(2)
+                    .color_range(1, ..line_number_len)
+                    .color_range(3, match_start..match_end);
+                if is_selected {
+                    item.selected()
+                } else {
+                    item
+                }
+            },
+        }
+    }
+    fn render_help(&self, y: usize, cols: usize) {
+        let full_text = "Help: <↓↑> - Navigate, <ENTER> - Jump to match, <TAB> - Toggle case sensitivity, <ESC> - Close";
+        let short_text = "<↓↑/ENTER/TAB/ESC> - Navigate/Jump/Case/Close";
+        if cols >= full_text.chars().count() {
+            let text = Text::new(full_text)
+                .color_range(3, 6..=9)
+                .color_range(3, 23..=29)
+                .color_range(3, 48..=52)
+                .color_range(3, 81..=85);
+            print_text_with_coordinates(text, 0, y, Some(cols), None);
+        } else {
+            let text = Text::new(short_text).color_range(3, ..=17);
+            print_text_with_coordinates(text, 0, y, Some(cols), None);
+        }
+    }
+}
@@ -0,0 +1,152 @@
+use zellij_tile::prelude::*;
+
+/// A row of the picker, matches are listed under their pane which is listed under its tab
+#[derive(Debug, Clone, PartialEq)]
+pub enum ResultRow {
+    Tab {
+        position: usize,
+        name: String,
+    },
+    Pane {
+        title: String,
+        match_count: usize,
+    },
+    Match {
+        result_index: usize,
+        match_index: usize,
+    },
+}
+
+impl ResultRow {
+    pub fn is_match(&self) -> bool {
+        matches!(self, ResultRow::Match { .. })
+    }
+}
+
+/// The results arrive ordered by tab, so a tab header is added whenever the tab changes
+pub fn result_rows(results: &[PaneSearchResult]) -> Vec<ResultRow> {
+    let mut rows = vec![];
+    let mut current_tab = None;
+    for (result_index, result) in results.iter().enumerate() {
+        if current_tab != Some(result.tab_position) {
+            current_tab = Some(result.tab_position);
+            rows.push(ResultRow::Tab {
+                position: result.tab_position,
+                name: result.tab_name.clone(),
+            });
+        }
+        rows.push(ResultRow::Pane {
+            title: result.pane_title.clone(),
+            match_count: result.matches.len(),
+        });
+        for match_index in 0..result.matches.len() {
+            rows.push(ResultRow::Match {
+                result_index,
+                match_index,
+            });
+        }
+    }
+    rows
+}
+
+/// Cuts a line down to `max_width` characters keeping the match (which starts at `column`) in
+/// view, returns the shortened line and the column of the match in it
+pub fn snippet(line: &str, column: usize, max_width: usize) -> (String, usize) {
+    let chars: Vec<char> = line.chars().collect();
+    if chars.len() <= max_width || max_width < 2 {
+        return (chars.iter().take(max_width).collect(), column);
+    }
+    let start = column
+        .saturating_sub(max_width / 3)
+        .min(chars.len() - max_width);
+    let end = start + max_width;
+    let mut shown = chars[start..end].to_vec();
+    if start > 0 {
+        shown[0] = '…';
+    }
+    if end < chars.len() {
+        shown[max_width - 1] = '…';
+    }
+    (shown.into_iter().collect(), column - start)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pane_result(tab_position: usize, pane_id: u32, match_count: usize) -> PaneSearchResult {
+        PaneSearchResult {
+            tab_position,
+            tab_name: format!("tab {}", tab_position),
+            pane_id: PaneId::Terminal(pane_id),
+            pane_title: format!("pane {}", pane_id),
+            matches: (0..match_count)
+                .map(|line_index| SearchMatch {
+                    line_index,
+                    column: 0,
+                    line: "error".to_owned(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn results_are_grouped_by_tab_and_pane() {
+        let results = vec![
+            pane_result(0, 1, 2),
+            pane_result(0, 2, 1),
+            pane_result(2, 3, 1),
+        ];
+        let rows = result_rows(&results);
+        assert_eq!(
+            rows,
+            vec![
+                ResultRow::Tab {
+                    position: 0,
+                    name: "tab 0".to_owned()
+                },
+                ResultRow::Pane {
+                    title: "pane 1".to_owned(),
+                    match_count: 2
+                },
+                ResultRow::Match {
+                    result_index: 0,
+                    match_index: 0
+                },
+                ResultRow::Match {
+                    result_index: 0,
+                    match_index: 1
+                },
+                ResultRow::Pane {
+                    title: "pane 2".to_owned(),
+                    match_count: 1
+                },
+                ResultRow::Match {
+                    result_index: 1,
+                    match_index: 0
+                },
+                ResultRow::Tab {
+                    position: 2,
+                    name: "tab 2".to_owned()
+                },
+                ResultRow::Pane {
+                    title: "pane 3".to_owned(),
+                    match_count: 1
+                },
+                ResultRow::Match {
+                    result_index: 2,
+                    match_index: 0
+                },
+            ]
+        );
+        assert_eq!(snippet("short line", 6, 20), ("short line".to_owned(), 6));
+        assert_eq!(
+            snippet("0123456789abcdefghij", 12, 9),
+            ("…abcdefg…".to_owned(), 3)
+        );
+        assert_eq!(
+            snippet("0123456789abcdefghij", 18, 9),
+            ("…cdefghij".to_owned(), 7)
+        );
+    }
+}
@@ -483,6 +483,134 @@ impl NewPluginScreen {
     }
 }
 
+#[derive(Default)]
+pub struct PermissionsScreen {
+    grants: BTreeMap<String, Vec<PermissionType>>,
+    selected_index: Option<usize>,
+}
+
+impl PermissionsScreen {
+    pub fn new() -> Self {
+        PermissionsScreen {
+            grants: list_plugin_permissions(),
+            selected_index: None,
+        }
+    }
+    pub fn render(&self, rows: usize, cols: usize) {
+        let title_text = "PLUGIN PERMISSIONS";
+        print_text_with_coordinates(
+            Text::new(title_text),
+            (cols / 2).saturating_sub(title_text.chars().count() / 2),
+            0,
+            None,
+            None,
+        );
+        if self.grants.is_empty() {
+            print_text_with_coordinates(
+                Text::new("No permissions have been granted to plugins").color_range(1, ..),
+                0,
+                2,
+                None,
+                None,
+            );
+        } else {
+            let mut list = vec![];
+            let mut selected_index_in_list = None;
+            let mut permission_index = 0;
+            for (plugin_url, permissions) in &self.grants {
+                list.push(
+                    NestedListItem::new(truncate_string_start(plugin_url, cols.saturating_sub(2)))
+                        .color_range(0, ..),
+                );
+                for permission in permissions {
+                    let mut item = NestedListItem::new(permission.display_name()).indent(1);
+                    if Some(permission_index) == self.selected_index {
+                        selected_index_in_list = Some(list.len());
+                        item = item.selected();
+                    }
+                    list.push(item);
+                    permission_index += 1;
+                }
+            }
+            let max_list_items = rows.saturating_sub(4); // 2 top padding, 2 bottom padding
+            let (_more_above, _more_below, truncated_list) =
+                truncate_list_to_screen(selected_index_in_list, list, max_list_items);
+            print_nested_list_with_coordinates(truncated_list, 0, 2, Some(cols), None);
+        }
+        let help_text = "Help: <↓↑> - Navigate, <Del> - Revoke, <ESC> - Back";
+        let help = Text::new(help_text)
+            .color_range(3, 6..=9)
+            .color_range(3, 23..=27)
+            .color_range(3, 39..=43);
+        print_text_with_coordinates(help, 0, rows, Some(cols), None);
+    }
+    fn permission_count(&self) -> usize {
+        self.grants.values().map(|p| p.len()).sum()
+    }
+    fn selected_permission(&self) -> Option<(String, PermissionType)> {
+        let selected_index = self.selected_index?;
+        self.grants
+            .iter()
+            .flat_map(|(plugin_url, permissions)| permissions.iter().map(move |p| (plugin_url, p)))
+            .nth(selected_index)
+            .map(|(plugin_url, permission)| (plugin_url.clone(), *permission))
+    }
+    fn revoke_selected(&mut self) {
+        if let Some((plugin_url, permission)) = self.selected_permission() {
+            revoke_plugin_permission(&plugin_url, permission);
+            if let Some(permissions) = self.grants.get_mut(&plugin_url) {
+                permissions.retain(|p| p != &permission);
+                if permissions.is_empty() {
+                    self.grants.remove(&plugin_url);
+                }
+            }
+            let permission_count = self.permission_count();
+            if permission_count == 0 {
+                self.selected_index = None;
+            } else {
+                self.selected_index = self
+                    .selected_index
+                    .map(|s| std::cmp::min(s, permission_count.saturating_sub(1)));
+            }
+        }
+    }
+    pub fn handle_key(&mut self, key: KeyWithModifier) -> (bool, bool) {
+        // return value is (should_render, should_close)
+        let mut should_render = false;
+        let mut should_close = false;
+        let max_index = self.permission_count().saturating_sub(1);
+        match key.bare_key {
+            BareKey::Down if key.has_no_modifiers() => {
+                if self.permission_count() > 0 {
+                    self.selected_index = match self.selected_index {
+                        Some(s) if s < max_index => Some(s + 1),
+                        _ => Some(0),
+                    };
+                    should_render = true;
+                }
+            },
+            BareKey::Up if key.has_no_modifiers() => {
+                if self.permission_count() > 0 {
+                    self.selected_index = match self.selected_index {
+                        Some(s) if s > 0 => Some(s - 1),
+                        _ => Some(max_index),
+                    };
+                    should_render = true;
+                }
+            },
+            BareKey::Delete if key.has_no_modifiers() => {
+                self.revoke_selected();
+                should_render = true;
+            },
+            BareKey::Esc if key.has_no_modifiers() => {
+                should_close = true;
+            },
+            _ => {},
+        }
+        (should_render, should_close)
+    }
+}
+
 #[derive(Default)]
 struct State {
     userspace_configuration: BTreeMap<String, String>,
@@ -494,6 +622,7 @@ struct State {
     plugin_id_to_tab_position: HashMap<u32, usize>,
     search_term: String,
     new_plugin_screen: Option<NewPluginScreen>,
+    permissions_screen: Option<PermissionsScreen>,
     colors: Styling,
 }
 
@@ -553,10 +682,13 @@ impl ZellijPlugin for State {
             Event::SessionUpdate(live_sessions, _dead_sessions) => {
                 for session in live_sessions {
                     if session.is_current_session {
-                        if session.plugins != self.plugins {
+                        if plugins_changed(&self.plugins, &session.plugins) {
                             self.plugins = session.plugins;
                             self.reset_selection();
                             self.update_search_term();
+                        } else {
+                            // only the CPU usage changed, no need to lose our selection
+                            self.plugins = session.plugins;
                         }
                         for tab in session.tabs {
                             self.tab_position_to_tab_name.insert(tab.position, tab.name);
@@ -575,8 +707,8 @@ impl ZellijPlugin for State {
                     }
                 }
             },
-            Event::Key(key) => match self.new_plugin_screen.as_mut() {
-                Some(new_plugin_screen) => {
+            Event::Key(key) => {
+                if let Some(new_plugin_screen) = self.new_plugin_screen.as_mut() {
                     let (should_render_new_plugin_screen, should_close_new_plugin_screen) =
                         new_plugin_screen.handle_key(key);
                     if should_close_new_plugin_screen {
@@ -585,8 +717,18 @@ impl ZellijPlugin for State {
                     } else {
                         should_render = should_render_new_plugin_screen;
                     }
-                },
-                None => should_render = self.handle_main_screen_key(key),
+                } else if let Some(permissions_screen) = self.permissions_screen.as_mut() {
+                    let (should_render_permissions_screen, should_close_permissions_screen) =
+                        permissions_screen.handle_key(key);
+                    if should_close_permissions_screen {
+                        self.permissions_screen = None;
+                        should_render = true;
+                    } else {
+                        should_render = should_render_permissions_screen;
+                    }
+                } else {
+                    should_render = self.handle_main_screen_key(key);
+                }
             },
             _ => (),
         };
@@ -594,11 +736,14 @@ impl ZellijPlugin for State {
     }
 
     fn render(&mut self, rows: usize, cols: usize) {
-        match &self.new_plugin_screen {
-            Some(new_plugin_screen) => {
+        match (&self.new_plugin_screen, &self.permissions_screen) {
+            (Some(new_plugin_screen), _) => {
                 new_plugin_screen.render(rows, cols);
             },
-            None => {
+            (None, Some(permissions_screen)) => {
+                permissions_screen.render(rows, cols);
+            },
+            (None, None) => {
                 self.render_search(cols);
                 let list_y = 2;
                 let max_list_items = rows.saturating_sub(4); // 2 top padding, 2 bottom padding
@@ -607,11 +752,8 @@ impl ZellijPlugin for State {
                 } else {
                     self.render_plugin_list(cols)
                 };
-                let (more_above, more_below, truncated_list) = self.truncate_list_to_screen(
-                    selected_index_in_list,
-                    plugin_list,
-                    max_list_items,
-                );
+                let (more_above, more_below, truncated_list) =
+                    truncate_list_to_screen(selected_index_in_list, plugin_list, max_list_items);
                 self.render_more_indication(
                     more_above,
                     more_below,
@@ -733,6 +875,7 @@ impl State {
         if is_expanded {
             let tab_line = self.render_tab_line(plugin_id, cols);
             items.push(tab_line);
+            items.push(self.render_cpu_time_line(plugin_info));
             if !plugin_info.configuration.is_empty() {
                 let config_line = NestedListItem::new(format!("Configuration:"))
                     .color_range(2, ..=13)
@@ -808,6 +951,7 @@ impl State {
         if is_expanded {
             let tab_line = self.render_tab_line(plugin_id, cols);
             items.push(tab_line);
+            items.push(self.render_cpu_time_line(plugin_info));
             if !plugin_info.configuration.is_empty() {
                 let config_line = NestedListItem::new(format!("Configuration:"))
                     .color_range(2, ..=13)
@@ -866,12 +1010,20 @@ impl State {
             .indent(1);
         tab_line
     }
+    fn render_cpu_time_line(&self, plugin_info: &PluginInfo) -> NestedListItem {
+        NestedListItem::new(format!(
+            "CPU time: {:.2}s",
+            plugin_info.cpu_time_ms as f64 / 1000.0
+        ))
+        .color_range(2, ..=8)
+        .indent(1)
+    }
     pub fn render_help(&self, y: usize, cols: usize) {
-        let full_text = "Help: <←↓↑→> - Navigate/Expand, <ENTER> - focus, <TAB> - Reload, <Del> - Close, <Ctrl a> - New, <ESC> - Exit";
+        let full_text = "Help: <←↓↑→> - Navigate/Expand, <ENTER> - focus, <TAB> - Reload, <Del> - Close, <Ctrl a> - New, <Ctrl p> - Permissions, <ESC> - Exit";
         let middle_text =
-            "Help: <←↓↑→/ENTER> - Navigate, <TAB> - Reload, <Del> - Close, <Ctrl a> - New, <ESC> - Exit";
+            "Help: <←↓↑→/ENTER> - Navigate, <TAB> - Reload, <Del> - Close, <Ctrl a> - New, <Ctrl p> - Permissions, <ESC> - Exit";
         let short_text =
-            "<←↓↑→/ENTER/TAB/Del> - Navigate/Expand/Reload/Close, <Ctrl a> - New, <ESC> - Exit";
+            "<←↓↑→/ENTER/TAB/Del> - Navigate/Expand/Reload/Close, <Ctrl a/p> - New/Permissions, <ESC> - Exit";
         if cols >= full_text.chars().count() {
             let text = Text::new(full_text)
                 .color_range(3, 5..=11)
@@ -879,7 +1031,8 @@ impl State {
                 .color_range(3, 49..=53)
                 .color_range(3, 65..=69)
                 .color_range(3, 80..=87)
-                .color_range(3, 96..=100);
+                .color_range(3, 96..=103)
+                .color_range(3, 120..=124);
             print_text_with_coordinates(text, 0, y, Some(cols), None);
         } else if cols >= middle_text.chars().count() {
             let text = Text::new(middle_text)
@@ -887,13 +1040,14 @@ impl State {
                 .color_range(3, 31..=35)
                 .color_range(3, 47..=51)
                 .color_range(3, 62..=69)
-                .color_range(3, 78..=82);
+                .color_range(3, 78..=85)
+                .color_range(3, 102..=106);
             print_text_with_coordinates(text, 0, y, Some(cols), None);
         } else {
             let text = Text::new(short_text)
                 .color_range(3, ..=21)
-                .color_range(3, 53..=60)
-                .color_range(3, 69..=73);
+                .color_range(3, 53..=62)
+                .color_range(3, 83..=87);
             print_text_with_coordinates(text, 0, y, Some(cols), None);
         }
     }
@@ -1023,6 +1177,10 @@ impl State {
                 self.new_plugin_screen = Some(NewPluginScreen::new(self.colors));
                 should_render = true;
             },
+            BareKey::Char('p') if key.has_modifiers(&[KeyModifier::Ctrl]) => {
+                self.permissions_screen = Some(PermissionsScreen::new());
+                should_render = true;
+            },
             BareKey::Delete if key.has_no_modifiers() => {
                 self.close_selected();
             },
@@ -1042,32 +1200,32 @@ impl State {
     pub fn is_searching(&self) -> bool {
         self.search_term.len() > 0
     }
-    fn truncate_list_to_screen(
-        &self,
-        selected_index_in_list: Option<usize>,
-        mut plugin_list: Vec<NestedListItem>,
-        max_list_items: usize,
-    ) -> (usize, usize, Vec<NestedListItem>) {
-        let mut more_above = 0;
-        let mut more_below = 0;
-        if plugin_list.len() > max_list_items {
-            let anchor_line = selected_index_in_list.unwrap_or(0);
-            let list_start = anchor_line.saturating_sub(max_list_items / 2);
-            let list_end = (list_start + max_list_items).saturating_sub(1);
-            let mut to_render = vec![];
-            for (i, item) in plugin_list.drain(..).enumerate() {
-                if i >= list_start && i < list_end {
-                    to_render.push(item);
-                } else if i >= list_end {
-                    more_below += 1;
-                } else if i < list_start {
-                    more_above += 1;
-                }
+}
+
+fn truncate_list_to_screen(
+    selected_index_in_list: Option<usize>,
+    mut plugin_list: Vec<NestedListItem>,
+    max_list_items: usize,
+) -> (usize, usize, Vec<NestedListItem>) {
+    let mut more_above = 0;
+    let mut more_below = 0;
+    if plugin_list.len() > max_list_items {
+        let anchor_line = selected_index_in_list.unwrap_or(0);
+        let list_start = anchor_line.saturating_sub(max_list_items / 2);
+        let list_end = (list_start + max_list_items).saturating_sub(1);
+        let mut to_render = vec![];
+        for (i, item) in plugin_list.drain(..).enumerate() {
+            if i >= list_start && i < list_end {
+                to_render.push(item);
+            } else if i >= list_end {
+                more_below += 1;
+            } else if i < list_start {
+                more_above += 1;
             }
-            plugin_list = to_render;
         }
-        (more_above, more_below, plugin_list)
+        plugin_list = to_render;
     }
+    (more_above, more_below, plugin_list)
 }
 
 fn truncate_string_start(string_to_truncate: &str, max_len: usize) -> String {
@@ -1101,3 +1259,17 @@ fn truncate_search_result(
         .collect();
     (truncated_location, adjusted_indices)
 }
+
+fn plugins_changed(
+    previous_plugins: &BTreeMap<u32, PluginInfo>,
+    plugins: &BTreeMap<u32, PluginInfo>,
+) -> bool {
+    previous_plugins.len() != plugins.len()
+        || previous_plugins.iter().zip(plugins.iter()).any(
+            |((previous_id, previous_info), (id, info))| {
+                previous_id != id
+                    || previous_info.location != info.location
+                    || previous_info.configuration != info.configuration
+            },
+        )
+}
@@ -1,6 +1,7 @@
 mod new_session_info;
 mod resurrectable_sessions;
 mod session_list;
+mod session_preview;
 mod single_screen;
 mod ui;
 use std::collections::BTreeMap;
@@ -21,6 +22,7 @@ use ui::{
 
 use resurrectable_sessions::ResurrectableSessions;
 use session_list::SessionList;
+use session_preview::{list_and_preview_cols, render_preview_panel, MIN_PREVIEW_PANEL_COLS};
 
 #[derive(Clone, Debug, Copy, PartialEq)]
 enum ActiveScreen {
@@ -54,6 +56,7 @@ struct State {
     request_ids: Vec<String>,
     is_web_client: bool,
     current_session_last_saved_time: Option<u64>,
+    focused_terminal_pane_id: Option<u32>,
 }
 
 register_plugin!(State);
@@ -213,12 +216,28 @@ impl ZellijPlugin for State {
                     let bottom_lines = 7;
                     let room_for_list = height.saturating_sub(bottom_lines);
                     self.sessions.update_rows(room_for_list);
-                    let list =
-                        self.sessions
-                            .render(room_for_list, width.saturating_sub(7), self.colors); // 7 for various ui
+                    let (list_width, preview_width) = list_and_preview_cols(width);
+                    let list = self.sessions.render(
+                        room_for_list,
+                        list_width.saturating_sub(7),
+                        self.colors,
+                    ); // 7 for various ui
                     for (i, line) in list.iter().enumerate() {
                         print!("\u{1b}[{};{}H{}", y + i + 5, x, line.render());
                     }
+                    if let Some(preview_width) = preview_width {
+                        let preview = self
+                            .sessions
+                            .get_selected_session_name()
+                            .and_then(|session_name| self.sessions.preview_for(&session_name));
+                        render_preview_panel(
+                            preview,
+                            x + list_width,
+                            y + 4,
+                            room_for_list,
+                            preview_width,
+                        );
+                    }
                 }
             },
             ActiveScreen::ResurrectSession => {
@@ -243,7 +262,12 @@ impl ZellijPlugin for State {
                             // Horizontal centering: cap content block and center
                             // within the full pane width
                             let content_width = std::cmp::min(width, 90);
-                            let x_centered = x + (width.saturating_sub(content_width)) / 2;
+                            let preview_width =
+                                Some(width.saturating_sub(content_width + 2).min(content_width))
+                                    .filter(|p| *p >= MIN_PREVIEW_PANEL_COLS);
+                            let block_width =
+                                content_width + preview_width.map(|p| p + 2).unwrap_or(0);
+                            let x_centered = x + (width.saturating_sub(block_width)) / 2;
 
                             let enter_action = if !self.single_screen_state.search_term.is_empty() {
                                 if let Some(result) = self.single_screen_state.get_selected_result()
@@ -283,6 +307,24 @@ impl ZellijPlugin for State {
                                 x_centered,
                                 y_offset + 2,
                             );
+                            if let Some(preview_width) = preview_width {
+                                let preview = self
+                                    .single_screen_state
+                                    .get_selected_result()
+                                    .map(|result| result.session_name().to_owned())
+                                    .and_then(|session_name| {
+                                        self.sessions.preview_for(&session_name).or_else(|| {
+                                            self.resurrectable_sessions.preview_for(&session_name)
+                                        })
+                                    });
+                                render_preview_panel(
+                                    preview,
+                                    x_centered + content_width + 2,
+                                    y_offset + 2,
+                                    max_table_rows,
+                                    preview_width,
+                                );
+                            }
                         }
                     },
                     SingleScreenMode::SelectingLayout => {
@@ -599,6 +641,18 @@ impl State {
                 BareKey::Char('x') if key.has_modifiers(&[KeyModifier::Ctrl]) => {
                     disconnect_other_clients()
                 },
+                BareKey::Char('p') if key.has_modifiers(&[KeyModifier::Ctrl]) => {
+                    if self.sessions.selected_is_current_session() {
+                        self.show_error("Select another session to send the pane to.");
+                    } else if let Some(selected_session_name) =
+                        self.sessions.get_selected_session_name()
+                    {
+                        self.send_focused_pane_to_session(&selected_session_name);
+                    } else {
+                        self.show_error("Must select session before sending a pane to it.");
+                    }
+                    should_render = true;
+                },
                 BareKey::Char('c') if key.has_modifiers(&[KeyModifier::Ctrl]) => {
                     if !self.search_term.is_empty() {
                         self.search_term.clear();
@@ -647,6 +701,14 @@ impl State {
                 self.resurrectable_sessions.move_selection_up();
                 should_render = true;
             },
+            BareKey::Right if key.has_no_modifiers() => {
+                self.resurrectable_sessions.select_next_snapshot();
+                should_render = true;
+            },
+            BareKey::Left if key.has_no_modifiers() => {
+                self.resurrectable_sessions.select_previous_snapshot();
+                should_render = true;
+            },
             BareKey::Enter if key.has_no_modifiers() => {
                 self.handle_selection();
                 should_render = true;
@@ -824,6 +886,28 @@ impl State {
             BareKey::Char('x') if key.has_modifiers(&[KeyModifier::Ctrl]) => {
                 disconnect_other_clients();
             },
+            BareKey::Char('p') if key.has_modifiers(&[KeyModifier::Ctrl]) => {
+                match self.single_screen_state.get_selected_result() {
+                    Some(UnifiedSearchResult::ActiveSession {
+                        session_name,
+                        is_current_session: false,
+                        ..
+                    }) => {
+                        let session_name = session_name.clone();
+                        self.send_focused_pane_to_session(&session_name);
+                    },
+                    Some(UnifiedSearchResult::ActiveSession { .. }) => {
+                        self.show_error("Select another session to send the pane to.");
+                    },
+                    Some(UnifiedSearchResult::ResurrectableSession { .. }) => {
+                        self.show_error("Panes can only be sent to running sessions.");
+                    },
+                    None => {
+                        self.show_error("Must select session before sending a pane to it.");
+                    },
+                }
+                should_render = true;
+            },
             BareKey::Char('a') if key.has_modifiers(&[KeyModifier::Ctrl]) => {
                 if !self.is_welcome_screen {
                     if let Err(e) = save_session() {
@@ -1010,7 +1094,17 @@ impl State {
                 if let Some(session_name_to_resurrect) =
                     self.resurrectable_sessions.get_selected_session_name()
                 {
-                    switch_session(Some(&session_name_to_resurrect));
+                    match self.resurrectable_sessions.get_selected_snapshot() {
+                        Some(snapshot) => switch_session_with_layout(
+                            Some(&session_name_to_resurrect),
+                            LayoutInfo::File(
+                                snapshot.layout_path.display().to_string(),
+                                LayoutMetadata::default(),
+                            ),
+                            None,
+                        ),
+                        None => switch_session(Some(&session_name_to_resurrect)),
+                    }
                     if self.is_welcome_screen {
                         // the welcome screen has done its job and now we need to quit this temporary
                         // session so as not to leave garbage sessions behind
@@ -1171,6 +1265,17 @@ impl State {
         }
         self.session_name = Some(new_name.to_owned());
     }
+    fn send_focused_pane_to_session(&mut self, session_name: &str) {
+        match self.focused_terminal_pane_id {
+            Some(pane_id) => {
+                send_pane_to_session(PaneId::Terminal(pane_id), session_name);
+                if !self.is_welcome_screen {
+                    hide_self();
+                }
+            },
+            None => self.show_error("No focused terminal pane to send."),
+        }
+    }
     fn update_session_infos(&mut self, session_infos: Vec<SessionInfo>) {
         let session_ui_infos: Vec<SessionUiInfo> = session_infos
             .iter()
@@ -1209,6 +1314,18 @@ impl State {
         if let Some(current_session_name) = current_session_name {
             self.session_name = Some(current_session_name);
         }
+        self.focused_terminal_pane_id = session_infos
+            .iter()
+            .find(|s| s.is_current_session)
+            .and_then(|s| {
+                let active_tab = s.tabs.iter().find(|t| t.active)?;
+                s.panes
+                    .panes
+                    .get(&active_tab.position)?
+                    .iter()
+                    .find(|p| p.is_focused && !p.is_plugin && !p.is_suppressed)
+                    .map(|p| p.id)
+            });
         self.sessions
             .set_sessions(session_ui_infos, forbidden_sessions);
     }
@@ -2,8 +2,12 @@ use fuzzy_matcher::skim::SkimMatcherV2;
 use fuzzy_matcher::FuzzyMatcher;
 use humantime::format_duration;
 
+use std::collections::HashMap;
 use std::time::Duration;
 
+use crate::session_preview::{list_and_preview_cols, render_preview_panel, SessionPreview};
+
+use zellij_tile::prelude::SessionSnapshot;
 use zellij_tile::shim::*;
 
 #[derive(Debug, Default)]
@@ -15,32 +19,52 @@ pub struct ResurrectableSessions {
     pub is_searching: bool,
     pub search_term: String,
     pub delete_all_dead_sessions_warning: bool,
+    previews: HashMap<String, Option<SessionPreview>>, // session name -> preview of its layout
+    snapshots: HashMap<String, Vec<SessionSnapshot>>,  // session name -> its named snapshots
+    selected_snapshot_index: Option<usize>,            // None resurrects the last serialized state
 }
 
 impl ResurrectableSessions {
     pub fn update(&mut self, mut list: Vec<(String, Duration)>) {
         list.sort_by(|a, b| a.1.cmp(&b.1));
+        self.previews
+            .retain(|session_name, _| list.iter().any(|s| &s.0 == session_name));
+        self.snapshots
+            .retain(|session_name, _| list.iter().any(|s| &s.0 == session_name));
         self.all_resurrectable_sessions = list;
         if self.is_searching {
             self.update_search_term();
         }
     }
-    pub fn render(&self, rows: usize, columns: usize, x: usize, y: usize) {
+    pub fn render(&mut self, rows: usize, columns: usize, x: usize, y: usize) {
         if self.delete_all_dead_sessions_warning {
             self.render_delete_all_sessions_warning(rows, columns, x, y);
             return;
         }
+        self.selected_session_snapshots();
         let search_indication =
             Text::new(format!("Search: {}_", self.search_term)).color_range(2, ..7);
         let table_rows = rows.saturating_sub(5); // search row, toggle row and some padding
-        let table_columns = columns;
+        let (table_columns, preview_columns) = list_and_preview_cols(columns);
         let table = if self.is_searching {
-            self.render_search_results(table_rows, columns)
+            self.render_search_results(table_rows, table_columns)
         } else {
-            self.render_all_entries(table_rows, columns)
+            self.render_all_entries(table_rows, table_columns)
         };
         print_text_with_coordinates(search_indication, x.saturating_sub(1), y + 2, None, None);
         print_table_with_coordinates(table, x, y + 3, Some(table_columns), Some(table_rows));
+        if let Some(preview_columns) = preview_columns {
+            let preview = self
+                .get_selected_session_name()
+                .and_then(|session_name| self.preview_for(&session_name));
+            render_preview_panel(
+                preview,
+                x + table_columns + 2,
+                y + 3,
+                table_rows,
+                preview_columns,
+            );
+        }
     }
     fn render_search_results(&self, table_rows: usize, _table_columns: usize) -> Table {
         let mut table = Table::new().add_row(vec![" ", " ", " "]); // skip the title row
@@ -186,7 +210,7 @@ impl ResurrectableSessions {
         is_selected: bool,
     ) -> Text {
         if is_selected {
-            Text::new(format!("<ENTER> - Resurrect Session")).color_range(3, 0..7)
+            self.render_resurrect_indication()
         } else if i == first_row_index_to_render && i > 0 {
             Text::new(format!("+ {} more", first_row_index_to_render)).color_range(1, ..)
         } else if i == last_row_index_to_render.saturating_sub(1)
@@ -201,7 +225,66 @@ impl ResurrectableSessions {
             Text::new(" ")
         }
     }
+    fn render_resurrect_indication(&self) -> Text {
+        let snapshots = self
+            .get_selected_session_name()
+            .and_then(|session_name| self.snapshots.get(&session_name));
+        let selected_snapshot = snapshots
+            .zip(self.selected_snapshot_index)
+            .and_then(|(snapshots, i)| snapshots.get(i));
+        match (snapshots, selected_snapshot) {
+            (_, Some(snapshot)) => {
+                Text::new(format!("<ENTER> - Resurrect Snapshot: {}", snapshot.name))
+                    .color_range(3, 0..7)
+                    .color_range(0, 30..)
+            },
+            (Some(snapshots), None) if !snapshots.is_empty() => Text::new(format!(
+                "<ENTER> - Resurrect Session, <←→> - Snapshots ({})",
+                snapshots.len()
+            ))
+            .color_range(3, 0..7)
+            .color_range(3, 29..33),
+            _ => Text::new("<ENTER> - Resurrect Session").color_range(3, 0..7),
+        }
+    }
+    /// Cycle through the snapshots of the selected session, with the last serialized state of
+    /// the session coming before the first snapshot and after the last one
+    pub fn select_next_snapshot(&mut self) {
+        let snapshot_count = self.selected_session_snapshots().len();
+        self.selected_snapshot_index = match self.selected_snapshot_index {
+            None if snapshot_count > 0 => Some(0),
+            Some(i) if i + 1 < snapshot_count => Some(i + 1),
+            _ => None,
+        };
+    }
+    pub fn select_previous_snapshot(&mut self) {
+        let snapshot_count = self.selected_session_snapshots().len();
+        self.selected_snapshot_index = match self.selected_snapshot_index {
+            None if snapshot_count > 0 => Some(snapshot_count - 1),
+            Some(i) if i > 0 => Some(i - 1),
+            _ => None,
+        };
+    }
+    pub fn get_selected_snapshot(&self) -> Option<SessionSnapshot> {
+        let session_name = self.get_selected_session_name()?;
+        self.snapshots
+            .get(&session_name)
+            .zip(self.selected_snapshot_index)
+            .and_then(|(snapshots, i)| snapshots.get(i))
+            .cloned()
+    }
+    /// The snapshots of the selected session, listed the first time they're asked for
+    fn selected_session_snapshots(&mut self) -> &[SessionSnapshot] {
+        match self.get_selected_session_name() {
+            Some(session_name) => self
+                .snapshots
+                .entry(session_name.clone())
+                .or_insert_with(|| list_session_snapshots(&session_name)),
+            None => &[],
+        }
+    }
     pub fn move_selection_down(&mut self) {
+        self.selected_snapshot_index = None;
         if self.is_searching {
             if let Some(selected_index) = self.selected_search_index.as_mut() {
                 if *selected_index == self.search_results.len().saturating_sub(1) {
@@ -225,6 +308,7 @@ impl ResurrectableSessions {
         }
     }
     pub fn move_selection_up(&mut self) {
+        self.selected_snapshot_index = None;
         if self.is_searching {
             if let Some(selected_index) = self.selected_search_index.as_mut() {
                 if *selected_index == 0 {
@@ -259,6 +343,7 @@ impl ResurrectableSessions {
         }
     }
     pub fn delete_selected_session(&mut self) {
+        self.selected_snapshot_index = None;
         if self.is_searching {
             self.selected_search_index
                 .and_then(|i| self.search_results.get(i))
@@ -311,7 +396,22 @@ impl ResurrectableSessions {
             .iter()
             .any(|s| s.0 == session_name)
     }
+    /// The preview of a session's serialized layout, read the first time it's asked for
+    pub fn preview_for(&mut self, session_name: &str) -> Option<&SessionPreview> {
+        if !self.has_session(session_name) {
+            return None;
+        }
+        self.previews
+            .entry(session_name.to_owned())
+            .or_insert_with(|| {
+                get_resurrectable_session_layout(session_name)
+                    .ok()
+                    .and_then(|raw_layout| SessionPreview::from_serialized_layout(&raw_layout))
+            })
+            .as_ref()
+    }
     fn update_search_term(&mut self) {
+        self.selected_snapshot_index = None;
         let mut matches = vec![];
         let matcher = SkimMatcherV2::default().use_cache(true);
         for (session_name, ctime) in &self.all_resurrectable_sessions {
@@ -1,6 +1,7 @@
 use fuzzy_matcher::skim::SkimMatcherV2;
 use fuzzy_matcher::FuzzyMatcher;
 
+use crate::session_preview::SessionPreview;
 use crate::ui::{
     components::{Colors, LineToRender, ListItem},
     SessionUiInfo,
@@ -324,6 +325,12 @@ impl SessionList {
     pub fn has_session(&self, session_name: &str) -> bool {
         self.session_ui_infos.iter().any(|s| s.name == session_name)
     }
+    pub fn preview_for(&self, session_name: &str) -> Option<&SessionPreview> {
+        self.session_ui_infos
+            .iter()
+            .find(|s| s.name == session_name)
+            .and_then(|s| s.preview.as_ref())
+    }
     pub fn has_forbidden_session(&self, session_name: &str) -> bool {
         self.forbidden_sessions
             .iter()
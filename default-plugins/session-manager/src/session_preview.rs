@@ -0,0 +1,529 @@
+use zellij_tile::prelude::layout::{Layout, Run, TiledPaneLayout};
+use zellij_tile::prelude::pane_size::PaneGeom;
+use zellij_tile::prelude::*;
+
+/// Lists get a preview panel beside them only from this width, so as not to cramp them
+pub const MIN_COLS_FOR_PREVIEW: usize = 100;
+/// The narrowest a preview panel can be and still show more than a couple of panes
+pub const MIN_PREVIEW_PANEL_COLS: usize = 30;
+
+// serialized layouts are mostly made of percentages, so we lay them out in an arbitrary space
+// that's big enough for fixed sizes to keep roughly their proportions
+const LAYOUT_SPACE_COLS: usize = 200;
+const LAYOUT_SPACE_ROWS: usize = 50;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CellStyle {
+    Plain,
+    Focused,
+    Title,
+    Command,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PreviewPane {
+    pub x: usize,
+    pub y: usize,
+    pub cols: usize,
+    pub rows: usize,
+    pub title: String,
+    pub command: Option<String>,
+    pub is_focused: bool,
+    pub is_floating: bool,
+}
+
+/// A wireframe of the focused tab of a session, either live (from the pane geometries the session
+/// reports) or resurrectable (from its serialized layout)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SessionPreview {
+    pub tab_name: String,
+    pub cols: usize,
+    pub rows: usize,
+    pub panes: Vec<PreviewPane>,
+}
+
+impl SessionPreview {
+    pub fn from_session_info(session_info: &SessionInfo) -> Option<Self> {
+        let tab = session_info
+            .tabs
+            .iter()
+            .find(|t| t.active)
+            .or_else(|| session_info.tabs.first())?;
+        let panes: Vec<PreviewPane> = session_info
+            .panes
+            .panes
+            .get(&tab.position)?
+            .iter()
+            .filter(|p| p.is_selectable && !p.is_suppressed)
+            .filter(|p| !p.is_floating || tab.are_floating_panes_visible)
+            .map(|p| {
+                let command = p
+                    .terminal_command
+                    .clone()
+                    .or_else(|| p.plugin_url.clone())
+                    .filter(|c| c != &p.title);
+                PreviewPane {
+                    x: p.pane_x,
+                    y: p.pane_y,
+                    cols: p.pane_columns,
+                    rows: p.pane_rows,
+                    title: p.title.clone(),
+                    command,
+                    is_focused: p.is_focused,
+                    is_floating: p.is_floating,
+                }
+            })
+            .collect();
+        SessionPreview::from_panes(tab.name.clone(), panes)
+    }
+    pub fn from_serialized_layout(raw_layout: &str) -> Option<Self> {
+        let layout =
+            Layout::from_kdl(raw_layout, Some("session-layout.kdl".into()), None, None).ok()?;
+        let tabs = layout.tabs();
+        let (tab_name, tiled_panes, floating_panes) = match tabs
+            .get(layout.focused_tab_index().unwrap_or(0))
+            .or_else(|| tabs.first())
+        {
+            Some((tab_name, tiled_panes, floating_panes)) => (
+                tab_name.clone().unwrap_or_else(|| "Tab #1".to_owned()),
+                tiled_panes.clone(),
+                floating_panes.clone(),
+            ),
+            None => {
+                let (tiled_panes, floating_panes) = layout.new_tab();
+                ("Tab #1".to_owned(), tiled_panes, floating_panes)
+            },
+        };
+        let mut space = PaneGeom::default();
+        space.cols.set_inner(LAYOUT_SPACE_COLS);
+        space.rows.set_inner(LAYOUT_SPACE_ROWS);
+        let mut panes: Vec<PreviewPane> = tiled_panes
+            .position_panes_in_space(&space, None, false, false)
+            .ok()?
+            .into_iter()
+            // the tab-bar, status-bar and their likes, which aren't part of the tab's content
+            .filter(|(pane_layout, _)| pane_layout.borderless != Some(true))
+            .map(|(pane_layout, geom)| {
+                let command = describe_run(&pane_layout.run);
+                PreviewPane {
+                    x: geom.x,
+                    y: geom.y,
+                    cols: geom.cols.as_usize(),
+                    rows: geom.rows.as_usize(),
+                    title: pane_title(&pane_layout, &command),
+                    command: command.filter(|_| pane_layout.name.is_some()),
+                    is_focused: pane_layout.focus == Some(true),
+                    is_floating: false,
+                }
+            })
+            .collect();
+        for floating_pane in floating_panes {
+            let cols = floating_pane
+                .width
+                .map(|w| w.to_position(LAYOUT_SPACE_COLS))
+                .unwrap_or(LAYOUT_SPACE_COLS / 2);
+            let rows = floating_pane
+                .height
+                .map(|h| h.to_position(LAYOUT_SPACE_ROWS))
+                .unwrap_or(LAYOUT_SPACE_ROWS / 2);
+            let x = floating_pane
+                .x
+                .map(|x| x.to_position(LAYOUT_SPACE_COLS))
+                .unwrap_or((LAYOUT_SPACE_COLS.saturating_sub(cols)) / 2);
+            let y = floating_pane
+                .y
+                .map(|y| y.to_position(LAYOUT_SPACE_ROWS))
+                .unwrap_or((LAYOUT_SPACE_ROWS.saturating_sub(rows)) / 2);
+            let command = describe_run(&floating_pane.run);
+            panes.push(PreviewPane {
+                x,
+                y,
+                cols,
+                rows,
+                title: floating_pane
+                    .name
+                    .clone()
+                    .or_else(|| command.clone())
+                    .unwrap_or_else(|| "Pane".to_owned()),
+                command: command.filter(|_| floating_pane.name.is_some()),
+                is_focused: floating_pane.focus == Some(true),
+                is_floating: true,
+            });
+        }
+        SessionPreview::from_panes(tab_name, panes)
+    }
+    /// Moves the panes so that the tiled ones start at the origin, leaving out the space taken by
+    /// any panes that were filtered out
+    fn from_panes(tab_name: String, mut panes: Vec<PreviewPane>) -> Option<Self> {
+        let tiled_panes = panes.iter().filter(|p| !p.is_floating);
+        let min_x = tiled_panes.clone().map(|p| p.x).min()?;
+        let min_y = tiled_panes.clone().map(|p| p.y).min()?;
+        let max_x = tiled_panes.clone().map(|p| p.x + p.cols).max()?;
+        let max_y = tiled_panes.map(|p| p.y + p.rows).max()?;
+        for pane in panes.iter_mut() {
+            pane.x = pane.x.saturating_sub(min_x);
+            pane.y = pane.y.saturating_sub(min_y);
+        }
+        // floating panes are drawn on top of the tiled ones
+        panes.sort_by_key(|p| p.is_floating);
+        Some(SessionPreview {
+            tab_name,
+            cols: max_x.saturating_sub(min_x),
+            rows: max_y.saturating_sub(min_y),
+            panes,
+        })
+    }
+    pub fn render(&self, x: usize, y: usize, rows: usize, cols: usize) {
+        let title = format!("Preview: {}", self.tab_name);
+        let title = Text::new(truncate(&title, cols)).color_range(2, ..8);
+        print_text_with_coordinates(title, x, y, Some(cols), None);
+        for (i, line) in self
+            .wireframe(rows.saturating_sub(1), cols)
+            .into_iter()
+            .enumerate()
+        {
+            print_text_with_coordinates(line, x, y + i + 1, Some(cols), None);
+        }
+    }
+    fn wireframe(&self, rows: usize, cols: usize) -> Vec<Text> {
+        self.wireframe_cells(rows, cols)
+            .into_iter()
+            .map(|row| {
+                let line: String = row.iter().map(|(c, _)| *c).collect();
+                let indices_of = |style: CellStyle| -> Vec<usize> {
+                    row.iter()
+                        .enumerate()
+                        .filter(|(_, (_, s))| *s == style)
+                        .map(|(i, _)| i)
+                        .collect()
+                };
+                Text::new(line)
+                    .color_indices(3, indices_of(CellStyle::Focused))
+                    .color_indices(0, indices_of(CellStyle::Title))
+                    .dim_indices(indices_of(CellStyle::Command))
+            })
+            .collect()
+    }
+    fn wireframe_cells(&self, rows: usize, cols: usize) -> Vec<Vec<(char, CellStyle)>> {
+        let mut cells = vec![vec![(' ', CellStyle::Plain); cols]; rows];
+        if self.cols == 0 || self.rows == 0 {
+            return cells;
+        }
+        let scale_x = |x: usize| std::cmp::min(x * cols / self.cols, cols);
+        let scale_y = |y: usize| std::cmp::min(y * rows / self.rows, rows);
+        for pane in &self.panes {
+            let (left, right) = (scale_x(pane.x), scale_x(pane.x + pane.cols));
+            let (top, bottom) = (scale_y(pane.y), scale_y(pane.y + pane.rows));
+            let (width, height) = (right.saturating_sub(left), bottom.saturating_sub(top));
+            if width == 0 || height == 0 {
+                continue;
+            }
+            if width < 3 || height < 2 {
+                // too small for a frame, we just show as much of the title as we can
+                for (i, c) in pane.title.chars().take(width).enumerate() {
+                    cells[top][left + i] = (c, CellStyle::Command);
+                }
+                continue;
+            }
+            let border_style = if pane.is_focused {
+                CellStyle::Focused
+            } else {
+                CellStyle::Plain
+            };
+            for (row, row_cells) in cells[top..bottom].iter_mut().enumerate() {
+                let (is_top, is_bottom) = (row == 0, row == height - 1);
+                for (col, cell) in row_cells[left..right].iter_mut().enumerate() {
+                    let (is_left, is_right) = (col == 0, col == width - 1);
+                    let c = match (is_top, is_bottom, is_left, is_right) {
+                        (true, _, true, _) => '┌',
+                        (true, _, _, true) => '┐',
+                        (_, true, true, _) => '└',
+                        (_, true, _, true) => '┘',
+                        (true, _, _, _) | (_, true, _, _) => '─',
+                        (_, _, true, _) | (_, _, _, true) => '│',
+                        _ => ' ',
+                    };
+                    let style = if c == ' ' {
+                        CellStyle::Plain
+                    } else {
+                        border_style
+                    };
+                    *cell = (c, style);
+                }
+            }
+            let inner_width = width - 2;
+            for (i, c) in pane.title.chars().take(inner_width).enumerate() {
+                cells[top][left + 1 + i] = (c, CellStyle::Title);
+            }
+            if let Some(command) = pane.command.as_ref().filter(|_| height > 2) {
+                for (i, c) in command.chars().take(inner_width).enumerate() {
+                    cells[top + 1][left + 1 + i] = (c, CellStyle::Command);
+                }
+            }
+        }
+        cells
+    }
+}
+
+/// Splits the width of a screen between a list and the preview panel beside it, if there's room for one
+pub fn list_and_preview_cols(cols: usize) -> (usize, Option<usize>) {
+    if cols >= MIN_COLS_FOR_PREVIEW {
+        let list_cols = cols * 3 / 5;
+        (list_cols, Some(cols.saturating_sub(list_cols + 2)))
+    } else {
+        (cols, None)
+    }
+}
+
+/// Renders the preview of the selected session, or an indication of why there's none
+pub fn render_preview_panel(
+    preview: Option<&SessionPreview>,
+    x: usize,
+    y: usize,
+    rows: usize,
+    cols: usize,
+) {
+    match preview {
+        Some(preview) => preview.render(x, y, rows, cols),
+        None => {
+            let text = Text::new(truncate("No preview available", cols)).dim_range(..);
+            print_text_with_coordinates(text, x, y, Some(cols), None);
+        },
+    }
+}
+
+fn describe_run(run: &Option<Run>) -> Option<String> {
+    match run {
+        Some(Run::Command(run_command)) => Some(run_command.to_string()),
+        Some(Run::Plugin(run_plugin)) => Some(run_plugin.location_string()),
+        Some(Run::EditFile(path, _, _)) => Some(path.display().to_string()),
+        Some(Run::Cwd(_)) | None => None,
+    }
+}
+
+fn pane_title(pane_layout: &TiledPaneLayout, command: &Option<String>) -> String {
+    pane_layout
+        .name
+        .clone()
+        .or_else(|| command.clone())
+        .unwrap_or_else(|| "Pane".to_owned())
+}
+
+fn truncate(text: &str, cols: usize) -> String {
+    text.chars().take(cols).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wireframe_lines(preview: &SessionPreview, rows: usize, cols: usize) -> Vec<String> {
+        preview
+            .wireframe_cells(rows, cols)
+            .iter()
+            .map(|row| row.iter().map(|(c, _)| *c).collect())
+            .collect()
+    }
+
+    #[test]
+    fn serialized_layout_is_scaled_into_a_wireframe() {
+        let raw_layout = r#"
+            layout {
+                tab name="editor" focus=true {
+                    pane size=1 borderless=true {
+                        plugin location="zellij:tab-bar"
+                    }
+                    pane split_direction="vertical" {
+                        pane name="code" focus=true command="vim" {
+                            args "main.rs"
+                        }
+                        pane command="cargo" {
+                            args "watch"
+                        }
+                    }
+                }
+            }
+        "#;
+        let preview = SessionPreview::from_serialized_layout(raw_layout).unwrap();
+        assert_eq!(preview.tab_name, "editor");
+        assert_eq!(preview.panes.len(), 2, "the tab-bar is left out");
+        assert_eq!(preview.panes[0].title, "code");
+        assert_eq!(preview.panes[0].command.as_deref(), Some("vim main.rs"));
+        assert!(preview.panes[0].is_focused);
+        assert_eq!(preview.panes[1].title, "cargo watch");
+        assert_eq!((preview.panes[0].x, preview.panes[0].y), (0, 0));
+
+        let lines = wireframe_lines(&preview, 4, 20);
+        assert_eq!(
+            lines,
+            vec![
+                "┌code────┐┌cargo wa┐",
+                "│vim main││        │",
+                "│        ││        │",
+                "└────────┘└────────┘",
+            ]
+        );
+    }
+
+    fn pane(x: usize, y: usize, cols: usize, rows: usize, title: &str) -> PaneInfo {
+        PaneInfo {
+            pane_x: x,
+            pane_y: y,
+            pane_columns: cols,
+            pane_rows: rows,
+            title: title.to_owned(),
+            is_selectable: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn live_session_preview_shows_the_active_tab_without_bars() {
+        let mut session_info = SessionInfo {
+            name: "session".to_owned(),
+            tabs: vec![
+                TabInfo {
+                    position: 0,
+                    name: "first".to_owned(),
+                    ..Default::default()
+                },
+                TabInfo {
+                    position: 1,
+                    name: "second".to_owned(),
+                    active: true,
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+        let tab_bar = PaneInfo {
+            is_selectable: false,
+            plugin_url: Some("zellij:tab-bar".to_owned()),
+            ..pane(0, 0, 80, 1, "tab-bar")
+        };
+        let editor = PaneInfo {
+            is_focused: true,
+            terminal_command: Some("vim".to_owned()),
+            ..pane(0, 1, 40, 20, "editor")
+        };
+        let shell = pane(40, 1, 40, 20, "shell");
+        let suppressed = PaneInfo {
+            is_suppressed: true,
+            ..pane(0, 1, 80, 20, "suppressed")
+        };
+        session_info
+            .panes
+            .panes
+            .insert(0, vec![pane(0, 0, 80, 20, "other tab")]);
+        session_info
+            .panes
+            .panes
+            .insert(1, vec![tab_bar, editor, shell, suppressed]);
+
+        let preview = SessionPreview::from_session_info(&session_info).unwrap();
+        assert_eq!(preview.tab_name, "second");
+        assert_eq!((preview.cols, preview.rows), (80, 20));
+        let titles: Vec<&str> = preview.panes.iter().map(|p| p.title.as_str()).collect();
+        assert_eq!(titles, vec!["editor", "shell"]);
+        assert_eq!(
+            (preview.panes[0].x, preview.panes[0].y),
+            (0, 0),
+            "panes are moved to where the tab-bar was"
+        );
+        assert_eq!(preview.panes[0].command.as_deref(), Some("vim"));
+        assert!(preview.panes[0].is_focused);
+    }
+
+    #[test]
+    fn hidden_floating_panes_are_left_out_of_live_previews() {
+        let floating_pane = PaneInfo {
+            is_floating: true,
+            ..pane(10, 5, 20, 10, "floating")
+        };
+        let mut session_info = SessionInfo {
+            tabs: vec![TabInfo {
+                active: true,
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        session_info
+            .panes
+            .panes
+            .insert(0, vec![pane(0, 0, 80, 20, "tiled"), floating_pane.clone()]);
+        let preview = SessionPreview::from_session_info(&session_info).unwrap();
+        assert_eq!(preview.panes.len(), 1);
+
+        session_info.tabs[0].are_floating_panes_visible = true;
+        let preview = SessionPreview::from_session_info(&session_info).unwrap();
+        assert_eq!(preview.panes.len(), 2);
+        assert!(
+            preview.panes[1].is_floating,
+            "floating panes are drawn on top"
+        );
+    }
+
+    #[test]
+    fn serialized_floating_panes_are_placed_by_their_coordinates() {
+        let raw_layout = r#"
+            layout {
+                tab {
+                    pane
+                    floating_panes {
+                        pane name="htop" command="htop" x="50%" y=0 width="50%" height="50%"
+                    }
+                }
+            }
+        "#;
+        let preview = SessionPreview::from_serialized_layout(raw_layout).unwrap();
+        assert_eq!(preview.tab_name, "Tab #1");
+        assert_eq!(preview.panes.len(), 2);
+        let floating_pane = &preview.panes[1];
+        assert!(floating_pane.is_floating);
+        assert_eq!(floating_pane.title, "htop");
+        assert_eq!(
+            (floating_pane.x, floating_pane.y),
+            (LAYOUT_SPACE_COLS / 2, 0)
+        );
+        assert_eq!(
+            (floating_pane.cols, floating_pane.rows),
+            (LAYOUT_SPACE_COLS / 2, LAYOUT_SPACE_ROWS / 2)
+        );
+    }
+
+    #[test]
+    fn unparsable_layout_has_no_preview() {
+        assert_eq!(
+            SessionPreview::from_serialized_layout("layout { tab { pane"),
+            None
+        );
+    }
+
+    #[test]
+    fn panes_too_small_for_a_frame_show_only_their_title() {
+        let preview = SessionPreview {
+            tab_name: "tab".to_owned(),
+            cols: 10,
+            rows: 10,
+            panes: vec![PreviewPane {
+                x: 0,
+                y: 0,
+                cols: 10,
+                rows: 2,
+                title: "narrow".to_owned(),
+                command: None,
+                is_focused: false,
+                is_floating: false,
+            }],
+        };
+        let lines = wireframe_lines(&preview, 5, 10);
+        assert_eq!(lines[0], "narrow    ");
+        assert!(lines[1..].iter().all(|line| line.trim().is_empty()));
+    }
+
+    #[test]
+    fn preview_panel_is_shown_only_on_wide_screens() {
+        assert_eq!(list_and_preview_cols(MIN_COLS_FOR_PREVIEW - 1), (99, None));
+        assert_eq!(list_and_preview_cols(150), (90, Some(58)));
+    }
+}
@@ -346,6 +346,7 @@ mod tests {
             connected_users: connected,
             is_current_session: is_current,
             creation_time: Duration::from_secs(creation_secs),
+            preview: None,
         }
     }
 
@@ -1470,8 +1470,15 @@ pub fn render_controls_line(
             let kill_text = colors.bold("Kill");
             let kill_all = colors.shortcuts("<Ctrl d>");
             let kill_all_text = colors.bold("Kill all");
+            let send_pane = colors.shortcuts("<Ctrl p>");
+            let send_pane_text = colors.bold("Send pane");
 
-            if max_cols > 90 {
+            if max_cols > 110 {
+                print!(
+                    "\u{1b}[m\u{1b}[{y};{x}HHelp: {rename} - {rename_text}, {disconnect} - {disconnect_text}, {kill} - {kill_text}, {kill_all} - {kill_all_text}, {send_pane} - {send_pane_text}"
+                );
+                true
+            } else if max_cols > 90 {
                 print!(
                     "\u{1b}[m\u{1b}[{y};{x}HHelp: {rename} - {rename_text}, {disconnect} - {disconnect_text}, {kill} - {kill_text}, {kill_all} - {kill_all_text}"
                 );
@@ -1492,12 +1499,22 @@ pub fn render_controls_line(
             let del_text = colors.bold("Delete");
             let del_all = colors.shortcuts("<Ctrl d>");
             let del_all_text = colors.bold("Delete all");
+            let snapshot_arrows = colors.shortcuts("<←→>");
+            let snapshot_text = colors.bold("Snapshot");
 
-            if max_cols > 83 {
+            if max_cols > 100 {
+                print!(
+                    "\u{1b}[m\u{1b}[{y};{x}HHelp: {arrows} - {navigate}, {enter} - {select}, {snapshot_arrows} - {snapshot_text}, {del} - {del_text}, {del_all} - {del_all_text}"
+                );
+                true
+            } else if max_cols > 83 {
                 print!(
                     "\u{1b}[m\u{1b}[{y};{x}HHelp: {arrows} - {navigate}, {enter} - {select}, {del} - {del_text}, {del_all} - {del_all_text}"
                 );
                 true
+            } else if max_cols >= 33 {
+                print!("\u{1b}[m\u{1b}[{y};{x}H{arrows}/{enter}/{snapshot_arrows}/{del}/{del_all}");
+                false
             } else if max_cols >= 28 {
                 print!("\u{1b}[m\u{1b}[{y};{x}H{arrows}/{enter}/{del}/{del_all}");
                 false
@@ -1513,9 +1530,17 @@ pub fn render_controls_line(
             let disconnect_short_text = colors.bold("Disconnect");
             let kill = colors.shortcuts("<Del>");
             let kill_text = colors.bold("Kill/Delete");
+            let send_pane = colors.shortcuts("<Ctrl p>");
+            let send_pane_text = colors.bold("Send pane");
 
+            // Extended: "Help: <Ctrl r> - Rename, <Ctrl x> - Disconnect others, <Del> - Kill/Delete, <Ctrl p> - Send pane" = 98 chars
+            if max_cols > 98 {
+                print!(
+                    "\u{1b}[m\u{1b}[{y};{x}HHelp: {rename} - {rename_text}, {disconnect} - {disconnect_full_text}, {kill} - {kill_text}, {send_pane} - {send_pane_text}"
+                );
+                true
             // Full: "Help: <Ctrl r> - Rename, <Ctrl x> - Disconnect others, <Del> - Kill/Delete" = 76 chars
-            if max_cols > 76 {
+            } else if max_cols > 76 {
                 print!(
                     "\u{1b}[m\u{1b}[{y};{x}HHelp: {rename} - {rename_text}, {disconnect} - {disconnect_full_text}, {kill} - {kill_text}"
                 );
@@ -4,6 +4,7 @@ use std::time::Duration;
 use zellij_tile::prelude::*;
 
 use crate::session_list::{SelectedIndex, SessionList};
+use crate::session_preview::SessionPreview;
 use components::{
     build_pane_ui_line, build_session_ui_line, build_tab_ui_line, minimize_lines, Colors,
     LineToRender,
@@ -229,6 +230,7 @@ pub struct SessionUiInfo {
     pub connected_users: usize,
     pub is_current_session: bool,
     pub creation_time: Duration,
+    pub preview: Option<SessionPreview>,
 }
 
 impl SessionUiInfo {
@@ -243,6 +245,7 @@ impl SessionUiInfo {
             connected_users: session_info.connected_clients,
             is_current_session: session_info.is_current_session,
             creation_time: session_info.creation_time,
+            preview: SessionPreview::from_session_info(session_info),
         }
     }
     pub fn line_count(&self, selected_index: &SelectedIndex) -> usize {
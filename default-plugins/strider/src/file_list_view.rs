@@ -1,8 +1,9 @@
+use crate::git_status::GitStatus;
 use crate::platform::Platform;
 use crate::shared::{calculate_list_bounds, render_list_tip};
 use crate::state::refresh_directory;
 use pretty_bytes::converter::convert as pretty_bytes;
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use std::path::PathBuf;
 use unicode_width::UnicodeWidthStr;
 use zellij_tile::prelude::*;
@@ -119,14 +120,23 @@ impl FileListView {
         }
     }
 
-    pub fn render(&mut self, rows: usize, cols: usize) {
+    pub fn render(
+        &mut self,
+        rows: usize,
+        cols: usize,
+        git_status: &GitStatus,
+        marked: &BTreeSet<PathBuf>,
+    ) {
         let (start_index, selected_index_in_range, end_index) =
             calculate_list_bounds(self.files.len(), rows.saturating_sub(1), self.selected());
 
         render_list_tip(3, cols);
+        let gutter_width = git_status.gutter_width();
+        let cols = cols.saturating_sub(gutter_width);
         for i in start_index..end_index {
             if let Some(entry) = self.files.get(i) {
                 let is_selected = Some(i) == selected_index_in_range;
+                let git_marker = git_status.marker_for(&entry.name());
                 let mut file_or_folder_name = entry.name();
                 let size = entry
                     .size()
@@ -147,19 +157,25 @@ impl FileListView {
                     let padding = " ".repeat(cols.saturating_sub(file_or_folder_name_width));
                     format!("{}{}", file_or_folder_name, padding)
                 };
+                let text = format!("{}{}", git_status.gutter(git_marker), text);
                 let mut text_element = if is_selected {
                     Text::new(text).selected()
                 } else {
                     Text::new(text)
                 };
-                if entry.is_folder() {
-                    text_element = text_element.color_range(0, ..);
+                if marked.contains(&entry.get_full_pathbuf()) {
+                    text_element = text_element.color_range(1, gutter_width..);
+                } else if entry.is_folder() {
+                    text_element = text_element.color_range(0, gutter_width..);
+                }
+                if let Some(git_marker) = git_marker {
+                    text_element = git_marker.style(text_element, 0..1);
                 }
                 print_text_with_coordinates(
                     text_element,
                     0,
                     4 + i.saturating_sub(start_index),
-                    Some(cols),
+                    Some(cols + gutter_width),
                     None,
                 );
             }
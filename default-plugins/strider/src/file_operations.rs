@@ -0,0 +1,428 @@
+use crate::platform::Platform;
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::path::{Component, Path, PathBuf};
+use unicode_width::UnicodeWidthStr;
+use zellij_tile::prelude::*;
+
+const TRASH_CONTEXT: &str = "strider_trash_attempt";
+const PASTE_CONTEXT: &str = "strider_paste";
+
+// tried in order until one of them is installed, all of them move the files to the desktop trash
+const UNIX_TRASH_COMMANDS: &[&[&str]] =
+    &[&["gio", "trash", "--"], &["trash-put", "--"], &["trash"]];
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Prompt {
+    New { input: String },
+    Rename { original: PathBuf, input: String },
+    ConfirmTrash { paths: Vec<PathBuf> },
+}
+
+impl Prompt {
+    pub fn handle_char(&mut self, character: char) {
+        match self {
+            Prompt::New { input } | Prompt::Rename { input, .. } => input.push(character),
+            Prompt::ConfirmTrash { .. } => {},
+        }
+    }
+    pub fn handle_backspace(&mut self) {
+        match self {
+            Prompt::New { input } | Prompt::Rename { input, .. } => {
+                input.pop();
+            },
+            Prompt::ConfirmTrash { .. } => {},
+        }
+    }
+    pub fn render(&self, cols: usize) {
+        match self {
+            Prompt::New { input } => {
+                let prompt = "NEW (end with / for a folder): ";
+                let text = Text::new(format!("{}{}_", prompt, input))
+                    .color_range(2, 0..prompt.width())
+                    .color_range(3, prompt.width()..);
+                print_text_with_coordinates(text, 0, 0, Some(cols), None);
+            },
+            Prompt::Rename { input, .. } => {
+                let prompt = "RENAME TO: ";
+                let text = Text::new(format!("{}{}_", prompt, input))
+                    .color_range(2, 0..prompt.width())
+                    .color_range(3, prompt.width()..);
+                print_text_with_coordinates(text, 0, 0, Some(cols), None);
+            },
+            Prompt::ConfirmTrash { paths } => {
+                let items = if paths.len() == 1 { "item" } else { "items" };
+                let question = format!("Move {} {} to the trash?", paths.len(), items);
+                let question_width = question.width();
+                let text = Text::new(format!("{} <ENTER> - confirm, <ESC> - cancel", question))
+                    .error_color_range(0..question_width)
+                    .color_range(3, question_width + 1..question_width + 8)
+                    .color_range(3, question_width + 20..question_width + 25);
+                print_text_with_coordinates(text, 0, 0, Some(cols), None);
+            },
+        }
+    }
+    /// Lists what the prompt is about to change, in place of the file list
+    pub fn render_affected_paths(&self, rows: usize, cols: usize, platform: Platform) {
+        if let Prompt::ConfirmTrash { paths } = self {
+            let items = paths
+                .iter()
+                .take(rows)
+                .map(|p| NestedListItem::new(Platform::to_host_display(p, platform)))
+                .collect();
+            print_nested_list_with_coordinates(items, 0, 4, Some(cols), None);
+        }
+    }
+}
+
+/// Creates a file (or a folder, if the name ends with a separator) in the current folder
+pub fn create_entry(name: &str) -> Result<(), String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("a name is required".to_owned());
+    }
+    let is_folder = name.ends_with('/') || name.ends_with('\\');
+    let path = PathBuf::from("/host").join(name.trim_end_matches(['/', '\\']));
+    if !path.starts_with("/host") || path.components().any(|c| c == Component::ParentDir) {
+        return Err(format!("{} is outside of the current folder", name));
+    }
+    if path.exists() {
+        return Err(format!("{} already exists", name));
+    }
+    if is_folder {
+        fs::create_dir_all(&path).map_err(|e| e.to_string())
+    } else {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    }
+}
+
+/// Renames an entry of the current folder, keeping it in that folder
+pub fn rename_entry(original_name: &str, new_name: &str) -> Result<(), String> {
+    let new_name = new_name.trim();
+    if new_name.is_empty() || new_name.contains(['/', '\\']) || new_name == ".." {
+        return Err(format!("{} is not a valid name", new_name));
+    }
+    let destination = PathBuf::from("/host").join(new_name);
+    if destination.exists() {
+        return Err(format!("{} already exists", new_name));
+    }
+    fs::rename(PathBuf::from("/host").join(original_name), destination).map_err(|e| e.to_string())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ClipboardMode {
+    Copy,
+    Cut,
+}
+
+/// A copy or a move of entries from anywhere on the host into the current folder. The sources
+/// might be outside of the host folder, so the system's own tools do the pasting.
+#[derive(Debug, Clone)]
+pub struct PasteOperation {
+    pub mode: ClipboardMode,
+    pub sources: Vec<PathBuf>,
+    pub destination: PathBuf, // the current folder, mounted as /host
+}
+
+impl PasteOperation {
+    /// Starts pasting the sources into the destination, the result arrives as a
+    /// `RunCommandResult` to be passed to `handle_result`. Returns how many entries are being
+    /// pasted.
+    pub fn start(&self, platform: Platform) -> Result<usize, String> {
+        let targets = self.targets()?;
+        if !targets.is_empty() {
+            self.run_paste_command(&targets, platform);
+        }
+        Ok(targets.len())
+    }
+    /// Returns `Some` with the outcome if the command result was of a paste command
+    pub fn handle_result(
+        exit_code: Option<i32>,
+        stderr: &[u8],
+        context: &BTreeMap<String, String>,
+    ) -> Option<Result<(), String>> {
+        if !context.contains_key(PASTE_CONTEXT) {
+            return None;
+        }
+        if exit_code == Some(0) {
+            return Some(Ok(()));
+        }
+        let error = String::from_utf8_lossy(stderr).trim().to_owned();
+        if error.is_empty() {
+            Some(Err("Failed to paste".to_owned()))
+        } else {
+            Some(Err(error))
+        }
+    }
+    /// Pairs each source with the host path it is pasted to, leaving out entries that are
+    /// moved to where they already are
+    fn targets(&self) -> Result<Vec<(PathBuf, PathBuf)>, String> {
+        let mut targets = vec![];
+        let mut taken = vec![];
+        for source in &self.sources {
+            let file_name = source
+                .file_name()
+                .ok_or_else(|| format!("Cannot paste {}", source.display()))?
+                .to_string_lossy()
+                .to_string();
+            if self.destination.starts_with(source) {
+                return Err(format!("Cannot paste {} into itself", source.display()));
+            }
+            let target_in_host_folder = match self.mode {
+                ClipboardMode::Copy => available_copy_name(Path::new("/host"), &file_name, &taken),
+                ClipboardMode::Cut => {
+                    if source.parent() == Some(self.destination.as_path()) {
+                        continue;
+                    }
+                    let target = PathBuf::from("/host").join(&file_name);
+                    if target.exists() || taken.contains(&target) {
+                        return Err(format!("{} already exists in the destination", file_name));
+                    }
+                    target
+                },
+            };
+            let target = self.destination.join(
+                target_in_host_folder
+                    .strip_prefix("/host")
+                    .unwrap_or(&target_in_host_folder),
+            );
+            taken.push(target_in_host_folder);
+            targets.push((source.clone(), target));
+        }
+        Ok(targets)
+    }
+    fn run_paste_command(&self, targets: &[(PathBuf, PathBuf)], platform: Platform) {
+        let host_targets = targets.iter().map(|(source, target)| {
+            (
+                Platform::to_host_display(source, platform),
+                Platform::to_host_display(target, platform),
+            )
+        });
+        let command: Vec<String> = match platform {
+            Platform::Unix => {
+                let paste_command = match self.mode {
+                    ClipboardMode::Copy => "cp -R",
+                    ClipboardMode::Cut => "mv",
+                };
+                // the sources and targets are passed as arguments, so they need no quoting
+                let script = format!(
+                    "while [ \"$#\" -gt 0 ]; do {} -- \"$1\" \"$2\" || exit 1; shift 2; done",
+                    paste_command
+                );
+                let mut command = vec!["sh".to_owned(), "-c".to_owned(), script, "sh".to_owned()];
+                for (source, target) in host_targets {
+                    command.push(source);
+                    command.push(target);
+                }
+                command
+            },
+            Platform::Windows => {
+                let paste_command = match self.mode {
+                    ClipboardMode::Copy => "Copy-Item -Recurse",
+                    ClipboardMode::Cut => "Move-Item",
+                };
+                let script = host_targets
+                    .map(|(source, target)| {
+                        format!(
+                            "{} -LiteralPath '{}' -Destination '{}'",
+                            paste_command,
+                            source.replace('\'', "''"),
+                            target.replace('\'', "''")
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join("; ");
+                vec![
+                    "powershell".to_owned(),
+                    "-NoProfile".to_owned(),
+                    "-Command".to_owned(),
+                    format!("$ErrorActionPreference = 'Stop'; {}", script),
+                ]
+            },
+        };
+        let mut context = BTreeMap::new();
+        context.insert(PASTE_CONTEXT.to_owned(), String::new());
+        let command: Vec<&str> = command.iter().map(|c| c.as_str()).collect();
+        run_command_with_env_variables_and_cwd(
+            &command,
+            BTreeMap::new(),
+            self.destination.clone(),
+            context,
+        );
+    }
+}
+
+/// A path in `folder` for a copy of `file_name` that doesn't overwrite anything, nor any of the
+/// `taken` paths
+fn available_copy_name(folder: &Path, file_name: &str, taken: &[PathBuf]) -> PathBuf {
+    let is_available = |target: &PathBuf| !target.exists() && !taken.contains(target);
+    let target = folder.join(file_name);
+    if is_available(&target) {
+        return target;
+    }
+    let (stem, extension) = match file_name.rsplit_once('.') {
+        Some((stem, extension)) if !stem.is_empty() => (stem, format!(".{}", extension)),
+        _ => (file_name, String::new()),
+    };
+    let mut copy_index = 1;
+    loop {
+        let copy_name = if copy_index == 1 {
+            format!("{} copy{}", stem, extension)
+        } else {
+            format!("{} copy {}{}", stem, copy_index, extension)
+        };
+        let target = folder.join(copy_name);
+        if is_available(&target) {
+            return target;
+        }
+        copy_index += 1;
+    }
+}
+
+/// Moves these host paths to the desktop trash using the system's own tools, the result
+/// arrives as a `RunCommandResult` to be passed to `handle_trash_result`
+pub fn trash_paths(paths: &[PathBuf], cwd: &Path, platform: Platform) {
+    run_trash_command(paths, cwd, platform, 0);
+}
+
+/// Returns `Some` with the outcome if the command result was of a trash command, retrying with
+/// the next trash command if the previous one could not run
+pub fn handle_trash_result(
+    paths: &[PathBuf],
+    cwd: &Path,
+    platform: Platform,
+    exit_code: Option<i32>,
+    stderr: &[u8],
+    context: &BTreeMap<String, String>,
+) -> Option<Result<(), String>> {
+    let attempt: usize = context.get(TRASH_CONTEXT)?.parse().ok()?;
+    if exit_code == Some(0) {
+        return Some(Ok(()));
+    }
+    let next_attempt = attempt + 1;
+    if platform == Platform::Unix && next_attempt < UNIX_TRASH_COMMANDS.len() {
+        run_trash_command(paths, cwd, platform, next_attempt);
+        return None;
+    }
+    let error = String::from_utf8_lossy(stderr).trim().to_owned();
+    if error.is_empty() {
+        Some(Err(
+            "Failed to move to the trash, is gio or trash-cli installed?".to_owned(),
+        ))
+    } else {
+        Some(Err(error))
+    }
+}
+
+fn run_trash_command(paths: &[PathBuf], cwd: &Path, platform: Platform, attempt: usize) {
+    let host_paths: Vec<String> = paths
+        .iter()
+        .map(|p| Platform::to_host_display(p, platform))
+        .collect();
+    let mut command: Vec<String> = match platform {
+        Platform::Unix => UNIX_TRASH_COMMANDS[attempt]
+            .iter()
+            .map(|c| c.to_string())
+            .collect(),
+        Platform::Windows => {
+            let script = host_paths
+                .iter()
+                .map(|p| {
+                    let p = p.replace('\'', "''");
+                    format!("if (Test-Path -LiteralPath '{p}' -PathType Container) {{ [Microsoft.VisualBasic.FileIO.FileSystem]::DeleteDirectory('{p}', 'OnlyErrorDialogs', 'SendToRecycleBin') }} else {{ [Microsoft.VisualBasic.FileIO.FileSystem]::DeleteFile('{p}', 'OnlyErrorDialogs', 'SendToRecycleBin') }}")
+                })
+                .collect::<Vec<_>>()
+                .join("; ");
+            vec![
+                "powershell".to_owned(),
+                "-NoProfile".to_owned(),
+                "-Command".to_owned(),
+                format!("Add-Type -AssemblyName Microsoft.VisualBasic; {}", script),
+            ]
+        },
+    };
+    if platform == Platform::Unix {
+        command.extend(host_paths);
+    }
+    let mut context = BTreeMap::new();
+    context.insert(TRASH_CONTEXT.to_owned(), attempt.to_string());
+    let command: Vec<&str> = command.iter().map(|c| c.as_str()).collect();
+    run_command_with_env_variables_and_cwd(&command, BTreeMap::new(), cwd.to_path_buf(), context);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pasted_entries_keep_their_names_in_the_destination() {
+        let paste_operation = PasteOperation {
+            mode: ClipboardMode::Copy,
+            sources: vec![
+                PathBuf::from("/home/user/project/src/main.rs"),
+                PathBuf::from("/home/user/other_project/src/main.rs"),
+                PathBuf::from("/home/user/project/README.md"),
+            ],
+            destination: PathBuf::from("/home/user/backup"),
+        };
+        assert_eq!(
+            paste_operation.targets(),
+            Ok(vec![
+                (
+                    PathBuf::from("/home/user/project/src/main.rs"),
+                    PathBuf::from("/home/user/backup/main.rs")
+                ),
+                (
+                    PathBuf::from("/home/user/other_project/src/main.rs"),
+                    PathBuf::from("/home/user/backup/main copy.rs")
+                ),
+                (
+                    PathBuf::from("/home/user/project/README.md"),
+                    PathBuf::from("/home/user/backup/README.md")
+                ),
+            ])
+        );
+    }
+
+    #[test]
+    fn entries_are_not_moved_to_where_they_already_are_or_into_themselves() {
+        let mut paste_operation = PasteOperation {
+            mode: ClipboardMode::Cut,
+            sources: vec![PathBuf::from("/home/user/backup/notes.txt")],
+            destination: PathBuf::from("/home/user/backup"),
+        };
+        assert_eq!(paste_operation.targets(), Ok(vec![]));
+        paste_operation.sources = vec![PathBuf::from("/home/user")];
+        assert!(paste_operation.targets().is_err());
+    }
+
+    #[test]
+    fn copies_do_not_overwrite_existing_entries() {
+        let folder =
+            std::env::temp_dir().join(format!("strider-copy-names-{}", std::process::id()));
+        fs::create_dir_all(&folder).unwrap();
+        fs::write(folder.join("notes.txt"), "").unwrap();
+        fs::write(folder.join("notes copy.txt"), "").unwrap();
+        assert_eq!(
+            available_copy_name(&folder, "notes.txt", &[]),
+            folder.join("notes copy 2.txt")
+        );
+        assert_eq!(
+            available_copy_name(&folder, "notes.txt", &[folder.join("notes copy 2.txt")]),
+            folder.join("notes copy 3.txt")
+        );
+        assert_eq!(
+            available_copy_name(&folder, "todo", &[]),
+            folder.join("todo")
+        );
+        fs::remove_dir_all(&folder).unwrap();
+    }
+}
@@ -0,0 +1,193 @@
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use zellij_tile::prelude::*;
+
+const GIT_PREFIX_CONTEXT: &str = "git_prefix_for";
+const GIT_STATUS_CONTEXT: &str = "git_status_for";
+const GIT_PREFIX_KEY: &str = "git_prefix";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum GitMarker {
+    // ordered by precedence, a folder shows the marker of its most significant child
+    Ignored,
+    Untracked,
+    Modified,
+}
+
+impl GitMarker {
+    pub fn symbol(&self) -> char {
+        match self {
+            GitMarker::Ignored => '!',
+            GitMarker::Untracked => '?',
+            GitMarker::Modified => 'M',
+        }
+    }
+    pub fn style(&self, text: Text, range: std::ops::Range<usize>) -> Text {
+        match self {
+            GitMarker::Ignored => text.dim_range(range),
+            GitMarker::Untracked => text.success_color_range(range),
+            GitMarker::Modified => text.color_range(3, range),
+        }
+    }
+}
+
+/// The git status of the entries in the folder strider is currently showing, fetched by running
+/// `git status --porcelain` on the host
+#[derive(Debug, Default)]
+pub struct GitStatus {
+    pub enabled: bool,
+    path: PathBuf,
+    is_repo: bool,
+    markers: HashMap<String, GitMarker>, // entry name -> marker
+}
+
+impl GitStatus {
+    /// Fetches the git status of this folder, replacing the current markers once it arrives
+    pub fn refresh(&mut self, path: &Path) {
+        if !self.enabled {
+            return;
+        }
+        if self.path != path {
+            self.path = path.to_path_buf();
+            self.is_repo = false;
+            self.markers.clear();
+        }
+        // git reports paths relative to the repository root, so we first find out where in the
+        // repository this folder is
+        let mut context = BTreeMap::new();
+        context.insert(
+            GIT_PREFIX_CONTEXT.to_owned(),
+            path.to_string_lossy().to_string(),
+        );
+        run_command_with_env_variables_and_cwd(
+            &["git", "rev-parse", "--show-prefix"],
+            BTreeMap::new(),
+            path.to_path_buf(),
+            context,
+        );
+    }
+    /// Returns true if the command result was ours and the markers should be re-rendered
+    pub fn handle_command_result(
+        &mut self,
+        exit_code: Option<i32>,
+        stdout: Vec<u8>,
+        context: BTreeMap<String, String>,
+    ) -> bool {
+        if let Some(path) = context.get(GIT_PREFIX_CONTEXT) {
+            if !self.is_current_path(path) {
+                return false;
+            }
+            if exit_code != Some(0) {
+                // not a git repository
+                let had_markers = self.is_repo;
+                self.is_repo = false;
+                self.markers.clear();
+                return had_markers;
+            }
+            let prefix = String::from_utf8_lossy(&stdout).trim().to_owned();
+            let mut context = BTreeMap::new();
+            context.insert(GIT_STATUS_CONTEXT.to_owned(), path.to_owned());
+            context.insert(GIT_PREFIX_KEY.to_owned(), prefix);
+            run_command_with_env_variables_and_cwd(
+                &["git", "status", "--porcelain", "--ignored", "-z", "."],
+                BTreeMap::new(),
+                self.path.clone(),
+                context,
+            );
+            false
+        } else if let Some(path) = context.get(GIT_STATUS_CONTEXT) {
+            if !self.is_current_path(path) || exit_code != Some(0) {
+                return false;
+            }
+            let prefix = context
+                .get(GIT_PREFIX_KEY)
+                .map(|p| p.as_str())
+                .unwrap_or("");
+            self.is_repo = true;
+            self.markers = parse_porcelain_status(&String::from_utf8_lossy(&stdout), prefix);
+            true
+        } else {
+            false
+        }
+    }
+    /// Whether the current folder is in a git repository, and so whether to leave room for markers
+    pub fn is_repo(&self) -> bool {
+        self.enabled && self.is_repo
+    }
+    pub fn marker_for(&self, entry_name: &str) -> Option<GitMarker> {
+        self.markers.get(entry_name).copied()
+    }
+    /// The room markers take at the start of each line, none outside of git repositories
+    pub fn gutter_width(&self) -> usize {
+        if self.is_repo() {
+            2
+        } else {
+            0
+        }
+    }
+    pub fn gutter(&self, marker: Option<GitMarker>) -> String {
+        if !self.is_repo() {
+            String::new()
+        } else {
+            format!("{} ", marker.map(|m| m.symbol()).unwrap_or(' '))
+        }
+    }
+    fn is_current_path(&self, path: &str) -> bool {
+        self.path.to_string_lossy() == path
+    }
+}
+
+/// Parses the output of `git status --porcelain -z` into a marker per entry of the folder at
+/// `prefix` (relative to the repository root)
+fn parse_porcelain_status(status: &str, prefix: &str) -> HashMap<String, GitMarker> {
+    let mut markers: HashMap<String, GitMarker> = HashMap::new();
+    let mut records = status.split('\0');
+    while let Some(record) = records.next() {
+        if record.len() < 4 {
+            continue;
+        }
+        let (status_code, path) = record.split_at(3);
+        let status_code = status_code.trim_end();
+        if status_code.contains(&['R', 'C'][..]) {
+            // renames and copies are followed by their original path
+            records.next();
+        }
+        let marker = match status_code {
+            "??" => GitMarker::Untracked,
+            "!!" => GitMarker::Ignored,
+            _ => GitMarker::Modified,
+        };
+        let relative_path = match path.strip_prefix(prefix) {
+            Some(relative_path) => relative_path,
+            None => continue,
+        };
+        let entry_name = match relative_path.split('/').next() {
+            Some(entry_name) if !entry_name.is_empty() => entry_name,
+            _ => continue,
+        };
+        let existing_marker = markers.entry(entry_name.to_owned()).or_insert(marker);
+        *existing_marker = std::cmp::max(*existing_marker, marker);
+    }
+    markers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn porcelain_status_is_grouped_by_entry() {
+        let status = " M src/main.rs\0?? src/new.rs\0!! target/\0R  src/lib/renamed.rs\0src/lib/original.rs\0!! src/lib/generated.rs\0?? README.md\0";
+        let markers = parse_porcelain_status(status, "src/");
+        assert_eq!(markers.get("main.rs"), Some(&GitMarker::Modified));
+        assert_eq!(markers.get("new.rs"), Some(&GitMarker::Untracked));
+        assert_eq!(markers.get("lib"), Some(&GitMarker::Modified));
+        assert_eq!(markers.get("original.rs"), None);
+        assert_eq!(markers.get("target"), None);
+
+        let markers = parse_porcelain_status(status, "");
+        assert_eq!(markers.get("src"), Some(&GitMarker::Modified));
+        assert_eq!(markers.get("target"), Some(&GitMarker::Ignored));
+        assert_eq!(markers.get("README.md"), Some(&GitMarker::Untracked));
+    }
+}
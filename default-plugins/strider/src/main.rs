@@ -1,12 +1,17 @@
 mod file_list_view;
+mod file_operations;
+mod git_status;
 mod platform;
+mod preview;
 mod search_view;
 mod shared;
 mod state;
 
+use file_operations::{ClipboardMode, Prompt};
 use platform::Platform;
 use shared::{
-    render_current_path, render_instruction_line, render_search_term, render_virtual_root_header,
+    render_current_path, render_file_operations_line, render_instruction_line, render_search_term,
+    render_virtual_root_header,
 };
 use state::{refresh_directory, State};
 use std::collections::BTreeMap;
@@ -33,14 +38,26 @@ impl ZellijPlugin for State {
             .get("close_on_selection")
             .map(|v| v == "true")
             .unwrap_or(false);
+        self.show_preview = configuration
+            .get("show_preview")
+            .map(|v| v == "true")
+            .unwrap_or(false);
+        self.git_status.enabled = configuration
+            .get("show_git_status")
+            .map(|v| v == "true")
+            .unwrap_or(true);
         subscribe(&[
             EventType::Key,
             EventType::Mouse,
             EventType::CustomMessage,
             EventType::Timer,
+            EventType::FileSystemCreate,
             EventType::FileSystemUpdate,
+            EventType::FileSystemDelete,
+            EventType::HostFolderScanned,
             EventType::HostFolderChanged,
             EventType::PermissionRequestResult,
+            EventType::RunCommandResult,
         ]);
         self.file_list_view.clear_selected();
 
@@ -58,19 +75,39 @@ impl ZellijPlugin for State {
         if self.initial_cwd != self.file_list_view.path {
             change_host_folder(self.file_list_view.path.clone());
         } else {
-            scan_host_folder(&"/host");
+            self.scan_current_folder();
         }
+        watch_filesystem();
     }
 
     fn update(&mut self, event: Event) -> bool {
         let mut should_render = false;
+        if let Event::Key(_) = &event {
+            // messages about the previous operation are only shown until the next key press
+            should_render = self.status_message.take().is_some();
+        }
         match event {
-            Event::FileSystemUpdate(paths) => {
+            Event::HostFolderScanned(paths) => {
                 self.update_files(paths);
                 should_render = true;
             },
+            Event::FileSystemCreate(paths)
+            | Event::FileSystemUpdate(paths)
+            | Event::FileSystemDelete(paths) => {
+                self.handle_filesystem_change(paths);
+            },
+            Event::RunCommandResult(exit_code, stdout, stderr, context) => {
+                should_render = self.handle_command_result(exit_code, &stderr, &context)
+                    || self
+                        .git_status
+                        .handle_command_result(exit_code, stdout, context);
+            },
             Event::HostFolderChanged(_new_host_folder) => {
-                scan_host_folder(&"/host");
+                self.scan_current_folder();
+                should_render = true;
+            },
+            Event::Key(key) if self.prompt.is_some() => {
+                self.handle_prompt_key(key);
                 should_render = true;
             },
             Event::Key(key) => match key.bare_key {
@@ -122,6 +159,39 @@ impl ZellijPlugin for State {
                     self.toggle_hidden_files();
                     refresh_directory(&self.file_list_view.path);
                 },
+                BareKey::Char('v') if key.has_modifiers(&[KeyModifier::Ctrl]) => {
+                    self.toggle_preview();
+                    should_render = true;
+                },
+                BareKey::Char('a') if key.has_modifiers(&[KeyModifier::Ctrl]) => {
+                    self.start_new_entry_prompt();
+                    should_render = true;
+                },
+                BareKey::Char('r') if key.has_modifiers(&[KeyModifier::Ctrl]) => {
+                    self.start_rename_prompt();
+                    should_render = true;
+                },
+                BareKey::Char('d') if key.has_modifiers(&[KeyModifier::Ctrl]) => {
+                    self.start_trash_prompt();
+                    should_render = true;
+                },
+                BareKey::Char('w') if key.has_modifiers(&[KeyModifier::Ctrl]) => {
+                    self.toggle_mark();
+                    self.move_selection_down();
+                    should_render = true;
+                },
+                BareKey::Char('y') if key.has_modifiers(&[KeyModifier::Ctrl]) => {
+                    self.copy_to_clipboard(ClipboardMode::Copy);
+                    should_render = true;
+                },
+                BareKey::Char('x') if key.has_modifiers(&[KeyModifier::Ctrl]) => {
+                    self.copy_to_clipboard(ClipboardMode::Cut);
+                    should_render = true;
+                },
+                BareKey::Char('u') if key.has_modifiers(&[KeyModifier::Ctrl]) => {
+                    self.paste();
+                    should_render = true;
+                },
                 _ => (),
             },
             Event::Mouse(mouse_event) => match mouse_event {
@@ -183,6 +253,7 @@ impl ZellijPlugin for State {
     fn render(&mut self, rows: usize, cols: usize) {
         self.current_rows = Some(rows);
         let rows_for_list = rows.saturating_sub(6);
+        let (list_cols, preview_cols) = self.list_and_preview_cols(cols);
         if self.is_in_virtual_root {
             render_search_term("");
             render_virtual_root_header(cols);
@@ -196,12 +267,29 @@ impl ZellijPlugin for State {
                 cols,
                 self.platform,
             );
-            if self.is_searching {
-                self.search_view.render(rows_for_list, cols);
+            self.render_status_message(cols);
+            if let Some(prompt) = &self.prompt {
+                // drawn over the search term
+                prompt.render(cols);
+            }
+            if let Some(prompt @ Prompt::ConfirmTrash { .. }) = &self.prompt {
+                prompt.render_affected_paths(rows_for_list, list_cols, self.platform);
+            } else if self.is_searching {
+                self.search_view
+                    .render(rows_for_list, list_cols, &self.git_status, &self.marked);
             } else {
-                self.file_list_view.render(rows_for_list, cols);
+                self.file_list_view.render(
+                    rows_for_list,
+                    list_cols,
+                    &self.git_status,
+                    &self.marked,
+                );
+            }
+            if let Some(preview_cols) = preview_cols {
+                self.render_preview(cols - preview_cols, 3, rows_for_list, preview_cols);
             }
         }
+        render_file_operations_line(rows.saturating_sub(2), cols);
         render_instruction_line(rows, cols);
     }
 }
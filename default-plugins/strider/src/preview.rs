@@ -0,0 +1,269 @@
+use crate::file_list_view::FsEntry;
+use pretty_bytes::converter::convert as pretty_bytes;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use unicode_width::UnicodeWidthChar;
+use zellij_tile::prelude::*;
+
+// only the head of a file is ever previewed, so there's no need to read more than this
+const PREVIEW_BYTES: u64 = 8 * 1024;
+
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "case", "class", "const", "continue", "def", "do", "elif",
+    "else", "enum", "export", "extern", "fn", "for", "from", "func", "function", "if", "impl",
+    "import", "in", "let", "loop", "match", "mod", "mut", "package", "pub", "return", "self",
+    "static", "struct", "switch", "then", "trait", "type", "use", "var", "where", "while",
+];
+
+#[derive(Debug, Clone)]
+pub enum Preview {
+    Text {
+        lines: Vec<String>,
+        comment_prefix: Option<&'static str>,
+    },
+    Folder {
+        child_count: usize,
+        hidden_child_count: usize,
+    },
+    Image {
+        format: &'static str,
+        size: u64,
+    },
+    Binary {
+        size: u64,
+    },
+    Unavailable(String),
+}
+
+impl Preview {
+    /// Reads the preview of an entry of the current folder, which is mounted at `/host`
+    pub fn for_entry(entry: &FsEntry) -> Self {
+        let path = PathBuf::from("/host").join(entry.name());
+        match entry {
+            FsEntry::Dir(_) => Preview::for_folder(&path),
+            FsEntry::File(_, size) => Preview::for_file(&path, *size),
+        }
+    }
+    fn for_folder(path: &Path) -> Self {
+        match std::fs::read_dir(path) {
+            Ok(children) => {
+                let mut child_count = 0;
+                let mut hidden_child_count = 0;
+                for child in children.flatten() {
+                    child_count += 1;
+                    if child.file_name().to_string_lossy().starts_with('.') {
+                        hidden_child_count += 1;
+                    }
+                }
+                Preview::Folder {
+                    child_count,
+                    hidden_child_count,
+                }
+            },
+            Err(e) => Preview::Unavailable(e.to_string()),
+        }
+    }
+    fn for_file(path: &Path, size: u64) -> Self {
+        let mut head = vec![];
+        let read_head = std::fs::File::open(path)
+            .and_then(|file| file.take(PREVIEW_BYTES).read_to_end(&mut head));
+        if let Err(e) = read_head {
+            return Preview::Unavailable(e.to_string());
+        }
+        if let Some(format) = image_format(&head) {
+            return Preview::Image { format, size };
+        }
+        if is_binary(&head) {
+            return Preview::Binary { size };
+        }
+        let lines = String::from_utf8_lossy(&head)
+            .lines()
+            .map(|l| l.replace('\t', "    "))
+            .collect();
+        let comment_prefix = path
+            .extension()
+            .and_then(|e| comment_prefix_for_extension(&e.to_string_lossy()));
+        Preview::Text {
+            lines,
+            comment_prefix,
+        }
+    }
+    pub fn render(&self, x: usize, y: usize, rows: usize, cols: usize) {
+        match self {
+            Preview::Text {
+                lines,
+                comment_prefix,
+            } => {
+                for (i, line) in lines.iter().take(rows).enumerate() {
+                    let line = truncate_to_width(line, cols);
+                    print_text_with_coordinates(
+                        highlight_line(&line, *comment_prefix),
+                        x,
+                        y + i,
+                        Some(cols),
+                        None,
+                    );
+                }
+            },
+            Preview::Folder {
+                child_count,
+                hidden_child_count,
+            } => {
+                let items = if *child_count == 1 { "item" } else { "items" };
+                let text = if *hidden_child_count > 0 {
+                    format!("{} {} ({} hidden)", child_count, items, hidden_child_count)
+                } else {
+                    format!("{} {}", child_count, items)
+                };
+                let count_len = child_count.to_string().chars().count();
+                let text = Text::new(text).color_range(0, ..count_len);
+                print_text_with_coordinates(text, x, y, Some(cols), None);
+            },
+            Preview::Image { format, size } => {
+                let text = format!("{} image, {}", format, pretty_bytes(*size as f64));
+                let text = Text::new(text).color_range(2, ..format.len());
+                print_text_with_coordinates(text, x, y, Some(cols), None);
+            },
+            Preview::Binary { size } => {
+                let text = format!("Binary file, {}", pretty_bytes(*size as f64));
+                let text = Text::new(text).color_range(2, ..11);
+                print_text_with_coordinates(text, x, y, Some(cols), None);
+            },
+            Preview::Unavailable(error) => {
+                let text = Text::new(format!("No preview: {}", error)).error_color_range(..11);
+                print_text_with_coordinates(text, x, y, Some(cols), None);
+            },
+        }
+    }
+}
+
+fn image_format(head: &[u8]) -> Option<&'static str> {
+    if head.starts_with(b"\x89PNG\r\n\x1a\n") {
+        Some("PNG")
+    } else if head.starts_with(b"\xff\xd8\xff") {
+        Some("JPEG")
+    } else if head.starts_with(b"GIF87a") || head.starts_with(b"GIF89a") {
+        Some("GIF")
+    } else if head.starts_with(b"RIFF") && head.get(8..12) == Some(b"WEBP") {
+        Some("WebP")
+    } else if head.starts_with(b"BM") && head.len() > 14 && head[6..10] == [0, 0, 0, 0] {
+        Some("BMP")
+    } else if head.starts_with(b"\0\0\x01\0") {
+        Some("ICO")
+    } else {
+        None
+    }
+}
+
+fn is_binary(head: &[u8]) -> bool {
+    if head.contains(&0) {
+        return true;
+    }
+    match std::str::from_utf8(head) {
+        Ok(_) => false,
+        // the head might end in the middle of a multi-byte character
+        Err(e) => e.error_len().is_some(),
+    }
+}
+
+fn comment_prefix_for_extension(extension: &str) -> Option<&'static str> {
+    match extension {
+        "rs" | "c" | "h" | "cc" | "cpp" | "hpp" | "cs" | "go" | "java" | "js" | "jsx" | "ts"
+        | "tsx" | "kt" | "swift" | "zig" | "kdl" | "scala" | "dart" | "proto" => Some("//"),
+        "py" | "sh" | "bash" | "zsh" | "fish" | "rb" | "pl" | "toml" | "yaml" | "yml" | "nix"
+        | "conf" | "ini" | "r" | "ex" | "exs" | "cmake" => Some("#"),
+        "lua" | "sql" | "hs" | "elm" => Some("--"),
+        "lisp" | "el" | "clj" | "scm" => Some(";"),
+        _ => None,
+    }
+}
+
+/// Colors comments, strings, numbers and common keywords, without any knowledge of the language
+/// beyond how its comments start
+fn highlight_line(line: &str, comment_prefix: Option<&str>) -> Text {
+    let chars: Vec<char> = line.chars().collect();
+    let byte_offsets: Vec<usize> = line.char_indices().map(|(offset, _)| offset).collect();
+    let mut string_indices = vec![];
+    let mut keyword_indices = vec![];
+    let mut number_indices = vec![];
+    let mut comment_start = None;
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if let Some(comment_prefix) = comment_prefix {
+            if line[byte_offsets[i]..].starts_with(comment_prefix) {
+                comment_start = Some(i);
+                break;
+            }
+        }
+        let is_string_start = match c {
+            '"' | '`' => true,
+            // could also be a rust lifetime, which isn't closed
+            '\'' => chars[i + 1..].contains(&'\''),
+            _ => false,
+        };
+        if is_string_start {
+            let string_start = i;
+            i += 1;
+            while i < chars.len() && chars[i] != c {
+                if chars[i] == '\\' {
+                    i += 1;
+                }
+                i += 1;
+            }
+            let string_end = std::cmp::min(i + 1, chars.len());
+            string_indices.extend(string_start..string_end);
+            i = string_end;
+        } else if c.is_alphanumeric() || c == '_' {
+            let word_start = i;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            let word: String = chars[word_start..i].iter().collect();
+            if word.chars().all(|c| c.is_ascii_digit()) {
+                number_indices.extend(word_start..i);
+            } else if KEYWORDS.contains(&word.as_str()) {
+                keyword_indices.extend(word_start..i);
+            }
+        } else {
+            i += 1;
+        }
+    }
+    let mut text = Text::new(line)
+        .color_indices(2, string_indices)
+        .color_indices(0, keyword_indices)
+        .color_indices(1, number_indices);
+    if let Some(comment_start) = comment_start {
+        text = text.dim_range(comment_start..);
+    }
+    text
+}
+
+fn truncate_to_width(line: &str, cols: usize) -> String {
+    let mut truncated = String::new();
+    let mut width = 0;
+    for c in line.chars() {
+        width += c.width().unwrap_or(0);
+        if width > cols {
+            break;
+        }
+        truncated.push(c);
+    }
+    truncated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detect_images_and_binaries() {
+        assert_eq!(image_format(b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR"), Some("PNG"));
+        assert_eq!(image_format(b"GIF89a\x01\0\x01\0"), Some("GIF"));
+        assert_eq!(image_format(b"fn main() {}"), None);
+        assert!(is_binary(b"\x7fELF\x02\x01\x01\0\0\0"));
+        assert!(!is_binary("fn main() { println!(\"héllo\"); }".as_bytes()));
+        // a multi-byte character cut off by the end of the preview is still text
+        assert!(!is_binary(&"héllo".as_bytes()[..2]));
+    }
+}
@@ -1,9 +1,12 @@
 use crate::file_list_view::FsEntry;
+use crate::git_status::GitStatus;
 use crate::platform::Platform;
 use crate::shared::{calculate_list_bounds, render_list_tip};
 use fuzzy_matcher::skim::SkimMatcherV2;
 use fuzzy_matcher::FuzzyMatcher;
 use pretty_bytes::converter::convert as pretty_bytes;
+use std::collections::BTreeSet;
+use std::path::PathBuf;
 use unicode_width::UnicodeWidthStr;
 use zellij_tile::prelude::*;
 
@@ -52,16 +55,25 @@ impl SearchView {
             .get(self.selected_search_result)
             .map(|s| s.entry.clone())
     }
-    pub fn render(&mut self, rows: usize, cols: usize) {
+    pub fn render(
+        &mut self,
+        rows: usize,
+        cols: usize,
+        git_status: &GitStatus,
+        marked: &BTreeSet<PathBuf>,
+    ) {
         let (start_index, selected_index_in_range, end_index) = calculate_list_bounds(
             self.search_results.len(),
             rows.saturating_sub(1),
             Some(self.selected_search_result),
         );
         render_list_tip(3, cols);
+        let gutter_width = git_status.gutter_width();
+        let cols = cols.saturating_sub(gutter_width);
         for i in start_index..end_index {
             if let Some(search_result) = self.search_results.get(i) {
                 let is_selected = Some(i) == selected_index_in_range;
+                let git_marker = git_status.marker_for(&search_result.name());
                 let mut search_result_text = search_result.name();
                 let size = search_result
                     .size()
@@ -84,20 +96,31 @@ impl SearchView {
                     let padding = " ".repeat(cols.saturating_sub(search_result_text_width));
                     format!("{}{}", search_result_text, padding)
                 };
+                let text = format!("{}{}", git_status.gutter(git_marker), text);
                 let mut text_element = if is_selected {
                     Text::new(text).selected()
                 } else {
                     Text::new(text)
                 };
-                if search_result.is_folder() {
-                    text_element = text_element.color_range(0, ..);
+                if marked.contains(&search_result.entry.get_full_pathbuf()) {
+                    text_element = text_element.color_range(1, gutter_width..);
+                } else if search_result.is_folder() {
+                    text_element = text_element.color_range(0, gutter_width..);
+                }
+                if let Some(git_marker) = git_marker {
+                    text_element = git_marker.style(text_element, 0..1);
                 }
-                text_element = text_element.color_indices(3, search_result.indices());
+                let match_indices = search_result
+                    .indices()
+                    .iter()
+                    .map(|i| i + gutter_width)
+                    .collect();
+                text_element = text_element.color_indices(3, match_indices);
                 print_text_with_coordinates(
                     text_element,
                     0,
                     i.saturating_sub(start_index) + 4,
-                    Some(cols),
+                    Some(cols + gutter_width),
                     None,
                 );
             }
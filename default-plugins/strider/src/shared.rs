@@ -4,7 +4,15 @@ use unicode_width::UnicodeWidthStr;
 use zellij_tile::prelude::*;
 
 pub fn render_instruction_line(y: usize, max_cols: usize) {
-    if max_cols > 78 {
+    if max_cols > 98 {
+        let text = "Help: go back with <Ctrl c>, go to root with /, <Ctrl e> - toggle hidden files, <Ctrl v> - preview";
+        let text = Text::new(text)
+            .color_range(3, 19..27)
+            .color_range(3, 45..46)
+            .color_range(3, 48..56)
+            .color_range(3, 80..88);
+        print_text_with_coordinates(text, 0, y, Some(max_cols), None);
+    } else if max_cols > 78 {
         let text = "Help: go back with <Ctrl c>, go to root with /, <Ctrl e> - toggle hidden files";
         let text = Text::new(text)
             .color_range(3, 19..27)
@@ -25,6 +33,23 @@ pub fn render_instruction_line(y: usize, max_cols: usize) {
     }
 }
 
+pub fn render_file_operations_line(y: usize, max_cols: usize) {
+    if max_cols > 100 {
+        let text = "<Ctrl a> - new, <Ctrl r> - rename, <Ctrl w> - mark, <Ctrl y/x/u> - copy/cut/paste, <Ctrl d> - delete";
+        let text = Text::new(text)
+            .color_range(3, ..8)
+            .color_range(3, 16..24)
+            .color_range(3, 35..43)
+            .color_range(3, 52..64)
+            .color_range(3, 83..91);
+        print_text_with_coordinates(text, 0, y, Some(max_cols), None);
+    } else if max_cols > 70 {
+        let text = "<Ctrl a/r/d> - new/rename/delete, <Ctrl w/y/x/u> - mark/copy/cut/paste";
+        let text = Text::new(text).color_range(3, ..12).color_range(3, 34..48);
+        print_text_with_coordinates(text, 0, y, Some(max_cols), None);
+    }
+}
+
 pub fn render_list_tip(y: usize, max_cols: usize) {
     let tip = Text::new(format!("(<↓↑> - Navigate, <TAB> - Select)"))
         .color_range(3, 1..5)
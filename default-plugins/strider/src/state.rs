@@ -1,9 +1,15 @@
 use crate::file_list_view::{FileListView, FsEntry};
+use crate::file_operations::{
+    create_entry, handle_trash_result, rename_entry, trash_paths, ClipboardMode, PasteOperation,
+    Prompt,
+};
+use crate::git_status::GitStatus;
 use crate::platform::Platform;
+use crate::preview::Preview;
 use crate::search_view::SearchView;
 use crate::shared::{calculate_list_bounds, render_list_tip};
 use std::{
-    collections::BTreeMap,
+    collections::{BTreeMap, BTreeSet},
     path::{Path, PathBuf},
 };
 use unicode_width::UnicodeWidthStr;
@@ -24,8 +30,20 @@ pub struct State {
     pub is_in_virtual_root: bool,
     pub virtual_root_entries: Vec<FsEntry>,
     pub virtual_root_selected: usize,
+    pub show_preview: bool,
+    pub preview: Option<(PathBuf, Preview)>, // cached preview of the selected entry
+    pub git_status: GitStatus,
+    pub prompt: Option<Prompt>,
+    pub marked: BTreeSet<PathBuf>,
+    pub clipboard: Option<(ClipboardMode, Vec<PathBuf>)>,
+    pub pending_paste: Option<(PasteOperation, usize)>, // operation, how many entries it pastes
+    pub pending_trash: Vec<PathBuf>,
+    pub status_message: Option<Result<String, String>>,
 }
 
+// below this width the file list would get too cramped to show a preview next to it
+const MIN_COLS_FOR_PREVIEW: usize = 60;
+
 impl State {
     pub fn update_search_term(&mut self, character: char) {
         if self.is_in_virtual_root {
@@ -196,6 +214,39 @@ impl State {
     pub fn toggle_hidden_files(&mut self) {
         self.hide_hidden_files = !self.hide_hidden_files;
     }
+    pub fn toggle_preview(&mut self) {
+        self.show_preview = !self.show_preview;
+    }
+    /// The width of the file list and of the preview next to it, if there's room to show it
+    pub fn list_and_preview_cols(&self, cols: usize) -> (usize, Option<usize>) {
+        if self.show_preview && !self.is_in_virtual_root && cols >= MIN_COLS_FOR_PREVIEW {
+            let list_cols = cols / 2;
+            let gap = 2;
+            (list_cols, Some(cols.saturating_sub(list_cols + gap)))
+        } else {
+            (cols, None)
+        }
+    }
+    pub fn selected_entry(&self) -> Option<FsEntry> {
+        if self.is_searching {
+            self.search_view.get_selected_entry()
+        } else {
+            self.file_list_view.get_selected_entry()
+        }
+    }
+    pub fn render_preview(&mut self, x: usize, y: usize, rows: usize, cols: usize) {
+        let Some(entry) = self.selected_entry() else {
+            return;
+        };
+        let path = entry.get_full_pathbuf();
+        let cached = matches!(&self.preview, Some((preview_path, _)) if preview_path == &path);
+        if !cached {
+            self.preview = Some((path, Preview::for_entry(&entry)));
+        }
+        if let Some((_, preview)) = &self.preview {
+            preview.render(x, y, rows, cols);
+        }
+    }
     pub fn traverse_dir(&mut self) {
         if self.is_in_virtual_root {
             if let Some(entry) = self.virtual_root_entries.get(self.virtual_root_selected) {
@@ -240,12 +291,238 @@ impl State {
         self.search_term.clear();
         self.search_view.clear_and_reset_selection();
     }
+    pub fn scan_current_folder(&mut self) {
+        scan_host_folder(&"/host");
+    }
+    /// Rescans the current folder if the filesystem watcher reports a change in it
+    pub fn handle_filesystem_change(&mut self, paths: Vec<(PathBuf, Option<FileMetadata>)>) {
+        if self.changes_current_folder(&paths) {
+            self.scan_current_folder();
+        }
+    }
+    fn changes_current_folder(&self, paths: &[(PathBuf, Option<FileMetadata>)]) -> bool {
+        if self.is_in_virtual_root {
+            return false;
+        }
+        paths.iter().any(|(path, _)| {
+            // the watcher reports paths in the session folder relative to /host
+            let path = match path.strip_prefix("/host") {
+                Ok(relative_path) => self.initial_cwd.join(relative_path),
+                Err(_) => Platform::normalize(path),
+            };
+            path.parent() == Some(self.file_list_view.path.as_path())
+        })
+    }
     pub fn update_files(&mut self, paths: Vec<(PathBuf, Option<FileMetadata>)>) {
         if self.is_in_virtual_root {
             self.update_virtual_root_entries(paths);
         } else {
             self.file_list_view
                 .update_files(paths, self.hide_hidden_files);
+            // the files might have changed since they were previewed
+            self.preview = None;
+            self.git_status.refresh(&self.file_list_view.path);
+        }
+    }
+    pub fn start_new_entry_prompt(&mut self) {
+        if self.is_in_virtual_root || !self.file_list_view.path_is_dir {
+            return;
+        }
+        self.prompt = Some(Prompt::New {
+            input: String::new(),
+        });
+    }
+    pub fn start_rename_prompt(&mut self) {
+        if self.is_in_virtual_root {
+            return;
+        }
+        if let Some(entry) = self.selected_entry() {
+            self.prompt = Some(Prompt::Rename {
+                original: entry.get_full_pathbuf(),
+                input: entry.name(),
+            });
+        }
+    }
+    pub fn start_trash_prompt(&mut self) {
+        let paths = self.marked_or_selected_paths();
+        if !paths.is_empty() {
+            self.prompt = Some(Prompt::ConfirmTrash { paths });
+        }
+    }
+    pub fn handle_prompt_key(&mut self, key: KeyWithModifier) {
+        match key.bare_key {
+            BareKey::Enter if key.has_no_modifiers() => self.confirm_prompt(),
+            BareKey::Esc if key.has_no_modifiers() => self.prompt = None,
+            BareKey::Char('c') if key.has_modifiers(&[KeyModifier::Ctrl]) => self.prompt = None,
+            BareKey::Backspace if key.has_no_modifiers() => {
+                if let Some(prompt) = self.prompt.as_mut() {
+                    prompt.handle_backspace();
+                }
+            },
+            BareKey::Char(character)
+                if key.has_no_modifiers() || key.has_modifiers(&[KeyModifier::Shift]) =>
+            {
+                if let Some(prompt) = self.prompt.as_mut() {
+                    prompt.handle_char(character);
+                }
+            },
+            _ => {},
+        }
+    }
+    pub fn confirm_prompt(&mut self) {
+        let Some(prompt) = self.prompt.take() else {
+            return;
+        };
+        let result = match prompt {
+            Prompt::New { input } => {
+                create_entry(&input).map(|_| format!("Created {}", input.trim()))
+            },
+            Prompt::Rename { original, input } => {
+                let original_name = original
+                    .file_name()
+                    .map(|f| f.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                rename_entry(&original_name, &input)
+                    .map(|_| format!("Renamed {} to {}", original_name, input.trim()))
+            },
+            Prompt::ConfirmTrash { paths } => {
+                trash_paths(&paths, &self.file_list_view.path, self.platform);
+                self.pending_trash = paths;
+                return;
+            },
+        };
+        if result.is_ok() {
+            self.scan_current_folder();
+        }
+        self.status_message = Some(result);
+    }
+    /// Marks the selected entry for the next copy, cut or delete, or unmarks it
+    pub fn toggle_mark(&mut self) {
+        if self.is_in_virtual_root {
+            return;
+        }
+        if let Some(entry) = self.selected_entry() {
+            let path = entry.get_full_pathbuf();
+            if !self.marked.remove(&path) {
+                self.marked.insert(path);
+            }
+        }
+    }
+    fn marked_or_selected_paths(&self) -> Vec<PathBuf> {
+        if self.is_in_virtual_root {
+            vec![]
+        } else if self.marked.is_empty() {
+            self.selected_entry()
+                .map(|e| vec![e.get_full_pathbuf()])
+                .unwrap_or_default()
+        } else {
+            self.marked.iter().cloned().collect()
+        }
+    }
+    pub fn copy_to_clipboard(&mut self, mode: ClipboardMode) {
+        let paths = self.marked_or_selected_paths();
+        if paths.is_empty() {
+            return;
+        }
+        let verb = match mode {
+            ClipboardMode::Copy => "Copied",
+            ClipboardMode::Cut => "Cut",
+        };
+        let items = if paths.len() == 1 { "item" } else { "items" };
+        self.status_message = Some(Ok(format!(
+            "{} {} {}, paste with <Ctrl u>",
+            verb,
+            paths.len(),
+            items
+        )));
+        self.clipboard = Some((mode, paths));
+        self.marked.clear();
+    }
+    /// Pastes the clipboard into the current folder, the outcome is reported once the paste
+    /// command is done (see `handle_command_result`)
+    pub fn paste(&mut self) {
+        if self.is_in_virtual_root
+            || !self.file_list_view.path_is_dir
+            || self.pending_paste.is_some()
+        {
+            return;
+        }
+        let Some((mode, sources)) = self.clipboard.clone() else {
+            return;
+        };
+        let paste_operation = PasteOperation {
+            mode,
+            sources,
+            destination: self.file_list_view.path.clone(),
+        };
+        match paste_operation.start(self.platform) {
+            Ok(0) => {
+                self.status_message = Some(Ok("Nothing to paste".to_owned()));
+            },
+            Ok(pasting) => {
+                self.pending_paste = Some((paste_operation, pasting));
+            },
+            Err(e) => {
+                self.status_message = Some(Err(e));
+            },
+        }
+    }
+    /// Returns true if this was the result of pasting or of moving entries to the trash
+    pub fn handle_command_result(
+        &mut self,
+        exit_code: Option<i32>,
+        stderr: &[u8],
+        context: &BTreeMap<String, String>,
+    ) -> bool {
+        if let Some(result) = PasteOperation::handle_result(exit_code, stderr, context) {
+            if let Some((paste_operation, pasted)) = self.pending_paste.take() {
+                if result.is_ok() && paste_operation.mode == ClipboardMode::Cut {
+                    // the cut entries are no longer where the clipboard says they are
+                    self.clipboard = None;
+                }
+                self.status_message = Some(result.map(|_| {
+                    let items = if pasted == 1 { "item" } else { "items" };
+                    format!("Pasted {} {}", pasted, items)
+                }));
+                self.scan_current_folder();
+            }
+            return true;
+        }
+        if self.pending_trash.is_empty() {
+            return false;
+        }
+        let Some(result) = handle_trash_result(
+            &self.pending_trash,
+            &self.file_list_view.path,
+            self.platform,
+            exit_code,
+            stderr,
+            context,
+        ) else {
+            return false;
+        };
+        let trashed = std::mem::take(&mut self.pending_trash);
+        self.status_message = Some(result.map(|_| {
+            let items = if trashed.len() == 1 { "item" } else { "items" };
+            format!("Moved {} {} to the trash", trashed.len(), items)
+        }));
+        for path in &trashed {
+            self.marked.remove(path);
+        }
+        self.scan_current_folder();
+        true
+    }
+    pub fn render_status_message(&self, cols: usize) {
+        match &self.status_message {
+            Some(Ok(message)) => {
+                let text = Text::new(message).success_color_range(..);
+                print_text_with_coordinates(text, 0, 2, Some(cols), None);
+            },
+            Some(Err(error)) => {
+                let text = Text::new(error).error_color_range(..);
+                print_text_with_coordinates(text, 0, 2, Some(cols), None);
+            },
+            None => {},
         }
     }
     pub fn open_selected_path(&mut self) {
@@ -378,3 +655,71 @@ impl State {
 pub(crate) fn refresh_directory(full_path: &Path) {
     change_host_folder(PathBuf::from(full_path));
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state_in_folder(folder: &str) -> State {
+        let mut state = State::default();
+        state.initial_cwd = PathBuf::from(folder);
+        state.file_list_view.path = PathBuf::from(folder);
+        state.file_list_view.path_is_dir = true;
+        state
+    }
+
+    fn file_metadata() -> Option<FileMetadata> {
+        Some(FileMetadata {
+            is_file: true,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn empty_watcher_reports_do_not_change_the_file_list() {
+        let mut state = state_in_folder("/project");
+        state.file_list_view.update_files(
+            vec![(PathBuf::from("/host/notes.txt"), file_metadata())],
+            false,
+        );
+        assert!(!state.changes_current_folder(&[]));
+        assert_eq!(state.file_list_view.files.len(), 1);
+    }
+
+    #[test]
+    fn only_watcher_reports_in_the_current_folder_are_changes() {
+        let state = state_in_folder("/project");
+        assert!(state.changes_current_folder(&[(PathBuf::from("/host/notes.txt"), None)]));
+        assert!(!state.changes_current_folder(&[(PathBuf::from("/host/src/main.rs"), None)]));
+    }
+
+    #[test]
+    fn empty_folder_scan_clears_the_file_list() {
+        let mut state = state_in_folder("/project");
+        state.file_list_view.update_files(
+            vec![(PathBuf::from("/host/notes.txt"), file_metadata())],
+            false,
+        );
+        state.file_list_view.update_files(vec![], false);
+        assert!(state.file_list_view.files.is_empty());
+    }
+
+    #[test]
+    fn scanned_entries_whose_metadata_could_not_be_read_are_listed() {
+        let mut state = state_in_folder("/project");
+        state.file_list_view.update_files(
+            vec![
+                (PathBuf::from("/host/notes.txt"), file_metadata()),
+                (PathBuf::from("/host/unreadable.txt"), None),
+            ],
+            false,
+        );
+        assert_eq!(
+            state.file_list_view.files,
+            vec![
+                FsEntry::File(PathBuf::from("/project/notes.txt"), 0),
+                FsEntry::File(PathBuf::from("/project/unreadable.txt"), 0),
+            ]
+        );
+    }
+}
@@ -1,4 +1,5 @@
 use dialoguer::Confirm;
+use std::collections::{BTreeMap, BTreeSet};
 use std::net::IpAddr;
 use std::{fs::File, io::prelude::*, path::PathBuf, process, time::Duration};
 
@@ -40,11 +41,14 @@ use zellij_utils::{
     cli::{CliArgs, Command, SessionCommand, Sessions},
     data::ConnectToSession,
     envs,
+    home::{find_default_config_dir, get_default_data_dir, get_layout_dir},
     input::{
         actions::Action,
         config::{Config, ConfigError},
+        layout::{Layout, RunPlugin, RunPluginLocation},
         options::Options,
     },
+    plugin_bundle::PluginBundle,
     setup::Setup,
 };
 
@@ -781,6 +785,7 @@ pub(crate) fn start_client(opts: CliArgs) {
                     ca_cert,
                     insecure,
                     config_options.client_async_worker_tasks,
+                    config_options.local_echo.unwrap_or_default(),
                 ) {
                     eprintln!("{}", e);
                     std::process::exit(2);
@@ -993,6 +998,115 @@ pub(crate) fn list_aliases(opts: CliArgs) {
     process::exit(0);
 }
 
+pub(crate) fn vendor_plugins(opts: &CliArgs, output: Option<PathBuf>) {
+    let (config, layout, config_options, _, _) = match Setup::from_cli_args(opts) {
+        Ok(results) => results,
+        Err(e) => {
+            if let ConfigError::KdlError(error) = e {
+                let report: Report = error.into();
+                eprintln!("{:?}", report);
+            } else {
+                eprintln!("{}", e);
+            }
+            process::exit(1);
+        },
+    };
+    let bundle_dir = output
+        .or_else(|| config_options.plugin_bundle_dir.clone())
+        .unwrap_or_else(|| {
+            opts.data_dir
+                .clone()
+                .unwrap_or_else(get_default_data_dir)
+                .join("plugin-bundle")
+        });
+
+    // the configuration (including its plugin aliases and background plugins), the default
+    // layout and every layout in the layout folder
+    let mut run_plugins: Vec<RunPlugin> = config.plugins.aliases.values().cloned().collect();
+    run_plugins.extend(
+        config
+            .background_plugins
+            .iter()
+            .filter_map(|p| p.get_run_plugin()),
+    );
+    let layout_dir = config_options
+        .layout_dir
+        .clone()
+        .or_else(|| get_layout_dir(opts.config_dir.clone().or_else(find_default_config_dir)));
+    let layout_vars: BTreeMap<String, String> = opts.layout_vars.iter().cloned().collect();
+    if let Some(mut layout) =
+        layout.and_then(|l| Layout::from_layout_info(&layout_dir, l, &layout_vars).ok())
+    {
+        layout.populate_plugin_aliases_in_layout(&config.plugins);
+        run_plugins.append(&mut layout.run_plugins());
+    }
+    let layout_files = layout_dir
+        .as_ref()
+        .and_then(|layout_dir| std::fs::read_dir(layout_dir).ok())
+        .map(|entries| entries.filter_map(|e| e.ok()).map(|e| e.path()).collect())
+        .unwrap_or_else(Vec::new);
+    for layout_file in layout_files {
+        if layout_file.extension().and_then(|e| e.to_str()) != Some("kdl") {
+            continue;
+        }
+        // layouts other than the chosen one are bundled with the defaults of their params
+        match Layout::from_path_or_default(
+            Some(&layout_file),
+            layout_dir.clone(),
+            config.clone(),
+            &BTreeMap::new(),
+        ) {
+            Ok((mut layout, layout_config)) => {
+                layout.populate_plugin_aliases_in_layout(&layout_config.plugins);
+                run_plugins.append(&mut layout.run_plugins());
+                run_plugins.extend(layout_config.plugins.aliases.values().cloned());
+            },
+            Err(e) => {
+                eprintln!("Skipping layout {}: {}", layout_file.display(), e);
+            },
+        }
+    }
+    let urls: BTreeSet<String> = run_plugins
+        .into_iter()
+        .filter_map(|run_plugin| match run_plugin.location {
+            RunPluginLocation::Remote(url) => Some(url),
+            _ => None,
+        })
+        .collect();
+
+    if let Err(e) = std::fs::create_dir_all(&bundle_dir) {
+        eprintln!("Failed to create {}: {}", bundle_dir.display(), e);
+        process::exit(1);
+    }
+    let mut plugin_bundle = match PluginBundle::from_dir(&bundle_dir) {
+        Ok(plugin_bundle) => plugin_bundle,
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(1);
+        },
+    };
+    let mut failed = false;
+    for url in &urls {
+        match plugin_bundle.vendor_blocking(url) {
+            Ok(()) => println!("Vendored {}", url),
+            Err(e) => {
+                eprintln!("Failed to vendor {}: {}", url, e);
+                failed = true;
+            },
+        }
+    }
+    if let Err(e) = plugin_bundle.write_lockfile() {
+        eprintln!("Failed to write the plugin bundle lockfile: {}", e);
+        process::exit(1);
+    }
+    println!(
+        "Vendored {} plugin(s) to {}",
+        plugin_bundle.plugins().len(),
+        bundle_dir.display()
+    );
+    process::exit(if failed { 1 } else { 0 });
+}
+
 pub(crate) fn watch_session(session_name: Option<String>, opts: CliArgs) {
     let (config, _, config_options, _, _) = match Setup::from_cli_args(&opts) {
         Ok(results) => results,
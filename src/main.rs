@@ -4,7 +4,7 @@ mod tests;
 
 use clap::Parser;
 use zellij_utils::{
-    cli::{CliAction, CliArgs, Command, Sessions},
+    cli::{CliAction, CliArgs, Command, PluginSubcommand, Sessions},
     consts::{create_config_and_cache_folders, VERSION},
     data::UnblockCondition,
     envs,
@@ -100,7 +100,15 @@ fn main() {
             std::process::exit(0);
         }
         if let Some(Command::Sessions(Sessions::Plugin {
-            url,
+            subcommand: Some(PluginSubcommand::Vendor { output }),
+            ..
+        })) = &opts.command
+        {
+            commands::vendor_plugins(&opts, output.clone());
+        }
+        if let Some(Command::Sessions(Sessions::Plugin {
+            subcommand: None,
+            url: Some(url),
             floating,
             in_place,
             close_replaced_pane,
@@ -275,6 +283,7 @@ fn main() {
                 layout: opts.layout.clone(),
                 layout_string: opts.layout_string.clone(),
                 layout_dir: options.as_ref().and_then(|o| o.layout_dir.clone()),
+                layout_vars: opts.layout_vars.clone(),
                 name: None,
                 cwd: options.as_ref().and_then(|o| o.default_cwd.clone()),
                 initial_command: vec![],
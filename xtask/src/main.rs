@@ -86,6 +86,14 @@ fn workspace_members() -> &'static Vec<WorkspaceMember> {
                 crate_name: "default-plugins/link",
                 build: true,
             },
+            WorkspaceMember {
+                crate_name: "default-plugins/pane-search",
+                build: true,
+            },
+            WorkspaceMember {
+                crate_name: "default-plugins/closed-panes",
+                build: true,
+            },
             WorkspaceMember {
                 crate_name: "zellij-utils",
                 build: false,
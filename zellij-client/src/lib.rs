@@ -36,6 +36,8 @@ use zellij_utils::shared::web_server_base_url;
 use futures_util::{SinkExt, StreamExt};
 #[cfg(feature = "web_server_capability")]
 use tokio_tungstenite::tungstenite::Message;
+#[cfg(feature = "web_server_capability")]
+use zellij_utils::input::options::LocalEcho;
 
 #[cfg(feature = "web_server_capability")]
 use crate::web_client::control_message::{
@@ -187,6 +189,8 @@ impl From<ServerToClientMsg> for ClientInstruction {
             // Subscribe-only messages — not handled by regular interactive clients
             ServerToClientMsg::PaneRenderUpdate { .. } => ClientInstruction::UnblockInputThread,
             ServerToClientMsg::SubscribedPaneClosed { .. } => ClientInstruction::UnblockInputThread,
+            // only sent to web clients
+            ServerToClientMsg::LocalEchoState { .. } => ClientInstruction::UnblockInputThread,
         }
     }
 }
@@ -385,6 +389,13 @@ impl ClientInfo {
     pub fn set_layout_info(&mut self, new_layout_info: LayoutInfo) {
         match self {
             ClientInfo::New(_, layout_info, _) => *layout_info = Some(new_layout_info),
+            // eg. resurrecting a session from one of its snapshots rather than from its last
+            // serialized state
+            ClientInfo::Resurrect(_, path_to_layout, _, _) => {
+                if let LayoutInfo::File(layout_path, _) = new_layout_info {
+                    *path_to_layout = PathBuf::from(layout_path);
+                }
+            },
             _ => {},
         }
     }
@@ -414,8 +425,15 @@ pub(crate) enum InputInstruction {
 pub async fn run_remote_client_terminal_loop(
     os_input: Box<dyn ClientOsApi>,
     mut connections: remote_attach::WebSocketConnections,
+    local_echo: LocalEcho,
 ) -> Result<Option<ConnectToSession>, RemoteClientError> {
     use crate::os_input_output::{AsyncSignals, AsyncStdin};
+    use remote_attach::local_echo::LocalEchoPredictor;
+    use remote_attach::reconnection::{
+        disconnected_status_overlay, KeystrokeBuffer, ReconnectionBackoff, HEARTBEAT_INTERVAL_SECS,
+        HEARTBEAT_TIMEOUT_SECS,
+    };
+    use std::time::{Duration, Instant};
 
     let synchronised_output = match os_input.env_variable("TERM").as_deref() {
         Some("alacritty") => Some(SyncOutput::DCS),
@@ -427,163 +445,314 @@ pub async fn run_remote_client_terminal_loop(
         .get_async_signal_listener()
         .map_err(|e| RemoteClientError::IoError(e))?;
 
-    let create_resize_message = |size: Size| {
+    let create_resize_message = |web_client_id: &str, size: Size| {
         Message::Text(
             serde_json::to_string(&WebClientToWebServerControlMessage {
-                web_client_id: connections.web_client_id.clone(),
+                web_client_id: web_client_id.to_owned(),
                 payload: WebClientToWebServerControlMessagePayload::TerminalResize(size),
             })
             .unwrap(),
         )
     };
 
-    // send size on startup
-    let new_size = os_input.get_terminal_size();
-    if let Err(e) = connections
-        .control_ws
-        .send(create_resize_message(new_size))
-        .await
-    {
-        log::error!("Failed to send resize message: {}", e);
-    }
+    let show_status = |message: &str| {
+        let overlay = disconnected_status_overlay(message, os_input.get_terminal_size());
+        let mut stdout = os_input.get_stdout_writer();
+        stdout
+            .write_all(overlay.as_bytes())
+            .expect("cannot write to stdout");
+        stdout.flush().expect("could not flush");
+    };
 
-    loop {
-        tokio::select! {
-            // Handle stdin input
-            result = async_stdin.read() => {
-                match result {
-                    Ok(buf) if !buf.is_empty() => {
-                        if let Err(e) = connections.terminal_ws.send(Message::Binary(buf)).await {
-                            log::error!("Failed to send stdin to terminal WebSocket: {}", e);
-                            break;
+    // keystrokes typed while we were disconnected
+    let mut keystroke_buffer = KeystrokeBuffer::default();
+    let mut buffered_keystrokes = vec![];
+
+    let mut local_echo = LocalEchoPredictor::new(local_echo, os_input.get_terminal_size());
+    let write_local_echo = |overlay: String| {
+        if !overlay.is_empty() {
+            let mut stdout = os_input.get_stdout_writer();
+            stdout
+                .write_all(overlay.as_bytes())
+                .expect("cannot write to stdout");
+            stdout.flush().expect("could not flush");
+        }
+    };
+
+    'connection: loop {
+        // send size on startup and whenever we reconnect
+        let new_size = os_input.get_terminal_size();
+        if let Err(e) = connections
+            .control_ws
+            .send(create_resize_message(&connections.web_client_id, new_size))
+            .await
+        {
+            log::error!("Failed to send resize message: {}", e);
+        }
+        if !buffered_keystrokes.is_empty() {
+            if let Err(e) = connections
+                .terminal_ws
+                .send(Message::Binary(std::mem::take(&mut buffered_keystrokes)))
+                .await
+            {
+                log::error!("Failed to send buffered keystrokes: {}", e);
+            }
+        }
+
+        let mut heartbeat = tokio::time::interval(Duration::from_secs(HEARTBEAT_INTERVAL_SECS));
+        let mut last_heard_from_server = Instant::now();
+        let mut local_echo_expiry = tokio::time::interval(Duration::from_millis(50));
+
+        let connection_lost = loop {
+            tokio::select! {
+                // Handle stdin input
+                result = async_stdin.read() => {
+                    match result {
+                        Ok(buf) if !buf.is_empty() => {
+                            write_local_echo(local_echo.handle_input(&buf, Instant::now()));
+                            if let Err(e) = connections.terminal_ws.send(Message::Binary(buf.clone())).await {
+                                log::error!("Failed to send stdin to terminal WebSocket: {}", e);
+                                keystroke_buffer.push(&buf);
+                                break true;
+                            }
+                        }
+                        Ok(_) => {
+                            // Empty buffer means EOF
+                            break false;
+                        }
+                        Err(e) => {
+                            log::error!("Error reading from stdin: {}", e);
+                            break false;
                         }
-                    }
-                    Ok(_) => {
-                        // Empty buffer means EOF
-                        break;
-                    }
-                    Err(e) => {
-                        log::error!("Error reading from stdin: {}", e);
-                        break;
                     }
                 }
-            }
 
-            // Handle signals
-            Some(signal) = async_signals.recv() => {
-                match signal {
-                    crate::os_input_output::SignalEvent::Resize => {
-                        let new_size = os_input.get_terminal_size();
-                        if let Err(e) = connections.control_ws.send(create_resize_message(new_size)).await {
-                            log::error!("Failed to send resize message: {}", e);
-                            break;
+                // Handle signals
+                Some(signal) = async_signals.recv() => {
+                    match signal {
+                        crate::os_input_output::SignalEvent::Resize => {
+                            let new_size = os_input.get_terminal_size();
+                            local_echo.resize(new_size);
+                            if let Err(e) = connections.control_ws.send(create_resize_message(&connections.web_client_id, new_size)).await {
+                                log::error!("Failed to send resize message: {}", e);
+                                break true;
+                            }
+                        }
+                        crate::os_input_output::SignalEvent::Quit => {
+                            break false;
                         }
                     }
-                    crate::os_input_output::SignalEvent::Quit => {
-                        break;
+                }
+
+                // Detect connections that went silent, eg. after the laptop slept
+                _ = heartbeat.tick() => {
+                    if last_heard_from_server.elapsed() > Duration::from_secs(HEARTBEAT_TIMEOUT_SECS) {
+                        log::error!("No response from the web server in {}s", HEARTBEAT_TIMEOUT_SECS);
+                        break true;
+                    }
+                    if let Err(e) = connections.terminal_ws.send(Message::Ping(vec![])).await {
+                        log::error!("Failed to ping terminal WebSocket: {}", e);
+                        break true;
                     }
                 }
-            }
 
-            // Handle terminal messages
-            terminal_msg = connections.terminal_ws.next() => {
-                match terminal_msg {
-                    Some(Ok(Message::Text(text))) => {
-                        let mut stdout = os_input.get_stdout_writer();
-                        if let Some(sync) = synchronised_output {
+                // Roll back predictions the server did not confirm in time
+                _ = local_echo_expiry.tick(), if local_echo.has_pending_predictions() => {
+                    write_local_echo(local_echo.expire_predictions(Instant::now()));
+                }
+
+                // Handle terminal messages
+                terminal_msg = connections.terminal_ws.next() => {
+                    last_heard_from_server = Instant::now();
+                    match terminal_msg {
+                        Some(Ok(Message::Text(text))) => {
+                            let echo_overlay = local_echo.handle_server_output(text.as_bytes(), Instant::now());
+                            let mut stdout = os_input.get_stdout_writer();
+                            if let Some(sync) = synchronised_output {
+                                stdout
+                                    .write_all(sync.start_seq())
+                                    .expect("cannot write to stdout");
+                            }
                             stdout
-                                .write_all(sync.start_seq())
+                                .write_all(text.as_bytes())
                                 .expect("cannot write to stdout");
-                        }
-                        stdout
-                            .write_all(text.as_bytes())
-                            .expect("cannot write to stdout");
-                        if let Some(sync) = synchronised_output {
                             stdout
-                                .write_all(sync.end_seq())
+                                .write_all(echo_overlay.as_bytes())
                                 .expect("cannot write to stdout");
+                            if let Some(sync) = synchronised_output {
+                                stdout
+                                    .write_all(sync.end_seq())
+                                    .expect("cannot write to stdout");
+                            }
+                            stdout.flush().expect("could not flush");
                         }
-                        stdout.flush().expect("could not flush");
-                    }
-                    Some(Ok(Message::Binary(data))) => {
-                        let mut stdout = os_input.get_stdout_writer();
-                        if let Some(sync) = synchronised_output {
+                        Some(Ok(Message::Binary(data))) => {
+                            let echo_overlay = local_echo.handle_server_output(&data, Instant::now());
+                            let mut stdout = os_input.get_stdout_writer();
+                            if let Some(sync) = synchronised_output {
+                                stdout
+                                    .write_all(sync.start_seq())
+                                    .expect("cannot write to stdout");
+                            }
                             stdout
-                                .write_all(sync.start_seq())
+                                .write_all(&data)
                                 .expect("cannot write to stdout");
-                        }
-                        stdout
-                            .write_all(&data)
-                            .expect("cannot write to stdout");
-                        if let Some(sync) = synchronised_output {
                             stdout
-                                .write_all(sync.end_seq())
+                                .write_all(echo_overlay.as_bytes())
                                 .expect("cannot write to stdout");
+                            if let Some(sync) = synchronised_output {
+                                stdout
+                                    .write_all(sync.end_seq())
+                                    .expect("cannot write to stdout");
+                            }
+                            stdout.flush().expect("could not flush");
                         }
-                        stdout.flush().expect("could not flush");
-                    }
-                    Some(Ok(Message::Close(_))) => {
-                        break;
-                    }
-                    Some(Err(e)) => {
-                        log::error!("Error: {}", e);
-                        break;
-                    }
-                    None => {
-                        log::error!("Received empty message from web server");
-                        break;
+                        Some(Ok(Message::Close(_))) => {
+                            break false;
+                        }
+                        Some(Err(e)) => {
+                            log::error!("Error: {}", e);
+                            break true;
+                        }
+                        None => {
+                            log::error!("Received empty message from web server");
+                            break true;
+                        }
+                        _ => {}
                     }
-                    _ => {}
                 }
-            }
 
-            control_msg = connections.control_ws.next() => {
-                match control_msg {
-                    Some(Ok(Message::Text(msg))) => {
-                        let deserialized_msg: Result<WebServerToWebClientControlMessage, _> =
-                            serde_json::from_str(&msg);
-                        match deserialized_msg {
-                            Ok(WebServerToWebClientControlMessage::SetConfig(..)) => {
-                                // no-op
-                            }
-                            Ok(WebServerToWebClientControlMessage::QueryTerminalSize) => {
-                                let new_size = os_input.get_terminal_size();
-                                if let Err(e) = connections.control_ws.send(create_resize_message(new_size)).await {
-                                    log::error!("Failed to send resize message: {}", e);
+                control_msg = connections.control_ws.next() => {
+                    last_heard_from_server = Instant::now();
+                    match control_msg {
+                        Some(Ok(Message::Text(msg))) => {
+                            let deserialized_msg: Result<WebServerToWebClientControlMessage, _> =
+                                serde_json::from_str(&msg);
+                            match deserialized_msg {
+                                Ok(WebServerToWebClientControlMessage::SetConfig(..)) => {
+                                    // no-op
                                 }
-                            }
-                            Ok(WebServerToWebClientControlMessage::Log { lines }) => {
-                                for line in lines {
-                                    log::info!("{}", line);
+                                Ok(WebServerToWebClientControlMessage::QueryTerminalSize) => {
+                                    let new_size = os_input.get_terminal_size();
+                                    if let Err(e) = connections.control_ws.send(create_resize_message(&connections.web_client_id, new_size)).await {
+                                        log::error!("Failed to send resize message: {}", e);
+                                    }
+                                }
+                                Ok(WebServerToWebClientControlMessage::Log { lines }) => {
+                                    for line in lines {
+                                        log::info!("{}", line);
+                                    }
+                                }
+                                Ok(WebServerToWebClientControlMessage::LogError { lines }) => {
+                                    for line in lines {
+                                        log::error!("{}", line);
+                                    }
+                                }
+                                Ok(WebServerToWebClientControlMessage::SwitchedSession{ .. }) => {
+                                    // no-op
+                                }
+                                Ok(WebServerToWebClientControlMessage::LocalEchoState { predictable, first_column, last_column }) => {
+                                    write_local_echo(local_echo.set_state(predictable, first_column, last_column));
+                                }
+                                Err(e) => {
+                                    log::error!("Failed to deserialize control message: {}", e);
                                 }
                             }
-                            Ok(WebServerToWebClientControlMessage::LogError { lines }) => {
-                                for line in lines {
-                                    log::error!("{}", line);
+
+                        }
+                        Some(Ok(Message::Close(_))) => {
+                            break false;
+                        }
+                        Some(Err(e)) => {
+                            log::error!("{}", e);
+                            break true;
+                        }
+                        None => break true,
+                        _ => {}
+                    }
+                }
+
+            }
+        };
+
+        if !connection_lost {
+            // let the server know right away, so that it does not keep our client around for
+            // a reconnection that will not come
+            let _ = connections.terminal_ws.close(None).await;
+            break;
+        }
+
+        keystroke_buffer.disconnected(Instant::now());
+        let previous_web_client_id = connections.web_client_id.clone();
+        let mut backoff = ReconnectionBackoff::default();
+        connections = loop {
+            let Some(delay) = backoff.next_delay() else {
+                return Err(RemoteClientError::ConnectionFailed(format!(
+                    "could not reconnect after {} attempts",
+                    backoff.attempt()
+                )));
+            };
+            show_status(&format!(
+                "Connection lost, reconnecting in {}s (attempt {})...",
+                delay.as_secs(),
+                backoff.attempt()
+            ));
+            let retry = tokio::time::sleep(delay);
+            tokio::pin!(retry);
+            loop {
+                tokio::select! {
+                    _ = &mut retry => break,
+                    result = async_stdin.read() => {
+                        match result {
+                            Ok(buf) if !buf.is_empty() => {
+                                if !keystroke_buffer.push(&buf) {
+                                    log::error!("Dropping keystrokes typed while disconnected");
                                 }
                             }
-                            Ok(WebServerToWebClientControlMessage::SwitchedSession{ .. }) => {
-                                // no-op
+                            _ => break 'connection,
+                        }
+                    }
+                    Some(signal) = async_signals.recv() => {
+                        match signal {
+                            crate::os_input_output::SignalEvent::Resize => {
+                                // we send our size once we reconnect
                             }
-                            Err(e) => {
-                                log::error!("Failed to deserialize control message: {}", e);
+                            crate::os_input_output::SignalEvent::Quit => {
+                                break 'connection;
                             }
                         }
-
-                    }
-                    Some(Ok(Message::Close(_))) => {
-                        break;
-                    }
-                    Some(Err(e)) => {
-                        log::error!("{}", e);
-                        break;
                     }
-                    None => break,
-                    _ => {}
                 }
             }
-
+            show_status("Reconnecting...");
+            match remote_attach::reconnect_to_remote_session(
+                &connections.reconnection_params,
+                &connections.web_client_id,
+            )
+            .await
+            {
+                Ok(new_connections) => break new_connections,
+                Err(
+                    e @ (RemoteClientError::SessionTokenExpired | RemoteClientError::Unauthorized),
+                ) => {
+                    return Err(e);
+                },
+                Err(e) => {
+                    log::error!("Failed to reconnect: {}", e);
+                },
+            }
+        };
+        log::info!("Reconnected as {}", connections.web_client_id);
+        let is_same_client = connections.web_client_id == previous_web_client_id;
+        let has_buffered_keystrokes = !keystroke_buffer.is_empty();
+        match keystroke_buffer.take_on_reconnection(Instant::now(), is_same_client) {
+            Some(keystrokes) => buffered_keystrokes = keystrokes,
+            None if has_buffered_keystrokes => {
+                log::error!("Dropping keystrokes typed while disconnected, our client expired");
+            },
+            None => {},
         }
+        local_echo.reset(os_input.get_terminal_size());
     }
 
     Ok(None)
@@ -599,6 +768,7 @@ pub fn start_remote_client(
     ca_cert: Option<std::path::PathBuf>,
     insecure: bool,
     async_worker_tasks: Option<usize>,
+    local_echo: LocalEcho,
 ) -> Result<Option<ConnectToSession>, RemoteClientError> {
     info!("Starting Zellij client!");
 
@@ -688,10 +858,13 @@ pub fn start_remote_client(
         std::process::exit(exit_status);
     };
 
-    runtime.block_on(run_remote_client_terminal_loop(
+    if let Err(e) = runtime.block_on(run_remote_client_terminal_loop(
         os_input.clone(),
         connections,
-    ))?;
+        local_echo,
+    )) {
+        reset_controlling_terminal_state(format!("{}", e), 1);
+    }
 
     let exit_msg = String::from("Bye from Zellij!");
 
@@ -810,6 +983,7 @@ pub fn start_client(
                             )
                         })
                 },
+                layout_vars: cli_args.layout_vars.iter().cloned().collect(),
                 terminal_window_size: full_screen_ws,
                 data_dir: cli_args.data_dir.clone(),
                 is_debug: cli_args.debug,
@@ -855,6 +1029,7 @@ pub fn start_client(
                     path_to_layout.display().to_string(),
                     LayoutMetadata::default(),
                 )),
+                layout_vars: Default::default(),
                 terminal_window_size: full_screen_ws,
                 data_dir: cli_args.data_dir.clone(),
                 is_debug: cli_args.debug,
@@ -909,6 +1084,7 @@ pub fn start_client(
                             )
                         })
                 }),
+                layout_vars: cli_args.layout_vars.iter().cloned().collect(),
                 terminal_window_size: full_screen_ws,
                 data_dir: cli_args.data_dir.clone(),
                 is_debug: cli_args.debug,
@@ -1328,6 +1504,7 @@ pub fn start_server_detached(
                     path_to_layout.display().to_string(),
                     LayoutMetadata::default(),
                 )),
+                layout_vars: Default::default(),
                 terminal_window_size: Size { cols: 50, rows: 50 }, // static number until a
                 // client connects
                 data_dir: cli_args.data_dir.clone(),
@@ -1383,6 +1560,7 @@ pub fn start_server_detached(
                             )
                         })
                 }),
+                layout_vars: cli_args.layout_vars.iter().cloned().collect(),
                 terminal_window_size: Size { cols: 50, rows: 50 }, // static number until a
                 // client connects
                 data_dir: cli_args.data_dir.clone(),
@@ -0,0 +1,539 @@
+//! Speculative local echo for clients attached to a remote session, in the spirit of mosh
+//!
+//! Typed characters and cursor moves are drawn (underlined) as soon as they are typed rather than
+//! a round trip later when the server renders them. We keep a copy of what the server last
+//! rendered, so that confirmed predictions can be repainted the way the server drew them and
+//! mistaken ones rolled back.
+
+use std::time::{Duration, Instant};
+use unicode_width::UnicodeWidthChar;
+use vte::{Params, Parser, Perform};
+use zellij_utils::input::options::LocalEcho;
+use zellij_utils::pane_size::Size;
+
+// adaptive local echo turns on when the round trip time goes above the first and off when it
+// goes below the second
+const ADAPTIVE_ENABLE_ROUND_TRIP: Duration = Duration::from_millis(60);
+const ADAPTIVE_DISABLE_ROUND_TRIP: Duration = Duration::from_millis(30);
+
+// a prediction the server did not confirm within a few round trips is considered mistaken
+const PREDICTION_TIMEOUT_ROUND_TRIPS: u32 = 3;
+const MIN_PREDICTION_TIMEOUT: Duration = Duration::from_millis(300);
+const DEFAULT_PREDICTION_TIMEOUT: Duration = Duration::from_secs(1);
+
+const CURSOR_RIGHT: [&str; 2] = ["\u{1b}[C", "\u{1b}OC"];
+const CURSOR_LEFT: [&str; 2] = ["\u{1b}[D", "\u{1b}OD"];
+
+#[derive(Debug, Clone, PartialEq)]
+struct Cell {
+    text: String,  // empty for the second half of a wide character
+    style: String, // the SGR sequences in effect when the text was printed
+}
+
+impl Default for Cell {
+    fn default() -> Self {
+        Cell {
+            text: String::from(" "),
+            style: String::new(),
+        }
+    }
+}
+
+/// What the server last rendered to our terminal, as far as predicting goes
+#[derive(Debug)]
+pub struct ShadowScreen {
+    rows: Vec<Vec<Cell>>,
+    size: Size,
+    cursor: (usize, usize), // (row, column)
+    cursor_is_visible: bool,
+    style: String,
+}
+
+impl ShadowScreen {
+    pub fn new(size: Size) -> Self {
+        ShadowScreen {
+            rows: vec![vec![Cell::default(); size.cols]; size.rows],
+            size,
+            cursor: (0, 0),
+            cursor_is_visible: true,
+            style: String::new(),
+        }
+    }
+    pub fn cursor(&self) -> (usize, usize) {
+        self.cursor
+    }
+    pub fn cursor_is_visible(&self) -> bool {
+        self.cursor_is_visible
+    }
+    pub fn text_at(&self, row: usize, column: usize) -> Option<&str> {
+        self.rows
+            .get(row)
+            .and_then(|r| r.get(column))
+            .map(|c| c.text.as_str())
+    }
+    fn cell(&self, row: usize, column: usize) -> Option<&Cell> {
+        self.rows.get(row).and_then(|r| r.get(column))
+    }
+    fn cell_mut(&mut self, row: usize, column: usize) -> Option<&mut Cell> {
+        self.rows.get_mut(row).and_then(|r| r.get_mut(column))
+    }
+    fn clear_line(&mut self, row: usize, columns: std::ops::Range<usize>) {
+        if let Some(line) = self.rows.get_mut(row) {
+            let end = columns.end.min(line.len());
+            let start = columns.start.min(end);
+            for cell in &mut line[start..end] {
+                *cell = Cell::default();
+            }
+        }
+    }
+    fn set_style(&mut self, params: &Params) {
+        let sequence = params
+            .iter()
+            .map(|param| {
+                param
+                    .iter()
+                    .map(|p| p.to_string())
+                    .collect::<Vec<_>>()
+                    .join(":")
+            })
+            .collect::<Vec<_>>()
+            .join(";");
+        let resets_style = params
+            .iter()
+            .next()
+            .map(|param| param.first() == Some(&0))
+            .unwrap_or(true);
+        if resets_style {
+            self.style.clear();
+        }
+        if !sequence.is_empty() && sequence != "0" {
+            self.style.push_str(&format!("\u{1b}[{}m", sequence));
+        }
+    }
+}
+
+impl Perform for ShadowScreen {
+    fn print(&mut self, c: char) {
+        let (row, column) = self.cursor;
+        let width = c.width().unwrap_or(0);
+        if width == 0 {
+            // combining characters belong to the previous cell
+            if let Some(cell) = column
+                .checked_sub(1)
+                .and_then(|column| self.cell_mut(row, column))
+            {
+                cell.text.push(c);
+            }
+            return;
+        }
+        let style = self.style.clone();
+        if let Some(cell) = self.cell_mut(row, column) {
+            *cell = Cell {
+                text: c.to_string(),
+                style: style.clone(),
+            };
+        }
+        for offset in 1..width {
+            if let Some(cell) = self.cell_mut(row, column + offset) {
+                *cell = Cell {
+                    text: String::new(),
+                    style: style.clone(),
+                };
+            }
+        }
+        self.cursor.1 = column + width;
+    }
+    fn execute(&mut self, byte: u8) {
+        match byte {
+            b'\r' => self.cursor.1 = 0,
+            b'\n' => self.cursor.0 = (self.cursor.0 + 1).min(self.size.rows.saturating_sub(1)),
+            0x08 => self.cursor.1 = self.cursor.1.saturating_sub(1),
+            _ => {},
+        }
+    }
+    fn csi_dispatch(&mut self, params: &Params, intermediates: &[u8], _ignore: bool, c: char) {
+        let mut numeric_params = params
+            .iter()
+            .map(|p| p.first().copied().unwrap_or(0) as usize);
+        let (row, column) = self.cursor;
+        match (intermediates, c) {
+            ([], 'H') | ([], 'f') => {
+                let row = numeric_params.next().unwrap_or(1).max(1);
+                let column = numeric_params.next().unwrap_or(1).max(1);
+                self.cursor = (row - 1, column - 1);
+            },
+            ([], 'm') => self.set_style(params),
+            ([], 'K') => match numeric_params.next().unwrap_or(0) {
+                0 => self.clear_line(row, column..self.size.cols),
+                1 => self.clear_line(row, 0..column + 1),
+                _ => self.clear_line(row, 0..self.size.cols),
+            },
+            ([], 'J') => {
+                let rows = match numeric_params.next().unwrap_or(0) {
+                    0 => {
+                        self.clear_line(row, column..self.size.cols);
+                        row + 1..self.size.rows
+                    },
+                    1 => {
+                        self.clear_line(row, 0..column + 1);
+                        0..row
+                    },
+                    _ => 0..self.size.rows,
+                };
+                for row in rows {
+                    self.clear_line(row, 0..self.size.cols);
+                }
+            },
+            ([b'?'], 'h') | ([b'?'], 'l') if params.iter().any(|p| p.first() == Some(&25)) => {
+                self.cursor_is_visible = c == 'h';
+            },
+            _ => {},
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Prediction {
+    row: usize,
+    column: usize,
+    character: char,
+    made_at: Instant,
+}
+
+pub struct LocalEchoPredictor {
+    mode: LocalEcho,
+    screen: ShadowScreen,
+    parser: Parser,
+    // the first and last columns of the focused pane, None if the server told us typing into it
+    // cannot be predicted (eg. it is in alternate screen mode)
+    bounds: Option<(usize, usize)>,
+    predictions: Vec<Prediction>,
+    // where our predictions left the cursor and when it was last confirmed to be moving
+    predicted_cursor: Option<((usize, usize), Instant)>,
+    // after a misprediction (eg. a password prompt) we stop predicting until the line changes
+    suspended_on_row: Option<usize>,
+    // after input we cannot predict (eg. ENTER) we wait for the server to render it
+    paused_until: Option<Instant>,
+    round_trip: Option<Duration>,
+    awaiting_render_since: Option<Instant>,
+    adaptive_is_enabled: bool,
+}
+
+impl LocalEchoPredictor {
+    pub fn new(mode: LocalEcho, size: Size) -> Self {
+        LocalEchoPredictor {
+            mode,
+            screen: ShadowScreen::new(size),
+            parser: Parser::new(),
+            bounds: None,
+            predictions: vec![],
+            predicted_cursor: None,
+            suspended_on_row: None,
+            paused_until: None,
+            round_trip: None,
+            awaiting_render_since: None,
+            adaptive_is_enabled: false,
+        }
+    }
+    pub fn screen(&self) -> &ShadowScreen {
+        &self.screen
+    }
+    pub fn round_trip(&self) -> Option<Duration> {
+        self.round_trip
+    }
+    pub fn has_pending_predictions(&self) -> bool {
+        !self.predictions.is_empty() || self.predicted_cursor.is_some()
+    }
+    /// Returns what should be written to the terminal to show our predictions for this input,
+    /// the input itself should be sent to the server as is
+    pub fn handle_input(&mut self, input: &[u8], now: Instant) -> String {
+        if self.awaiting_render_since.is_none() {
+            self.awaiting_render_since = Some(now);
+        }
+        if !self.can_predict() {
+            return String::new();
+        }
+        let mut overlay = String::new();
+        let mut moved_cursor = false;
+        let Ok(mut input) = std::str::from_utf8(input) else {
+            self.pause(now);
+            return overlay;
+        };
+        while !input.is_empty() && self.can_predict() {
+            let (row, column) = self.cursor();
+            let Some((first_column, last_column)) = self.bounds else {
+                break;
+            };
+            if let Some(sequence) = CURSOR_RIGHT.iter().find(|s| input.starts_with(*s)) {
+                input = &input[sequence.len()..];
+                if column < last_column {
+                    self.move_predicted_cursor((row, column + 1), now);
+                    moved_cursor = true;
+                } else {
+                    self.pause(now);
+                }
+                continue;
+            }
+            if let Some(sequence) = CURSOR_LEFT.iter().find(|s| input.starts_with(*s)) {
+                input = &input[sequence.len()..];
+                if column > first_column {
+                    self.move_predicted_cursor((row, column - 1), now);
+                    moved_cursor = true;
+                } else {
+                    self.pause(now);
+                }
+                continue;
+            }
+            let Some(character) = input.chars().next() else {
+                break;
+            };
+            input = &input[character.len_utf8()..];
+            match character {
+                '\u{7f}' | '\u{8}' => {
+                    // we can only erase characters we predicted ourselves, the server
+                    // decides what happens to anything else
+                    match self.predictions.last() {
+                        Some(last) if last.row == row && last.column + 1 == column => {
+                            let last = self.predictions.pop().unwrap();
+                            overlay.push_str(&self.repaint(last.row, last.column));
+                            self.move_predicted_cursor((row, column - 1), now);
+                            moved_cursor = true;
+                        },
+                        _ => self.pause(now),
+                    }
+                },
+                c if c.is_control() || c.width() != Some(1) => self.pause(now),
+                // typing in the last column wraps the line, we leave that to the server
+                _ if column < first_column || column >= last_column => self.pause(now),
+                character => {
+                    self.predictions
+                        .retain(|p| !(p.row == row && p.column == column));
+                    let prediction = Prediction {
+                        row,
+                        column,
+                        character,
+                        made_at: now,
+                    };
+                    overlay.push_str(&self.draw(&prediction));
+                    self.predictions.push(prediction);
+                    self.move_predicted_cursor((row, column + 1), now);
+                    moved_cursor = true;
+                },
+            }
+        }
+        if moved_cursor {
+            overlay.push_str(&self.goto_cursor());
+        }
+        overlay
+    }
+    /// Updates our copy of the screen with output the server rendered (which should be written
+    /// to the terminal first), returns what should be written after it to confirm, roll back or
+    /// redraw our predictions
+    pub fn handle_server_output(&mut self, output: &[u8], now: Instant) -> String {
+        for byte in output {
+            self.parser.advance(&mut self.screen, *byte);
+        }
+        if let Some(since) = self.awaiting_render_since.take() {
+            self.record_round_trip(now.saturating_duration_since(since));
+        }
+        if self.paused_until.map(|until| now >= until).unwrap_or(false) {
+            self.paused_until = None;
+        }
+        if self.suspended_on_row.is_some() && self.suspended_on_row != Some(self.screen.cursor.0) {
+            self.suspended_on_row = None;
+        }
+        self.reconcile(now)
+    }
+    /// Rolls back predictions the server did not confirm in time, should be called periodically
+    /// while there are pending predictions
+    pub fn expire_predictions(&mut self, now: Instant) -> String {
+        if self.has_expired_predictions(now) {
+            self.roll_back(true)
+        } else {
+            String::new()
+        }
+    }
+    /// The server tells us whether typing into the focused pane can be predicted and its bounds
+    pub fn set_state(
+        &mut self,
+        predictable: bool,
+        first_column: usize,
+        last_column: usize,
+    ) -> String {
+        if predictable {
+            self.bounds = Some((first_column, last_column));
+            String::new()
+        } else {
+            self.bounds = None;
+            if self.has_pending_predictions() {
+                self.roll_back(false)
+            } else {
+                String::new()
+            }
+        }
+    }
+    /// The server renders everything again after we resize, our predictions no longer apply
+    pub fn resize(&mut self, size: Size) {
+        self.screen = ShadowScreen::new(size);
+        self.parser = Parser::new();
+        self.predictions.clear();
+        self.predicted_cursor = None;
+        self.suspended_on_row = None;
+    }
+    /// Called when we reconnect, the server will render everything again and tell us its state
+    pub fn reset(&mut self, size: Size) {
+        self.resize(size);
+        self.bounds = None;
+        self.paused_until = None;
+        self.awaiting_render_since = None;
+    }
+    fn can_predict(&self) -> bool {
+        let is_enabled = match self.mode {
+            LocalEcho::Always => true,
+            LocalEcho::Adaptive => self.adaptive_is_enabled,
+            LocalEcho::Never => false,
+        };
+        is_enabled
+            && self.bounds.is_some()
+            && self.screen.cursor_is_visible
+            && self.paused_until.is_none()
+            && self.suspended_on_row != Some(self.screen.cursor.0)
+    }
+    fn cursor(&self) -> (usize, usize) {
+        self.predicted_cursor
+            .map(|(cursor, _)| cursor)
+            .unwrap_or(self.screen.cursor)
+    }
+    fn move_predicted_cursor(&mut self, cursor: (usize, usize), now: Instant) {
+        let since = self.predicted_cursor.map(|(_, since)| since).unwrap_or(now);
+        self.predicted_cursor = Some((cursor, since));
+    }
+    fn pause(&mut self, now: Instant) {
+        self.paused_until = Some(now + self.round_trip.unwrap_or_default());
+    }
+    fn record_round_trip(&mut self, sample: Duration) {
+        let round_trip = match self.round_trip {
+            Some(round_trip) => (round_trip * 7 + sample) / 8,
+            None => sample,
+        };
+        self.round_trip = Some(round_trip);
+        if round_trip > ADAPTIVE_ENABLE_ROUND_TRIP {
+            self.adaptive_is_enabled = true;
+        } else if round_trip < ADAPTIVE_DISABLE_ROUND_TRIP {
+            self.adaptive_is_enabled = false;
+        }
+    }
+    fn prediction_timeout(&self) -> Duration {
+        self.round_trip
+            .map(|round_trip| {
+                (round_trip * PREDICTION_TIMEOUT_ROUND_TRIPS).max(MIN_PREDICTION_TIMEOUT)
+            })
+            .unwrap_or(DEFAULT_PREDICTION_TIMEOUT)
+    }
+    fn has_expired_predictions(&self, now: Instant) -> bool {
+        let timeout = self.prediction_timeout();
+        let expired = |made_at: Instant| now.saturating_duration_since(made_at) > timeout;
+        let prediction_expired = self.predictions.iter().any(|p| expired(p.made_at));
+        let cursor_expired = self.predictions.is_empty()
+            && self
+                .predicted_cursor
+                .map(|(cursor, since)| cursor != self.screen.cursor && expired(since))
+                .unwrap_or(false);
+        prediction_expired || cursor_expired
+    }
+    fn reconcile(&mut self, now: Instant) -> String {
+        if !self.has_pending_predictions() {
+            return String::new();
+        }
+        let server_cursor = self.screen.cursor;
+        let mut mispredicted = false;
+        let mut settled = vec![];
+        for prediction in std::mem::take(&mut self.predictions) {
+            let is_confirmed = self.screen.text_at(prediction.row, prediction.column)
+                == Some(prediction.character.to_string().as_str());
+            if is_confirmed || prediction.row != server_cursor.0 {
+                // the server printed it, or moved on to another line (eg. the screen scrolled)
+                settled.push(prediction);
+            } else {
+                if server_cursor == (prediction.row, prediction.column + 1) {
+                    // the server's cursor moved past this cell without printing our character
+                    mispredicted = true;
+                }
+                self.predictions.push(prediction);
+            }
+        }
+        let mut overlay = String::new();
+        for prediction in &settled {
+            overlay.push_str(&self.repaint(prediction.row, prediction.column));
+        }
+        if !settled.is_empty() {
+            // the server is catching up, so the cursor prediction is not stale
+            if let Some((cursor, _)) = self.predicted_cursor {
+                self.predicted_cursor = Some((cursor, now));
+            }
+        }
+        if mispredicted || self.has_expired_predictions(now) {
+            overlay.push_str(&self.roll_back(true));
+            return overlay;
+        }
+        if self.predictions.is_empty()
+            && self
+                .predicted_cursor
+                .map(|(cursor, _)| cursor == server_cursor)
+                .unwrap_or(false)
+        {
+            self.predicted_cursor = None;
+        }
+        // the server's render may have drawn over predictions it has not caught up with yet, and
+        // it left the cursor where it is on the server
+        for prediction in &self.predictions {
+            overlay.push_str(&self.draw(prediction));
+        }
+        if !overlay.is_empty() || self.predicted_cursor.is_some() {
+            overlay.push_str(&self.goto_cursor());
+        }
+        overlay
+    }
+    fn roll_back(&mut self, suspend: bool) -> String {
+        let mut overlay = String::new();
+        for prediction in std::mem::take(&mut self.predictions) {
+            overlay.push_str(&self.repaint(prediction.row, prediction.column));
+        }
+        self.predicted_cursor = None;
+        if suspend {
+            self.suspended_on_row = Some(self.screen.cursor.0);
+        }
+        overlay.push_str(&self.goto_cursor());
+        overlay
+    }
+    fn draw(&self, prediction: &Prediction) -> String {
+        let style = self
+            .screen
+            .cell(prediction.row, prediction.column)
+            .map(|c| c.style.as_str())
+            .unwrap_or("");
+        format!(
+            "\u{1b}[{};{}H\u{1b}[m{}\u{1b}[4m{}",
+            prediction.row + 1,
+            prediction.column + 1,
+            style,
+            prediction.character
+        )
+    }
+    fn repaint(&self, row: usize, column: usize) -> String {
+        match self.screen.cell(row, column) {
+            Some(cell) if !cell.text.is_empty() => format!(
+                "\u{1b}[{};{}H\u{1b}[m{}{}",
+                row + 1,
+                column + 1,
+                cell.style,
+                cell.text
+            ),
+            _ => String::new(),
+        }
+    }
+    fn goto_cursor(&self) -> String {
+        let (row, column) = self.cursor();
+        format!("\u{1b}[m\u{1b}[{};{}H", row + 1, column + 1)
+    }
+}
@@ -1,12 +1,14 @@
 mod auth;
 mod config;
 pub mod http_client;
+pub mod local_echo;
+pub mod reconnection;
 pub mod websockets;
 
 #[cfg(test)]
 mod unit;
 
-pub use websockets::WebSocketConnections;
+pub use websockets::{ReconnectionParams, WebSocketConnections};
 
 use crate::os_input_output::ClientOsApi;
 use crate::RemoteClientError;
@@ -240,6 +242,68 @@ async fn remote_attach_with_session_token(
     Ok(connections)
 }
 
+/// Connect again to a remote session after the connection dropped
+///
+/// The server keeps our client attached to the session for a grace period, within which we
+/// reconnect to that same client. Once it is gone we attach as a new client using the session
+/// token we authenticated with.
+pub async fn reconnect_to_remote_session(
+    reconnection_params: &ReconnectionParams,
+    web_client_id: &str,
+) -> Result<WebSocketConnections, RemoteClientError> {
+    let ReconnectionParams {
+        server_base_url,
+        session_name,
+        session_token,
+        ca_cert,
+        insecure,
+    } = reconnection_params;
+    let session_token = session_token
+        .as_deref()
+        .ok_or(RemoteClientError::SessionTokenExpired)?;
+    let http_client = http_client::HttpClientWithCookies::new(ca_cert.as_deref(), *insecure)
+        .map_err(|e| RemoteClientError::Other(Box::new(e)))?;
+    http_client.set_cookie("session_token".to_string(), session_token.to_string());
+    // the error is not Send, so we only keep what we need of it before awaiting again
+    let first_attempt = websockets::establish_websocket_connections(
+        web_client_id,
+        &http_client,
+        server_base_url,
+        session_name,
+        ca_cert.as_deref(),
+        *insecure,
+    )
+    .await
+    .map_err(|e| (websockets::is_rejected_by_server(e.as_ref()), e.to_string()));
+    match first_attempt {
+        Ok(connections) => Ok(connections),
+        Err((true, _)) => {
+            log::info!(
+                "Client {} expired on the server, attaching anew",
+                web_client_id
+            );
+            let (web_client_id, http_client) = auth::validate_session_token(
+                server_base_url,
+                session_token,
+                ca_cert.as_deref(),
+                *insecure,
+            )
+            .await?;
+            websockets::establish_websocket_connections(
+                &web_client_id,
+                &http_client,
+                server_base_url,
+                session_name,
+                ca_cert.as_deref(),
+                *insecure,
+            )
+            .await
+            .map_err(|e| RemoteClientError::ConnectionFailed(e.to_string()))
+        },
+        Err((false, e)) => Err(RemoteClientError::ConnectionFailed(e)),
+    }
+}
+
 pub fn extract_server_url(full_url: &str) -> Result<String, RemoteClientError> {
     let parsed = url::Url::parse(full_url)?;
     let mut base_url = parsed.clone();
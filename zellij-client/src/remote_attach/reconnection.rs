@@ -0,0 +1,91 @@
+use crate::web_client::RECONNECTION_GRACE_PERIOD_SECS;
+use std::time::{Duration, Instant};
+use zellij_utils::pane_size::Size;
+
+// Delays between reconnection attempts, the last one repeats until we give up
+const RECONNECTION_DELAYS_SECS: [u64; 5] = [1, 2, 4, 8, 16];
+const MAX_RECONNECTION_ATTEMPTS: usize = 20;
+
+// Keystrokes typed while disconnected are sent once we reconnect, up to this many bytes
+const MAX_BUFFERED_KEYSTROKE_BYTES: usize = 4096;
+
+// A silent connection is considered dropped after this long, we ping it in between so that an
+// idle session is not mistaken for one
+pub const HEARTBEAT_INTERVAL_SECS: u64 = 10;
+pub const HEARTBEAT_TIMEOUT_SECS: u64 = 30;
+
+#[derive(Debug, Default)]
+pub struct ReconnectionBackoff {
+    attempt: usize,
+}
+
+impl ReconnectionBackoff {
+    /// The delay before the next attempt, or None once we should give up
+    pub fn next_delay(&mut self) -> Option<Duration> {
+        if self.attempt >= MAX_RECONNECTION_ATTEMPTS {
+            return None;
+        }
+        let delay_index = self.attempt.min(RECONNECTION_DELAYS_SECS.len() - 1);
+        self.attempt += 1;
+        Some(Duration::from_secs(RECONNECTION_DELAYS_SECS[delay_index]))
+    }
+    pub fn attempt(&self) -> usize {
+        self.attempt
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct KeystrokeBuffer {
+    bytes: Vec<u8>,
+    disconnected_at: Option<Instant>,
+}
+
+impl KeystrokeBuffer {
+    /// Returns false if the keystrokes were dropped because the buffer is full, we keep whole
+    /// reads so as not to send half an escape sequence
+    pub fn push(&mut self, keystrokes: &[u8]) -> bool {
+        if self.bytes.len() + keystrokes.len() > MAX_BUFFERED_KEYSTROKE_BYTES {
+            return false;
+        }
+        self.bytes.extend_from_slice(keystrokes);
+        true
+    }
+    pub fn disconnected(&mut self, now: Instant) {
+        self.disconnected_at.get_or_insert(now);
+    }
+    /// The keystrokes to send once we reconnect, or None if they were dropped: after the grace
+    /// period the server no longer kept our client, so they would land in a session that moved
+    /// on without us
+    pub fn take_on_reconnection(&mut self, now: Instant, is_same_client: bool) -> Option<Vec<u8>> {
+        let bytes = std::mem::take(&mut self.bytes);
+        let outlasted_grace_period = self.disconnected_at.take().is_some_and(|disconnected_at| {
+            now.duration_since(disconnected_at)
+                > Duration::from_secs(RECONNECTION_GRACE_PERIOD_SECS)
+        });
+        if is_same_client && !outlasted_grace_period {
+            Some(bytes)
+        } else {
+            None
+        }
+    }
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+}
+
+/// A status line drawn over the bottom of the terminal while we are disconnected, the session
+/// is rendered again in full once we reconnect
+pub fn disconnected_status_overlay(message: &str, terminal_size: Size) -> String {
+    let save_cursor = "\u{1b}7";
+    let restore_cursor = "\u{1b}8";
+    let goto_last_line = format!("\u{1b}[{};1H", terminal_size.rows);
+    let clear_line = "\u{1b}[2K";
+    let message: String = format!(" {} ", message)
+        .chars()
+        .take(terminal_size.cols)
+        .collect();
+    format!(
+        "{}{}{}\u{1b}[7m{}\u{1b}[m{}",
+        save_cursor, goto_last_line, clear_line, message, restore_cursor
+    )
+}
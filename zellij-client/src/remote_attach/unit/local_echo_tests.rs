@@ -0,0 +1,177 @@
+use super::super::local_echo::*;
+use std::time::{Duration, Instant};
+use zellij_utils::input::options::LocalEcho;
+use zellij_utils::pane_size::Size;
+
+// a shell prompt on the third line, leaving the cursor after it
+const PROMPT: &[u8] = b"\x1b[?25l\x1b[3;1H\x1b[m$ \x1b[?25h\x1b[3;3H\x1b[m";
+
+fn predictor_at_prompt(mode: LocalEcho, now: Instant) -> LocalEchoPredictor {
+    let mut predictor = LocalEchoPredictor::new(mode, Size { rows: 10, cols: 20 });
+    predictor.handle_server_output(PROMPT, now);
+    predictor.set_state(true, 0, 19);
+    predictor
+}
+
+#[test]
+fn shadow_screen_tracks_what_the_server_rendered() {
+    let now = Instant::now();
+    let mut predictor = LocalEchoPredictor::new(LocalEcho::Always, Size { rows: 10, cols: 20 });
+    predictor.handle_server_output(b"\x1b[?25l\x1b[2;5H\x1b[1mhi\x1b[m there", now);
+    let screen = predictor.screen();
+    assert_eq!(screen.text_at(1, 4), Some("h"));
+    assert_eq!(screen.text_at(1, 5), Some("i"));
+    assert_eq!(screen.text_at(1, 7), Some("t"));
+    assert_eq!(screen.cursor(), (1, 12));
+    assert!(!screen.cursor_is_visible());
+    predictor.handle_server_output(b"\x1b[2;6H\x1b[K\x1b[?25h", now);
+    let screen = predictor.screen();
+    assert_eq!(
+        screen.text_at(1, 4),
+        Some("h"),
+        "cleared from the cursor onwards"
+    );
+    assert_eq!(screen.text_at(1, 5), Some(" "));
+    assert!(screen.cursor_is_visible());
+}
+
+#[test]
+fn typed_characters_are_predicted_underlined_at_the_cursor() {
+    let now = Instant::now();
+    let mut predictor = predictor_at_prompt(LocalEcho::Always, now);
+    assert_eq!(
+        predictor.handle_input(b"ls", now),
+        "\u{1b}[3;3H\u{1b}[m\u{1b}[4ml\u{1b}[3;4H\u{1b}[m\u{1b}[4ms\u{1b}[m\u{1b}[3;5H"
+    );
+    assert!(predictor.has_pending_predictions());
+}
+
+#[test]
+fn nothing_is_predicted_when_local_echo_is_off_or_the_pane_is_unpredictable() {
+    let now = Instant::now();
+    let mut predictor = predictor_at_prompt(LocalEcho::Never, now);
+    assert_eq!(predictor.handle_input(b"ls", now), "");
+    let mut predictor = predictor_at_prompt(LocalEcho::Always, now);
+    predictor.set_state(false, 0, 0);
+    assert_eq!(
+        predictor.handle_input(b"ls", now),
+        "",
+        "eg. the focused pane is in alternate screen mode"
+    );
+}
+
+#[test]
+fn confirmed_predictions_are_repainted_as_the_server_drew_them() {
+    let now = Instant::now();
+    let mut predictor = predictor_at_prompt(LocalEcho::Always, now);
+    predictor.handle_input(b"l", now);
+    let overlay = predictor.handle_server_output(b"\x1b[3;3H\x1b[m\x1b[32ml\x1b[3;4H\x1b[m", now);
+    assert_eq!(overlay, "\u{1b}[3;3H\u{1b}[m\u{1b}[32ml\u{1b}[m\u{1b}[3;4H");
+    assert!(!predictor.has_pending_predictions());
+}
+
+#[test]
+fn pending_predictions_are_redrawn_after_the_server_renders() {
+    let now = Instant::now();
+    let mut predictor = predictor_at_prompt(LocalEcho::Always, now);
+    predictor.handle_input(b"ab", now);
+    let overlay = predictor.handle_server_output(b"\x1b[3;3Ha\x1b[3;4H", now);
+    assert_eq!(
+        overlay, "\u{1b}[3;3H\u{1b}[ma\u{1b}[3;4H\u{1b}[m\u{1b}[4mb\u{1b}[m\u{1b}[3;5H",
+        "the server only caught up with the first character"
+    );
+    assert!(predictor.has_pending_predictions());
+}
+
+#[test]
+fn mispredictions_are_rolled_back_and_prediction_suspended_for_the_line() {
+    let now = Instant::now();
+    let mut predictor = predictor_at_prompt(LocalEcho::Always, now);
+    predictor.handle_input(b"a", now);
+    let overlay = predictor.handle_server_output(b"\x1b[3;3H*\x1b[3;4H", now);
+    assert_eq!(overlay, "\u{1b}[3;3H\u{1b}[m*\u{1b}[m\u{1b}[3;4H");
+    assert!(!predictor.has_pending_predictions());
+    assert_eq!(
+        predictor.handle_input(b"b", now),
+        "",
+        "eg. a password prompt"
+    );
+    predictor.handle_server_output(b"\x1b[4;1H$ \x1b[4;3H", now);
+    assert_ne!(
+        predictor.handle_input(b"c", now),
+        "",
+        "predicting again on the next line"
+    );
+}
+
+#[test]
+fn unconfirmed_predictions_expire() {
+    let now = Instant::now();
+    let mut predictor = predictor_at_prompt(LocalEcho::Always, now);
+    predictor.handle_input(b"a", now);
+    assert_eq!(
+        predictor.expire_predictions(now + Duration::from_millis(100)),
+        ""
+    );
+    assert_eq!(
+        predictor.expire_predictions(now + Duration::from_secs(2)),
+        "\u{1b}[3;3H\u{1b}[m \u{1b}[m\u{1b}[3;3H"
+    );
+    assert!(!predictor.has_pending_predictions());
+}
+
+#[test]
+fn backspace_erases_predicted_characters() {
+    let now = Instant::now();
+    let mut predictor = predictor_at_prompt(LocalEcho::Always, now);
+    predictor.handle_input(b"ab", now);
+    assert_eq!(
+        predictor.handle_input(b"\x7f", now),
+        "\u{1b}[3;4H\u{1b}[m \u{1b}[m\u{1b}[3;4H"
+    );
+}
+
+#[test]
+fn cursor_moves_are_predicted_within_the_pane() {
+    let now = Instant::now();
+    let mut predictor = predictor_at_prompt(LocalEcho::Always, now);
+    assert_eq!(
+        predictor.handle_input(b"\x1b[D", now),
+        "\u{1b}[m\u{1b}[3;2H"
+    );
+    assert_eq!(
+        predictor.handle_input(b"\x1bOC\x1bOC", now),
+        "\u{1b}[m\u{1b}[3;4H"
+    );
+    let mut predictor = predictor_at_prompt(LocalEcho::Always, now);
+    predictor.set_state(true, 2, 19);
+    assert_eq!(
+        predictor.handle_input(b"\x1b[D", now),
+        "",
+        "not past the left edge of the pane"
+    );
+}
+
+#[test]
+fn unpredictable_input_pauses_prediction_until_the_server_renders() {
+    let now = Instant::now();
+    let mut predictor = predictor_at_prompt(LocalEcho::Always, now);
+    assert_eq!(predictor.handle_input(b"\r", now), "");
+    assert_eq!(predictor.handle_input(b"x", now), "");
+    predictor.handle_server_output(b"\x1b[4;1H$ \x1b[4;3H", now);
+    assert_ne!(predictor.handle_input(b"x", now), "");
+}
+
+#[test]
+fn adaptive_local_echo_only_predicts_over_slow_connections() {
+    let now = Instant::now();
+    let mut predictor = predictor_at_prompt(LocalEcho::Adaptive, now);
+    assert_eq!(predictor.handle_input(b"a", now), "");
+    let later = now + Duration::from_millis(200);
+    predictor.handle_server_output(b"\x1b[3;3Ha\x1b[3;4H", later);
+    assert_eq!(predictor.round_trip(), Some(Duration::from_millis(200)));
+    assert_eq!(
+        predictor.handle_input(b"b", later),
+        "\u{1b}[3;4H\u{1b}[m\u{1b}[4mb\u{1b}[m\u{1b}[3;5H"
+    );
+}
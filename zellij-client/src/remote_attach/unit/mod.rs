@@ -1 +1,3 @@
+mod local_echo_tests;
+mod reconnection_tests;
 mod remote_attach_tests;
@@ -0,0 +1,115 @@
+use super::super::reconnection::*;
+use std::time::{Duration, Instant};
+use zellij_utils::pane_size::Size;
+
+#[test]
+fn reconnection_backoff_grows_then_gives_up() {
+    let mut backoff = ReconnectionBackoff::default();
+    let delays: Vec<u64> = std::iter::from_fn(|| backoff.next_delay())
+        .map(|d| d.as_secs())
+        .collect();
+    assert_eq!(&delays[..6], &[1, 2, 4, 8, 16, 16]);
+    assert_eq!(delays.len(), 20, "gives up after the maximum attempts");
+    assert_eq!(backoff.next_delay(), None);
+    assert_eq!(backoff.attempt(), 20);
+}
+
+#[test]
+fn reconnection_backoff_starts_with_a_short_delay() {
+    let mut backoff = ReconnectionBackoff::default();
+    assert_eq!(backoff.next_delay(), Some(Duration::from_secs(1)));
+    assert_eq!(backoff.attempt(), 1);
+}
+
+#[test]
+fn keystroke_buffer_keeps_keystrokes_in_order() {
+    let mut keystroke_buffer = KeystrokeBuffer::default();
+    assert!(keystroke_buffer.is_empty());
+    assert!(keystroke_buffer.push(b"ls"));
+    assert!(keystroke_buffer.push(b"\r"));
+    let now = Instant::now();
+    keystroke_buffer.disconnected(now);
+    let is_same_client = true;
+    assert_eq!(
+        keystroke_buffer.take_on_reconnection(now + Duration::from_secs(5), is_same_client),
+        Some(b"ls\r".to_vec())
+    );
+    assert!(keystroke_buffer.is_empty(), "taking empties the buffer");
+}
+
+#[test]
+fn keystroke_buffer_drops_keystrokes_once_the_outage_outlasts_the_grace_period() {
+    let mut keystroke_buffer = KeystrokeBuffer::default();
+    let now = Instant::now();
+    keystroke_buffer.disconnected(now);
+    assert!(keystroke_buffer.push(b"rm -rf build\r"));
+    let is_same_client = true;
+    assert_eq!(
+        keystroke_buffer.take_on_reconnection(now + Duration::from_secs(61), is_same_client),
+        None
+    );
+    assert!(
+        keystroke_buffer.is_empty(),
+        "dropped keystrokes are not kept"
+    );
+}
+
+#[test]
+fn keystroke_buffer_drops_keystrokes_when_reconnecting_as_a_new_client() {
+    let mut keystroke_buffer = KeystrokeBuffer::default();
+    let now = Instant::now();
+    keystroke_buffer.disconnected(now);
+    assert!(keystroke_buffer.push(b"rm -rf build\r"));
+    let is_same_client = false;
+    assert_eq!(
+        keystroke_buffer.take_on_reconnection(now + Duration::from_secs(5), is_same_client),
+        None
+    );
+    assert!(
+        keystroke_buffer.is_empty(),
+        "dropped keystrokes are not kept"
+    );
+
+    // the outage is over, the next one is timed anew
+    let now = now + Duration::from_secs(120);
+    keystroke_buffer.disconnected(now);
+    assert!(keystroke_buffer.push(b"ls\r"));
+    let is_same_client = true;
+    assert_eq!(
+        keystroke_buffer.take_on_reconnection(now + Duration::from_secs(5), is_same_client),
+        Some(b"ls\r".to_vec())
+    );
+}
+
+#[test]
+fn keystroke_buffer_drops_whole_reads_once_full() {
+    let mut keystroke_buffer = KeystrokeBuffer::default();
+    assert!(keystroke_buffer.push(&[b'a'; 4092]));
+    assert!(
+        !keystroke_buffer.push(b"\x1b[1;5A"),
+        "an escape sequence that does not fit is dropped whole"
+    );
+    assert!(keystroke_buffer.push(b"b"));
+    let is_same_client = true;
+    assert_eq!(
+        keystroke_buffer
+            .take_on_reconnection(Instant::now(), is_same_client)
+            .map(|keystrokes| keystrokes.len()),
+        Some(4093)
+    );
+}
+
+#[test]
+fn disconnected_status_overlay_is_drawn_on_the_last_line() {
+    let overlay = disconnected_status_overlay("Connection lost", Size { rows: 24, cols: 80 });
+    assert_eq!(
+        overlay,
+        "\u{1b}7\u{1b}[24;1H\u{1b}[2K\u{1b}[7m Connection lost \u{1b}[m\u{1b}8"
+    );
+}
+
+#[test]
+fn disconnected_status_overlay_fits_the_terminal_width() {
+    let overlay = disconnected_status_overlay("Connection lost", Size { rows: 10, cols: 6 });
+    assert!(overlay.contains("\u{1b}[7m Conne\u{1b}[m"));
+}
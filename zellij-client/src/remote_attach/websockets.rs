@@ -1,6 +1,6 @@
 use super::config::{WS_CONTROL_ENDPOINT, WS_TERMINAL_ENDPOINT};
 use super::http_client::HttpClientWithCookies;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::pin::Pin;
 use std::sync::Arc;
 use std::task::{Context, Poll};
@@ -174,6 +174,27 @@ pub struct WebSocketConnections {
     pub terminal_ws: WebSocketStream<MaybeTls>,
     pub control_ws: WebSocketStream<MaybeTls>,
     pub web_client_id: String,
+    pub reconnection_params: ReconnectionParams,
+}
+
+/// What we need to connect to the same session again once the connection drops
+#[derive(Debug, Clone)]
+pub struct ReconnectionParams {
+    pub server_base_url: String,
+    pub session_name: String,
+    pub session_token: Option<String>,
+    pub ca_cert: Option<PathBuf>,
+    pub insecure: bool,
+}
+
+/// Whether the server refused the WebSocket upgrade, as it does for a web_client_id it no
+/// longer knows
+pub fn is_rejected_by_server(error: &(dyn std::error::Error + 'static)) -> bool {
+    use tokio_tungstenite::tungstenite::Error;
+    match error.downcast_ref::<Error>() {
+        Some(Error::Http(response)) => response.status().is_client_error(),
+        _ => false,
+    }
 }
 
 impl std::fmt::Debug for WebSocketConnections {
@@ -274,5 +295,12 @@ pub async fn establish_websocket_connections(
         terminal_ws,
         control_ws,
         web_client_id: web_client_id.to_owned(),
+        reconnection_params: ReconnectionParams {
+            server_base_url: server_base_url.to_owned(),
+            session_name: session_name.to_owned(),
+            session_token: http_client.get_cookie("session_token"),
+            ca_cert: ca_cert.map(|p| p.to_path_buf()),
+            insecure,
+        },
     })
 }
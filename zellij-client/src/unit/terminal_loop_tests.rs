@@ -1,5 +1,5 @@
 use crate::os_input_output::{AsyncSignals, AsyncStdin, ClientOsApi, SignalEvent};
-use crate::remote_attach::websockets::MaybeTls;
+use crate::remote_attach::websockets::{MaybeTls, ReconnectionParams};
 use crate::remote_attach::WebSocketConnections;
 use crate::run_remote_client_terminal_loop;
 use crate::web_client::control_message::{
@@ -17,6 +17,7 @@ use tokio::sync::mpsc;
 use tokio_tungstenite::tungstenite::Message;
 use zellij_utils::data::Palette;
 use zellij_utils::errors::ErrorContext;
+use zellij_utils::input::options::LocalEcho;
 use zellij_utils::ipc::{ClientToServerMsg, ServerToClientMsg};
 use zellij_utils::pane_size::Size;
 
@@ -316,6 +317,16 @@ mod mock_ws_server {
     }
 }
 
+fn test_reconnection_params() -> ReconnectionParams {
+    ReconnectionParams {
+        server_base_url: "http://127.0.0.1".to_string(),
+        session_name: "test-session".to_string(),
+        session_token: None,
+        ca_cert: None,
+        insecure: false,
+    }
+}
+
 #[tokio::test]
 #[serial]
 async fn test_stdin_forwarded_to_terminal_websocket() {
@@ -351,6 +362,7 @@ async fn test_stdin_forwarded_to_terminal_websocket() {
         terminal_ws,
         control_ws,
         web_client_id: "test-stdin".to_string(),
+        reconnection_params: test_reconnection_params(),
     };
 
     // Create mock OS API with controllable stdin
@@ -360,8 +372,9 @@ async fn test_stdin_forwarded_to_terminal_websocket() {
     let os_input = Box::new(TestClientOsApi::new(stdin_rx, signal_rx));
 
     // Spawn the async loop
-    let loop_handle =
-        tokio::spawn(async move { run_remote_client_terminal_loop(os_input, connections).await });
+    let loop_handle = tokio::spawn(async move {
+        run_remote_client_terminal_loop(os_input, connections, LocalEcho::Never).await
+    });
 
     // Send stdin data
     let test_data = b"hello from stdin\n".to_vec();
@@ -423,6 +436,7 @@ async fn test_terminal_output_written_to_stdout() {
         terminal_ws,
         control_ws,
         web_client_id: "test-stdout".to_string(),
+        reconnection_params: test_reconnection_params(),
     };
 
     let (_stdin_tx, stdin_rx) = mpsc::unbounded_channel();
@@ -432,8 +446,9 @@ async fn test_terminal_output_written_to_stdout() {
     let stdout_buffer = os_input.stdout_buffer.clone();
     let os_input = Box::new(os_input);
 
-    let loop_handle =
-        tokio::spawn(async move { run_remote_client_terminal_loop(os_input, connections).await });
+    let loop_handle = tokio::spawn(async move {
+        run_remote_client_terminal_loop(os_input, connections, LocalEcho::Never).await
+    });
 
     // Send terminal output from server
     let test_output = "Hello from terminal";
@@ -498,6 +513,7 @@ async fn test_resize_signal_sends_control_message() {
         terminal_ws,
         control_ws,
         web_client_id: "test-resize".to_string(),
+        reconnection_params: test_reconnection_params(),
     };
 
     let (_stdin_tx, stdin_rx) = mpsc::unbounded_channel();
@@ -505,8 +521,9 @@ async fn test_resize_signal_sends_control_message() {
 
     let os_input = Box::new(TestClientOsApi::new(stdin_rx, signal_rx));
 
-    let loop_handle =
-        tokio::spawn(async move { run_remote_client_terminal_loop(os_input, connections).await });
+    let loop_handle = tokio::spawn(async move {
+        run_remote_client_terminal_loop(os_input, connections, LocalEcho::Never).await
+    });
 
     // Wait for initial resize message to be sent on startup
     tokio::time::sleep(Duration::from_millis(200)).await;
@@ -589,6 +606,7 @@ async fn test_quit_signal_exits_loop() {
         terminal_ws,
         control_ws,
         web_client_id: "test-quit".to_string(),
+        reconnection_params: test_reconnection_params(),
     };
 
     let (_stdin_tx, stdin_rx) = mpsc::unbounded_channel();
@@ -596,8 +614,9 @@ async fn test_quit_signal_exits_loop() {
 
     let os_input = Box::new(TestClientOsApi::new(stdin_rx, signal_rx));
 
-    let loop_handle =
-        tokio::spawn(async move { run_remote_client_terminal_loop(os_input, connections).await });
+    let loop_handle = tokio::spawn(async move {
+        run_remote_client_terminal_loop(os_input, connections, LocalEcho::Never).await
+    });
 
     // Send quit signal
     signal_tx.send(SignalEvent::Quit).unwrap();
@@ -644,6 +663,7 @@ async fn test_websocket_close_exits_loop() {
         terminal_ws,
         control_ws,
         web_client_id: "test-close".to_string(),
+        reconnection_params: test_reconnection_params(),
     };
 
     let (_stdin_tx, stdin_rx) = mpsc::unbounded_channel();
@@ -651,8 +671,9 @@ async fn test_websocket_close_exits_loop() {
 
     let os_input = Box::new(TestClientOsApi::new(stdin_rx, signal_rx));
 
-    let loop_handle =
-        tokio::spawn(async move { run_remote_client_terminal_loop(os_input, connections).await });
+    let loop_handle = tokio::spawn(async move {
+        run_remote_client_terminal_loop(os_input, connections, LocalEcho::Never).await
+    });
 
     // Send close message
     server
@@ -702,6 +723,7 @@ async fn test_control_message_handling() {
         terminal_ws,
         control_ws,
         web_client_id: "test-control".to_string(),
+        reconnection_params: test_reconnection_params(),
     };
 
     let (_stdin_tx, stdin_rx) = mpsc::unbounded_channel();
@@ -711,8 +733,9 @@ async fn test_control_message_handling() {
     let terminal_size = os_input.terminal_size;
     let os_input = Box::new(os_input);
 
-    let loop_handle =
-        tokio::spawn(async move { run_remote_client_terminal_loop(os_input, connections).await });
+    let loop_handle = tokio::spawn(async move {
+        run_remote_client_terminal_loop(os_input, connections, LocalEcho::Never).await
+    });
 
     // Wait for initial resize message to be sent on startup
     tokio::time::sleep(Duration::from_millis(200)).await;
@@ -1,6 +1,8 @@
 use crate::os_input_output::ClientOsApi;
 use crate::web_client::control_message::WebServerToWebClientControlMessage;
-use crate::web_client::types::{ClientChannels, ClientConnectionBus, ConnectionTable};
+use crate::web_client::types::{
+    ClientChannels, ClientConnectionBus, ConnectionTable, TerminalDisconnection,
+};
 use axum::extract::ws::{CloseFrame, Message};
 use std::sync::{atomic::AtomicBool, Arc};
 use tokio::sync::mpsc::UnboundedSender;
@@ -50,10 +52,33 @@ impl ConnectionTable {
         &mut self,
         client_id: &str,
         terminal_channel_tx: UnboundedSender<String>,
-    ) {
+    ) -> Option<usize> {
+        self.client_id_to_channels
+            .get_mut(client_id)
+            .map(|c| c.add_terminal_tx(terminal_channel_tx))
+    }
+
+    pub fn take_over_client_terminal(
+        &mut self,
+        client_id: &str,
+    ) -> Option<UnboundedSender<String>> {
         self.client_id_to_channels
             .get_mut(client_id)
-            .map(|c| c.add_terminal_tx(terminal_channel_tx));
+            .and_then(|c| c.take_over_terminal())
+    }
+
+    pub fn client_terminal_disconnected(
+        &mut self,
+        client_id: &str,
+        terminal_connection: usize,
+    ) -> TerminalDisconnection {
+        match self.client_id_to_channels.get_mut(client_id) {
+            None => TerminalDisconnection::ClientRemoved,
+            Some(c) if !c.is_current_terminal_connection(terminal_connection) => {
+                TerminalDisconnection::Superseded
+            },
+            Some(c) => TerminalDisconnection::AwaitingReconnection(c.await_reconnection()),
+        }
     }
 
     pub fn add_client_terminal_channel_cancellation_token(
@@ -99,35 +124,37 @@ impl ConnectionTable {
 
 impl ClientConnectionBus {
     pub fn send_stdout(&mut self, stdout: String) {
-        match self.stdout_channel_tx.as_ref() {
-            Some(stdout_channel_tx) => {
-                let _ = stdout_channel_tx.send(stdout);
-            },
-            None => {
-                self.get_stdout_channel_tx();
-                if let Some(stdout_channel_tx) = self.stdout_channel_tx.as_ref() {
-                    let _ = stdout_channel_tx.send(stdout);
-                } else {
-                    log::error!("Failed to send STDOUT message to client");
-                }
+        // a failed send means the client reconnected since we got this channel, so we fetch the
+        // one belonging to its new connection
+        let stdout = match self.stdout_channel_tx.as_ref() {
+            Some(stdout_channel_tx) => match stdout_channel_tx.send(stdout) {
+                Ok(()) => return,
+                Err(e) => e.0,
             },
+            None => stdout,
+        };
+        self.get_stdout_channel_tx();
+        if let Some(stdout_channel_tx) = self.stdout_channel_tx.as_ref() {
+            let _ = stdout_channel_tx.send(stdout);
+        } else {
+            log::error!("Failed to send STDOUT message to client");
         }
     }
 
     pub fn send_control(&mut self, message: WebServerToWebClientControlMessage) {
         let message = Message::Text(serde_json::to_string(&message).unwrap().into());
-        match self.control_channel_tx.as_ref() {
-            Some(control_channel_tx) => {
-                let _ = control_channel_tx.send(message);
-            },
-            None => {
-                self.get_control_channel_tx();
-                if let Some(control_channel_tx) = self.control_channel_tx.as_ref() {
-                    let _ = control_channel_tx.send(message);
-                } else {
-                    log::error!("Failed to send control message to client");
-                }
+        let message = match self.control_channel_tx.as_ref() {
+            Some(control_channel_tx) => match control_channel_tx.send(message) {
+                Ok(()) => return,
+                Err(e) => e.0,
             },
+            None => message,
+        };
+        self.get_control_channel_tx();
+        if let Some(control_channel_tx) = self.control_channel_tx.as_ref() {
+            let _ = control_channel_tx.send(message);
+        } else {
+            log::error!("Failed to send control message to client");
         }
     }
     pub fn close_connection(&mut self) {
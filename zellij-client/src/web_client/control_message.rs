@@ -19,9 +19,20 @@ pub enum WebClientToWebServerControlMessagePayload {
 pub enum WebServerToWebClientControlMessage {
     SetConfig(SetConfigPayload),
     QueryTerminalSize,
-    Log { lines: Vec<String> },
-    LogError { lines: Vec<String> },
-    SwitchedSession { new_session_name: String },
+    Log {
+        lines: Vec<String>,
+    },
+    LogError {
+        lines: Vec<String>,
+    },
+    SwitchedSession {
+        new_session_name: String,
+    },
+    LocalEchoState {
+        predictable: bool,
+        first_column: usize,
+        last_column: usize,
+    },
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -47,6 +47,7 @@ use http_handlers::{
 };
 use ipc_listener::listen_to_web_server_instructions;
 
+pub(crate) use types::RECONNECTION_GRACE_PERIOD_SECS;
 use types::{
     AppState, ClientOsApiFactory, ConnectionTable, RealClientOsApiFactory, RealSessionManager,
     SessionManager,
@@ -176,6 +176,15 @@ pub fn zellij_server_listener(
                                     WebServerToWebClientControlMessage::LogError { lines },
                                 );
                             },
+                            Some(ServerToClientMsg::LocalEchoState{predictable, first_column, last_column}) => {
+                                client_connection_bus.send_control(
+                                    WebServerToWebClientControlMessage::LocalEchoState {
+                                        predictable,
+                                        first_column,
+                                        last_column,
+                                    },
+                                );
+                            },
                             Some(ServerToClientMsg::RenamedSession{name: new_session_name}) => {
                                 client_connection_bus.send_control(
                                     WebServerToWebClientControlMessage::SwitchedSession {
@@ -96,6 +96,7 @@ pub fn create_first_message(
             should_ignore_config: false,
             configuration_options: Some(config_opts),
             layout: layout_info,
+            layout_vars: Default::default(),
             terminal_window_size: client_attributes.size,
             data_dir: None,
             is_debug: false,
@@ -115,6 +116,7 @@ pub fn create_first_message(
             should_ignore_config: false,
             configuration_options: Some(config_opts),
             layout: None,
+            layout_vars: Default::default(),
             terminal_window_size: client_attributes.size,
             data_dir: None,
             is_debug: false,
@@ -91,6 +91,8 @@ pub struct ClientChannels {
     pub control_channel_tx: Option<UnboundedSender<Message>>,
     pub terminal_channel_tx: Option<UnboundedSender<String>>,
     terminal_channel_cancellation_token: Option<CancellationToken>,
+    reconnection_cancellation_token: Option<CancellationToken>,
+    terminal_connection_count: usize,
     pub should_not_reconnect: Arc<AtomicBool>,
 }
 
@@ -101,6 +103,8 @@ impl ClientChannels {
             control_channel_tx: None,
             terminal_channel_tx: None,
             terminal_channel_cancellation_token: None,
+            reconnection_cancellation_token: None,
+            terminal_connection_count: 0,
             should_not_reconnect: Arc::new(AtomicBool::new(false)),
         }
     }
@@ -109,8 +113,36 @@ impl ClientChannels {
         self.control_channel_tx = Some(control_channel_tx);
     }
 
-    pub fn add_terminal_tx(&mut self, terminal_channel_tx: UnboundedSender<String>) {
+    /// Returns the index of this terminal connection, a client gets a new one every time it
+    /// reconnects
+    pub fn add_terminal_tx(&mut self, terminal_channel_tx: UnboundedSender<String>) -> usize {
         self.terminal_channel_tx = Some(terminal_channel_tx);
+        self.terminal_connection_count += 1;
+        self.terminal_connection_count
+    }
+
+    pub fn is_current_terminal_connection(&self, terminal_connection: usize) -> bool {
+        self.terminal_connection_count == terminal_connection
+    }
+
+    pub fn await_reconnection(&mut self) -> CancellationToken {
+        let reconnection_cancellation_token = CancellationToken::new();
+        self.reconnection_cancellation_token = Some(reconnection_cancellation_token.clone());
+        reconnection_cancellation_token
+    }
+
+    /// Ends the previous terminal connection of a reconnecting client, returning its channel
+    /// if there was one
+    pub fn take_over_terminal(&mut self) -> Option<UnboundedSender<String>> {
+        if let Some(reconnection_cancellation_token) = self.reconnection_cancellation_token.take() {
+            reconnection_cancellation_token.cancel();
+        }
+        if let Some(terminal_channel_cancellation_token) =
+            self.terminal_channel_cancellation_token.take()
+        {
+            terminal_channel_cancellation_token.cancel();
+        }
+        self.terminal_channel_tx.take()
     }
 
     pub fn add_terminal_channel_cancellation_token(
@@ -125,6 +157,9 @@ impl ClientChannels {
         {
             terminal_channel_cancellation_token.cancel();
         }
+        if let Some(reconnection_cancellation_token) = self.reconnection_cancellation_token.take() {
+            reconnection_cancellation_token.cancel();
+        }
     }
 }
 
@@ -173,6 +208,17 @@ pub struct CreateClientIdResponse {
     pub is_read_only: bool,
 }
 
+/// What became of a client whose terminal WebSocket went away without closing
+#[derive(Debug)]
+pub enum TerminalDisconnection {
+    /// The client was already removed, eg. it was kicked or its session ended
+    ClientRemoved,
+    /// The client has since reconnected on a newer terminal WebSocket
+    Superseded,
+    /// The client is kept for the grace period, the token is cancelled if it reconnects
+    AwaitingReconnection(CancellationToken),
+}
+
 #[derive(Deserialize)]
 pub struct TerminalParams {
     pub web_client_id: String,
@@ -192,3 +238,7 @@ pub struct LoginResponse {
 
 pub const BRACKETED_PASTE_START: [u8; 6] = [27, 91, 50, 48, 48, 126]; // \u{1b}[200~
 pub const BRACKETED_PASTE_END: [u8; 6] = [27, 91, 50, 48, 49, 126]; // \u{1b}[201~
+
+// how long a client whose connection dropped is kept attached to its session, so that a
+// reconnecting client finds its focus, mode and scroll position as it left them
+pub const RECONNECTION_GRACE_PERIOD_SECS: u64 = 60;
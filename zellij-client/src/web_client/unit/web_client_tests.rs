@@ -2568,6 +2568,102 @@ mod web_client_tests {
         tokio::time::sleep(Duration::from_millis(100)).await;
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn test_dropped_terminal_websocket_reconnects_to_same_client() {
+        let _ = delete_db();
+
+        let test_token_name = "test_token_reconnection";
+        let read_only = false;
+        let (auth_token, _) = create_token(Some(test_token_name.to_string()), read_only)
+            .expect("Failed to create test token");
+
+        let session_manager = Arc::new(MockSessionManager::new());
+        let client_os_api_factory = Arc::new(MockClientOsApiFactory::new());
+        let factory_for_verification = client_os_api_factory.clone();
+
+        let config = Config::default();
+        let options = Options::default();
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let port = addr.port();
+
+        let temp_config_path = std::env::temp_dir().join("test_config.kdl");
+        let server_handle = tokio::spawn(async move {
+            serve_web_client(
+                config,
+                options,
+                Some(temp_config_path),
+                listener,
+                None,
+                Some(session_manager),
+                Some(client_os_api_factory),
+                addr.ip(),
+                port,
+            )
+            .await;
+        });
+
+        wait_for_server(port, Duration::from_secs(5))
+            .await
+            .expect("Server failed to start");
+
+        let session_token = login_and_get_session_token(port, &auth_token).await;
+        let web_client_id = create_client_session(port, &session_token).await;
+
+        let terminal_ws_url = format!(
+            "ws://127.0.0.1:{}/ws/terminal?web_client_id={}",
+            port, web_client_id
+        );
+        let (terminal_ws, _) = timeout(
+            Duration::from_secs(5),
+            connect_async_with_cookie(&terminal_ws_url, &session_token),
+        )
+        .await
+        .expect("Terminal WebSocket connection timed out")
+        .expect("Failed to connect to terminal WebSocket");
+        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        // Drop the connection without a close frame, as a network outage would
+        drop(terminal_ws);
+        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        let (reconnected_terminal_ws, _) = timeout(
+            Duration::from_secs(5),
+            connect_async_with_cookie(&terminal_ws_url, &session_token),
+        )
+        .await
+        .expect("Terminal WebSocket reconnection timed out")
+        .expect("Failed to reconnect to terminal WebSocket");
+        tokio::time::sleep(Duration::from_millis(1500)).await;
+
+        let mut found_client_reconnected = false;
+        let mut found_client_exited = false;
+        for (_, mock_api) in factory_for_verification.mock_apis.lock().unwrap().iter() {
+            for msg in mock_api.get_sent_messages() {
+                match msg {
+                    ClientToServerMsg::ClientReconnected => found_client_reconnected = true,
+                    ClientToServerMsg::ClientExited => found_client_exited = true,
+                    _ => {},
+                }
+            }
+        }
+        assert!(
+            found_client_reconnected,
+            "The server should have been asked to render the reconnected client again"
+        );
+        assert!(
+            !found_client_exited,
+            "The client should have been kept attached while it reconnected"
+        );
+
+        drop(reconnected_terminal_ws);
+        server_handle.abort();
+        revoke_token(test_token_name).expect("Failed to revoke test token");
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+
     // ========== Task 8: HTML-escape base_url test ==========
 
     #[tokio::test]
@@ -7,17 +7,21 @@ use crate::web_client::message_handlers::{
     parse_stdin, render_to_client, send_control_messages_to_client,
 };
 use crate::web_client::server_listener::zellij_server_listener;
-use crate::web_client::types::{AppState, TerminalParams};
+use crate::web_client::types::{
+    AppState, TerminalDisconnection, TerminalParams, RECONNECTION_GRACE_PERIOD_SECS,
+};
 
 use axum::{
     extract::{
         ws::{Message, WebSocket, WebSocketUpgrade},
         Path as AxumPath, Query, State,
     },
-    response::IntoResponse,
+    http::StatusCode,
+    response::{IntoResponse, Response},
 };
 use futures::StreamExt;
 use std::sync::{atomic::AtomicBool, Arc};
+use std::time::Duration;
 use tokio_util::sync::CancellationToken;
 use zellij_utils::{input::mouse::MouseEvent, ipc::ClientToServerMsg};
 
@@ -36,10 +40,22 @@ pub async fn ws_handler_terminal(
     Query(params): Query<TerminalParams>,
     State(state): State<AppState>,
     axum::Extension(session_token_hash): axum::Extension<SessionTokenHash>,
-) -> impl IntoResponse {
-    ws.on_upgrade(move |socket| {
-        handle_ws_terminal(socket, session_name, params, state, session_token_hash)
-    })
+) -> Response {
+    // Verify the session token owns this web_client_id, we do this before the upgrade so that a
+    // reconnecting client whose slot expired can tell and ask for a new one
+    if !state
+        .connection_table
+        .lock()
+        .unwrap()
+        .verify_client_ownership(&params.web_client_id, &session_token_hash.0)
+    {
+        log::error!(
+            "Terminal WebSocket: client does not own web_client_id {}",
+            params.web_client_id
+        );
+        return StatusCode::FORBIDDEN.into_response();
+    }
+    ws.on_upgrade(move |socket| handle_ws_terminal(socket, session_name, params, state))
 }
 
 async fn handle_ws_control(
@@ -136,24 +152,9 @@ async fn handle_ws_terminal(
     session_name: Option<AxumPath<String>>,
     params: TerminalParams,
     state: AppState,
-    session_token_hash: SessionTokenHash,
 ) {
     let web_client_id = params.web_client_id;
 
-    // Verify the session token owns this web_client_id
-    if !state
-        .connection_table
-        .lock()
-        .unwrap()
-        .verify_client_ownership(&web_client_id, &session_token_hash.0)
-    {
-        log::error!(
-            "Terminal WebSocket: client does not own web_client_id {}",
-            web_client_id
-        );
-        return;
-    }
-
     let Some(os_input) = state
         .connection_table
         .lock()
@@ -165,27 +166,41 @@ async fn handle_ws_terminal(
         return;
     };
 
+    // a client that had a terminal connection before is reconnecting after it dropped, it is
+    // still attached to its session so we only replace its connection
+    let previous_terminal_channel_tx = state
+        .connection_table
+        .lock()
+        .unwrap()
+        .take_over_client_terminal(&web_client_id);
+
     let (client_terminal_channel_tx, mut client_terminal_channel_rx) = socket.split();
     let (stdout_channel_tx, stdout_channel_rx) = tokio::sync::mpsc::unbounded_channel();
-    state
+    let Some(terminal_connection) = state
         .connection_table
         .lock()
         .unwrap()
-        .add_client_terminal_tx(&web_client_id, stdout_channel_tx);
+        .add_client_terminal_tx(&web_client_id, stdout_channel_tx)
+    else {
+        log::error!("Unknown web_client_id: {}", web_client_id);
+        return;
+    };
 
     let (attachment_complete_tx, attachment_complete_rx) = tokio::sync::oneshot::channel();
 
-    zellij_server_listener(
-        os_input.clone(),
-        state.connection_table.clone(),
-        session_name.map(|p| p.0),
-        state.config.lock().unwrap().clone(),
-        state.config_options.clone(),
-        Some(state.config_file_path.clone()),
-        web_client_id.clone(),
-        state.session_manager.clone(),
-        Some(attachment_complete_tx),
-    );
+    if previous_terminal_channel_tx.is_none() {
+        zellij_server_listener(
+            os_input.clone(),
+            state.connection_table.clone(),
+            session_name.map(|p| p.0),
+            state.config.lock().unwrap().clone(),
+            state.config_options.clone(),
+            Some(state.config_file_path.clone()),
+            web_client_id.clone(),
+            state.session_manager.clone(),
+            Some(attachment_complete_tx),
+        );
+    }
 
     let terminal_channel_cancellation_token = CancellationToken::new();
     let should_not_reconnect = state
@@ -206,7 +221,7 @@ async fn handle_ws_terminal(
         .unwrap()
         .add_client_terminal_channel_cancellation_token(
             &web_client_id,
-            terminal_channel_cancellation_token,
+            terminal_channel_cancellation_token.clone(),
         );
 
     let explicitly_disable_kitty_keyboard_protocol = state
@@ -218,10 +233,32 @@ async fn handle_ws_terminal(
         .map(|e| !e)
         .unwrap_or(false);
 
-    let _ = attachment_complete_rx.await;
+    match previous_terminal_channel_tx {
+        Some(previous_terminal_channel_tx) => {
+            // the previous connection's renderer lets go of its channel once it notices it was
+            // cancelled, after which renders can only reach this connection
+            let _ = tokio::time::timeout(
+                Duration::from_secs(1),
+                previous_terminal_channel_tx.closed(),
+            )
+            .await;
+            os_input.send_to_server(ClientToServerMsg::ClientReconnected);
+        },
+        None => {
+            let _ = attachment_complete_rx.await;
+        },
+    }
 
     let mut mouse_old_event = MouseEvent::new();
-    while let Some(Ok(msg)) = client_terminal_channel_rx.next().await {
+    let mut closed_by_client = false;
+    loop {
+        let msg = tokio::select! {
+            _ = terminal_channel_cancellation_token.cancelled() => break,
+            msg = client_terminal_channel_rx.next() => msg,
+        };
+        let Some(Ok(msg)) = msg else {
+            break;
+        };
         match msg {
             Message::Binary(buf) => {
                 let Some(client_connection) = state
@@ -265,6 +302,7 @@ async fn handle_ws_terminal(
                     .lock()
                     .unwrap()
                     .remove_client(&web_client_id);
+                closed_by_client = true;
                 break;
             },
             // TODO: support Message::Binary
@@ -273,5 +311,49 @@ async fn handle_ws_terminal(
             },
         }
     }
+    if !closed_by_client {
+        let disconnection = state
+            .connection_table
+            .lock()
+            .unwrap()
+            .client_terminal_disconnected(&web_client_id, terminal_connection);
+        match disconnection {
+            TerminalDisconnection::ClientRemoved => {},
+            TerminalDisconnection::Superseded => {
+                // the client's new connection carries on with it
+                return;
+            },
+            TerminalDisconnection::AwaitingReconnection(reconnection_cancellation_token) => {
+                log::info!(
+                    "Terminal WebSocket of {} dropped, waiting for it to reconnect",
+                    web_client_id
+                );
+                let grace_period = Duration::from_secs(RECONNECTION_GRACE_PERIOD_SECS);
+                if tokio::time::timeout(grace_period, reconnection_cancellation_token.cancelled())
+                    .await
+                    .is_ok()
+                {
+                    // either the client reconnected or it was removed in the meantime
+                    if state
+                        .connection_table
+                        .lock()
+                        .unwrap()
+                        .get_client_os_api(&web_client_id)
+                        .is_some()
+                    {
+                        return;
+                    }
+                } else {
+                    let mut connection_table = state.connection_table.lock().unwrap();
+                    // it might have reconnected just as the grace period ended
+                    if reconnection_cancellation_token.is_cancelled() {
+                        return;
+                    }
+                    log::info!("{} did not reconnect in time", web_client_id);
+                    connection_table.remove_client(&web_client_id);
+                }
+            },
+        }
+    }
     os_input.send_to_server(ClientToServerMsg::ClientExited);
 }
@@ -1,12 +1,15 @@
 #[allow(unused_imports)] // some imports used only with web_server_capability feature
 use zellij_utils::consts::{
     session_info_cache_file_name, session_info_folder_for_session, session_layout_cache_file_name,
-    VERSION, ZELLIJ_SESSION_INFO_CACHE_DIR, ZELLIJ_SOCK_DIR,
+    session_plugin_states_cache_file_name, SNAPSHOT_LAYOUT_FILE_NAME, VERSION,
+    ZELLIJ_SESSION_INFO_CACHE_DIR, ZELLIJ_SOCK_DIR,
 };
 #[allow(unused_imports)]
-use zellij_utils::data::{Event, HttpVerb, SessionInfo, WebServerStatus};
+use zellij_utils::data::{Event, HttpVerb, SessionInfo, SessionSnapshot, WebServerStatus};
 use zellij_utils::errors::{prelude::*, BackgroundJobContext, ContextType};
+use zellij_utils::input::hooks::{Hook, HookContext, HookEvent, Hooks};
 use zellij_utils::input::layout::RunPlugin;
+use zellij_utils::ipc::ServerToClientMsg;
 #[allow(unused_imports)]
 use zellij_utils::shared::parse_base_url;
 
@@ -23,19 +26,21 @@ use isahc::{config::RedirectPolicy, HttpClient, Request};
 use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::io::Write;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::{
     atomic::{AtomicBool, Ordering},
     Arc, Mutex,
 };
 use std::time::{Duration, Instant};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
 use zellij_utils::consts::is_ipc_socket;
 
 use crate::panes::PaneId;
-use crate::plugins::{PluginId, PluginInstruction};
+use crate::plugins::{PluginCpuUsage, PluginId, PluginInstruction};
 use crate::pty::PtyInstruction;
 use crate::screen::ScreenInstruction;
-use crate::thread_bus::Bus;
+use crate::thread_bus::{Bus, ThreadSenders};
 use crate::{ClientId, ServerInstruction};
 
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
@@ -47,6 +52,7 @@ pub enum BackgroundJob {
     ReportSessionInfo(String, SessionInfo),               // String - session name
     ReportPluginList(BTreeMap<PluginId, RunPlugin>),      // String - session name
     ReportLayoutInfo((String, BTreeMap<String, String>)), // BTreeMap<file_name, pane_contents>
+    ReportPluginStates(String),                           // serialized plugin states
     RunCommand(
         PluginId,
         ClientId,
@@ -56,6 +62,21 @@ pub enum BackgroundJob {
         PathBuf,
         BTreeMap<String, String>,
     ), // command, args, env_variables, cwd, context
+    SpawnProcess(
+        PluginId,
+        ClientId,
+        u32, // process id
+        String,
+        Vec<String>,
+        BTreeMap<String, String>,
+        PathBuf,
+        BTreeMap<String, String>,
+    ), // command, args, env_variables, cwd, context
+    WriteToProcessStdin(PluginId, u32, Vec<u8>),          // u32 - process id
+    CloseProcessStdin(PluginId, u32),                     // u32 - process id
+    KillProcess(PluginId, u32),                           // u32 - process id
+    SignalProcess(PluginId, u32, String),                 // u32 - process id, String - signal name
+    KillPluginProcesses(PluginId),
     WebRequest(
         PluginId,
         ClientId,
@@ -75,6 +96,16 @@ pub enum BackgroundJob {
     StopFlashPaneBell(Vec<PaneId>),
     FlashTabBell(usize),     // usize = tab_id
     StopFlashTabBell(usize), // usize = tab_id
+    RunHooks(HookEvent, HookContext),
+    ReconfigureHooks(Hooks),
+    SendTerminalToSession {
+        terminal_id: u32,
+        session_name: String,
+        pane_title: Option<String>,
+        scrollback: String,
+        child_pid: Option<u32>,
+        client_id: ClientId,
+    },
     Exit,
 }
 
@@ -91,7 +122,14 @@ impl From<&BackgroundJob> for BackgroundJobContext {
             },
             BackgroundJob::ReportSessionInfo(..) => BackgroundJobContext::ReportSessionInfo,
             BackgroundJob::ReportLayoutInfo(..) => BackgroundJobContext::ReportLayoutInfo,
+            BackgroundJob::ReportPluginStates(..) => BackgroundJobContext::ReportPluginStates,
             BackgroundJob::RunCommand(..) => BackgroundJobContext::RunCommand,
+            BackgroundJob::SpawnProcess(..) => BackgroundJobContext::SpawnProcess,
+            BackgroundJob::WriteToProcessStdin(..) => BackgroundJobContext::WriteToProcessStdin,
+            BackgroundJob::CloseProcessStdin(..) => BackgroundJobContext::CloseProcessStdin,
+            BackgroundJob::KillProcess(..) => BackgroundJobContext::KillProcess,
+            BackgroundJob::SignalProcess(..) => BackgroundJobContext::SignalProcess,
+            BackgroundJob::KillPluginProcesses(..) => BackgroundJobContext::KillPluginProcesses,
             BackgroundJob::WebRequest(..) => BackgroundJobContext::WebRequest,
             BackgroundJob::ReportPluginList(..) => BackgroundJobContext::ReportPluginList,
             BackgroundJob::RenderToClients => BackgroundJobContext::ReportPluginList,
@@ -106,6 +144,11 @@ impl From<&BackgroundJob> for BackgroundJobContext {
             BackgroundJob::StopFlashPaneBell(..) => BackgroundJobContext::StopFlashPaneBell,
             BackgroundJob::FlashTabBell(..) => BackgroundJobContext::FlashTabBell,
             BackgroundJob::StopFlashTabBell(..) => BackgroundJobContext::StopFlashTabBell,
+            BackgroundJob::RunHooks(..) => BackgroundJobContext::RunHooks,
+            BackgroundJob::ReconfigureHooks(..) => BackgroundJobContext::ReconfigureHooks,
+            BackgroundJob::SendTerminalToSession { .. } => {
+                BackgroundJobContext::SendTerminalToSession
+            },
             BackgroundJob::Exit => BackgroundJobContext::Exit,
         }
     }
@@ -125,6 +168,8 @@ pub(crate) fn background_jobs_main(
     serialization_interval: Option<u64>,
     disable_session_metadata: bool,
     web_server_base_url: String,
+    plugin_cpu_usage: PluginCpuUsage,
+    mut hooks: Hooks,
 ) -> Result<()> {
     let err_context = || "failed to write to pty".to_string();
     let mut running_jobs: HashMap<BackgroundJob, Instant> = HashMap::new();
@@ -134,6 +179,9 @@ pub(crate) fn background_jobs_main(
     let current_session_plugin_list: Arc<Mutex<BTreeMap<PluginId, RunPlugin>>> =
         Arc::new(Mutex::new(BTreeMap::new()));
     let current_session_layout = Arc::new(Mutex::new((String::new(), BTreeMap::new())));
+    // None until a plugin reports its state, so that we don't overwrite states saved by a
+    // previous incarnation of this session before its plugins had a chance to restore them
+    let current_session_plugin_states: Arc<Mutex<Option<String>>> = Arc::new(Mutex::new(None));
     let last_serialization_time = Arc::new(Mutex::new(Instant::now()));
     let serialization_interval = serialization_interval.map(|s| s * 1000); // convert to
                                                                            // milliseconds
@@ -142,6 +190,9 @@ pub(crate) fn background_jobs_main(
         Arc::new(Mutex::new(HashMap::new()));
     let mut flashing_pane_bells: HashMap<PaneId, Arc<AtomicBool>> = HashMap::new();
     let mut flashing_tab_bells: HashMap<usize, Arc<AtomicBool>> = HashMap::new();
+    let spawned_processes: Arc<Mutex<HashMap<u32, SpawnedProcess>>> =
+        Arc::new(Mutex::new(HashMap::new())); // u32 - process id
+    let mut running_hooks: Vec<tokio::task::JoinHandle<()>> = vec![];
 
     let http_client = HttpClient::builder()
         // TODO: timeout?
@@ -225,6 +276,9 @@ pub(crate) fn background_jobs_main(
                     .senders
                     .send_to_plugin(PluginInstruction::UpdateSessionSaveTime(timestamp_millis));
             },
+            BackgroundJob::ReportPluginStates(plugin_states) => {
+                *current_session_plugin_states.lock().unwrap() = Some(plugin_states);
+            },
             BackgroundJob::ReadAllSessionInfosOnMachine => {
                 // this job should only be run once and it keeps track of other sessions (as well
                 // as this one's) infos (metadata mostly) and sends it to the screen which in turn
@@ -238,7 +292,9 @@ pub(crate) fn background_jobs_main(
                     let current_session_info = current_session_info.clone();
                     let current_session_name = current_session_name.clone();
                     let current_session_layout = current_session_layout.clone();
+                    let current_session_plugin_states = current_session_plugin_states.clone();
                     let current_session_plugin_list = current_session_plugin_list.clone();
+                    let plugin_cpu_usage = plugin_cpu_usage.clone();
                     let last_serialization_time = last_serialization_time.clone();
                     async move {
                         loop {
@@ -248,12 +304,20 @@ pub(crate) fn background_jobs_main(
                             let available_layouts = current_session_info.available_layouts.clone();
                             let current_session_layout =
                                 current_session_layout.lock().unwrap().clone();
+                            let current_session_plugin_states =
+                                current_session_plugin_states.lock().unwrap().clone();
                             if !disable_session_metadata {
                                 write_session_state_to_disk(
                                     current_session_name.clone(),
                                     current_session_info,
                                     current_session_layout,
                                 );
+                                if let Some(plugin_states) = current_session_plugin_states {
+                                    write_plugin_states_to_disk(
+                                        &current_session_name,
+                                        &plugin_states,
+                                    );
+                                }
 
                                 // Send SavedCurrentSession instruction to plugin thread
                                 let _timestamp_millis = std::time::SystemTime::now()
@@ -270,6 +334,9 @@ pub(crate) fn background_jobs_main(
                                     let current_session_plugin_list =
                                         current_session_plugin_list.lock().unwrap().clone();
                                     session_info.populate_plugin_list(current_session_plugin_list);
+                                    session_info.populate_plugin_cpu_usage(
+                                        plugin_cpu_usage.lock().unwrap().clone(),
+                                    );
                                     // these are not serialized, so must be explicitly added
                                     session_info.available_layouts = available_layouts.clone();
                                 }
@@ -350,6 +417,86 @@ pub(crate) fn background_jobs_main(
                     }
                 });
             },
+            BackgroundJob::SpawnProcess(
+                plugin_id,
+                client_id,
+                process_id,
+                command,
+                args,
+                env_variables,
+                cwd,
+                context,
+            ) => {
+                let (control_sender, control_receiver) = tokio::sync::mpsc::unbounded_channel();
+                spawned_processes.lock().unwrap().insert(
+                    process_id,
+                    SpawnedProcess {
+                        plugin_id,
+                        control: control_sender,
+                    },
+                );
+                runtime.spawn({
+                    let senders = bus.senders.clone();
+                    let spawned_processes = spawned_processes.clone();
+                    async move {
+                        run_spawned_process(
+                            senders,
+                            SpawnedProcessArgs {
+                                plugin_id,
+                                client_id,
+                                process_id,
+                                command,
+                                args,
+                                env_variables,
+                                cwd,
+                                context,
+                            },
+                            control_receiver,
+                        )
+                        .await;
+                        spawned_processes.lock().unwrap().remove(&process_id);
+                    }
+                });
+            },
+            BackgroundJob::WriteToProcessStdin(plugin_id, process_id, bytes) => {
+                send_to_spawned_process(
+                    &spawned_processes,
+                    plugin_id,
+                    process_id,
+                    SpawnedProcessControl::Write(bytes),
+                );
+            },
+            BackgroundJob::CloseProcessStdin(plugin_id, process_id) => {
+                send_to_spawned_process(
+                    &spawned_processes,
+                    plugin_id,
+                    process_id,
+                    SpawnedProcessControl::CloseStdin,
+                );
+            },
+            BackgroundJob::KillProcess(plugin_id, process_id) => {
+                send_to_spawned_process(
+                    &spawned_processes,
+                    plugin_id,
+                    process_id,
+                    SpawnedProcessControl::Kill,
+                );
+            },
+            BackgroundJob::SignalProcess(plugin_id, process_id, signal) => {
+                send_to_spawned_process(
+                    &spawned_processes,
+                    plugin_id,
+                    process_id,
+                    SpawnedProcessControl::Signal(signal),
+                );
+            },
+            BackgroundJob::KillPluginProcesses(plugin_id) => {
+                for spawned_process in spawned_processes.lock().unwrap().values() {
+                    if spawned_process.plugin_id == plugin_id {
+                        let _ = spawned_process.control.send(SpawnedProcessControl::Kill);
+                    }
+                }
+            },
             BackgroundJob::WebRequest(plugin_id, client_id, url, verb, headers, body, context) => {
                 runtime.spawn({
                     let senders = bus.senders.clone();
@@ -633,11 +780,83 @@ pub(crate) fn background_jobs_main(
                     .senders
                     .send_to_screen(ScreenInstruction::SetTabBellFlash(tab_id, false));
             },
+            BackgroundJob::RunHooks(hook_event, hook_context) => {
+                running_hooks.retain(|running_hook| !running_hook.is_finished());
+                let mut env_variables = hook_context.env_variables(hook_event);
+                let session_name = current_session_name.lock().unwrap().to_owned();
+                if !session_name.is_empty() {
+                    env_variables.insert("ZELLIJ_SESSION_NAME".to_owned(), session_name);
+                }
+                for hook in hooks.for_event(hook_event) {
+                    running_hooks
+                        .push(runtime.spawn(run_hook(hook.clone(), env_variables.clone())));
+                }
+            },
+            BackgroundJob::ReconfigureHooks(new_hooks) => {
+                hooks = new_hooks;
+            },
+            BackgroundJob::SendTerminalToSession {
+                terminal_id,
+                session_name,
+                pane_title,
+                scrollback,
+                child_pid,
+                client_id,
+            } => {
+                // this waits for the other session to pick up the terminal
+                runtime.spawn_blocking({
+                    let senders = bus.senders.clone();
+                    let os_input = bus.os_input.clone();
+                    move || {
+                        let result = os_input
+                            .as_ref()
+                            .context("no OS I/O interface found")
+                            .and_then(|os_input| {
+                                os_input.send_terminal_to_session(
+                                    terminal_id,
+                                    &session_name,
+                                    pane_title,
+                                    scrollback,
+                                    child_pid,
+                                )
+                            });
+                        if let Err(e) = &result {
+                            let error_text =
+                                format!("Failed to send pane to session {}: {:#}", session_name, e);
+                            log::error!("{}", error_text);
+                            if let Some(os_input) = os_input.as_ref() {
+                                let _ = os_input.send_to_client(
+                                    client_id,
+                                    ServerToClientMsg::LogError {
+                                        lines: vec![error_text],
+                                    },
+                                );
+                            }
+                        }
+                        let _ = senders.send_to_pty(PtyInstruction::TerminalSentToSession {
+                            terminal_id,
+                            sent: result.is_ok(),
+                        });
+                    }
+                });
+            },
             BackgroundJob::Exit => {
                 for loading_plugin in loading_plugins.values() {
                     loading_plugin.store(false, Ordering::SeqCst);
                 }
 
+                // hooks are killed once they time out, so this does not block for long - we wait
+                // for them so that eg. session_killed hooks get to run before the server exits
+                runtime.block_on(async {
+                    for running_hook in running_hooks.drain(..) {
+                        let _ = running_hook.await;
+                    }
+                });
+
+                for spawned_process in spawned_processes.lock().unwrap().values() {
+                    let _ = spawned_process.control.send(SpawnedProcessControl::Kill);
+                }
+
                 let cache_file_name =
                     session_info_cache_file_name(&current_session_name.lock().unwrap().to_owned());
                 let _ = std::fs::remove_file(cache_file_name);
@@ -647,6 +866,275 @@ pub(crate) fn background_jobs_main(
     }
 }
 
+async fn run_hook(hook: Hook, env_variables: BTreeMap<String, String>) {
+    let output = tokio::time::timeout(
+        hook.timeout(),
+        hook_shell_command(&hook.command)
+            .envs(env_variables)
+            .stdin(std::process::Stdio::null())
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::piped())
+            .kill_on_drop(true)
+            .output(),
+    )
+    .await;
+    match output {
+        Ok(Ok(output)) if !output.status.success() => {
+            log::error!(
+                "Hook for {} failed ({}): {}",
+                hook.event,
+                hook.command,
+                String::from_utf8_lossy(&output.stderr)
+            );
+        },
+        Ok(Ok(_)) => {},
+        Ok(Err(e)) => {
+            log::error!(
+                "Failed to run hook for {} ({}): {}",
+                hook.event,
+                hook.command,
+                e
+            );
+        },
+        Err(_) => {
+            log::error!(
+                "Hook for {} timed out after {}s and was killed ({})",
+                hook.event,
+                hook.timeout().as_secs(),
+                hook.command
+            );
+        },
+    }
+}
+
+#[cfg(not(windows))]
+fn hook_shell_command(command: &str) -> tokio::process::Command {
+    let mut shell_command = tokio::process::Command::new("sh");
+    shell_command.arg("-c").arg(command);
+    shell_command
+}
+
+#[cfg(windows)]
+fn hook_shell_command(command: &str) -> tokio::process::Command {
+    let mut shell_command = tokio::process::Command::new("cmd");
+    shell_command.arg("/C").arg(command);
+    shell_command
+}
+
+struct SpawnedProcess {
+    plugin_id: PluginId,
+    control: UnboundedSender<SpawnedProcessControl>,
+}
+
+struct SpawnedProcessArgs {
+    plugin_id: PluginId,
+    client_id: ClientId,
+    process_id: u32,
+    command: String,
+    args: Vec<String>,
+    env_variables: BTreeMap<String, String>,
+    cwd: PathBuf,
+    context: BTreeMap<String, String>,
+}
+
+enum SpawnedProcessControl {
+    Write(Vec<u8>),
+    CloseStdin,
+    Signal(String),
+    Kill,
+}
+
+fn send_to_spawned_process(
+    spawned_processes: &Arc<Mutex<HashMap<u32, SpawnedProcess>>>,
+    plugin_id: PluginId,
+    process_id: u32,
+    control: SpawnedProcessControl,
+) {
+    match spawned_processes.lock().unwrap().get(&process_id) {
+        // plugins may only control processes they spawned themselves
+        Some(spawned_process) if spawned_process.plugin_id == plugin_id => {
+            let _ = spawned_process.control.send(control);
+        },
+        _ => {
+            log::error!(
+                "Plugin {} has no running process with id {}",
+                plugin_id,
+                process_id
+            );
+        },
+    }
+}
+
+async fn run_spawned_process(
+    senders: ThreadSenders,
+    spawned_process_args: SpawnedProcessArgs,
+    mut control_receiver: UnboundedReceiver<SpawnedProcessControl>,
+) {
+    let SpawnedProcessArgs {
+        plugin_id,
+        client_id,
+        process_id,
+        command,
+        args,
+        env_variables,
+        cwd,
+        context,
+    } = spawned_process_args;
+    let send_event = move |senders: &ThreadSenders, event: Event| {
+        let _ = senders.send_to_plugin(PluginInstruction::Update(vec![(
+            Some(plugin_id),
+            Some(client_id),
+            event,
+        )]));
+    };
+    let mut child = match tokio::process::Command::new(&command)
+        .args(&args)
+        .envs(env_variables)
+        .current_dir(cwd)
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+    {
+        Ok(child) => child,
+        Err(e) => {
+            log::error!("Failed to spawn process: {}", e);
+            send_event(
+                &senders,
+                Event::SpawnedProcessStderr(
+                    process_id,
+                    format!("{}", e).as_bytes().to_vec(),
+                    context.clone(),
+                ),
+            );
+            send_event(
+                &senders,
+                Event::SpawnedProcessExited(process_id, None, context),
+            );
+            return;
+        },
+    };
+    let mut stdin = child.stdin.take();
+    let stdout_reader = child.stdout.take().map(|stdout| {
+        let senders = senders.clone();
+        let context = context.clone();
+        tokio::spawn(async move {
+            forward_process_output(stdout, |bytes| {
+                send_event(
+                    &senders,
+                    Event::SpawnedProcessStdout(process_id, bytes, context.clone()),
+                )
+            })
+            .await
+        })
+    });
+    let stderr_reader = child.stderr.take().map(|stderr| {
+        let senders = senders.clone();
+        let context = context.clone();
+        tokio::spawn(async move {
+            forward_process_output(stderr, |bytes| {
+                send_event(
+                    &senders,
+                    Event::SpawnedProcessStderr(process_id, bytes, context.clone()),
+                )
+            })
+            .await
+        })
+    });
+    let mut control_closed = false;
+    let exit_code = loop {
+        tokio::select! {
+            status = child.wait() => {
+                break match status {
+                    Ok(status) => status.code(),
+                    Err(e) => {
+                        log::error!("Failed to wait for process {}: {}", process_id, e);
+                        None
+                    },
+                };
+            },
+            control = control_receiver.recv(), if !control_closed => {
+                match control {
+                    Some(SpawnedProcessControl::Write(bytes)) => {
+                        if let Some(stdin) = stdin.as_mut() {
+                            if let Err(e) = stdin.write_all(&bytes).await {
+                                log::error!("Failed to write to process {}: {}", process_id, e);
+                            }
+                        }
+                    },
+                    Some(SpawnedProcessControl::CloseStdin) => {
+                        // dropping the handle closes the pipe, so the process sees the end of its input
+                        stdin = None;
+                    },
+                    Some(SpawnedProcessControl::Signal(signal)) => {
+                        signal_spawned_process(&mut child, &signal);
+                    },
+                    Some(SpawnedProcessControl::Kill) => {
+                        let _ = child.start_kill();
+                    },
+                    None => {
+                        control_closed = true;
+                    },
+                }
+            },
+        }
+    };
+    // make sure all of the output reaches the plugin before it is told the process exited
+    for reader in [stdout_reader, stderr_reader].into_iter().flatten() {
+        let _ = reader.await;
+    }
+    send_event(
+        &senders,
+        Event::SpawnedProcessExited(process_id, exit_code, context),
+    );
+}
+
+async fn forward_process_output(
+    mut output: impl tokio::io::AsyncRead + Unpin,
+    mut forward: impl FnMut(Vec<u8>),
+) {
+    let mut buf = [0; 4096];
+    loop {
+        match output.read(&mut buf).await {
+            Ok(0) | Err(_) => break,
+            Ok(n) => forward(buf[..n].to_vec()),
+        }
+    }
+}
+
+#[cfg(unix)]
+fn signal_spawned_process(child: &mut tokio::process::Child, signal: &str) {
+    use nix::sys::signal::{kill, Signal};
+    use nix::unistd::Pid;
+    use std::str::FromStr;
+    let signal_name = signal.to_uppercase();
+    let signal_name = if signal_name.starts_with("SIG") {
+        signal_name
+    } else {
+        format!("SIG{}", signal_name)
+    };
+    match (Signal::from_str(&signal_name), child.id()) {
+        (Ok(signal), Some(pid)) => {
+            if let Err(e) = kill(Pid::from_raw(pid as i32), signal) {
+                log::error!("Failed to send {} to process: {}", signal_name, e);
+            }
+        },
+        (Err(_), _) => log::error!("Unknown signal: {}", signal),
+        (_, None) => {}, // process already exited
+    }
+}
+
+#[cfg(windows)]
+fn signal_spawned_process(child: &mut tokio::process::Child, signal: &str) {
+    match signal.to_uppercase().trim_start_matches("SIG") {
+        "KILL" | "TERM" | "INT" => {
+            let _ = child.start_kill();
+        },
+        _ => log::error!("Signal {} is not supported on this platform", signal),
+    }
+}
+
 fn job_already_running(
     job: BackgroundJob,
     running_jobs: &mut HashMap<BackgroundJob, Instant>,
@@ -702,6 +1190,45 @@ pub fn write_session_state_to_disk(
     }
 }
 
+// a snapshot by the same name is replaced rather than merged, so that pane contents files of the
+// previous snapshot don't linger
+pub fn write_snapshot_to_disk(
+    snapshots_folder: &Path,
+    snapshot_name: &str,
+    snapshot_layout: (String, BTreeMap<String, String>),
+) -> Result<(), String> {
+    // the snapshot folder is about to be removed, so we can't rely on clients having checked
+    // that its name isn't a path
+    SessionSnapshot::validate_name(snapshot_name)?;
+    let (snapshot_layout, layout_files_to_write) = snapshot_layout;
+    let snapshot_folder = snapshots_folder.join(snapshot_name);
+    if snapshot_folder.exists() {
+        std::fs::remove_dir_all(&snapshot_folder)
+            .map_err(|e| format!("Failed to replace snapshot {:?}: {}", snapshot_name, e))?;
+    }
+    std::fs::create_dir_all(&snapshot_folder)
+        .and_then(|_| std::fs::File::create(snapshot_folder.join(SNAPSHOT_LAYOUT_FILE_NAME)))
+        .and_then(|mut f| write!(f, "{}", snapshot_layout))
+        .and_then(|_| {
+            for (external_file_name, external_file_contents) in layout_files_to_write {
+                std::fs::File::create(snapshot_folder.join(external_file_name))
+                    .and_then(|mut f| write!(f, "{}", external_file_contents))?;
+            }
+            Ok(())
+        })
+        .map_err(|e| format!("Failed to save snapshot {:?}: {}", snapshot_name, e))
+}
+
+pub fn write_plugin_states_to_disk(current_session_name: &str, plugin_states: &str) {
+    let _wrote_plugin_states_file =
+        std::fs::create_dir_all(session_info_folder_for_session(current_session_name).as_path())
+            .and_then(|_| {
+                std::fs::File::create(session_plugin_states_cache_file_name(current_session_name))
+            })
+            .and_then(|mut f| write!(f, "{}", plugin_states))
+            .map_err(|e| log::error!("Failed to write plugin states file: {:?}", e));
+}
+
 fn read_other_live_session_states(current_session_name: &str) -> BTreeMap<String, SessionInfo> {
     let mut other_session_names: Vec<(String, Duration)> = vec![];
     let mut session_infos_on_machine = BTreeMap::new();
@@ -823,3 +1350,7 @@ fn query_webserver_via_ipc(web_server_base_url: &str) -> Result<WebServerStatus>
 
     Ok(WebServerStatus::Offline)
 }
+
+#[cfg(test)]
+#[path = "./unit/background_jobs_tests.rs"]
+mod background_jobs_tests;
@@ -0,0 +1,165 @@
+use std::collections::VecDeque;
+use std::path::PathBuf;
+
+use zellij_utils::data::ClosedPaneInfo;
+use zellij_utils::input::command::RunCommand;
+use zellij_utils::shared::strip_ansi_codes;
+
+/// How many closed panes are kept, the oldest one is dropped when another pane is closed
+const MAX_CLOSED_PANES: usize = 10;
+
+/// How many lines above the viewport are kept when a pane is closed, serializing the whole
+/// scrollback of a long-running pane would stall the screen thread
+pub const MAX_CLOSED_PANE_SCROLLBACK_LINES: usize = 1000;
+
+/// A closed terminal pane with everything needed to show it again
+#[derive(Debug, Clone, Default)]
+pub struct ClosedPane {
+    pub id: u32, // assigned when added to the history
+    pub title: String,
+    pub run_command: Option<RunCommand>, // None if this was a shell
+    pub cwd: Option<PathBuf>,
+    pub exit_status: Option<i32>,
+    pub serialized_scrollback: String, // including styling, replayed into the reopened pane
+}
+
+impl ClosedPane {
+    pub fn info(&self) -> ClosedPaneInfo {
+        ClosedPaneInfo {
+            id: self.id,
+            title: self.title.clone(),
+            command: self.run_command.as_ref().map(|c| c.to_string()),
+            cwd: self.cwd.clone(),
+            exit_status: self.exit_status,
+        }
+    }
+    /// The scrollback as plain text lines, without the trailing empty ones
+    pub fn scrollback_lines(&self) -> Vec<String> {
+        let scrollback = strip_ansi_codes(&self.serialized_scrollback);
+        // every serialized line starts with a line break
+        let mut scrollback_lines: Vec<String> = scrollback
+            .strip_prefix('\n')
+            .unwrap_or(&scrollback)
+            .lines()
+            .map(|line| line.trim_end().to_owned())
+            .collect();
+        while scrollback_lines
+            .last()
+            .map(|l| l.is_empty())
+            .unwrap_or(false)
+        {
+            scrollback_lines.pop();
+        }
+        scrollback_lines
+    }
+}
+
+/// The recently closed terminal panes of the session, most recently closed first
+#[derive(Debug, Default)]
+pub struct ClosedPanes {
+    panes: VecDeque<ClosedPane>,
+    last_id: u32,
+}
+
+impl ClosedPanes {
+    pub fn add(&mut self, mut closed_pane: ClosedPane) {
+        self.last_id += 1;
+        closed_pane.id = self.last_id;
+        self.panes.push_front(closed_pane);
+        self.panes.truncate(MAX_CLOSED_PANES);
+    }
+    /// Removes a pane from the history in order to reopen it, the most recently closed one if no
+    /// id is given
+    pub fn take(&mut self, id: Option<u32>) -> Option<ClosedPane> {
+        match id {
+            Some(id) => {
+                let index = self.panes.iter().position(|p| p.id == id)?;
+                self.panes.remove(index)
+            },
+            None => self.panes.pop_front(),
+        }
+    }
+    pub fn list(&self) -> Vec<ClosedPaneInfo> {
+        self.panes.iter().map(|p| p.info()).collect()
+    }
+    pub fn scrollback_lines(&self, id: u32) -> Option<Vec<String>> {
+        self.panes
+            .iter()
+            .find(|p| p.id == id)
+            .map(|p| p.scrollback_lines())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn closed_pane(title: &str) -> ClosedPane {
+        ClosedPane {
+            title: title.to_owned(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn most_recently_closed_pane_is_reopened_first() {
+        let mut closed_panes = ClosedPanes::default();
+        closed_panes.add(closed_pane("first"));
+        closed_panes.add(closed_pane("second"));
+        let titles: Vec<String> = closed_panes.list().into_iter().map(|p| p.title).collect();
+        assert_eq!(titles, vec!["second".to_owned(), "first".to_owned()]);
+        assert_eq!(
+            closed_panes.take(None).map(|p| p.title),
+            Some("second".into())
+        );
+        assert_eq!(
+            closed_panes.take(None).map(|p| p.title),
+            Some("first".into())
+        );
+        assert!(closed_panes.take(None).is_none());
+    }
+
+    #[test]
+    fn closed_pane_can_be_reopened_by_id() {
+        let mut closed_panes = ClosedPanes::default();
+        closed_panes.add(closed_pane("first"));
+        closed_panes.add(closed_pane("second"));
+        let first_id = closed_panes.list()[1].id;
+        assert_eq!(
+            closed_panes.take(Some(first_id)).map(|p| p.title),
+            Some("first".into())
+        );
+        assert!(closed_panes.take(Some(first_id)).is_none());
+        assert_eq!(closed_panes.list().len(), 1);
+    }
+
+    #[test]
+    fn closed_pane_history_is_bounded() {
+        let mut closed_panes = ClosedPanes::default();
+        for i in 0..MAX_CLOSED_PANES + 5 {
+            closed_panes.add(closed_pane(&format!("pane {}", i)));
+        }
+        let closed_pane_list = closed_panes.list();
+        assert_eq!(closed_pane_list.len(), MAX_CLOSED_PANES);
+        assert_eq!(
+            closed_pane_list.last().map(|p| p.title.clone()),
+            Some("pane 5".to_owned())
+        );
+    }
+
+    #[test]
+    fn closed_pane_scrollback_is_given_as_plain_text() {
+        let mut closed_panes = ClosedPanes::default();
+        closed_panes.add(ClosedPane {
+            serialized_scrollback:
+                "\n\r\u{1b}[m\u{1b}[31m$ cargo test\u{1b}[m   \n\r\u{1b}[mok\n\r\n\r   ".to_owned(),
+            ..Default::default()
+        });
+        let id = closed_panes.list()[0].id;
+        assert_eq!(
+            closed_panes.scrollback_lines(id),
+            Some(vec!["$ cargo test".to_owned(), "ok".to_owned()])
+        );
+        assert!(closed_panes.scrollback_lines(id + 1).is_none());
+    }
+}
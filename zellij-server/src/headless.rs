@@ -0,0 +1,440 @@
+//! A headless harness that runs a whole session in-process, for testing plugins and layouts.
+//!
+//! [`HeadlessSession`] boots the server with a [`FakeInputOutput`] and attaches a single virtual
+//! client of a given size to it. Tests can then send it keys, mouse events and actions, and
+//! assert on the text the client would have on its screen, on the events plugins received or on
+//! what plugins piped out.
+//!
+//! ```no_run
+//! use zellij_server::headless::HeadlessSession;
+//! use zellij_utils::data::{BareKey, KeyWithModifier, LayoutInfo};
+//! use zellij_utils::pane_size::Size;
+//!
+//! let layout = LayoutInfo::Stringified(
+//!     r#"layout { pane; pane size=1 { plugin location="zellij:status-bar"; }; }"#.to_owned(),
+//! );
+//! let mut session = HeadlessSession::start(Size { rows: 20, cols: 80 }, Some(layout)).unwrap();
+//! session.wait_for_text("LOCK", std::time::Duration::from_secs(10)).unwrap();
+//! session
+//!     .send_key(KeyWithModifier::new(BareKey::Char('p')).with_ctrl_modifier())
+//!     .unwrap();
+//! ```
+//!
+//! Permissions granted to plugins are cached in the session's temporary folder rather than on
+//! the machine, so each session asks for them anew.
+//!
+//! Plugin timers run on a simulated clock, which only moves with
+//! [`HeadlessSession::advance_time`]. Everything else (eg. rendering) happens in the background
+//! as it would in a real session, so tests wait for its outcome with the `wait_for_*` methods.
+
+use crate::{
+    os_input_output_fake::FakeInputOutput,
+    panes::{grid::Grid, link_handler::LinkHandler, sixel::SixelImageStore},
+    plugins::PluginId,
+    server_thread_main, ClientId, ServerInstruction,
+};
+
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, HashMap},
+    path::Path,
+    rc::Rc,
+    thread,
+    time::{Duration, Instant},
+};
+use zellij_utils::{
+    channels::{self, ChannelWithContext, SenderWithContext},
+    consts::ipc_connect,
+    data::{Event, KeyWithModifier, LayoutInfo, Palette, Style},
+    errors::prelude::*,
+    input::{
+        actions::Action, cli_assets::CliAssets, command::TerminalAction, mouse::MouseEvent,
+        options::Options,
+    },
+    ipc::{ClientToServerMsg, ExitReason, IpcSenderWithContext, ServerToClientMsg},
+    pane_size::{Size, SizeInPixels},
+};
+
+#[cfg(windows)]
+use interprocess::local_socket::Stream as LocalSocketStream;
+
+// the first client to connect to a server always gets this id
+const HEADLESS_CLIENT_ID: ClientId = 1;
+const SERVER_START_TIMEOUT: Duration = Duration::from_secs(10);
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+pub struct HeadlessSession {
+    os_input: FakeInputOutput,
+    client: IpcSenderWithContext<ClientToServerMsg>,
+    #[cfg(windows)]
+    _reply_stream: LocalSocketStream,
+    size: Size,
+    grid: Grid,
+    vte_parser: vte::Parser,
+    handled_messages: usize,
+    pipe_ids: usize,
+    server_thread: Option<thread::JoinHandle<()>>,
+    _session_folder: tempfile::TempDir,
+}
+
+impl HeadlessSession {
+    /// Start a session with a client of `size` and load `layout` in it (or the default layout if
+    /// `None`)
+    pub fn start(size: Size, layout: Option<LayoutInfo>) -> Result<Self> {
+        HeadlessSession::start_with_options(size, layout, Options::default())
+    }
+    /// Same as [`HeadlessSession::start`], with `options` overriding the defaults of the
+    /// harness (which disable startup tips, release notes and session serialization)
+    pub fn start_with_options(
+        size: Size,
+        layout: Option<LayoutInfo>,
+        options: Options,
+    ) -> Result<Self> {
+        HeadlessSession::start_session(size, layout, options, None)
+    }
+    /// Same as [`HeadlessSession::start`], with the session loading `config` (in the KDL of a
+    /// config file) rather than the default configuration, eg. to set up hooks
+    pub fn start_with_config(size: Size, layout: Option<LayoutInfo>, config: &str) -> Result<Self> {
+        HeadlessSession::start_session(size, layout, Options::default(), Some(config))
+    }
+    fn start_session(
+        size: Size,
+        layout: Option<LayoutInfo>,
+        options: Options,
+        config: Option<&str>,
+    ) -> Result<Self> {
+        let err_context = || "failed to start headless session";
+
+        let session_folder = tempfile::tempdir().with_context(err_context)?;
+        let config_file_path = match config {
+            Some(config) => {
+                let config_file_path = session_folder.path().join("config.kdl");
+                std::fs::write(&config_file_path, config).with_context(err_context)?;
+                Some(config_file_path)
+            },
+            None => None,
+        };
+        let socket_path = session_folder.path().join("headless");
+        let session_name = format!("headless-{}", uuid::Uuid::new_v4());
+
+        let os_input = FakeInputOutput {
+            plugin_permissions_cache_path: Some(session_folder.path().join("permissions.kdl")),
+            ..Default::default()
+        };
+        let (to_server, server_receiver): ChannelWithContext<ServerInstruction> =
+            channels::bounded(50);
+        let to_server = SenderWithContext::new(to_server);
+        let server_thread = thread::Builder::new()
+            .name("headless_server".to_string())
+            .spawn({
+                let os_input = os_input.clone();
+                let socket_path = socket_path.clone();
+                move || {
+                    server_thread_main(
+                        Box::new(os_input),
+                        socket_path,
+                        Some(session_name),
+                        to_server,
+                        server_receiver,
+                    )
+                }
+            })
+            .with_context(err_context)?;
+
+        let stream = connect_to_server(&socket_path).with_context(err_context)?;
+        #[cfg(windows)]
+        let reply_stream =
+            zellij_utils::consts::ipc_connect_reply(&socket_path).with_context(err_context)?;
+        let mut client = IpcSenderWithContext::new(stream);
+
+        let default_options = Options {
+            show_startup_tips: Some(false),
+            show_release_notes: Some(false),
+            session_serialization: Some(false),
+            disable_session_metadata: Some(true),
+            ..Default::default()
+        };
+        let cli_assets = CliAssets {
+            should_ignore_config: config_file_path.is_none(),
+            config_file_path,
+            config_dir: Some(session_folder.path().to_path_buf()),
+            configuration_options: Some(default_options.merge(options)),
+            layout,
+            terminal_window_size: size,
+            data_dir: Some(session_folder.path().join("data")),
+            ..Default::default()
+        };
+        client
+            .send_client_msg(ClientToServerMsg::FirstClientConnected {
+                cli_assets,
+                is_web_client: false,
+            })
+            .with_context(err_context)?;
+
+        Ok(HeadlessSession {
+            os_input,
+            client,
+            #[cfg(windows)]
+            _reply_stream: reply_stream,
+            size,
+            grid: new_grid(size),
+            vte_parser: vte::Parser::new(),
+            handled_messages: 0,
+            pipe_ids: 0,
+            server_thread: Some(server_thread),
+            _session_folder: session_folder,
+        })
+    }
+    /// Send a key as if it was pressed in the client's terminal
+    pub fn send_key(&mut self, key: KeyWithModifier) -> Result<()> {
+        let raw_bytes = key
+            .serialize_non_kitty()
+            .map(|k| k.into_bytes())
+            .unwrap_or_default();
+        self.client.send_client_msg(ClientToServerMsg::Key {
+            key,
+            raw_bytes,
+            is_kitty_keyboard_protocol: false,
+        })
+    }
+    /// Send a mouse event as if it happened in the client's terminal
+    pub fn send_mouse_event(&mut self, event: MouseEvent) -> Result<()> {
+        self.send_action(Action::MouseEvent { event })
+    }
+    /// Perform an action on behalf of the client, as a keybinding would
+    pub fn send_action(&mut self, action: Action) -> Result<()> {
+        self.client.send_client_msg(ClientToServerMsg::Action {
+            action,
+            terminal_id: None,
+            client_id: None,
+            is_cli_client: false,
+        })
+    }
+    /// Send a message to plugins the way `zellij pipe` does, to `plugin` if specified (launching
+    /// it if it is not running) or to all plugins otherwise. Returns the name of the pipe,
+    /// through which plugins can reply (see [`HeadlessSession::pipe_output`])
+    pub fn pipe(
+        &mut self,
+        name: &str,
+        payload: Option<String>,
+        plugin: Option<String>,
+    ) -> Result<String> {
+        self.pipe_ids += 1;
+        let pipe_id = format!("headless-pipe-{}", self.pipe_ids);
+        self.send_action(Action::CliPipe {
+            pipe_id: pipe_id.clone(),
+            name: Some(name.to_owned()),
+            payload,
+            args: None,
+            plugin,
+            configuration: None,
+            launch_new: false,
+            skip_cache: false,
+            floating: None,
+            in_place: None,
+            cwd: None,
+            pane_title: None,
+        })?;
+        Ok(pipe_id)
+    }
+    /// Resize the client's terminal
+    pub fn resize(&mut self, size: Size) -> Result<()> {
+        self.render_new_messages();
+        self.size = size;
+        self.grid.change_size(size.rows, size.cols);
+        self.client
+            .send_client_msg(ClientToServerMsg::TerminalResize { new_size: size })
+    }
+    /// Move the session's simulated clock forward by `duration`, firing the plugin timers that
+    /// are due. Plugins handle them in the background, use the `wait_for_*` methods to see what
+    /// they did
+    pub fn advance_time(&mut self, duration: Duration) {
+        self.os_input.advance_time(duration);
+    }
+    /// The text currently on the client's screen, one line per row
+    pub fn screen_text(&mut self) -> String {
+        self.render_new_messages();
+        self.grid.dump_screen(false)
+    }
+    /// Wait until `text` appears on the client's screen and return the whole screen
+    pub fn wait_for_text(&mut self, text: &str, timeout: Duration) -> Result<String> {
+        self.wait_for_screen(|screen| screen.contains(text), timeout)
+            .with_context(|| format!("text {:?} did not appear on screen", text))
+    }
+    /// Wait until the client's screen satisfies `condition` and return it
+    pub fn wait_for_screen(
+        &mut self,
+        condition: impl Fn(&str) -> bool,
+        timeout: Duration,
+    ) -> Result<String> {
+        let started_at = Instant::now();
+        loop {
+            let screen = self.screen_text();
+            if condition(&screen) {
+                return Ok(screen);
+            }
+            if started_at.elapsed() > timeout {
+                bail!("timed out after {:?}, screen was:\n{}", timeout, screen);
+            }
+            thread::sleep(POLL_INTERVAL);
+        }
+    }
+    /// Everything plugins sent to the pipe called `pipe_name` so far
+    pub fn pipe_output(&self, pipe_name: &str) -> Vec<String> {
+        self.messages()
+            .into_iter()
+            .filter_map(|message| match message {
+                ServerToClientMsg::CliPipeOutput {
+                    pipe_name: name,
+                    output,
+                } if name == pipe_name => Some(output),
+                _ => None,
+            })
+            .collect()
+    }
+    /// Wait until a plugin sends something to the pipe called `pipe_name` and return all its
+    /// output so far
+    pub fn wait_for_pipe_output(&self, pipe_name: &str, timeout: Duration) -> Result<Vec<String>> {
+        let started_at = Instant::now();
+        loop {
+            let output = self.pipe_output(pipe_name);
+            if !output.is_empty() {
+                return Ok(output);
+            }
+            if started_at.elapsed() > timeout {
+                bail!(
+                    "timed out after {:?} waiting for output on pipe {:?}",
+                    timeout,
+                    pipe_name
+                );
+            }
+            thread::sleep(POLL_INTERVAL);
+        }
+    }
+    /// The events sent to plugins so far, along with the plugin and client they were sent to
+    /// (`None` for all of them)
+    pub fn plugin_events(&self) -> Vec<(Option<PluginId>, Option<ClientId>, Event)> {
+        self.os_input.plugin_events()
+    }
+    /// Wait until an event satisfying `condition` was sent to plugins and return it
+    pub fn wait_for_plugin_event(
+        &self,
+        condition: impl Fn(&Event) -> bool,
+        timeout: Duration,
+    ) -> Result<Event> {
+        let started_at = Instant::now();
+        loop {
+            let event = self
+                .plugin_events()
+                .into_iter()
+                .map(|(_plugin_id, _client_id, event)| event)
+                .find(|event| condition(event));
+            if let Some(event) = event {
+                return Ok(event);
+            }
+            if started_at.elapsed() > timeout {
+                bail!("timed out after {:?} waiting for a plugin event", timeout);
+            }
+            thread::sleep(POLL_INTERVAL);
+        }
+    }
+    /// All messages the server sent to the client so far
+    pub fn messages(&self) -> Vec<ServerToClientMsg> {
+        self.os_input.messages_to_client(HEADLESS_CLIENT_ID)
+    }
+    /// Why the server disconnected the client, if it did
+    pub fn exit_reason(&self) -> Option<ExitReason> {
+        self.messages()
+            .into_iter()
+            .find_map(|message| match message {
+                ServerToClientMsg::Exit { exit_reason } => Some(exit_reason),
+                _ => None,
+            })
+    }
+    /// The ids of the terminals opened in this session along with what they were asked to run
+    pub fn terminals(&self) -> BTreeMap<u32, TerminalAction> {
+        self.os_input.spawned_terminals()
+    }
+    /// Feed `bytes` to the session as output of the program running in this terminal
+    pub fn write_to_terminal(&self, terminal_id: u32, bytes: &[u8]) -> Result<()> {
+        self.os_input.write_to_terminal(terminal_id, bytes)
+    }
+    /// Make the command running in this terminal exit with `exit_status`
+    pub fn exit_terminal(&self, terminal_id: u32, exit_status: Option<i32>) -> Result<()> {
+        self.os_input.exit_terminal(terminal_id, exit_status)
+    }
+    /// Everything that was written to the STDIN of this terminal so far, eg. keys typed into it
+    pub fn terminal_input(&self, terminal_id: u32) -> Vec<u8> {
+        self.os_input.terminal_input(terminal_id)
+    }
+    pub fn os_input(&self) -> &FakeInputOutput {
+        &self.os_input
+    }
+    fn render_new_messages(&mut self) {
+        let messages = self.messages();
+        for message in messages.iter().skip(self.handled_messages) {
+            if let ServerToClientMsg::Render { content } = message {
+                for byte in content.as_bytes() {
+                    self.vte_parser.advance(&mut self.grid, *byte);
+                }
+            }
+        }
+        self.handled_messages = messages.len();
+    }
+}
+
+impl Drop for HeadlessSession {
+    fn drop(&mut self) {
+        if self.exit_reason().is_none() {
+            let _ = self.client.send_client_msg(ClientToServerMsg::KillSession);
+        }
+        self.os_input.close_terminals();
+        if let Some(server_thread) = self.server_thread.take() {
+            let _ = server_thread.join();
+        }
+    }
+}
+
+fn connect_to_server(socket_path: &Path) -> Result<interprocess::local_socket::Stream> {
+    // the server binds its socket in its own thread
+    let started_at = Instant::now();
+    loop {
+        match ipc_connect(socket_path) {
+            Ok(stream) => return Ok(stream),
+            Err(e) if started_at.elapsed() > SERVER_START_TIMEOUT => {
+                return Err(e).context("server did not start listening in time");
+            },
+            Err(_) => thread::sleep(POLL_INTERVAL),
+        }
+    }
+}
+
+fn new_grid(size: Size) -> Grid {
+    let debug = false;
+    let arrow_fonts = true;
+    let styled_underlines = true;
+    let osc8_hyperlinks = true;
+    let explicitly_disable_kitty_keyboard_protocol = false;
+    Grid::new(
+        size.rows,
+        size.cols,
+        Rc::new(RefCell::new(Palette::default())),
+        Rc::new(RefCell::new(HashMap::new())),
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(Some(SizeInPixels {
+            width: 8,
+            height: 21,
+        }))),
+        Rc::new(RefCell::new(SixelImageStore::default())),
+        Style::default(),
+        debug,
+        arrow_fonts,
+        styled_underlines,
+        osc8_hyperlinks,
+        explicitly_disable_kitty_keyboard_protocol,
+    )
+}
+
+#[cfg(test)]
+#[path = "./unit/headless_tests.rs"]
+mod headless_tests;
@@ -5,12 +5,17 @@ mod os_input_output_unix;
 #[path = "os_input_output_windows.rs"]
 mod os_input_output_windows;
 
+#[cfg(any(test, feature = "headless"))]
+pub mod headless;
 pub mod os_input_output;
+#[cfg(any(test, feature = "headless"))]
+pub mod os_input_output_fake;
 pub mod output;
 pub mod panes;
 pub mod tab;
 
 mod background_jobs;
+mod closed_panes;
 mod global_async_runtime;
 mod logging_pipe;
 mod pane_groups;
@@ -43,34 +48,38 @@ use wasmi::Engine;
 
 use crate::{
     os_input_output::ServerOsApi,
-    plugins::{plugin_thread_main, PluginInstruction},
+    plugins::{plugin_thread_main, PluginCpuUsage, PluginInstruction},
     pty::{get_default_shell, pty_thread_main, Pty, PtyInstruction},
-    screen::{screen_thread_main, ScreenInstruction},
+    screen::{screen_thread_main, MouseRegions, ScreenInstruction},
     thread_bus::{Bus, ThreadSenders},
 };
 use route::{route_thread_main, NotificationEnd};
 use zellij_utils::{
     channels::{self, ChannelWithContext, SenderWithContext},
     consts::{
+        session_layout_cache_file_name, session_plugin_states_cache_file_name,
+        session_snapshots_folder_for_session, DEFAULT_PLUGIN_CPU_BUDGET,
         DEFAULT_SCROLL_BUFFER_SIZE, SCROLL_BUFFER_SIZE, ZELLIJ_SEEN_RELEASE_NOTES_CACHE_FILE,
     },
     data::{
         ConnectToSession, InputMode, KeyWithModifier, LayoutInfo, LayoutWithError,
         PluginCapabilities, Style, WebSharing,
     },
-    errors::{prelude::*, ContextType, ErrorInstruction, FatalError, ServerContext},
+    errors::{prelude::*, ContextType, ErrorContext, ErrorInstruction, FatalError, ServerContext},
     home::{default_layout_dir, get_default_data_dir},
     input::{
         actions::Action,
         command::{RunCommand, TerminalAction},
         config::{watch_config_file_changes, watch_layout_dir_changes, Config},
         get_mode_info,
+        hooks::{HookContext, HookEvent},
         keybinds::Keybinds,
         layout::{FloatingPaneLayout, Layout, PluginAlias, Run, RunPluginOrAlias},
         options::Options,
         plugins::PluginAliases,
     },
     ipc::{ClientAttributes, ExitReason, ServerToClientMsg},
+    session_serialization::{deserialize_plugin_states, PluginStatesToRestore},
     shared::{default_palette, web_server_base_url},
 };
 
@@ -132,6 +141,12 @@ pub enum ServerInstruction {
     WebServerStarted(String), // String -> base_url
     FailedToStartWebServer(String),
     ClearMouseHelpText(ClientId),
+    LocalEchoState {
+        client_id: ClientId,
+        predictable: bool,
+        first_column: usize,
+        last_column: usize,
+    },
 }
 
 impl From<&ServerInstruction> for ServerContext {
@@ -180,6 +195,7 @@ impl From<&ServerInstruction> for ServerContext {
                 ServerContext::SendWebClientsForbidden
             },
             ServerInstruction::ClearMouseHelpText(..) => ServerContext::ClearMouseHelpText,
+            ServerInstruction::LocalEchoState { .. } => ServerContext::LocalEchoState,
         }
     }
 }
@@ -323,6 +339,7 @@ pub(crate) struct SessionMetaData {
     pub layout: Box<Layout>,
     pub current_input_modes: HashMap<ClientId, InputMode>,
     pub session_configuration: SessionConfiguration,
+    pub mouse_regions: MouseRegions,
     pub web_sharing: WebSharing, // this is a special attribute explicitly set on session
     // initialization because we don't want it to be overridden by
     // configuration changes, the only way it can be overwritten is by
@@ -365,10 +382,14 @@ impl SessionMetaData {
         config_was_written_to_disk: bool,
     ) {
         let mut new_plugin_config = None;
+        let mut new_hooks = None;
         for (client_id, new_config) in config_changes {
             if new_plugin_config.is_none() {
                 new_plugin_config = Some(new_config.plugins.clone());
             }
+            if new_hooks.is_none() {
+                new_hooks = Some(new_config.hooks.clone());
+            }
 
             self.default_shell = new_config.options.default_shell.as_ref().map(|shell| {
                 TerminalAction::RunCommand(RunCommand {
@@ -429,6 +450,13 @@ impl SessionMetaData {
                 .unwrap();
         }
 
+        // hooks are per session rather than per client
+        if let Some(new_hooks) = new_hooks {
+            let _ = self
+                .senders
+                .send_to_background_jobs(BackgroundJob::ReconfigureHooks(new_hooks));
+        }
+
         // Detect and notify plugins of configuration changes
         if config_was_written_to_disk {
             if let Some(new_plugins) = new_plugin_config {
@@ -651,7 +679,7 @@ impl SessionState {
     }
 }
 
-pub fn start_server(mut os_input: Box<dyn ServerOsApi>, socket_path: PathBuf) {
+pub fn start_server(os_input: Box<dyn ServerOsApi>, socket_path: PathBuf) {
     info!("Starting Zellij server!");
 
     #[cfg(unix)]
@@ -685,8 +713,6 @@ pub fn start_server(mut os_input: Box<dyn ServerOsApi>, socket_path: PathBuf) {
 
     let (to_server, server_receiver): ChannelWithContext<ServerInstruction> = channels::bounded(50);
     let to_server = SenderWithContext::new(to_server);
-    let session_data: Arc<RwLock<Option<SessionMetaData>>> = Arc::new(RwLock::new(None));
-    let session_state = Arc::new(RwLock::new(SessionState::new()));
 
     std::panic::set_hook({
         use zellij_utils::errors::handle_panic;
@@ -696,6 +722,28 @@ pub fn start_server(mut os_input: Box<dyn ServerOsApi>, socket_path: PathBuf) {
         })
     });
 
+    let session_name = envs::get_session_name().ok();
+    server_thread_main(
+        os_input,
+        socket_path,
+        session_name,
+        to_server,
+        server_receiver,
+    );
+}
+
+/// Listens for clients on `socket_path` and handles server instructions until the session is
+/// killed, this is the body of the server process after it has daemonized
+pub(crate) fn server_thread_main(
+    mut os_input: Box<dyn ServerOsApi>,
+    socket_path: PathBuf,
+    session_name: Option<String>,
+    to_server: SenderWithContext<ServerInstruction>,
+    server_receiver: channels::Receiver<(ServerInstruction, ErrorContext)>,
+) {
+    let session_data: Arc<RwLock<Option<SessionMetaData>>> = Arc::new(RwLock::new(None));
+    let session_state = Arc::new(RwLock::new(SessionState::new()));
+
     let _ = thread::Builder::new()
         .name("server_listener".to_string())
         .spawn({
@@ -819,6 +867,7 @@ pub fn start_server(mut os_input: Box<dyn ServerOsApi>, socket_path: PathBuf) {
                     config.clone(),
                     config.plugins.clone(),
                     client_id,
+                    session_name.clone(),
                 );
                 info!("FirstClientConnected: session initialized, spawning tabs");
                 let mut runtime_configuration = config.clone();
@@ -849,9 +898,28 @@ pub fn start_server(mut os_input: Box<dyn ServerOsApi>, socket_path: PathBuf) {
                         ..Default::default()
                     })
                 });
+                let session_hook_event = if session_is_resurrected(&cli_assets, &session_name) {
+                    HookEvent::SessionResurrected
+                } else {
+                    HookEvent::SessionCreated
+                };
                 let cwd = cli_assets
                     .cwd
                     .or_else(|| runtime_config_options.default_cwd);
+                session_data
+                    .read()
+                    .unwrap()
+                    .as_ref()
+                    .unwrap()
+                    .senders
+                    .send_to_background_jobs(BackgroundJob::RunHooks(
+                        session_hook_event,
+                        HookContext {
+                            cwd: cwd.clone(),
+                            ..Default::default()
+                        },
+                    ))
+                    .unwrap();
 
                 let spawn_tabs = |tab_layout,
                                   floating_panes_layout,
@@ -1167,6 +1235,7 @@ pub fn start_server(mut os_input: Box<dyn ServerOsApi>, socket_path: PathBuf) {
                         .send_to_plugin(PluginInstruction::RemoveClient(client_id))
                         .unwrap();
                     if !session_state.read().unwrap().active_clients_are_connected() {
+                        run_session_killed_hooks(&session_data);
                         *session_data.write().unwrap() = None;
                         let client_ids_to_cleanup: Vec<ClientId> = session_state
                             .read()
@@ -1262,6 +1331,7 @@ pub fn start_server(mut os_input: Box<dyn ServerOsApi>, socket_path: PathBuf) {
                 }
             },
             ServerInstruction::KillSession => {
+                run_session_killed_hooks(&session_data);
                 let client_ids = session_state.read().unwrap().client_ids();
                 for client_id in client_ids {
                     let _ = os_input.send_to_client(
@@ -1708,6 +1778,23 @@ pub fn start_server(mut os_input: Box<dyn ServerOsApi>, socket_path: PathBuf) {
                     .send_to_screen(ScreenInstruction::ClearMouseHelpText(client_id))
                     .unwrap();
             },
+            ServerInstruction::LocalEchoState {
+                client_id,
+                predictable,
+                first_column,
+                last_column,
+            } => {
+                send_to_client!(
+                    client_id,
+                    os_input,
+                    ServerToClientMsg::LocalEchoState {
+                        predictable,
+                        first_column,
+                        last_column,
+                    },
+                    session_state
+                );
+            },
         }
     }
 
@@ -1727,6 +1814,7 @@ fn init_session(
     mut config: Config,
     plugin_aliases: PluginAliases,
     client_id: ClientId,
+    session_name: Option<String>,
 ) -> SessionMetaData {
     config.options = config.options.merge(*config_options.clone());
 
@@ -1756,6 +1844,15 @@ fn init_session(
         channels::unbounded();
     let to_background_jobs = SenderWithContext::new(to_background_jobs);
 
+    // states saved on disk belong to a previous incarnation of this session, a new session that
+    // happens to reuse its name should start afresh
+    let plugin_states_to_restore = match &session_name {
+        Some(name) if session_is_resurrected(&cli_assets, &session_name) => {
+            read_plugin_states_from_disk(name)
+        },
+        _ => PluginStatesToRestore::default(),
+    };
+
     // Determine and initialize the data directory
     let data_dir = cli_assets.data_dir.unwrap_or_else(get_default_data_dir);
 
@@ -1764,6 +1861,13 @@ fn init_session(
     };
 
     let serialization_interval = config_options.serialization_interval;
+    let plugin_cpu_budget = match config_options.plugin_cpu_budget {
+        Some(0) => None,
+        Some(plugin_cpu_budget) => Some(plugin_cpu_budget),
+        None => Some(DEFAULT_PLUGIN_CPU_BUDGET),
+    };
+    let plugin_cpu_usage = PluginCpuUsage::default();
+    let plugin_bundle_dir = config_options.plugin_bundle_dir.clone();
     let disable_session_metadata = config_options.disable_session_metadata.unwrap_or(false);
     let web_server_ip = config_options
         .web_server_ip
@@ -1812,6 +1916,7 @@ fn init_session(
         })
         .unwrap();
 
+    let mouse_regions = MouseRegions::default();
     let screen_thread = thread::Builder::new()
         .name("screen".to_string())
         .spawn({
@@ -1832,6 +1937,7 @@ fn init_session(
             let debug = cli_assets.is_debug;
             let layout = layout.clone();
             let config = config.clone();
+            let mouse_regions = mouse_regions.clone();
             move || {
                 screen_thread_main(
                     screen_bus,
@@ -1840,6 +1946,8 @@ fn init_session(
                     config,
                     debug,
                     layout,
+                    session_name,
+                    mouse_regions,
                 )
                 .fatal();
             }
@@ -1862,7 +1970,7 @@ fn init_session(
                 Some(&to_server),
                 Some(&to_pty_writer),
                 Some(&to_background_jobs),
-                None,
+                Some(os_input.clone()),
             );
             let engine = get_engine();
 
@@ -1876,6 +1984,7 @@ fn init_session(
                 .or_else(|| default_layout_dir());
             let background_plugins = config.background_plugins.clone();
             let session_env_vars = session_env_vars.clone();
+            let plugin_cpu_usage = plugin_cpu_usage.clone();
             move || {
                 plugin_thread_main(
                     plugin_bus,
@@ -1894,6 +2003,10 @@ fn init_session(
                     plugin_aliases,
                     default_mode,
                     default_keybinds,
+                    plugin_cpu_budget,
+                    plugin_cpu_usage,
+                    plugin_bundle_dir,
+                    plugin_states_to_restore,
                     background_plugins,
                     client_id,
                 )
@@ -1938,12 +2051,15 @@ fn init_session(
                 has_certificate,
                 enforce_https_for_localhost,
             );
+            let hooks = config.hooks.clone();
             move || {
                 background_jobs_main(
                     background_jobs_bus,
                     serialization_interval,
                     disable_session_metadata,
                     web_server_base_url,
+                    plugin_cpu_usage,
+                    hooks,
                 )
                 .fatal()
             }
@@ -1986,6 +2102,7 @@ fn init_session(
         layout,
         session_configuration: Default::default(),
         current_input_modes: HashMap::new(),
+        mouse_regions,
         screen_thread: Some(screen_thread),
         pty_thread: Some(pty_thread),
         plugin_thread: Some(plugin_thread),
@@ -1999,6 +2116,42 @@ fn init_session(
     }
 }
 
+fn read_plugin_states_from_disk(session_name: &str) -> PluginStatesToRestore {
+    std::fs::read_to_string(session_plugin_states_cache_file_name(session_name))
+        .ok()
+        .and_then(|raw_plugin_states| {
+            deserialize_plugin_states(&raw_plugin_states)
+                .map_err(|e| log::error!("Failed to read saved plugin states: {}", e))
+                .ok()
+        })
+        .unwrap_or_default()
+}
+
+fn session_is_resurrected(cli_assets: &CliAssets, session_name: &Option<String>) -> bool {
+    // resurrected sessions are started with the layout they serialized before they exited, or
+    // with one of their snapshots
+    match (&cli_assets.layout, session_name) {
+        (Some(LayoutInfo::File(layout_path, _)), Some(session_name)) => {
+            let layout_path = PathBuf::from(layout_path);
+            layout_path == session_layout_cache_file_name(session_name)
+                || layout_path.starts_with(session_snapshots_folder_for_session(session_name))
+        },
+        _ => false,
+    }
+}
+
+// the background jobs thread waits for these before it exits, so they run before the server does
+fn run_session_killed_hooks(session_data: &Arc<RwLock<Option<SessionMetaData>>>) {
+    if let Some(session_data) = session_data.read().unwrap().as_ref() {
+        let _ = session_data
+            .senders
+            .send_to_background_jobs(BackgroundJob::RunHooks(
+                HookEvent::SessionKilled,
+                HookContext::default(),
+            ));
+    }
+}
+
 fn setup_wizard_floating_pane() -> FloatingPaneLayout {
     let mut setup_wizard_pane = FloatingPaneLayout::new();
     let configuration = BTreeMap::from_iter([("is_setup_wizard".to_owned(), "true".to_owned())]);
@@ -2209,7 +2362,10 @@ fn update_new_saved_config(
 
 pub fn get_engine() -> Engine {
     log::info!("Loading plugins using Wasmi interpreter");
-    Engine::default()
+    let mut config = wasmi::Config::default();
+    // fuel metering lets us stop plugins that get stuck in a loop (see plugin_cpu_budget)
+    config.consume_fuel(true);
+    Engine::new(&config)
 }
 
 // TODO: move elsewhere
@@ -1,4 +1,4 @@
-use crate::{panes::PaneId, ClientId};
+use crate::{panes::PaneId, plugins::PluginId, ClientId};
 
 use interprocess::local_socket::Stream as LocalSocketStream;
 
@@ -13,7 +13,8 @@ use tempfile::tempfile;
 use zellij_utils::{
     channels,
     channels::TrySendError,
-    data::Palette,
+    consts::{ipc_connect, ZELLIJ_SOCK_DIR, ZELLIJ_TMP_DIR},
+    data::{Event, Palette},
     errors::prelude::*,
     input::command::{RunCommand, TerminalAction},
     ipc::{
@@ -27,10 +28,13 @@ use std::{
     collections::{BTreeMap, HashMap},
     env,
     fs::File,
+    future::Future,
     io::{self, Write},
-    path::PathBuf,
+    path::{Path, PathBuf},
+    pin::Pin,
     process::Command,
     sync::{Arc, Mutex},
+    time::{Duration, Instant},
 };
 
 pub use async_trait::async_trait;
@@ -360,6 +364,49 @@ pub trait ServerOsApi: Send + Sync {
     fn clear_terminal_id(&self, terminal_id: u32) -> Result<()>;
     fn cache_resizes(&mut self) {}
     fn apply_cached_resizes(&mut self) {}
+    /// Hand the PTY of `terminal_id` (and with it its running process) over to the session
+    /// called `session_name`, which will open it in a new pane
+    fn send_terminal_to_session(
+        &self,
+        _terminal_id: u32,
+        _session_name: &str,
+        _pane_title: Option<String>,
+        _scrollback: String,
+        _child_pid: Option<u32>,
+    ) -> Result<()> {
+        Err(anyhow!(
+            "moving panes between sessions is not supported on this platform"
+        ))
+    }
+    /// Receive a PTY sent by another session through `transfer_socket`. The returned tuple
+    /// contains the newly allocated terminal_id and an async reader for the PTY output
+    fn adopt_terminal(&self, _transfer_socket: &Path) -> Result<(u32, Box<dyn AsyncReader>)> {
+        Err(anyhow!(
+            "moving panes between sessions is not supported on this platform"
+        ))
+    }
+    /// Refuse a PTY sent by another session through `transfer_socket`, it then stays in the
+    /// session that sent it
+    fn refuse_terminal(&self, _transfer_socket: &Path) -> Result<()> {
+        Err(anyhow!(
+            "moving panes between sessions is not supported on this platform"
+        ))
+    }
+    /// Resolves once `duration` has passed, with the time that actually elapsed. Plugin timers
+    /// wait on this so that they can run on a simulated clock
+    fn sleep(&self, duration: Duration) -> Pin<Box<dyn Future<Output = Duration> + Send>> {
+        Box::pin(async move {
+            let start_time = Instant::now();
+            tokio::time::sleep(duration).await;
+            start_time.elapsed()
+        })
+    }
+    /// Called with every batch of events sent to plugins (target plugin, target client, event)
+    fn report_plugin_events(&self, _events: &[(Option<PluginId>, Option<ClientId>, Event)]) {}
+    /// Where the permissions granted to plugins are cached, `None` for the cache of this machine
+    fn plugin_permissions_cache_path(&self) -> Option<PathBuf> {
+        None
+    }
 }
 
 impl ServerOsApi for ServerOsInputOutput {
@@ -682,6 +729,55 @@ impl ServerOsApi for ServerOsInputOutput {
             }
         }
     }
+    #[cfg(unix)]
+    fn send_terminal_to_session(
+        &self,
+        terminal_id: u32,
+        session_name: &str,
+        pane_title: Option<String>,
+        scrollback: String,
+        child_pid: Option<u32>,
+    ) -> Result<()> {
+        let err_context = || {
+            format!(
+                "failed to send terminal {} to session {}",
+                terminal_id, session_name
+            )
+        };
+
+        let session_socket = ZELLIJ_SOCK_DIR.join(session_name);
+        let transfer_socket = ZELLIJ_TMP_DIR.join(format!(
+            "pane-transfer-{}-{}",
+            std::process::id(),
+            terminal_id
+        ));
+        self.pty_backend
+            .send_terminal_fd(terminal_id, &transfer_socket, || {
+                let stream = ipc_connect(&session_socket)
+                    .with_context(|| format!("failed to connect to session {}", session_name))?;
+                IpcSenderWithContext::<ClientToServerMsg>::new(stream).send_client_msg(
+                    ClientToServerMsg::AdoptPane {
+                        transfer_socket: transfer_socket.clone(),
+                        pane_title,
+                        scrollback,
+                        child_pid,
+                    },
+                )
+            })
+            .with_context(err_context)
+    }
+    #[cfg(unix)]
+    fn adopt_terminal(&self, transfer_socket: &Path) -> Result<(u32, Box<dyn AsyncReader>)> {
+        self.pty_backend
+            .adopt_terminal_fd(transfer_socket)
+            .with_context(|| format!("failed to adopt terminal from {:?}", transfer_socket))
+    }
+    #[cfg(unix)]
+    fn refuse_terminal(&self, transfer_socket: &Path) -> Result<()> {
+        self.pty_backend
+            .refuse_terminal_fd(transfer_socket)
+            .with_context(|| format!("failed to refuse terminal from {:?}", transfer_socket))
+    }
 }
 
 impl Clone for Box<dyn ServerOsApi> {
@@ -0,0 +1,336 @@
+//! An in-memory [`ServerOsApi`] for running the server without real PTYs or client sockets.
+//!
+//! Terminals spawned through it are not backed by any process: whatever is written to a
+//! terminal with [`FakeInputOutput::write_to_terminal`] is read by the server as that terminal's
+//! output, and whatever the server writes to its STDIN is recorded. The command of a terminal
+//! exits only when told to with [`FakeInputOutput::exit_terminal`]. Messages the server sends
+//! to clients are recorded rather than sent over the wire.
+//!
+//! Plugin timers run on a simulated clock that only moves forward with
+//! [`FakeInputOutput::advance_time`].
+
+use crate::{
+    os_input_output::{async_trait, AsyncReader, ServerOsApi},
+    panes::PaneId,
+    plugins::PluginId,
+    ClientId,
+};
+
+use interprocess::local_socket::Stream as LocalSocketStream;
+use std::{
+    collections::{BTreeMap, HashMap},
+    future::{self, Future},
+    io,
+    path::PathBuf,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tokio::sync::{
+    mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender},
+    oneshot,
+};
+use zellij_utils::{
+    data::{Event, Palette},
+    errors::prelude::*,
+    input::command::{RunCommand, TerminalAction},
+    ipc::{ClientToServerMsg, IpcReceiverWithContext, ServerToClientMsg},
+    shared::default_palette,
+};
+
+struct FakeTerminalReader {
+    receiver: UnboundedReceiver<Vec<u8>>,
+    pending: Vec<u8>,
+}
+
+#[async_trait]
+impl AsyncReader for FakeTerminalReader {
+    async fn read(&mut self, buf: &mut [u8]) -> Result<usize, io::Error> {
+        if self.pending.is_empty() {
+            match self.receiver.recv().await {
+                Some(bytes) => self.pending = bytes,
+                None => return Ok(0), // EOF, the terminal was closed
+            }
+        }
+        let count = std::cmp::min(buf.len(), self.pending.len());
+        buf[..count].copy_from_slice(&self.pending[..count]);
+        self.pending.drain(..count);
+        Ok(count)
+    }
+}
+
+struct Timer {
+    started_at: Duration,
+    fires_at: Duration,
+    sender: oneshot::Sender<Duration>, // receives the time that elapsed until the timer fired
+}
+
+#[derive(Default)]
+pub(crate) struct SimulatedClock {
+    now: Duration, // since the clock was created
+    timers: Vec<Timer>,
+}
+
+impl SimulatedClock {
+    fn add_timer(&mut self, duration: Duration) -> oneshot::Receiver<Duration> {
+        let (sender, receiver) = oneshot::channel();
+        self.timers.push(Timer {
+            started_at: self.now,
+            fires_at: self.now + duration,
+            sender,
+        });
+        receiver
+    }
+    fn advance(&mut self, duration: Duration) {
+        self.now += duration;
+        let now = self.now;
+        let (mut due, pending): (Vec<Timer>, Vec<Timer>) = self
+            .timers
+            .drain(..)
+            .partition(|timer| timer.fires_at <= now);
+        self.timers = pending;
+        due.sort_by_key(|timer| timer.fires_at);
+        for timer in due {
+            let _ = timer.sender.send(timer.fires_at - timer.started_at);
+        }
+    }
+}
+
+pub type PluginEvents = Vec<(Option<PluginId>, Option<ClientId>, Event)>; // target plugin, target client, event
+
+type QuitCallback = Box<dyn Fn(PaneId, Option<i32>, RunCommand) + Send>;
+
+#[derive(Clone, Default)]
+pub struct FakeInputOutput {
+    pub(crate) fake_filesystem: Arc<Mutex<HashMap<String, String>>>,
+    pub(crate) server_to_client_messages: Arc<Mutex<HashMap<ClientId, Vec<ServerToClientMsg>>>>,
+    pub(crate) tty_stdin_bytes: Arc<Mutex<BTreeMap<u32, Vec<u8>>>>,
+    pub(crate) terminal_outputs: Arc<Mutex<BTreeMap<u32, UnboundedSender<Vec<u8>>>>>,
+    pub(crate) spawned_terminals: Arc<Mutex<BTreeMap<u32, TerminalAction>>>,
+    pub(crate) next_terminal_id: Arc<Mutex<u32>>,
+    pub(crate) clock: Arc<Mutex<SimulatedClock>>,
+    pub(crate) plugin_events: Arc<Mutex<PluginEvents>>,
+    pub(crate) running_commands: Arc<Mutex<BTreeMap<u32, (RunCommand, QuitCallback)>>>,
+    pub(crate) plugin_permissions_cache_path: Option<PathBuf>,
+}
+
+impl FakeInputOutput {
+    /// Feed `bytes` to the server as if they were output by the program running in this terminal
+    pub fn write_to_terminal(&self, terminal_id: u32, bytes: &[u8]) -> Result<()> {
+        self.terminal_outputs
+            .lock()
+            .to_anyhow()?
+            .get(&terminal_id)
+            .ok_or_else(|| anyhow!("terminal with id {} is not running", terminal_id))?
+            .send(bytes.to_vec())
+            .map_err(|_| anyhow!("terminal with id {} was closed", terminal_id))
+    }
+    /// Everything the server wrote to the STDIN of this terminal so far
+    pub fn terminal_input(&self, terminal_id: u32) -> Vec<u8> {
+        self.tty_stdin_bytes
+            .lock()
+            .unwrap()
+            .get(&terminal_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+    /// The ids of the terminals spawned so far along with what they were asked to run
+    pub fn spawned_terminals(&self) -> BTreeMap<u32, TerminalAction> {
+        self.spawned_terminals.lock().unwrap().clone()
+    }
+    /// Messages the server sent to this client so far
+    pub fn messages_to_client(&self, client_id: ClientId) -> Vec<ServerToClientMsg> {
+        self.server_to_client_messages
+            .lock()
+            .unwrap()
+            .get(&client_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+    /// Move the simulated clock forward by `duration`, firing the plugin timers that are due
+    pub fn advance_time(&self, duration: Duration) {
+        self.clock.lock().unwrap().advance(duration);
+    }
+    /// The events sent to plugins so far, along with the plugin and client they were sent to
+    /// (`None` for all of them)
+    pub fn plugin_events(&self) -> PluginEvents {
+        self.plugin_events.lock().unwrap().clone()
+    }
+    /// Close all terminals, the server sees this as EOF on their output
+    pub fn close_terminals(&self) {
+        self.terminal_outputs.lock().unwrap().clear();
+    }
+    /// Make the command running in this terminal exit with `exit_status`, as its process would
+    pub fn exit_terminal(&self, terminal_id: u32, exit_status: Option<i32>) -> Result<()> {
+        let (run_command, quit_cb) = self
+            .running_commands
+            .lock()
+            .to_anyhow()?
+            .remove(&terminal_id)
+            .ok_or_else(|| anyhow!("no command is running in terminal {}", terminal_id))?;
+        self.terminal_outputs
+            .lock()
+            .to_anyhow()?
+            .remove(&terminal_id);
+        quit_cb(PaneId::Terminal(terminal_id), exit_status, run_command);
+        Ok(())
+    }
+    fn open_terminal(&self, terminal_id: u32) -> Box<dyn AsyncReader> {
+        let (sender, receiver) = unbounded_channel();
+        self.terminal_outputs
+            .lock()
+            .unwrap()
+            .insert(terminal_id, sender);
+        Box::new(FakeTerminalReader {
+            receiver,
+            pending: vec![],
+        })
+    }
+}
+
+impl ServerOsApi for FakeInputOutput {
+    fn set_terminal_size_using_terminal_id(
+        &self,
+        _terminal_id: u32,
+        _cols: u16,
+        _rows: u16,
+        _width_in_pixels: Option<u16>,
+        _height_in_pixels: Option<u16>,
+    ) -> Result<()> {
+        // noop
+        Ok(())
+    }
+    fn spawn_terminal(
+        &self,
+        terminal_action: TerminalAction,
+        quit_cb: Box<dyn Fn(PaneId, Option<i32>, RunCommand) + Send>,
+        _default_editor: Option<PathBuf>,
+    ) -> Result<(u32, Box<dyn AsyncReader>, Option<u32>)> {
+        let terminal_id = self.reserve_terminal_id()?;
+        if let TerminalAction::RunCommand(run_command) = &terminal_action {
+            self.running_commands
+                .lock()
+                .to_anyhow()?
+                .insert(terminal_id, (run_command.clone(), quit_cb));
+        }
+        self.spawned_terminals
+            .lock()
+            .to_anyhow()?
+            .insert(terminal_id, terminal_action);
+        Ok((terminal_id, self.open_terminal(terminal_id), None))
+    }
+    fn reserve_terminal_id(&self) -> Result<u32> {
+        let mut next_terminal_id = self.next_terminal_id.lock().to_anyhow()?;
+        let terminal_id = *next_terminal_id;
+        *next_terminal_id += 1;
+        Ok(terminal_id)
+    }
+    fn write_to_tty_stdin(&self, terminal_id: u32, buf: &[u8]) -> Result<usize> {
+        self.tty_stdin_bytes
+            .lock()
+            .to_anyhow()?
+            .entry(terminal_id)
+            .or_default()
+            .extend_from_slice(buf);
+        Ok(buf.len())
+    }
+    fn tcdrain(&self, _terminal_id: u32) -> Result<()> {
+        Ok(())
+    }
+    fn kill(&self, _pid: u32) -> Result<()> {
+        Ok(())
+    }
+    fn force_kill(&self, _pid: u32) -> Result<()> {
+        Ok(())
+    }
+    fn send_sigint(&self, _pid: u32) -> Result<()> {
+        Ok(())
+    }
+    fn box_clone(&self) -> Box<dyn ServerOsApi> {
+        Box::new((*self).clone())
+    }
+    fn send_to_client(&self, client_id: ClientId, msg: ServerToClientMsg) -> Result<()> {
+        self.server_to_client_messages
+            .lock()
+            .unwrap()
+            .entry(client_id)
+            .or_default()
+            .push(msg);
+        Ok(())
+    }
+    fn new_client(
+        &mut self,
+        _client_id: ClientId,
+        stream: LocalSocketStream,
+    ) -> Result<IpcReceiverWithContext<ClientToServerMsg>> {
+        Ok(IpcReceiverWithContext::new(stream))
+    }
+    fn new_client_with_reply(
+        &mut self,
+        _client_id: ClientId,
+        stream: LocalSocketStream,
+        _reply_stream: LocalSocketStream,
+    ) -> Result<IpcReceiverWithContext<ClientToServerMsg>> {
+        Ok(IpcReceiverWithContext::new(stream))
+    }
+    fn remove_client(&mut self, _client_id: ClientId) -> Result<()> {
+        Ok(())
+    }
+    fn load_palette(&self) -> Palette {
+        default_palette()
+    }
+    fn get_cwd(&self, _pid: u32) -> Option<PathBuf> {
+        None
+    }
+    fn write_to_file(&mut self, contents: String, filename: Option<String>) -> Result<()> {
+        if let Some(filename) = filename {
+            self.fake_filesystem
+                .lock()
+                .unwrap()
+                .insert(filename, contents);
+        }
+        Ok(())
+    }
+    fn re_run_command_in_terminal(
+        &self,
+        terminal_id: u32,
+        run_command: RunCommand,
+        quit_cb: Box<dyn Fn(PaneId, Option<i32>, RunCommand) + Send>,
+    ) -> Result<(Box<dyn AsyncReader>, Option<u32>)> {
+        self.running_commands
+            .lock()
+            .to_anyhow()?
+            .insert(terminal_id, (run_command.clone(), quit_cb));
+        self.spawned_terminals
+            .lock()
+            .to_anyhow()?
+            .insert(terminal_id, TerminalAction::RunCommand(run_command));
+        Ok((self.open_terminal(terminal_id), None))
+    }
+    fn clear_terminal_id(&self, terminal_id: u32) -> Result<()> {
+        self.terminal_outputs
+            .lock()
+            .to_anyhow()?
+            .remove(&terminal_id);
+        Ok(())
+    }
+    fn sleep(&self, duration: Duration) -> Pin<Box<dyn Future<Output = Duration> + Send>> {
+        let timer = self.clock.lock().unwrap().add_timer(duration);
+        Box::pin(async move {
+            match timer.await {
+                Ok(elapsed) => elapsed,
+                // the clock was dropped along with the session, time will never reach this timer
+                Err(_) => future::pending().await,
+            }
+        })
+    }
+    fn plugin_permissions_cache_path(&self) -> Option<PathBuf> {
+        self.plugin_permissions_cache_path.clone()
+    }
+    fn report_plugin_events(&self, events: &[(Option<PluginId>, Option<ClientId>, Event)]) {
+        self.plugin_events
+            .lock()
+            .unwrap()
+            .extend(events.iter().cloned());
+    }
+}
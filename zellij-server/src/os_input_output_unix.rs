@@ -2,11 +2,14 @@ use crate::os_input_output::{command_exists, AsyncReader};
 use crate::panes::PaneId;
 
 use nix::{
+    cmsg_space,
     fcntl::{fcntl, FcntlArg, OFlag},
     pty::{openpty, OpenptyResult, Winsize},
     sys::{
         signal::{kill, Signal},
+        socket::{recvmsg, sendmsg, ControlMessage, ControlMessageOwned, MsgFlags},
         termios,
+        uio::IoVec,
     },
     unistd,
 };
@@ -17,21 +20,24 @@ use signal_hook;
 use signal_hook::consts::*;
 
 use std::{
-    collections::BTreeMap,
+    collections::{BTreeMap, HashSet},
     fs::File,
     io,
+    io::{Read, Write},
     os::fd::FromRawFd,
     os::unix::{
         io::{AsRawFd, RawFd},
+        net::{UnixListener, UnixStream},
         process::CommandExt,
     },
+    path::Path,
     process::{Child, Command},
     sync::{
         atomic::{AtomicU32, Ordering},
         Arc, Mutex,
     },
     thread,
-    time::Duration,
+    time::{Duration, Instant},
 };
 
 use zellij_utils::{errors::prelude::*, input::command::RunCommand};
@@ -274,12 +280,127 @@ fn handle_terminal(
     }
 }
 
+/// How long either side of a pane transfer waits for the other before giving up.
+const PANE_TRANSFER_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// What the adopting session replies once it received the PTY fd. A session refuses the pane
+/// when it has no connected client to show it to.
+const PANE_TRANSFER_ACCEPTED: u8 = 0;
+const PANE_TRANSFER_REFUSED: u8 = 1;
+
+/// Wait for the adopting session to connect to `listener`, pass it `fd` and wait for it to
+/// acknowledge having received it.
+fn send_fd_to_transfer_socket(listener: &UnixListener, fd: RawFd) -> Result<()> {
+    let err_context = || "failed to send PTY fd over transfer socket".to_string();
+
+    listener.set_nonblocking(true).with_context(err_context)?;
+    let started_waiting = Instant::now();
+    let mut stream = loop {
+        match listener.accept() {
+            Ok((stream, _)) => break stream,
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock => {
+                if started_waiting.elapsed() > PANE_TRANSFER_TIMEOUT {
+                    return Err(anyhow!(
+                        "timed out waiting for the other session to connect"
+                    ))
+                    .with_context(err_context);
+                }
+                thread::sleep(Duration::from_millis(10));
+            },
+            Err(e) => return Err(e).with_context(err_context),
+        }
+    };
+    stream.set_nonblocking(false).with_context(err_context)?;
+    stream
+        .set_read_timeout(Some(PANE_TRANSFER_TIMEOUT))
+        .with_context(err_context)?;
+
+    let fds = [fd];
+    sendmsg(
+        stream.as_raw_fd(),
+        &[IoVec::from_slice(&[0])],
+        &[ControlMessage::ScmRights(&fds)],
+        MsgFlags::empty(),
+        None,
+    )
+    .with_context(err_context)?;
+
+    let mut ack = [0; 1];
+    stream
+        .read_exact(&mut ack)
+        .context("the other session did not acknowledge the transfer")
+        .with_context(err_context)?;
+    if ack[0] == PANE_TRANSFER_REFUSED {
+        return Err(anyhow!(
+            "the other session refused the pane, it has no connected clients"
+        ))
+        .with_context(err_context);
+    }
+    Ok(())
+}
+
+/// Connect to a transfer socket opened by another session, receive the PTY fd it sends and
+/// acknowledge it.
+fn recv_fd_from_transfer_socket(transfer_socket: &Path) -> Result<RawFd> {
+    let err_context = || format!("failed to receive PTY fd from {:?}", transfer_socket);
+
+    let (mut stream, fd) = connect_to_transfer_socket(transfer_socket).with_context(err_context)?;
+    if let Err(e) = stream.write_all(&[PANE_TRANSFER_ACCEPTED]) {
+        let _ = unistd::close(fd);
+        return Err(e).with_context(err_context);
+    }
+    Ok(fd)
+}
+
+/// Connect to a transfer socket opened by another session and refuse the PTY fd it sends, the
+/// terminal then stays in the sending session.
+fn refuse_fd_from_transfer_socket(transfer_socket: &Path) -> Result<()> {
+    let err_context = || format!("failed to refuse PTY fd from {:?}", transfer_socket);
+
+    let (mut stream, fd) = connect_to_transfer_socket(transfer_socket).with_context(err_context)?;
+    let _ = unistd::close(fd);
+    stream
+        .write_all(&[PANE_TRANSFER_REFUSED])
+        .with_context(err_context)
+}
+
+fn connect_to_transfer_socket(transfer_socket: &Path) -> Result<(UnixStream, RawFd)> {
+    let err_context = || format!("failed to connect to transfer socket {:?}", transfer_socket);
+
+    let stream = UnixStream::connect(transfer_socket).with_context(err_context)?;
+    stream
+        .set_read_timeout(Some(PANE_TRANSFER_TIMEOUT))
+        .with_context(err_context)?;
+
+    let mut buf = [0; 1];
+    let mut cmsg_buffer = cmsg_space!([RawFd; 1]);
+    let message = recvmsg(
+        stream.as_raw_fd(),
+        &[IoVec::from_mut_slice(&mut buf)],
+        Some(&mut cmsg_buffer),
+        MsgFlags::empty(),
+    )
+    .with_context(err_context)?;
+    let fd = message
+        .cmsgs()
+        .find_map(|cmsg| match cmsg {
+            ControlMessageOwned::ScmRights(fds) => fds.first().copied(),
+            _ => None,
+        })
+        .ok_or_else(|| anyhow!("no file descriptor in transfer message"))
+        .with_context(err_context)?;
+    Ok((stream, fd))
+}
+
 /// The Unix PTY backend. Manages native PTY file descriptors and signals.
 #[derive(Clone)]
 pub(crate) struct UnixPtyBackend {
     orig_termios: Arc<Mutex<Option<termios::Termios>>>,
     terminal_id_to_raw_fd: Arc<Mutex<BTreeMap<u32, Option<RawFd>>>>,
     next_terminal_id_counter: Arc<AtomicU32>,
+    // terminals sent to another session, we remain the parent of their process but its exit is
+    // no longer ours to report
+    detached_terminal_ids: Arc<Mutex<HashSet<u32>>>,
 }
 
 /// Try to write as many bytes from `buf` as possible to `fd` without blocking.
@@ -312,6 +433,7 @@ impl UnixPtyBackend {
             orig_termios: Arc::new(Mutex::new(current_termios)),
             terminal_id_to_raw_fd: Arc::new(Mutex::new(BTreeMap::new())),
             next_terminal_id_counter: Arc::new(AtomicU32::new(0)),
+            detached_terminal_ids: Arc::new(Mutex::new(HashSet::new())),
         })
     }
 
@@ -327,6 +449,17 @@ impl UnixPtyBackend {
             .lock()
             .to_anyhow()
             .context("failed to lock orig_termios")?;
+        let quit_cb = Box::new({
+            let detached_terminal_ids = self.detached_terminal_ids.clone();
+            move |pane_id, exit_status, cmd| {
+                if let PaneId::Terminal(terminal_id) = pane_id {
+                    if detached_terminal_ids.lock().unwrap().remove(&terminal_id) {
+                        return;
+                    }
+                }
+                quit_cb(pane_id, exit_status, cmd)
+            }
+        });
         let (pid_primary, child_fd) = handle_terminal(
             cmd,
             failover_cmd,
@@ -443,6 +576,76 @@ impl UnixPtyBackend {
             .remove(&terminal_id);
     }
 
+    /// Send the PTY of `terminal_id` to another session through a socket bound at
+    /// `transfer_socket`. `notify_receiver` is called once the socket is ready, and should tell
+    /// the other session where to find it.
+    ///
+    /// The terminal is left as-is here, it is up to the caller to stop reading from it and clear
+    /// its id once this returns successfully. The exit of its process will not be reported.
+    pub fn send_terminal_fd(
+        &self,
+        terminal_id: u32,
+        transfer_socket: &Path,
+        notify_receiver: impl FnOnce() -> Result<()>,
+    ) -> Result<()> {
+        let err_context = || format!("failed to send fd of terminal {}", terminal_id);
+
+        let fd = match self
+            .terminal_id_to_raw_fd
+            .lock()
+            .to_anyhow()
+            .with_context(err_context)?
+            .get(&terminal_id)
+        {
+            Some(Some(fd)) => *fd,
+            _ => {
+                return Err(anyhow!("could not find raw file descriptor")).with_context(err_context)
+            },
+        };
+        // we send a duplicate because the original is closed along with its reader once the
+        // caller stops reading from this terminal
+        let duplicate_fd = unistd::dup(fd).with_context(err_context)?;
+        let _ = std::fs::remove_file(transfer_socket);
+        let result = UnixListener::bind(transfer_socket)
+            .with_context(err_context)
+            .and_then(|listener| {
+                notify_receiver()?;
+                send_fd_to_transfer_socket(&listener, duplicate_fd)
+            });
+        let _ = unistd::close(duplicate_fd);
+        let _ = std::fs::remove_file(transfer_socket);
+        if result.is_ok() {
+            self.detached_terminal_ids
+                .lock()
+                .to_anyhow()
+                .with_context(err_context)?
+                .insert(terminal_id);
+        }
+        result
+    }
+
+    /// Adopt a PTY sent by another session through `transfer_socket`, registering it under a
+    /// newly allocated terminal id.
+    pub fn adopt_terminal_fd(&self, transfer_socket: &Path) -> Result<(u32, Box<dyn AsyncReader>)> {
+        let fd = recv_fd_from_transfer_socket(transfer_socket)?;
+        let terminal_id = self
+            .next_terminal_id()
+            .context("no more terminal IDs left to allocate")?;
+        self.terminal_id_to_raw_fd
+            .lock()
+            .to_anyhow()?
+            .insert(terminal_id, Some(fd));
+        let async_reader = Box::new(
+            RawFdAsyncReader::new(fd)
+                .map_err(|e| anyhow::anyhow!("failed to create async reader: {}", e))?,
+        ) as Box<dyn AsyncReader>;
+        Ok((terminal_id, async_reader))
+    }
+
+    pub fn refuse_terminal_fd(&self, transfer_socket: &Path) -> Result<()> {
+        refuse_fd_from_transfer_socket(transfer_socket)
+    }
+
     pub fn next_terminal_id(&self) -> Option<u32> {
         Some(
             self.next_terminal_id_counter
@@ -556,4 +759,73 @@ mod tests {
             libc::close(pty.slave);
         }
     }
+
+    /// Verify that a PTY passed over a transfer socket can be written to by the session that
+    /// received it, and that the sending side learns the transfer went through.
+    #[test]
+    fn pty_fd_is_passed_over_the_transfer_socket() {
+        let pty = openpty(None, &None).expect("openpty failed");
+        let mut attrs = termios::tcgetattr(pty.slave).expect("tcgetattr failed");
+        termios::cfmakeraw(&mut attrs);
+        termios::tcsetattr(pty.slave, termios::SetArg::TCSANOW, &attrs).expect("tcsetattr failed");
+
+        let temp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        let transfer_socket = temp_dir.path().join("pane-transfer");
+        let listener = UnixListener::bind(&transfer_socket).expect("failed to bind");
+        let master = pty.master;
+        let sender = thread::spawn(move || send_fd_to_transfer_socket(&listener, master));
+
+        let received_fd =
+            recv_fd_from_transfer_socket(&transfer_socket).expect("failed to receive fd");
+        assert!(
+            sender.join().unwrap().is_ok(),
+            "transfer was not acknowledged"
+        );
+        assert_ne!(
+            received_fd, pty.master,
+            "expected a new fd for the same PTY"
+        );
+
+        unistd::write(received_fd, b"moved").expect("failed to write to the received fd");
+        let mut read_back = [0u8; 5];
+        let mut slave_file = unsafe { std::fs::File::from_raw_fd(pty.slave) };
+        slave_file
+            .read_exact(&mut read_back)
+            .expect("slave read failed");
+        assert_eq!(&read_back, b"moved");
+
+        unsafe {
+            libc::close(received_fd);
+            libc::close(pty.master);
+        }
+    }
+
+    /// Verify that a session refusing a PTY makes the transfer fail on the sending side, so
+    /// that the pane stays where it was.
+    #[test]
+    fn refused_pty_transfer_fails_for_the_sender() {
+        let pty = openpty(None, &None).expect("openpty failed");
+
+        let temp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        let transfer_socket = temp_dir.path().join("pane-transfer");
+        let listener = UnixListener::bind(&transfer_socket).expect("failed to bind");
+        let master = pty.master;
+        let sender = thread::spawn(move || send_fd_to_transfer_socket(&listener, master));
+
+        refuse_fd_from_transfer_socket(&transfer_socket).expect("failed to refuse fd");
+        let error = sender
+            .join()
+            .unwrap()
+            .expect_err("refused transfer should fail");
+        assert!(
+            format!("{:?}", error).contains("refused the pane"),
+            "unexpected error: {:?}",
+            error
+        );
+
+        unsafe {
+            libc::close(pty.master);
+            libc::close(pty.slave);
+        }
+    }
 }
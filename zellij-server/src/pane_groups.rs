@@ -8,6 +8,7 @@ use crate::{panes::PaneId, pty::PtyInstruction, thread_bus::ThreadSenders, Clien
 
 pub struct PaneGroups {
     panes_in_group: HashMap<ClientId, Vec<PaneId>>,
+    broadcasting_clients: HashSet<ClientId>, // these clients write their input to their whole group
     senders: ThreadSenders,
 }
 
@@ -15,6 +16,7 @@ impl std::fmt::Debug for PaneGroups {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("PaneGroups")
             .field("panes_in_group", &self.panes_in_group)
+            .field("broadcasting_clients", &self.broadcasting_clients)
             .finish_non_exhaustive()
     }
 }
@@ -23,6 +25,7 @@ impl PaneGroups {
     pub fn new(senders: ThreadSenders) -> Self {
         PaneGroups {
             panes_in_group: HashMap::new(),
+            broadcasting_clients: HashSet::new(),
             senders,
         }
     }
@@ -37,6 +40,30 @@ impl PaneGroups {
     }
     pub fn clear_pane_group(&mut self, client_id: &ClientId) {
         self.panes_in_group.get_mut(client_id).map(|p| p.clear());
+        self.broadcasting_clients.remove(client_id);
+    }
+    pub fn is_broadcasting(&self, client_id: &ClientId) -> bool {
+        self.broadcasting_clients.contains(client_id)
+    }
+    pub fn broadcasting_clients(&self) -> HashSet<ClientId> {
+        self.broadcasting_clients.clone()
+    }
+    pub fn toggle_broadcast(&mut self, client_id: &ClientId) -> bool {
+        // returns true if the client is broadcasting now, a client can only start broadcasting if
+        // it has panes in its group
+        let has_panes_in_group = self
+            .panes_in_group
+            .get(client_id)
+            .map(|p| !p.is_empty())
+            .unwrap_or(false);
+        if self.broadcasting_clients.remove(client_id) {
+            false
+        } else if has_panes_in_group {
+            self.broadcasting_clients.insert(*client_id);
+            true
+        } else {
+            false
+        }
     }
     pub fn toggle_pane_id_in_group(
         &mut self,
@@ -125,6 +152,13 @@ impl PaneGroups {
     }
     pub fn override_groups_with(&mut self, new_pane_groups: HashMap<ClientId, Vec<PaneId>>) {
         self.panes_in_group = new_pane_groups;
+        let panes_in_group = &self.panes_in_group;
+        self.broadcasting_clients.retain(|c| {
+            panes_in_group
+                .get(c)
+                .map(|p| !p.is_empty())
+                .unwrap_or(false)
+        });
     }
     fn should_launch_plugin(
         &self,
@@ -498,4 +532,43 @@ mod tests {
 
         assert!(!pane_groups.should_launch_plugin(&previous_groups, &client_id));
     }
+
+    #[test]
+    fn toggle_broadcast_requires_panes_in_group() {
+        let mut pane_groups = create_test_pane_groups();
+        let client_id: ClientId = 1;
+        let screen_size = create_test_screen_size();
+
+        assert!(!pane_groups.toggle_broadcast(&client_id));
+        assert!(!pane_groups.is_broadcasting(&client_id));
+
+        pane_groups.add_pane_id_to_group(PaneId::Terminal(10), screen_size, &client_id);
+        assert!(pane_groups.toggle_broadcast(&client_id));
+        assert!(pane_groups.is_broadcasting(&client_id));
+
+        assert!(!pane_groups.toggle_broadcast(&client_id));
+        assert!(!pane_groups.is_broadcasting(&client_id));
+    }
+
+    #[test]
+    fn broadcast_stops_when_group_is_emptied() {
+        let mut pane_groups = create_test_pane_groups();
+        let client_id: ClientId = 1;
+        let other_client_id: ClientId = 2;
+        let screen_size = create_test_screen_size();
+
+        pane_groups.add_pane_id_to_group(PaneId::Terminal(10), screen_size, &client_id);
+        pane_groups.add_pane_id_to_group(PaneId::Terminal(20), screen_size, &other_client_id);
+        pane_groups.toggle_broadcast(&client_id);
+        pane_groups.toggle_broadcast(&other_client_id);
+
+        let mut new_groups = pane_groups.clone_inner();
+        new_groups.insert(client_id, vec![]);
+        pane_groups.override_groups_with(new_groups);
+        assert!(!pane_groups.is_broadcasting(&client_id));
+        assert!(pane_groups.is_broadcasting(&other_client_id));
+
+        pane_groups.clear_pane_group(&other_client_id);
+        assert!(!pane_groups.is_broadcasting(&other_client_id));
+    }
 }
@@ -381,6 +381,7 @@ impl FloatingPanes {
         output: &mut Output,
         mouse_hover_pane_id: &HashMap<ClientId, PaneId>,
         current_pane_group: HashMap<ClientId, Vec<PaneId>>,
+        broadcasting_clients: &HashSet<ClientId>,
         client_id_override: Option<ClientId>,
         help_text_visible: &HashMap<ClientId, bool>,
     ) -> Result<()> {
@@ -471,6 +472,7 @@ impl FloatingPanes {
                 true,
                 mouse_hover_pane_id,
                 current_pane_group.clone(),
+                broadcasting_clients,
                 show_help_text,
             );
             for client_id in &connected_clients {
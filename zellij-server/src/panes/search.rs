@@ -480,6 +480,39 @@ impl Grid {
         self.search_results.unset_active_selection_if_nonexistent();
     }
 
+    /// Scrolls a line into view and marks the matches of `needle` in the viewport, making the one
+    /// on that line active. `line_index` counts lines from the top of the scrollback, as in
+    /// `pane_contents`.
+    pub fn reveal_search_match(&mut self, needle: &str, case_sensitive: bool, line_index: usize) {
+        self.clear_search();
+        let target_row = if line_index < self.lines_above.len() {
+            // lines above the viewport are whole lines, so we scroll until ours is the first row
+            while self.lines_above.len() > line_index && self.viewport.len() == self.height {
+                self.scroll_up_one_line();
+            }
+            0
+        } else {
+            let mut row = line_index - self.lines_above.len();
+            while row >= self.height && !self.lines_below.is_empty() {
+                self.scroll_down_one_line();
+                row -= 1;
+            }
+            row
+        };
+        self.search_results.needle = needle.to_owned();
+        self.search_results.case_insensitive = !case_sensitive;
+        self.search_viewport();
+        self.search_results.active = self
+            .search_results
+            .selections
+            .iter()
+            .find(|s| s.start.line() as usize >= target_row)
+            .or_else(|| self.search_results.selections.first())
+            .cloned();
+        self.is_scrolled = true;
+        self.output_buffer.update_all_lines();
+    }
+
     fn search_scrollbuffer(&mut self, dir: SearchDirection) {
         let first_sel = self.search_results.selections.first();
         let last_sel = self.search_results.selections.last();
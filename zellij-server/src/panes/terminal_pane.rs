@@ -22,7 +22,7 @@ use zellij_utils::pane_size::Offset;
 use zellij_utils::{
     data::{
         BareKey, InputMode, KeyWithModifier, Palette, PaletteColor, PaneId as ZellijUtilsPaneId,
-        RegexHighlight, Style, Styling,
+        PaneInputLock, RegexHighlight, Style, Styling,
     },
     errors::prelude::*,
     input::layout::Run,
@@ -141,6 +141,8 @@ pub struct TerminalPane {
     frame: HashMap<ClientId, PaneFrame>,
     borderless: bool,
     exclude_from_sync: bool,
+    input_lock: Option<PaneInputLock>,
+    is_awaiting_input_confirmation: bool,
     fake_cursor_locations: HashSet<(usize, usize)>, // (x, y) - these hold a record of previous fake cursors which we need to clear on render
     search_term: String,
     is_held: Option<(Option<i32>, IsFirstRun, RunCommand)>, // a "held" pane means that its command has either exited and the pane is waiting for a
@@ -413,7 +415,11 @@ impl Pane for TerminalPane {
             pane_title,
             frame_params,
         )
-        .is_pinned(is_pinned);
+        .is_pinned(is_pinned)
+        .input_lock(
+            self.input_lock.is_some(),
+            self.is_awaiting_input_confirmation,
+        );
         if let Some((exit_status, is_first_run, _run_command)) = &self.is_held {
             if *is_first_run {
                 frame.indicate_first_run();
@@ -679,6 +685,27 @@ impl Pane for TerminalPane {
         self.exclude_from_sync
     }
 
+    fn set_input_lock(&mut self, input_lock: Option<PaneInputLock>) {
+        self.input_lock = input_lock;
+        if input_lock.is_none() {
+            self.is_awaiting_input_confirmation = false;
+        }
+        self.set_should_render(true);
+    }
+
+    fn input_lock(&self) -> Option<PaneInputLock> {
+        self.input_lock
+    }
+
+    fn set_awaiting_input_confirmation(&mut self, is_awaiting_input_confirmation: bool) {
+        self.is_awaiting_input_confirmation = is_awaiting_input_confirmation;
+        self.set_should_render(true);
+    }
+
+    fn is_awaiting_input_confirmation(&self) -> bool {
+        self.is_awaiting_input_confirmation
+    }
+
     fn mouse_event(&self, event: &MouseEvent, _client_id: ClientId) -> Option<String> {
         self.grid.mouse_event_signal(event)
     }
@@ -765,6 +792,12 @@ impl Pane for TerminalPane {
         self.grid.clear_search();
         self.search_term.clear();
     }
+    fn reveal_search_match(&mut self, needle: &str, case_sensitive: bool, line_index: usize) {
+        self.search_term = needle.to_owned();
+        self.grid
+            .reveal_search_match(needle, case_sensitive, line_index);
+        self.set_should_render(true);
+    }
     fn is_alternate_mode_active(&self) -> bool {
         self.grid.is_alternate_mode_active()
     }
@@ -1069,6 +1102,8 @@ impl TerminalPane {
             prev_pane_name: pane_name,
             borderless: false,
             exclude_from_sync: false,
+            input_lock: None,
+            is_awaiting_input_confirmation: false,
             fake_cursor_locations: HashSet::new(),
             search_term: String::new(),
             is_held: None,
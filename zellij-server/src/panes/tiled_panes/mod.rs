@@ -1,6 +1,7 @@
 mod pane_resizer;
 mod stacked_panes;
 mod tiled_pane_grid;
+mod tiling;
 
 use crate::resize_pty;
 use tiled_pane_grid::{split, TiledPaneGrid, RESIZE_PERCENT};
@@ -22,9 +23,10 @@ use zellij_utils::{
     errors::prelude::*,
     input::{
         command::RunCommand,
-        layout::{Run, RunPluginOrAlias, SplitDirection},
+        layout::{Run, RunPluginOrAlias, SplitDirection, TilingStrategy},
     },
-    pane_size::{Offset, PaneGeom, Size, SizeInPixels, Viewport},
+    pane_size::{Dimension, Offset, PaneGeom, Size, SizeInPixels, Viewport},
+    position::Position,
 };
 
 use std::{
@@ -53,6 +55,23 @@ fn pane_content_offset(position_and_size: &PaneGeom, viewport: &Viewport) -> (us
     (columns_offset, rows_offset)
 }
 
+// dropping a dragged pane closer than this (relative to the pane size) to one of the edges of
+// another pane splits that pane, dropping it further inside swaps the two panes
+const DROP_TARGET_EDGE_RATIO: f64 = 0.25;
+
+/// Where a tiled pane being dragged with the mouse would be placed if it were dropped now
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TiledPaneDropTarget {
+    pub pane_id: PaneId,
+    pub placement: TiledPaneDropPlacement,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TiledPaneDropPlacement {
+    Swap,             // swap places with the target pane
+    Split(Direction), // split the target pane and take its side in this direction
+}
+
 pub struct TiledPanes {
     pub panes: BTreeMap<PaneId, Box<dyn Pane>>,
     display_area: Rc<RefCell<Size>>,
@@ -74,6 +93,9 @@ pub struct TiledPanes {
     client_id_to_boundaries: HashMap<ClientId, Boundaries>,
     tombstones_before_increase: Option<(PaneId, Vec<HashMap<PaneId, PaneGeom>>)>,
     tombstones_before_decrease: Option<(PaneId, Vec<HashMap<PaneId, PaneGeom>>)>,
+    panes_being_moved_with_mouse: HashMap<ClientId, (PaneId, Option<TiledPaneDropTarget>)>, // (pane being moved, current drop target)
+    tiling_strategy: Option<TilingStrategy>,
+    tiling_order: Vec<PaneId>, // the order in which the tiling strategy places the panes
 }
 
 impl TiledPanes {
@@ -114,6 +136,9 @@ impl TiledPanes {
             client_id_to_boundaries: HashMap::new(),
             tombstones_before_increase: None,
             tombstones_before_decrease: None,
+            panes_being_moved_with_mouse: HashMap::new(),
+            tiling_strategy: None,
+            tiling_order: vec![],
         }
     }
     pub fn add_pane_with_existing_geom(&mut self, pane_id: PaneId, mut pane: Box<dyn Pane>) {
@@ -1004,6 +1029,7 @@ impl TiledPanes {
         floating_panes_are_visible: bool,
         mouse_hover_pane_id: &HashMap<ClientId, PaneId>,
         current_pane_group: HashMap<ClientId, Vec<PaneId>>,
+        broadcasting_clients: &HashSet<ClientId>,
         client_id_override: Option<ClientId>,
         help_text_visible: &HashMap<ClientId, bool>,
     ) -> Result<()> {
@@ -1105,6 +1131,7 @@ impl TiledPanes {
                     should_draw_pane_frames,
                     &mouse_hover_pane_id,
                     current_pane_group.clone(),
+                    broadcasting_clients,
                     show_help_text,
                 );
                 for client_id in &connected_clients {
@@ -1203,6 +1230,24 @@ impl TiledPanes {
                 .add_character_chunks_to_client(client_id, boundaries_to_render, None)
                 .with_context(err_context)?;
         }
+        // render the drop target preview of the panes being moved with the mouse
+        let drop_target_previews: Vec<(ClientId, Viewport)> = self
+            .panes_being_moved_with_mouse
+            .iter()
+            .filter_map(|(client_id, (_pane_id, drop_target))| {
+                let preview_area = self.drop_target_preview_area((*drop_target)?)?;
+                Some((*client_id, preview_area))
+            })
+            .collect();
+        for (client_id, preview_area) in drop_target_previews {
+            let mut drop_target_preview = Boundaries::new(*self.viewport.borrow());
+            drop_target_preview
+                .add_drop_target_preview(preview_area, self.style.colors.frame_highlight.base);
+            let preview_to_render = drop_target_preview.render(None).with_context(err_context)?;
+            output
+                .add_character_chunks_to_client(client_id, preview_to_render, None)
+                .with_context(err_context)?;
+        }
         if floating_panes_are_visible {
             // we do this here so that when they are toggled off, we will make sure to re-render the title
             self.window_title = None;
@@ -1352,6 +1397,154 @@ impl TiledPanes {
 
         Ok(())
     }
+    pub fn equalize_panes(&mut self, direction: Option<SplitDirection>, client_id: ClientId) {
+        // direction: only equalize the split of this direction containing the client's active pane
+        if self.fullscreen_is_active.is_some() {
+            return;
+        }
+        let scope = match direction {
+            Some(direction) => match self.get_active_pane_id(client_id) {
+                Some(active_pane_id) => Some((direction, active_pane_id)),
+                None => return,
+            },
+            None => None,
+        };
+        let mut pane_grid = TiledPaneGrid::new(
+            &mut self.panes,
+            &self.panes_to_hide,
+            *self.display_area.borrow(),
+            *self.viewport.borrow(),
+        );
+        pane_grid.equalize_panes(scope);
+        self.reapply_pane_frames();
+        for pane in self.panes.values_mut() {
+            let _ = resize_pty!(pane, self.os_api, self.senders, self.character_cell_size);
+        }
+        self.reset_boundaries();
+        self.set_force_render();
+    }
+    pub fn tiling_strategy(&self) -> Option<TilingStrategy> {
+        self.tiling_strategy
+    }
+    pub fn set_tiling_strategy(&mut self, tiling_strategy: Option<TilingStrategy>) {
+        self.tiling_strategy = tiling_strategy;
+        self.tiling_order.clear();
+    }
+    pub fn retile(&mut self) -> bool {
+        // true => the panes were arranged by the tiling strategy, false if there is no strategy
+        // or the panes can't be arranged by it (eg. they would be too small)
+        let Some(tiling_strategy) = self.tiling_strategy else {
+            return false;
+        };
+        if self.fullscreen_is_active.is_some() {
+            return false;
+        }
+        let pane_ids = self.pane_ids_in_tiling_order();
+        let Some(area) = self.tiling_area(&pane_ids) else {
+            return false;
+        };
+        let tiles = tiling::tile(tiling_strategy, pane_ids.len(), area);
+        if tiles
+            .iter()
+            .any(|tile| tile.rows < MIN_TERMINAL_HEIGHT || tile.cols < MIN_TERMINAL_WIDTH)
+        {
+            return false;
+        }
+        for (logical_position, (pane_id, tile)) in pane_ids.iter().zip(tiles).enumerate() {
+            let Some(pane) = self.panes.get_mut(pane_id) else {
+                continue;
+            };
+            // the sizes are relative to the tiled area, the same as the space left for them by
+            // the fixed size panes around it (eg. the tab-bar) in the constraint system
+            let mut cols = Dimension::percent(tile.cols as f64 / area.cols as f64 * 100.0);
+            cols.set_inner(tile.cols);
+            let mut rows = Dimension::percent(tile.rows as f64 / area.rows as f64 * 100.0);
+            rows.set_inner(tile.rows);
+            pane.set_geom(PaneGeom {
+                x: tile.x,
+                y: tile.y,
+                cols,
+                rows,
+                stacked: None,
+                logical_position: Some(logical_position),
+                ..pane.position_and_size()
+            });
+        }
+        let display_area = *self.display_area.borrow();
+        let mut pane_grid = TiledPaneGrid::new(
+            &mut self.panes,
+            &self.panes_to_hide,
+            display_area,
+            *self.viewport.borrow(),
+        );
+        let _ = pane_grid.layout(SplitDirection::Horizontal, display_area.cols);
+        let _ = pane_grid.layout(SplitDirection::Vertical, display_area.rows);
+        self.reapply_pane_frames();
+        for pane in self.panes.values_mut() {
+            let _ = resize_pty!(pane, self.os_api, self.senders, self.character_cell_size);
+        }
+        self.reset_boundaries();
+        self.set_force_render();
+        true
+    }
+    fn pane_ids_in_tiling_order(&mut self) -> Vec<PaneId> {
+        // panes keep their place in the order, new ones are added after the existing ones
+        let panes = &self.panes;
+        let panes_to_hide = &self.panes_to_hide;
+        let is_tiled = |pane_id: &PaneId| {
+            !panes_to_hide.contains(pane_id)
+                && panes.get(pane_id).map(|p| p.selectable()).unwrap_or(false)
+        };
+        self.tiling_order.retain(|pane_id| is_tiled(pane_id));
+        let mut new_pane_ids: Vec<PaneId> = panes
+            .keys()
+            .filter(|pane_id| is_tiled(pane_id) && !self.tiling_order.contains(pane_id))
+            .copied()
+            .collect();
+        new_pane_ids.sort_by_key(|pane_id| {
+            let geom = panes[pane_id].position_and_size();
+            (geom.y, geom.x)
+        });
+        self.tiling_order.append(&mut new_pane_ids);
+        self.tiling_order.clone()
+    }
+    fn tiling_area(&self, pane_ids: &[PaneId]) -> Option<Viewport> {
+        // the area covered by these panes, None if other panes (eg. an unselectable plugin) are
+        // inside it
+        let geoms: Vec<PaneGeom> = pane_ids
+            .iter()
+            .filter_map(|pane_id| self.panes.get(pane_id))
+            .map(|pane| pane.position_and_size())
+            .collect();
+        let x = geoms.iter().map(|g| g.x).min()?;
+        let y = geoms.iter().map(|g| g.y).min()?;
+        let right_edge = geoms.iter().map(|g| g.x + g.cols.as_usize()).max()?;
+        let bottom_edge = geoms.iter().map(|g| g.y + g.rows.as_usize()).max()?;
+        let area = Viewport {
+            x,
+            y,
+            cols: right_edge.saturating_sub(x),
+            rows: bottom_edge.saturating_sub(y),
+        };
+        let has_other_panes_inside = self
+            .panes
+            .iter()
+            .filter(|(pane_id, _)| {
+                !pane_ids.contains(pane_id) && !self.panes_to_hide.contains(pane_id)
+            })
+            .any(|(_, pane)| {
+                let geom = pane.position_and_size();
+                geom.x < area.x + area.cols
+                    && area.x < geom.x + geom.cols.as_usize()
+                    && geom.y < area.y + area.rows
+                    && area.y < geom.y + geom.rows.as_usize()
+            });
+        if has_other_panes_inside || !area.has_positive_size() {
+            None
+        } else {
+            Some(area)
+        }
+    }
     fn resize_or_stack_pane_up(&mut self, pane_id: PaneId, resize_percent: (f64, f64)) -> bool {
         // true - successfully resized
         let mut strategy = ResizeStrategy::new(Resize::Increase, Some(Direction::Up));
@@ -2402,6 +2595,229 @@ impl TiledPanes {
             self.set_pane_frames(self.draw_pane_frames);
         }
     }
+    pub fn start_moving_pane_with_mouse(&mut self, pane_id: PaneId, client_id: ClientId) -> bool {
+        // true => started moving the pane
+        if self.fullscreen_is_active.is_some() || self.panes_to_hide.contains(&pane_id) {
+            return false;
+        }
+        let can_be_moved = self
+            .panes
+            .get(&pane_id)
+            .map(|p| p.selectable() && !p.current_geom().is_stacked())
+            .unwrap_or(false);
+        if can_be_moved {
+            self.panes_being_moved_with_mouse
+                .insert(client_id, (pane_id, None));
+        }
+        can_be_moved
+    }
+    pub fn pane_is_being_moved_with_mouse(&self, client_id: ClientId) -> bool {
+        self.panes_being_moved_with_mouse.contains_key(&client_id)
+    }
+    pub fn drop_target(&self, client_id: ClientId) -> Option<TiledPaneDropTarget> {
+        self.panes_being_moved_with_mouse
+            .get(&client_id)
+            .and_then(|(_pane_id, drop_target)| *drop_target)
+    }
+    pub fn update_drop_target(&mut self, position: &Position, client_id: ClientId) -> bool {
+        // true => the drop target changed
+        let Some((pane_id, current_drop_target)) =
+            self.panes_being_moved_with_mouse.get(&client_id).copied()
+        else {
+            return false;
+        };
+        let drop_target = self.drop_target_at(pane_id, position);
+        if drop_target != current_drop_target {
+            self.panes_being_moved_with_mouse
+                .insert(client_id, (pane_id, drop_target));
+            self.set_force_render();
+            true
+        } else {
+            false
+        }
+    }
+    pub fn cancel_moving_pane_with_mouse(&mut self, client_id: ClientId) -> bool {
+        // true => a pane was being moved by this client
+        let was_moving = self
+            .panes_being_moved_with_mouse
+            .remove(&client_id)
+            .is_some();
+        if was_moving {
+            self.set_force_render();
+        }
+        was_moving
+    }
+    pub fn stop_moving_pane_with_mouse(
+        &mut self,
+        position: &Position,
+        client_id: ClientId,
+    ) -> bool {
+        // true => the pane was moved
+        self.update_drop_target(position, client_id);
+        let Some((pane_id, drop_target)) = self.panes_being_moved_with_mouse.remove(&client_id)
+        else {
+            return false;
+        };
+        self.set_force_render();
+        match drop_target {
+            Some(drop_target) if self.move_pane_to_drop_target(pane_id, drop_target) => {
+                self.focus_pane(pane_id, client_id);
+                true
+            },
+            _ => false,
+        }
+    }
+    pub fn move_pane_to_drop_target(
+        &mut self,
+        pane_id: PaneId,
+        drop_target: TiledPaneDropTarget,
+    ) -> bool {
+        // true => the pane was moved
+        if pane_id == drop_target.pane_id
+            || !self.panes.contains_key(&pane_id)
+            || !self.panes.contains_key(&drop_target.pane_id)
+        {
+            return false;
+        }
+        match drop_target.placement {
+            TiledPaneDropPlacement::Swap => {
+                self.swap_pane_positions(pane_id, drop_target.pane_id);
+            },
+            TiledPaneDropPlacement::Split(direction) => {
+                let moved_pane_geom = {
+                    let mut pane_grid = TiledPaneGrid::new(
+                        &mut self.panes,
+                        &self.panes_to_hide,
+                        *self.display_area.borrow(),
+                        *self.viewport.borrow(),
+                    );
+                    pane_grid.move_pane_to_edge_of(pane_id, drop_target.pane_id, direction)
+                };
+                let Some(moved_pane_geom) = moved_pane_geom else {
+                    return false;
+                };
+                if let Some(pane) = self.panes.get_mut(&pane_id) {
+                    pane.set_geom(moved_pane_geom);
+                }
+                match direction {
+                    Direction::Left | Direction::Right => self.relayout(SplitDirection::Horizontal),
+                    Direction::Up | Direction::Down => self.relayout(SplitDirection::Vertical),
+                }
+            },
+        }
+        self.reapply_pane_focus();
+        true
+    }
+    fn swap_pane_positions(&mut self, first_pane_id: PaneId, second_pane_id: PaneId) {
+        let Some(first_pane) = self.panes.get(&first_pane_id) else {
+            return;
+        };
+        let first_geom = first_pane.position_and_size();
+        let first_geom_override = first_pane.geom_override();
+        let Some(second_pane) = self.panes.get_mut(&second_pane_id) else {
+            return;
+        };
+        let second_geom = second_pane.position_and_size();
+        let second_geom_override = second_pane.geom_override();
+        second_pane.set_geom(first_geom);
+        if let Some(geom) = first_geom_override {
+            second_pane.set_geom_override(geom);
+        }
+        resize_pty!(
+            second_pane,
+            self.os_api,
+            self.senders,
+            self.character_cell_size
+        )
+        .non_fatal();
+        second_pane.set_should_render(true);
+        if let Some(first_pane) = self.panes.get_mut(&first_pane_id) {
+            first_pane.set_geom(second_geom);
+            if let Some(geom) = second_geom_override {
+                first_pane.set_geom_override(geom);
+            }
+            resize_pty!(
+                first_pane,
+                self.os_api,
+                self.senders,
+                self.character_cell_size
+            )
+            .non_fatal();
+            first_pane.set_should_render(true);
+        }
+        self.set_pane_frames(self.draw_pane_frames);
+    }
+    fn drop_target_at(
+        &mut self,
+        moved_pane_id: PaneId,
+        position: &Position,
+    ) -> Option<TiledPaneDropTarget> {
+        let (target_pane_id, target_geom) = self
+            .panes
+            .iter()
+            .find(|(pane_id, pane)| {
+                **pane_id != moved_pane_id
+                    && !self.panes_to_hide.contains(pane_id)
+                    && pane.selectable()
+                    && pane.contains(position)
+            })
+            .map(|(pane_id, pane)| (*pane_id, pane.position_and_size()))?;
+        if target_geom.is_stacked() {
+            return None;
+        }
+        // how far the position is from each edge of the target pane, relative to its size
+        let x_ratio = (position.column().saturating_sub(target_geom.x)) as f64
+            / target_geom.cols.as_usize().max(1) as f64;
+        let y_ratio = (position.line() as usize).saturating_sub(target_geom.y) as f64
+            / target_geom.rows.as_usize().max(1) as f64;
+        let (closest_edge, distance_to_edge) = [
+            (Direction::Left, x_ratio),
+            (Direction::Right, 1.0 - x_ratio),
+            (Direction::Up, y_ratio),
+            (Direction::Down, 1.0 - y_ratio),
+        ]
+        .into_iter()
+        .min_by(|a, b| a.1.total_cmp(&b.1))?;
+        let placement = if distance_to_edge >= DROP_TARGET_EDGE_RATIO {
+            TiledPaneDropPlacement::Swap
+        } else {
+            let pane_grid = TiledPaneGrid::new(
+                &mut self.panes,
+                &self.panes_to_hide,
+                *self.display_area.borrow(),
+                *self.viewport.borrow(),
+            );
+            if !pane_grid.can_move_pane_to_edge_of(&target_pane_id, closest_edge) {
+                return None;
+            }
+            TiledPaneDropPlacement::Split(closest_edge)
+        };
+        Some(TiledPaneDropTarget {
+            pane_id: target_pane_id,
+            placement,
+        })
+    }
+    fn drop_target_preview_area(&self, drop_target: TiledPaneDropTarget) -> Option<Viewport> {
+        let target_geom = self.panes.get(&drop_target.pane_id)?.position_and_size();
+        let (x, y, cols, rows) = (
+            target_geom.x,
+            target_geom.y,
+            target_geom.cols.as_usize(),
+            target_geom.rows.as_usize(),
+        );
+        let (x, y, cols, rows) = match drop_target.placement {
+            TiledPaneDropPlacement::Swap => (x, y, cols, rows),
+            TiledPaneDropPlacement::Split(Direction::Left) => (x, y, cols / 2, rows),
+            TiledPaneDropPlacement::Split(Direction::Right) => {
+                (x + cols / 2, y, cols - cols / 2, rows)
+            },
+            TiledPaneDropPlacement::Split(Direction::Up) => (x, y, cols, rows / 2),
+            TiledPaneDropPlacement::Split(Direction::Down) => {
+                (x, y + rows / 2, cols, rows - rows / 2)
+            },
+        };
+        Some(Viewport { x, y, cols, rows })
+    }
     pub fn move_clients_out_of_pane(&mut self, pane_id: PaneId) {
         let active_panes: Vec<(ClientId, PaneId)> = self
             .active_panes
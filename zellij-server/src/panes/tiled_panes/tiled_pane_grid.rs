@@ -1355,6 +1355,205 @@ impl<'a> TiledPaneGrid<'a> {
         }
         false
     }
+    pub fn equalize_panes(&mut self, scope: Option<(SplitDirection, PaneId)>) {
+        // scope: equalize only the innermost split in this direction that contains this pane,
+        // otherwise equalize all splits in the tab
+        let scope = scope.map(|(direction, pane_id)| {
+            let is_stacked = self
+                .panes
+                .borrow()
+                .get(&pane_id)
+                .map(|p| p.current_geom().is_stacked())
+                .unwrap_or(false);
+            if is_stacked {
+                let flexible_pane_id = StackedPanes::new(self.panes.clone())
+                    .flexible_pane_id_in_stack(&pane_id)
+                    .unwrap_or(pane_id);
+                (direction, flexible_pane_id)
+            } else {
+                (direction, pane_id)
+            }
+        });
+        let rects = self.layout_rects();
+        if self.equalize_split(&rects, scope) {
+            let mut pane_resizer = PaneResizer::new(self.panes.clone());
+            let _ = pane_resizer.layout(SplitDirection::Horizontal, self.display_area.cols);
+            let _ = pane_resizer.layout(SplitDirection::Vertical, self.display_area.rows);
+        }
+    }
+    fn layout_rects(&self) -> Vec<(PaneId, PaneGeom)> {
+        // every pane as a rect in the layout, stacks are represented by their flexible pane
+        let pane_ids: Vec<PaneId> = self
+            .panes
+            .borrow()
+            .iter()
+            .filter(|(_, p)| {
+                let geom = p.current_geom();
+                !geom.is_stacked() || geom.rows.is_percent()
+            })
+            .map(|(p_id, _)| *p_id)
+            .collect();
+        pane_ids
+            .into_iter()
+            .filter_map(|pane_id| self.get_pane_geom(&pane_id).map(|geom| (pane_id, geom)))
+            .collect()
+    }
+    fn equalize_split(
+        &mut self,
+        rects: &[(PaneId, PaneGeom)],
+        scope: Option<(SplitDirection, PaneId)>,
+    ) -> bool {
+        // true => equalized something
+        if rects.len() < 2 {
+            return false;
+        }
+        let Some((direction, children)) = [SplitDirection::Vertical, SplitDirection::Horizontal]
+            .into_iter()
+            .find_map(|direction| {
+                split_rects_along(rects, direction).map(|children| (direction, children))
+            })
+        else {
+            // this part of the layout can't be described as a split (eg. panes were resized
+            // across split boundaries), so we leave it as is
+            return false;
+        };
+        match scope {
+            None => {
+                let mut equalized = self.equalize_children(direction, &children);
+                for child in &children {
+                    equalized = self.equalize_split(child, None) || equalized;
+                }
+                equalized
+            },
+            Some((scope_direction, pane_id)) => {
+                let Some(child_with_pane) = children
+                    .iter()
+                    .find(|child| child.iter().any(|(p_id, _)| *p_id == pane_id))
+                else {
+                    return false;
+                };
+                if self.equalize_split(child_with_pane, scope) {
+                    true
+                } else if direction == scope_direction {
+                    self.equalize_children(direction, &children)
+                } else {
+                    false
+                }
+            },
+        }
+    }
+    fn equalize_children(
+        &mut self,
+        direction: SplitDirection,
+        children: &[Vec<(PaneId, PaneGeom)>],
+    ) -> bool {
+        // true => equalized the children
+        let child_is_fixed = |child: &Vec<(PaneId, PaneGeom)>| match child.as_slice() {
+            [(_, geom)] => match direction {
+                SplitDirection::Vertical => geom.cols.is_fixed(),
+                SplitDirection::Horizontal => geom.rows.is_fixed(),
+            },
+            _ => false,
+        };
+        let flexible_children: Vec<&Vec<(PaneId, PaneGeom)>> =
+            children.iter().filter(|c| !child_is_fixed(c)).collect();
+        if flexible_children.len() < 2 {
+            return false;
+        }
+        let flexible_space: usize = flexible_children
+            .iter()
+            .map(|c| rects_size_along(c, direction))
+            .sum();
+        let equal_size = flexible_space as f64 / flexible_children.len() as f64;
+        for child in flexible_children {
+            let current_size = rects_size_along(child, direction).max(1) as f64;
+            for (pane_id, _) in child {
+                self.scale_pane_size(pane_id, direction, equal_size / current_size);
+            }
+        }
+        true
+    }
+    fn scale_pane_size(&mut self, pane_id: &PaneId, direction: SplitDirection, factor: f64) {
+        let stack_id = self
+            .panes
+            .borrow()
+            .get(pane_id)
+            .and_then(|p| p.current_geom().stacked);
+        // all panes in a stack share its width, but only the flexible pane has a relative height
+        let pane_ids = match (direction, stack_id) {
+            (SplitDirection::Vertical, Some(stack_id)) => {
+                StackedPanes::new(self.panes.clone()).pane_ids_in_stack(stack_id)
+            },
+            _ => vec![*pane_id],
+        };
+        let mut panes = self.panes.borrow_mut();
+        for pane_id in pane_ids {
+            let Some(pane) = panes.get_mut(&pane_id) else {
+                continue;
+            };
+            let mut geom = pane.current_geom();
+            let dimension = match direction {
+                SplitDirection::Vertical => &mut geom.cols,
+                SplitDirection::Horizontal => &mut geom.rows,
+            };
+            if let Some(percent) = dimension.as_percent() {
+                dimension.set_percent(percent * factor);
+                pane.set_geom(geom);
+            }
+        }
+    }
+    pub fn can_move_pane_to_edge_of(&self, target_pane_id: &PaneId, direction: Direction) -> bool {
+        let panes = self.panes.borrow();
+        let Some(target_pane) = panes.get(target_pane_id) else {
+            return false;
+        };
+        let target_geom = target_pane.current_geom();
+        if target_geom.is_stacked() {
+            return false;
+        }
+        match direction {
+            Direction::Left | Direction::Right => {
+                target_geom.cols.as_percent().is_some()
+                    && target_geom.cols.as_usize() >= MIN_TERMINAL_WIDTH * 2
+            },
+            Direction::Up | Direction::Down => {
+                target_geom.rows.as_percent().is_some()
+                    && target_geom.rows.as_usize() >= MIN_TERMINAL_HEIGHT * 2
+            },
+        }
+    }
+    pub fn move_pane_to_edge_of(
+        &mut self,
+        pane_id: PaneId,
+        target_pane_id: PaneId,
+        direction: Direction,
+    ) -> Option<PaneGeom> {
+        // closes the space left by the pane and splits the target pane to make room for it on
+        // its edge in direction, returns the geom the moved pane should take
+        if pane_id == target_pane_id || !self.can_move_pane_to_edge_of(&target_pane_id, direction) {
+            return None;
+        }
+        let split_direction = match direction {
+            Direction::Left | Direction::Right => SplitDirection::Vertical,
+            Direction::Up | Direction::Down => SplitDirection::Horizontal,
+        };
+        // make sure the target can be split before changing the layout, filling the space over
+        // the moved pane only grows the target so it can still be split afterwards
+        let target_geom = self.panes.borrow().get(&target_pane_id)?.current_geom();
+        split(split_direction, &target_geom)?;
+        if !self.fill_space_over_pane(pane_id) {
+            return None;
+        }
+        let mut panes = self.panes.borrow_mut();
+        let target_pane = panes.get_mut(&target_pane_id)?;
+        let (first_geom, second_geom) = split(split_direction, &target_pane.current_geom())?;
+        let (target_pane_geom, moved_pane_geom) = match direction {
+            Direction::Left | Direction::Up => (second_geom, first_geom),
+            Direction::Right | Direction::Down => (first_geom, second_geom),
+        };
+        target_pane.set_geom(target_pane_geom);
+        Some(moved_pane_geom)
+    }
     pub fn find_room_for_new_pane(
         &self,
         cursor_height_width_ratio: Option<usize>,
@@ -2359,3 +2558,52 @@ pub fn split(direction: SplitDirection, rect: &PaneGeom) -> Option<(PaneGeom, Pa
         None
     }
 }
+
+fn rect_span_along(geom: &PaneGeom, direction: SplitDirection) -> (usize, usize) {
+    // (start, end) - SplitDirection::Vertical means the rects are side by side, as in layouts
+    match direction {
+        SplitDirection::Vertical => (geom.x, geom.x + geom.cols.as_usize()),
+        SplitDirection::Horizontal => (geom.y, geom.y + geom.rows.as_usize()),
+    }
+}
+
+fn rects_size_along(rects: &[(PaneId, PaneGeom)], direction: SplitDirection) -> usize {
+    let start = rects
+        .iter()
+        .map(|(_, geom)| rect_span_along(geom, direction).0)
+        .min()
+        .unwrap_or(0);
+    let end = rects
+        .iter()
+        .map(|(_, geom)| rect_span_along(geom, direction).1)
+        .max()
+        .unwrap_or(0);
+    end.saturating_sub(start)
+}
+
+fn split_rects_along(
+    rects: &[(PaneId, PaneGeom)],
+    direction: SplitDirection,
+) -> Option<Vec<Vec<(PaneId, PaneGeom)>>> {
+    // groups the rects by the cuts that go all the way through them in this direction, None if
+    // there are no such cuts
+    let mut sorted_rects = rects.to_vec();
+    sorted_rects.sort_by_key(|(_, geom)| rect_span_along(geom, direction).0);
+    let mut groups = vec![];
+    let mut current_group: Vec<(PaneId, PaneGeom)> = vec![];
+    let mut current_group_end = 0;
+    for (pane_id, geom) in sorted_rects {
+        let (start, end) = rect_span_along(&geom, direction);
+        if !current_group.is_empty() && start >= current_group_end {
+            groups.push(std::mem::take(&mut current_group));
+        }
+        current_group_end = current_group_end.max(end);
+        current_group.push((pane_id, geom));
+    }
+    groups.push(current_group);
+    if groups.len() > 1 {
+        Some(groups)
+    } else {
+        None
+    }
+}
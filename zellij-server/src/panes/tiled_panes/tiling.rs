@@ -0,0 +1,171 @@
+//! The built-in tiling strategies, each one divides an area of the screen between a number of
+//! panes
+
+use zellij_utils::{
+    input::layout::{SplitDirection, TilingStrategy},
+    pane_size::Viewport,
+};
+
+/// The rects of `count` panes arranged in `area` by `strategy`, ordered like the panes
+pub fn tile(strategy: TilingStrategy, count: usize, area: Viewport) -> Vec<Viewport> {
+    if count == 0 {
+        return vec![];
+    }
+    match strategy {
+        TilingStrategy::MasterStack {
+            master_ratio,
+            master_count,
+        } => master_stack(area, count, master_ratio, master_count),
+        TilingStrategy::Bsp => bsp(area, count),
+        TilingStrategy::Spiral => spiral(area, count),
+        TilingStrategy::Grid => grid(area, count),
+        TilingStrategy::Columns => split_evenly(area, SplitDirection::Vertical, count),
+    }
+}
+
+fn master_stack(
+    area: Viewport,
+    count: usize,
+    master_ratio: usize,
+    master_count: usize,
+) -> Vec<Viewport> {
+    let master_count = master_count.min(count);
+    if master_count == 0 || master_count == count {
+        // only one column, no need to leave room for the other
+        return split_evenly(area, SplitDirection::Horizontal, count);
+    }
+    let master_cols = (area.cols * master_ratio / 100)
+        .max(1)
+        .min(area.cols.saturating_sub(1));
+    let (master_area, stack_area) = split_at(area, SplitDirection::Vertical, master_cols);
+    let mut rects = split_evenly(master_area, SplitDirection::Horizontal, master_count);
+    rects.append(&mut split_evenly(
+        stack_area,
+        SplitDirection::Horizontal,
+        count - master_count,
+    ));
+    rects
+}
+
+fn bsp(area: Viewport, count: usize) -> Vec<Viewport> {
+    if count == 1 {
+        return vec![area];
+    }
+    let direction = longer_side(&area);
+    let first_count = count / 2;
+    let (first, second) = split_at(area, direction, length_along(&area, direction) / 2);
+    let mut rects = bsp(first, first_count);
+    rects.append(&mut bsp(second, count - first_count));
+    rects
+}
+
+fn spiral(area: Viewport, count: usize) -> Vec<Viewport> {
+    let mut rects = vec![];
+    let mut rest = area;
+    for i in 0..count - 1 {
+        // left, top, right, bottom and then around again towards the middle
+        let (direction, pane_comes_first) = match i % 4 {
+            0 => (SplitDirection::Vertical, true),
+            1 => (SplitDirection::Horizontal, true),
+            2 => (SplitDirection::Vertical, false),
+            _ => (SplitDirection::Horizontal, false),
+        };
+        let length = length_along(&rest, direction);
+        if pane_comes_first {
+            let (pane, remaining) = split_at(rest, direction, length - length / 2);
+            rects.push(pane);
+            rest = remaining;
+        } else {
+            let (remaining, pane) = split_at(rest, direction, length / 2);
+            rects.push(pane);
+            rest = remaining;
+        }
+    }
+    rects.push(rest);
+    rects
+}
+
+fn grid(area: Viewport, count: usize) -> Vec<Viewport> {
+    let columns = (1..).find(|c| c * c >= count).unwrap_or(1);
+    let rows = count.div_ceil(columns);
+    split_evenly(area, SplitDirection::Horizontal, rows)
+        .into_iter()
+        .enumerate()
+        .flat_map(|(row, row_area)| {
+            // the last row takes whatever panes are left, they share its whole width
+            let panes_in_row = if row == rows - 1 {
+                count - columns * (rows - 1)
+            } else {
+                columns
+            };
+            split_evenly(row_area, SplitDirection::Vertical, panes_in_row)
+        })
+        .collect()
+}
+
+fn longer_side(area: &Viewport) -> SplitDirection {
+    // a cell is about twice as high as it is wide
+    if area.cols > area.rows * 2 {
+        SplitDirection::Vertical
+    } else {
+        SplitDirection::Horizontal
+    }
+}
+
+fn length_along(area: &Viewport, direction: SplitDirection) -> usize {
+    // SplitDirection::Vertical means the rects are side by side, as in layouts
+    match direction {
+        SplitDirection::Vertical => area.cols,
+        SplitDirection::Horizontal => area.rows,
+    }
+}
+
+fn split_at(
+    area: Viewport,
+    direction: SplitDirection,
+    first_length: usize,
+) -> (Viewport, Viewport) {
+    let first_length = first_length.min(length_along(&area, direction));
+    match direction {
+        SplitDirection::Vertical => (
+            Viewport {
+                cols: first_length,
+                ..area
+            },
+            Viewport {
+                x: area.x + first_length,
+                cols: area.cols - first_length,
+                ..area
+            },
+        ),
+        SplitDirection::Horizontal => (
+            Viewport {
+                rows: first_length,
+                ..area
+            },
+            Viewport {
+                y: area.y + first_length,
+                rows: area.rows - first_length,
+                ..area
+            },
+        ),
+    }
+}
+
+fn split_evenly(area: Viewport, direction: SplitDirection, count: usize) -> Vec<Viewport> {
+    let length = length_along(&area, direction);
+    let mut rects = vec![];
+    let mut rest = area;
+    for i in 0..count {
+        // the remainder goes to the first rects, one cell each
+        let rect_length = length / count + if i < length % count { 1 } else { 0 };
+        let (rect, remaining) = split_at(rest, direction, rect_length);
+        rects.push(rect);
+        rest = remaining;
+    }
+    rects
+}
+
+#[cfg(test)]
+#[path = "./unit/tiling_tests.rs"]
+mod tiling_tests;
@@ -0,0 +1,100 @@
+use super::tile;
+use zellij_utils::input::layout::TilingStrategy;
+use zellij_utils::pane_size::Viewport;
+
+fn rect(x: usize, y: usize, cols: usize, rows: usize) -> Viewport {
+    Viewport { x, y, rows, cols }
+}
+
+fn area() -> Viewport {
+    rect(0, 0, 80, 20)
+}
+
+#[test]
+fn master_stack_with_one_master_pane() {
+    let strategy = TilingStrategy::MasterStack {
+        master_ratio: 60,
+        master_count: 1,
+    };
+    assert_eq!(
+        tile(strategy, 3, area()),
+        vec![
+            rect(0, 0, 48, 20),
+            rect(48, 0, 32, 10),
+            rect(48, 10, 32, 10)
+        ]
+    );
+}
+
+#[test]
+fn master_stack_with_two_master_panes() {
+    let strategy = TilingStrategy::MasterStack {
+        master_ratio: 50,
+        master_count: 2,
+    };
+    assert_eq!(
+        tile(strategy, 3, area()),
+        vec![rect(0, 0, 40, 10), rect(0, 10, 40, 10), rect(40, 0, 40, 20)]
+    );
+}
+
+#[test]
+fn master_stack_with_only_master_panes() {
+    let strategy = TilingStrategy::MasterStack {
+        master_ratio: 60,
+        master_count: 1,
+    };
+    assert_eq!(tile(strategy, 1, area()), vec![area()]);
+}
+
+#[test]
+fn bsp_splits_the_longer_side() {
+    assert_eq!(
+        tile(TilingStrategy::Bsp, 3, area()),
+        vec![
+            rect(0, 0, 40, 20),
+            rect(40, 0, 40, 10),
+            rect(40, 10, 40, 10)
+        ]
+    );
+}
+
+#[test]
+fn spiral_turns_clockwise() {
+    assert_eq!(
+        tile(TilingStrategy::Spiral, 4, area()),
+        vec![
+            rect(0, 0, 40, 20),
+            rect(40, 0, 40, 10),
+            rect(60, 10, 20, 10),
+            rect(40, 10, 20, 10),
+        ]
+    );
+}
+
+#[test]
+fn grid_spreads_the_last_row() {
+    assert_eq!(
+        tile(TilingStrategy::Grid, 5, area()),
+        vec![
+            rect(0, 0, 27, 10),
+            rect(27, 0, 27, 10),
+            rect(54, 0, 26, 10),
+            rect(0, 10, 40, 10),
+            rect(40, 10, 40, 10),
+        ]
+    );
+}
+
+#[test]
+fn columns_in_an_offset_area() {
+    assert_eq!(
+        tile(TilingStrategy::Columns, 3, rect(0, 1, 80, 18)),
+        vec![rect(0, 1, 27, 18), rect(27, 1, 27, 18), rect(54, 1, 26, 18)]
+    );
+}
+
+#[test]
+fn no_panes_no_rects() {
+    assert_eq!(tile(TilingStrategy::Grid, 0, area()), vec![]);
+}
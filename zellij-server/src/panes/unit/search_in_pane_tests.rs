@@ -424,3 +424,27 @@ pub fn searching_whole_word_case_insensitive() {
         format!("{:?}", terminal_pane.grid)
     );
 }
+
+#[test]
+pub fn revealing_a_search_match_in_the_scrollback() {
+    let mut terminal_pane = create_pane();
+    let contents = terminal_pane.pane_contents(None, true, None);
+    let line_index = contents
+        .lines_above_viewport
+        .iter()
+        .position(|line| line.contains("tortor"))
+        .expect("fixture has a match above the viewport");
+    terminal_pane.reveal_search_match("tortor", true, line_index);
+    let contents = terminal_pane.pane_contents(None, true, None);
+    assert_eq!(
+        contents.lines_above_viewport.len(),
+        line_index,
+        "matching line scrolled to the top of the viewport"
+    );
+    let active = terminal_pane
+        .grid
+        .search_results
+        .active
+        .expect("match is active");
+    assert!(contents.viewport[active.start.line() as usize].contains("tortor"));
+}
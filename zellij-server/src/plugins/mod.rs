@@ -13,6 +13,7 @@ use std::{
     collections::{BTreeMap, HashMap, HashSet},
     fs,
     path::PathBuf,
+    sync::{Arc, Mutex},
     time::Duration,
 };
 use wasmi::Engine;
@@ -44,10 +45,11 @@ use zellij_utils::{
     },
     ipc::ClientAttributes,
     pane_size::Size,
-    session_serialization,
+    session_serialization::{self, PluginStatesToRestore},
 };
 
 pub type PluginId = u32;
+pub type PluginCpuUsage = Arc<Mutex<BTreeMap<PluginId, Duration>>>; // CPU time spent running each plugin
 
 #[derive(Clone, Debug)]
 pub struct DumpSessionLayoutResponse {
@@ -221,6 +223,7 @@ pub enum PluginInstruction {
         matched_string: String,
         context: BTreeMap<String, String>,
     },
+    RevokePluginPermission(String, PermissionType, Option<PathBuf>), // String - plugin url, Option<PathBuf> - permission cache path
     Exit,
 }
 
@@ -285,6 +288,7 @@ impl From<&PluginInstruction> for PluginContext {
                 PluginContext::DetectPluginConfigChanges
             },
             PluginInstruction::HighlightClicked { .. } => PluginContext::HighlightClicked,
+            PluginInstruction::RevokePluginPermission(..) => PluginContext::RevokePluginPermission,
         }
     }
 }
@@ -306,6 +310,10 @@ pub(crate) fn plugin_thread_main(
     plugin_aliases: PluginAliases,
     default_mode: InputMode,
     default_keybinds: Keybinds,
+    plugin_cpu_budget: Option<u64>, // fuel per call into a plugin, None for no limit
+    plugin_cpu_usage: PluginCpuUsage,
+    plugin_bundle_dir: Option<PathBuf>, // load remote plugins from this bundle rather than download them
+    plugin_states_to_restore: PluginStatesToRestore, // saved before a resurrected session exited
     background_plugins: HashSet<RunPluginOrAlias>,
     // the client id that started the session,
     // we need it here because the thread's own list of connected clients might not yet be updated
@@ -324,6 +332,7 @@ pub(crate) fn plugin_thread_main(
 
     let mut wasm_bridge = WasmBridge::new(
         bus.senders.clone(),
+        bus.os_input.clone(),
         engine,
         plugin_dir,
         path_to_default_shell,
@@ -338,6 +347,10 @@ pub(crate) fn plugin_thread_main(
         available_layout_errors,
         default_mode,
         default_keybinds,
+        plugin_cpu_budget,
+        plugin_cpu_usage,
+        plugin_bundle_dir,
+        plugin_states_to_restore,
     );
 
     for run_plugin_or_alias in background_plugins {
@@ -427,6 +440,9 @@ pub(crate) fn plugin_thread_main(
                 );
             },
             PluginInstruction::Update(updates) => {
+                if let Some(os_input) = bus.os_input.as_ref() {
+                    os_input.report_plugin_events(&updates);
+                }
                 wasm_bridge.update_plugins(updates, shutdown_send.clone())?;
             },
             PluginInstruction::Unload(pid) => {
@@ -922,6 +938,7 @@ pub(crate) fn plugin_thread_main(
                     &plugin_aliases,
                     None,
                 );
+                wasm_bridge.save_plugin_states();
                 drop(
                     bus.senders
                         .send_to_pty(PtyInstruction::LogLayoutToHd(session_layout_metadata)),
@@ -1271,6 +1288,13 @@ pub(crate) fn plugin_thread_main(
                 let updates = vec![(Some(plugin_id), Some(client_id), event)];
                 wasm_bridge.update_plugins(updates, shutdown_send.clone())?;
             },
+            PluginInstruction::RevokePluginPermission(plugin_url, permission, cache_path) => {
+                if let Err(e) =
+                    wasm_bridge.revoke_plugin_permission(&plugin_url, permission, cache_path)
+                {
+                    log::error!("{}", e);
+                }
+            },
             PluginInstruction::Exit => {
                 break;
             },
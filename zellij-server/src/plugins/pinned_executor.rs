@@ -460,9 +460,10 @@ mod tests {
 
         let layout = Box::new(Layout::default());
 
-        let plugin_cache = Arc::new(Mutex::new(
-            std::collections::HashMap::<PathBuf, Module>::new(),
-        ));
+        let plugin_cache = Arc::new(Mutex::new(std::collections::HashMap::<
+            PathBuf,
+            (Module, String),
+        >::new()));
 
         let engine = Engine::default();
 
@@ -1,5 +1,5 @@
 use super::{PluginId, PluginInstruction};
-use crate::plugins::plugin_map::RunningPlugin;
+use crate::plugins::plugin_map::{call_with_cpu_budget, RunningPlugin};
 use crate::plugins::wasm_bridge::PluginRenderAsset;
 use crate::plugins::zellij_exports::{wasi_read_string, wasi_write_object};
 use std::collections::{HashMap, HashSet};
@@ -161,17 +161,19 @@ pub fn apply_pipe_message_to_plugin(
                 &protobuf_pipe_message.encode_to_vec(),
             )
             .with_context(err_context)?;
-            let should_render = pipe
-                .call(&mut running_plugin.store, ())
-                .with_context(err_context)?;
+            let should_render =
+                call_with_cpu_budget(&mut running_plugin.store, |store| pipe.call(store, ()))
+                    .with_context(err_context)?;
             let should_render = should_render == 1;
             if rows > 0 && columns > 0 && should_render {
                 let rendered_bytes = instance
                     .get_typed_func::<(i32, i32), ()>(&mut running_plugin.store, "render")
+                    .map_err(|e| anyhow!(e))
                     .and_then(|render| {
-                        render.call(&mut running_plugin.store, (rows as i32, columns as i32))
+                        call_with_cpu_budget(&mut running_plugin.store, |store| {
+                            render.call(store, (rows as i32, columns as i32))
+                        })
                     })
-                    .map_err(|e| anyhow!(e))
                     .and_then(|_| {
                         wasi_read_string(running_plugin.store.data()).map_err(|e| anyhow!(e))
                     })
@@ -1,10 +1,11 @@
 use crate::plugins::plugin_map::{
-    PluginEnv, PluginMap, RunningPlugin, VecDequeInputStream, WriteOutputStream,
+    call_with_cpu_budget, PluginEnv, PluginMap, RunningPlugin, VecDequeInputStream,
+    WriteOutputStream,
 };
 use crate::plugins::plugin_worker::{plugin_worker, RunningWorker};
-use crate::plugins::wasm_bridge::{LoadingContext, PluginCache};
+use crate::plugins::wasm_bridge::{restore_plugin_state, LoadingContext, PluginCache};
 use crate::plugins::zellij_exports::{wasi_write_object, zellij_exports};
-use crate::plugins::PluginId;
+use crate::plugins::{PluginCpuUsage, PluginId};
 use prost::Message;
 use std::{
     collections::{HashMap, HashSet, VecDeque},
@@ -19,7 +20,7 @@ use wasmi_wasi::Dir;
 use wasmi_wasi::WasiCtx;
 
 use crate::{
-    logging_pipe::LoggingPipe, thread_bus::ThreadSenders,
+    logging_pipe::LoggingPipe, os_input_output::ServerOsApi, thread_bus::ThreadSenders,
     ui::loading_indication::LoadingIndication, ClientId,
 };
 
@@ -31,6 +32,7 @@ use zellij_utils::{
     input::command::TerminalAction,
     input::keybinds::Keybinds,
     input::layout::Layout,
+    input::permission::plugin_content_hash,
     input::plugins::PluginConfig,
     ipc::ClientAttributes,
     pane_size::Size,
@@ -85,6 +87,11 @@ pub struct PluginLoader<'a> {
     keybinds: Keybinds,
     plugin_dir: PathBuf,
     size: Size,
+    saved_state: Option<Vec<u8>>,
+    cpu_budget: Option<u64>,
+    cpu_usage: PluginCpuUsage,
+    os_input: Option<Box<dyn ServerOsApi>>,
+    content_hash: String,
     loading_indication: LoadingIndication,
     senders: ThreadSenders,
     engine: Engine,
@@ -130,6 +137,11 @@ impl<'a> PluginLoader<'a> {
             keybinds: loading_context.keybinds,
             plugin_dir: loading_context.plugin_dir,
             size: loading_context.size,
+            saved_state: loading_context.saved_state,
+            cpu_budget: loading_context.cpu_budget,
+            cpu_usage: loading_context.cpu_usage,
+            os_input: loading_context.os_input,
+            content_hash: String::new(),
 
             skip_cache,
             senders,
@@ -160,6 +172,7 @@ impl<'a> PluginLoader<'a> {
     fn interpret_module(&mut self) -> Result<Module> {
         self.loading_indication.override_previous_error();
         let wasm_bytes = self.plugin_config.resolve_wasm_bytes(&self.plugin_dir)?;
+        self.content_hash = plugin_content_hash(&wasm_bytes);
         let timer = std::time::Instant::now();
         let module = Module::new(&self.engine, &wasm_bytes)?;
         log::info!(
@@ -170,13 +183,14 @@ impl<'a> PluginLoader<'a> {
         Ok(module)
     }
     fn load_module_from_memory(&mut self) -> Result<Module> {
-        let module = self
+        let (module, content_hash) = self
             .plugin_cache
             .lock()
             .unwrap()
             .remove(&self.plugin_config.path) // TODO: do we still bring it back later?
             // maybe we can forgo this dance?
             .ok_or(anyhow!("Plugin is not stored in memory"))?;
+        self.content_hash = content_hash;
         Ok(module)
     }
     fn load_plugin_instance(
@@ -203,9 +217,10 @@ impl<'a> PluginLoader<'a> {
                 let start_function_for_worker = instance
                     .get_typed_func::<(), ()>(&mut store, "_start")
                     .with_context(err_context)?;
-                start_function_for_worker
-                    .call(&mut store, ())
-                    .with_context(err_context)?;
+                call_with_cpu_budget(&mut store, |store| {
+                    start_function_for_worker.call(store, ())
+                })
+                .with_context(err_context)?;
 
                 let worker = RunningWorker::new(store, instance, &function_name);
                 let worker_sender = plugin_worker(worker);
@@ -228,9 +243,10 @@ impl<'a> PluginLoader<'a> {
             workers,
         );
 
-        start_function
-            .call(&mut plugin.lock().unwrap().store, ())
-            .with_context(err_context)?;
+        call_with_cpu_budget(&mut plugin.lock().unwrap().store, |store| {
+            start_function.call(store, ())
+        })
+        .with_context(err_context)?;
 
         let protobuf_plugin_configuration: ProtobufPluginConfiguration = self
             .plugin_config
@@ -241,9 +257,14 @@ impl<'a> PluginLoader<'a> {
         let protobuf_bytes = protobuf_plugin_configuration.encode_to_vec();
         wasi_write_object(plugin.lock().unwrap().store.data(), &protobuf_bytes)
             .with_context(err_context)?;
-        load_function
-            .call(&mut plugin.lock().unwrap().store, ())
-            .with_context(err_context)?;
+        call_with_cpu_budget(&mut plugin.lock().unwrap().store, |store| {
+            load_function.call(store, ())
+        })
+        .with_context(err_context)?;
+        if let Some(saved_state) = self.saved_state.as_ref() {
+            restore_plugin_state(self.plugin_id, &mut plugin.lock().unwrap(), saved_state)
+                .with_context(err_context)?;
+        }
 
         Ok(())
     }
@@ -298,6 +319,12 @@ impl<'a> PluginLoader<'a> {
             stdin_pipe,
             stdout_pipe,
             store_limits: create_optimized_store_limits(),
+            cpu_budget: self.cpu_budget,
+            cpu_usage: self.cpu_usage.clone(),
+            os_input: self.os_input.clone(),
+            exceeded_cpu_budget: false,
+            state_may_have_changed: true,
+            content_hash: self.content_hash.clone(),
         };
         let mut store = Store::new(&self.engine, plugin_env);
 
@@ -310,20 +337,21 @@ impl<'a> PluginLoader<'a> {
         })?;
         zellij_exports(&mut linker);
 
-        let instance = linker
-            .instantiate_and_start(&mut store, &module)
-            .with_context(err_context)?;
+        let instance = call_with_cpu_budget(&mut store, |store| {
+            linker.instantiate_and_start(store, &module)
+        })
+        .with_context(err_context)?;
 
         if let Some(func) = instance.get_func(&mut store, "_initialize") {
             if let Ok(typed_func) = func.typed::<(), ()>(&store) {
-                let _ = typed_func.call(&mut store, ());
+                let _ = call_with_cpu_budget(&mut store, |store| typed_func.call(store, ()));
             }
         }
 
         self.plugin_cache
             .lock()
             .unwrap()
-            .insert(plugin_path.clone(), module);
+            .insert(plugin_path.clone(), (module, self.content_hash.clone()));
         Ok((store, instance))
     }
     pub fn clone_instance_for_other_clients(&mut self) -> Result<()> {
@@ -360,8 +388,8 @@ impl<'a> PluginLoader<'a> {
             .lock()
             .unwrap()
             .get(&self.plugin_config.path)
-            .with_context(err_context)?
-            .clone();
+            .map(|(module, _content_hash)| module.clone())
+            .with_context(err_context)?;
         let (store, instance) = self.create_plugin_instance_env(&module)?;
         Ok((store, instance))
     }
@@ -413,6 +441,12 @@ impl<'a> PluginLoader<'a> {
             stdin_pipe,
             stdout_pipe,
             store_limits: create_optimized_store_limits(),
+            cpu_budget: self.cpu_budget,
+            cpu_usage: self.cpu_usage.clone(),
+            os_input: self.os_input.clone(),
+            exceeded_cpu_budget: false,
+            state_may_have_changed: true,
+            content_hash: self.content_hash.clone(),
         };
         let mut store = Store::new(&self.engine, plugin_env);
 
@@ -425,13 +459,14 @@ impl<'a> PluginLoader<'a> {
         })?;
         zellij_exports(&mut linker);
 
-        let instance = linker
-            .instantiate_and_start(&mut store, module)
-            .with_context(err_context)?;
+        let instance = call_with_cpu_budget(&mut store, |store| {
+            linker.instantiate_and_start(store, module)
+        })
+        .with_context(err_context)?;
 
         if let Some(func) = instance.get_func(&mut store, "_initialize") {
             if let Ok(typed_func) = func.typed::<(), ()>(&store) {
-                let _ = typed_func.call(&mut store, ());
+                let _ = call_with_cpu_budget(&mut store, |store| typed_func.call(store, ()));
             }
         }
 
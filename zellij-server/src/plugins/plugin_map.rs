@@ -1,15 +1,16 @@
 use crate::plugins::plugin_worker::MessageToWorker;
-use crate::plugins::PluginId;
+use crate::plugins::{PluginCpuUsage, PluginId};
 use std::io::Write;
 use std::{
     collections::{BTreeMap, HashMap, HashSet, VecDeque},
     path::PathBuf,
     sync::{Arc, Mutex},
+    time::Duration,
 };
-use wasmi::{Instance, Store, StoreLimits};
+use wasmi::{Instance, Store, StoreLimits, TrapCode};
 use wasmi_wasi::WasiCtx;
 
-use crate::{thread_bus::ThreadSenders, ClientId};
+use crate::{os_input_output::ServerOsApi, thread_bus::ThreadSenders, ClientId};
 
 use tokio::sync::mpsc::UnboundedSender;
 use zellij_utils::{
@@ -301,6 +302,12 @@ pub struct PluginEnv {
     pub keybinds: Keybinds,
     pub intercepting_key_presses: bool,
     pub store_limits: StoreLimits,
+    pub cpu_budget: Option<u64>, // fuel per call into the plugin, None for no limit
+    pub cpu_usage: PluginCpuUsage,
+    pub os_input: Option<Box<dyn ServerOsApi>>, // None when the plugin thread runs without one, eg. in tests
+    pub exceeded_cpu_budget: bool,
+    pub state_may_have_changed: bool, // set by every call into the plugin, cleared when its state is saved
+    pub content_hash: String, // sha256 of the plugin's wasm bytes, permissions are pinned to it
 }
 
 #[derive(Clone)]
@@ -352,6 +359,64 @@ impl PluginEnv {
     }
 }
 
+/// Calls into a plugin (or one of its workers) with a fresh CPU budget (measured in wasm fuel),
+/// adding the CPU time spent in the call to the plugin's CPU usage. A plugin that runs out of
+/// budget is stopped until reloaded.
+pub fn call_with_cpu_budget<T>(
+    store: &mut Store<PluginEnv>,
+    call: impl FnOnce(&mut Store<PluginEnv>) -> std::result::Result<T, wasmi::Error>,
+) -> Result<T> {
+    if store.data().exceeded_cpu_budget {
+        return Err(anyhow!(
+            "plugin {} was stopped after exceeding its CPU budget",
+            store.data().name()
+        ));
+    }
+    // this fails if fuel metering is disabled in the engine, in which case there's nothing to limit
+    let _ = store.set_fuel(store.data().cpu_budget.unwrap_or(u64::MAX));
+    let started_at = thread_cpu_time();
+    let result = call(store);
+    store.data_mut().state_may_have_changed = true;
+    let plugin_env = store.data();
+    *plugin_env
+        .cpu_usage
+        .lock()
+        .unwrap()
+        .entry(plugin_env.plugin_id)
+        .or_default() += thread_cpu_time().saturating_sub(started_at);
+    match result {
+        Ok(result) => Ok(result),
+        Err(e) if e.as_trap_code() == Some(TrapCode::OutOfFuel) => {
+            store.data_mut().exceeded_cpu_budget = true;
+            let plugin_env = store.data();
+            Err(anyhow!(
+                "plugin {} exceeded its CPU budget.\nReload it from the plugin manager or with: zellij action start-or-reload-plugin {}",
+                plugin_env.name(),
+                plugin_env.plugin.location.display()
+            ))
+        },
+        Err(e) => Err(e.into()),
+    }
+}
+
+// calls into plugins run synchronously on the calling thread, so the difference in this thread's
+// CPU time is the CPU time of the call, regardless of how long the thread waited to be scheduled
+#[cfg(unix)]
+fn thread_cpu_time() -> Duration {
+    use nix::time::{clock_gettime, ClockId};
+    clock_gettime(ClockId::CLOCK_THREAD_CPUTIME_ID)
+        .map(|time| Duration::new(time.tv_sec() as u64, time.tv_nsec() as u32))
+        .unwrap_or_default()
+}
+
+#[cfg(not(unix))]
+fn thread_cpu_time() -> Duration {
+    // no per-thread CPU clock here, fall back to wall-clock time since the first call
+    use std::time::Instant;
+    static STARTED_AT: std::sync::OnceLock<Instant> = std::sync::OnceLock::new();
+    STARTED_AT.get_or_init(Instant::now).elapsed()
+}
+
 #[derive(Eq, PartialEq, Hash)]
 pub enum AtomicEvent {
     Resize,
@@ -1,4 +1,4 @@
-use crate::plugins::plugin_map::PluginEnv;
+use crate::plugins::plugin_map::{call_with_cpu_budget, PluginEnv};
 use crate::plugins::zellij_exports::wasi_write_object;
 use wasmi::{Instance, Store};
 
@@ -34,8 +34,7 @@ impl RunningWorker {
             .get_typed_func::<(), ()>(&mut self.store, &self.name)
             .with_context(err_context)?;
         wasi_write_object(self.store.data(), &protobuf_bytes).with_context(err_context)?;
-        work_function
-            .call(&mut self.store, ())
+        call_with_cpu_budget(&mut self.store, |store| work_function.call(store, ()))
             .with_context(err_context)?;
         Ok(())
     }
@@ -21,6 +21,7 @@ use zellij_utils::input::permission::PermissionCache;
 use zellij_utils::input::plugins::PluginAliases;
 use zellij_utils::ipc::ClientAttributes;
 use zellij_utils::pane_size::Size;
+use zellij_utils::session_serialization::{deserialize_plugin_states, PluginStatesToRestore};
 
 use crate::background_jobs::BackgroundJob;
 use crate::pty_writer::PtyWriteInstruction;
@@ -301,6 +302,18 @@ fn create_plugin_thread(
     SenderWithContext<PluginInstruction>,
     Receiver<(ScreenInstruction, ErrorContext)>,
     Box<dyn FnOnce()>,
+) {
+    create_plugin_thread_with_cpu_budget(zellij_cwd, session_env_vars, None)
+}
+
+fn create_plugin_thread_with_cpu_budget(
+    zellij_cwd: Option<PathBuf>,
+    session_env_vars: Option<std::collections::BTreeMap<String, String>>,
+    plugin_cpu_budget: Option<u64>,
+) -> (
+    SenderWithContext<PluginInstruction>,
+    Receiver<(ScreenInstruction, ErrorContext)>,
+    Box<dyn FnOnce()>,
 ) {
     let zellij_cwd = zellij_cwd.unwrap_or_else(|| PathBuf::from("."));
     let session_env_vars = session_env_vars.unwrap_or_else(|| std::env::vars().collect());
@@ -339,6 +352,7 @@ fn create_plugin_thread(
     let mut config = wasmi::Config::default();
     config.set_max_stack_height(1024 * 1024);
     config.set_max_recursion_depth(1000);
+    config.consume_fuel(plugin_cpu_budget.is_some());
     let engine = Engine::new(&config);
     let data_dir = PathBuf::from(tempdir().unwrap().path());
     let layout_dir = PathBuf::from(tempdir().unwrap().path());
@@ -378,6 +392,10 @@ fn create_plugin_thread(
                 plugin_aliases,
                 InputMode::Normal,
                 Keybinds::default(),
+                plugin_cpu_budget,
+                Default::default(),
+                None,
+                Default::default(),
                 Default::default(),
                 initiating_client_id,
             )
@@ -470,6 +488,10 @@ fn create_plugin_thread_with_server_receiver(
                 PluginAliases::default(),
                 InputMode::Normal,
                 Keybinds::default(),
+                None,
+                Default::default(),
+                None,
+                Default::default(),
                 Default::default(),
                 initiating_client_id,
             )
@@ -570,6 +592,10 @@ fn create_plugin_thread_with_pty_receiver(
                 PluginAliases::default(),
                 InputMode::Normal,
                 Keybinds::default(),
+                None,
+                Default::default(),
+                None,
+                Default::default(),
                 Default::default(),
                 initiating_client_id,
             )
@@ -598,6 +624,23 @@ fn create_plugin_thread_with_background_jobs_receiver(
     Receiver<(BackgroundJob, ErrorContext)>,
     Receiver<(ScreenInstruction, ErrorContext)>,
     Box<dyn FnOnce()>,
+) {
+    create_plugin_thread_with_plugin_states_to_restore(
+        zellij_cwd,
+        session_env_vars,
+        PluginStatesToRestore::default(),
+    )
+}
+
+fn create_plugin_thread_with_plugin_states_to_restore(
+    zellij_cwd: Option<PathBuf>,
+    session_env_vars: Option<std::collections::BTreeMap<String, String>>,
+    plugin_states_to_restore: PluginStatesToRestore,
+) -> (
+    SenderWithContext<PluginInstruction>,
+    Receiver<(BackgroundJob, ErrorContext)>,
+    Receiver<(ScreenInstruction, ErrorContext)>,
+    Box<dyn FnOnce()>,
 ) {
     let zellij_cwd = zellij_cwd.unwrap_or_else(|| PathBuf::from("."));
     let session_env_vars = session_env_vars.unwrap_or_else(|| std::env::vars().collect());
@@ -663,6 +706,10 @@ fn create_plugin_thread_with_background_jobs_receiver(
                 PluginAliases::default(),
                 InputMode::Normal,
                 Keybinds::default(),
+                None,
+                Default::default(),
+                None,
+                plugin_states_to_restore,
                 Default::default(),
                 initiating_client_id,
             )
@@ -785,6 +832,227 @@ pub fn load_new_plugin_from_hd() {
     assert_snapshot!(format!("{:#?}", plugin_bytes_event));
 }
 
+/// Load identical instances of the fixture plugin that save their state, send
+/// `inputs_received[i]` InputReceived events to the i-th of them and return the last render of
+/// each that contains `expected_render`, in the order they were loaded
+fn load_plugins_that_save_their_state(
+    plugin_thread_sender: &SenderWithContext<PluginInstruction>,
+    screen_receiver: Receiver<(ScreenInstruction, ErrorContext)>,
+    inputs_received: &[usize],
+    cache_path: PathBuf,
+    expected_render: &'static str,
+) -> Vec<String> {
+    let client_id = 1;
+    let plugin_count = inputs_received.len();
+    let rendered = Arc::new(Mutex::new(BTreeMap::new()));
+    let screen_thread = std::thread::Builder::new()
+        .name("fake_screen_thread".to_string())
+        .spawn({
+            let rendered = rendered.clone();
+            let plugin_thread_sender = plugin_thread_sender.clone();
+            move || {
+                while let Ok((event, _err_ctx)) =
+                    screen_receiver.recv_timeout(std::time::Duration::from_secs(10))
+                {
+                    match event {
+                        ScreenInstruction::RequestPluginPermissions(plugin_id, permissions) => {
+                            let _ = plugin_thread_sender.send(
+                                PluginInstruction::PermissionRequestResult(
+                                    plugin_id,
+                                    Some(client_id),
+                                    permissions.permissions,
+                                    PermissionStatus::Granted,
+                                    Some(cache_path.clone()),
+                                ),
+                            );
+                        },
+                        ScreenInstruction::PluginBytes(plugin_render_assets) => {
+                            let mut rendered = rendered.lock().unwrap();
+                            for plugin_render_asset in plugin_render_assets {
+                                let plugin_bytes =
+                                    String::from_utf8_lossy(plugin_render_asset.bytes.as_slice())
+                                        .to_string();
+                                if plugin_bytes.contains(expected_render) {
+                                    rendered.insert(plugin_render_asset.plugin_id, plugin_bytes);
+                                }
+                            }
+                            if rendered.len() == plugin_count {
+                                break;
+                            }
+                        },
+                        _ => {},
+                    }
+                }
+            }
+        })
+        .unwrap();
+    let run_plugin = RunPluginOrAlias::RunPlugin(RunPlugin {
+        _allow_exec_host_cmd: false,
+        location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
+        configuration: PluginUserConfiguration::new(BTreeMap::from([(
+            "save_state".to_owned(),
+            "true".to_owned(),
+        )])),
+        ..Default::default()
+    });
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
+    for _ in inputs_received {
+        let _ = plugin_thread_sender.send(PluginInstruction::Load(
+            Some(false),
+            false,
+            false,
+            None,
+            run_plugin.clone(),
+            Some(1),
+            None,
+            client_id,
+            size,
+            None,
+            None,
+            false,
+            None,
+            None,
+            None,
+        ));
+    }
+    std::thread::sleep(std::time::Duration::from_millis(500));
+    for (plugin_id, inputs_received) in inputs_received.iter().enumerate() {
+        for _ in 0..*inputs_received {
+            let _ = plugin_thread_sender.send(PluginInstruction::Update(vec![(
+                Some(plugin_id as u32),
+                Some(client_id),
+                Event::InputReceived,
+            )]));
+        }
+    }
+    screen_thread.join().unwrap();
+    let rendered = rendered.lock().unwrap().values().cloned().collect();
+    rendered
+}
+
+#[test]
+#[ignore]
+pub fn plugin_states_of_identical_plugins_survive_session_resurrection() {
+    let temp_folder = tempdir().unwrap();
+    let plugin_host_folder = PathBuf::from(temp_folder.path());
+    let cache_path = plugin_host_folder.join("permissions_test.kdl");
+
+    let (plugin_thread_sender, background_jobs_receiver, screen_receiver, teardown) =
+        create_plugin_thread_with_background_jobs_receiver(None, None);
+    load_plugins_that_save_their_state(
+        &plugin_thread_sender,
+        screen_receiver,
+        &[1, 3],
+        cache_path.clone(),
+        "InputReceived",
+    );
+    let _ = plugin_thread_sender.send(PluginInstruction::LogLayoutToHd(Default::default()));
+    // each plugin reports the states of all plugins once it saved its own
+    let mut serialized_plugin_states = None;
+    while let Ok((event, _err_ctx)) =
+        background_jobs_receiver.recv_timeout(std::time::Duration::from_secs(10))
+    {
+        if let BackgroundJob::ReportPluginStates(plugin_states) = event {
+            let saved_plugin_count = plugin_states.matches("plugin location=").count();
+            serialized_plugin_states = Some(plugin_states);
+            if saved_plugin_count == 2 {
+                break;
+            }
+        }
+    }
+    teardown();
+    let plugin_states_to_restore =
+        deserialize_plugin_states(&serialized_plugin_states.expect("plugin states reported"))
+            .unwrap();
+
+    let (plugin_thread_sender, _background_jobs_receiver, screen_receiver, teardown) =
+        create_plugin_thread_with_plugin_states_to_restore(None, None, plugin_states_to_restore);
+    let rendered = load_plugins_that_save_their_state(
+        &plugin_thread_sender,
+        screen_receiver,
+        &[0, 0],
+        cache_path,
+        "Restored inputs received",
+    );
+    teardown();
+    assert_eq!(rendered.len(), 2, "Both plugins restored their state");
+    assert!(
+        rendered[0].contains("Restored inputs received: 1"),
+        "{:?}",
+        rendered
+    );
+    assert!(
+        rendered[1].contains("Restored inputs received: 3"),
+        "{:?}",
+        rendered
+    );
+}
+
+#[test]
+#[ignore]
+pub fn plugin_over_its_cpu_budget_is_stopped_and_marked_errored() {
+    // a budget of a single unit of fuel is exhausted as soon as the plugin starts running, so it
+    // should be stopped and its pane should show the error rather than anything it rendered
+    let (plugin_thread_sender, screen_receiver, teardown) =
+        create_plugin_thread_with_cpu_budget(None, None, Some(1));
+    let run_plugin = RunPluginOrAlias::RunPlugin(RunPlugin {
+        _allow_exec_host_cmd: false,
+        location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
+        configuration: Default::default(),
+        ..Default::default()
+    });
+    let client_id = 1;
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
+    let _ = plugin_thread_sender.send(PluginInstruction::Load(
+        Some(false),
+        false,
+        false, // close_replaced_pane
+        Some("test_plugin".to_owned()),
+        run_plugin,
+        Some(1),
+        None,
+        client_id,
+        size,
+        None,
+        None,
+        false,
+        None,
+        None,
+        None,
+    ));
+    let _ = plugin_thread_sender.send(PluginInstruction::Update(vec![(
+        None,
+        Some(client_id),
+        Event::InputReceived,
+    )]));
+    let mut rendered_plugin_bytes = false;
+    let marked_errored = loop {
+        match screen_receiver.recv_timeout(std::time::Duration::from_secs(30)) {
+            Ok((ScreenInstruction::UpdatePluginLoadingStage(_, loading_indication), _)) => {
+                if loading_indication.is_error() {
+                    break true;
+                }
+            },
+            Ok((ScreenInstruction::PluginBytes(_), _)) => {
+                rendered_plugin_bytes = true;
+            },
+            Ok(_) => {},
+            Err(_) => break false,
+        }
+    };
+    teardown();
+    assert!(marked_errored, "plugin marked errored");
+    assert!(!rendered_plugin_bytes, "plugin stopped before rendering");
+}
+
 #[test]
 #[ignore]
 pub fn load_new_plugin_with_plugin_alias() {
@@ -5976,6 +6244,117 @@ pub fn granted_permission_request_result() {
     assert_snapshot!(format!("{:#?}", permissions));
 }
 
+#[test]
+#[ignore]
+pub fn revoked_permission_is_removed_from_cache_and_running_plugin() {
+    let temp_folder = tempdir().unwrap();
+    let plugin_host_folder = PathBuf::from(temp_folder.path());
+    let cache_path = plugin_host_folder.join("permissions_test.kdl");
+
+    let (plugin_thread_sender, screen_receiver, teardown) =
+        create_plugin_thread(Some(plugin_host_folder), None);
+    let run_plugin = RunPluginOrAlias::RunPlugin(RunPlugin {
+        _allow_exec_host_cmd: false,
+        location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
+        configuration: Default::default(),
+        ..Default::default()
+    });
+    let plugin_url = PathBuf::from(&*PLUGIN_FIXTURE).display().to_string();
+    let client_id = 1;
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+
+    // grants every permission the plugin asks for and reports the tab switches it performs
+    let (tab_switch_sender, tab_switch_receiver) = std::sync::mpsc::channel();
+    let screen_thread = std::thread::Builder::new()
+        .name("fake_screen_thread".to_string())
+        .spawn({
+            let cache_path = cache_path.clone();
+            let plugin_thread_sender = plugin_thread_sender.clone();
+            move || loop {
+                let (event, _err_ctx) = screen_receiver
+                    .recv()
+                    .expect("failed to receive event on channel");
+                match event {
+                    ScreenInstruction::RequestPluginPermissions(_, plugin_permission) => {
+                        let _ =
+                            plugin_thread_sender.send(PluginInstruction::PermissionRequestResult(
+                                0,
+                                Some(client_id),
+                                plugin_permission.permissions,
+                                PermissionStatus::Granted,
+                                Some(cache_path.clone()),
+                            ));
+                    },
+                    ScreenInstruction::SwitchTabNext(..) => {
+                        let _ = tab_switch_sender.send(());
+                    },
+                    ScreenInstruction::Exit => {
+                        break;
+                    },
+                    _ => {},
+                }
+            }
+        })
+        .unwrap();
+
+    let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
+    let _ = plugin_thread_sender.send(PluginInstruction::Load(
+        Some(false),
+        false,
+        false, // close_replaced_pane
+        Some("test_plugin".to_owned()),
+        run_plugin,
+        Some(1),
+        None,
+        client_id,
+        size,
+        None,
+        None,
+        false,
+        None,
+        None,
+        None,
+    ));
+    std::thread::sleep(std::time::Duration::from_millis(500));
+    let switch_to_next_tab = || {
+        let _ = plugin_thread_sender.send(PluginInstruction::Update(vec![(
+            None,
+            Some(client_id),
+            Event::Key(KeyWithModifier::new(BareKey::Char('d'))), // this triggers the event in the fixture plugin
+        )]));
+    };
+    switch_to_next_tab();
+    tab_switch_receiver
+        .recv_timeout(std::time::Duration::from_secs(60)) // this might take a while if the cache is cold
+        .expect("Plugin could not switch tabs before its permission was revoked");
+    let _ = plugin_thread_sender.send(PluginInstruction::RevokePluginPermission(
+        plugin_url.clone(),
+        PermissionType::ChangeApplicationState,
+        Some(cache_path.clone()),
+    ));
+    switch_to_next_tab();
+    let switched_after_revoking = tab_switch_receiver
+        .recv_timeout(std::time::Duration::from_secs(1))
+        .is_ok();
+    teardown();
+    screen_thread.join().unwrap();
+
+    assert!(
+        !switched_after_revoking,
+        "Running plugin lost the permission as soon as it was revoked"
+    );
+    let permission_cache = PermissionCache::from_path_or_default(Some(cache_path));
+    let permissions = permission_cache.get_permissions(plugin_url).unwrap();
+    assert!(!permissions.contains(&PermissionType::ChangeApplicationState));
+    assert!(
+        permissions.contains(&PermissionType::ReadApplicationState),
+        "Other permissions are kept"
+    );
+}
+
 #[test]
 #[ignore]
 pub fn denied_permission_request_result() {
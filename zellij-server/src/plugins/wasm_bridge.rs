@@ -1,14 +1,16 @@
-use super::{PinnedExecutor, PluginId, PluginInstruction};
+use super::{PinnedExecutor, PluginCpuUsage, PluginId, PluginInstruction};
 use crate::global_async_runtime::get_tokio_runtime;
 use crate::plugins::pipes::{
     apply_pipe_message_to_plugin, pipes_to_block_or_unblock, PendingPipes, PipeStateChange,
 };
 use crate::plugins::plugin_loader::PluginLoader;
-use crate::plugins::plugin_map::{AtomicEvent, PluginEnv, PluginMap, RunningPlugin, Subscriptions};
+use crate::plugins::plugin_map::{
+    call_with_cpu_budget, AtomicEvent, PluginEnv, PluginMap, RunningPlugin, Subscriptions,
+};
 
 use crate::plugins::plugin_worker::MessageToWorker;
 use crate::plugins::watch_filesystem::watch_filesystem;
-use crate::plugins::zellij_exports::{wasi_read_string, wasi_write_object};
+use crate::plugins::zellij_exports::{wasi_read_bytes, wasi_read_string, wasi_write_object};
 use highway::{HighwayHash, PortableHash};
 use log::info;
 use notify_debouncer_full::{notify::RecommendedWatcher, Debouncer, FileIdMap};
@@ -30,14 +32,18 @@ use zellij_utils::downloader::Downloader;
 use zellij_utils::input::keybinds::Keybinds;
 use zellij_utils::input::permission::PermissionCache;
 use zellij_utils::plugin_api::event::ProtobufEvent;
+use zellij_utils::plugin_bundle::PluginBundle;
+use zellij_utils::session_serialization::{
+    serialize_plugin_states, PluginStates, PluginStatesToRestore, SavedPluginState,
+};
 
 use prost::Message;
 
 use crate::panes::PaneId;
 use crate::{
-    background_jobs::BackgroundJob, route::NotificationEnd, screen::ScreenInstruction,
-    thread_bus::ThreadSenders, ui::loading_indication::LoadingIndication, ClientId,
-    ServerInstruction,
+    background_jobs::BackgroundJob, os_input_output::ServerOsApi, route::NotificationEnd,
+    screen::ScreenInstruction, thread_bus::ThreadSenders,
+    ui::loading_indication::LoadingIndication, ClientId, ServerInstruction,
 };
 use zellij_utils::{
     data::{Event, EventType, PluginCapabilities},
@@ -93,7 +99,7 @@ impl PluginRenderAsset {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct LoadingContext {
     pub plugin_id: PluginId,
     pub client_id: ClientId,
@@ -112,6 +118,10 @@ pub struct LoadingContext {
     pub keybinds: Keybinds,
     pub plugin_dir: PathBuf,
     pub size: Size,
+    pub saved_state: Option<Vec<u8>>,
+    pub cpu_budget: Option<u64>,
+    pub cpu_usage: PluginCpuUsage,
+    pub os_input: Option<Box<dyn ServerOsApi>>,
 }
 
 impl LoadingContext {
@@ -163,18 +173,27 @@ impl LoadingContext {
             tab_index,
             plugin_dir: wasm_bridge.plugin_dir.clone(),
             size,
+            saved_state: None,
+            cpu_budget: wasm_bridge.plugin_cpu_budget,
+            cpu_usage: wasm_bridge.plugin_cpu_usage.clone(),
+            os_input: wasm_bridge.os_input.clone(),
         }
     }
+    pub fn with_saved_state(mut self, saved_state: Option<Vec<u8>>) -> Self {
+        self.saved_state = saved_state;
+        self
+    }
     pub fn update_plugin_path(&mut self, new_path: PathBuf) {
         self.plugin_config.path = new_path;
     }
 }
 
-pub type PluginCache = Arc<Mutex<HashMap<PathBuf, Module>>>;
+pub type PluginCache = Arc<Mutex<HashMap<PathBuf, (Module, String)>>>; // String - content hash
 
 pub struct WasmBridge {
     connected_clients: Arc<Mutex<Vec<ClientId>>>,
     senders: ThreadSenders,
+    os_input: Option<Box<dyn ServerOsApi>>,
     plugin_dir: PathBuf,
     plugin_map: Arc<Mutex<PluginMap>>,
     plugin_executor: Arc<PinnedExecutor>,
@@ -208,11 +227,17 @@ pub struct WasmBridge {
     downloader: Downloader,
     previous_pane_render_report: Option<PaneRenderReport>,
     pub last_session_save_time: Arc<Mutex<Option<u64>>>, // milliseconds since UNIX epoch
+    saved_plugin_states: Arc<Mutex<PluginStates>>,       // states reported by running plugins
+    plugin_states_to_restore: PluginStatesToRestore, // states read from disk when resurrecting a session
+    plugin_cpu_budget: Option<u64>, // fuel per call into a plugin, None for no limit
+    plugin_cpu_usage: PluginCpuUsage,
+    plugin_bundle_dir: Option<PathBuf>, // load remote plugins from this bundle rather than download them
 }
 
 impl WasmBridge {
     pub fn new(
         senders: ThreadSenders,
+        os_input: Option<Box<dyn ServerOsApi>>,
         engine: Engine,
         plugin_dir: PathBuf,
         path_to_default_shell: PathBuf,
@@ -227,11 +252,14 @@ impl WasmBridge {
         available_layout_errors: Vec<LayoutWithError>,
         default_mode: InputMode,
         default_keybinds: Keybinds,
+        plugin_cpu_budget: Option<u64>,
+        plugin_cpu_usage: PluginCpuUsage,
+        plugin_bundle_dir: Option<PathBuf>,
+        plugin_states_to_restore: PluginStatesToRestore,
     ) -> Self {
         let plugin_map = Arc::new(Mutex::new(PluginMap::default()));
         let connected_clients: Arc<Mutex<Vec<ClientId>>> = Arc::new(Mutex::new(vec![]));
-        let plugin_cache: Arc<Mutex<HashMap<PathBuf, Module>>> =
-            Arc::new(Mutex::new(HashMap::new()));
+        let plugin_cache: PluginCache = Arc::new(Mutex::new(HashMap::new()));
         let watcher = None;
         let downloader = Downloader::new(ZELLIJ_CACHE_DIR.to_path_buf());
         let max_threads = num_cpus::get().max(4).min(16);
@@ -247,6 +275,7 @@ impl WasmBridge {
         WasmBridge {
             connected_clients,
             senders,
+            os_input,
             plugin_dir,
             plugin_map,
             plugin_executor,
@@ -276,6 +305,11 @@ impl WasmBridge {
             downloader,
             previous_pane_render_report: None,
             last_session_save_time: Arc::new(Mutex::new(None)),
+            saved_plugin_states: Arc::new(Mutex::new(PluginStates::new())),
+            plugin_states_to_restore,
+            plugin_cpu_budget,
+            plugin_cpu_usage,
+            plugin_bundle_dir,
         }
     }
     pub fn load_plugin(
@@ -353,6 +387,9 @@ impl WasmBridge {
                 // Check if we need to download (async I/O required)
                 let needs_download = matches!(plugin.location, RunPluginLocation::Remote(_));
 
+                let saved_state = self
+                    .plugin_states_to_restore
+                    .take(&run.location.display(), &run.configuration);
                 let mut loading_context = LoadingContext::new(
                     &self,
                     Some(zellij_cwd.clone()),
@@ -361,17 +398,39 @@ impl WasmBridge {
                     client_id,
                     tab_index,
                     size,
-                );
+                )
+                .with_saved_state(saved_state);
 
                 if needs_download {
                     let downloader = self.downloader.clone();
+                    let plugin_bundle_dir = self.plugin_bundle_dir.clone();
                     get_tokio_runtime().spawn(async move {
                         let _ = senders.send_to_background_jobs(
                             BackgroundJob::AnimatePluginLoading(plugin_id),
                         );
                         let mut loading_indication = LoadingIndication::new(plugin_name.clone());
 
-                        if let RunPluginLocation::Remote(url) = &plugin.location {
+                        if let (RunPluginLocation::Remote(url), Some(plugin_bundle_dir)) =
+                            (&plugin.location, &plugin_bundle_dir)
+                        {
+                            match PluginBundle::from_dir(plugin_bundle_dir)
+                                .and_then(|plugin_bundle| plugin_bundle.resolve(url))
+                            {
+                                Ok(bundled_plugin_path) => {
+                                    loading_context.update_plugin_path(bundled_plugin_path)
+                                },
+                                Err(e) => {
+                                    handle_plugin_loading_failure(
+                                        &senders,
+                                        plugin_id,
+                                        &mut loading_indication,
+                                        e,
+                                        Some(client_id),
+                                    );
+                                    return;
+                                },
+                            }
+                        } else if let RunPluginLocation::Remote(url) = &plugin.location {
                             let file_name: String = PortableHash::default()
                                 .hash128(url.as_bytes())
                                 .iter()
@@ -540,6 +599,11 @@ impl WasmBridge {
             let _ = self
                 .senders
                 .send_to_screen(ScreenInstruction::ClearAllPluginHighlights(plugin_id));
+            self.plugin_cpu_usage.lock().unwrap().remove(&plugin_id);
+            // Kill any processes this plugin spawned and is still streaming from
+            let _ = self
+                .senders
+                .send_to_background_jobs(BackgroundJob::KillPluginProcesses(plugin_id));
 
             // Send worker exit messages
             for (_worker_name, worker_sender) in workers {
@@ -674,6 +738,19 @@ impl WasmBridge {
             move |senders, plugin_map, connected_clients, default_layout, plugin_cache, engine| {
                 let skip_cache = true; // we want to explicitly reload the plugin
                 let mut plugin_map = plugin_map.lock().unwrap();
+                // save the state of the running instance so that the reloaded one can pick up
+                // where it left off
+                let saved_state =
+                    plugin_map
+                        .get_running_plugin(plugin_id, None)
+                        .and_then(|running_plugin| {
+                            save_plugin_state(plugin_id, &mut running_plugin.lock().unwrap())
+                                .unwrap_or_else(|e| {
+                                    log::error!("{:?}", e);
+                                    None
+                                })
+                        });
+                let loading_context = loading_context.with_saved_state(saved_state);
                 match PluginLoader::new(
                     skip_cache,
                     loading_context,
@@ -719,6 +796,97 @@ impl WasmBridge {
         }
         Ok(())
     }
+    pub fn save_plugin_states(&self) {
+        let mut plugin_ids: Vec<PluginId> = self
+            .all_plugin_ids()
+            .into_iter()
+            .map(|(plugin_id, _client_id)| plugin_id)
+            .collect();
+        plugin_ids.sort();
+        plugin_ids.dedup();
+        {
+            // states of plugins that were unloaded since they were saved
+            let mut saved_plugin_states = self.saved_plugin_states.lock().unwrap();
+            let saved_plugin_count = saved_plugin_states.len();
+            saved_plugin_states.retain(|plugin_id, _| plugin_ids.binary_search(plugin_id).is_ok());
+            if saved_plugin_states.len() != saved_plugin_count {
+                let _ = self
+                    .senders
+                    .send_to_background_jobs(BackgroundJob::ReportPluginStates(
+                        serialize_plugin_states(&saved_plugin_states),
+                    ));
+            }
+        }
+        for plugin_id in plugin_ids {
+            let Some(run_plugin) = self.run_plugin_of_plugin_id(plugin_id) else {
+                continue;
+            };
+            let location = run_plugin.location.display();
+            let configuration = run_plugin.configuration.clone();
+            let saved_plugin_states = self.saved_plugin_states.clone();
+            self.plugin_executor.execute_for_plugin(
+                plugin_id,
+                move |senders,
+                      plugin_map,
+                      _connected_clients,
+                      _default_layout,
+                      _plugin_cache,
+                      _engine| {
+                    let Some(running_plugin) = plugin_map
+                        .lock()
+                        .unwrap()
+                        .get_running_plugin(plugin_id, None)
+                    else {
+                        return;
+                    };
+                    if !running_plugin
+                        .lock()
+                        .unwrap()
+                        .store
+                        .data()
+                        .state_may_have_changed
+                    {
+                        // nothing happened in the plugin since we last saved its state
+                        return;
+                    }
+                    let saved_state =
+                        match save_plugin_state(plugin_id, &mut running_plugin.lock().unwrap()) {
+                            Ok(saved_state) => saved_state,
+                            Err(e) => {
+                                log::error!("{:?}", e);
+                                return;
+                            },
+                        };
+                    let mut saved_plugin_states = saved_plugin_states.lock().unwrap();
+                    match saved_state {
+                        Some(saved_state) => {
+                            if saved_plugin_states.get(&plugin_id).map(|s| &s.state)
+                                == Some(&saved_state)
+                            {
+                                return;
+                            }
+                            saved_plugin_states.insert(
+                                plugin_id,
+                                SavedPluginState {
+                                    location,
+                                    configuration,
+                                    state: saved_state,
+                                },
+                            );
+                        },
+                        None => {
+                            if saved_plugin_states.remove(&plugin_id).is_none() {
+                                return;
+                            }
+                        },
+                    }
+                    let _ = senders.send_to_background_jobs(BackgroundJob::ReportPluginStates(
+                        serialize_plugin_states(&saved_plugin_states),
+                    ));
+                },
+            );
+        }
+    }
     pub fn add_client(&mut self, client_id: ClientId) -> Result<()> {
         if self.client_is_connected(&client_id) {
             return Ok(());
@@ -877,13 +1045,13 @@ impl WasmBridge {
                                         &mut running_plugin.store,
                                         "render",
                                     )
+                                    .map_err(|e| anyhow!(e))
                                     .and_then(|render| {
-                                        render.call(
-                                            &mut running_plugin.store,
-                                            (new_rows as i32, new_columns as i32),
-                                        )
+                                        call_with_cpu_budget(&mut running_plugin.store, |store| {
+                                            render
+                                                .call(store, (new_rows as i32, new_columns as i32))
+                                        })
                                     })
-                                    .map_err(|e| anyhow!(e))
                                     .and_then(|_| {
                                         wasi_read_string(running_plugin.store.data())
                                             .map_err(|e| anyhow!(e))
@@ -1844,6 +2012,11 @@ impl WasmBridge {
             };
         }
     }
+    fn plugin_permissions_cache_path(&self) -> Option<PathBuf> {
+        self.os_input
+            .as_ref()
+            .and_then(|os_input| os_input.plugin_permissions_cache_path())
+    }
     pub fn cache_plugin_permissions(
         &mut self,
         plugin_id: PluginId,
@@ -1874,14 +2047,45 @@ impl WasmBridge {
             .data_mut()
             .set_permissions(HashSet::from_iter(permissions.clone()));
 
+        let cache_path = cache_path.or_else(|| self.plugin_permissions_cache_path());
         let mut permission_cache = PermissionCache::from_path_or_default(cache_path);
         permission_cache.cache(
             running_plugin.store.data().plugin.location.to_string(),
+            Some(running_plugin.store.data().content_hash.clone()),
             permissions,
         );
 
         permission_cache.write_to_file().with_context(err_context)
     }
+    pub fn revoke_plugin_permission(
+        &mut self,
+        plugin_url: &str,
+        permission: PermissionType,
+        cache_path: Option<PathBuf>,
+    ) -> Result<()> {
+        let err_context = || format!("Failed to revoke permission for plugin {plugin_url}");
+
+        let cache_path = cache_path.or_else(|| self.plugin_permissions_cache_path());
+        let mut permission_cache = PermissionCache::from_path_or_default(cache_path);
+        if !permission_cache.revoke(plugin_url, &permission) {
+            return Ok(());
+        }
+        permission_cache.write_to_file().with_context(err_context)?;
+
+        // running instances lose the permission immediately rather than on their next load
+        for (_plugin_id, _client_id, running_plugin) in
+            self.plugin_map.lock().unwrap().running_plugins()
+        {
+            let running_plugin = running_plugin.lock().unwrap();
+            let plugin_env = running_plugin.store.data();
+            if plugin_env.plugin.location.to_string() == plugin_url {
+                if let Some(permissions) = plugin_env.permissions.lock().unwrap().as_mut() {
+                    permissions.remove(&permission);
+                }
+            }
+        }
+        Ok(())
+    }
     pub fn cache_plugin_events(&mut self, plugin_id: PluginId) {
         self.plugin_ids_waiting_for_permission_request
             .insert(plugin_id);
@@ -2207,9 +2411,10 @@ pub fn apply_event_to_plugin(
                         .with_context(err_context)?;
                     wasi_write_object(running_plugin.store.data(), &protobuf_event.encode_to_vec())
                         .with_context(err_context)?;
-                    let should_render = update
-                        .call(&mut running_plugin.store, ())
-                        .with_context(err_context)?;
+                    let should_render = call_with_cpu_budget(&mut running_plugin.store, |store| {
+                        update.call(store, ())
+                    })
+                    .with_context(err_context)?;
                     let mut should_render = should_render == 1;
                     if let Event::PermissionRequestResult(..) = event {
                         // we always render in this case, otherwise the request permission screen stays on
@@ -2219,11 +2424,12 @@ pub fn apply_event_to_plugin(
                     if rows > 0 && columns > 0 && should_render {
                         let rendered_bytes = instance
                             .get_typed_func::<(i32, i32), ()>(&mut running_plugin.store, "render")
+                            .map_err(|e| anyhow!(e))
                             .and_then(|render| {
-                                render
-                                    .call(&mut running_plugin.store, (rows as i32, columns as i32))
+                                call_with_cpu_budget(&mut running_plugin.store, |store| {
+                                    render.call(store, (rows as i32, columns as i32))
+                                })
                             })
-                            .map_err(|e| anyhow!(e))
                             .and_then(|_| {
                                 wasi_read_string(running_plugin.store.data())
                                     .map_err(|e| anyhow!(e))
@@ -2281,6 +2487,59 @@ pub fn handle_plugin_crash(plugin_id: PluginId, message: String, senders: Thread
     ));
 }
 
+pub fn save_plugin_state(
+    plugin_id: PluginId,
+    running_plugin: &mut RunningPlugin,
+) -> Result<Option<Vec<u8>>> {
+    let err_context = || format!("Failed to save state of plugin {plugin_id}");
+    let instance = &running_plugin.instance;
+    // plugins compiled against an older zellij-tile do not export this function
+    if instance
+        .get_func(&mut running_plugin.store, "save_state")
+        .is_none()
+    {
+        return Ok(None);
+    }
+    let save_state = instance
+        .get_typed_func::<(), ()>(&mut running_plugin.store, "save_state")
+        .with_context(err_context)?;
+    call_with_cpu_budget(&mut running_plugin.store, |store| {
+        save_state.call(store, ())
+    })
+    .with_context(err_context)?;
+    running_plugin.store.data_mut().state_may_have_changed = false;
+    let saved_state = wasi_read_bytes(running_plugin.store.data()).with_context(err_context)?;
+    if saved_state.is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(saved_state))
+    }
+}
+
+pub fn restore_plugin_state(
+    plugin_id: PluginId,
+    running_plugin: &mut RunningPlugin,
+    saved_state: &Vec<u8>,
+) -> Result<()> {
+    let err_context = || format!("Failed to restore state of plugin {plugin_id}");
+    let instance = &running_plugin.instance;
+    if instance
+        .get_func(&mut running_plugin.store, "restore_state")
+        .is_none()
+    {
+        return Ok(());
+    }
+    let restore_state = instance
+        .get_typed_func::<(), ()>(&mut running_plugin.store, "restore_state")
+        .with_context(err_context)?;
+    wasi_write_object(running_plugin.store.data(), saved_state).with_context(err_context)?;
+    call_with_cpu_budget(&mut running_plugin.store, |store| {
+        restore_state.call(store, ())
+    })
+    .with_context(err_context)?;
+    Ok(())
+}
+
 pub fn apply_before_close_event_to_plugin(
     plugin_id: PluginId,
     client_id: ClientId,
@@ -2300,9 +2559,9 @@ pub fn apply_before_close_event_to_plugin(
         .with_context(err_context)?;
     wasi_write_object(running_plugin.store.data(), &protobuf_event.encode_to_vec())
         .with_context(err_context)?;
-    let _should_render = update
-        .call(&mut running_plugin.store, ())
-        .with_context(err_context)?;
+    let _should_render =
+        call_with_cpu_budget(&mut running_plugin.store, |store| update.call(store, ()))
+            .with_context(err_context)?;
     let pipes_to_block_or_unblock = pipes_to_block_or_unblock(running_plugin, None);
     let plugin_render_asset =
         PluginRenderAsset::new(plugin_id, client_id, vec![]).with_pipes(pipes_to_block_or_unblock);
@@ -14,6 +14,7 @@ use std::{
     path::PathBuf,
     process,
     str::FromStr,
+    sync::atomic::{AtomicU32, Ordering},
     thread,
     time::{Duration, Instant},
 };
@@ -23,26 +24,29 @@ use zellij_utils::consts::ipc_connect;
 use zellij_utils::data::{
     BreakPanesToNewTabResponse, BreakPanesToTabWithIdResponse, BreakPanesToTabWithIndexResponse,
     CommandType, ConnectToSession, DeleteLayoutResponse, EditLayoutResponse, Event,
-    FloatingPaneCoordinates, FocusOrCreateTabResponse, GetFocusedPaneInfoResponse,
-    GetPaneCwdResponse, GetPanePidResponse, GetPaneRunningCommandResponse, HttpVerb,
-    KeyWithModifier, LayoutInfo, LayoutMetadata, LayoutParsingError, MessageToPlugin,
-    NewPanePlacement, NewTabResponse, OpenCommandPaneBackgroundResponse,
-    OpenCommandPaneFloatingNearPluginResponse, OpenCommandPaneFloatingResponse,
-    OpenCommandPaneInPlaceOfPaneIdResponse, OpenCommandPaneInPlaceOfPluginResponse,
-    OpenCommandPaneInPlaceResponse, OpenCommandPaneNearPluginResponse, OpenCommandPaneResponse,
+    FloatingPaneCoordinates, FocusOrCreateTabResponse, GetClosedPaneScrollbackResponse,
+    GetFocusedPaneInfoResponse, GetPaneCwdResponse, GetPanePidResponse,
+    GetPaneRunningCommandResponse, HttpVerb, KeyWithModifier, LayoutInfo, LayoutMetadata,
+    LayoutParsingError, ListClosedPanesResponse, MessageToPlugin, NewPanePlacement, NewTabResponse,
+    OpenCommandPaneBackgroundResponse, OpenCommandPaneFloatingNearPluginResponse,
+    OpenCommandPaneFloatingResponse, OpenCommandPaneInPlaceOfPaneIdResponse,
+    OpenCommandPaneInPlaceOfPluginResponse, OpenCommandPaneInPlaceResponse,
+    OpenCommandPaneNearPluginResponse, OpenCommandPaneResponse,
     OpenEditPaneInPlaceOfPaneIdResponse, OpenFileFloatingNearPluginResponse,
     OpenFileFloatingResponse, OpenFileInPlaceOfPluginResponse, OpenFileInPlaceResponse,
     OpenFileNearPluginResponse, OpenFileResponse, OpenPaneInNewTabResponse,
     OpenPluginPaneFloatingResponse, OpenTerminalFloatingNearPluginResponse,
     OpenTerminalFloatingResponse, OpenTerminalInPlaceOfPluginResponse, OpenTerminalInPlaceResponse,
     OpenTerminalNearPluginResponse, OpenTerminalPaneInPlaceOfPaneIdResponse, OpenTerminalResponse,
-    OriginatingPlugin, PaneScrollbackResponse, PermissionStatus, PermissionType, PluginPermission,
-    RegexHighlight, RenameLayoutResponse, SaveLayoutResponse, TabMetadata,
+    OriginatingPlugin, PaneInputLock, PaneScrollbackResponse, PermissionStatus, PermissionType,
+    PluginPermission, RegexHighlight, RenameLayoutResponse, SaveLayoutResponse,
+    SearchAllPanesResponse, TabMetadata,
 };
 use zellij_utils::home::default_layout_dir;
 use zellij_utils::input::permission::PermissionCache;
 use zellij_utils::ipc::{ClientToServerMsg, IpcSenderWithContext};
 use zellij_utils::sessions::generate_random_name as generate_random_name_impl;
+use zellij_utils::sessions::{get_session_snapshots, resurrectable_session_layout};
 #[cfg(feature = "web_server_capability")]
 use zellij_utils::web_authentication_tokens::{
     create_token, list_tokens, rename_token, revoke_all_tokens, revoke_token,
@@ -50,12 +54,18 @@ use zellij_utils::web_authentication_tokens::{
 #[cfg(feature = "web_server_capability")]
 use zellij_utils::web_server_commands::shutdown_all_webserver_instances;
 
-use crate::{panes::PaneId, screen::ScreenInstruction};
+use crate::{
+    panes::PaneId,
+    screen::{search_panes, ScreenInstruction},
+};
 use kdl::KdlDocument;
 
 use prost::Message;
 use zellij_utils::{
-    consts::{VERSION, ZELLIJ_SESSION_INFO_CACHE_DIR, ZELLIJ_SOCK_DIR, ZELLIJ_TMP_DIR},
+    consts::{
+        session_snapshots_folder_for_session, VERSION, ZELLIJ_SESSION_INFO_CACHE_DIR,
+        ZELLIJ_SOCK_DIR, ZELLIJ_TMP_DIR,
+    },
     data::{
         CommandOrPlugin, CommandToRun, Direction, EventType, FileToOpen, InputMode, PluginCommand,
         PluginIds, PluginMessage, Resize, ResizeStrategy,
@@ -65,7 +75,7 @@ use zellij_utils::{
         actions::Action,
         command::{OpenFilePayload, RunCommand, RunCommandAction, TerminalAction},
         config::ConfigError,
-        layout::{Layout, RunPluginOrAlias, TabLayoutInfo},
+        layout::{Layout, RunPluginOrAlias, SplitDirection, TabLayoutInfo},
     },
     plugin_api::{
         event::{
@@ -79,11 +89,13 @@ use zellij_utils::{
             ProtobufBreakPanesToTabWithIndexResponse, ProtobufDeleteLayoutResponse,
             ProtobufDumpLayoutResponse, ProtobufDumpSessionLayoutResponse,
             ProtobufEditLayoutResponse, ProtobufFocusOrCreateTabResponse,
-            ProtobufGenerateRandomNameResponse, ProtobufGetFocusedPaneInfoResponse,
-            ProtobufGetLayoutDirResponse, ProtobufGetPaneCwdResponse, ProtobufGetPaneInfoResponse,
-            ProtobufGetPanePidResponse, ProtobufGetPaneRunningCommandResponse,
-            ProtobufGetSessionEnvironmentVariablesResponse, ProtobufGetTabInfoResponse,
-            ProtobufHideFloatingPanesResponse, ProtobufNewTabResponse, ProtobufNewTabsResponse,
+            ProtobufGenerateRandomNameResponse, ProtobufGetClosedPaneScrollbackResponse,
+            ProtobufGetFocusedPaneInfoResponse, ProtobufGetLayoutDirResponse,
+            ProtobufGetPaneCwdResponse, ProtobufGetPaneInfoResponse, ProtobufGetPanePidResponse,
+            ProtobufGetPaneRunningCommandResponse, ProtobufGetSessionEnvironmentVariablesResponse,
+            ProtobufGetTabInfoResponse, ProtobufHideFloatingPanesResponse,
+            ProtobufListClosedPanesResponse, ProtobufListPluginPermissionsResponse,
+            ProtobufListSessionSnapshotsResponse, ProtobufNewTabResponse, ProtobufNewTabsResponse,
             ProtobufOpenCommandPaneBackgroundResponse,
             ProtobufOpenCommandPaneFloatingNearPluginResponse,
             ProtobufOpenCommandPaneFloatingResponse,
@@ -99,9 +111,11 @@ use zellij_utils::{
             ProtobufOpenTerminalInPlaceOfPluginResponse, ProtobufOpenTerminalInPlaceResponse,
             ProtobufOpenTerminalNearPluginResponse,
             ProtobufOpenTerminalPaneInPlaceOfPaneIdResponse, ProtobufOpenTerminalResponse,
-            ProtobufParseLayoutResponse, ProtobufPluginCommand, ProtobufRenameLayoutResponse,
+            ProtobufParseLayoutResponse, ProtobufPermissionType, ProtobufPluginCommand,
+            ProtobufPluginPermissionGrant, ProtobufRenameLayoutResponse,
             ProtobufSaveLayoutResponse, ProtobufSaveSessionResponse,
-            ProtobufShowFloatingPanesResponse,
+            ProtobufSearchAllPanesResponse, ProtobufShowFloatingPanesResponse,
+            ProtobufSpawnProcessResponse,
         },
         plugin_ids::{ProtobufPluginIds, ProtobufZellijVersion},
     },
@@ -249,6 +263,23 @@ fn host_run_plugin_command(mut caller: Caller<'_, PluginEnv>) {
                     PluginCommand::WebRequest(url, verb, headers, body, context) => {
                         web_request(env, url, verb, headers, body, context)
                     },
+                    PluginCommand::SpawnProcess(command_line, env_variables, cwd, context) => {
+                        spawn_process(env, command_line, env_variables, cwd, context)
+                    },
+                    PluginCommand::WriteToProcessStdin(process_id, bytes) => {
+                        write_to_process_stdin(env, process_id, bytes)
+                    },
+                    PluginCommand::CloseProcessStdin(process_id) => {
+                        close_process_stdin(env, process_id)
+                    },
+                    PluginCommand::KillProcess(process_id) => kill_process(env, process_id),
+                    PluginCommand::SignalProcess(process_id, signal) => {
+                        signal_process(env, process_id, signal)
+                    },
+                    PluginCommand::ListPluginPermissions => list_plugin_permissions(env),
+                    PluginCommand::RevokePluginPermission(plugin_url, permission) => {
+                        revoke_plugin_permission(env, plugin_url, permission)
+                    },
                     PluginCommand::PostMessageTo(plugin_message) => {
                         post_message_to(env, plugin_message)?
                     },
@@ -265,8 +296,8 @@ fn host_run_plugin_command(mut caller: Caller<'_, PluginEnv>) {
                     PluginCommand::NewTabsWithLayout(raw_layout) => {
                         new_tabs_with_layout(env, &raw_layout)?
                     },
-                    PluginCommand::NewTabsWithLayoutInfo(layout_info) => {
-                        new_tabs_with_layout_info(env, layout_info)?
+                    PluginCommand::NewTabsWithLayoutInfo(layout_info, layout_vars) => {
+                        new_tabs_with_layout_info(env, layout_info, layout_vars)?
                     },
                     PluginCommand::OverrideLayout(
                         layout_info,
@@ -274,6 +305,7 @@ fn host_run_plugin_command(mut caller: Caller<'_, PluginEnv>) {
                         retain_existing_plugin_panes,
                         apply_only_to_active_tab,
                         context,
+                        layout_vars,
                     ) => override_layout(
                         env,
                         layout_info,
@@ -281,6 +313,7 @@ fn host_run_plugin_command(mut caller: Caller<'_, PluginEnv>) {
                         retain_existing_plugin_panes,
                         apply_only_to_active_tab,
                         context,
+                        layout_vars,
                     )?,
                     PluginCommand::SaveLayout {
                         layout_name,
@@ -441,6 +474,12 @@ fn host_run_plugin_command(mut caller: Caller<'_, PluginEnv>) {
                     PluginCommand::DumpSessionLayout { tab_index } => {
                         dump_session_layout(env, tab_index)
                     },
+                    PluginCommand::ListSessionSnapshots(session_name) => {
+                        list_session_snapshots(env, session_name)
+                    },
+                    PluginCommand::GetResurrectableSessionLayout(session_name) => {
+                        get_resurrectable_session_layout(env, session_name)
+                    },
                     PluginCommand::CloseSelf => close_self(env),
                     PluginCommand::Reconfigure(new_config, write_config_to_disk) => {
                         reconfigure(env, new_config, write_config_to_disk)?
@@ -474,6 +513,19 @@ fn host_run_plugin_command(mut caller: Caller<'_, PluginEnv>) {
                         pane_id,
                         get_full_scrollback,
                     } => get_pane_scrollback(env, pane_id.into(), get_full_scrollback),
+                    PluginCommand::SearchAllPanes {
+                        query,
+                        case_sensitive,
+                    } => search_all_panes(env, query, case_sensitive),
+                    PluginCommand::RevealSearchMatch {
+                        pane_id,
+                        query,
+                        case_sensitive,
+                        line_index,
+                    } => {
+                        reveal_search_match(env, pane_id.into(), query, case_sensitive, line_index)
+                    },
+                    PluginCommand::ToggleGroupBroadcast => toggle_group_broadcast(env),
                     PluginCommand::WriteToPaneId(bytes, pane_id) => {
                         write_to_pane_id(env, bytes, pane_id.into())
                     },
@@ -598,6 +650,14 @@ fn host_run_plugin_command(mut caller: Caller<'_, PluginEnv>) {
                     PluginCommand::SetPaneBorderless(pane_id, borderless) => {
                         set_pane_borderless(env, pane_id.into(), borderless)
                     },
+                    PluginCommand::SetPaneInputLock(pane_id, input_lock) => {
+                        set_pane_input_lock(env, pane_id.into(), input_lock)
+                    },
+                    PluginCommand::ListClosedPanes => list_closed_panes(env),
+                    PluginCommand::ReopenClosedPane(id) => reopen_closed_pane(env, id),
+                    PluginCommand::GetClosedPaneScrollback(id) => {
+                        get_closed_pane_scrollback(env, id)
+                    },
                     PluginCommand::SetPaneColor(pane_id, fg, bg) => {
                         set_pane_color(env, pane_id.into(), fg, bg)
                     },
@@ -761,6 +821,10 @@ fn host_run_plugin_command(mut caller: Caller<'_, PluginEnv>) {
                     PluginCommand::ClearPaneHighlights(pane_id) => {
                         clear_pane_highlights(env, pane_id)
                     },
+                    PluginCommand::EqualizePanes(direction) => equalize_panes(env, direction),
+                    PluginCommand::SendPaneToSession(pane_id, session_name) => {
+                        send_pane_to_session(env, pane_id, session_name)
+                    },
                 },
                 (PermissionStatus::Denied, permission) => {
                     log::error!(
@@ -882,10 +946,18 @@ fn show_cursor(env: &PluginEnv, cursor_position: Option<(usize, usize)>) {
         .non_fatal();
 }
 
+fn plugin_permissions_cache_path(env: &PluginEnv) -> Option<PathBuf> {
+    env.os_input
+        .as_ref()
+        .and_then(|os_input| os_input.plugin_permissions_cache_path())
+}
+
 fn request_permission(env: &PluginEnv, permissions: Vec<PermissionType>) -> Result<()> {
-    if PermissionCache::from_path_or_default(None)
-        .check_permissions(env.plugin.location.to_string(), &permissions)
-    {
+    if PermissionCache::from_path_or_default(plugin_permissions_cache_path(env)).check_permissions(
+        env.plugin.location.to_string(),
+        Some(&env.content_hash),
+        &permissions,
+    ) {
         return env
             .senders
             .send_to_plugin(PluginInstruction::PermissionRequestResult(
@@ -912,6 +984,35 @@ fn request_permission(env: &PluginEnv, permissions: Vec<PermissionType>) -> Resu
         ))
 }
 
+fn list_plugin_permissions(env: &PluginEnv) {
+    let permission_cache =
+        PermissionCache::from_path_or_default(plugin_permissions_cache_path(env));
+    let mut grants: Vec<ProtobufPluginPermissionGrant> = permission_cache
+        .granted()
+        .iter()
+        .map(|(plugin_url, grant)| ProtobufPluginPermissionGrant {
+            plugin_url: plugin_url.clone(),
+            permissions: grant
+                .permissions
+                .iter()
+                .filter_map(|p| ProtobufPermissionType::try_from(*p).ok())
+                .map(|p| p as i32)
+                .collect(),
+        })
+        .collect();
+    grants.sort_by(|a, b| a.plugin_url.cmp(&b.plugin_url));
+    let response = ProtobufListPluginPermissionsResponse { grants };
+    let _ = wasi_write_object(env, &response.encode_to_vec());
+}
+
+fn revoke_plugin_permission(env: &PluginEnv, plugin_url: String, permission: PermissionType) {
+    let _ = env
+        .senders
+        .send_to_plugin(PluginInstruction::RevokePluginPermission(
+            plugin_url, permission, None,
+        ));
+}
+
 fn get_plugin_ids(env: &PluginEnv) {
     let ids = PluginIds {
         plugin_id: env.plugin_id,
@@ -1316,6 +1417,7 @@ fn parse_layout(env: &PluginEnv, layout_string: String) {
                 tabs,
                 creation_time: current_time.clone(),
                 update_time: current_time,
+                params: Layout::params_from_kdl(&layout_string).unwrap_or_default(),
             };
 
             // Convert LayoutMetadata to protobuf
@@ -2463,13 +2565,20 @@ fn set_timeout(env: &PluginEnv, secs: f64) {
     let update_target = Some(env.plugin_id);
     let client_id = env.client_id;
     let plugin_name = env.name();
+    let duration = Duration::from_secs_f64(secs);
+    let timer = match env.os_input.as_ref() {
+        Some(os_input) => os_input.sleep(duration),
+        None => Box::pin(async move {
+            let start_time = Instant::now();
+            tokio::time::sleep(duration).await;
+            start_time.elapsed()
+        }),
+    };
     // Use tokio runtime for async I/O (timer operation)
     get_tokio_runtime().spawn(async move {
-        let start_time = Instant::now();
-        tokio::time::sleep(Duration::from_secs_f64(secs)).await;
         // FIXME: The way that elapsed time is being calculated here is not exact; it doesn't take into account the
         // time it takes an event to actually reach the plugin after it's sent to the `wasm` thread.
-        let elapsed_time = Instant::now().duration_since(start_time).as_secs_f64();
+        let elapsed_time = timer.await.as_secs_f64();
 
         send_plugin_instructions
             .ok_or(anyhow!("found no sender to send plugin instruction to"))
@@ -2543,6 +2652,72 @@ fn run_command(
     }
 }
 
+fn spawn_process(
+    env: &PluginEnv,
+    mut command_line: Vec<String>,
+    env_variables: BTreeMap<String, String>,
+    cwd: PathBuf,
+    context: BTreeMap<String, String>,
+) {
+    if command_line.is_empty() {
+        log::error!("Command cannot be empty");
+        return;
+    }
+    // process ids are unique across plugins so that background jobs can track them in one place
+    static NEXT_PROCESS_ID: AtomicU32 = AtomicU32::new(0);
+    let process_id = NEXT_PROCESS_ID.fetch_add(1, Ordering::SeqCst);
+    let command = command_line.remove(0);
+    let cwd = translate_plugin_path(env, cwd);
+    let _ = env
+        .senders
+        .send_to_background_jobs(BackgroundJob::SpawnProcess(
+            env.plugin_id,
+            env.client_id,
+            process_id,
+            command,
+            command_line,
+            env_variables,
+            cwd,
+            context,
+        ));
+    let response = ProtobufSpawnProcessResponse { process_id };
+    wasi_write_object(env, &response.encode_to_vec())
+        .with_context(|| format!("failed to send process id to plugin {}", env.name()))
+        .non_fatal();
+}
+
+fn write_to_process_stdin(env: &PluginEnv, process_id: u32, bytes: Vec<u8>) {
+    let _ = env
+        .senders
+        .send_to_background_jobs(BackgroundJob::WriteToProcessStdin(
+            env.plugin_id,
+            process_id,
+            bytes,
+        ));
+}
+
+fn close_process_stdin(env: &PluginEnv, process_id: u32) {
+    let _ = env
+        .senders
+        .send_to_background_jobs(BackgroundJob::CloseProcessStdin(env.plugin_id, process_id));
+}
+
+fn kill_process(env: &PluginEnv, process_id: u32) {
+    let _ = env
+        .senders
+        .send_to_background_jobs(BackgroundJob::KillProcess(env.plugin_id, process_id));
+}
+
+fn signal_process(env: &PluginEnv, process_id: u32, signal: String) {
+    let _ = env
+        .senders
+        .send_to_background_jobs(BackgroundJob::SignalProcess(
+            env.plugin_id,
+            process_id,
+            signal,
+        ));
+}
+
 fn web_request(
     env: &PluginEnv,
     url: String,
@@ -2711,9 +2886,13 @@ fn new_tabs_with_layout(env: &PluginEnv, raw_layout: &str) -> Result<()> {
     Ok(())
 }
 
-fn new_tabs_with_layout_info(env: &PluginEnv, layout_info: LayoutInfo) -> Result<()> {
+fn new_tabs_with_layout_info(
+    env: &PluginEnv,
+    layout_info: LayoutInfo,
+    layout_vars: BTreeMap<String, String>,
+) -> Result<()> {
     // TODO: cwd
-    let layout = Layout::from_layout_info(&env.layout_dir, layout_info)
+    let layout = Layout::from_layout_info(&env.layout_dir, layout_info, &layout_vars)
         .map_err(|e| anyhow!("Failed to parse layout: {:?}", e))?;
     apply_layout(env, layout);
     Ok(())
@@ -3013,6 +3192,18 @@ fn move_pane_with_direction(env: &PluginEnv, direction: Direction) {
     apply_action!(action, error_msg, env);
 }
 
+fn equalize_panes(env: &PluginEnv, direction: Option<SplitDirection>) {
+    let error_msg = || format!("failed to equalize panes in plugin {}", env.name());
+    let action = Action::EqualizePanes { direction };
+    apply_action!(action, error_msg, env);
+}
+
+fn toggle_group_broadcast(env: &PluginEnv) {
+    let error_msg = || format!("failed to toggle group broadcast in plugin {}", env.name());
+    let action = Action::ToggleGroupBroadcast;
+    apply_action!(action, error_msg, env);
+}
+
 fn clear_screen(env: &PluginEnv) {
     let error_msg = || format!("failed to clear screen in plugin {}", env.name());
     let action = Action::ClearScreen;
@@ -3265,6 +3456,19 @@ fn rename_session(env: &PluginEnv, new_session_name: String) {
     }
 }
 
+fn send_pane_to_session(
+    env: &PluginEnv,
+    pane_id: zellij_utils::data::PaneId,
+    session_name: String,
+) {
+    let error_msg = || format!("failed to send pane to session in plugin {}", env.name());
+    let action = Action::SendPaneToSession {
+        name: session_name,
+        pane_id: Some(pane_id),
+    };
+    apply_action!(action, error_msg, env);
+}
+
 fn disconnect_other_clients(env: &PluginEnv) {
     let _ = env
         .senders
@@ -3386,6 +3590,33 @@ fn dump_session_layout(env: &PluginEnv, tab_index: Option<usize>) {
         .with_context(|| format!("failed to send session layout to plugin {}", env.name()));
 }
 
+fn get_resurrectable_session_layout(env: &PluginEnv, session_name: String) {
+    let result = match resurrectable_session_layout(&session_name) {
+        Ok(layout_content) => dump_session_layout_response::Result::LayoutContent(layout_content),
+        Err(e) => dump_session_layout_response::Result::Error(e),
+    };
+    let protobuf_response = ProtobufDumpSessionLayoutResponse {
+        result: Some(result),
+        metadata: None,
+    };
+    let _ = wasi_write_object(env, &protobuf_response.encode_to_vec()).with_context(|| {
+        format!(
+            "failed to send resurrectable session layout to plugin {}",
+            env.name()
+        )
+    });
+}
+
+fn list_session_snapshots(env: &PluginEnv, session_name: String) {
+    let response = ProtobufListSessionSnapshotsResponse {
+        snapshots: get_session_snapshots(&session_snapshots_folder_for_session(&session_name))
+            .into_iter()
+            .map(|snapshot| snapshot.into())
+            .collect(),
+    };
+    let _ = wasi_write_object(env, &response.encode_to_vec());
+}
+
 fn save_session(env: &PluginEnv) {
     use save_session_response::Result as SaveSessionResult;
     let (completion_tx, completion_rx) = oneshot::channel();
@@ -3722,12 +3953,134 @@ fn set_pane_borderless(env: &PluginEnv, pane_id: PaneId, borderless: bool) {
         ));
 }
 
+fn set_pane_input_lock(env: &PluginEnv, pane_id: PaneId, input_lock: Option<PaneInputLock>) {
+    let _ = env
+        .senders
+        .send_to_screen(ScreenInstruction::SetPaneInputLock(
+            pane_id, input_lock, None,
+        ));
+}
+
+fn list_closed_panes(env: &PluginEnv) {
+    use crossbeam::channel::RecvTimeoutError;
+    use std::time::Duration;
+
+    let err_context = || format!("failed to list closed panes for plugin {}", env.name());
+
+    let (response_sender, response_receiver) = crossbeam::channel::bounded(1);
+    env.senders
+        .send_to_pty(PtyInstruction::ListClosedPanes {
+            response_channel: response_sender,
+        })
+        .with_context(err_context)
+        .non_fatal();
+
+    let response = match response_receiver.recv_timeout(Duration::from_secs(5)) {
+        Ok(response) => response,
+        Err(RecvTimeoutError::Timeout) => {
+            log::error!(
+                "ListClosedPanes timed out after 5s for plugin {}",
+                env.plugin_id
+            );
+            ListClosedPanesResponse::Err("Timeout listing closed panes".to_owned())
+        },
+        Err(RecvTimeoutError::Disconnected) => {
+            log::error!(
+                "ListClosedPanes channel disconnected for plugin {}",
+                env.plugin_id
+            );
+            ListClosedPanesResponse::Err(
+                "Channel disconnected while listing closed panes".to_owned(),
+            )
+        },
+    };
+
+    let serialized = ProtobufListClosedPanesResponse::from(response);
+    wasi_write_object(env, &serialized.encode_to_vec())
+        .with_context(err_context)
+        .non_fatal();
+}
+
+fn get_closed_pane_scrollback(env: &PluginEnv, id: u32) {
+    use crossbeam::channel::RecvTimeoutError;
+    use std::time::Duration;
+
+    let err_context = || {
+        format!(
+            "failed to get the scrollback of closed pane {} for plugin {}",
+            id,
+            env.name()
+        )
+    };
+
+    let (response_sender, response_receiver) = crossbeam::channel::bounded(1);
+    env.senders
+        .send_to_pty(PtyInstruction::GetClosedPaneScrollback {
+            id,
+            response_channel: response_sender,
+        })
+        .with_context(err_context)
+        .non_fatal();
+
+    let response = match response_receiver.recv_timeout(Duration::from_secs(5)) {
+        Ok(response) => response,
+        Err(RecvTimeoutError::Timeout) => {
+            log::error!(
+                "GetClosedPaneScrollback timed out after 5s for plugin {}",
+                env.plugin_id
+            );
+            GetClosedPaneScrollbackResponse::Err(
+                "Timeout getting closed pane scrollback".to_owned(),
+            )
+        },
+        Err(RecvTimeoutError::Disconnected) => {
+            log::error!(
+                "GetClosedPaneScrollback channel disconnected for plugin {}",
+                env.plugin_id
+            );
+            GetClosedPaneScrollbackResponse::Err(
+                "Channel disconnected while getting closed pane scrollback".to_owned(),
+            )
+        },
+    };
+
+    let serialized = ProtobufGetClosedPaneScrollbackResponse::from(response);
+    wasi_write_object(env, &serialized.encode_to_vec())
+        .with_context(err_context)
+        .non_fatal();
+}
+
+fn reopen_closed_pane(env: &PluginEnv, id: u32) {
+    let _ = env.senders.send_to_pty(PtyInstruction::ReopenClosedPane {
+        id: Some(id),
+        client_id: env.client_id,
+        completion_tx: None,
+    });
+}
+
 fn set_pane_color(env: &PluginEnv, pane_id: PaneId, fg: Option<String>, bg: Option<String>) {
     let _ = env
         .senders
         .send_to_screen(ScreenInstruction::SetPaneColor(pane_id, fg, bg, None));
 }
 
+// plugins subscribed to HostFolderScanned can tell the results of their scans apart from changes
+// reported by the filesystem watcher, the others get them as a FileSystemUpdate
+fn scan_result_event(
+    env: &PluginEnv,
+) -> fn(Vec<(PathBuf, Option<zellij_utils::data::FileMetadata>)>) -> Event {
+    let subscribed = env
+        .subscriptions
+        .lock()
+        .map(|subscriptions| subscriptions.contains(&EventType::HostFolderScanned))
+        .unwrap_or(false);
+    if subscribed {
+        Event::HostFolderScanned
+    } else {
+        Event::FileSystemUpdate
+    }
+}
+
 fn scan_host_folder(env: &PluginEnv, folder_to_scan: PathBuf) {
     if !folder_to_scan.starts_with("/host") {
         log::error!(
@@ -3757,6 +4110,7 @@ fn scan_host_folder(env: &PluginEnv, folder_to_scan: PathBuf) {
                     let send_plugin_instructions = env.senders.to_plugin.clone();
                     let update_target = Some(env.plugin_id);
                     let client_id = env.client_id;
+                    let scan_result_event = scan_result_event(env);
                     thread::spawn({
                         move || {
                             let mut paths_in_folder = vec![];
@@ -3777,7 +4131,7 @@ fn scan_host_folder(env: &PluginEnv, folder_to_scan: PathBuf) {
                                     let _ = sender.send(PluginInstruction::Update(vec![(
                                         update_target,
                                         Some(client_id),
-                                        Event::FileSystemUpdate(paths_in_folder),
+                                        scan_result_event(paths_in_folder),
                                     )]));
                                 })
                                 .non_fatal();
@@ -3808,6 +4162,7 @@ fn list_windows_volumes(env: &PluginEnv) {
     let send_plugin_instructions = env.senders.to_plugin.clone();
     let update_target = Some(env.plugin_id);
     let client_id = env.client_id;
+    let scan_result_event = scan_result_event(env);
     thread::spawn(move || {
         let mut entries = enumerate_drives();
         entries.extend(enumerate_wsl_distributions());
@@ -3817,7 +4172,7 @@ fn list_windows_volumes(env: &PluginEnv) {
                 let _ = sender.send(PluginInstruction::Update(vec![(
                     update_target,
                     Some(client_id),
-                    Event::FileSystemUpdate(entries),
+                    scan_result_event(entries),
                 )]));
             })
             .non_fatal();
@@ -3958,6 +4313,72 @@ fn get_pane_scrollback(env: &PluginEnv, pane_id: PaneId, get_full_scrollback: bo
         .non_fatal();
 }
 
+fn search_all_panes(env: &PluginEnv, query: String, case_sensitive: bool) {
+    use crossbeam::channel::RecvTimeoutError;
+    use std::time::Duration;
+
+    let err_context = || format!("failed to search all panes from plugin {}", env.name());
+
+    let response = if query.is_empty() {
+        SearchAllPanesResponse::Err("Search query is empty".to_owned())
+    } else {
+        let (response_sender, response_receiver) = crossbeam::channel::bounded(1);
+        env.senders
+            .send_to_screen(ScreenInstruction::ListSearchablePanes {
+                response_channel: response_sender,
+            })
+            .with_context(err_context)
+            .non_fatal();
+        // the screen thread only hands over the text of the panes, the search itself runs here
+        match response_receiver.recv_timeout(Duration::from_secs(5)) {
+            Ok(searchable_panes) => {
+                SearchAllPanesResponse::Ok(search_panes(searchable_panes, &query, case_sensitive))
+            },
+            Err(RecvTimeoutError::Timeout) => {
+                log::error!(
+                    "SearchAllPanes timed out after 5s for plugin {}",
+                    env.plugin_id
+                );
+                SearchAllPanesResponse::Err("Timeout searching panes".to_owned())
+            },
+            Err(RecvTimeoutError::Disconnected) => {
+                log::error!(
+                    "SearchAllPanes channel disconnected for plugin {}",
+                    env.plugin_id
+                );
+                SearchAllPanesResponse::Err("Channel disconnected while searching panes".to_owned())
+            },
+        }
+    };
+
+    ProtobufSearchAllPanesResponse::try_from(response)
+        .map_err(|e| anyhow!("Failed to serialize search results: {}", e))
+        .and_then(|serialized| {
+            wasi_write_object(env, &serialized.encode_to_vec())?;
+            Ok(())
+        })
+        .with_context(err_context)
+        .non_fatal();
+}
+
+fn reveal_search_match(
+    env: &PluginEnv,
+    pane_id: PaneId,
+    query: String,
+    case_sensitive: bool,
+    line_index: usize,
+) {
+    let _ = env
+        .senders
+        .send_to_screen(ScreenInstruction::RevealSearchMatch {
+            pane_id,
+            query,
+            case_sensitive,
+            line_index,
+            client_id: env.client_id,
+        });
+}
+
 fn write_to_pane_id(env: &PluginEnv, bytes: Vec<u8>, pane_id: PaneId) {
     let (completion_tx, completion_rx) = oneshot::channel();
     let send_result = env.senders.send_to_screen(ScreenInstruction::WriteToPaneId(
@@ -5055,8 +5476,9 @@ fn override_layout(
     retain_existing_plugin_panes: bool,
     apply_only_to_active_tab: bool,
     context: BTreeMap<String, String>,
+    layout_vars: BTreeMap<String, String>,
 ) -> Result<()> {
-    let layout = Layout::from_layout_info(&env.layout_dir, layout_info)
+    let layout = Layout::from_layout_info(&env.layout_dir, layout_info, &layout_vars)
         .map_err(|e| anyhow!("Failed to parse layout: {:?}", e))?;
 
     // Convert all tabs to Vec<TabLayoutInfo>
@@ -5187,7 +5609,12 @@ fn check_command_permission(
         | PluginCommand::OpenCommandPaneBackground(..)
         | PluginCommand::OpenCommandPaneInPlaceOfPaneId(..)
         | PluginCommand::RunCommand(..)
-        | PluginCommand::ExecCmd(..) => PermissionType::RunCommands,
+        | PluginCommand::ExecCmd(..)
+        | PluginCommand::SpawnProcess(..)
+        | PluginCommand::WriteToProcessStdin(..)
+        | PluginCommand::CloseProcessStdin(..)
+        | PluginCommand::KillProcess(..)
+        | PluginCommand::SignalProcess(..) => PermissionType::RunCommands,
         PluginCommand::WebRequest(..) => PermissionType::WebAccess,
         PluginCommand::Write(..)
         | PluginCommand::WriteChars(..)
@@ -5209,6 +5636,8 @@ fn check_command_permission(
         | PluginCommand::Detach
         | PluginCommand::EditScrollback
         | PluginCommand::EditScrollbackForPaneWithId(..)
+        | PluginCommand::RevealSearchMatch { .. }
+        | PluginCommand::ToggleGroupBroadcast
         | PluginCommand::ToggleTab
         | PluginCommand::MovePane
         | PluginCommand::MovePaneWithDirection(..)
@@ -5275,6 +5704,8 @@ fn check_command_permission(
         | PluginCommand::ChangeFloatingPanesCoordinates(..)
         | PluginCommand::TogglePaneBorderless(..)
         | PluginCommand::SetPaneBorderless(..)
+        | PluginCommand::SetPaneInputLock(..)
+        | PluginCommand::ReopenClosedPane(..)
         | PluginCommand::SetPaneColor(..)
         | PluginCommand::GroupAndUngroupPanes(..)
         | PluginCommand::HighlightAndUnhighlightPanes(..)
@@ -5293,13 +5724,22 @@ fn check_command_permission(
         | PluginCommand::ShowFloatingPanes { .. }
         | PluginCommand::HideFloatingPanes { .. }
         | PluginCommand::SetPaneRegexHighlights(..)
-        | PluginCommand::ClearPaneHighlights(..) => PermissionType::ChangeApplicationState,
+        | PluginCommand::ClearPaneHighlights(..)
+        | PluginCommand::SendPaneToSession(..)
+        | PluginCommand::EqualizePanes(..) => PermissionType::ChangeApplicationState,
+        PluginCommand::RevokePluginPermission(..) => {
+            // only the built-in plugin manager may change the permissions of other plugins,
+            // there's no permission a plugin can ask for in order to do so
+            return (PermissionStatus::Denied, None);
+        },
         PluginCommand::UnblockCliPipeInput(..)
         | PluginCommand::BlockCliPipeInput(..)
         | PluginCommand::CliPipeOutput(..) => PermissionType::ReadCliPipes,
         PluginCommand::MessageToPlugin(..) => PermissionType::MessageAndLaunchOtherPlugins,
         PluginCommand::ListClients
         | PluginCommand::DumpSessionLayout { .. }
+        | PluginCommand::GetResurrectableSessionLayout(..)
+        | PluginCommand::ListSessionSnapshots(..)
         | PluginCommand::GetPanePid { .. }
         | PluginCommand::GetPaneRunningCommand { .. }
         | PluginCommand::GetPaneCwd { .. }
@@ -5311,7 +5751,8 @@ fn check_command_permission(
         | PluginCommand::SaveSession
         | PluginCommand::CurrentSessionLastSavedTime
         | PluginCommand::GetPaneInfo(..)
-        | PluginCommand::GetTabInfo(..) => PermissionType::ReadApplicationState,
+        | PluginCommand::GetTabInfo(..)
+        | PluginCommand::ListPluginPermissions => PermissionType::ReadApplicationState,
         PluginCommand::RebindKeys { .. } | PluginCommand::Reconfigure(..) => {
             PermissionType::Reconfigure
         },
@@ -5331,7 +5772,10 @@ fn check_command_permission(
         PluginCommand::InterceptKeyPresses | PluginCommand::ClearKeyPressesIntercepts => {
             PermissionType::InterceptInput
         },
-        PluginCommand::GetPaneScrollback { .. } => PermissionType::ReadPaneContents,
+        PluginCommand::GetPaneScrollback { .. }
+        | PluginCommand::SearchAllPanes { .. }
+        | PluginCommand::ListClosedPanes
+        | PluginCommand::GetClosedPaneScrollback(..) => PermissionType::ReadPaneContents,
         PluginCommand::RunAction(..) => PermissionType::RunActionsAsUser,
         PluginCommand::GetSessionEnvironmentVariables => {
             PermissionType::ReadSessionEnvironmentVariables
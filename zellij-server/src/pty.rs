@@ -1,5 +1,6 @@
-use crate::background_jobs::write_session_state_to_disk;
 use crate::background_jobs::BackgroundJob;
+use crate::background_jobs::{write_session_state_to_disk, write_snapshot_to_disk};
+use crate::closed_panes::{ClosedPane, ClosedPanes};
 use crate::global_async_runtime::get_tokio_runtime as async_runtime;
 use crate::os_input_output::{AsyncReader, NullAsyncReader};
 use crate::route::NotificationEnd;
@@ -16,19 +17,23 @@ use std::sync::Arc;
 use std::{collections::HashMap, path::PathBuf};
 use tokio::task::JoinHandle;
 use zellij_utils::{
+    consts::session_snapshots_folder_for_session,
     data::{
-        CommandOrPlugin, Event, FloatingPaneCoordinates, GetPaneCwdResponse, GetPanePidResponse,
-        GetPaneRunningCommandResponse, NewPanePlacement, OriginatingPlugin, SessionInfo,
+        CommandOrPlugin, Event, FloatingPaneCoordinates, GetClosedPaneScrollbackResponse,
+        GetPaneCwdResponse, GetPanePidResponse, GetPaneRunningCommandResponse,
+        ListClosedPanesResponse, NewPanePlacement, OriginatingPlugin, SessionInfo,
     },
     errors::prelude::*,
     errors::{ContextType, PtyContext},
     input::{
         command::{OpenFilePayload, RunCommand, TerminalAction},
+        hooks::{HookContext, HookEvent},
         layout::{
             FloatingPaneLayout, Layout, Run, RunPluginOrAlias, SwapFloatingLayout, SwapTiledLayout,
             TabLayoutInfo, TiledPaneLayout,
         },
     },
+    ipc::ServerToClientMsg,
     pane_size::Size,
     session_serialization,
 };
@@ -56,6 +61,12 @@ pub enum PtyInstruction {
         bool,                    // set_blocking
     ), // bool (if Some) is
     // should_float, String is an optional pane name
+    SpawnScratchpad {
+        name: String,
+        run_command: RunCommand,
+        coordinates: Option<FloatingPaneCoordinates>,
+        client_id: ClientId,
+    },
     OpenInPlaceEditor(
         PathBuf,
         Option<usize>,
@@ -115,6 +126,12 @@ pub enum PtyInstruction {
         session_layout_metadata: SessionLayoutMetadata,
         completion_tx: Option<NotificationEnd>,
     },
+    SaveSnapshotToDisk {
+        session_name: String,
+        snapshot_name: String,
+        session_layout_metadata: SessionLayoutMetadata,
+        completion_tx: Option<NotificationEnd>,
+    },
     FillPluginCwd(
         Option<bool>,   // should float
         bool,           // should be opened in place
@@ -154,6 +171,38 @@ pub enum PtyInstruction {
         response_channel: crossbeam::channel::Sender<GetPaneCwdResponse>,
     },
     UpdateAndReportCwds,
+    SendTerminalToSession {
+        terminal_id: u32,
+        session_name: String,
+        pane_title: Option<String>,
+        scrollback: String,
+        client_id: ClientId,
+        completion_tx: Option<NotificationEnd>,
+    },
+    TerminalSentToSession {
+        terminal_id: u32,
+        sent: bool, // false if the transfer failed and the terminal stays in this session
+    },
+    AdoptTerminal {
+        transfer_socket: PathBuf,
+        pane_title: Option<String>,
+        scrollback: String,
+        child_pid: Option<u32>,
+        client_id: Option<ClientId>, // None if no client is attached, the pane is then refused
+    },
+    AddClosedPane(u32, ClosedPane), // u32 - the terminal id the pane had while it was open
+    ListClosedPanes {
+        response_channel: crossbeam::channel::Sender<ListClosedPanesResponse>,
+    },
+    GetClosedPaneScrollback {
+        id: u32,
+        response_channel: crossbeam::channel::Sender<GetClosedPaneScrollbackResponse>,
+    },
+    ReopenClosedPane {
+        id: Option<u32>, // None - the most recently closed pane
+        client_id: ClientId,
+        completion_tx: Option<NotificationEnd>,
+    },
     Exit,
 }
 
@@ -161,6 +210,7 @@ impl From<&PtyInstruction> for PtyContext {
     fn from(pty_instruction: &PtyInstruction) -> Self {
         match *pty_instruction {
             PtyInstruction::SpawnTerminal(..) => PtyContext::SpawnTerminal,
+            PtyInstruction::SpawnScratchpad { .. } => PtyContext::SpawnScratchpad,
             PtyInstruction::OpenInPlaceEditor(..) => PtyContext::OpenInPlaceEditor,
             PtyInstruction::UpdateActivePane(..) => PtyContext::UpdateActivePane,
             PtyInstruction::GoToTab(..) => PtyContext::GoToTab,
@@ -175,6 +225,7 @@ impl From<&PtyInstruction> for PtyContext {
             PtyInstruction::DumpLayoutToPlugin { .. } => PtyContext::DumpLayoutToPlugin,
             PtyInstruction::LogLayoutToHd(..) => PtyContext::LogLayoutToHd,
             PtyInstruction::SaveSessionToDisk { .. } => PtyContext::SaveSessionToDisk,
+            PtyInstruction::SaveSnapshotToDisk { .. } => PtyContext::SaveSnapshotToDisk,
             PtyInstruction::FillPluginCwd(..) => PtyContext::FillPluginCwd,
             PtyInstruction::ListClientsMetadata(..) => PtyContext::ListClientsMetadata,
             PtyInstruction::Reconfigure { .. } => PtyContext::Reconfigure,
@@ -186,6 +237,13 @@ impl From<&PtyInstruction> for PtyContext {
             PtyInstruction::GetPaneRunningCommand { .. } => PtyContext::GetPaneRunningCommand,
             PtyInstruction::GetPaneCwd { .. } => PtyContext::GetPaneCwd,
             PtyInstruction::UpdateAndReportCwds => PtyContext::UpdateAndReportCwds,
+            PtyInstruction::SendTerminalToSession { .. } => PtyContext::SendTerminalToSession,
+            PtyInstruction::TerminalSentToSession { .. } => PtyContext::TerminalSentToSession,
+            PtyInstruction::AdoptTerminal { .. } => PtyContext::AdoptTerminal,
+            PtyInstruction::AddClosedPane(..) => PtyContext::AddClosedPane,
+            PtyInstruction::ListClosedPanes { .. } => PtyContext::ListClosedPanes,
+            PtyInstruction::GetClosedPaneScrollback { .. } => PtyContext::GetClosedPaneScrollback,
+            PtyInstruction::ReopenClosedPane { .. } => PtyContext::ReopenClosedPane,
             PtyInstruction::Exit => PtyContext::Exit,
         }
     }
@@ -202,6 +260,10 @@ pub(crate) struct Pty {
     post_command_discovery_hook: Option<String>,
     plugin_cwds: HashMap<u32, PathBuf>,   // plugin_id -> cwd
     terminal_cwds: HashMap<u32, PathBuf>, // terminal_id -> cwd
+    closed_panes: ClosedPanes,
+    // terminals being handed over to another session, the action that sent them ends once the
+    // transfer is done
+    terminals_being_sent: HashMap<u32, Option<NotificationEnd>>,
 }
 
 pub(crate) fn pty_thread_main(mut pty: Pty, layout: Box<Layout>) -> Result<()> {
@@ -344,6 +406,84 @@ pub(crate) fn pty_thread_main(mut pty: Pty, layout: Box<Layout>) -> Result<()> {
                     },
                 }
             },
+            PtyInstruction::SpawnScratchpad {
+                name,
+                run_command,
+                coordinates,
+                client_id,
+            } => {
+                let err_context = || format!("failed to spawn scratchpad {name}");
+                let pane_title = Some(name.clone());
+                let invoked_with = Some(Run::Command(run_command.clone()));
+                let client_or_tab_index = ClientTabIndexOrPaneId::ClientId(client_id);
+                let (pid, hold_for_command, command_not_found) = match pty.spawn_terminal(
+                    Some(TerminalAction::RunCommand(run_command.clone())),
+                    client_or_tab_index,
+                ) {
+                    Ok((pid, starts_held)) => {
+                        (pid, starts_held.then(|| run_command.clone()), false)
+                    },
+                    Err(err) => match err.downcast_ref::<ZellijError>() {
+                        Some(ZellijError::CommandNotFound { terminal_id, .. })
+                            if run_command.hold_on_close =>
+                        {
+                            // we do not hold an "error" pane
+                            (*terminal_id, None, true)
+                        },
+                        Some(ZellijError::CommandNotFound { terminal_id, .. }) => {
+                            log::error!("Failed to spawn scratchpad: {:?}", err);
+                            pty.bus
+                                .senders
+                                .send_to_screen(ScreenInstruction::ScratchpadOpened(
+                                    name.clone(),
+                                    None,
+                                ))
+                                .with_context(err_context)?;
+                            pty.close_pane(PaneId::Terminal(*terminal_id))
+                                .with_context(err_context)?;
+                            continue;
+                        },
+                        _ => {
+                            pty.bus
+                                .senders
+                                .send_to_screen(ScreenInstruction::ScratchpadOpened(
+                                    name.clone(),
+                                    None,
+                                ))
+                                .with_context(err_context)?;
+                            Err::<(), _>(err).with_context(err_context).non_fatal();
+                            continue;
+                        },
+                    },
+                };
+                let start_suppressed = false;
+                let set_blocking = false;
+                pty.bus
+                    .senders
+                    .send_to_screen(ScreenInstruction::NewPane(
+                        PaneId::Terminal(pid),
+                        pane_title,
+                        hold_for_command,
+                        invoked_with,
+                        NewPanePlacement::Floating(coordinates),
+                        start_suppressed,
+                        client_or_tab_index,
+                        None,
+                        set_blocking,
+                    ))
+                    .with_context(err_context)?;
+                pty.bus
+                    .senders
+                    .send_to_screen(ScreenInstruction::ScratchpadOpened(
+                        name.clone(),
+                        Some(PaneId::Terminal(pid)),
+                    ))
+                    .with_context(err_context)?;
+                if command_not_found {
+                    send_command_not_found_to_screen(pty.bus.senders.clone(), pid, run_command)
+                        .with_context(err_context)?;
+                }
+            },
             PtyInstruction::SpawnInPlaceTerminal(
                 terminal_action,
                 name,
@@ -607,6 +747,12 @@ pub(crate) fn pty_thread_main(mut pty: Pty, layout: Box<Layout>) -> Result<()> {
                                         .to_vec(),
                                     ))
                                     .with_context(err_context)?;
+                                run_command_pane_exited_hooks(
+                                    &pty.bus.senders,
+                                    *terminal_id,
+                                    Some(2),
+                                    &run_command,
+                                );
                                 pty.bus
                                     .senders
                                     .send_to_screen(ScreenInstruction::HoldPane(
@@ -659,6 +805,12 @@ pub(crate) fn pty_thread_main(mut pty: Pty, layout: Box<Layout>) -> Result<()> {
                                         .to_vec(),
                                     ))
                                     .with_context(err_context)?;
+                                run_command_pane_exited_hooks(
+                                    &pty.bus.senders,
+                                    *terminal_id,
+                                    Some(2),
+                                    &run_command,
+                                );
                                 pty.bus
                                     .senders
                                     .send_to_screen(ScreenInstruction::HoldPane(
@@ -816,6 +968,31 @@ pub(crate) fn pty_thread_main(mut pty: Pty, layout: Box<Layout>) -> Result<()> {
                     },
                 };
             },
+            PtyInstruction::SaveSnapshotToDisk {
+                session_name,
+                snapshot_name,
+                mut session_layout_metadata,
+                completion_tx,
+            } => {
+                pty.populate_session_layout_metadata(&mut session_layout_metadata);
+                let saved =
+                    session_serialization::serialize_session_layout(session_layout_metadata.into())
+                        .map_err(|e| format!("Failed to serialize layout: {}", e))
+                        .and_then(|kdl_and_files| {
+                            write_snapshot_to_disk(
+                                &session_snapshots_folder_for_session(&session_name),
+                                &snapshot_name,
+                                kdl_and_files,
+                            )
+                        });
+                if let Err(e) = saved {
+                    log::error!("{}", e);
+                    if let Some(mut completion_tx) = completion_tx {
+                        completion_tx.set_exit_status(1);
+                        completion_tx.set_error_message(e);
+                    }
+                }
+            },
             PtyInstruction::FillPluginCwd(
                 should_float,
                 should_be_open_in_place,
@@ -886,6 +1063,95 @@ pub(crate) fn pty_thread_main(mut pty: Pty, layout: Box<Layout>) -> Result<()> {
             PtyInstruction::UpdateAndReportCwds => {
                 pty.update_and_report_cwds();
             },
+            PtyInstruction::SendTerminalToSession {
+                terminal_id,
+                session_name,
+                pane_title,
+                scrollback,
+                client_id,
+                completion_tx,
+            } => {
+                if let Err(e) = pty.send_terminal_to_session(
+                    terminal_id,
+                    &session_name,
+                    pane_title,
+                    scrollback,
+                    client_id,
+                    completion_tx,
+                ) {
+                    let error_text =
+                        format!("Failed to send pane to session {}: {}", session_name, e);
+                    log::error!("{}", error_text);
+                    if let Some(os_input) = pty.bus.os_input.as_ref() {
+                        let _ = os_input.send_to_client(
+                            client_id,
+                            ServerToClientMsg::LogError {
+                                lines: vec![error_text],
+                            },
+                        );
+                    }
+                }
+            },
+            PtyInstruction::TerminalSentToSession { terminal_id, sent } => {
+                pty.terminal_sent_to_session(terminal_id, sent)
+                    .with_context(|| "failed to close pane sent to another session")
+                    .non_fatal();
+            },
+            PtyInstruction::AdoptTerminal {
+                transfer_socket,
+                pane_title,
+                scrollback,
+                child_pid,
+                client_id,
+            } => {
+                pty.adopt_terminal(
+                    transfer_socket,
+                    pane_title,
+                    scrollback,
+                    child_pid,
+                    client_id,
+                )
+                .with_context(|| "failed to adopt terminal from another session")
+                .non_fatal();
+            },
+            PtyInstruction::AddClosedPane(terminal_id, closed_pane) => {
+                pty.add_closed_pane(terminal_id, closed_pane);
+            },
+            PtyInstruction::ListClosedPanes { response_channel } => {
+                let _ = response_channel.send(ListClosedPanesResponse::Ok(pty.closed_panes.list()));
+            },
+            PtyInstruction::GetClosedPaneScrollback {
+                id,
+                response_channel,
+            } => {
+                let response = match pty.closed_panes.scrollback_lines(id) {
+                    Some(lines) => GetClosedPaneScrollbackResponse::Ok(lines),
+                    None => GetClosedPaneScrollbackResponse::Err(format!(
+                        "No closed pane with id {}",
+                        id
+                    )),
+                };
+                let _ = response_channel.send(response);
+            },
+            PtyInstruction::ReopenClosedPane {
+                id,
+                client_id,
+                completion_tx: _completion_tx, // the action ends here, dropping this will release
+                                               // anything waiting for it
+            } => {
+                if let Err(e) = pty.reopen_closed_pane(id, client_id) {
+                    let error_text = format!("Failed to reopen closed pane: {}", e);
+                    log::error!("{}", error_text);
+                    if let Some(os_input) = pty.bus.os_input.as_ref() {
+                        let _ = os_input.send_to_client(
+                            client_id,
+                            ServerToClientMsg::LogError {
+                                lines: vec![error_text],
+                            },
+                        );
+                    }
+                }
+            },
             PtyInstruction::Exit => break,
         }
     }
@@ -910,6 +1176,8 @@ impl Pty {
             post_command_discovery_hook,
             plugin_cwds: HashMap::new(),
             terminal_cwds: HashMap::new(),
+            closed_panes: ClosedPanes::default(),
+            terminals_being_sent: HashMap::new(),
         }
     }
     pub fn get_default_terminal(
@@ -1026,6 +1294,10 @@ impl Pty {
                 terminal_action
             },
         };
+        let pane_command = match &terminal_action {
+            TerminalAction::RunCommand(run_command) => Some(run_command.clone()),
+            TerminalAction::OpenFile(..) => None,
+        };
         let (hold_on_start, hold_on_close, originating_command_plugin, originating_edit_plugin) =
             match &terminal_action {
                 TerminalAction::RunCommand(run_command) => (
@@ -1052,11 +1324,13 @@ impl Pty {
                 .context("couldn't get mutable reference to OS interface")
                 .and_then(|os_input| os_input.reserve_terminal_id())
                 .with_context(err_context)?;
+            self.run_pane_opened_hooks(terminal_id, pane_command.as_ref());
             return Ok((terminal_id, starts_held));
         }
 
         let originating_command_plugin = Arc::new(originating_command_plugin.clone());
         let originating_edit_plugin = Arc::new(originating_edit_plugin.clone());
+        let is_command_pane = pane_command.is_some();
         let quit_cb = Box::new({
             let senders = self.bus.senders.clone();
             move |pane_id, exit_status, command| {
@@ -1087,6 +1361,9 @@ impl Pty {
                             update_event,
                         )]));
                     }
+                    if is_command_pane {
+                        run_command_pane_exited_hooks(&senders, pane_id, exit_status, &command);
+                    }
                 }
 
                 if hold_on_close {
@@ -1133,6 +1410,7 @@ impl Pty {
             self.id_to_child_pid.insert(terminal_id, child_pid);
             self.capture_initial_cwd(terminal_id, child_pid);
         }
+        self.run_pane_opened_hooks(terminal_id, pane_command.as_ref());
 
         let starts_held = false;
         Ok((terminal_id, starts_held))
@@ -1569,7 +1847,7 @@ impl Pty {
             }
         });
 
-        match run_instruction {
+        let new_pane = match run_instruction {
             Some(Run::Command(mut command)) => {
                 let starts_held = command.hold_on_start;
                 let hold_on_close = command.hold_on_close;
@@ -1589,6 +1867,12 @@ impl Pty {
                                     update_event,
                                 )]));
                             }
+                            run_command_pane_exited_hooks(
+                                &senders,
+                                terminal_pane_id,
+                                exit_status,
+                                &command,
+                            );
                         }
 
                         if hold_on_close {
@@ -1755,7 +2039,30 @@ impl Pty {
             },
             // Investigate moving plugin loading to here.
             Some(Run::Plugin(_)) => Ok(None),
+        };
+        if let Ok(Some((terminal_id, _starts_held, run_command, _))) = &new_pane {
+            self.run_pane_opened_hooks(*terminal_id, run_command.as_ref());
         }
+        new_pane
+    }
+    fn run_pane_opened_hooks(&self, terminal_id: u32, run_command: Option<&RunCommand>) {
+        let cwd = self
+            .terminal_cwds
+            .get(&terminal_id)
+            .cloned()
+            .or_else(|| run_command.and_then(|r| r.cwd.clone()));
+        let _ = self
+            .bus
+            .senders
+            .send_to_background_jobs(BackgroundJob::RunHooks(
+                HookEvent::PaneOpened,
+                HookContext {
+                    pane_id: Some(terminal_id),
+                    command: run_command.map(|r| r.to_string()),
+                    cwd,
+                    ..Default::default()
+                },
+            ));
     }
     pub fn close_pane(&mut self, id: PaneId) -> Result<()> {
         let err_context = || format!("failed to close for pane {id:?}");
@@ -1835,6 +2142,7 @@ impl Pty {
                                     update_event,
                                 )]));
                             }
+                            run_command_pane_exited_hooks(&senders, pane_id, exit_status, &command);
                         }
                         if hold_on_close {
                             let _ = senders.send_to_screen(ScreenInstruction::HoldPane(
@@ -2055,6 +2363,17 @@ impl Pty {
                             None,
                             Event::CwdChanged(pane_id.into(), cwd.clone(), focused_client_ids),
                         )]));
+                    let _ = self
+                        .bus
+                        .senders
+                        .send_to_background_jobs(BackgroundJob::RunHooks(
+                            HookEvent::CwdChanged,
+                            HookContext {
+                                pane_id: Some(terminal_id),
+                                cwd: Some(cwd.clone()),
+                                ..Default::default()
+                            },
+                        ));
                 }
                 self.terminal_cwds.insert(terminal_id, cwd.clone());
             }
@@ -2070,6 +2389,221 @@ impl Pty {
         self.post_command_discovery_hook = post_command_discovery_hook;
     }
 
+    pub fn send_terminal_to_session(
+        &mut self,
+        terminal_id: u32,
+        session_name: &str,
+        pane_title: Option<String>,
+        scrollback: String,
+        client_id: ClientId,
+        completion_tx: Option<NotificationEnd>,
+    ) -> Result<()> {
+        if self.terminals_being_sent.contains_key(&terminal_id) {
+            return Err(anyhow!("the pane is already being sent to another session"));
+        }
+        let child_pid = self.id_to_child_pid.get(&terminal_id).copied();
+        // the handover waits for the other session, so it happens in the background and we hear
+        // back once it is done
+        self.bus
+            .senders
+            .send_to_background_jobs(BackgroundJob::SendTerminalToSession {
+                terminal_id,
+                session_name: session_name.to_owned(),
+                pane_title,
+                scrollback,
+                child_pid,
+                client_id,
+            })?;
+        self.terminals_being_sent.insert(terminal_id, completion_tx);
+        Ok(())
+    }
+
+    pub fn terminal_sent_to_session(&mut self, terminal_id: u32, sent: bool) -> Result<()> {
+        // dropping the completion_tx ends the action that sent the terminal
+        let _completion_tx = self.terminals_being_sent.remove(&terminal_id);
+        if !sent {
+            return Ok(());
+        }
+
+        // the other session now owns the process, so we only stop reading from it and close the
+        // pane without killing anything
+        if let Some(handle) = self.task_handles.remove(&terminal_id) {
+            handle.abort();
+        }
+        self.id_to_child_pid.remove(&terminal_id);
+        self.originating_plugins.remove(&terminal_id);
+        self.terminal_cwds.remove(&terminal_id);
+        self.bus
+            .os_input
+            .as_ref()
+            .context("no OS I/O interface found")
+            .and_then(|os_input| os_input.clear_terminal_id(terminal_id))?;
+        self.bus
+            .senders
+            .send_to_screen(ScreenInstruction::ClosePane(
+                PaneId::Terminal(terminal_id),
+                None,
+                None,
+                None,
+            ))
+            .context("failed to close pane sent to another session")?;
+        Ok(())
+    }
+
+    pub fn adopt_terminal(
+        &mut self,
+        transfer_socket: PathBuf,
+        pane_title: Option<String>,
+        scrollback: String,
+        child_pid: Option<u32>,
+        client_id: Option<ClientId>,
+    ) -> Result<()> {
+        let err_context = || format!("failed to adopt terminal from {:?}", transfer_socket);
+        let os_input = self
+            .bus
+            .os_input
+            .as_ref()
+            .context("no OS I/O interface found")
+            .with_context(err_context)?;
+        let Some(client_id) = client_id else {
+            // with nobody attached there is no tab to place the pane in, so it stays in the
+            // session that sent it
+            os_input
+                .refuse_terminal(&transfer_socket)
+                .with_context(err_context)?;
+            return Err(anyhow!("no client is attached to show the pane to"))
+                .with_context(err_context);
+        };
+        let (terminal_id, reader) = os_input
+            .adopt_terminal(&transfer_socket)
+            .with_context(err_context)?;
+
+        self.bus
+            .senders
+            .send_to_screen(ScreenInstruction::NewPane(
+                PaneId::Terminal(terminal_id),
+                pane_title,
+                None,
+                None,
+                NewPanePlacement::default(),
+                false,
+                ClientTabIndexOrPaneId::ClientId(client_id),
+                None,
+                false,
+            ))
+            .with_context(err_context)?;
+        if !scrollback.is_empty() {
+            self.bus
+                .senders
+                .send_to_screen(ScreenInstruction::PtyBytes(
+                    terminal_id,
+                    scrollback.into_bytes(),
+                ))
+                .with_context(err_context)?;
+        }
+
+        let terminal_bytes = async_runtime().spawn({
+            let senders = self.bus.senders.clone();
+            let debug_to_file = self.debug_to_file;
+            async move {
+                TerminalBytes::new(terminal_id, reader, senders.clone(), debug_to_file)
+                    .listen()
+                    .await
+                    .with_context(|| format!("failed to run async task for terminal {terminal_id}"))
+                    .fatal();
+                // the process is not our child, so there is no quit callback to let us know it
+                // exited - the pty closing is our only indication
+                let _ = senders.send_to_screen(ScreenInstruction::ClosePane(
+                    PaneId::Terminal(terminal_id),
+                    None,
+                    None,
+                    None,
+                ));
+            }
+        });
+        self.task_handles.insert(terminal_id, terminal_bytes);
+        if let Some(child_pid) = child_pid {
+            self.id_to_child_pid.insert(terminal_id, child_pid);
+            self.capture_initial_cwd(terminal_id, child_pid);
+        }
+        Ok(())
+    }
+
+    pub fn add_closed_pane(&mut self, terminal_id: u32, mut closed_pane: ClosedPane) {
+        // the pane is closed here only after this, so its process might still be running
+        closed_pane.cwd = self
+            .id_to_child_pid
+            .get(&terminal_id)
+            .and_then(|&pid| {
+                self.bus
+                    .os_input
+                    .as_ref()
+                    .and_then(|input| input.get_cwd(pid))
+            })
+            .or_else(|| self.terminal_cwds.get(&terminal_id).cloned())
+            .or_else(|| closed_pane.run_command.as_ref().and_then(|r| r.cwd.clone()));
+        self.closed_panes.add(closed_pane);
+    }
+
+    /// Opens a held pane showing the final scrollback of a closed pane, its command (or shell) is
+    /// only run again once the user asks for it
+    pub fn reopen_closed_pane(&mut self, id: Option<u32>, client_id: ClientId) -> Result<()> {
+        let err_context = || format!("failed to reopen closed pane {:?}", id);
+
+        let closed_pane = match self.closed_panes.take(id) {
+            Some(closed_pane) => closed_pane,
+            None => match id {
+                Some(id) => return Err(anyhow!("Could not find closed pane {}.", id)),
+                None => return Err(anyhow!("There are no closed panes to reopen.")),
+            },
+        };
+        let run_command = match closed_pane.run_command.clone() {
+            Some(run_command) => RunCommand {
+                hold_on_start: false,
+                cwd: run_command.cwd.or_else(|| closed_pane.cwd.clone()),
+                ..run_command
+            },
+            None => RunCommand {
+                command: get_default_shell(),
+                hold_on_close: false,
+                hold_on_start: false,
+                cwd: closed_pane.cwd.clone(),
+                ..Default::default()
+            },
+        };
+        let terminal_id = self
+            .bus
+            .os_input
+            .as_mut()
+            .context("couldn't get mutable reference to OS interface")
+            .and_then(|os_input| os_input.reserve_terminal_id())
+            .with_context(err_context)?;
+        self.bus
+            .senders
+            .send_to_screen(ScreenInstruction::NewPane(
+                PaneId::Terminal(terminal_id),
+                Some(closed_pane.title.clone()),
+                None,
+                None,
+                NewPanePlacement::default(),
+                false,
+                ClientTabIndexOrPaneId::ClientId(client_id),
+                None,
+                false,
+            ))
+            .with_context(err_context)?;
+        self.bus
+            .senders
+            .send_to_screen(ScreenInstruction::RestoreClosedPane {
+                terminal_id,
+                scrollback: closed_pane.serialized_scrollback,
+                exit_status: closed_pane.exit_status,
+                run_command,
+            })
+            .with_context(err_context)?;
+        Ok(())
+    }
+
     pub fn send_sigint_to_pane(&self, pane_id: PaneId) {
         let err_context = || format!("failed to send SIGINT to pane {:?}", pane_id);
 
@@ -2231,6 +2765,7 @@ fn send_command_not_found_to_screen(
                 .to_vec(),
         ))
         .with_context(err_context)?;
+    run_command_pane_exited_hooks(&senders, terminal_id, Some(2), &run_command);
     senders
         .send_to_screen(ScreenInstruction::HoldPane(
             PaneId::Terminal(terminal_id),
@@ -2241,6 +2776,25 @@ fn send_command_not_found_to_screen(
     Ok(())
 }
 
+// fired from here rather than from the screen so that it runs whether the pane is held or closed
+fn run_command_pane_exited_hooks(
+    senders: &ThreadSenders,
+    terminal_id: u32,
+    exit_status: Option<i32>,
+    run_command: &RunCommand,
+) {
+    let _ = senders.send_to_background_jobs(BackgroundJob::RunHooks(
+        HookEvent::CommandPaneExited,
+        HookContext {
+            pane_id: Some(terminal_id),
+            command: Some(run_command.to_string()),
+            exit_code: exit_status,
+            cwd: run_command.cwd.clone(),
+            ..Default::default()
+        },
+    ));
+}
+
 #[cfg(not(windows))]
 pub fn get_default_shell() -> PathBuf {
     PathBuf::from(std::env::var("SHELL").unwrap_or_else(|_| {
@@ -10,6 +10,7 @@ use crate::{
     plugins::PluginInstruction,
     pty::{ClientTabIndexOrPaneId, PtyInstruction},
     screen::ScreenInstruction,
+    tab::MouseRegionMap,
     ServerInstruction, SessionMetaData, SessionState,
 };
 use std::thread;
@@ -17,6 +18,7 @@ use std::time::Duration;
 use uuid::Uuid;
 use zellij_utils::{
     channels::SenderWithContext,
+    consts::session_snapshots_folder_for_session,
     data::{
         BareKey, ConnectToSession, Direction, Event, InputMode, KeyModifier, ListPanesResponse,
         ListTabsResponse, NewPanePlacement, PaneListEntry, PluginCapabilities, ResizeStrategy,
@@ -29,11 +31,16 @@ use zellij_utils::{
         command::TerminalAction,
         get_mode_info,
         keybinds::Keybinds,
-        layout::Layout,
+        layout::{Layout, TabLayoutInfo},
+        mouse::{MouseEvent, MouseEventType},
+        mousebinds::{MouseButton, MouseClickCounter, Mousebinds},
     },
     ipc::{
         ClientAttributes, ClientToServerMsg, ExitReason, IpcReceiverWithContext, ServerToClientMsg,
     },
+    sessions::{
+        delete_session_snapshot, format_session_snapshots, get_session_snapshots, snapshot_layout,
+    },
 };
 
 use crate::ClientId;
@@ -450,6 +457,24 @@ pub(crate) fn route_action(
                 ))
                 .with_context(err_context)?;
         },
+        Action::EqualizePanes { direction } => {
+            senders
+                .send_to_screen(ScreenInstruction::EqualizePanes(
+                    client_id,
+                    direction,
+                    Some(NotificationEnd::new(completion_tx)),
+                ))
+                .with_context(err_context)?;
+        },
+        Action::SetTilingStrategy { strategy } => {
+            senders
+                .send_to_screen(ScreenInstruction::SetTilingStrategy(
+                    client_id,
+                    strategy,
+                    Some(NotificationEnd::new(completion_tx)),
+                ))
+                .with_context(err_context)?;
+        },
         Action::ClearScreen => {
             senders
                 .send_to_screen(ScreenInstruction::ClearScreen(
@@ -499,6 +524,131 @@ pub(crate) fn route_action(
                 ))
                 .with_context(err_context)?;
         },
+        Action::SaveSnapshot {
+            name,
+            include_viewport,
+        } => {
+            senders
+                .send_to_screen(ScreenInstruction::SaveSnapshot(
+                    name,
+                    include_viewport,
+                    Some(NotificationEnd::new(completion_tx)),
+                ))
+                .with_context(err_context)?;
+        },
+        Action::ListSnapshots => {
+            let session_name = envs::get_session_name().unwrap_or_default();
+            senders
+                .send_to_server(ServerInstruction::Log(
+                    format_session_snapshots(&get_session_snapshots(
+                        &session_snapshots_folder_for_session(&session_name),
+                    )),
+                    cli_client_id.unwrap_or(client_id),
+                    Some(NotificationEnd::new(completion_tx)),
+                ))
+                .with_context(err_context)?;
+        },
+        Action::DeleteSnapshot { name } => {
+            let session_name = envs::get_session_name().unwrap_or_default();
+            let mut completion = NotificationEnd::new(completion_tx);
+            if let Err(e) =
+                delete_session_snapshot(&session_snapshots_folder_for_session(&session_name), &name)
+            {
+                completion.set_exit_status(1);
+                completion.set_error_message(e);
+            }
+        },
+        Action::RestoreSnapshot { name, replace } => {
+            let session_name = envs::get_session_name().unwrap_or_default();
+            match snapshot_layout(&session_snapshots_folder_for_session(&session_name), &name) {
+                Ok(layout) if replace => {
+                    let swap_tiled_layouts = Some(layout.swap_tiled_layouts.clone());
+                    let swap_floating_layouts = Some(layout.swap_floating_layouts.clone());
+                    let mut tabs: Vec<TabLayoutInfo> = layout
+                        .tabs()
+                        .into_iter()
+                        .enumerate()
+                        .map(|(tab_index, (tab_name, tiled, floating))| TabLayoutInfo {
+                            tab_index,
+                            tab_name,
+                            tiled_layout: tiled,
+                            floating_layouts: floating,
+                            swap_tiled_layouts: swap_tiled_layouts.clone(),
+                            swap_floating_layouts: swap_floating_layouts.clone(),
+                        })
+                        .collect();
+                    if tabs.is_empty() {
+                        let (tiled, floating) = layout.new_tab();
+                        tabs.push(TabLayoutInfo {
+                            tab_index: 0,
+                            tab_name: None,
+                            tiled_layout: tiled,
+                            floating_layouts: floating,
+                            swap_tiled_layouts,
+                            swap_floating_layouts,
+                        });
+                    }
+                    let retain_existing_terminal_panes = false;
+                    let retain_existing_plugin_panes = false;
+                    let apply_only_to_active_tab = false;
+                    senders
+                        .send_to_screen(ScreenInstruction::OverrideLayout(
+                            None,
+                            default_shell.clone(),
+                            tabs,
+                            retain_existing_terminal_panes,
+                            retain_existing_plugin_panes,
+                            apply_only_to_active_tab,
+                            client_id,
+                            Some(NotificationEnd::new(completion_tx)),
+                        ))
+                        .with_context(err_context)?;
+                },
+                Ok(layout) => {
+                    let swap_layouts = (
+                        layout.swap_tiled_layouts.clone(),
+                        layout.swap_floating_layouts.clone(),
+                    );
+                    let mut tabs = layout.tabs();
+                    if tabs.is_empty() {
+                        let (tiled, floating) = layout.new_tab();
+                        tabs.push((None, tiled, floating));
+                    }
+                    let focused_tab_index = layout.focused_tab_index().unwrap_or(0);
+                    let is_web_client = false; // actions cannot be initiated directly from the web
+                    let last_tab_index = tabs.len().saturating_sub(1);
+                    let mut completion_tx = Some(completion_tx);
+                    for (tab_index, (tab_name, tiled, floating)) in tabs.into_iter().enumerate() {
+                        // the tabs are opened in order, so the action ends with the last one
+                        let tab_completion_tx = if tab_index == last_tab_index {
+                            completion_tx.take().map(NotificationEnd::new)
+                        } else {
+                            None
+                        };
+                        senders
+                            .send_to_screen(ScreenInstruction::NewTab(
+                                None,
+                                default_shell.clone(),
+                                Some(tiled),
+                                floating,
+                                tab_name,
+                                swap_layouts.clone(),
+                                None,
+                                false,
+                                tab_index == focused_tab_index,
+                                (client_id, is_web_client),
+                                tab_completion_tx,
+                            ))
+                            .with_context(err_context)?;
+                    }
+                },
+                Err(e) => {
+                    let mut completion = NotificationEnd::new(completion_tx);
+                    completion.set_exit_status(1);
+                    completion.set_error_message(e);
+                },
+            }
+        },
         Action::EditScrollback { ansi } => {
             senders
                 .send_to_screen(ScreenInstruction::EditScrollback(
@@ -972,6 +1122,15 @@ pub(crate) fn route_action(
                 ))
                 .with_context(err_context)?;
         },
+        Action::ReopenClosedPane { id } => {
+            senders
+                .send_to_pty(PtyInstruction::ReopenClosedPane {
+                    id,
+                    client_id,
+                    completion_tx: Some(NotificationEnd::new(completion_tx)),
+                })
+                .with_context(err_context)?;
+        },
         Action::NewTab {
             tiled_layout: tab_layout,
             floating_layouts: floating_panes_layout,
@@ -1549,6 +1708,16 @@ pub(crate) fn route_action(
                 ))
                 .with_context(err_context)?;
         },
+        Action::SendPaneToSession { name, pane_id } => {
+            senders
+                .send_to_screen(ScreenInstruction::SendPaneToSession {
+                    session_name: name,
+                    pane_id: pane_id.map(|p| p.into()),
+                    client_id,
+                    completion_tx: Some(NotificationEnd::new(completion_tx)),
+                })
+                .with_context(err_context)?;
+        },
         Action::CliPipe {
             pipe_id,
             mut name,
@@ -1758,6 +1927,15 @@ pub(crate) fn route_action(
                 ))
                 .with_context(err_context)?;
         },
+        Action::TogglePaneInputLock { input_lock } => {
+            senders
+                .send_to_screen(ScreenInstruction::TogglePaneInputLock(
+                    client_id,
+                    input_lock,
+                    Some(NotificationEnd::new(completion_tx)),
+                ))
+                .with_context(err_context)?;
+        },
         Action::StackPanes {
             pane_ids: pane_ids_to_stack,
         } => {
@@ -1816,6 +1994,23 @@ pub(crate) fn route_action(
                 ))
                 .with_context(err_context)?;
         },
+        Action::ToggleGroupBroadcast => {
+            senders
+                .send_to_screen(ScreenInstruction::ToggleGroupBroadcast(
+                    client_id,
+                    Some(NotificationEnd::new(completion_tx)),
+                ))
+                .with_context(err_context)?;
+        },
+        Action::ToggleScratchpad { name } => {
+            senders
+                .send_to_screen(ScreenInstruction::ToggleScratchpad(
+                    name,
+                    client_id,
+                    Some(NotificationEnd::new(completion_tx)),
+                ))
+                .with_context(err_context)?;
+        },
         Action::ShowFloatingPanes { tab_id } => {
             senders
                 .send_to_screen(ScreenInstruction::ShowFloatingPanes {
@@ -2005,6 +2200,18 @@ pub(crate) fn route_action(
                 ))
                 .with_context(err_context)?;
         },
+        Action::TogglePaneInputLockByPaneId {
+            pane_id,
+            input_lock,
+        } => {
+            senders
+                .send_to_screen(ScreenInstruction::TogglePaneInputLockWithPaneId(
+                    pane_id.into(),
+                    input_lock,
+                    Some(NotificationEnd::new(completion_tx)),
+                ))
+                .with_context(err_context)?;
+        },
         // Tab-targeting CLI-only variants
         Action::UndoRenameTabByTabId { id } => {
             senders
@@ -2137,6 +2344,47 @@ macro_rules! send_to_screen_or_retry_queue {
     }};
 }
 
+/// Matches the mouse events of a client against its mousebinds
+#[derive(Debug, Default)]
+struct MousebindResolver {
+    click_counter: MouseClickCounter,
+    // the press of a bound button is handled by its bind, so the drags and release that follow it
+    // should not reach the default mouse handling either
+    swallowing_press: bool,
+}
+
+impl MousebindResolver {
+    /// The actions bound to this mouse event in the `mousebinds` config section, or the event
+    /// itself if there are none so that it's left to the default mouse handling of the screen
+    fn actions_for_event(
+        &mut self,
+        event: MouseEvent,
+        mousebinds: &Mousebinds,
+        mouse_regions: Option<&MouseRegionMap>,
+    ) -> Vec<Action> {
+        let click_count = self.click_counter.register(&event);
+        let mouse_region = mouse_regions.and_then(|m| m.region_at(&event.position));
+        let bound_actions = mousebinds.get_actions_for_event(&event, click_count, mouse_region);
+        let is_button_press = event.event_type == MouseEventType::Press
+            && !matches!(
+                MouseButton::from_event(&event),
+                Some(MouseButton::WheelUp) | Some(MouseButton::WheelDown) | None
+            );
+        if is_button_press {
+            self.swallowing_press = bound_actions.is_some();
+        }
+        match bound_actions {
+            Some(actions) => actions.clone(),
+            None if self.swallowing_press && event.event_type == MouseEventType::Release => {
+                self.swallowing_press = false;
+                vec![]
+            },
+            None if self.swallowing_press && event.event_type == MouseEventType::Motion => vec![],
+            None => vec![Action::MouseEvent { event }],
+        }
+    }
+}
+
 pub(crate) fn route_thread_main(
     session_data: Arc<RwLock<Option<SessionMetaData>>>,
     session_state: Arc<RwLock<SessionState>>,
@@ -2148,6 +2396,7 @@ pub(crate) fn route_thread_main(
     let mut retry_queue = VecDeque::new();
     let err_context = || format!("failed to handle instruction for client {client_id}");
     let mut seen_cli_pipes = HashSet::new();
+    let mut mousebind_resolver = MousebindResolver::default();
     let mut consecutive_unknown_messages_received = 0;
     'route_loop: loop {
         match receiver.recv_client_msg() {
@@ -2366,29 +2615,61 @@ pub(crate) fn route_thread_main(
                                 client_keybinds,
                             )) = session_data_assets
                             {
-                                match route_action(
-                                    action,
-                                    client_id,
-                                    Some(cli_client_id),
-                                    maybe_pane_id.map(|p| PaneId::Terminal(p)),
-                                    senders,
-                                    capabilities,
-                                    client_attributes,
-                                    default_shell,
-                                    layout,
-                                    Some(&mut seen_cli_pipes),
-                                    client_keybinds,
-                                    client_input_mode,
-                                    Some(os_input.clone()),
-                                ) {
-                                    Ok(route_action_should_break) => {
-                                        if route_action_should_break.0 {
-                                            should_break = true;
+                                let actions = match action {
+                                    Action::MouseEvent { event } if !is_cli_client => {
+                                        let mousebinds_and_regions =
+                                            session_data.read().unwrap().as_ref().map(|s| {
+                                                (
+                                                    s.session_configuration
+                                                        .get_client_configuration(&client_id)
+                                                        .mousebinds,
+                                                    s.mouse_regions.read().ok().and_then(
+                                                        |mouse_regions| {
+                                                            mouse_regions.get(&client_id).cloned()
+                                                        },
+                                                    ),
+                                                )
+                                            });
+                                        match mousebinds_and_regions {
+                                            Some((mousebinds, mouse_regions))
+                                                if !mousebinds.is_empty() =>
+                                            {
+                                                mousebind_resolver.actions_for_event(
+                                                    event,
+                                                    &mousebinds,
+                                                    mouse_regions.as_deref(),
+                                                )
+                                            },
+                                            _ => vec![Action::MouseEvent { event }],
                                         }
                                     },
-                                    Err(e) => {
-                                        log::error!("{}", e);
-                                    },
+                                    action => vec![action],
+                                };
+                                for action in actions {
+                                    match route_action(
+                                        action,
+                                        client_id,
+                                        Some(cli_client_id),
+                                        maybe_pane_id.map(PaneId::Terminal),
+                                        senders.clone(),
+                                        capabilities,
+                                        client_attributes.clone(),
+                                        default_shell.clone(),
+                                        layout.clone(),
+                                        Some(&mut seen_cli_pipes),
+                                        client_keybinds.clone(),
+                                        client_input_mode,
+                                        Some(os_input.clone()),
+                                    ) {
+                                        Ok(route_action_should_break) => {
+                                            if route_action_should_break.0 {
+                                                should_break = true;
+                                            }
+                                        },
+                                        Err(e) => {
+                                            log::error!("{}", e);
+                                        },
+                                    }
                                 }
                             }
                         },
@@ -2601,6 +2882,37 @@ pub(crate) fn route_thread_main(
                                 retry_queue
                             );
                         },
+                        ClientToServerMsg::ClientReconnected => {
+                            let _ = send_to_screen_or_retry_queue!(
+                                senders,
+                                ScreenInstruction::ClientReconnected(client_id),
+                                instruction,
+                                retry_queue
+                            );
+                        },
+                        ClientToServerMsg::AdoptPane {
+                            ref transfer_socket,
+                            ref pane_title,
+                            ref scrollback,
+                            child_pid,
+                        } => {
+                            // the pane is placed next to whichever client was last active in
+                            // this session, the connection that sent it is not a real client
+                            let target_client_id =
+                                session_state.read().unwrap().get_last_active_client();
+                            if let Some(senders) = senders.as_ref() {
+                                senders
+                                    .send_to_pty(PtyInstruction::AdoptTerminal {
+                                        transfer_socket: transfer_socket.clone(),
+                                        pane_title: pane_title.clone(),
+                                        scrollback: scrollback.clone(),
+                                        child_pid,
+                                        client_id: target_client_id,
+                                    })
+                                    .with_context(err_context)?;
+                            }
+                            should_break = true;
+                        },
                         ClientToServerMsg::SubscribeToPaneRenders {
                             ref pane_ids,
                             ref scrollback,
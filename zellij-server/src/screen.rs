@@ -34,6 +34,7 @@ use std::net::{IpAddr, Ipv4Addr};
 use std::path::PathBuf;
 use std::rc::Rc;
 use std::str;
+use std::sync::{Arc, RwLock};
 use std::time::{Duration, Instant};
 
 use crate::route::NotificationEnd;
@@ -42,16 +43,18 @@ use log::{debug, warn};
 use zellij_utils::data::{
     CommandOrPlugin, Direction, EventType, FloatingPaneCoordinates, GetFocusedPaneInfoResponse,
     KeyWithModifier, LayoutInfo, LayoutWithError, ListPanesResponse, ListTabsResponse,
-    NewPanePlacement, PaneContents, PaneInfo, PaneListEntry, PaneManifest, PaneRenderReport,
-    PaneScrollbackResponse, PluginPermission, RegexHighlight, Resize, ResizeStrategy, SessionInfo,
-    Styling, TabInfo, WebSharing,
+    NewPanePlacement, PaneContents, PaneInfo, PaneInputLock, PaneListEntry, PaneManifest,
+    PaneRenderReport, PaneScrollbackResponse, PaneSearchResult, PluginPermission, RegexHighlight,
+    Resize, ResizeStrategy, SearchMatch, SessionInfo, Styling, TabInfo, WebSharing,
 };
 use zellij_utils::errors::prelude::*;
 use zellij_utils::input::command::RunCommand;
 use zellij_utils::input::config::Config;
+use zellij_utils::input::hooks::{HookContext, HookEvent};
 use zellij_utils::input::keybinds::Keybinds;
 use zellij_utils::input::mouse::MouseEvent;
 use zellij_utils::input::options::Clipboard;
+use zellij_utils::input::scratchpads::Scratchpads;
 use zellij_utils::ipc::{ExitReason, ServerToClientMsg};
 use zellij_utils::pane_size::{PaneGeom, Size, SizeInPixels};
 use zellij_utils::shared::clean_string_from_control_and_linebreak;
@@ -60,8 +63,8 @@ use zellij_utils::{
     envs::set_session_name,
     input::command::TerminalAction,
     input::layout::{
-        FloatingPaneLayout, Layout, PercentOrFixed, Run, RunPluginOrAlias, SwapFloatingLayout,
-        SwapTiledLayout, TabLayoutInfo, TiledPaneLayout,
+        FloatingPaneLayout, Layout, PercentOrFixed, Run, RunPluginOrAlias, SplitDirection,
+        SwapFloatingLayout, SwapTiledLayout, TabLayoutInfo, TiledPaneLayout, TilingStrategy,
     },
     position::Position,
 };
@@ -80,7 +83,7 @@ use crate::{
     panes::PaneId,
     plugins::{DumpSessionLayoutResponse, PluginId, PluginInstruction, PluginRenderAsset},
     pty::{get_default_shell, ClientTabIndexOrPaneId, PtyInstruction, VteBytes},
-    tab::{SuppressedPanes, Tab},
+    tab::{MouseRegionMap, SuppressedPanes, Tab},
     thread_bus::Bus,
     ui::loading_indication::LoadingIndication,
     ClientId, ServerInstruction,
@@ -92,6 +95,68 @@ use zellij_utils::{
     ipc::{ClientAttributes, PixelDimensions},
 };
 
+/// Caps on the matches returned by a session-wide search, only the first match in each line is
+/// reported
+const MAX_SEARCH_MATCHES_PER_PANE: usize = 100;
+const MAX_SEARCH_MATCHES: usize = 1000;
+
+/// The text of a terminal pane, for searching all panes of the session
+pub struct SearchablePane {
+    tab_position: usize,
+    tab_name: String,
+    pane_id: PaneId,
+    pane_title: String,
+    lines: Vec<String>,
+}
+
+/// Searches the panes in order, matches are capped so that a common query can't flood the plugin
+pub(crate) fn search_panes(
+    panes: Vec<SearchablePane>,
+    query: &str,
+    case_sensitive: bool,
+) -> Vec<PaneSearchResult> {
+    let needle = if case_sensitive {
+        query.to_owned()
+    } else {
+        query.to_lowercase()
+    };
+    let mut results = vec![];
+    let mut total_matches = 0;
+    for pane in panes {
+        let mut matches = vec![];
+        for (line_index, line) in pane.lines.iter().enumerate() {
+            if matches.len() >= MAX_SEARCH_MATCHES_PER_PANE
+                || total_matches + matches.len() >= MAX_SEARCH_MATCHES
+            {
+                break;
+            }
+            let haystack = if case_sensitive {
+                line.to_owned()
+            } else {
+                line.to_lowercase()
+            };
+            if let Some(byte_index) = haystack.find(&needle) {
+                matches.push(SearchMatch {
+                    line_index,
+                    column: haystack[..byte_index].chars().count(),
+                    line: line.trim_end().to_owned(),
+                });
+            }
+        }
+        if !matches.is_empty() {
+            total_matches += matches.len();
+            results.push(PaneSearchResult {
+                tab_position: pane.tab_position,
+                tab_name: pane.tab_name,
+                pane_id: pane.pane_id.into(),
+                pane_title: pane.pane_title,
+                matches,
+            });
+        }
+    }
+    results
+}
+
 /// Parses a namespaced OSC 99 response and extracts the original pane ID
 /// and un-namespaced response bytes.
 ///
@@ -370,6 +435,8 @@ pub enum ScreenInstruction {
     MoveFocusRightOrNextTab(ClientId, Option<NotificationEnd>),
     MovePane(ClientId, Option<NotificationEnd>),
     MovePaneBackwards(ClientId, Option<NotificationEnd>),
+    EqualizePanes(ClientId, Option<SplitDirection>, Option<NotificationEnd>),
+    SetTilingStrategy(ClientId, Option<TilingStrategy>, Option<NotificationEnd>),
     MovePaneUp(ClientId, Option<NotificationEnd>),
     MovePaneDown(ClientId, Option<NotificationEnd>),
     MovePaneRight(ClientId, Option<NotificationEnd>),
@@ -388,6 +455,8 @@ pub enum ScreenInstruction {
     DumpLayout(Option<PathBuf>, ClientId, Option<NotificationEnd>), // PathBuf is the default configured
     // shell
     SaveSession(ClientId, Option<NotificationEnd>),
+    SaveSnapshot(String, bool, Option<NotificationEnd>), // String is the snapshot name,
+    // bool is whether to include the pane viewports
     DumpLayoutToPlugin {
         plugin_id: PluginId,
         tab_index: Option<usize>,
@@ -412,6 +481,22 @@ pub enum ScreenInstruction {
         get_full_scrollback: bool,
         response_channel: crossbeam::channel::Sender<PaneScrollbackResponse>,
     },
+    ListSearchablePanes {
+        response_channel: crossbeam::channel::Sender<Vec<SearchablePane>>,
+    },
+    RevealSearchMatch {
+        pane_id: PaneId,
+        query: String,
+        case_sensitive: bool,
+        line_index: usize,
+        client_id: ClientId,
+    },
+    RestoreClosedPane {
+        terminal_id: u32,
+        scrollback: String,
+        exit_status: Option<i32>,
+        run_command: RunCommand,
+    },
     ScrollUp(ClientId, Option<NotificationEnd>),
     ScrollUpAt(Position, ClientId, Option<NotificationEnd>),
     ScrollDown(ClientId, Option<NotificationEnd>),
@@ -653,6 +738,12 @@ pub enum ScreenInstruction {
     ),
     SerializeLayoutForResurrection,
     RenameSession(String, ClientId, Option<NotificationEnd>), // String -> new name
+    SendPaneToSession {
+        session_name: String,
+        pane_id: Option<PaneId>,
+        client_id: ClientId,
+        completion_tx: Option<NotificationEnd>,
+    },
     ListClientsMetadata(Option<PathBuf>, ClientId, Option<NotificationEnd>), // Option<PathBuf> - default shell
     ListPanes {
         show_all: bool,
@@ -744,6 +835,9 @@ pub enum ScreenInstruction {
     ),
     TogglePaneBorderless(PaneId, Option<NotificationEnd>),
     SetPaneBorderless(PaneId, bool, Option<NotificationEnd>),
+    TogglePaneInputLock(ClientId, PaneInputLock, Option<NotificationEnd>),
+    TogglePaneInputLockWithPaneId(PaneId, PaneInputLock, Option<NotificationEnd>),
+    SetPaneInputLock(PaneId, Option<PaneInputLock>, Option<NotificationEnd>), // None => unlock
     AddHighlightPaneFrameColorOverride(Vec<PaneId>, Option<String>), // Option<String> => optional
     // message
     GroupAndUngroupPanes(Vec<PaneId>, Vec<PaneId>, bool, ClientId), // panes_to_group, panes_to_ungroup, bool -> for all clients
@@ -752,6 +846,9 @@ pub enum ScreenInstruction {
     EmbedMultiplePanes(Vec<PaneId>, ClientId),
     TogglePaneInGroup(ClientId, Option<NotificationEnd>),
     ToggleGroupMarking(ClientId, Option<NotificationEnd>),
+    ToggleGroupBroadcast(ClientId, Option<NotificationEnd>),
+    ToggleScratchpad(String, ClientId, Option<NotificationEnd>), // String -> scratchpad name
+    ScratchpadOpened(String, Option<PaneId>), // String -> scratchpad name, None if it failed
     SessionSharingStatusChange(bool),
     SetMouseSelectionSupport(PaneId, bool),
     InterceptKeyPresses(PluginId, ClientId),
@@ -783,6 +880,7 @@ pub enum ScreenInstruction {
         pane_id: zellij_utils::data::PaneId,
     },
     DesktopNotificationResponse(Vec<u8>, ClientId),
+    ClientReconnected(ClientId),
     PluginSubscribedToAnsiPaneContents(bool), // true = at least one plugin needs ANSI content
     UpdateBackgroundPluginSubscriptions(PluginId, ClientId, HashSet<EventType>),
     BroadcastModeUpdate(ModeInfo, Option<ClientId>), // ModeInfo, optional specific client_id (None = all clients)
@@ -873,6 +971,8 @@ impl From<&ScreenInstruction> for ScreenContext {
             },
             ScreenInstruction::MovePane(..) => ScreenContext::MovePane,
             ScreenInstruction::MovePaneBackwards(..) => ScreenContext::MovePaneBackwards,
+            ScreenInstruction::EqualizePanes(..) => ScreenContext::EqualizePanes,
+            ScreenInstruction::SetTilingStrategy(..) => ScreenContext::SetTilingStrategy,
             ScreenInstruction::MovePaneDown(..) => ScreenContext::MovePaneDown,
             ScreenInstruction::MovePaneUp(..) => ScreenContext::MovePaneUp,
             ScreenInstruction::MovePaneRight(..) => ScreenContext::MovePaneRight,
@@ -882,12 +982,16 @@ impl From<&ScreenInstruction> for ScreenContext {
             ScreenInstruction::DumpScreen(..) => ScreenContext::DumpScreen,
             ScreenInstruction::DumpLayout(..) => ScreenContext::DumpLayout,
             ScreenInstruction::SaveSession(..) => ScreenContext::SaveSession,
+            ScreenInstruction::SaveSnapshot(..) => ScreenContext::SaveSnapshot,
             ScreenInstruction::DumpLayoutToPlugin { .. } => ScreenContext::DumpLayoutToPlugin,
             ScreenInstruction::GetFocusedPaneInfo { .. } => ScreenContext::GetFocusedPaneInfo,
             ScreenInstruction::GetPaneInfo { .. } => ScreenContext::GetPaneInfo,
             ScreenInstruction::GetTabInfo { .. } => ScreenContext::GetTabInfo,
             ScreenInstruction::EditScrollback(..) => ScreenContext::EditScrollback,
             ScreenInstruction::GetPaneScrollback { .. } => ScreenContext::GetPaneScrollback,
+            ScreenInstruction::ListSearchablePanes { .. } => ScreenContext::ListSearchablePanes,
+            ScreenInstruction::RevealSearchMatch { .. } => ScreenContext::RevealSearchMatch,
+            ScreenInstruction::RestoreClosedPane { .. } => ScreenContext::RestoreClosedPane,
             ScreenInstruction::ScrollUp(..) => ScreenContext::ScrollUp,
             ScreenInstruction::ScrollDown(..) => ScreenContext::ScrollDown,
             ScreenInstruction::ScrollToBottom(..) => ScreenContext::ScrollToBottom,
@@ -1005,6 +1109,7 @@ impl From<&ScreenInstruction> for ScreenContext {
                 ScreenContext::SerializeLayoutForResurrection
             },
             ScreenInstruction::RenameSession(..) => ScreenContext::RenameSession,
+            ScreenInstruction::SendPaneToSession { .. } => ScreenContext::SendPaneToSession,
             ScreenInstruction::ListClientsMetadata(..) => ScreenContext::ListClientsMetadata,
             ScreenInstruction::ListPanes { .. } => ScreenContext::ListPanes,
             ScreenInstruction::ListTabs { .. } => ScreenContext::ListTabs,
@@ -1049,6 +1154,11 @@ impl From<&ScreenInstruction> for ScreenContext {
             },
             ScreenInstruction::TogglePaneBorderless(..) => ScreenContext::TogglePaneBorderless,
             ScreenInstruction::SetPaneBorderless(..) => ScreenContext::SetPaneBorderless,
+            ScreenInstruction::TogglePaneInputLock(..) => ScreenContext::TogglePaneInputLock,
+            ScreenInstruction::TogglePaneInputLockWithPaneId(..) => {
+                ScreenContext::TogglePaneInputLockWithPaneId
+            },
+            ScreenInstruction::SetPaneInputLock(..) => ScreenContext::SetPaneInputLock,
             ScreenInstruction::AddHighlightPaneFrameColorOverride(..) => {
                 ScreenContext::AddHighlightPaneFrameColorOverride
             },
@@ -1060,6 +1170,9 @@ impl From<&ScreenInstruction> for ScreenContext {
             ScreenInstruction::EmbedMultiplePanes(..) => ScreenContext::EmbedMultiplePanes,
             ScreenInstruction::TogglePaneInGroup(..) => ScreenContext::TogglePaneInGroup,
             ScreenInstruction::ToggleGroupMarking(..) => ScreenContext::ToggleGroupMarking,
+            ScreenInstruction::ToggleGroupBroadcast(..) => ScreenContext::ToggleGroupBroadcast,
+            ScreenInstruction::ToggleScratchpad(..) => ScreenContext::ToggleScratchpad,
+            ScreenInstruction::ScratchpadOpened(..) => ScreenContext::ScratchpadOpened,
             ScreenInstruction::SessionSharingStatusChange(..) => {
                 ScreenContext::SessionSharingStatusChange
             },
@@ -1087,6 +1200,7 @@ impl From<&ScreenInstruction> for ScreenContext {
             ScreenInstruction::DesktopNotificationResponse(..) => {
                 ScreenContext::DesktopNotificationResponse
             },
+            ScreenInstruction::ClientReconnected(..) => ScreenContext::ClientReconnected,
             ScreenInstruction::SubscribeToPaneRenders { .. } => {
                 ScreenContext::SubscribeToPaneRenders
             },
@@ -1285,6 +1399,10 @@ struct PaneRenderSubscription {
 
 /// A [`Screen`] holds multiple [`Tab`]s, each one holding multiple [`panes`](crate::client::panes).
 /// It only directly controls which tab is active, delegating the rest to the individual `Tab`.
+/// The mouse regions of the tab each client is looking at, shared with the route threads so they
+/// can match mousebinds without asking the screen
+pub(crate) type MouseRegions = Arc<RwLock<HashMap<ClientId, Arc<MouseRegionMap>>>>;
+
 pub(crate) struct Screen {
     /// A Bus for sending and receiving messages with the other threads.
     pub bus: Bus<ScreenInstruction>,
@@ -1354,6 +1472,9 @@ pub(crate) struct Screen {
     pane_render_subscribers: HashMap<ClientId, PaneRenderSubscription>,
     plugins_need_ansi_pane_contents: bool,
     background_plugin_subscriptions: HashMap<(PluginId, ClientId), HashSet<EventType>>,
+    scratchpads: Scratchpads,
+    scratchpad_panes: HashMap<String, Option<PaneId>>, // String is the scratchpad name, None while it opens
+    mouse_regions: MouseRegions,
 }
 
 impl Screen {
@@ -1390,6 +1511,7 @@ impl Screen {
         mouse_click_through: bool,
         web_server_ip: IpAddr,
         web_server_port: u16,
+        scratchpads: Scratchpads,
     ) -> Self {
         let session_name = mode_info.session_name.clone().unwrap_or_default();
         let session_info = SessionInfo::new(session_name.clone());
@@ -1455,6 +1577,24 @@ impl Screen {
             pane_render_subscribers: HashMap::new(),
             plugins_need_ansi_pane_contents: false,
             background_plugin_subscriptions: HashMap::new(),
+            scratchpads,
+            scratchpad_panes: HashMap::new(),
+            mouse_regions: MouseRegions::default(),
+        }
+    }
+
+    /// Publish the mouse regions of the tab each client is looking at, for its route thread to
+    /// match mousebinds against
+    fn update_mouse_regions(&mut self) {
+        let mut mouse_regions = HashMap::new();
+        for (client_id, tab_id) in &self.active_tab_ids {
+            if let Some(tab) = self.tabs.get_mut(tab_id) {
+                mouse_regions.insert(*client_id, tab.mouse_region_map());
+            }
+        }
+        match self.mouse_regions.write() {
+            Ok(mut shared_mouse_regions) => *shared_mouse_regions = mouse_regions,
+            Err(e) => log::error!("Failed to update mouse regions: {}", e),
         }
     }
 
@@ -1812,6 +1952,14 @@ impl Screen {
 
         let mut tab_to_close = self.tabs.remove(&tab_id).with_context(err_context)?;
         let mut pane_ids = tab_to_close.get_all_pane_ids();
+        self.run_hooks(
+            HookEvent::TabClosed,
+            HookContext {
+                tab_name: Some(tab_to_close.name.clone()),
+                tab_position: Some(tab_to_close.position),
+                ..Default::default()
+            },
+        );
 
         // here we extract the suppressed panes (these are background panes that don't care which
         // tab they are in, and in the future we should probably make them global to screen rather
@@ -2091,6 +2239,7 @@ impl Screen {
             if bell_state_changed {
                 self.log_and_report_session_state()?;
             }
+            self.update_mouse_regions();
         } else {
             // No regular clients, output is not dirty
             non_watcher_output_was_dirty = false;
@@ -2454,6 +2603,14 @@ impl Screen {
         for (client_id, mode_info) in &self.mode_info {
             tab.change_mode_info(mode_info.clone(), *client_id);
         }
+        self.run_hooks(
+            HookEvent::TabCreated,
+            HookContext {
+                tab_name: Some(tab.name.clone()),
+                tab_position: Some(tab.position),
+                ..Default::default()
+            },
+        );
         self.tabs.insert(tab_id, tab);
         Ok(())
     }
@@ -2611,6 +2768,13 @@ impl Screen {
             .borrow_mut()
             .insert(client_id, is_web_client);
         self.tab_history.insert(client_id, tab_history);
+        self.run_hooks(
+            HookEvent::ClientAttached,
+            HookContext {
+                client_id: Some(client_id),
+                ..Default::default()
+            },
+        );
         self.tabs
             .get_mut(&tab_index)
             .with_context(|| err_context(tab_index))?
@@ -2651,7 +2815,20 @@ impl Screen {
         if self.tab_history.contains_key(&client_id) {
             self.tab_history.remove(&client_id);
         }
-        self.connected_clients.borrow_mut().remove(&client_id);
+        if self
+            .connected_clients
+            .borrow_mut()
+            .remove(&client_id)
+            .is_some()
+        {
+            self.run_hooks(
+                HookEvent::ClientDetached,
+                HookContext {
+                    client_id: Some(client_id),
+                    ..Default::default()
+                },
+            );
+        }
         self.pane_render_subscribers.remove(&client_id);
         self.log_and_report_session_state()
             .with_context(err_context)
@@ -3261,6 +3438,17 @@ impl Screen {
             }
         }
 
+        if previous_mode != mode_info.mode {
+            self.run_hooks(
+                HookEvent::ModeChanged,
+                HookContext {
+                    client_id: Some(client_id),
+                    mode: Some(mode_info.mode),
+                    ..Default::default()
+                },
+            );
+        }
+
         self.style = mode_info.style;
         self.mode_info.insert(client_id, mode_info.clone());
         for tab in self.tabs.values_mut() {
@@ -3302,6 +3490,12 @@ impl Screen {
         }
         Ok(())
     }
+    fn run_hooks(&self, hook_event: HookEvent, hook_context: HookContext) {
+        let _ = self
+            .bus
+            .senders
+            .send_to_background_jobs(BackgroundJob::RunHooks(hook_event, hook_context));
+    }
     /// Collect plugin IDs that should receive a broadcast event for a given client.
     /// Returns plugin IDs from the client's active tab plus background plugins
     /// subscribed to the given event type.
@@ -3548,6 +3742,55 @@ impl Screen {
         };
         Ok(())
     }
+    /// The viewport and scrollback of every terminal pane in the session, with tabs in order.
+    /// Searching them is left to the caller, so that it doesn't hold up the screen thread.
+    pub fn searchable_panes(&self) -> Vec<SearchablePane> {
+        let mut searchable_panes = vec![];
+        let mut tabs: Vec<&Tab> = self.tabs.values().collect();
+        tabs.sort_by_key(|tab| tab.position);
+        for tab in tabs {
+            for pane_id in tab.get_static_and_floating_pane_ids() {
+                if !matches!(pane_id, PaneId::Terminal(_)) {
+                    continue;
+                }
+                let Some(pane) = tab.get_pane_with_id(pane_id) else {
+                    continue;
+                };
+                let contents = pane.pane_contents(None, true, None);
+                let lines = contents
+                    .lines_above_viewport
+                    .into_iter()
+                    .chain(contents.viewport)
+                    .chain(contents.lines_below_viewport)
+                    .collect();
+                searchable_panes.push(SearchablePane {
+                    tab_position: tab.position,
+                    tab_name: tab.name.clone(),
+                    pane_id,
+                    pane_title: pane.current_title(),
+                    lines,
+                });
+            }
+        }
+        searchable_panes
+    }
+    pub fn reveal_search_match(
+        &mut self,
+        pane_id: PaneId,
+        query: &str,
+        case_sensitive: bool,
+        line_index: usize,
+        client_id: ClientId,
+    ) -> Result<()> {
+        self.focus_pane_with_id(pane_id, true, false, client_id)?;
+        for tab in self.tabs.values_mut() {
+            if let Some(pane) = tab.get_pane_with_id_mut(pane_id) {
+                pane.reveal_search_match(query, case_sensitive, line_index);
+                break;
+            }
+        }
+        Ok(())
+    }
     pub fn rerun_command_pane_with_id(
         &mut self,
         terminal_pane_id: u32,
@@ -4204,6 +4447,32 @@ impl Screen {
             }
         }
     }
+    fn tab_with_pane_id_mut(&mut self, pane_id: PaneId) -> Result<&mut Tab> {
+        self.tabs
+            .values_mut()
+            .find(|tab| tab.has_pane_with_pid(&pane_id))
+            .ok_or_else(|| anyhow!("Pane with id {:?} not found", pane_id))
+    }
+    fn report_pane_input_lock_result(
+        &mut self,
+        result: Result<()>,
+        mut completion: Option<NotificationEnd>,
+    ) -> Result<()> {
+        match result {
+            Ok(()) => {
+                self.render(None)?;
+                self.log_and_report_session_state()?;
+            },
+            Err(e) => {
+                log::error!("Failed to change pane input lock: {}", e);
+                if let Some(c) = completion.as_mut() {
+                    c.set_exit_status(1);
+                    c.set_error_message(e.to_string());
+                }
+            },
+        }
+        Ok(())
+    }
     pub fn set_pane_borderless(&mut self, pane_id: PaneId, borderless: bool) {
         for (_tab_id, tab) in self.tabs.iter_mut() {
             if tab.has_pane_with_pid(&pane_id) {
@@ -4304,10 +4573,145 @@ impl Screen {
         }
         Ok(())
     }
+    pub fn toggle_group_broadcast(&mut self, client_id: ClientId) -> Result<()> {
+        let (was_broadcasting_before, broadcasting_now) = {
+            let mut pane_groups = self.current_pane_group.borrow_mut();
+            let was_broadcasting_before = pane_groups.is_broadcasting(&client_id);
+            (
+                was_broadcasting_before,
+                pane_groups.toggle_broadcast(&client_id),
+            )
+        };
+        if was_broadcasting_before != broadcasting_now {
+            for tab in self.tabs.values_mut() {
+                tab.update_input_modes()?;
+                tab.set_force_render();
+            }
+            self.render(None)?;
+        }
+        Ok(())
+    }
+    pub fn toggle_scratchpad(&mut self, name: &str, client_id: ClientId) -> Result<()> {
+        let err_context = || format!("failed to toggle scratchpad {name}");
+        let scratchpad = self
+            .scratchpads
+            .get(name)
+            .cloned()
+            .with_context(err_context)?;
+        let open_scratchpad = match self.scratchpad_panes.get(name) {
+            Some(None) => {
+                // toggled again before its pane was spawned
+                return Ok(());
+            },
+            Some(Some(pane_id)) => self
+                .tabs
+                .values()
+                .find(|tab| tab.has_pane_with_pid(pane_id))
+                .map(|tab| (*pane_id, tab.id, tab.position)),
+            None => None,
+        };
+        let Some((pane_id, tab_id, tab_position)) = open_scratchpad else {
+            // the scratchpad was never opened or its pane was closed since
+            self.scratchpad_panes.insert(name.to_owned(), None);
+            self.bus
+                .senders
+                .send_to_pty(PtyInstruction::SpawnScratchpad {
+                    name: name.to_owned(),
+                    run_command: scratchpad.to_run_command(&self.default_shell),
+                    coordinates: scratchpad.coordinates,
+                    client_id,
+                })
+                .with_context(err_context)?;
+            return Ok(());
+        };
+        let active_tab_id = self
+            .active_tab_ids
+            .get(&client_id)
+            .copied()
+            .with_context(err_context)?;
+        if tab_id == active_tab_id {
+            let active_tab = self.get_active_tab_mut(client_id)?;
+            if active_tab.scratchpad_is_visible(pane_id) {
+                active_tab.hide_scratchpad(pane_id, client_id);
+            } else {
+                active_tab.show_scratchpad(pane_id, scratchpad.coordinates, client_id)?;
+            }
+        } else if scratchpad.move_to_focused_tab {
+            let pane = self
+                .tabs
+                .get_mut(&tab_id)
+                .and_then(|tab| tab.extract_scratchpad(pane_id))
+                .with_context(err_context)?;
+            self.get_active_tab_mut(client_id)?
+                .add_scratchpad_pane(pane, scratchpad.coordinates)?;
+        } else {
+            self.go_to_tab(tab_position + 1, client_id)?;
+            self.get_active_tab_mut(client_id)?.show_scratchpad(
+                pane_id,
+                scratchpad.coordinates,
+                client_id,
+            )?;
+        }
+        self.log_and_report_session_state()
+            .and_then(|_| self.render(None))
+            .with_context(err_context)
+    }
+    fn is_broadcasting_to_pane_group(&self, client_id: &ClientId) -> bool {
+        self.current_pane_group.borrow().is_broadcasting(client_id)
+    }
+    pub fn write_to_pane_group(
+        &mut self,
+        key_with_modifier: &Option<KeyWithModifier>,
+        raw_bytes: Vec<u8>,
+        is_kitty_keyboard_protocol: bool,
+        client_id: ClientId,
+    ) -> Result<bool> {
+        // returns true if a UI update should be triggered (eg. when closing a command pane with
+        // ctrl-c)
+        let err_context = || format!("failed to write to pane group of client {client_id}");
+        let mut should_trigger_ui_change = false;
+        let pane_group = self.get_client_pane_group(&client_id);
+        for tab in self.tabs.values_mut() {
+            // panes with locked input do not receive broadcast input
+            let pane_ids_in_tab: Vec<PaneId> = pane_group
+                .iter()
+                .filter(|p| tab.has_pane_with_pid(p) && tab.pane_input_lock(**p).is_none())
+                .copied()
+                .collect();
+            for pane_id in pane_ids_in_tab {
+                let ui_change_triggered = tab
+                    .write_to_pane_id(
+                        key_with_modifier,
+                        raw_bytes.clone(),
+                        is_kitty_keyboard_protocol,
+                        pane_id,
+                        Some(client_id),
+                        None,
+                    )
+                    .with_context(err_context)?;
+                if ui_change_triggered {
+                    should_trigger_ui_change = true;
+                }
+            }
+        }
+        Ok(should_trigger_ui_change)
+    }
     fn get_layout_metadata(
         &self,
         default_shell: Option<PathBuf>,
         tab_index: Option<usize>,
+    ) -> SessionLayoutMetadata {
+        self.get_layout_metadata_with_viewport(
+            default_shell,
+            tab_index,
+            self.serialize_pane_viewport,
+        )
+    }
+    fn get_layout_metadata_with_viewport(
+        &self,
+        default_shell: Option<PathBuf>,
+        tab_index: Option<usize>,
+        serialize_pane_viewport: bool,
     ) -> SessionLayoutMetadata {
         let mut session_layout_metadata = SessionLayoutMetadata::new(self.default_layout.clone());
         if let Some(default_shell) = default_shell {
@@ -4378,7 +4782,7 @@ impl Screen {
                         p.invoked_with().clone(),
                         p.custom_title(),
                         !focused_clients.is_empty(),
-                        if self.serialize_pane_viewport {
+                        if serialize_pane_viewport {
                             p.serialize(self.scrollback_lines_to_serialize)
                         } else {
                             None
@@ -4419,7 +4823,7 @@ impl Screen {
                         p.invoked_with().clone(),
                         p.custom_title(),
                         !focused_clients.is_empty(),
-                        if self.serialize_pane_viewport {
+                        if serialize_pane_viewport {
                             p.serialize(self.scrollback_lines_to_serialize)
                         } else {
                             None
@@ -4434,6 +4838,7 @@ impl Screen {
                 tab.name.clone(),
                 tab_is_focused,
                 hide_floating_panes,
+                tab.tiling_strategy(),
                 tiled_panes,
                 floating_panes,
             );
@@ -4503,6 +4908,62 @@ impl Screen {
         }
         self.retain_only_existing_panes_in_pane_groups();
     }
+    pub fn send_pane_to_session(
+        &mut self,
+        session_name: String,
+        pane_id: Option<PaneId>,
+        client_id: ClientId,
+        completion_tx: Option<NotificationEnd>,
+    ) -> Result<()> {
+        let err_context = || format!("failed to send pane to session {}", session_name);
+        let error_text = if session_name == self.session_name {
+            Some("The pane is already in this session.".to_owned())
+        } else {
+            match pane_id.or_else(|| self.get_active_pane_id(&client_id)) {
+                Some(PaneId::Terminal(terminal_id)) => {
+                    let pane = self
+                        .tabs
+                        .values()
+                        .find_map(|tab| tab.get_pane_with_id(PaneId::Terminal(terminal_id)));
+                    match pane {
+                        Some(pane) => {
+                            // the pane stays in this session until the target session has
+                            // adopted its pty, pty will close it here once that happens
+                            self.bus
+                                .senders
+                                .send_to_pty(PtyInstruction::SendTerminalToSession {
+                                    terminal_id,
+                                    session_name: session_name.clone(),
+                                    pane_title: pane.custom_title(),
+                                    scrollback: pane.serialize(Some(0)).unwrap_or_default(),
+                                    client_id,
+                                    completion_tx,
+                                })
+                                .with_context(err_context)?;
+                            None
+                        },
+                        None => Some(format!("Could not find terminal pane {}.", terminal_id)),
+                    }
+                },
+                Some(PaneId::Plugin(_)) => {
+                    Some("Only terminal panes can be sent to another session.".to_owned())
+                },
+                None => Some("No pane to send to another session.".to_owned()),
+            }
+        };
+        if let Some(error_text) = error_text {
+            log::error!("{}", error_text);
+            if let Some(os_input) = &mut self.bus.os_input {
+                let _ = os_input.send_to_client(
+                    client_id,
+                    ServerToClientMsg::LogError {
+                        lines: vec![error_text],
+                    },
+                );
+            }
+        }
+        Ok(())
+    }
     fn get_active_pane_id(&self, client_id: &ClientId) -> Option<PaneId> {
         let active_tab = self.get_active_tab(*client_id).ok()?;
         active_tab.get_active_pane_id(*client_id)
@@ -4934,6 +5395,8 @@ pub(crate) fn screen_thread_main(
     config: Config,
     debug: bool,
     default_layout: Box<Layout>,
+    session_name: Option<String>,
+    mouse_regions: MouseRegions,
 ) -> Result<()> {
     let config_options = config.options;
     let arrow_fonts = !config_options.simplified_ui.unwrap_or_default();
@@ -4990,22 +5453,28 @@ pub(crate) fn screen_thread_main(
     let visual_bell = config_options.visual_bell.unwrap_or(true);
     let focus_follows_mouse = config_options.focus_follows_mouse.unwrap_or(false);
     let mouse_click_through = config_options.mouse_click_through.unwrap_or(false);
+    // scratchpads defined in the layout take precedence over those in the config
+    let mut scratchpads = config.scratchpads;
+    scratchpads.merge(default_layout.scratchpads.clone());
+
+    let mut mode_info = get_mode_info(
+        config_options.default_mode.unwrap_or_default(),
+        &client_attributes,
+        PluginCapabilities {
+            //  ¯\_(ツ)_/¯
+            arrow_fonts: !arrow_fonts,
+        },
+        &config.keybinds,
+        config_options.default_mode,
+    );
+    mode_info.session_name = session_name;
 
     let thread_senders = bus.senders.clone();
     let mut screen = Screen::new(
         bus,
         &client_attributes,
         max_panes,
-        get_mode_info(
-            config_options.default_mode.unwrap_or_default(),
-            &client_attributes,
-            PluginCapabilities {
-                //  ¯\_(ツ)_/¯
-                arrow_fonts: !arrow_fonts,
-            },
-            &config.keybinds,
-            config_options.default_mode,
-        ),
+        mode_info,
         draw_pane_frames,
         auto_layout,
         session_is_mirrored,
@@ -5033,7 +5502,9 @@ pub(crate) fn screen_thread_main(
         mouse_click_through,
         web_server_ip,
         web_server_port,
+        scratchpads,
     );
+    screen.mouse_regions = mouse_regions;
 
     let mut pending_tab_ids: HashSet<usize> = HashSet::new();
     let mut pending_tab_switches: HashSet<(usize, ClientId)> = HashSet::new(); // usize is the
@@ -5325,6 +5796,21 @@ pub(crate) fn screen_thread_main(
                             state_changed = true;
                         }
                     },
+                    mode if !matches!(
+                        mode,
+                        Some(InputMode::EnterSearch) | Some(InputMode::RenamePane)
+                    ) && screen.is_broadcasting_to_pane_group(&client_id) =>
+                    {
+                        let write_result = screen.write_to_pane_group(
+                            &key_with_modifier,
+                            raw_bytes,
+                            is_kitty_keyboard_protocol,
+                            client_id,
+                        );
+                        if let Ok(true) = write_result {
+                            state_changed = true;
+                        }
+                    },
                     _ => {
                         active_tab_and_connected_client_id!(
                             screen,
@@ -5866,6 +6352,56 @@ pub(crate) fn screen_thread_main(
                     );
                 }
             },
+            ScreenInstruction::ListSearchablePanes { response_channel } => {
+                if response_channel.send(screen.searchable_panes()).is_err() {
+                    // the plugin likely timed out and dropped the receiver
+                    log::debug!("Plugin timed out before the panes to search were sent");
+                }
+            },
+            ScreenInstruction::RevealSearchMatch {
+                pane_id,
+                query,
+                case_sensitive,
+                line_index,
+                client_id,
+            } => {
+                screen.reveal_search_match(
+                    pane_id,
+                    &query,
+                    case_sensitive,
+                    line_index,
+                    client_id,
+                )?;
+                screen.render(None)?;
+                screen.log_and_report_session_state()?;
+            },
+            ScreenInstruction::RestoreClosedPane {
+                terminal_id,
+                scrollback,
+                exit_status,
+                run_command,
+            } => {
+                // the pane shows its old contents and is held as if its command had just exited,
+                // so that it can be re-run in place
+                let is_first_run = false;
+                for tab in screen.get_tabs_mut().values_mut() {
+                    if tab.has_terminal_pid(terminal_id) {
+                        if !scrollback.is_empty() {
+                            tab.handle_pty_bytes(terminal_id, scrollback.into_bytes())
+                                .context("failed to restore closed pane scrollback")?;
+                        }
+                        tab.hold_pane(
+                            PaneId::Terminal(terminal_id),
+                            exit_status,
+                            is_first_run,
+                            run_command,
+                        );
+                        break;
+                    }
+                }
+                screen.render(None)?;
+                screen.log_and_report_session_state()?;
+            },
             ScreenInstruction::ScrollUp(
                 client_id,
                 _completion_tx, // the action ends here, dropping this will release anything
@@ -5904,6 +6440,34 @@ pub(crate) fn screen_thread_main(
                 screen.render(None)?;
                 screen.log_and_report_session_state()?;
             },
+            ScreenInstruction::EqualizePanes(
+                client_id,
+                direction,
+                _completion_tx, // the action ends here, dropping this will release anything
+                                // waiting for it
+            ) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, client_id: ClientId| tab.equalize_panes(client_id, direction)
+                );
+                screen.render(None)?;
+                screen.log_and_report_session_state()?;
+            },
+            ScreenInstruction::SetTilingStrategy(
+                client_id,
+                tiling_strategy,
+                _completion_tx, // the action ends here, dropping this will release anything
+                                // waiting for it
+            ) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, _client_id: ClientId| tab.set_tiling_strategy(tiling_strategy)
+                );
+                screen.render(None)?;
+                screen.log_and_report_session_state()?;
+            },
             ScreenInstruction::MovePaneDown(
                 client_id,
                 _completion_tx, // the action ends here, dropping this will release anything
@@ -7872,6 +8436,23 @@ pub(crate) fn screen_thread_main(
                     })
                     .with_context(err_context)?;
             },
+            ScreenInstruction::SaveSnapshot(snapshot_name, include_viewport, completion_tx) => {
+                let session_layout_metadata = screen.get_layout_metadata_with_viewport(
+                    Some(screen.default_shell.clone()),
+                    None,
+                    include_viewport,
+                );
+                screen
+                    .bus
+                    .senders
+                    .send_to_pty(PtyInstruction::SaveSnapshotToDisk {
+                        session_name: screen.session_name.clone(),
+                        snapshot_name,
+                        session_layout_metadata,
+                        completion_tx,
+                    })
+                    .with_context(|| "Failed to save snapshot")?;
+            },
             ScreenInstruction::RenameSession(
                 name,
                 client_id,
@@ -7953,6 +8534,14 @@ pub(crate) fn screen_thread_main(
                     }
                 }
             },
+            ScreenInstruction::SendPaneToSession {
+                session_name,
+                pane_id,
+                client_id,
+                completion_tx,
+            } => {
+                screen.send_pane_to_session(session_name, pane_id, client_id, completion_tx)?;
+            },
             ScreenInstruction::Reconfigure {
                 client_id,
                 keybinds,
@@ -8384,6 +8973,28 @@ pub(crate) fn screen_thread_main(
                 screen.set_pane_borderless(pane_id, borderless);
                 let _ = screen.render(None);
             },
+            ScreenInstruction::TogglePaneInputLock(client_id, input_lock, completion_tx) => {
+                let result = screen
+                    .get_active_tab_mut(client_id)
+                    .and_then(|tab| tab.toggle_pane_input_lock(client_id, input_lock));
+                screen.report_pane_input_lock_result(result, completion_tx)?;
+            },
+            ScreenInstruction::TogglePaneInputLockWithPaneId(
+                pane_id,
+                input_lock,
+                completion_tx,
+            ) => {
+                let result = screen
+                    .tab_with_pane_id_mut(pane_id)
+                    .and_then(|tab| tab.toggle_pane_input_lock_by_pane_id(pane_id, input_lock));
+                screen.report_pane_input_lock_result(result, completion_tx)?;
+            },
+            ScreenInstruction::SetPaneInputLock(pane_id, input_lock, completion_tx) => {
+                let result = screen
+                    .tab_with_pane_id_mut(pane_id)
+                    .and_then(|tab| tab.set_pane_input_lock(pane_id, input_lock));
+                screen.report_pane_input_lock_result(result, completion_tx)?;
+            },
             ScreenInstruction::GroupAndUngroupPanes(
                 pane_ids_to_group,
                 pane_ids_to_ungroup,
@@ -8412,6 +9023,35 @@ pub(crate) fn screen_thread_main(
             ) => {
                 screen.toggle_group_marking(client_id).non_fatal();
             },
+            ScreenInstruction::ToggleGroupBroadcast(client_id, mut _completion_tx) => {
+                if screen.get_client_pane_group(&client_id).is_empty() {
+                    log::error!("No grouped panes to broadcast to");
+                    if let Some(ref mut c) = _completion_tx {
+                        c.set_exit_status(1);
+                        c.set_error_message("No grouped panes to broadcast to".to_string());
+                    }
+                } else {
+                    screen.toggle_group_broadcast(client_id).non_fatal();
+                }
+            },
+            ScreenInstruction::ToggleScratchpad(name, client_id, mut _completion_tx) => {
+                if screen.scratchpads.get(&name).is_none() {
+                    log::error!("No scratchpad named {}", name);
+                    if let Some(ref mut c) = _completion_tx {
+                        c.set_exit_status(1);
+                        c.set_error_message(format!("No scratchpad named {}", name));
+                    }
+                } else {
+                    screen.toggle_scratchpad(&name, client_id).non_fatal();
+                }
+            },
+            ScreenInstruction::ScratchpadOpened(name, Some(pane_id)) => {
+                screen.scratchpad_panes.insert(name, Some(pane_id));
+            },
+            ScreenInstruction::ScratchpadOpened(name, None) => {
+                // so that toggling it tries to open it again
+                screen.scratchpad_panes.remove(&name);
+            },
             ScreenInstruction::SessionSharingStatusChange(web_sharing) => {
                 if web_sharing {
                     screen.web_sharing = WebSharing::On;
@@ -8592,6 +9232,16 @@ pub(crate) fn screen_thread_main(
                 }
                 screen.render(None)?;
             },
+            ScreenInstruction::ClientReconnected(client_id) => {
+                // whatever was rendered while the client was disconnected never reached its
+                // terminal, so we render everything again
+                log::info!("Client {} reconnected, re-rendering", client_id);
+                for tab in screen.get_tabs_mut().values_mut() {
+                    tab.set_force_render();
+                    tab.clear_local_echo_state(client_id);
+                }
+                screen.render(None)?;
+            },
             ScreenInstruction::DesktopNotificationResponse(raw_bytes, client_id) => {
                 if let Some((terminal_id, app_wants_report, is_query, rewritten_bytes)) =
                     denormalize_notification_response(&raw_bytes)
@@ -8,7 +8,7 @@ use zellij_utils::pane_size::PaneGeom;
 use zellij_utils::{
     data::{LayoutMetadata, PaneMetadata, TabMetadata},
     input::command::RunCommand,
-    input::layout::{Layout, Run, RunPlugin, RunPluginOrAlias},
+    input::layout::{Layout, Run, RunPlugin, RunPluginOrAlias, TilingStrategy},
     input::plugins::PluginAliases,
     session_serialization::{
         extract_command_and_args, extract_edit_and_line_number, extract_plugin_and_config,
@@ -185,6 +185,12 @@ impl SessionLayoutMetadata {
             if location_string == "zellij:share" {
                 return true;
             }
+            if location_string == "zellij:pane-search" {
+                return true;
+            }
+            if location_string == "zellij:closed-panes" {
+                return true;
+            }
         }
         false
     }
@@ -208,6 +214,7 @@ impl SessionLayoutMetadata {
         name: String,
         is_focused: bool,
         hide_floating_panes: bool,
+        tiling_strategy: Option<TilingStrategy>,
         tiled_panes: Vec<PaneLayoutMetadata>,
         floating_panes: Vec<PaneLayoutMetadata>,
     ) {
@@ -215,6 +222,7 @@ impl SessionLayoutMetadata {
             name: Some(name),
             is_focused,
             hide_floating_panes,
+            tiling_strategy,
             tiled_panes,
             floating_panes,
         })
@@ -472,6 +480,7 @@ impl SessionLayoutMetadata {
             tabs,
             creation_time: current_time.clone(),
             update_time: current_time,
+            params: vec![],
         }
     }
 }
@@ -498,6 +507,7 @@ impl Into<TabLayoutManifest> for TabLayoutMetadata {
             floating_panes: self.floating_panes.into_iter().map(|t| t.into()).collect(),
             is_focused: self.is_focused,
             hide_floating_panes: self.hide_floating_panes,
+            tiling_strategy: self.tiling_strategy,
         }
     }
 }
@@ -546,6 +556,7 @@ pub struct TabLayoutMetadata {
     floating_panes: Vec<PaneLayoutMetadata>,
     is_focused: bool,
     hide_floating_panes: bool,
+    tiling_strategy: Option<TilingStrategy>,
 }
 
 #[derive(Debug, Clone)]
@@ -726,7 +737,7 @@ mod tests {
     fn session_with_editor(editor: &str, panes: Vec<PaneLayoutMetadata>) -> SessionLayoutMetadata {
         let mut meta = SessionLayoutMetadata::default();
         meta.default_editor = Some(PathBuf::from(editor));
-        meta.add_tab("tab1".to_string(), true, false, panes, vec![]);
+        meta.add_tab("tab1".to_string(), true, false, None, panes, vec![]);
         meta
     }
 
@@ -728,6 +728,9 @@ impl<'a> LayoutApplier<'a> {
         if let Some(exclude_from_sync) = layout.exclude_from_sync {
             new_pane.set_exclude_from_sync(exclude_from_sync);
         }
+        if layout.input_lock.is_some() {
+            new_pane.set_input_lock(layout.input_lock);
+        }
         if layout.default_fg.is_some() || layout.default_bg.is_some() {
             new_pane.set_pane_default_colors(layout.default_fg.clone(), layout.default_bg.clone());
         }
@@ -9,15 +9,16 @@ mod swap_layouts;
 
 use crate::plugins::PluginId;
 use copy_command::CopyCommand;
-pub use mouse_handler::{MouseEffect, MouseHandler, PaneEdge, PaneResizeState};
+pub use mouse_handler::{MouseEffect, MouseHandler, MouseRegionMap, PaneEdge, PaneResizeState};
 use std::env::temp_dir;
 use std::net::IpAddr;
 use std::path::PathBuf;
 use uuid::Uuid;
 use zellij_utils::data::PaneContents;
 use zellij_utils::data::{
-    Direction, KeyWithModifier, NewPanePlacement, PaneInfo, PermissionStatus, PermissionType,
-    PluginPermission, RegexHighlight, ResizeStrategy, Style, WebSharing,
+    BareKey, Direction, KeyWithModifier, NewPanePlacement, PaneInfo, PaneInputLock,
+    PermissionStatus, PermissionType, PluginPermission, RegexHighlight, ResizeStrategy, Style,
+    WebSharing,
 };
 use zellij_utils::errors::prelude::*;
 use zellij_utils::input::command::RunCommand;
@@ -27,6 +28,7 @@ use zellij_utils::position::{Column, Line};
 use zellij_utils::shared::clean_string_from_control_and_linebreak;
 
 use crate::background_jobs::BackgroundJob;
+use crate::closed_panes::{ClosedPane, MAX_CLOSED_PANE_SCROLLBACK_LINES};
 use crate::pane_groups::PaneGroups;
 use crate::pty_writer::PtyWriteInstruction;
 use crate::screen::{CopyOptions, ScreenInstruction};
@@ -51,18 +53,20 @@ use crate::{
 };
 use std::cell::RefCell;
 use std::rc::Rc;
+use std::sync::Arc;
 use std::time::Instant;
 use std::{
     collections::{BTreeMap, HashMap, HashSet},
     str,
 };
+use unicode_width::UnicodeWidthStr;
 use zellij_utils::{
     data::{Event, FloatingPaneCoordinates, InputMode, ModeInfo, Palette, PaletteColor, Styling},
     input::{
         command::TerminalAction,
         layout::{
-            FloatingPaneLayout, Run, RunPluginOrAlias, SwapFloatingLayout, SwapTiledLayout,
-            TiledPaneLayout,
+            FloatingPaneLayout, Run, RunPluginOrAlias, SplitDirection, SwapFloatingLayout,
+            SwapTiledLayout, TiledPaneLayout, TilingStrategy,
         },
         parse_keys,
     },
@@ -153,6 +157,26 @@ enum BufferedTabInstruction {
     HoldPane(PaneId, Option<i32>, bool, RunCommand), // Option<i32> is the exit status, bool is is_first_run
 }
 
+// input sent to a pane locked with PaneInputLock::Confirm, held until the user confirms or
+// discards it
+struct HeldInput {
+    key_with_modifier: Option<KeyWithModifier>,
+    raw_input_bytes: Vec<u8>,
+    raw_input_bytes_are_kitty: bool,
+    client_id: Option<ClientId>,
+}
+
+impl HeldInput {
+    fn is_key(&self, key: BareKey, key_bytes: &[u8]) -> bool {
+        self.raw_input_bytes.as_slice() == key_bytes
+            || self
+                .key_with_modifier
+                .as_ref()
+                .map(|k| k.is_key_without_modifier(key))
+                .unwrap_or(false)
+    }
+}
+
 pub(crate) struct Tab {
     pub id: usize,
     pub position: usize,
@@ -161,6 +185,9 @@ pub(crate) struct Tab {
     tiled_panes: TiledPanes,
     floating_panes: FloatingPanes,
     suppressed_panes: SuppressedPanes,
+    // scratchpads shown while the floating panes were hidden keep them hidden, they are shown
+    // again when the scratchpad is hidden or closed
+    floating_panes_hidden_by_scratchpads: HashMap<PaneId, Vec<PaneId>>,
     max_panes: Option<usize>,
     viewport: Rc<RefCell<Viewport>>, // includes all non-UI panes
     display_area: Rc<RefCell<Size>>, // includes all panes (including eg. the status bar and tab bar in the default layout)
@@ -191,6 +218,8 @@ pub(crate) struct Tab {
     cursor_positions_and_shape: HashMap<ClientId, (usize, usize, String)>, // (x_position,
     // y_position,
     // cursor_shape_csi)
+    local_echo_states: HashMap<ClientId, (bool, usize, usize)>, // (predictable, first_column,
+    // last_column), last sent to web clients
     is_pending: bool, // a pending tab is one that is still being loaded or otherwise waiting
     pending_instructions: Vec<BufferedTabInstruction>, // instructions that came while the tab was
     // pending and need to be re-applied
@@ -205,6 +234,7 @@ pub(crate) struct Tab {
     web_clients_allowed: bool,
     web_sharing: WebSharing,
     mouse_hover_pane_id: HashMap<ClientId, PaneId>,
+    mouse_region_map: Option<(mouse_handler::MouseRegionKey, Arc<MouseRegionMap>)>,
     mouse_help_text_visible: HashMap<ClientId, bool>,
     last_mouse_activity_time: HashMap<ClientId, Instant>,
     current_pane_group: Rc<RefCell<PaneGroups>>,
@@ -222,6 +252,7 @@ pub(crate) struct Tab {
     pub tab_has_pending_bell: bool,
     pub tab_bell_flash: bool, // currently in mid-notification-flash
     pub tab_bell_ring: bool,  // need to send ANSI BEL to the controlling terminal
+    held_input_for_locked_panes: HashMap<PaneId, Vec<HeldInput>>,
 }
 
 // FIXME: Use a struct that has a pane_type enum, to reduce all of the duplication
@@ -442,6 +473,20 @@ pub trait Pane {
         }
         false
     }
+    fn position_is_on_frame_title(&self, position: &Position) -> bool {
+        // the title is rendered on the top frame line right after the top-left corner, padded by
+        // a space on each side
+        if !self.position_is_on_frame(position)
+            || self.get_content_y() == self.y()
+            || position.line() != self.y() as isize
+        {
+            return false;
+        }
+        let title_start = self.x() + 1;
+        let title_end = (title_start + self.current_title().width() + 2)
+            .min((self.x() + self.cols()).saturating_sub(1));
+        (title_start..title_end).contains(&position.column())
+    }
     fn get_edge_at_position(&self, position: &Position) -> Option<PaneEdge> {
         if !self.contains(position) {
             return None;
@@ -486,6 +531,18 @@ pub trait Pane {
     fn borderless(&self) -> bool;
     fn set_exclude_from_sync(&mut self, exclude_from_sync: bool);
     fn exclude_from_sync(&self) -> bool;
+    fn set_input_lock(&mut self, _input_lock: Option<PaneInputLock>) {
+        // No-op by default (only terminal-panes can have their input locked)
+    }
+    fn input_lock(&self) -> Option<PaneInputLock> {
+        None
+    }
+    fn set_awaiting_input_confirmation(&mut self, _awaiting_input_confirmation: bool) {
+        // No-op by default (only terminal-panes can have their input locked)
+    }
+    fn is_awaiting_input_confirmation(&self) -> bool {
+        false
+    }
 
     // TODO: this should probably be merged with the mouse_right_click
     fn handle_right_click(&mut self, _to: &Position, _client_id: ClientId) {}
@@ -546,6 +603,9 @@ pub trait Pane {
     fn clear_search(&mut self) {
         // No-op by default (only terminal-panes currently have search capability)
     }
+    fn reveal_search_match(&mut self, _needle: &str, _case_sensitive: bool, _line_index: usize) {
+        // No-op by default (only terminal-panes currently have search capability)
+    }
     fn is_alternate_mode_active(&self) -> bool {
         // False by default (only terminal-panes support alternate mode)
         false
@@ -775,6 +835,7 @@ impl Tab {
             tiled_panes,
             floating_panes,
             suppressed_panes: HashMap::new(),
+            floating_panes_hidden_by_scratchpads: HashMap::new(),
             name: name.clone(),
             prev_name: name,
             max_panes,
@@ -803,6 +864,7 @@ impl Tab {
             terminal_emulator_color_codes,
             pids_waiting_resize: HashSet::new(),
             cursor_positions_and_shape: HashMap::new(),
+            local_echo_states: HashMap::new(),
             is_pending: true, // will be switched to false once the layout is applied
             pending_instructions: vec![],
             swap_layouts,
@@ -816,6 +878,7 @@ impl Tab {
             web_clients_allowed,
             web_sharing,
             mouse_hover_pane_id: HashMap::new(),
+            mouse_region_map: None,
             mouse_help_text_visible: HashMap::new(),
             last_mouse_activity_time: HashMap::new(),
             current_pane_group,
@@ -831,6 +894,7 @@ impl Tab {
             tab_has_pending_bell: false,
             tab_bell_flash: false,
             tab_bell_ring: false,
+            held_input_for_locked_panes: HashMap::new(),
         }
     }
 
@@ -844,6 +908,7 @@ impl Tab {
         client_id: ClientId,
         blocking_terminal: Option<(u32, NotificationEnd)>,
     ) -> Result<()> {
+        let tiling_strategy = layout.tiling_strategy;
         self.swap_layouts
             .set_base_layout((layout.clone(), floating_panes_layout.clone()));
         match LayoutApplier::new(
@@ -886,6 +951,10 @@ impl Tab {
                         .non_fatal();
                 }
                 self.tiled_panes.reapply_pane_frames();
+                if tiling_strategy.is_some() {
+                    self.tiled_panes.set_tiling_strategy(tiling_strategy);
+                    self.retile_tiled_panes();
+                }
                 self.is_pending = false;
                 self.apply_buffered_instructions().non_fatal();
             },
@@ -922,6 +991,7 @@ impl Tab {
             .set_swap_tiled_layouts(new_swap_tiled_layouts);
         self.swap_layouts
             .set_swap_floating_layouts(new_swap_floating_layouts);
+        let tiling_strategy = layout.tiling_strategy;
         self.swap_layouts
             .set_base_layout((layout.clone(), floating_panes_layout.clone()));
         match LayoutApplier::new(
@@ -985,6 +1055,9 @@ impl Tab {
                     &mut self.tiled_panes,
                     self.draw_pane_frames,
                 );
+                // the new layout decides whether the tab has a tiling strategy
+                self.tiled_panes.set_tiling_strategy(tiling_strategy);
+                self.retile_tiled_panes();
 
                 self.apply_buffered_instructions().non_fatal();
             },
@@ -1103,6 +1176,8 @@ impl Tab {
         if self.floating_panes.panes_are_visible() {
             self.relayout_floating_panes(search_backwards)?;
         } else {
+            // the user chose a swap layout over the tiling strategy
+            self.tiled_panes.set_tiling_strategy(None);
             self.relayout_tiled_panes(search_backwards)?;
         }
         Ok(())
@@ -1112,6 +1187,8 @@ impl Tab {
         if self.floating_panes.panes_are_visible() {
             self.relayout_floating_panes(search_backwards)?;
         } else {
+            // the user chose a swap layout over the tiling strategy
+            self.tiled_panes.set_tiling_strategy(None);
             self.relayout_tiled_panes(search_backwards)?;
         }
         Ok(())
@@ -1166,6 +1243,8 @@ impl Tab {
             mode_info.web_sharing = Some(self.web_sharing);
             mode_info.currently_marking_pane_group =
                 currently_marking_pane_group.get(client_id).copied();
+            mode_info.broadcasting_to_pane_group =
+                Some(self.current_pane_group.borrow().is_broadcasting(client_id));
             mode_info.web_server_ip = Some(self.web_server_ip);
             mode_info.web_server_port = Some(self.web_server_port);
             mode_info.is_web_client = self
@@ -2355,6 +2434,7 @@ impl Tab {
                 self.set_should_clear_display_before_rendering();
                 self.tiled_panes.focus_pane(pid, client_id);
                 self.swap_layouts.set_is_tiled_damaged();
+                self.retile_tiled_panes();
             }
         } else {
             log::error!("No room to split pane horizontally");
@@ -2422,6 +2502,7 @@ impl Tab {
                 self.set_should_clear_display_before_rendering();
                 self.tiled_panes.focus_pane(pid, client_id);
                 self.swap_layouts.set_is_tiled_damaged();
+                self.retile_tiled_panes();
             }
         } else {
             log::error!("No room to split pane vertically");
@@ -2845,6 +2926,21 @@ impl Tab {
         let bracketed_paste_begin = vec![27, 91, 50, 48, 48, 126];
         let bracketed_paste_end = vec![27, 91, 50, 48, 49, 126];
 
+        if self.pane_input_lock(pane_id).is_some() {
+            // a paste is held (or dropped) as a whole rather than as three separate writes
+            let held_input = [bracketed_paste_begin, bytes, bracketed_paste_end]
+                .into_iter()
+                .map(|raw_input_bytes| HeldInput {
+                    key_with_modifier: None,
+                    raw_input_bytes,
+                    raw_input_bytes_are_kitty: false,
+                    client_id: None,
+                })
+                .collect();
+            self.intercept_input_to_locked_pane(pane_id, held_input)?;
+            return Ok(());
+        }
+
         self.write_to_pane_id(&None, bracketed_paste_begin, false, pane_id, None, None)?;
         self.write_to_pane_id(&None, bytes, false, pane_id, None, None)?;
         self.write_to_pane_id(&None, bracketed_paste_end, false, pane_id, None, completion)?;
@@ -2877,7 +2973,6 @@ impl Tab {
         // returns true if we need to update the UI (eg. when a command pane is closed with ctrl-c)
         let err_context = || format!("failed to write to pane with id {pane_id:?}");
 
-        let mut should_update_ui = false;
         let is_sync_panes_active = self.is_sync_panes_active();
 
         let active_pane = self
@@ -2891,13 +2986,56 @@ impl Tab {
         // We always write for non-synced terminals.
         // However if the terminal is part of a tab-sync, we need to
         // check if the terminal should receive input or not (depending on its
-        // 'exclude_from_sync' configuration).
-        let should_not_write_to_terminal = is_sync_panes_active && active_pane.exclude_from_sync();
+        // 'exclude_from_sync' configuration or on its input being locked).
+        let should_not_write_to_terminal = is_sync_panes_active
+            && (active_pane.exclude_from_sync() || active_pane.input_lock().is_some());
 
         if should_not_write_to_terminal {
-            return Ok(should_update_ui);
+            return Ok(false);
+        }
+
+        if active_pane.input_lock().is_some() {
+            let held_input = vec![HeldInput {
+                key_with_modifier: key_with_modifier.clone(),
+                raw_input_bytes,
+                raw_input_bytes_are_kitty,
+                client_id,
+            }];
+            return self
+                .intercept_input_to_locked_pane(pane_id, held_input)
+                .with_context(err_context);
         }
 
+        self.write_to_pane_id_ignoring_input_lock(
+            key_with_modifier,
+            raw_input_bytes,
+            raw_input_bytes_are_kitty,
+            pane_id,
+            client_id,
+            completion_tx,
+        )
+    }
+    fn write_to_pane_id_ignoring_input_lock(
+        &mut self,
+        key_with_modifier: &Option<KeyWithModifier>,
+        raw_input_bytes: Vec<u8>,
+        raw_input_bytes_are_kitty: bool,
+        pane_id: PaneId,
+        client_id: Option<ClientId>,
+        completion_tx: Option<NotificationEnd>,
+    ) -> Result<bool> {
+        // returns true if we need to update the UI (eg. when a command pane is closed with ctrl-c)
+        let err_context = || format!("failed to write to pane with id {pane_id:?}");
+
+        let mut should_update_ui = false;
+        let active_pane = self
+            .floating_panes
+            .get_mut(&pane_id)
+            .or_else(|| self.tiled_panes.get_pane_mut(pane_id))
+            .or_else(|| self.suppressed_panes.get_mut(&pane_id).map(|p| &mut p.1))
+            .ok_or_else(|| anyhow!(format!("failed to find pane with id {pane_id:?}")))
+            .with_context(err_context)?;
+
         match pane_id {
             PaneId::Terminal(active_terminal_id) => {
                 match active_pane.adjust_input_to_terminal(
@@ -2993,6 +3131,63 @@ impl Tab {
         }
         Ok(should_update_ui)
     }
+    fn intercept_input_to_locked_pane(
+        &mut self,
+        pane_id: PaneId,
+        held_input: Vec<HeldInput>,
+    ) -> Result<bool> {
+        // returns true if we need to update the UI (eg. when the pane starts or stops waiting for
+        // the user to confirm its held input)
+        let err_context = || format!("failed to intercept input to locked pane {pane_id:?}");
+        let pane = self
+            .get_pane_with_id_mut(pane_id)
+            .ok_or_else(|| anyhow!("failed to find pane with id {pane_id:?}"))
+            .with_context(err_context)?;
+        match pane.input_lock() {
+            Some(PaneInputLock::Confirm) if pane.is_awaiting_input_confirmation() => {
+                let is_single_key = |key: BareKey, key_bytes: &[u8]| {
+                    held_input.len() == 1 && held_input[0].is_key(key, key_bytes)
+                };
+                if is_single_key(BareKey::Enter, &[13]) {
+                    // the pane stays locked, only the input that was held is let through
+                    pane.set_awaiting_input_confirmation(false);
+                    let held_input = self
+                        .held_input_for_locked_panes
+                        .remove(&pane_id)
+                        .unwrap_or_default();
+                    for held_input in held_input {
+                        self.write_to_pane_id_ignoring_input_lock(
+                            &held_input.key_with_modifier,
+                            held_input.raw_input_bytes,
+                            held_input.raw_input_bytes_are_kitty,
+                            pane_id,
+                            held_input.client_id,
+                            None,
+                        )
+                        .with_context(err_context)?;
+                    }
+                    Ok(true)
+                } else if is_single_key(BareKey::Esc, &[27]) {
+                    pane.set_awaiting_input_confirmation(false);
+                    self.held_input_for_locked_panes.remove(&pane_id);
+                    Ok(true)
+                } else {
+                    // other input is held along with the input awaiting confirmation
+                    self.held_input_for_locked_panes
+                        .entry(pane_id)
+                        .or_default()
+                        .extend(held_input);
+                    Ok(false)
+                }
+            },
+            Some(PaneInputLock::Confirm) => {
+                pane.set_awaiting_input_confirmation(true);
+                self.held_input_for_locked_panes.insert(pane_id, held_input);
+                Ok(true)
+            },
+            Some(PaneInputLock::Drop) | None => Ok(false),
+        }
+    }
     pub fn write_to_pane_id_without_preprocessing(
         &mut self,
         raw_input_bytes: Vec<u8>,
@@ -3183,12 +3378,14 @@ impl Tab {
 
         let current_pane_group: HashMap<ClientId, Vec<PaneId>> =
             { self.current_pane_group.borrow().clone_inner() };
+        let broadcasting_clients = { self.current_pane_group.borrow().broadcasting_clients() };
         self.tiled_panes
             .render(
                 output,
                 self.floating_panes.panes_are_visible(),
                 &self.mouse_hover_pane_id,
                 current_pane_group.clone(),
+                &broadcasting_clients,
                 client_id_override,
                 &self.mouse_help_text_visible,
             )
@@ -3201,6 +3398,7 @@ impl Tab {
                     output,
                     &self.mouse_hover_pane_id,
                     current_pane_group,
+                    &broadcasting_clients,
                     client_id_override,
                     &self.mouse_help_text_visible,
                 )
@@ -3236,7 +3434,7 @@ impl Tab {
         let connected_clients: Vec<ClientId> =
             { self.connected_clients.borrow().iter().copied().collect() };
         for client_id in connected_clients {
-            match self
+            let visible_cursor_position = self
                 .get_active_terminal_cursor_position(client_id)
                 .and_then(|(cursor_position_x, cursor_position_y)| {
                     let active_pane_z_index = self
@@ -3251,7 +3449,8 @@ impl Tab {
                     } else {
                         None
                     }
-                }) {
+                });
+            match visible_cursor_position {
                 Some((cursor_position_x, cursor_position_y)) => {
                     let desired_cursor_shape = self
                         .get_active_pane(client_id)
@@ -3297,8 +3496,60 @@ impl Tab {
                     output.add_post_vte_instruction_to_client(client_id, hide_cursor);
                 },
             }
+            self.update_local_echo_state(client_id, visible_cursor_position.is_some());
         }
     }
+    fn update_local_echo_state(&mut self, client_id: ClientId, cursor_is_visible: bool) {
+        // only remote clients echo locally, and only when typing into the focused pane is
+        // likely to print at the cursor (eg. a shell prompt rather than a full screen app or a
+        // mode in which keys are zellij commands)
+        let is_web_client = self
+            .connected_clients_in_app
+            .borrow()
+            .get(&client_id)
+            .copied()
+            .unwrap_or(false);
+        if !is_web_client {
+            return;
+        }
+        let input_mode = self
+            .mode_info
+            .borrow()
+            .get(&client_id)
+            .unwrap_or(&self.default_mode_info)
+            .mode;
+        let local_echo_state = self
+            .get_active_pane(client_id)
+            .filter(|pane| {
+                cursor_is_visible
+                    && matches!(pane.pid(), PaneId::Terminal(_))
+                    && matches!(input_mode, InputMode::Normal | InputMode::Locked)
+                    && !pane.is_alternate_mode_active()
+                    && !pane.is_scrolled()
+            })
+            .map(|pane| {
+                let first_column = pane.get_content_x();
+                let last_column = first_column + pane.get_content_columns().saturating_sub(1);
+                (true, first_column, last_column)
+            })
+            .unwrap_or((false, 0, 0));
+        if self.local_echo_states.get(&client_id) != Some(&local_echo_state) {
+            self.local_echo_states.insert(client_id, local_echo_state);
+            let (predictable, first_column, last_column) = local_echo_state;
+            self.senders
+                .send_to_server(ServerInstruction::LocalEchoState {
+                    client_id,
+                    predictable,
+                    first_column,
+                    last_column,
+                })
+                .non_fatal();
+        }
+    }
+    pub fn clear_local_echo_state(&mut self, client_id: ClientId) {
+        // the client missed what we sent it while it was disconnected
+        self.local_echo_states.remove(&client_id);
+    }
     pub(crate) fn get_tiled_panes(&self) -> impl Iterator<Item = (&PaneId, &Box<dyn Pane>)> {
         self.tiled_panes.get_panes()
     }
@@ -3431,6 +3682,34 @@ impl Tab {
         }
         Ok(())
     }
+    pub fn equalize_panes(&mut self, client_id: ClientId, direction: Option<SplitDirection>) {
+        if self.tiled_panes.fullscreen_is_active() {
+            return;
+        }
+        self.tiled_panes.equalize_panes(direction, client_id);
+        self.swap_layouts.set_is_tiled_damaged();
+    }
+    pub fn set_tiling_strategy(&mut self, tiling_strategy: Option<TilingStrategy>) {
+        if self.tiled_panes.fullscreen_is_active() {
+            self.tiled_panes.unset_fullscreen();
+        }
+        self.tiled_panes.set_tiling_strategy(tiling_strategy);
+        self.retile_tiled_panes();
+    }
+    pub fn tiling_strategy(&self) -> Option<TilingStrategy> {
+        self.tiled_panes.tiling_strategy()
+    }
+    fn retile_tiled_panes(&mut self) -> bool {
+        // true => the tiled panes are arranged by a tiling strategy rather than the swap layouts
+        if self.tiled_panes.tiling_strategy().is_none() {
+            return false;
+        }
+        if self.tiled_panes.retile() {
+            self.swap_layouts.set_is_tiled_damaged();
+            self.set_should_clear_display_before_rendering();
+        }
+        true
+    }
     fn set_pane_active_at(&mut self, pane_id: PaneId) {
         if let Some(pane) = self.tiled_panes.get_pane_mut(pane_id) {
             pane.set_active_at(Instant::now());
@@ -3836,6 +4115,7 @@ impl Tab {
         ignore_suppressed_panes: bool,
         exit_status: Option<i32>,
     ) {
+        self.restore_floating_panes_hidden_by_scratchpad(id);
         // we need to ignore suppressed panes when we toggle a pane to be floating/embedded(tiled)
         // this is because in that case, while we do use this logic, we're not actually closing the
         // pane, we're moving it
@@ -3875,7 +4155,9 @@ impl Tab {
             let closed_pane = self.tiled_panes.remove_pane(id);
             self.set_force_render();
             self.tiled_panes.set_force_render();
-            if self.auto_layout && !self.swap_layouts.is_tiled_damaged() {
+            // a tiling strategy takes the place of the swap layouts
+            let is_tiled_by_strategy = self.retile_tiled_panes();
+            if !is_tiled_by_strategy && self.auto_layout && !self.swap_layouts.is_tiled_damaged() {
                 self.swap_layouts.set_is_tiled_damaged();
                 // only relayout if the user is already "in" a layout, otherwise this might be
                 // confusing
@@ -3883,6 +4165,9 @@ impl Tab {
             }
             closed_pane
         };
+        if let (PaneId::Terminal(terminal_id), Some(closed_pane)) = (id, closed_pane.as_ref()) {
+            self.add_to_closed_panes(terminal_id, closed_pane.as_ref(), exit_status);
+        }
         if let Some(exit_status) = exit_status {
             if let Some(mut closed_pane) = closed_pane {
                 // in case we need to update on Drop
@@ -3900,6 +4185,25 @@ impl Tab {
                 pane_id: id.into(),
             });
     }
+    fn add_to_closed_panes(&self, terminal_id: u32, pane: &dyn Pane, exit_status: Option<i32>) {
+        let run_command = match pane.invoked_with() {
+            Some(Run::Command(run_command)) => Some(run_command.clone()),
+            _ => None,
+        };
+        let closed_pane = ClosedPane {
+            title: pane.current_title(),
+            run_command,
+            exit_status: exit_status.or_else(|| pane.exit_status()),
+            serialized_scrollback: pane
+                .serialize(Some(MAX_CLOSED_PANE_SCROLLBACK_LINES))
+                .unwrap_or_default(),
+            ..Default::default()
+        };
+        // pty fills in the cwd, it still knows the pane's process at this point
+        let _ = self
+            .senders
+            .send_to_pty(PtyInstruction::AddClosedPane(terminal_id, closed_pane));
+    }
     pub fn extract_pane(
         &mut self,
         id: PaneId,
@@ -3953,7 +4257,9 @@ impl Tab {
             let mut closed_pane = self.tiled_panes.remove_pane(id);
             self.set_force_render();
             self.tiled_panes.set_force_render();
-            if self.auto_layout && !self.swap_layouts.is_tiled_damaged() {
+            // a tiling strategy takes the place of the swap layouts
+            let is_tiled_by_strategy = self.retile_tiled_panes();
+            if !is_tiled_by_strategy && self.auto_layout && !self.swap_layouts.is_tiled_damaged() {
                 self.swap_layouts.set_is_tiled_damaged();
                 // only relayout if the user is already "in" a layout, otherwise this might be
                 // confusing
@@ -4569,6 +4875,19 @@ impl Tab {
         MouseHandler::handle_mouse_event(self, event, client_id)
     }
 
+    /// The mouse regions of this tab, only rebuilt if its panes changed since the last call
+    pub fn mouse_region_map(&mut self) -> Arc<MouseRegionMap> {
+        let key = MouseHandler::mouse_region_key(self);
+        match &self.mouse_region_map {
+            Some((cached_key, mouse_region_map)) if *cached_key == key => mouse_region_map.clone(),
+            _ => {
+                let mouse_region_map = Arc::new(MouseHandler::mouse_region_map(self));
+                self.mouse_region_map = Some((key, mouse_region_map.clone()));
+                mouse_region_map
+            },
+        }
+    }
+
     pub fn copy_selection(&self, client_id: ClientId) -> Result<()> {
         let selected_text = self
             .get_active_pane(client_id)
@@ -5157,6 +5476,103 @@ impl Tab {
             self.insert_suppressed_pane(pane_id, (false, pane));
         }
     }
+    pub fn scratchpad_is_visible(&self, pane_id: PaneId) -> bool {
+        self.tiled_panes.panes_contain(&pane_id)
+            || (self.floating_panes.panes_contain(&pane_id) && self.are_floating_panes_visible())
+    }
+    pub fn show_scratchpad(
+        &mut self,
+        pane_id: PaneId,
+        floating_pane_coordinates: Option<FloatingPaneCoordinates>,
+        client_id: ClientId,
+    ) -> Result<()> {
+        match self.extract_suppressed_scratchpad(pane_id) {
+            Some(pane) => self.add_scratchpad_pane(pane, floating_pane_coordinates),
+            None if self.floating_panes.panes_contain(&pane_id) => {
+                // the scratchpad was hidden along with the rest of the floating panes
+                self.show_only_floating_scratchpad(pane_id);
+                self.floating_panes.focus_pane(pane_id, client_id);
+                Ok(())
+            },
+            None => {
+                let should_float_if_hidden = true;
+                let should_be_in_place = false;
+                self.focus_pane_with_id(
+                    pane_id,
+                    should_float_if_hidden,
+                    should_be_in_place,
+                    client_id,
+                )
+            },
+        }
+    }
+    pub fn hide_scratchpad(&mut self, pane_id: PaneId, client_id: ClientId) {
+        self.suppress_pane(pane_id, Some(client_id));
+        self.restore_floating_panes_hidden_by_scratchpad(pane_id);
+    }
+    pub fn extract_scratchpad(&mut self, pane_id: PaneId) -> Option<Box<dyn Pane>> {
+        self.restore_floating_panes_hidden_by_scratchpad(pane_id);
+        self.extract_suppressed_scratchpad(pane_id).or_else(|| {
+            let dont_swap_if_suppressed = true;
+            self.extract_pane(pane_id, dont_swap_if_suppressed)
+        })
+    }
+    fn extract_suppressed_scratchpad(&mut self, pane_id: PaneId) -> Option<Box<dyn Pane>> {
+        // a hidden scratchpad is suppressed with its own id, see suppress_pane
+        self.suppressed_panes
+            .extract_if(|_key, (_, pane)| pane.pid() == pane_id)
+            .next()
+            .map(|(_key, (_, pane))| pane)
+    }
+    pub fn add_scratchpad_pane(
+        &mut self,
+        pane: Box<dyn Pane>,
+        floating_pane_coordinates: Option<FloatingPaneCoordinates>,
+    ) -> Result<()> {
+        let pane_id = pane.pid();
+        let should_focus_pane = true;
+        self.show_only_floating_scratchpad(pane_id);
+        self.add_floating_pane(pane, pane_id, floating_pane_coordinates, should_focus_pane)
+    }
+    /// Shows the floating panes without revealing the ones that were hidden, which are suppressed
+    /// until the scratchpad is hidden again
+    fn show_only_floating_scratchpad(&mut self, scratchpad_pane_id: PaneId) {
+        if self.are_floating_panes_visible() {
+            return;
+        }
+        let hidden_pane_ids: Vec<PaneId> = self
+            .floating_panes
+            .pane_ids()
+            .filter(|pane_id| **pane_id != scratchpad_pane_id)
+            .copied()
+            .collect();
+        for pane_id in &hidden_pane_ids {
+            self.suppress_pane(*pane_id, None);
+        }
+        self.floating_panes_hidden_by_scratchpads
+            .entry(scratchpad_pane_id)
+            .or_default()
+            .extend(hidden_pane_ids);
+        self.show_floating_panes();
+    }
+    fn restore_floating_panes_hidden_by_scratchpad(&mut self, scratchpad_pane_id: PaneId) {
+        let Some(hidden_pane_ids) = self
+            .floating_panes_hidden_by_scratchpads
+            .remove(&scratchpad_pane_id)
+        else {
+            return;
+        };
+        if self.are_floating_panes_visible() {
+            self.hide_floating_panes();
+        }
+        for pane_id in hidden_pane_ids {
+            // panes that were closed in the meantime are no longer here
+            if let Some(pane) = self.extract_suppressed_scratchpad(pane_id) {
+                self.floating_panes.add_pane(pane_id, pane);
+            }
+        }
+        self.set_force_render();
+    }
     pub fn unsuppress_pane(&mut self, pane_id: PaneId, should_float_if_hidden: bool) {
         // removes a pane from being suppressed (hidden) but does not focus it
         match self
@@ -5324,8 +5740,11 @@ impl Tab {
         if self.tiled_panes.fullscreen_is_active() {
             self.tiled_panes.unset_fullscreen();
         }
-        let should_auto_layout =
-            self.auto_layout && !self.swap_layouts.is_tiled_damaged() && !without_relayout;
+        let should_retile = self.tiled_panes.tiling_strategy().is_some() && !without_relayout;
+        let should_auto_layout = self.auto_layout
+            && !self.swap_layouts.is_tiled_damaged()
+            && !without_relayout
+            && !should_retile;
         if self.tiled_panes.has_room_for_new_pane() {
             pane.set_active_at(Instant::now());
             if should_auto_layout {
@@ -5341,7 +5760,9 @@ impl Tab {
                 self.tiled_panes.focus_pane(pane_id, client_id);
             }
         }
-        if should_auto_layout {
+        if should_retile {
+            self.retile_tiled_panes();
+        } else if should_auto_layout {
             // only do this if we're already in this layout, otherwise it might be
             // confusing and not what the user intends
             self.swap_layouts.set_is_tiled_damaged(); // we do this so that we won't skip to the
@@ -5988,6 +6409,50 @@ impl Tab {
             self.set_force_render();
         }
     }
+    pub fn toggle_pane_input_lock(
+        &mut self,
+        client_id: ClientId,
+        input_lock: PaneInputLock,
+    ) -> Result<()> {
+        let active_pane_id = self
+            .get_active_pane_id(client_id)
+            .ok_or_else(|| anyhow!("no active pane for client {client_id}"))?;
+        self.toggle_pane_input_lock_by_pane_id(active_pane_id, input_lock)
+    }
+    pub fn toggle_pane_input_lock_by_pane_id(
+        &mut self,
+        pane_id: PaneId,
+        input_lock: PaneInputLock,
+    ) -> Result<()> {
+        let input_lock = match self.pane_input_lock(pane_id) {
+            Some(_) => None,
+            None => Some(input_lock),
+        };
+        self.set_pane_input_lock(pane_id, input_lock)
+    }
+    pub fn set_pane_input_lock(
+        &mut self,
+        pane_id: PaneId,
+        input_lock: Option<PaneInputLock>,
+    ) -> Result<()> {
+        if let PaneId::Plugin(_) = pane_id {
+            return Err(anyhow!(
+                "only terminal panes can have their input locked, {pane_id:?} is a plugin pane"
+            ));
+        }
+        let pane = self
+            .get_pane_with_id_mut(pane_id)
+            .ok_or_else(|| anyhow!("failed to find pane with id {pane_id:?}"))?;
+        pane.set_input_lock(input_lock);
+        pane.set_awaiting_input_confirmation(false);
+        self.held_input_for_locked_panes.remove(&pane_id);
+        self.set_force_render();
+        Ok(())
+    }
+    pub fn pane_input_lock(&self, pane_id: PaneId) -> Option<PaneInputLock> {
+        self.get_pane_with_id(pane_id)
+            .and_then(|pane| pane.input_lock())
+    }
 }
 
 pub fn pane_info_for_pane(
@@ -6010,6 +6475,7 @@ pub fn pane_info_for_pane(
     pane_info.exited = pane.exited();
     pane_info.exit_status = pane.exit_status();
     pane_info.is_held = pane.is_held();
+    pane_info.input_lock = pane.input_lock();
     let index_in_pane_group: BTreeMap<ClientId, usize> = current_pane_group
         .iter()
         .filter_map(|(client_id, pane_ids)| {
@@ -1,12 +1,14 @@
+use std::collections::BTreeSet;
 use std::time::Instant;
 use zellij_utils::data::{Direction, Resize, ResizeStrategy};
 use zellij_utils::errors::prelude::*;
 use zellij_utils::input::mouse::{MouseEvent, MouseEventType};
-use zellij_utils::pane_size::PaneGeom;
+use zellij_utils::input::mousebinds::MouseRegion;
+use zellij_utils::pane_size::{PaneGeom, Size, Viewport};
 use zellij_utils::position::Position;
 
 use crate::background_jobs::BackgroundJob;
-use crate::panes::PaneId;
+use crate::panes::{is_inside_viewport, PaneId};
 use crate::plugins::PluginInstruction;
 use crate::ClientId;
 
@@ -140,6 +142,16 @@ enum MouseAction {
     StopMovingFloatingPane {
         position: Position,
     },
+    StartMovingTiledPane {
+        pane_id: PaneId,
+    },
+    ContinueMovingTiledPane {
+        position: Position,
+    },
+    StopMovingTiledPane {
+        position: Position,
+    },
+    CancelMovingTiledPane,
     ScrollUp {
         pane_id: PaneId,
         lines: usize,
@@ -197,6 +209,7 @@ pub struct PaneResizeState {
 struct ClickedPaneDetails {
     pane_id: PaneId,
     on_frame: bool,
+    on_frame_title: bool,
     frame_intercepted: bool,
     edge: Option<PaneEdge>,
     is_floating: bool,
@@ -211,6 +224,7 @@ struct MouseEventContext {
     pane_being_resized: bool,
     selecting_with_mouse: bool,
     pane_being_moved: bool,
+    tiled_pane_being_moved: bool,
     clicked_pane: Option<ClickedPaneDetails>,
     pinned_selectable: Option<PaneId>,
     pinned_unselectable: Option<PaneId>,
@@ -334,6 +348,36 @@ fn edge_and_delta_to_strategies(
     }
 }
 
+/// The region of each position on the screen, so that mousebinds can be matched from outside of
+/// the screen thread
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct MouseRegionMap {
+    // for each line, the columns at which the region changes and the region from there on
+    lines: Vec<Vec<(usize, Option<MouseRegion>)>>,
+}
+
+impl MouseRegionMap {
+    pub fn region_at(&self, position: &Position) -> Option<MouseRegion> {
+        let line = usize::try_from(position.line()).ok()?;
+        let segments = self.lines.get(line)?;
+        let segment_index = segments.partition_point(|(start, _)| *start <= position.column());
+        segments.get(segment_index.checked_sub(1)?)?.1
+    }
+}
+
+// id, outer and content geometry, selectable, pinned and z-index of a pane
+type PaneRegionKey = (PaneId, [usize; 8], bool, bool, Option<usize>);
+
+/// Everything the regions of a tab depend on, the region map is only rebuilt when this changes
+#[derive(Debug, Default, Clone, PartialEq)]
+pub(crate) struct MouseRegionKey {
+    panes: Vec<PaneRegionKey>,
+    floating_panes_visible: bool,
+    fullscreen_pane: Option<PaneId>,
+    viewport: Viewport,
+    display_area: Size,
+}
+
 pub struct MouseHandler;
 
 impl MouseHandler {
@@ -347,6 +391,95 @@ impl MouseHandler {
         Self::execute_mouse_action(tab, action, event, client_id)
     }
 
+    pub(crate) fn mouse_region_at(tab: &mut Tab, position: &Position) -> Option<MouseRegion> {
+        let viewport = *tab.viewport.borrow();
+        let pane = Self::get_pane_at(tab, position, false).ok()??;
+        // bars are the non-selectable plugin panes the layout places outside of the viewport,
+        // whichever plugin they happen to run
+        let is_bar = matches!(pane.pid(), PaneId::Plugin(_))
+            && !pane.selectable()
+            && !is_inside_viewport(&viewport, pane);
+        if is_bar {
+            Some(MouseRegion::TabBar)
+        } else if pane.position_is_on_frame(position) {
+            Some(MouseRegion::PaneFrame)
+        } else {
+            Some(MouseRegion::PaneBody)
+        }
+    }
+
+    pub(crate) fn mouse_region_key(tab: &Tab) -> MouseRegionKey {
+        let panes = tab
+            .tiled_panes
+            .get_panes()
+            .chain(tab.floating_panes.get_panes())
+            .map(|(pane_id, pane)| {
+                (
+                    *pane_id,
+                    [
+                        pane.x(),
+                        pane.y(),
+                        pane.cols(),
+                        pane.rows(),
+                        pane.get_content_x(),
+                        pane.get_content_y(),
+                        pane.get_content_columns(),
+                        pane.get_content_rows(),
+                    ],
+                    pane.selectable(),
+                    pane.current_geom().is_pinned,
+                    tab.floating_panes.get_pane_z_index(*pane_id),
+                )
+            })
+            .collect();
+        let fullscreen_pane = if tab.tiled_panes.fullscreen_is_active() {
+            let first_client_id = tab.connected_clients.borrow().iter().copied().next();
+            first_client_id.and_then(|c| tab.tiled_panes.get_active_pane_id(c))
+        } else {
+            None
+        };
+        MouseRegionKey {
+            panes,
+            floating_panes_visible: tab.floating_panes.panes_are_visible(),
+            fullscreen_pane,
+            viewport: *tab.viewport.borrow(),
+            display_area: *tab.display_area.borrow(),
+        }
+    }
+
+    pub(crate) fn mouse_region_map(tab: &mut Tab) -> MouseRegionMap {
+        // the region can only change at the edge of a pane or of its content, so it's enough to
+        // look up a single position between each two consecutive edges
+        let display_area = *tab.display_area.borrow();
+        let mut edges = BTreeSet::from([0]);
+        for (_, pane) in tab
+            .tiled_panes
+            .get_panes()
+            .chain(tab.floating_panes.get_panes())
+        {
+            edges.insert(pane.x());
+            edges.insert(pane.get_content_x());
+            edges.insert(pane.get_content_x() + pane.get_content_columns());
+            edges.insert(pane.x() + pane.cols());
+        }
+        let edges: Vec<usize> = edges
+            .into_iter()
+            .filter(|edge| *edge < display_area.cols)
+            .collect();
+        let mut lines = Vec::with_capacity(display_area.rows);
+        for line in 0..display_area.rows {
+            let mut segments: Vec<(usize, Option<MouseRegion>)> = vec![];
+            for edge in &edges {
+                let region = Self::mouse_region_at(tab, &Position::new(line as i32, *edge as u16));
+                if segments.last().map(|(_, r)| *r) != Some(region) {
+                    segments.push((*edge, region));
+                }
+            }
+            lines.push(segments);
+        }
+        MouseRegionMap { lines }
+    }
+
     fn gather_mouse_event_context(
         tab: &mut Tab,
         event: &MouseEvent,
@@ -394,6 +527,7 @@ impl MouseHandler {
             pane_being_resized: tab.pane_being_resized_with_mouse.is_some(),
             selecting_with_mouse: tab.selecting_with_mouse_in_pane.is_some(),
             pane_being_moved: tab.floating_panes.pane_is_being_moved_with_mouse(),
+            tiled_pane_being_moved: tab.tiled_panes.pane_is_being_moved_with_mouse(client_id),
             clicked_pane,
             pinned_selectable,
             pinned_unselectable,
@@ -414,6 +548,11 @@ impl MouseHandler {
         let pane = Self::get_pane_at(tab, position, false).ok()??;
 
         let on_frame = pane.position_is_on_frame(position);
+        // stacked panes are moved around with their stack
+        let on_frame_title = on_frame
+            && !is_floating
+            && !pane.current_geom().is_stacked()
+            && pane.position_is_on_frame_title(position);
         let frame_intercepted = on_frame && pane.intercept_mouse_event_on_frame(event, client_id);
         let edge = if on_frame {
             pane.get_edge_at_position(position)
@@ -430,6 +569,7 @@ impl MouseHandler {
         Some(ClickedPaneDetails {
             pane_id,
             on_frame,
+            on_frame_title,
             frame_intercepted,
             edge,
             is_floating,
@@ -761,6 +901,36 @@ impl MouseHandler {
             MouseAction::StopMovingFloatingPane { position } => {
                 Self::execute_stop_moving_floating_pane(tab, position, client_id)
             },
+            MouseAction::StartMovingTiledPane { pane_id } => {
+                clear_hover_for_client(tab, client_id);
+                if tab
+                    .tiled_panes
+                    .start_moving_pane_with_mouse(pane_id, client_id)
+                {
+                    Ok(MouseEffect::state_changed())
+                } else {
+                    Ok(MouseEffect::default())
+                }
+            },
+            MouseAction::ContinueMovingTiledPane { position } => {
+                if tab.tiled_panes.update_drop_target(&position, client_id) {
+                    tab.set_force_render();
+                    Ok(MouseEffect::state_changed())
+                } else {
+                    Ok(MouseEffect::default())
+                }
+            },
+            MouseAction::StopMovingTiledPane { position } => {
+                Self::execute_stop_moving_tiled_pane(tab, position, client_id)
+            },
+            MouseAction::CancelMovingTiledPane => {
+                if tab.tiled_panes.cancel_moving_pane_with_mouse(client_id) {
+                    tab.set_force_render();
+                    Ok(MouseEffect::state_changed())
+                } else {
+                    Ok(MouseEffect::default())
+                }
+            },
             MouseAction::ScrollUp { pane_id: _, lines } => {
                 Self::handle_scrollwheel_up(tab, &event.position, lines, client_id)
                     .with_context(err_context)
@@ -1011,6 +1181,31 @@ impl MouseHandler {
         Ok(MouseEffect::default())
     }
 
+    fn execute_stop_moving_tiled_pane(
+        tab: &mut Tab,
+        position: Position,
+        client_id: ClientId,
+    ) -> Result<MouseEffect> {
+        let err_context = || "failed to stop moving tiled pane";
+        let moved = tab
+            .tiled_panes
+            .stop_moving_pane_with_mouse(&position, client_id);
+        if moved {
+            tab.swap_layouts.set_is_tiled_damaged();
+        } else {
+            // the pane was not dropped anywhere, so treat this as a click on whatever is under it
+            let active_pane_id = tab.get_active_pane_id(client_id);
+            let pane_id_at_position = Self::get_pane_at(tab, &position, false)
+                .with_context(err_context)?
+                .map(|p| p.pid());
+            if pane_id_at_position.is_some() && pane_id_at_position != active_pane_id {
+                Self::focus_pane_at(tab, &position, client_id).with_context(err_context)?;
+            }
+        }
+        tab.set_force_render();
+        Ok(MouseEffect::state_changed())
+    }
+
     fn execute_focus_on_hover(
         tab: &mut Tab,
         pane_id: PaneId,
@@ -1223,6 +1418,20 @@ impl MouseHandler {
             });
         }
 
+        if ctx.tiled_pane_being_moved {
+            return Ok(match event.event_type {
+                MouseEventType::Motion if event.left => MouseAction::ContinueMovingTiledPane {
+                    position: event.position,
+                },
+                MouseEventType::Release => MouseAction::StopMovingTiledPane {
+                    position: event.position,
+                },
+                // anything else is not part of the drag (eg. a press of another button or a
+                // buttonless motion after a release we missed), so the pane stays where it was
+                _ => MouseAction::CancelMovingTiledPane,
+            });
+        }
+
         if event.alt {
             let is_left_press = event.left && event.event_type == MouseEventType::Press;
             let is_left_motion = event.left && event.event_type == MouseEventType::Motion;
@@ -1314,6 +1523,12 @@ impl MouseHandler {
                     });
                 }
 
+                if details.on_frame_title {
+                    return Ok(MouseAction::StartMovingTiledPane {
+                        pane_id: details.pane_id,
+                    });
+                }
+
                 if let Some(edge) = details.edge {
                     return Ok(MouseAction::StartResize {
                         pane_id: details.pane_id,
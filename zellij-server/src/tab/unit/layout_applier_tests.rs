@@ -1,128 +1,27 @@
-use crate::os_input_output::AsyncReader;
 use crate::panes::sixel::SixelImageStore;
 use crate::panes::{FloatingPanes, TiledPanes};
 use crate::panes::{LinkHandler, PaneId};
 use crate::plugins::PluginInstruction;
 use crate::pty::PtyInstruction;
 use crate::tab::layout_applier::LayoutApplier;
-use crate::{os_input_output::ServerOsApi, thread_bus::ThreadSenders, ClientId};
+use crate::{
+    os_input_output::ServerOsApi, os_input_output_fake::FakeInputOutput, thread_bus::ThreadSenders,
+    ClientId,
+};
 use insta::assert_snapshot;
 use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
 use std::fmt::Write;
-use std::path::PathBuf;
 use std::rc::Rc;
 
-use interprocess::local_socket::Stream as LocalSocketStream;
 use zellij_utils::{
     channels::{self, ChannelWithContext, Receiver, SenderWithContext},
     data::{ModeInfo, Palette, Style},
-    errors::prelude::*,
-    input::command::{RunCommand, TerminalAction},
     input::layout::RunPluginOrAlias,
     input::layout::{FloatingPaneLayout, Layout, Run, TiledPaneLayout},
-    ipc::{ClientToServerMsg, IpcReceiverWithContext, ServerToClientMsg},
     pane_size::{Size, SizeInPixels, Viewport},
 };
 
-#[derive(Clone)]
-struct FakeInputOutput {}
-
-impl ServerOsApi for FakeInputOutput {
-    fn set_terminal_size_using_terminal_id(
-        &self,
-        _id: u32,
-        _cols: u16,
-        _rows: u16,
-        _width_in_pixels: Option<u16>,
-        _height_in_pixels: Option<u16>,
-    ) -> Result<()> {
-        Ok(())
-    }
-
-    fn spawn_terminal(
-        &self,
-        _file_to_open: TerminalAction,
-        _quit_cb: Box<dyn Fn(PaneId, Option<i32>, RunCommand) + Send>,
-        _default_editor: Option<PathBuf>,
-    ) -> Result<(u32, Box<dyn AsyncReader>, Option<u32>)> {
-        unimplemented!()
-    }
-
-    fn write_to_tty_stdin(&self, _id: u32, _buf: &[u8]) -> Result<usize> {
-        unimplemented!()
-    }
-
-    fn tcdrain(&self, _id: u32) -> Result<()> {
-        unimplemented!()
-    }
-
-    fn kill(&self, _pid: u32) -> Result<()> {
-        unimplemented!()
-    }
-
-    fn force_kill(&self, _pid: u32) -> Result<()> {
-        unimplemented!()
-    }
-
-    fn box_clone(&self) -> Box<dyn ServerOsApi> {
-        Box::new((*self).clone())
-    }
-
-    fn send_to_client(&self, _client_id: ClientId, _msg: ServerToClientMsg) -> Result<()> {
-        unimplemented!()
-    }
-
-    fn new_client(
-        &mut self,
-        _client_id: ClientId,
-        _stream: LocalSocketStream,
-    ) -> Result<IpcReceiverWithContext<ClientToServerMsg>> {
-        unimplemented!()
-    }
-    fn new_client_with_reply(
-        &mut self,
-        _client_id: ClientId,
-        _stream: LocalSocketStream,
-        _reply_stream: LocalSocketStream,
-    ) -> Result<IpcReceiverWithContext<ClientToServerMsg>> {
-        unimplemented!()
-    }
-
-    fn remove_client(&mut self, _client_id: ClientId) -> Result<()> {
-        unimplemented!()
-    }
-
-    fn load_palette(&self) -> Palette {
-        unimplemented!()
-    }
-
-    fn get_cwd(&self, _pid: u32) -> Option<PathBuf> {
-        unimplemented!()
-    }
-
-    fn write_to_file(&mut self, _buf: String, _name: Option<String>) -> Result<()> {
-        unimplemented!()
-    }
-
-    fn re_run_command_in_terminal(
-        &self,
-        _terminal_id: u32,
-        _run_command: RunCommand,
-        _quit_cb: Box<dyn Fn(PaneId, Option<i32>, RunCommand) + Send>,
-    ) -> Result<(Box<dyn AsyncReader>, Option<u32>)> {
-        unimplemented!()
-    }
-
-    fn clear_terminal_id(&self, _terminal_id: u32) -> Result<()> {
-        unimplemented!()
-    }
-
-    fn send_sigint(&self, _pid: u32) -> Result<()> {
-        unimplemented!()
-    }
-}
-
 /// Parse KDL layout string and extract tiled and floating layouts
 fn parse_kdl_layout(kdl_str: &str) -> (TiledPaneLayout, Vec<FloatingPaneLayout>) {
     let layout = Layout::from_kdl(kdl_str, Some("test_layout".into()), None, None)
@@ -180,7 +79,7 @@ fn create_layout_applier_fixtures(
     let style = Style::default();
     let display_area = Rc::new(RefCell::new(size));
 
-    let os_api = Box::new(FakeInputOutput {});
+    let os_api = Box::new(FakeInputOutput::default());
 
     // Create TiledPanes
     let connected_clients_set = Rc::new(RefCell::new(HashSet::from([client_id])));
@@ -310,7 +209,7 @@ fn create_layout_applier_fixtures_with_receivers(
     let style = Style::default();
     let display_area = Rc::new(RefCell::new(size));
 
-    let os_api = Box::new(FakeInputOutput {});
+    let os_api = Box::new(FakeInputOutput::default());
 
     // Create TiledPanes
     let connected_clients_set = Rc::new(RefCell::new(HashSet::from([client_id])));
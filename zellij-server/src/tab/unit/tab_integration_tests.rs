@@ -1,11 +1,15 @@
-use super::{Output, Tab};
+use super::{MouseHandler, Output, Tab};
 use crate::panes::sixel::SixelImageStore;
 use crate::screen::CopyOptions;
 use crate::Arc;
 
 use crate::{
-    os_input_output::ServerOsApi, pane_groups::PaneGroups, panes::PaneId,
-    plugins::PluginInstruction, thread_bus::ThreadSenders, ClientId, ServerInstruction,
+    os_input_output_fake::FakeInputOutput,
+    pane_groups::PaneGroups,
+    panes::{PaneId, TiledPaneDropPlacement, TiledPaneDropTarget},
+    plugins::PluginInstruction,
+    thread_bus::ThreadSenders,
+    ServerInstruction,
 };
 use std::net::{IpAddr, Ipv4Addr};
 use std::path::PathBuf;
@@ -13,133 +17,32 @@ use std::sync::Mutex;
 
 use zellij_utils::channels::Receiver;
 use zellij_utils::data::Direction;
+use zellij_utils::data::PaneInputLock;
 use zellij_utils::data::Resize;
 use zellij_utils::data::ResizeStrategy;
 use zellij_utils::data::WebSharing;
 use zellij_utils::envs::set_session_name;
-use zellij_utils::errors::{prelude::*, ErrorContext};
+use zellij_utils::errors::ErrorContext;
 use zellij_utils::input::layout::{
     FloatingPaneLayout, Layout, PercentOrFixed, RunPluginOrAlias, SwapFloatingLayout,
     SwapTiledLayout, TiledPaneLayout,
 };
 use zellij_utils::input::mouse::MouseEvent;
-use zellij_utils::ipc::IpcReceiverWithContext;
 use zellij_utils::pane_size::{Size, SizeInPixels};
 use zellij_utils::position::Position;
 
 use crate::pty_writer::PtyWriteInstruction;
 use zellij_utils::channels::{self, ChannelWithContext, SenderWithContext};
 
-use crate::os_input_output::AsyncReader;
 use std::cell::RefCell;
 use std::collections::{BTreeMap, HashMap};
 use std::rc::Rc;
 
-use interprocess::local_socket::Stream as LocalSocketStream;
 use zellij_utils::{
     data::{FloatingPaneCoordinates, InputMode, ModeInfo, NewPanePlacement, Palette, Style},
-    input::command::{RunCommand, TerminalAction},
-    ipc::{ClientToServerMsg, ServerToClientMsg},
+    input::command::RunCommand,
 };
 
-#[derive(Clone, Default)]
-struct FakeInputOutput {
-    file_dumps: Arc<Mutex<HashMap<String, String>>>,
-    pub tty_stdin_bytes: Arc<Mutex<BTreeMap<u32, Vec<u8>>>>,
-}
-
-impl ServerOsApi for FakeInputOutput {
-    fn set_terminal_size_using_terminal_id(
-        &self,
-        _id: u32,
-        _cols: u16,
-        _rows: u16,
-        _width_in_pixels: Option<u16>,
-        _height_in_pixels: Option<u16>,
-    ) -> Result<()> {
-        // noop
-        Ok(())
-    }
-    fn spawn_terminal(
-        &self,
-        _file_to_open: TerminalAction,
-        _quit_db: Box<dyn Fn(PaneId, Option<i32>, RunCommand) + Send>,
-        _default_editor: Option<PathBuf>,
-    ) -> Result<(u32, Box<dyn AsyncReader>, Option<u32>)> {
-        unimplemented!()
-    }
-    fn write_to_tty_stdin(&self, id: u32, buf: &[u8]) -> Result<usize> {
-        self.tty_stdin_bytes
-            .lock()
-            .unwrap()
-            .entry(id)
-            .or_insert_with(|| vec![])
-            .extend_from_slice(buf);
-        Ok(buf.len())
-    }
-    fn tcdrain(&self, _id: u32) -> Result<()> {
-        unimplemented!()
-    }
-    fn kill(&self, _pid: u32) -> Result<()> {
-        unimplemented!()
-    }
-    fn force_kill(&self, _pid: u32) -> Result<()> {
-        unimplemented!()
-    }
-    fn box_clone(&self) -> Box<dyn ServerOsApi> {
-        Box::new((*self).clone())
-    }
-    fn send_to_client(&self, _client_id: ClientId, _msg: ServerToClientMsg) -> Result<()> {
-        unimplemented!()
-    }
-    fn new_client(
-        &mut self,
-        _client_id: ClientId,
-        _stream: LocalSocketStream,
-    ) -> Result<IpcReceiverWithContext<ClientToServerMsg>> {
-        unimplemented!()
-    }
-    fn new_client_with_reply(
-        &mut self,
-        _client_id: ClientId,
-        _stream: LocalSocketStream,
-        _reply_stream: LocalSocketStream,
-    ) -> Result<IpcReceiverWithContext<ClientToServerMsg>> {
-        unimplemented!()
-    }
-    fn remove_client(&mut self, _client_id: ClientId) -> Result<()> {
-        unimplemented!()
-    }
-    fn load_palette(&self) -> Palette {
-        unimplemented!()
-    }
-    fn get_cwd(&self, _pid: u32) -> Option<PathBuf> {
-        unimplemented!()
-    }
-    fn write_to_file(&mut self, buf: String, name: Option<String>) -> Result<()> {
-        let f: String = match name {
-            Some(x) => x,
-            None => "tmp-name".to_owned(),
-        };
-        self.file_dumps.lock().to_anyhow()?.insert(f, buf);
-        Ok(())
-    }
-    fn re_run_command_in_terminal(
-        &self,
-        _terminal_id: u32,
-        _run_command: RunCommand,
-        _quit_cb: Box<dyn Fn(PaneId, Option<i32>, RunCommand) + Send>,
-    ) -> Result<(Box<dyn AsyncReader>, Option<u32>)> {
-        unimplemented!()
-    }
-    fn clear_terminal_id(&self, _terminal_id: u32) -> Result<()> {
-        unimplemented!()
-    }
-    fn send_sigint(&self, _pid: u32) -> Result<()> {
-        unimplemented!()
-    }
-}
-
 struct MockPtyInstructionBus {
     output: Arc<Mutex<Vec<String>>>,
     pty_writer_sender: SenderWithContext<PtyWriteInstruction>,
@@ -1295,7 +1198,7 @@ fn dump_screen() {
     let mut tab = create_new_tab(size, ModeInfo::default());
     let map = Arc::new(Mutex::new(HashMap::new()));
     tab.os_api = Box::new(FakeInputOutput {
-        file_dumps: map.clone(),
+        fake_filesystem: map.clone(),
         ..Default::default()
     });
     let new_pane_id = PaneId::Terminal(2);
@@ -1332,7 +1235,7 @@ fn clear_screen() {
     let mut tab = create_new_tab(size, ModeInfo::default());
     let map = Arc::new(Mutex::new(HashMap::new()));
     tab.os_api = Box::new(FakeInputOutput {
-        file_dumps: map.clone(),
+        fake_filesystem: map.clone(),
         ..Default::default()
     });
     let new_pane_id = PaneId::Terminal(2);
@@ -14798,3 +14701,479 @@ fn osc99_query_flag_roundtrip() {
     assert!(!wants_report);
     assert!(!is_query);
 }
+
+#[test]
+fn mouse_region_distinguishes_pane_frame_and_body() {
+    use zellij_utils::input::mousebinds::MouseRegion;
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size, ModeInfo::default());
+    assert_eq!(
+        MouseHandler::mouse_region_at(&mut tab, &Position::new(0, 10)),
+        Some(MouseRegion::PaneFrame),
+        "Top frame of the pane"
+    );
+    assert_eq!(
+        MouseHandler::mouse_region_at(&mut tab, &Position::new(5, 10)),
+        Some(MouseRegion::PaneBody),
+        "Inside the pane"
+    );
+}
+
+#[test]
+fn mouse_region_detects_bars_regardless_of_their_location() {
+    use zellij_utils::input::mousebinds::MouseRegion;
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let base_layout = r#"
+        layout {
+            pane size=1 borderless=true {
+                plugin location="file:/path/to/my-bar.wasm"
+            }
+            pane
+        }
+    "#;
+    let (base_layout, base_floating_layout) =
+        Layout::from_kdl(base_layout, Some("file_name.kdl".into()), None, None)
+            .unwrap()
+            .template
+            .unwrap();
+    let mut new_plugin_ids = HashMap::new();
+    new_plugin_ids.insert(
+        RunPluginOrAlias::from_url("file:/path/to/my-bar.wasm", &None, None, None).unwrap(),
+        vec![1],
+    );
+    let mut tab = create_new_tab_with_swap_layouts(
+        size,
+        ModeInfo::default(),
+        (vec![], vec![]),
+        Some((
+            base_layout,
+            base_floating_layout,
+            vec![(1, None)],
+            vec![],
+            new_plugin_ids,
+        )),
+        true,
+        true,
+    );
+    // the plugin makes itself unselectable, as the tab-bar does
+    tab.set_pane_selectable(PaneId::Plugin(1), false);
+    assert_eq!(
+        MouseHandler::mouse_region_at(&mut tab, &Position::new(0, 10)),
+        Some(MouseRegion::TabBar),
+        "Non-selectable plugin pane outside of the viewport"
+    );
+    assert_eq!(
+        MouseHandler::mouse_region_at(&mut tab, &Position::new(5, 10)),
+        Some(MouseRegion::PaneBody),
+        "Inside the terminal pane"
+    );
+}
+
+#[test]
+fn mouse_region_map_matches_mouse_region_at_every_position() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mut tab = create_new_tab(size, ModeInfo::default());
+    tab.vertical_split(PaneId::Terminal(2), None, client_id, None, None)
+        .unwrap();
+    tab.toggle_floating_panes(Some(client_id), None, None)
+        .unwrap();
+    tab.new_pane(
+        PaneId::Terminal(3),
+        None,
+        None,
+        false,
+        true,
+        NewPanePlacement::default(),
+        Some(client_id),
+        None,
+    )
+    .unwrap();
+    let mouse_region_map = tab.mouse_region_map();
+    for line in 0..size.rows {
+        for column in 0..size.cols {
+            let position = Position::new(line as i32, column as u16);
+            assert_eq!(
+                mouse_region_map.region_at(&position),
+                MouseHandler::mouse_region_at(&mut tab, &position),
+                "Region at {:?}",
+                position
+            );
+        }
+    }
+}
+
+#[test]
+fn mouse_region_map_is_rebuilt_when_panes_move() {
+    use zellij_utils::input::mousebinds::MouseRegion;
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mut tab = create_new_tab(size, ModeInfo::default());
+    let middle_of_the_screen = Position::new(10, 60);
+    let first_mouse_region_map = tab.mouse_region_map();
+    assert!(
+        std::sync::Arc::ptr_eq(&first_mouse_region_map, &tab.mouse_region_map()),
+        "Region map is reused while nothing changed"
+    );
+    assert_eq!(
+        first_mouse_region_map.region_at(&middle_of_the_screen),
+        Some(MouseRegion::PaneBody)
+    );
+    tab.vertical_split(PaneId::Terminal(2), None, client_id, None, None)
+        .unwrap();
+    assert_eq!(
+        tab.mouse_region_map().region_at(&middle_of_the_screen),
+        Some(MouseRegion::PaneFrame),
+        "The split put a frame in the middle of the screen"
+    );
+}
+
+#[test]
+fn drag_tiled_pane_by_frame_title_onto_another_pane_to_swap() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mut tab = create_new_tab(size, ModeInfo::default());
+    tab.vertical_split(PaneId::Terminal(2), None, client_id, None, None)
+        .unwrap();
+    let left_pane_x = tab.tiled_panes.get_pane(PaneId::Terminal(1)).unwrap().x();
+    let right_pane_x = tab.tiled_panes.get_pane(PaneId::Terminal(2)).unwrap().x();
+
+    // press on the title of the left pane, drag it to the middle of the right pane and drop it
+    tab.handle_mouse_event(
+        &MouseEvent::new_left_press_event(Position::new(0, 3)),
+        client_id,
+    )
+    .unwrap();
+    tab.handle_mouse_event(
+        &MouseEvent::new_left_motion_event(Position::new(10, 90)),
+        client_id,
+    )
+    .unwrap();
+    assert_eq!(
+        tab.tiled_panes.drop_target(client_id),
+        Some(TiledPaneDropTarget {
+            pane_id: PaneId::Terminal(2),
+            placement: TiledPaneDropPlacement::Swap,
+        }),
+        "Drop target previewed while dragging"
+    );
+    tab.handle_mouse_event(
+        &MouseEvent::new_left_release_event(Position::new(10, 90)),
+        client_id,
+    )
+    .unwrap();
+
+    assert_eq!(
+        tab.tiled_panes.get_pane(PaneId::Terminal(1)).unwrap().x(),
+        right_pane_x,
+        "Dragged pane took the place of the pane it was dropped on"
+    );
+    assert_eq!(
+        tab.tiled_panes.get_pane(PaneId::Terminal(2)).unwrap().x(),
+        left_pane_x,
+        "Pane it was dropped on took the place of the dragged pane"
+    );
+    assert_eq!(tab.get_active_pane_id(client_id), Some(PaneId::Terminal(1)));
+    assert!(!tab.tiled_panes.pane_is_being_moved_with_mouse(client_id));
+}
+
+#[test]
+fn drag_tiled_pane_by_frame_title_onto_edge_of_another_pane_to_split_it() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mut tab = create_new_tab(size, ModeInfo::default());
+    tab.vertical_split(PaneId::Terminal(2), None, client_id, None, None)
+        .unwrap();
+
+    // drop the left pane on the bottom edge of the right pane
+    tab.handle_mouse_event(
+        &MouseEvent::new_left_press_event(Position::new(0, 3)),
+        client_id,
+    )
+    .unwrap();
+    tab.handle_mouse_event(
+        &MouseEvent::new_left_motion_event(Position::new(18, 90)),
+        client_id,
+    )
+    .unwrap();
+    tab.handle_mouse_event(
+        &MouseEvent::new_left_release_event(Position::new(18, 90)),
+        client_id,
+    )
+    .unwrap();
+
+    let dragged_pane = tab.tiled_panes.get_pane(PaneId::Terminal(1)).unwrap();
+    let target_pane = tab.tiled_panes.get_pane(PaneId::Terminal(2)).unwrap();
+    assert_eq!(target_pane.y(), 0, "Target pane is on top");
+    assert_eq!(
+        dragged_pane.y(),
+        target_pane.y() + target_pane.rows(),
+        "Dragged pane is below the target pane"
+    );
+    assert_eq!(dragged_pane.cols(), size.cols, "Dragged pane spans the tab");
+    assert_eq!(target_pane.cols(), size.cols, "Target pane spans the tab");
+    assert_eq!(dragged_pane.rows() + target_pane.rows(), size.rows);
+}
+
+#[test]
+fn drag_tiled_pane_is_cancelled_by_a_mouse_event_that_is_not_a_drag() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mut tab = create_new_tab(size, ModeInfo::default());
+    tab.vertical_split(PaneId::Terminal(2), None, client_id, None, None)
+        .unwrap();
+    let left_pane_x = tab.tiled_panes.get_pane(PaneId::Terminal(1)).unwrap().x();
+
+    tab.handle_mouse_event(
+        &MouseEvent::new_left_press_event(Position::new(0, 3)),
+        client_id,
+    )
+    .unwrap();
+    tab.handle_mouse_event(
+        &MouseEvent::new_left_motion_event(Position::new(10, 90)),
+        client_id,
+    )
+    .unwrap();
+    assert!(tab.tiled_panes.pane_is_being_moved_with_mouse(client_id));
+    assert!(
+        !tab.tiled_panes.pane_is_being_moved_with_mouse(2),
+        "Only the client dragging the pane is moving it"
+    );
+
+    // the release was missed, so the next event is a buttonless motion
+    tab.handle_mouse_event(
+        &MouseEvent::new_buttonless_motion(Position::new(10, 90)),
+        client_id,
+    )
+    .unwrap();
+    assert!(!tab.tiled_panes.pane_is_being_moved_with_mouse(client_id));
+    assert_eq!(tab.tiled_panes.drop_target(client_id), None);
+    assert_eq!(
+        tab.tiled_panes.get_pane(PaneId::Terminal(1)).unwrap().x(),
+        left_pane_x,
+        "Dragged pane stayed where it was"
+    );
+}
+
+#[test]
+fn drag_tiled_pane_drop_target_preview() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mut tab = create_new_tab(size, ModeInfo::default());
+    let mut output = Output::default();
+    tab.vertical_split(PaneId::Terminal(2), None, client_id, None, None)
+        .unwrap();
+    tab.handle_mouse_event(
+        &MouseEvent::new_left_press_event(Position::new(0, 3)),
+        client_id,
+    )
+    .unwrap();
+    tab.handle_mouse_event(
+        &MouseEvent::new_left_motion_event(Position::new(10, 118)),
+        client_id,
+    )
+    .unwrap();
+    tab.render(&mut output, None).unwrap();
+    let (snapshot, _cursor_coordinates) = take_snapshot_and_cursor_position(
+        output.serialize().unwrap().get(&client_id).unwrap(),
+        size.rows,
+        size.cols,
+        Palette::default(),
+    );
+    assert_snapshot!(snapshot);
+}
+
+#[test]
+fn input_locked_pane_drops_input() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mut pty_instruction_bus = MockPtyInstructionBus::new();
+    let mut tab = create_new_tab_with_mock_pty_writer(
+        size,
+        ModeInfo::default(),
+        pty_instruction_bus.pty_write_sender(),
+    );
+    pty_instruction_bus.start();
+
+    tab.toggle_pane_input_lock(client_id, PaneInputLock::Drop)
+        .unwrap();
+    tab.write_to_active_terminal(&None, "dropped".as_bytes().to_vec(), false, client_id)
+        .unwrap();
+    tab.paste_to_active_terminal("also dropped".as_bytes().to_vec(), client_id, None)
+        .unwrap();
+    tab.toggle_pane_input_lock(client_id, PaneInputLock::Drop)
+        .unwrap();
+    tab.write_to_active_terminal(&None, "written".as_bytes().to_vec(), false, client_id)
+        .unwrap();
+
+    pty_instruction_bus.exit();
+
+    assert_eq!(pty_instruction_bus.clone_output(), vec!["written"]);
+}
+
+#[test]
+fn input_locked_pane_holds_input_until_confirmed() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mut pty_instruction_bus = MockPtyInstructionBus::new();
+    let mut tab = create_new_tab_with_mock_pty_writer(
+        size,
+        ModeInfo::default(),
+        pty_instruction_bus.pty_write_sender(),
+    );
+    pty_instruction_bus.start();
+
+    tab.toggle_pane_input_lock(client_id, PaneInputLock::Confirm)
+        .unwrap();
+    tab.write_to_active_terminal(&None, "held".as_bytes().to_vec(), false, client_id)
+        .unwrap();
+    let pane = tab.get_pane_with_id(PaneId::Terminal(1)).unwrap();
+    assert!(
+        pane.is_awaiting_input_confirmation(),
+        "pane waits for the held input to be confirmed"
+    );
+    tab.write_to_active_terminal(&None, " and more".as_bytes().to_vec(), false, client_id)
+        .unwrap();
+    tab.write_to_active_terminal(&None, vec![13], false, client_id)
+        .unwrap();
+    assert_eq!(
+        tab.pane_input_lock(PaneId::Terminal(1)),
+        Some(PaneInputLock::Confirm),
+        "confirming held input does not unlock the pane"
+    );
+    tab.write_to_active_terminal(&None, "after".as_bytes().to_vec(), false, client_id)
+        .unwrap();
+    let pane = tab.get_pane_with_id(PaneId::Terminal(1)).unwrap();
+    assert!(
+        pane.is_awaiting_input_confirmation(),
+        "input after the confirmation is held again"
+    );
+
+    pty_instruction_bus.exit();
+
+    assert_eq!(
+        pty_instruction_bus.clone_output(),
+        vec!["held", " and more"],
+        "all the input held while waiting is sent on confirmation"
+    );
+}
+
+#[test]
+fn input_locked_pane_discards_held_input() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mut pty_instruction_bus = MockPtyInstructionBus::new();
+    let mut tab = create_new_tab_with_mock_pty_writer(
+        size,
+        ModeInfo::default(),
+        pty_instruction_bus.pty_write_sender(),
+    );
+    pty_instruction_bus.start();
+
+    tab.toggle_pane_input_lock(client_id, PaneInputLock::Confirm)
+        .unwrap();
+    tab.paste_to_active_terminal("discarded".as_bytes().to_vec(), client_id, None)
+        .unwrap();
+    tab.write_to_active_terminal(&None, vec![27], false, client_id)
+        .unwrap();
+
+    pty_instruction_bus.exit();
+
+    assert!(pty_instruction_bus.clone_output().is_empty());
+    let pane = tab.get_pane_with_id(PaneId::Terminal(1)).unwrap();
+    assert!(!pane.is_awaiting_input_confirmation());
+    assert_eq!(
+        tab.pane_input_lock(PaneId::Terminal(1)),
+        Some(PaneInputLock::Confirm),
+        "discarding held input keeps the pane locked"
+    );
+}
+
+#[test]
+fn sync_panes_skip_input_locked_panes() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mut pty_instruction_bus = MockPtyInstructionBus::new();
+    let mut tab = create_new_tab_with_mock_pty_writer(
+        size,
+        ModeInfo::default(),
+        pty_instruction_bus.pty_write_sender(),
+    );
+    pty_instruction_bus.start();
+
+    tab.vertical_split(PaneId::Terminal(2), None, client_id, None, None)
+        .unwrap();
+    tab.set_pane_input_lock(PaneId::Terminal(1), Some(PaneInputLock::Confirm))
+        .unwrap();
+    tab.toggle_sync_panes_is_active();
+    tab.write_to_terminals_on_current_tab(&None, "synced".as_bytes().to_vec(), false, client_id)
+        .unwrap();
+
+    pty_instruction_bus.exit();
+
+    assert_eq!(pty_instruction_bus.clone_output(), vec!["synced"]);
+    let pane = tab.get_pane_with_id(PaneId::Terminal(1)).unwrap();
+    assert!(
+        !pane.is_awaiting_input_confirmation(),
+        "synced input is not held for confirmation"
+    );
+}
+
+#[test]
+fn input_locked_pane_frame_while_awaiting_confirmation() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mut tab = create_new_tab(size, ModeInfo::default());
+    let mut output = Output::default();
+    tab.toggle_pane_input_lock(client_id, PaneInputLock::Confirm)
+        .unwrap();
+    tab.write_to_active_terminal(&None, "held".as_bytes().to_vec(), false, client_id)
+        .unwrap();
+    tab.render(&mut output, None).unwrap();
+    let snapshot = take_snapshot(
+        output.serialize().unwrap().get(&client_id).unwrap(),
+        size.rows,
+        size.cols,
+        Palette::default(),
+    );
+    assert_snapshot!(snapshot);
+}
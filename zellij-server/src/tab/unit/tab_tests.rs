@@ -2,111 +2,20 @@ use super::Tab;
 use crate::pane_groups::PaneGroups;
 use crate::panes::sixel::SixelImageStore;
 use crate::screen::CopyOptions;
-use crate::{os_input_output::ServerOsApi, panes::PaneId, thread_bus::ThreadSenders, ClientId};
+use crate::{
+    os_input_output_fake::FakeInputOutput, panes::PaneId, thread_bus::ThreadSenders, ClientId,
+};
 use std::net::{IpAddr, Ipv4Addr};
 use std::path::PathBuf;
 use zellij_utils::data::{Direction, NewPanePlacement, Resize, ResizeStrategy, WebSharing};
-use zellij_utils::errors::prelude::*;
-use zellij_utils::input::layout::{SplitDirection, SplitSize, TiledPaneLayout};
-use zellij_utils::ipc::IpcReceiverWithContext;
+use zellij_utils::input::layout::{SplitDirection, SplitSize, TiledPaneLayout, TilingStrategy};
 use zellij_utils::pane_size::{Size, SizeInPixels};
 
-use crate::os_input_output::AsyncReader;
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::rc::Rc;
 
-use interprocess::local_socket::Stream as LocalSocketStream;
-use zellij_utils::{
-    data::{ModeInfo, Palette, Style},
-    input::command::{RunCommand, TerminalAction},
-    ipc::{ClientToServerMsg, ServerToClientMsg},
-};
-
-#[derive(Clone)]
-struct FakeInputOutput {}
-
-impl ServerOsApi for FakeInputOutput {
-    fn set_terminal_size_using_terminal_id(
-        &self,
-        _id: u32,
-        _cols: u16,
-        _rows: u16,
-        _width_in_pixels: Option<u16>,
-        _height_in_pixels: Option<u16>,
-    ) -> Result<()> {
-        // noop
-        Ok(())
-    }
-    fn spawn_terminal(
-        &self,
-        _file_to_open: TerminalAction,
-        _quit_cb: Box<dyn Fn(PaneId, Option<i32>, RunCommand) + Send>,
-        _default_editor: Option<PathBuf>,
-    ) -> Result<(u32, Box<dyn AsyncReader>, Option<u32>)> {
-        unimplemented!()
-    }
-    fn write_to_tty_stdin(&self, _id: u32, _buf: &[u8]) -> Result<usize> {
-        unimplemented!()
-    }
-    fn tcdrain(&self, _id: u32) -> Result<()> {
-        unimplemented!()
-    }
-    fn kill(&self, _pid: u32) -> Result<()> {
-        unimplemented!()
-    }
-    fn force_kill(&self, _pid: u32) -> Result<()> {
-        unimplemented!()
-    }
-    fn box_clone(&self) -> Box<dyn ServerOsApi> {
-        Box::new((*self).clone())
-    }
-    fn send_to_client(&self, _client_id: ClientId, _msg: ServerToClientMsg) -> Result<()> {
-        unimplemented!()
-    }
-    fn new_client(
-        &mut self,
-        _client_id: ClientId,
-        _stream: LocalSocketStream,
-    ) -> Result<IpcReceiverWithContext<ClientToServerMsg>> {
-        unimplemented!()
-    }
-    fn new_client_with_reply(
-        &mut self,
-        _client_id: ClientId,
-        _stream: LocalSocketStream,
-        _reply_stream: LocalSocketStream,
-    ) -> Result<IpcReceiverWithContext<ClientToServerMsg>> {
-        unimplemented!()
-    }
-    fn remove_client(&mut self, _client_id: ClientId) -> Result<()> {
-        unimplemented!()
-    }
-    fn load_palette(&self) -> Palette {
-        unimplemented!()
-    }
-    fn get_cwd(&self, _pid: u32) -> Option<PathBuf> {
-        unimplemented!()
-    }
-
-    fn write_to_file(&mut self, _buf: String, _name: Option<String>) -> Result<()> {
-        unimplemented!()
-    }
-    fn re_run_command_in_terminal(
-        &self,
-        _terminal_id: u32,
-        _run_command: RunCommand,
-        _quit_cb: Box<dyn Fn(PaneId, Option<i32>, RunCommand) + Send>,
-    ) -> Result<(Box<dyn AsyncReader>, Option<u32>)> {
-        unimplemented!()
-    }
-    fn clear_terminal_id(&self, _terminal_id: u32) -> Result<()> {
-        unimplemented!()
-    }
-    fn send_sigint(&self, _pid: u32) -> Result<()> {
-        unimplemented!()
-    }
-}
+use zellij_utils::data::{ModeInfo, Palette, Style};
 
 fn tab_resize_increase(tab: &mut Tab, id: ClientId) {
     tab.resize(id, ResizeStrategy::new(Resize::Increase, None))
@@ -149,7 +58,7 @@ fn create_new_tab(size: Size, stacked_resize: bool) -> Tab {
     let index = 0;
     let position = 0;
     let name = String::new();
-    let os_api = Box::new(FakeInputOutput {});
+    let os_api = Box::new(FakeInputOutput::default());
     let senders = ThreadSenders::default().silently_fail_on_send();
     let max_panes = None;
     let mode_info = ModeInfo::default();
@@ -235,7 +144,7 @@ fn create_new_tab_with_layout(size: Size, layout: TiledPaneLayout) -> Tab {
     let index = 0;
     let position = 0;
     let name = String::new();
-    let os_api = Box::new(FakeInputOutput {});
+    let os_api = Box::new(FakeInputOutput::default());
     let senders = ThreadSenders::default().silently_fail_on_send();
     let max_panes = None;
     let mode_info = ModeInfo::default();
@@ -328,7 +237,7 @@ fn create_new_tab_with_cell_size(
     let index = 0;
     let position = 0;
     let name = String::new();
-    let os_api = Box::new(FakeInputOutput {});
+    let os_api = Box::new(FakeInputOutput::default());
     let senders = ThreadSenders::default().silently_fail_on_send();
     let max_panes = None;
     let mode_info = ModeInfo::default();
@@ -15587,3 +15496,432 @@ pub fn scroll_up_nonexistent_pane_id_does_not_panic() {
     assert!(!tab.has_pane_with_pid(&pane_id));
     tab.scroll_up_by_pane_id(pane_id);
 }
+
+fn pane_cols(tab: &Tab, pane_id: PaneId) -> usize {
+    tab.tiled_panes
+        .panes
+        .get(&pane_id)
+        .unwrap()
+        .position_and_size()
+        .cols
+        .as_usize()
+}
+
+fn pane_rows(tab: &Tab, pane_id: PaneId) -> usize {
+    tab.tiled_panes
+        .panes
+        .get(&pane_id)
+        .unwrap()
+        .position_and_size()
+        .rows
+        .as_usize()
+}
+
+#[test]
+pub fn equalize_panes_in_whole_tab() {
+    // ┌───────┬───┐                 ┌─────┬─────┐
+    // │       │   │                 │     │     │
+    // │       ├───┤ ==equalize==>   │     ├─────┤
+    // │       │███│                 │     │█████│
+    // └───────┴───┘                 └─────┴─────┘
+    // █ == focused pane
+
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let stacked_resize = false;
+    let mut tab = create_new_tab(size, stacked_resize);
+    tab.vertical_split(PaneId::Terminal(2), None, 1, None, None)
+        .unwrap();
+    tab.horizontal_split(PaneId::Terminal(3), None, 1, None, None)
+        .unwrap();
+    tab_resize_left(&mut tab, 1);
+    tab_resize_left(&mut tab, 1);
+    tab_resize_down(&mut tab, 1);
+    assert_ne!(
+        pane_cols(&tab, PaneId::Terminal(1)),
+        pane_cols(&tab, PaneId::Terminal(3)),
+        "panes have different widths before equalizing"
+    );
+    assert_ne!(
+        pane_rows(&tab, PaneId::Terminal(2)),
+        pane_rows(&tab, PaneId::Terminal(3)),
+        "panes have different heights before equalizing"
+    );
+
+    tab.equalize_panes(1, None);
+
+    assert_eq!(
+        pane_cols(&tab, PaneId::Terminal(1)),
+        61,
+        "pane 1 column count"
+    );
+    assert_eq!(
+        pane_cols(&tab, PaneId::Terminal(2)),
+        60,
+        "pane 2 column count"
+    );
+    assert_eq!(
+        pane_cols(&tab, PaneId::Terminal(3)),
+        60,
+        "pane 3 column count"
+    );
+    assert_eq!(pane_rows(&tab, PaneId::Terminal(1)), 20, "pane 1 row count");
+    assert_eq!(pane_rows(&tab, PaneId::Terminal(2)), 10, "pane 2 row count");
+    assert_eq!(pane_rows(&tab, PaneId::Terminal(3)), 10, "pane 3 row count");
+}
+
+#[test]
+pub fn equalize_panes_only_in_split_of_focused_pane() {
+    // ┌───────┬───┐                                    ┌───────┬───┐
+    // │       │   │                                    │       │   │
+    // │       ├───┤ ==equalize=horizontal=splits==>    │       ├───┤
+    // │       │███│                                    │       │███│
+    // └───────┴───┘                                    └───────┴───┘
+    // █ == focused pane
+
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let stacked_resize = false;
+    let mut tab = create_new_tab(size, stacked_resize);
+    tab.vertical_split(PaneId::Terminal(2), None, 1, None, None)
+        .unwrap();
+    tab.horizontal_split(PaneId::Terminal(3), None, 1, None, None)
+        .unwrap();
+    tab_resize_left(&mut tab, 1);
+    tab_resize_left(&mut tab, 1);
+    tab_resize_down(&mut tab, 1);
+    let pane_1_cols = pane_cols(&tab, PaneId::Terminal(1));
+
+    tab.equalize_panes(1, Some(SplitDirection::Horizontal));
+
+    assert_eq!(
+        pane_cols(&tab, PaneId::Terminal(1)),
+        pane_1_cols,
+        "pane 1 column count did not change"
+    );
+    assert_eq!(pane_rows(&tab, PaneId::Terminal(2)), 10, "pane 2 row count");
+    assert_eq!(pane_rows(&tab, PaneId::Terminal(3)), 10, "pane 3 row count");
+
+    tab.equalize_panes(1, Some(SplitDirection::Vertical));
+
+    assert_eq!(
+        pane_cols(&tab, PaneId::Terminal(1)),
+        61,
+        "pane 1 column count"
+    );
+    assert_eq!(
+        pane_cols(&tab, PaneId::Terminal(3)),
+        60,
+        "pane 3 column count"
+    );
+}
+
+#[test]
+pub fn equalize_panes_does_not_change_fixed_panes() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut initial_layout = TiledPaneLayout::default();
+    initial_layout.children_split_direction = SplitDirection::Vertical;
+    let mut fixed_child = TiledPaneLayout::default();
+    fixed_child.split_size = Some(SplitSize::Fixed(31));
+    let mut small_child = TiledPaneLayout::default();
+    small_child.split_size = Some(SplitSize::Percent(20));
+    let mut large_child = TiledPaneLayout::default();
+    large_child.split_size = Some(SplitSize::Percent(80));
+    initial_layout.children = vec![fixed_child, small_child, large_child];
+    let mut tab = create_new_tab_with_layout(size, initial_layout);
+
+    tab.equalize_panes(1, None);
+
+    assert_eq!(
+        pane_cols(&tab, PaneId::Terminal(0)),
+        31,
+        "fixed pane column count"
+    );
+    assert_eq!(
+        pane_cols(&tab, PaneId::Terminal(1)),
+        45,
+        "pane 1 column count"
+    );
+    assert_eq!(
+        pane_cols(&tab, PaneId::Terminal(2)),
+        45,
+        "pane 2 column count"
+    );
+}
+
+#[test]
+pub fn equalize_panes_with_stacked_panes() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut initial_layout = TiledPaneLayout::default();
+    initial_layout.children_split_direction = SplitDirection::Vertical;
+    let mut small_child = TiledPaneLayout::default();
+    small_child.split_size = Some(SplitSize::Percent(30));
+    let mut stacked_child = TiledPaneLayout::default();
+    stacked_child.split_size = Some(SplitSize::Percent(70));
+    stacked_child.children_are_stacked = true;
+    stacked_child.children = vec![
+        TiledPaneLayout::default(),
+        TiledPaneLayout::default(),
+        TiledPaneLayout::default(),
+    ];
+    initial_layout.children = vec![small_child, stacked_child];
+    let mut tab = create_new_tab_with_layout(size, initial_layout);
+
+    tab.equalize_panes(1, None);
+
+    assert_eq!(
+        pane_cols(&tab, PaneId::Terminal(0)),
+        61,
+        "pane 0 column count"
+    );
+    for pane_id in 1..=3 {
+        assert_eq!(
+            pane_cols(&tab, PaneId::Terminal(pane_id)),
+            60,
+            "stacked pane column count"
+        );
+    }
+    assert_eq!(
+        (1..=3)
+            .map(|pane_id| pane_rows(&tab, PaneId::Terminal(pane_id)))
+            .sum::<usize>(),
+        20,
+        "stacked panes still fill the stack"
+    );
+}
+
+#[test]
+pub fn master_stack_tiling_strategy_arranges_tiled_panes() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut initial_layout = TiledPaneLayout::default();
+    initial_layout.children = vec![
+        TiledPaneLayout::default(),
+        TiledPaneLayout::default(),
+        TiledPaneLayout::default(),
+    ];
+    let mut tab = create_new_tab_with_layout(size, initial_layout);
+
+    tab.set_tiling_strategy(Some(TilingStrategy::MasterStack {
+        master_ratio: 60,
+        master_count: 1,
+    }));
+
+    let master_cols = pane_cols(&tab, PaneId::Terminal(0));
+    assert_eq!(
+        pane_rows(&tab, PaneId::Terminal(0)),
+        20,
+        "master pane row count"
+    );
+    for pane_id in 1..=2 {
+        assert_eq!(
+            master_cols + pane_cols(&tab, PaneId::Terminal(pane_id)),
+            121,
+            "master and stack share the width"
+        );
+        assert_eq!(
+            pane_rows(&tab, PaneId::Terminal(pane_id)),
+            10,
+            "stack pane row count"
+        );
+    }
+    assert!(master_cols > 121 / 2, "master pane is the wider column");
+}
+
+#[test]
+pub fn tiling_strategy_retiles_panes_opened_by_splitting() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let stacked_resize = false;
+    let mut tab = create_new_tab(size, stacked_resize);
+    tab.set_tiling_strategy(Some(TilingStrategy::MasterStack {
+        master_ratio: 60,
+        master_count: 1,
+    }));
+
+    tab.vertical_split(PaneId::Terminal(2), None, 1, None, None)
+        .unwrap();
+    tab.horizontal_split(PaneId::Terminal(3), None, 1, None, None)
+        .unwrap();
+
+    let master_cols = pane_cols(&tab, PaneId::Terminal(1));
+    assert_eq!(
+        pane_rows(&tab, PaneId::Terminal(1)),
+        20,
+        "master pane row count"
+    );
+    for pane_id in 2..=3 {
+        assert_eq!(
+            master_cols + pane_cols(&tab, PaneId::Terminal(pane_id)),
+            121,
+            "master and stack share the width"
+        );
+        assert_eq!(
+            pane_rows(&tab, PaneId::Terminal(pane_id)),
+            10,
+            "stack pane row count"
+        );
+    }
+    assert!(
+        master_cols >= 121 * 60 / 100,
+        "master pane takes the master ratio"
+    );
+}
+
+#[test]
+pub fn tiling_strategy_retiles_panes_when_one_is_closed() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let stacked_resize = false;
+    let mut tab = create_new_tab(size, stacked_resize);
+    tab.set_tiling_strategy(Some(TilingStrategy::MasterStack {
+        master_ratio: 60,
+        master_count: 1,
+    }));
+    tab.vertical_split(PaneId::Terminal(2), None, 1, None, None)
+        .unwrap();
+    tab.horizontal_split(PaneId::Terminal(3), None, 1, None, None)
+        .unwrap();
+
+    tab.close_pane(PaneId::Terminal(1), false, None);
+
+    let master_cols = pane_cols(&tab, PaneId::Terminal(2));
+    let stack_cols = pane_cols(&tab, PaneId::Terminal(3));
+    assert_eq!(master_cols + stack_cols, 121, "panes share the width");
+    assert!(
+        master_cols >= 121 * 60 / 100,
+        "next pane takes the master column"
+    );
+    for pane_id in 2..=3 {
+        assert_eq!(
+            pane_rows(&tab, PaneId::Terminal(pane_id)),
+            20,
+            "pane row count"
+        );
+    }
+}
+
+#[test]
+fn showing_a_scratchpad_does_not_reveal_hidden_floating_panes() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let stacked_resize = false;
+    let mut tab = create_new_tab(size, stacked_resize);
+    let client_id = 1;
+    let other_floating_pane_id = PaneId::Terminal(2);
+    let scratchpad_id = PaneId::Terminal(3);
+
+    tab.new_floating_pane(other_floating_pane_id, None, None, false, true, None, None)
+        .unwrap();
+    tab.new_floating_pane(scratchpad_id, None, None, false, true, None, None)
+        .unwrap();
+    tab.hide_scratchpad(scratchpad_id, client_id);
+    tab.hide_floating_panes();
+
+    tab.show_scratchpad(scratchpad_id, None, client_id).unwrap();
+    assert!(tab.scratchpad_is_visible(scratchpad_id));
+    assert!(
+        !tab.floating_panes.panes_contain(&other_floating_pane_id),
+        "hidden floating pane stays hidden"
+    );
+    assert_eq!(tab.get_active_pane_id(client_id), Some(scratchpad_id));
+
+    tab.hide_scratchpad(scratchpad_id, client_id);
+    assert!(
+        !tab.are_floating_panes_visible(),
+        "floating panes are hidden again"
+    );
+    assert!(
+        tab.floating_panes.panes_contain(&other_floating_pane_id),
+        "hidden floating pane is back with the floating panes"
+    );
+}
+
+#[test]
+fn scratchpad_hidden_with_the_floating_panes_is_shown_alone() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let stacked_resize = false;
+    let mut tab = create_new_tab(size, stacked_resize);
+    let client_id = 1;
+    let other_floating_pane_id = PaneId::Terminal(2);
+    let scratchpad_id = PaneId::Terminal(3);
+
+    tab.new_floating_pane(other_floating_pane_id, None, None, false, true, None, None)
+        .unwrap();
+    tab.new_floating_pane(scratchpad_id, None, None, false, true, None, None)
+        .unwrap();
+    tab.hide_floating_panes();
+    assert!(!tab.scratchpad_is_visible(scratchpad_id));
+
+    tab.show_scratchpad(scratchpad_id, None, client_id).unwrap();
+    assert!(tab.scratchpad_is_visible(scratchpad_id));
+    assert!(!tab.floating_panes.panes_contain(&other_floating_pane_id));
+
+    tab.close_pane(scratchpad_id, false, None);
+    assert!(
+        tab.floating_panes.panes_contain(&other_floating_pane_id),
+        "closing the scratchpad brings back the panes it hid"
+    );
+    assert!(!tab.are_floating_panes_visible());
+}
+
+#[test]
+fn hiding_a_scratchpad_leaves_other_floating_panes_visible() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let stacked_resize = false;
+    let mut tab = create_new_tab(size, stacked_resize);
+    let client_id = 1;
+    let scratchpad_id = PaneId::Terminal(3);
+
+    tab.new_floating_pane(PaneId::Terminal(2), None, None, false, true, None, None)
+        .unwrap();
+    tab.new_floating_pane(scratchpad_id, None, None, false, true, None, None)
+        .unwrap();
+    assert!(tab.scratchpad_is_visible(scratchpad_id));
+
+    tab.suppress_pane(scratchpad_id, Some(client_id));
+    assert!(
+        !tab.scratchpad_is_visible(scratchpad_id),
+        "scratchpad is hidden"
+    );
+    assert!(
+        tab.are_floating_panes_visible(),
+        "other floating panes are still shown"
+    );
+    assert!(tab.has_pane_with_pid(&scratchpad_id), "scratchpad is kept");
+
+    tab.show_scratchpad(scratchpad_id, None, client_id).unwrap();
+    assert!(
+        tab.scratchpad_is_visible(scratchpad_id),
+        "scratchpad is shown again"
+    );
+    assert_eq!(
+        tab.get_active_pane_id(client_id),
+        Some(scratchpad_id),
+        "scratchpad is focused"
+    );
+}
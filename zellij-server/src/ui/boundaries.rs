@@ -578,6 +578,47 @@ impl Boundaries {
             }
         }
     }
+    pub fn add_drop_target_preview(&mut self, area: Viewport, color: PaletteColor) {
+        // unlike add_rect, this draws over the edges of the area itself rather than around it, so
+        // that the preview is visible on top of pane frames
+        if area.cols < 2 || area.rows < 2 {
+            return;
+        }
+        let color = Some((color, usize::MAX));
+        let last_col = area.x + area.cols - 1;
+        let last_row = area.y + area.rows - 1;
+        for col in area.x..=last_col {
+            let (top_symbol, bottom_symbol) = if col == area.x {
+                (
+                    boundary_type::TOP_LEFT_ROUND,
+                    boundary_type::BOTTOM_LEFT_ROUND,
+                )
+            } else if col == last_col {
+                (
+                    boundary_type::TOP_RIGHT_ROUND,
+                    boundary_type::BOTTOM_RIGHT_ROUND,
+                )
+            } else {
+                (boundary_type::HORIZONTAL, boundary_type::HORIZONTAL)
+            };
+            self.boundary_characters.insert(
+                Coordinates::new(col, area.y),
+                BoundarySymbol::new(top_symbol).color(color),
+            );
+            self.boundary_characters.insert(
+                Coordinates::new(col, last_row),
+                BoundarySymbol::new(bottom_symbol).color(color),
+            );
+        }
+        for row in area.y + 1..last_row {
+            for col in [area.x, last_col] {
+                self.boundary_characters.insert(
+                    Coordinates::new(col, row),
+                    BoundarySymbol::new(boundary_type::VERTICAL).color(color),
+                );
+            }
+        }
+    }
     pub fn render(
         &self,
         existing_boundaries_on_screen: Option<&Boundaries>,
@@ -67,6 +67,7 @@ pub struct FrameParams {
     pub pane_is_selectable: bool,
     pub show_help_text: bool,
     pub highlight_tooltip: Option<String>,
+    pub is_broadcast_target: bool, // the focused client writes its input to this pane
 }
 
 #[derive(Default, PartialEq)]
@@ -92,6 +93,9 @@ pub struct PaneFrame {
     is_selectable: bool,
     show_help_text: bool,
     highlight_tooltip: Option<String>,
+    is_broadcast_target: bool,
+    is_input_locked: bool,
+    is_awaiting_input_confirmation: bool,
 }
 
 impl PaneFrame {
@@ -123,12 +127,24 @@ impl PaneFrame {
             is_selectable: frame_params.pane_is_selectable,
             show_help_text: frame_params.show_help_text,
             highlight_tooltip: frame_params.highlight_tooltip,
+            is_broadcast_target: frame_params.is_broadcast_target,
+            is_input_locked: false,
+            is_awaiting_input_confirmation: false,
         }
     }
     pub fn is_pinned(mut self, is_pinned: bool) -> Self {
         self.is_pinned = is_pinned;
         self
     }
+    pub fn input_lock(
+        mut self,
+        is_input_locked: bool,
+        is_awaiting_input_confirmation: bool,
+    ) -> Self {
+        self.is_input_locked = is_input_locked;
+        self.is_awaiting_input_confirmation = is_awaiting_input_confirmation;
+        self
+    }
     pub fn add_exit_status(&mut self, exit_status: Option<i32>) {
         self.exit_status = match exit_status {
             Some(exit_status) => Some(ExitStatus::Code(exit_status)),
@@ -174,6 +190,39 @@ impl PaneFrame {
         max_length: usize,
     ) -> Option<(Vec<TerminalCharacter>, usize)> {
         // string and length because of color
+        let broadcast_indication = if self.is_broadcast_target {
+            self.render_broadcast_indication(max_length)
+        } else {
+            None
+        };
+        let space_for_other_indications = broadcast_indication
+            .as_ref()
+            .map(|(_, length)| max_length.saturating_sub(*length + 1))
+            .unwrap_or(max_length);
+        let other_indications = self.render_scroll_and_pin_indications(space_for_other_indications);
+        match (broadcast_indication, other_indications) {
+            (
+                Some((mut broadcast_indication, broadcast_indication_len)),
+                Some((mut other_indications, other_indications_len)),
+            ) => {
+                let mut characters = std::mem::take(&mut broadcast_indication);
+                let mut separator = foreground_color("|", self.color);
+                characters.append(&mut separator);
+                characters.append(&mut other_indications);
+                Some((
+                    characters,
+                    broadcast_indication_len + other_indications_len + 1,
+                ))
+            },
+            (Some(broadcast_indication), None) => Some(broadcast_indication),
+            (None, Some(other_indications)) => Some(other_indications),
+            _ => None,
+        }
+    }
+    fn render_scroll_and_pin_indications(
+        &self,
+        max_length: usize,
+    ) -> Option<(Vec<TerminalCharacter>, usize)> {
         let has_scroll = self.scroll_position.0 > 0 || self.scroll_position.1 > 0;
         if has_scroll && self.is_selectable {
             // TODO: don't show SCROLL at all for plugins
@@ -237,6 +286,28 @@ impl PaneFrame {
             None
         }
     }
+    fn render_broadcast_indication(
+        &self,
+        max_length: usize,
+    ) -> Option<(Vec<TerminalCharacter>, usize)> {
+        let full_indication = " BROADCAST ";
+        let short_indication = " B ";
+        let full_indication_len = full_indication.chars().count();
+        let short_indication_len = short_indication.chars().count();
+        if full_indication_len <= max_length {
+            Some((
+                foreground_color(full_indication, self.color),
+                full_indication_len,
+            ))
+        } else if short_indication_len <= max_length {
+            Some((
+                foreground_color(short_indication, self.color),
+                short_indication_len,
+            ))
+        } else {
+            None
+        }
+    }
     fn render_pinned_indication(
         &self,
         max_length: usize,
@@ -416,7 +487,11 @@ impl PaneFrame {
     fn render_title_left_side(&self, max_length: usize) -> Option<(Vec<TerminalCharacter>, usize)> {
         let middle_truncated_sign = "[..]";
         let middle_truncated_sign_long = "[...]";
-        let full_text = format!(" {} ", &self.title);
+        let full_text = if self.is_input_locked {
+            format!(" 🔒 {} ", &self.title)
+        } else {
+            format!(" {} ", &self.title)
+        };
         if max_length <= 6 || self.title.is_empty() {
             None
         } else if full_text.width() <= max_length {
@@ -824,6 +899,29 @@ impl PaneFrame {
         }
     }
 
+    fn render_input_confirmation_undertitle(&self) -> Result<Vec<TerminalCharacter>> {
+        let max_undertitle_length = self.geom.cols.saturating_sub(2); // 2 for the left and right corners
+        let mut left_boundary =
+            foreground_color(self.get_corner(boundary_type::BOTTOM_LEFT), self.color);
+        let mut right_boundary =
+            foreground_color(self.get_corner(boundary_type::BOTTOM_RIGHT), self.color);
+        let res = match self.input_confirmation_part(max_undertitle_length) {
+            Some((mut input_confirmation, input_confirmation_len)) => {
+                let mut padding = String::new();
+                for _ in input_confirmation_len..max_undertitle_length {
+                    padding.push_str(boundary_type::HORIZONTAL);
+                }
+                let mut ret = vec![];
+                ret.append(&mut left_boundary);
+                ret.append(&mut input_confirmation);
+                ret.append(&mut foreground_color(&padding, self.color));
+                ret.append(&mut right_boundary);
+                ret
+            },
+            None => self.empty_undertitle(max_undertitle_length),
+        };
+        Ok(res)
+    }
     fn render_held_undertitle(&self) -> Result<Vec<TerminalCharacter>> {
         let max_undertitle_length = self.geom.cols.saturating_sub(2); // 2 for the left and right corners
         let (mut first_part, first_part_len) = self.first_exited_held_title_part_full();
@@ -965,7 +1063,16 @@ impl PaneFrame {
                     character_chunks.push(CharacterChunk::new(title, x, y));
                 } else if row == self.geom.rows - 1 {
                     // bottom row
-                    if self.highlight_tooltip.is_some() && self.is_main_client {
+                    if self.is_awaiting_input_confirmation {
+                        let x = self.geom.x;
+                        let y = self.geom.y + row;
+                        character_chunks.push(CharacterChunk::new(
+                            self.render_input_confirmation_undertitle()
+                                .with_context(err_context)?,
+                            x,
+                            y,
+                        ));
+                    } else if self.highlight_tooltip.is_some() && self.is_main_client {
                         let x = self.geom.x;
                         let y = self.geom.y + row;
                         character_chunks.push(CharacterChunk::new(
@@ -1146,6 +1253,47 @@ impl PaneFrame {
                 + break_tip.len(),
         )
     }
+    fn input_confirmation_part(
+        &self,
+        max_length: usize,
+    ) -> Option<(Vec<TerminalCharacter>, usize)> {
+        // (title part, length)
+        let left_bracket = " <";
+        let enter_text = "ENTER";
+        let right_bracket = ">";
+        let left_esc_bracket = "<";
+        let esc_text = "ESC";
+        let length_without_tips = left_bracket.len()
+            + enter_text.len()
+            + right_bracket.len()
+            + left_esc_bracket.len()
+            + esc_text.len()
+            + right_bracket.len();
+        let (enter_tip, esc_tip, length) =
+            [(" send input, ", " discard "), (" send, ", " discard ")]
+                .into_iter()
+                .map(|(enter_tip, esc_tip)| {
+                    let length = length_without_tips + enter_tip.len() + esc_tip.len();
+                    (enter_tip, esc_tip, length)
+                })
+                .find(|(_, _, length)| *length <= max_length)?;
+        let mut input_confirmation = vec![];
+        input_confirmation.append(&mut foreground_color(left_bracket, self.color));
+        input_confirmation.append(&mut foreground_color(
+            enter_text,
+            Some(self.style.colors.text_unselected.emphasis_0),
+        ));
+        input_confirmation.append(&mut foreground_color(right_bracket, self.color));
+        input_confirmation.append(&mut foreground_color(enter_tip, self.color));
+        input_confirmation.append(&mut foreground_color(left_esc_bracket, self.color));
+        input_confirmation.append(&mut foreground_color(
+            esc_text,
+            Some(self.style.colors.text_unselected.emphasis_0),
+        ));
+        input_confirmation.append(&mut foreground_color(right_bracket, self.color));
+        input_confirmation.append(&mut foreground_color(esc_tip, self.color));
+        Some((input_confirmation, length))
+    }
     fn hover_shortcuts_part_full(&self) -> (Vec<TerminalCharacter>, usize) {
         // (title part, length)
         let mut hover_shortcuts = vec![];
@@ -19,6 +19,7 @@ pub struct PaneContentsAndUi<'a> {
     should_draw_pane_frames: bool,
     mouse_is_hovering_over_pane_for_clients: HashSet<ClientId>,
     current_pane_group: HashMap<ClientId, Vec<PaneId>>,
+    broadcasting_clients: &'a HashSet<ClientId>,
     show_help_text: bool,
 }
 
@@ -35,6 +36,7 @@ impl<'a> PaneContentsAndUi<'a> {
         should_draw_pane_frames: bool,
         mouse_hover_pane_id: &HashMap<ClientId, PaneId>,
         current_pane_group: HashMap<ClientId, Vec<PaneId>>,
+        broadcasting_clients: &'a HashSet<ClientId>,
         show_help_text: bool,
     ) -> Self {
         let mut focused_clients: Vec<ClientId> = active_panes
@@ -65,6 +67,7 @@ impl<'a> PaneContentsAndUi<'a> {
             should_draw_pane_frames,
             mouse_is_hovering_over_pane_for_clients,
             current_pane_group,
+            broadcasting_clients,
             show_help_text,
         }
     }
@@ -228,6 +231,8 @@ impl<'a> PaneContentsAndUi<'a> {
 
         let frame_color = self.frame_color(client_id, client_mode, session_is_mirrored);
         let highlight_tooltip = self.pane.cached_hover_tooltip();
+        let is_broadcast_target =
+            self.broadcasting_clients.contains(&client_id) && self.pane_is_in_group(client_id);
         let focused_client = if pane_focused_for_client_id {
             Some(client_id)
         } else if pane_focused_for_differet_client {
@@ -254,6 +259,7 @@ impl<'a> PaneContentsAndUi<'a> {
                 pane_is_selectable,
                 show_help_text: self.show_help_text,
                 highlight_tooltip: highlight_tooltip.clone(),
+                is_broadcast_target,
             }
         } else {
             FrameParams {
@@ -274,6 +280,7 @@ impl<'a> PaneContentsAndUi<'a> {
                 pane_is_selectable,
                 show_help_text: self.show_help_text,
                 highlight_tooltip,
+                is_broadcast_target,
             }
         };
 
@@ -320,11 +327,7 @@ impl<'a> PaneContentsAndUi<'a> {
         // (color, color_precedence) (the color_precedence is used
         // for the no-pane-frames mode)
         let pane_focused_for_client_id = self.focused_clients.contains(&client_id);
-        let pane_is_in_group = self
-            .current_pane_group
-            .get(&client_id)
-            .map(|p| p.contains(&self.pane.pid()))
-            .unwrap_or(false);
+        let pane_is_in_group = self.pane_is_in_group(client_id);
         if self.pane.frame_color_override().is_some() && !pane_is_in_group {
             self.pane
                 .frame_color_override()
@@ -360,4 +363,10 @@ impl<'a> PaneContentsAndUi<'a> {
                 .map(|frame| (frame.base, 0))
         }
     }
+    fn pane_is_in_group(&self, client_id: ClientId) -> bool {
+        self.current_pane_group
+            .get(&client_id)
+            .map(|p| p.contains(&self.pane.pid()))
+            .unwrap_or(false)
+    }
 }
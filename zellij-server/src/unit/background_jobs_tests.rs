@@ -0,0 +1,288 @@
+use super::*;
+use crate::global_async_runtime::get_tokio_runtime;
+use zellij_utils::channels::{self, ChannelWithContext, Receiver, SenderWithContext};
+use zellij_utils::errors::ErrorContext;
+use zellij_utils::sessions::{delete_session_snapshot, get_session_snapshots, snapshot_layout};
+
+const PLUGIN_ID: PluginId = 1;
+const CLIENT_ID: ClientId = 2;
+const PROCESS_ID: u32 = 3;
+
+fn senders_with_plugin_receiver() -> (ThreadSenders, Receiver<(PluginInstruction, ErrorContext)>) {
+    let mut senders = ThreadSenders::default().silently_fail_on_send();
+    let (to_plugin, plugin_receiver): ChannelWithContext<PluginInstruction> = channels::unbounded();
+    senders.replace_to_plugin(SenderWithContext::new(to_plugin));
+    (senders, plugin_receiver)
+}
+
+fn spawn_process(
+    senders: ThreadSenders,
+    command: &str,
+    args: &[&str],
+) -> UnboundedSender<SpawnedProcessControl> {
+    let (control_sender, control_receiver) = tokio::sync::mpsc::unbounded_channel();
+    let mut context = BTreeMap::new();
+    context.insert("request".to_owned(), "test".to_owned());
+    get_tokio_runtime().spawn(run_spawned_process(
+        senders,
+        SpawnedProcessArgs {
+            plugin_id: PLUGIN_ID,
+            client_id: CLIENT_ID,
+            process_id: PROCESS_ID,
+            command: command.to_owned(),
+            args: args.iter().map(|a| a.to_string()).collect(),
+            env_variables: BTreeMap::new(),
+            cwd: std::env::temp_dir(),
+            context,
+        },
+        control_receiver,
+    ));
+    control_sender
+}
+
+// collects the events sent to the plugin until the process exits, returns (stdout, stderr, exit_code)
+fn collect_process_events(
+    plugin_receiver: &Receiver<(PluginInstruction, ErrorContext)>,
+) -> (Vec<u8>, Vec<u8>, Option<i32>) {
+    let mut stdout = vec![];
+    let mut stderr = vec![];
+    loop {
+        let (instruction, _err_ctx) = plugin_receiver
+            .recv_timeout(Duration::from_secs(10))
+            .expect("process did not exit in time");
+        let PluginInstruction::Update(updates) = instruction else {
+            continue;
+        };
+        for (plugin_id, client_id, event) in updates {
+            assert_eq!(
+                plugin_id,
+                Some(PLUGIN_ID),
+                "event sent to the spawning plugin"
+            );
+            assert_eq!(
+                client_id,
+                Some(CLIENT_ID),
+                "event sent to the spawning client"
+            );
+            match event {
+                Event::SpawnedProcessStdout(process_id, bytes, context) => {
+                    assert_eq!(process_id, PROCESS_ID);
+                    assert_eq!(context.get("request").map(|r| r.as_str()), Some("test"));
+                    stdout.extend(bytes);
+                },
+                Event::SpawnedProcessStderr(process_id, bytes, context) => {
+                    assert_eq!(process_id, PROCESS_ID);
+                    assert_eq!(context.get("request").map(|r| r.as_str()), Some("test"));
+                    stderr.extend(bytes);
+                },
+                Event::SpawnedProcessExited(process_id, exit_code, context) => {
+                    assert_eq!(process_id, PROCESS_ID);
+                    assert_eq!(context.get("request").map(|r| r.as_str()), Some("test"));
+                    return (stdout, stderr, exit_code);
+                },
+                event => panic!("unexpected event: {:?}", event),
+            }
+        }
+    }
+}
+
+#[test]
+fn spawned_process_output_is_sent_to_plugin() {
+    let (senders, plugin_receiver) = senders_with_plugin_receiver();
+    let _control = spawn_process(
+        senders,
+        "sh",
+        &[
+            "-c",
+            "printf 'hello from stdout'; printf 'hello from stderr' >&2; exit 3",
+        ],
+    );
+    let (stdout, stderr, exit_code) = collect_process_events(&plugin_receiver);
+    assert_eq!(String::from_utf8_lossy(&stdout), "hello from stdout");
+    assert_eq!(String::from_utf8_lossy(&stderr), "hello from stderr");
+    assert_eq!(exit_code, Some(3));
+}
+
+#[test]
+fn plugin_can_write_to_spawned_process_stdin() {
+    let (senders, plugin_receiver) = senders_with_plugin_receiver();
+    let control = spawn_process(senders, "head", &["-c", "5"]);
+    control
+        .send(SpawnedProcessControl::Write(b"hello world".to_vec()))
+        .unwrap();
+    let (stdout, _stderr, exit_code) = collect_process_events(&plugin_receiver);
+    assert_eq!(String::from_utf8_lossy(&stdout), "hello");
+    assert_eq!(exit_code, Some(0));
+}
+
+#[test]
+fn plugin_can_close_spawned_process_stdin() {
+    let (senders, plugin_receiver) = senders_with_plugin_receiver();
+    let control = spawn_process(senders, "wc", &["-c"]);
+    control
+        .send(SpawnedProcessControl::Write(b"hello world".to_vec()))
+        .unwrap();
+    control.send(SpawnedProcessControl::CloseStdin).unwrap();
+    let (stdout, _stderr, exit_code) = collect_process_events(&plugin_receiver);
+    assert_eq!(
+        String::from_utf8_lossy(&stdout).trim(),
+        "11",
+        "wc counted all of its input once stdin was closed"
+    );
+    assert_eq!(exit_code, Some(0));
+}
+
+#[test]
+fn plugin_can_kill_spawned_process() {
+    let (senders, plugin_receiver) = senders_with_plugin_receiver();
+    let control = spawn_process(senders, "sleep", &["100"]);
+    control.send(SpawnedProcessControl::Kill).unwrap();
+    let (_stdout, _stderr, exit_code) = collect_process_events(&plugin_receiver);
+    assert_eq!(
+        exit_code, None,
+        "killed by a signal, so there is no exit code"
+    );
+}
+
+#[test]
+fn failing_to_spawn_process_is_reported_to_plugin() {
+    let (senders, plugin_receiver) = senders_with_plugin_receiver();
+    let _control = spawn_process(senders, "/path/to/nonexistent/command", &[]);
+    let (stdout, stderr, exit_code) = collect_process_events(&plugin_receiver);
+    assert!(stdout.is_empty());
+    assert!(!stderr.is_empty(), "spawn error sent as stderr");
+    assert_eq!(exit_code, None);
+}
+
+// snapshots are saved in a temporary folder rather than the session info cache folder of the
+// machine
+struct SnapshotTestSession(tempfile::TempDir);
+
+impl SnapshotTestSession {
+    fn new() -> Self {
+        SnapshotTestSession(tempfile::tempdir().unwrap())
+    }
+    fn snapshots_folder(&self) -> &Path {
+        self.0.path()
+    }
+}
+
+fn snapshot_kdl_and_files(pane_name: &str) -> (String, BTreeMap<String, String>) {
+    let layout = format!("layout {{\n    pane name=\"{}\"\n}}\n", pane_name);
+    let mut files = BTreeMap::new();
+    files.insert(
+        "pane-contents-1.txt".to_owned(),
+        format!("contents of {}", pane_name),
+    );
+    (layout, files)
+}
+
+#[test]
+fn saved_snapshot_can_be_listed_and_restored() {
+    let session = SnapshotTestSession::new();
+    write_snapshot_to_disk(
+        session.snapshots_folder(),
+        "before-refactor",
+        snapshot_kdl_and_files("editor"),
+    )
+    .unwrap();
+
+    let snapshots = get_session_snapshots(session.snapshots_folder());
+    assert_eq!(snapshots.len(), 1);
+    assert_eq!(snapshots[0].name, "before-refactor");
+
+    let layout = snapshot_layout(session.snapshots_folder(), "before-refactor").unwrap();
+    let (tiled_panes, _floating_panes) = layout.new_tab();
+    assert_eq!(tiled_panes.children[0].name.as_deref(), Some("editor"));
+    let contents_file = session
+        .snapshots_folder()
+        .join("before-refactor")
+        .join("pane-contents-1.txt");
+    assert_eq!(
+        std::fs::read_to_string(contents_file).unwrap(),
+        "contents of editor"
+    );
+}
+
+#[test]
+fn saving_a_snapshot_by_the_same_name_replaces_it() {
+    let session = SnapshotTestSession::new();
+    let mut first_snapshot = snapshot_kdl_and_files("first");
+    first_snapshot
+        .1
+        .insert("stale-file.txt".to_owned(), "stale".to_owned());
+    write_snapshot_to_disk(session.snapshots_folder(), "work", first_snapshot).unwrap();
+    write_snapshot_to_disk(
+        session.snapshots_folder(),
+        "work",
+        snapshot_kdl_and_files("second"),
+    )
+    .unwrap();
+
+    assert_eq!(get_session_snapshots(session.snapshots_folder()).len(), 1);
+    let layout = snapshot_layout(session.snapshots_folder(), "work").unwrap();
+    let (tiled_panes, _floating_panes) = layout.new_tab();
+    assert_eq!(tiled_panes.children[0].name.as_deref(), Some("second"));
+    assert!(
+        !session
+            .snapshots_folder()
+            .join("work")
+            .join("stale-file.txt")
+            .exists(),
+        "files of the replaced snapshot are removed"
+    );
+}
+
+#[test]
+fn deleted_snapshot_is_no_longer_listed_or_restorable() {
+    let session = SnapshotTestSession::new();
+    write_snapshot_to_disk(
+        session.snapshots_folder(),
+        "old",
+        snapshot_kdl_and_files("old"),
+    )
+    .unwrap();
+    write_snapshot_to_disk(
+        session.snapshots_folder(),
+        "new",
+        snapshot_kdl_and_files("new"),
+    )
+    .unwrap();
+
+    delete_session_snapshot(session.snapshots_folder(), "old").unwrap();
+    let snapshot_names: Vec<String> = get_session_snapshots(session.snapshots_folder())
+        .into_iter()
+        .map(|s| s.name)
+        .collect();
+    assert_eq!(snapshot_names, vec!["new".to_owned()]);
+    assert!(snapshot_layout(session.snapshots_folder(), "old").is_err());
+    assert!(delete_session_snapshot(session.snapshots_folder(), "old").is_err());
+}
+
+#[test]
+fn snapshot_names_that_are_paths_are_not_saved() {
+    let session = SnapshotTestSession::new();
+    write_snapshot_to_disk(
+        session.snapshots_folder(),
+        "kept",
+        snapshot_kdl_and_files("kept"),
+    )
+    .unwrap();
+    for snapshot_name in ["..", ".", "../kept", "nested/name", "back\\slash", " "] {
+        assert!(
+            write_snapshot_to_disk(
+                session.snapshots_folder(),
+                snapshot_name,
+                snapshot_kdl_and_files("evil")
+            )
+            .is_err(),
+            "{:?} was saved",
+            snapshot_name
+        );
+    }
+    assert!(
+        session.snapshots_folder().join("kept").exists(),
+        "folders outside the snapshot's aren't removed"
+    );
+    assert_eq!(get_session_snapshots(session.snapshots_folder()).len(), 1);
+}
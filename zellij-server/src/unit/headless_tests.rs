@@ -0,0 +1,293 @@
+use super::HeadlessSession;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+use zellij_utils::data::{BareKey, Event, KeyWithModifier, LayoutInfo};
+use zellij_utils::input::actions::Action;
+use zellij_utils::input::command::TerminalAction;
+use zellij_utils::input::mouse::MouseEvent;
+use zellij_utils::pane_size::Size;
+use zellij_utils::position::Position;
+
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+fn single_pane_layout() -> Option<LayoutInfo> {
+    Some(LayoutInfo::Stringified("layout { pane; }".to_owned()))
+}
+
+fn wait_for_terminal_count(session: &HeadlessSession, count: usize) {
+    let started_at = Instant::now();
+    while session.terminals().len() < count {
+        assert!(
+            started_at.elapsed() < TIMEOUT,
+            "expected {} terminals, found {:?}",
+            count,
+            session.terminals()
+        );
+        std::thread::sleep(Duration::from_millis(10));
+    }
+}
+
+#[test]
+fn renders_terminal_output_to_the_client() {
+    let size = Size { rows: 10, cols: 40 };
+    let mut session = HeadlessSession::start(size, single_pane_layout()).unwrap();
+    wait_for_terminal_count(&session, 1);
+    let terminal_id = *session.terminals().keys().next().unwrap();
+    session
+        .write_to_terminal(terminal_id, b"hello from the terminal")
+        .unwrap();
+    let screen = session
+        .wait_for_text("hello from the terminal", TIMEOUT)
+        .unwrap();
+    assert_eq!(
+        screen.lines().count(),
+        size.rows,
+        "screen has one line per row"
+    );
+}
+
+#[test]
+fn keys_are_written_to_the_focused_terminal() {
+    let size = Size { rows: 10, cols: 40 };
+    let mut session = HeadlessSession::start(size, single_pane_layout()).unwrap();
+    wait_for_terminal_count(&session, 1);
+    let terminal_id = *session.terminals().keys().next().unwrap();
+    session
+        .send_key(KeyWithModifier::new(BareKey::Char('a')))
+        .unwrap();
+    let started_at = Instant::now();
+    while session.terminal_input(terminal_id) != b"a" {
+        assert!(
+            started_at.elapsed() < TIMEOUT,
+            "terminal input was {:?}",
+            session.terminal_input(terminal_id)
+        );
+        std::thread::sleep(Duration::from_millis(10));
+    }
+}
+
+#[test]
+fn actions_change_the_session() {
+    let size = Size { rows: 20, cols: 80 };
+    let mut session = HeadlessSession::start(size, single_pane_layout()).unwrap();
+    wait_for_terminal_count(&session, 1);
+    session
+        .send_action(Action::NewPane {
+            direction: None,
+            pane_name: None,
+            start_suppressed: false,
+        })
+        .unwrap();
+    wait_for_terminal_count(&session, 2);
+}
+
+#[test]
+fn loads_plugins_from_the_layout() {
+    let size = Size { rows: 10, cols: 80 };
+    let layout = LayoutInfo::Stringified(
+        r#"layout { pane size=1 borderless=true { plugin location="zellij:tab-bar"; }; pane; }"#
+            .to_owned(),
+    );
+    let mut session = HeadlessSession::start(size, Some(layout)).unwrap();
+    session.wait_for_text("Tab #1", TIMEOUT).unwrap();
+    session
+        .send_action(Action::NewTab {
+            tiled_layout: None,
+            floating_layouts: vec![],
+            swap_tiled_layouts: None,
+            swap_floating_layouts: None,
+            tab_name: None,
+            should_change_focus_to_new_tab: true,
+            cwd: None,
+            initial_panes: None,
+            first_pane_unblock_condition: None,
+        })
+        .unwrap();
+    session.wait_for_text("Tab #2", TIMEOUT).unwrap();
+}
+
+#[test]
+fn mouse_clicks_change_the_focused_pane() {
+    let size = Size { rows: 10, cols: 80 };
+    let layout = LayoutInfo::Stringified(
+        r#"layout { pane split_direction="vertical" { pane focus=true; pane; }; }"#.to_owned(),
+    );
+    let mut session = HeadlessSession::start(size, Some(layout)).unwrap();
+    wait_for_terminal_count(&session, 2);
+    let right_pane_id = *session.terminals().keys().last().unwrap();
+    session
+        .send_mouse_event(MouseEvent::new_left_press_event(Position::new(5, 60)))
+        .unwrap();
+    session
+        .send_mouse_event(MouseEvent::new_left_release_event(Position::new(5, 60)))
+        .unwrap();
+    session
+        .send_key(KeyWithModifier::new(BareKey::Char('x')))
+        .unwrap();
+    let started_at = Instant::now();
+    while session.terminal_input(right_pane_id) != b"x" {
+        assert!(
+            started_at.elapsed() < TIMEOUT,
+            "terminal input was {:?}",
+            session.terminal_input(right_pane_id)
+        );
+        std::thread::sleep(Duration::from_millis(10));
+    }
+}
+
+#[test]
+fn plugin_timers_fire_when_time_is_advanced() {
+    let size = Size { rows: 20, cols: 80 };
+    let layout = LayoutInfo::Stringified(
+        r#"layout { pane { plugin location="zellij:session-manager"; }; }"#.to_owned(),
+    );
+    let mut session = HeadlessSession::start(size, Some(layout)).unwrap();
+    // the session manager sets a 0.1 second timer when it loads, before it first renders
+    session.wait_for_text("Session Manager", TIMEOUT).unwrap();
+    std::thread::sleep(Duration::from_millis(200));
+    assert!(
+        !session
+            .plugin_events()
+            .iter()
+            .any(|(_, _, event)| matches!(event, Event::Timer(_))),
+        "timer fired before time was advanced"
+    );
+    session.advance_time(Duration::from_millis(100));
+    let timer = session
+        .wait_for_plugin_event(|event| matches!(event, Event::Timer(_)), TIMEOUT)
+        .unwrap();
+    assert_eq!(timer, Event::Timer(0.1));
+}
+
+#[test]
+fn command_pane_exited_hooks_run_for_held_and_closed_panes() {
+    let size = Size { rows: 20, cols: 80 };
+    let hook_output = tempfile::tempdir().unwrap();
+    let hook_output_file = hook_output.path().join("exited");
+    let config = format!(
+        r#"hooks {{ command_pane_exited "echo $ZELLIJ_HOOK_PANE_ID:$ZELLIJ_HOOK_EXIT_CODE >> '{}'"; }}"#,
+        hook_output_file.display()
+    );
+    let layout = LayoutInfo::Stringified(
+        r#"layout { pane command="make"; pane command="cargo" close_on_exit=true; }"#.to_owned(),
+    );
+    let session = HeadlessSession::start_with_config(size, Some(layout), &config).unwrap();
+    wait_for_terminal_count(&session, 2);
+    let terminal_ids: Vec<u32> = session.terminals().keys().copied().collect();
+    session.exit_terminal(terminal_ids[0], Some(0)).unwrap();
+    session.exit_terminal(terminal_ids[1], Some(1)).unwrap();
+
+    let expected = vec![
+        format!("{}:0", terminal_ids[0]),
+        format!("{}:1", terminal_ids[1]),
+    ];
+    let started_at = Instant::now();
+    loop {
+        let mut exited: Vec<String> = std::fs::read_to_string(&hook_output_file)
+            .unwrap_or_default()
+            .lines()
+            .map(|line| line.to_owned())
+            .collect();
+        exited.sort();
+        if exited == expected {
+            break;
+        }
+        assert!(
+            started_at.elapsed() < TIMEOUT,
+            "hooks ran for {:?}, expected {:?}",
+            exited,
+            expected
+        );
+        std::thread::sleep(Duration::from_millis(10));
+    }
+}
+
+#[test]
+fn collects_what_plugins_pipe_out() {
+    let size = Size { rows: 20, cols: 80 };
+    let fixture_plugin = format!(
+        "file:{}/../zellij-utils/assets/plugins/fixture-plugin-for-tests.wasm",
+        env!("CARGO_MANIFEST_DIR")
+    );
+    let layout = LayoutInfo::Stringified(format!(
+        r#"layout {{ pane {{ plugin location="{}"; }}; }}"#,
+        fixture_plugin
+    ));
+    let mut session = HeadlessSession::start(size, Some(layout)).unwrap();
+    session.wait_for_text("Allow? (y/n)", TIMEOUT).unwrap();
+    session
+        .send_key(KeyWithModifier::new(BareKey::Char('y')))
+        .unwrap();
+    session.wait_for_text("Rows:", TIMEOUT).unwrap();
+    session.pipe("pipe_output", None, None).unwrap();
+    let output = session
+        .wait_for_pipe_output("pipe_output", TIMEOUT)
+        .unwrap();
+    assert_eq!(output, vec!["this_is_my_output".to_owned()]);
+}
+
+#[test]
+fn reopened_closed_pane_shows_its_scrollback_and_reruns_on_enter() {
+    let size = Size {
+        rows: 20,
+        cols: 160,
+    };
+    let layout = LayoutInfo::Stringified(
+        r#"layout { pane; pane command="cargo" close_on_exit=true; }"#.to_owned(),
+    );
+    let mut session = HeadlessSession::start(size, Some(layout)).unwrap();
+    wait_for_terminal_count(&session, 2);
+    let command_terminal_id = session
+        .terminals()
+        .into_iter()
+        .find_map(|(terminal_id, terminal_action)| match terminal_action {
+            TerminalAction::RunCommand(_) => Some(terminal_id),
+            _ => None,
+        })
+        .unwrap();
+    session
+        .write_to_terminal(command_terminal_id, b"output of the closed pane")
+        .unwrap();
+    session
+        .wait_for_text("output of the closed pane", TIMEOUT)
+        .unwrap();
+    session.exit_terminal(command_terminal_id, Some(1)).unwrap();
+    session
+        .wait_for_screen(
+            |screen| !screen.contains("output of the closed pane"),
+            TIMEOUT,
+        )
+        .unwrap();
+
+    session
+        .send_action(Action::ReopenClosedPane { id: None })
+        .unwrap();
+    let screen = session
+        .wait_for_screen(
+            |screen| screen.contains("output of the closed pane") && screen.contains("re-run"),
+            TIMEOUT,
+        )
+        .unwrap();
+    assert!(screen.contains("EXIT CODE: 1"), "pane is held: {}", screen);
+    assert_eq!(
+        session.terminals().len(),
+        2,
+        "the command is not run again until the user asks for it"
+    );
+
+    session
+        .send_key(KeyWithModifier::new(BareKey::Enter))
+        .unwrap();
+    wait_for_terminal_count(&session, 3);
+    let rerun_command =
+        session
+            .terminals()
+            .into_iter()
+            .find_map(|(terminal_id, terminal_action)| match terminal_action {
+                TerminalAction::RunCommand(run_command) if terminal_id != command_terminal_id => {
+                    Some(run_command.command)
+                },
+                _ => None,
+            });
+    assert_eq!(rerun_command, Some(PathBuf::from("cargo")));
+}
@@ -1,7 +1,10 @@
-use super::{screen_thread_main, CopyOptions, Screen, ScreenInstruction};
+use super::{
+    screen_thread_main, search_panes, CopyOptions, Screen, ScreenInstruction, SearchablePane,
+    MAX_SEARCH_MATCHES_PER_PANE,
+};
 use crate::panes::PaneId;
 use crate::{
-    channels::SenderWithContext, os_input_output::ServerOsApi, route::route_action,
+    channels::SenderWithContext, os_input_output_fake::FakeInputOutput, route::route_action,
     thread_bus::Bus, ClientId, ServerInstruction, SessionMetaData, ThreadSenders,
 };
 use insta::assert_snapshot;
@@ -9,9 +12,8 @@ use std::net::{IpAddr, Ipv4Addr};
 use std::path::PathBuf;
 use zellij_utils::cli::CliAction;
 use zellij_utils::data::{Event, EventType, Resize, Style, WebSharing};
-use zellij_utils::errors::{prelude::*, ErrorContext};
+use zellij_utils::errors::ErrorContext;
 use zellij_utils::input::actions::Action;
-use zellij_utils::input::command::{RunCommand, TerminalAction};
 use zellij_utils::input::config::Config;
 use zellij_utils::input::layout::{
     FloatingPaneLayout, Layout, PercentOrFixed, PluginAlias, PluginUserConfiguration, Run,
@@ -19,12 +21,11 @@ use zellij_utils::input::layout::{
 };
 use zellij_utils::input::mouse::MouseEvent;
 use zellij_utils::input::options::Options;
-use zellij_utils::ipc::IpcReceiverWithContext;
+use zellij_utils::input::scratchpads::{Scratchpad, Scratchpads};
 use zellij_utils::pane_size::{Size, SizeInPixels};
 use zellij_utils::position::Position;
 
 use crate::background_jobs::BackgroundJob;
-use crate::os_input_output::AsyncReader;
 use crate::pty_writer::PtyWriteInstruction;
 use std::collections::HashSet;
 use std::env::set_var;
@@ -36,14 +37,13 @@ use crate::{
 };
 use zellij_utils::ipc::PixelDimensions;
 
-use interprocess::local_socket::Stream as LocalSocketStream;
 use zellij_utils::{
     channels::{self, ChannelWithContext, Receiver},
     data::{
         Direction, FloatingPaneCoordinates, InputMode, ModeInfo, NewPanePlacement, Palette,
         PluginCapabilities,
     },
-    ipc::{ClientAttributes, ClientToServerMsg, ServerToClientMsg},
+    ipc::{ClientAttributes, ServerToClientMsg},
 };
 
 use crate::panes::grid::Grid;
@@ -159,105 +159,6 @@ fn send_cli_action_to_server(
     }
 }
 
-#[derive(Clone, Default)]
-struct FakeInputOutput {
-    fake_filesystem: Arc<Mutex<HashMap<String, String>>>,
-    server_to_client_messages: Arc<Mutex<HashMap<ClientId, Vec<ServerToClientMsg>>>>,
-}
-
-impl ServerOsApi for FakeInputOutput {
-    fn set_terminal_size_using_terminal_id(
-        &self,
-        _terminal_id: u32,
-        _cols: u16,
-        _rows: u16,
-        _width_in_pixels: Option<u16>,
-        _height_in_pixels: Option<u16>,
-    ) -> Result<()> {
-        // noop
-        Ok(())
-    }
-    fn spawn_terminal(
-        &self,
-        _file_to_open: TerminalAction,
-        _quit_db: Box<dyn Fn(PaneId, Option<i32>, RunCommand) + Send>,
-        _default_editor: Option<PathBuf>,
-    ) -> Result<(u32, Box<dyn AsyncReader>, Option<u32>)> {
-        unimplemented!()
-    }
-    fn write_to_tty_stdin(&self, _id: u32, _buf: &[u8]) -> Result<usize> {
-        unimplemented!()
-    }
-    fn tcdrain(&self, _id: u32) -> Result<()> {
-        unimplemented!()
-    }
-    fn kill(&self, _pid: u32) -> Result<()> {
-        unimplemented!()
-    }
-    fn force_kill(&self, _pid: u32) -> Result<()> {
-        unimplemented!()
-    }
-    fn box_clone(&self) -> Box<dyn ServerOsApi> {
-        Box::new((*self).clone())
-    }
-    fn send_to_client(&self, client_id: ClientId, msg: ServerToClientMsg) -> Result<()> {
-        self.server_to_client_messages
-            .lock()
-            .unwrap()
-            .entry(client_id)
-            .or_insert_with(Vec::new)
-            .push(msg);
-        Ok(())
-    }
-    fn new_client(
-        &mut self,
-        _client_id: ClientId,
-        _stream: LocalSocketStream,
-    ) -> Result<IpcReceiverWithContext<ClientToServerMsg>> {
-        unimplemented!()
-    }
-    fn new_client_with_reply(
-        &mut self,
-        _client_id: ClientId,
-        _stream: LocalSocketStream,
-        _reply_stream: LocalSocketStream,
-    ) -> Result<IpcReceiverWithContext<ClientToServerMsg>> {
-        unimplemented!()
-    }
-    fn remove_client(&mut self, _client_id: ClientId) -> Result<()> {
-        unimplemented!()
-    }
-    fn load_palette(&self) -> Palette {
-        unimplemented!()
-    }
-    fn get_cwd(&self, _pid: u32) -> Option<PathBuf> {
-        unimplemented!()
-    }
-    fn write_to_file(&mut self, contents: String, filename: Option<String>) -> Result<()> {
-        if let Some(filename) = filename {
-            self.fake_filesystem
-                .lock()
-                .unwrap()
-                .insert(filename, contents);
-        }
-        Ok(())
-    }
-    fn re_run_command_in_terminal(
-        &self,
-        _terminal_id: u32,
-        _run_command: RunCommand,
-        _quit_cb: Box<dyn Fn(PaneId, Option<i32>, RunCommand) + Send>,
-    ) -> Result<(Box<dyn AsyncReader>, Option<u32>)> {
-        unimplemented!()
-    }
-    fn clear_terminal_id(&self, _terminal_id: u32) -> Result<()> {
-        unimplemented!()
-    }
-    fn send_sigint(&self, _pid: u32) -> Result<()> {
-        unimplemented!()
-    }
-}
-
 fn create_new_screen(
     size: Size,
     advanced_mouse_actions: bool,
@@ -327,6 +228,7 @@ fn create_new_screen(
         false, // mouse_click_through
         web_server_ip,
         web_server_port,
+        Scratchpads::default(),
     );
     screen
 }
@@ -378,6 +280,7 @@ impl MockScreen {
         )
         .should_silently_fail();
         let debug = false;
+        let mouse_regions = self.session_metadata.mouse_regions.clone();
         let screen_thread = std::thread::Builder::new()
             .name("screen_thread".to_string())
             .spawn(move || {
@@ -389,6 +292,8 @@ impl MockScreen {
                     config,
                     debug,
                     Box::new(Layout::default()),
+                    Some("zellij-test".to_owned()),
+                    mouse_regions,
                 )
                 .expect("TEST")
             })
@@ -464,6 +369,7 @@ impl MockScreen {
         )
         .should_silently_fail();
         let debug = false;
+        let mouse_regions = self.session_metadata.mouse_regions.clone();
         let screen_thread = std::thread::Builder::new()
             .name("screen_thread".to_string())
             .spawn(move || {
@@ -475,6 +381,8 @@ impl MockScreen {
                     config,
                     debug,
                     Box::new(Layout::default()),
+                    Some("zellij-test".to_owned()),
+                    mouse_regions,
                 )
                 .expect("TEST")
             })
@@ -649,6 +557,7 @@ impl MockScreen {
             session_configuration: self.session_metadata.session_configuration.clone(),
             layout,
             current_input_modes: self.session_metadata.current_input_modes.clone(),
+            mouse_regions: self.session_metadata.mouse_regions.clone(),
             web_sharing: WebSharing::Off,
             config_file_path: self.session_metadata.config_file_path.clone(),
         }
@@ -709,6 +618,7 @@ impl MockScreen {
             layout,
             session_configuration: Default::default(),
             current_input_modes: HashMap::new(),
+            mouse_regions: Default::default(),
             web_sharing: WebSharing::Off,
             config_file_path: None,
         };
@@ -3221,6 +3131,45 @@ pub fn send_cli_new_pane_action_with_command_and_cwd() {
     assert_snapshot!(format!("{:?}", new_pane_instruction));
 }
 
+#[test]
+pub fn send_cli_toggle_scratchpad_twice_before_it_opens_spawns_it_once() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut mock_screen = MockScreen::new(size);
+    mock_screen.config.scratchpads = Scratchpads(std::collections::BTreeMap::from([(
+        "notes".to_owned(),
+        Scratchpad::default(),
+    )]));
+    let pty_receiver = mock_screen.pty_receiver.take().unwrap();
+    let session_metadata = mock_screen.clone_session_metadata();
+    let screen_thread = mock_screen.run(None, vec![]);
+    let received_pty_instructions = Arc::new(Mutex::new(vec![]));
+    let pty_thread = log_actions_in_thread!(
+        received_pty_instructions,
+        PtyInstruction::Exit,
+        pty_receiver
+    );
+    let toggle_scratchpad = CliAction::ToggleScratchpad {
+        name: "notes".to_owned(),
+    };
+    // the fake pty thread never opens the scratchpad
+    send_cli_action_to_server(&session_metadata, toggle_scratchpad.clone(), client_id);
+    send_cli_action_to_server(&session_metadata, toggle_scratchpad, client_id);
+    std::thread::sleep(std::time::Duration::from_millis(100)); // give time for actions to be
+    mock_screen.teardown(vec![pty_thread, screen_thread]);
+
+    let spawn_scratchpad_count = received_pty_instructions
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|instruction| matches!(instruction, PtyInstruction::SpawnScratchpad { .. }))
+        .count();
+    assert_eq!(spawn_scratchpad_count, 1);
+}
+
 #[test]
 pub fn send_cli_new_pane_action_with_floating_pane_and_coordinates() {
     let size = Size {
@@ -3567,6 +3516,52 @@ pub fn send_cli_close_pane_action() {
     assert_snapshot!(format!("{}", snapshot_count));
 }
 
+#[test]
+pub fn closing_a_terminal_pane_adds_it_to_the_closed_panes() {
+    let size = Size { cols: 80, rows: 10 };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut initial_layout = TiledPaneLayout::default();
+    initial_layout.children_split_direction = SplitDirection::Vertical;
+    initial_layout.children = vec![TiledPaneLayout::default(), TiledPaneLayout::default()];
+    let mut mock_screen = MockScreen::new(size);
+    let session_metadata = mock_screen.clone_session_metadata();
+    let screen_thread = mock_screen.run(Some(initial_layout), vec![]);
+    let received_pty_instructions = Arc::new(Mutex::new(vec![]));
+    let pty_receiver = mock_screen.pty_receiver.take().unwrap();
+    let pty_thread = log_actions_in_thread!(
+        received_pty_instructions,
+        PtyInstruction::Exit,
+        pty_receiver
+    );
+    let _ = mock_screen.to_screen.send(ScreenInstruction::PtyBytes(
+        0,
+        "output of a closed pane".as_bytes().to_vec(),
+    ));
+    let close_pane_action = CliAction::ClosePane { pane_id: None };
+    send_cli_action_to_server(&session_metadata, close_pane_action, client_id);
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![pty_thread, screen_thread]);
+    let closed_pane =
+        received_pty_instructions.lock().unwrap().iter().find_map(
+            |instruction| match instruction {
+                PtyInstruction::AddClosedPane(terminal_id, closed_pane) => {
+                    Some((*terminal_id, closed_pane.clone()))
+                },
+                _ => None,
+            },
+        );
+    let (terminal_id, closed_pane) = closed_pane.expect("closed pane was not added");
+    assert_eq!(terminal_id, 0);
+    assert_eq!(
+        closed_pane.scrollback_lines(),
+        vec!["output of a closed pane".to_owned()]
+    );
+    assert!(closed_pane
+        .serialized_scrollback
+        .contains("output of a closed pane"));
+    assert!(closed_pane.run_command.is_none());
+}
+
 #[test]
 pub fn send_cli_new_tab_action_default_params() {
     let size = Size { cols: 80, rows: 10 };
@@ -3589,6 +3584,7 @@ pub fn send_cli_new_tab_action_default_params() {
         layout: None,
         layout_string: None,
         layout_dir: None,
+        layout_vars: vec![],
         cwd: None,
         initial_command: vec![],
         initial_plugin: None,
@@ -3637,6 +3633,7 @@ pub fn send_cli_new_tab_action_with_name_and_layout() {
         ))),
         layout_string: None,
         layout_dir: None,
+        layout_vars: vec![],
         cwd: None,
         initial_command: vec![],
         initial_plugin: None,
@@ -5295,6 +5292,7 @@ fn create_new_screen_with_message_capture(
         false, // mouse_click_through
         web_server_ip,
         web_server_port,
+        Scratchpads::default(),
     );
     (screen, messages)
 }
@@ -7702,6 +7700,7 @@ pub fn send_cli_new_tab_action_with_layout_string() {
         layout: None,
         layout_string: Some("layout {\n    pane\n    pane\n    pane\n}\n".into()),
         layout_dir: None,
+        layout_vars: vec![],
         cwd: None,
         initial_command: vec![],
         initial_plugin: None,
@@ -7756,6 +7755,7 @@ pub fn send_cli_new_tab_action_with_layout_string_and_name() {
         layout: None,
         layout_string: Some("layout {\n    pane\n    pane\n    pane\n}\n".into()),
         layout_dir: None,
+        layout_vars: vec![],
         cwd: None,
         initial_command: vec![],
         initial_plugin: None,
@@ -8077,3 +8077,115 @@ pub fn send_cli_new_pane_action_with_tab_id_and_stacked() {
         pty_debug
     );
 }
+
+fn send_pane_to_session_error(
+    session_name: &str,
+    pane_id: Option<PaneId>,
+) -> Vec<ServerToClientMsg> {
+    let size = Size { cols: 80, rows: 20 };
+    let client_id = 1;
+    let (mut screen, messages) = create_new_screen_with_message_capture(size);
+    new_tab(&mut screen, 1, 0);
+    screen
+        .send_pane_to_session(session_name.to_owned(), pane_id, client_id, None)
+        .unwrap();
+    let messages = messages.lock().unwrap();
+    messages.get(&client_id).cloned().unwrap_or_default()
+}
+
+#[test]
+fn send_pane_to_session_rejects_the_current_session() {
+    let messages = send_pane_to_session_error("zellij-test", None);
+    assert_eq!(
+        messages,
+        vec![ServerToClientMsg::LogError {
+            lines: vec!["The pane is already in this session.".to_owned()]
+        }]
+    );
+}
+
+#[test]
+fn send_pane_to_session_rejects_plugin_panes() {
+    let messages = send_pane_to_session_error("other-session", Some(PaneId::Plugin(1)));
+    assert_eq!(
+        messages,
+        vec![ServerToClientMsg::LogError {
+            lines: vec!["Only terminal panes can be sent to another session.".to_owned()]
+        }]
+    );
+}
+
+#[test]
+fn send_pane_to_session_rejects_missing_panes() {
+    let messages = send_pane_to_session_error("other-session", Some(PaneId::Terminal(99)));
+    assert_eq!(
+        messages,
+        vec![ServerToClientMsg::LogError {
+            lines: vec!["Could not find terminal pane 99.".to_owned()]
+        }]
+    );
+}
+
+fn searchable_pane(tab_position: usize, terminal_id: u32, lines: &[&str]) -> SearchablePane {
+    SearchablePane {
+        tab_position,
+        tab_name: format!("Tab #{}", tab_position + 1),
+        pane_id: PaneId::Terminal(terminal_id),
+        pane_title: format!("Pane #{}", terminal_id),
+        lines: lines.iter().map(|line| line.to_string()).collect(),
+    }
+}
+
+#[test]
+fn search_panes_reports_the_first_match_of_each_line() {
+    let panes = vec![
+        searchable_pane(
+            0,
+            1,
+            &[
+                "cargo build",
+                "error: mismatched types  ",
+                "Error and error",
+            ],
+        ),
+        searchable_pane(0, 2, &["nothing to see here"]),
+        searchable_pane(1, 3, &["ERROR"]),
+    ];
+    let results = search_panes(panes, "error", false);
+    let matches: Vec<(u32, usize, usize, &str)> = results
+        .iter()
+        .flat_map(|result| {
+            result.matches.iter().map(move |search_match| {
+                let zellij_utils::data::PaneId::Terminal(terminal_id) = result.pane_id else {
+                    panic!("only terminal panes are searched");
+                };
+                (
+                    terminal_id,
+                    search_match.line_index,
+                    search_match.column,
+                    search_match.line.as_str(),
+                )
+            })
+        })
+        .collect();
+    assert_eq!(
+        matches,
+        vec![
+            (1, 1, 0, "error: mismatched types"),
+            (1, 2, 0, "Error and error"),
+            (3, 0, 0, "ERROR"),
+        ]
+    );
+    assert_eq!(results[1].tab_position, 1);
+
+    let panes = vec![searchable_pane(0, 1, &["Error and error"])];
+    let results = search_panes(panes, "error", true);
+    assert_eq!(results[0].matches[0].column, 10, "case sensitive match");
+}
+
+#[test]
+fn search_panes_caps_the_matches_per_pane() {
+    let lines = vec!["match"; MAX_SEARCH_MATCHES_PER_PANE + 10];
+    let results = search_panes(vec![searchable_pane(0, 1, &lines)], "match", false);
+    assert_eq!(results[0].matches.len(), MAX_SEARCH_MATCHES_PER_PANE);
+}
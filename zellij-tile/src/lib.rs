@@ -46,6 +46,16 @@ pub trait ZellijPlugin: Default {
     /// Will be called either after an `update` that requested it, or when the plugin otherwise needs to be re-rendered (eg. on startup, or when the plugin is resized).
     /// The `rows` and `cols` values represent the "content size" of the plugin (this will not include its surrounding frame if the user has pane frames enabled).
     fn render(&mut self, rows: usize, cols: usize) {}
+    /// Will be called before the plugin is reloaded and, if the plugin was called since its state
+    /// was last saved, when the session is serialized for resurrection. The returned bytes are handed back to [`restore_state`](ZellijPlugin::restore_state)
+    /// once the plugin is loaded again with the same location and configuration.
+    /// Returning an empty `Vec` (the default) means there is nothing to save.
+    fn save_state(&self) -> Vec<u8> {
+        vec![]
+    }
+    /// Will be called right after `load` with the bytes previously returned from [`save_state`](ZellijPlugin::save_state),
+    /// if the plugin is being reloaded or its session is being resurrected.
+    fn restore_state(&mut self, state: Vec<u8>) {}
 }
 
 /// This trait is used to create workers. Workers can be used by plugins to run longer running
@@ -164,6 +174,22 @@ macro_rules! register_plugin {
             });
         }
 
+        #[no_mangle]
+        pub fn save_state() {
+            STATE.with(|state| {
+                let saved_state: Vec<u8> = state.borrow().save_state();
+                $crate::shim::object_to_stdout(&saved_state);
+            });
+        }
+
+        #[no_mangle]
+        pub fn restore_state() {
+            STATE.with(|state| {
+                let saved_state: Vec<u8> = $crate::shim::object_from_stdin().unwrap();
+                state.borrow_mut().restore_state(saved_state);
+            });
+        }
+
         #[no_mangle]
         pub fn plugin_version() {
             println!("{}", $crate::prelude::VERSION);
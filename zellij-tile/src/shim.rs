@@ -7,6 +7,7 @@ use std::{
 use zellij_utils::data::*;
 use zellij_utils::errors::prelude::*;
 use zellij_utils::input::actions::Action;
+pub use zellij_utils::input::layout::SplitDirection;
 pub use zellij_utils::plugin_api;
 use zellij_utils::plugin_api::event::ProtobufPaneScrollbackResponse;
 use zellij_utils::plugin_api::generated_api::api::plugin_command::{
@@ -20,24 +21,28 @@ use zellij_utils::plugin_api::plugin_command::{
     ProtobufCurrentSessionLastSavedTimeResponse, ProtobufDeleteLayoutResponse,
     ProtobufDumpLayoutResponse, ProtobufDumpSessionLayoutResponse, ProtobufEditLayoutResponse,
     ProtobufFocusOrCreateTabResponse, ProtobufGenerateRandomNameResponse,
-    ProtobufGetFocusedPaneInfoResponse, ProtobufGetLayoutDirResponse, ProtobufGetPaneCwdResponse,
-    ProtobufGetPaneInfoResponse, ProtobufGetPanePidResponse, ProtobufGetPaneRunningCommandResponse,
+    ProtobufGetClosedPaneScrollbackResponse, ProtobufGetFocusedPaneInfoResponse,
+    ProtobufGetLayoutDirResponse, ProtobufGetPaneCwdResponse, ProtobufGetPaneInfoResponse,
+    ProtobufGetPanePidResponse, ProtobufGetPaneRunningCommandResponse,
     ProtobufGetSessionEnvironmentVariablesResponse, ProtobufGetTabInfoResponse,
-    ProtobufHideFloatingPanesResponse, ProtobufNewTabResponse, ProtobufNewTabsResponse,
-    ProtobufOpenCommandPaneBackgroundResponse, ProtobufOpenCommandPaneFloatingNearPluginResponse,
-    ProtobufOpenCommandPaneFloatingResponse, ProtobufOpenCommandPaneInPlaceOfPaneIdResponse,
-    ProtobufOpenCommandPaneInPlaceOfPluginResponse, ProtobufOpenCommandPaneInPlaceResponse,
-    ProtobufOpenCommandPaneNearPluginResponse, ProtobufOpenCommandPaneResponse,
-    ProtobufOpenEditPaneInPlaceOfPaneIdResponse, ProtobufOpenFileFloatingNearPluginResponse,
-    ProtobufOpenFileFloatingResponse, ProtobufOpenFileInPlaceOfPluginResponse,
-    ProtobufOpenFileInPlaceResponse, ProtobufOpenFileNearPluginResponse, ProtobufOpenFileResponse,
-    ProtobufOpenPaneInNewTabResponse, ProtobufOpenPluginPaneFloatingResponse,
-    ProtobufOpenTerminalFloatingNearPluginResponse, ProtobufOpenTerminalFloatingResponse,
-    ProtobufOpenTerminalInPlaceOfPluginResponse, ProtobufOpenTerminalInPlaceResponse,
-    ProtobufOpenTerminalNearPluginResponse, ProtobufOpenTerminalPaneInPlaceOfPaneIdResponse,
-    ProtobufOpenTerminalResponse, ProtobufParseLayoutResponse, ProtobufPluginCommand,
+    ProtobufHideFloatingPanesResponse, ProtobufListClosedPanesResponse,
+    ProtobufListPluginPermissionsResponse, ProtobufListSessionSnapshotsResponse,
+    ProtobufNewTabResponse, ProtobufNewTabsResponse, ProtobufOpenCommandPaneBackgroundResponse,
+    ProtobufOpenCommandPaneFloatingNearPluginResponse, ProtobufOpenCommandPaneFloatingResponse,
+    ProtobufOpenCommandPaneInPlaceOfPaneIdResponse, ProtobufOpenCommandPaneInPlaceOfPluginResponse,
+    ProtobufOpenCommandPaneInPlaceResponse, ProtobufOpenCommandPaneNearPluginResponse,
+    ProtobufOpenCommandPaneResponse, ProtobufOpenEditPaneInPlaceOfPaneIdResponse,
+    ProtobufOpenFileFloatingNearPluginResponse, ProtobufOpenFileFloatingResponse,
+    ProtobufOpenFileInPlaceOfPluginResponse, ProtobufOpenFileInPlaceResponse,
+    ProtobufOpenFileNearPluginResponse, ProtobufOpenFileResponse, ProtobufOpenPaneInNewTabResponse,
+    ProtobufOpenPluginPaneFloatingResponse, ProtobufOpenTerminalFloatingNearPluginResponse,
+    ProtobufOpenTerminalFloatingResponse, ProtobufOpenTerminalInPlaceOfPluginResponse,
+    ProtobufOpenTerminalInPlaceResponse, ProtobufOpenTerminalNearPluginResponse,
+    ProtobufOpenTerminalPaneInPlaceOfPaneIdResponse, ProtobufOpenTerminalResponse,
+    ProtobufParseLayoutResponse, ProtobufPermissionType, ProtobufPluginCommand,
     ProtobufRenameLayoutResponse, ProtobufSaveLayoutResponse, ProtobufSaveSessionResponse,
-    ProtobufShowFloatingPanesResponse, RenameWebTokenResponse, RevokeAllWebTokensResponse,
+    ProtobufSearchAllPanesResponse, ProtobufShowFloatingPanesResponse,
+    ProtobufSpawnProcessResponse, RenameWebTokenResponse, RevokeAllWebTokensResponse,
     RevokeTokenResponse,
 };
 use zellij_utils::plugin_api::plugin_ids::{ProtobufPluginIds, ProtobufZellijVersion};
@@ -93,6 +98,48 @@ pub fn request_permission(permissions: &[PermissionType]) {
     unsafe { host_run_plugin_command() };
 }
 
+/// Returns the permissions granted to each plugin, keyed by plugin url
+pub fn list_plugin_permissions() -> BTreeMap<String, Vec<PermissionType>> {
+    let plugin_command = PluginCommand::ListPluginPermissions;
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+    match bytes_from_stdin() {
+        Ok(bytes_from_stdin) => {
+            ProtobufListPluginPermissionsResponse::decode(bytes_from_stdin.as_slice())
+                .map(|response| {
+                    response
+                        .grants
+                        .into_iter()
+                        .map(|grant| {
+                            let permissions = grant
+                                .permissions
+                                .iter()
+                                .filter_map(|p| ProtobufPermissionType::from_i32(*p))
+                                .filter_map(|p| PermissionType::try_from(p).ok())
+                                .collect();
+                            (grant.plugin_url, permissions)
+                        })
+                        .collect()
+                })
+                .unwrap_or_default()
+        },
+        Err(e) => {
+            eprintln!("{}", e);
+            BTreeMap::new()
+        },
+    }
+}
+
+/// Revoke a single permission previously granted to the plugin with this url, the plugin will be
+/// prompted again the next time it requests it
+pub fn revoke_plugin_permission(plugin_url: &str, permission: PermissionType) {
+    let plugin_command = PluginCommand::RevokePluginPermission(plugin_url.to_owned(), permission);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
 // Query Functions
 /// Returns the unique Zellij pane ID for the plugin as well as the Zellij process id.
 pub fn get_plugin_ids() -> PluginIds {
@@ -854,6 +901,76 @@ pub fn run_command_with_env_variables_and_cwd(
     unsafe { host_run_plugin_command() };
 }
 
+/// Spawn a long running process on the host machine, returning a handle to it (or `None` if it
+/// could not be started). Its output is streamed through the `SpawnedProcessStdout` and
+/// `SpawnedProcessStderr` Events and its exit through the `SpawnedProcessExited` Event, all of
+/// which include the handle and the context provided here
+pub fn spawn_process(cmd: &[&str], context: BTreeMap<String, String>) -> Option<u32> {
+    spawn_process_with_env_variables_and_cwd(cmd, BTreeMap::new(), PathBuf::from("."), context)
+}
+
+/// Spawn a long running process on the host machine, providing environment variables and a cwd.
+/// See `spawn_process` for how its output is delivered
+pub fn spawn_process_with_env_variables_and_cwd(
+    cmd: &[&str],
+    env_variables: BTreeMap<String, String>,
+    cwd: PathBuf,
+    context: BTreeMap<String, String>,
+) -> Option<u32> {
+    let plugin_command = PluginCommand::SpawnProcess(
+        cmd.iter().cloned().map(|s| s.to_owned()).collect(),
+        env_variables,
+        cwd,
+        context,
+    );
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+    match bytes_from_stdin() {
+        Ok(bytes_from_stdin) => ProtobufSpawnProcessResponse::decode(bytes_from_stdin.as_slice())
+            .ok()
+            .map(|response| response.process_id),
+        Err(e) => {
+            eprintln!("{}", e);
+            None
+        },
+    }
+}
+
+/// Write bytes to the STDIN of a process started with `spawn_process`
+pub fn write_to_process_stdin(process_id: u32, bytes: Vec<u8>) {
+    let plugin_command = PluginCommand::WriteToProcessStdin(process_id, bytes);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Close the STDIN of a process started with `spawn_process`, so that commands reading until the
+/// end of their input (eg. `cat` or `wc`) can finish
+pub fn close_process_stdin(process_id: u32) {
+    let plugin_command = PluginCommand::CloseProcessStdin(process_id);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Forcefully kill a process started with `spawn_process`
+pub fn kill_process(process_id: u32) {
+    let plugin_command = PluginCommand::KillProcess(process_id);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Send a signal by name (eg. "SIGTERM", "SIGINT" or "SIGHUP") to a process started with
+/// `spawn_process`
+pub fn signal_process(process_id: u32, signal: &str) {
+    let plugin_command = PluginCommand::SignalProcess(process_id, signal.to_owned());
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
 /// Make a web request, optionally being notified of its output
 /// if subscribed to the `WebRequestResult` Event, the context will be returned verbatim in this
 /// event and can be used for eg. marking the request_id
@@ -934,7 +1051,17 @@ pub fn new_tabs_with_layout(layout: &str) -> Vec<usize> {
 
 /// Provide a LayoutInfo to be applied to the current session in a new tab. If the layout has multiple tabs, they will all be opened.
 pub fn new_tabs_with_layout_info<L: AsRef<LayoutInfo>>(layout_info: L) -> Vec<usize> {
-    let plugin_command = PluginCommand::NewTabsWithLayoutInfo(layout_info.as_ref().clone());
+    new_tabs_with_layout_info_and_vars(layout_info, BTreeMap::new())
+}
+
+/// Like `new_tabs_with_layout_info`, providing values for the params declared in the layout
+/// (params left out fall back to their defaults)
+pub fn new_tabs_with_layout_info_and_vars<L: AsRef<LayoutInfo>>(
+    layout_info: L,
+    layout_vars: BTreeMap<String, String>,
+) -> Vec<usize> {
+    let plugin_command =
+        PluginCommand::NewTabsWithLayoutInfo(layout_info.as_ref().clone(), layout_vars);
     let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
     object_to_stdout(&protobuf_plugin_command.encode_to_vec());
     unsafe { host_run_plugin_command() };
@@ -1189,6 +1316,16 @@ pub fn move_pane_with_direction(direction: Direction) {
     unsafe { host_run_plugin_command() };
 }
 
+/// Give all tiled panes in the current tab equal sizes, or if a direction is specified, only the
+/// panes in the split of that direction (eg. `Vertical` for side by side panes) that holds the
+/// focused pane. Fixed size panes keep their size.
+pub fn equalize_panes(direction: Option<SplitDirection>) {
+    let plugin_command = PluginCommand::EqualizePanes(direction);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
 /// Clear the scroll buffer of the focused pane
 pub fn clear_screen() {
     let plugin_command = PluginCommand::ClearScreen;
@@ -1588,7 +1725,8 @@ where
 }
 
 /// List Windows volumes (drives and WSL distributions).
-/// Results are returned via the `FileSystemUpdate` event.
+/// Results are returned via the `HostFolderScanned` event if the plugin is subscribed to it, and
+/// via the `FileSystemUpdate` event otherwise.
 /// This command is only supported on Windows and requires FullHdAccess permission.
 pub fn list_windows_volumes() {
     let plugin_command = PluginCommand::ListWindowsVolumes;
@@ -1599,6 +1737,9 @@ pub fn list_windows_volumes() {
 
 /// Scan a specific folder in the host filesystem (this is a hack around some WASI runtime performance
 /// issues), will not follow symlinks
+/// Results are returned via the `HostFolderScanned` event if the plugin is subscribed to it, and
+/// via the `FileSystemUpdate` event otherwise (where they can't be told apart from changes
+/// reported by `watch_filesystem`).
 pub fn scan_host_folder<S: AsRef<Path>>(folder_to_scan: &S) {
     let plugin_command = PluginCommand::ScanHostFolder(folder_to_scan.as_ref().to_path_buf());
     let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
@@ -1657,6 +1798,39 @@ fn dump_session_layout_impl(
     }
 }
 
+/// Get the serialized layout a resurrectable session was last saved with, in KDL format
+pub fn get_resurrectable_session_layout(session_name: &str) -> Result<String, String> {
+    let plugin_command = PluginCommand::GetResurrectableSessionLayout(session_name.to_owned());
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+
+    unsafe { host_run_plugin_command() };
+
+    let response_bytes =
+        bytes_from_stdin().map_err(|e| format!("Failed to read response from stdin: {:?}", e))?;
+    let protobuf_response = ProtobufDumpSessionLayoutResponse::decode(response_bytes.as_slice())
+        .map_err(|e| format!("Failed to decode protobuf response: {}", e))?;
+    match protobuf_response.result {
+        Some(dump_session_layout_response::Result::LayoutContent(content)) => Ok(content),
+        Some(dump_session_layout_response::Result::Error(error)) => Err(error),
+        None => Err("Server returned empty response".to_string()),
+    }
+}
+
+/// List the named snapshots saved for a session, most recent first - a snapshot can be resurrected
+/// by switching to its (exited) session with `switch_session_with_layout` and its `layout_path`
+pub fn list_session_snapshots(session_name: &str) -> Vec<SessionSnapshot> {
+    let plugin_command = PluginCommand::ListSessionSnapshots(session_name.to_owned());
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+    bytes_from_stdin()
+        .ok()
+        .and_then(|bytes| ProtobufListSessionSnapshotsResponse::decode(bytes.as_slice()).ok())
+        .map(|response| response.snapshots.into_iter().map(|s| s.into()).collect())
+        .unwrap_or_default()
+}
+
 /// Parses a KDL layout string and returns LayoutMetadata
 pub fn parse_layout(layout_string: &str) -> Result<LayoutMetadata, LayoutParsingError> {
     let plugin_command = PluginCommand::ParseLayout(layout_string.to_string());
@@ -1797,6 +1971,111 @@ pub fn get_pane_scrollback(
     }
 }
 
+/// Search the viewport and scrollback of every terminal pane in the session
+///
+/// # Arguments
+/// * `query` - The text to look for
+/// * `case_sensitive` - Whether the case of the query must match
+///
+/// # Returns
+/// * `Ok(Vec<PaneSearchResult>)` - The matching panes ordered by tab, only the first match in
+///   each line is reported and the number of matches is capped
+/// * `Err(String)` - An error message if the query was empty or the search timed out
+pub fn search_all_panes(
+    query: &str,
+    case_sensitive: bool,
+) -> Result<Vec<PaneSearchResult>, String> {
+    let plugin_command = PluginCommand::SearchAllPanes {
+        query: query.to_owned(),
+        case_sensitive,
+    };
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+
+    let response_bytes =
+        bytes_from_stdin().map_err(|e| format!("Failed to read response from stdin: {:?}", e))?;
+    let protobuf_response = ProtobufSearchAllPanesResponse::decode(response_bytes.as_slice())
+        .map_err(|e| format!("Failed to decode protobuf response: {}", e))?;
+    let response = SearchAllPanesResponse::try_from(protobuf_response)
+        .map_err(|e| format!("Failed to convert protobuf response: {}", e))?;
+    match response {
+        SearchAllPanesResponse::Ok(results) => Ok(results),
+        SearchAllPanesResponse::Err(error_msg) => Err(error_msg),
+    }
+}
+
+/// List the terminal panes that were recently closed in this session (the history is bounded)
+///
+/// # Returns
+/// * `Ok(Vec<ClosedPaneInfo>)` - The closed panes, most recently closed first
+/// * `Err(String)` - An error message if the history could not be retrieved
+pub fn list_closed_panes() -> Result<Vec<ClosedPaneInfo>, String> {
+    let plugin_command = PluginCommand::ListClosedPanes;
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+
+    let response_bytes =
+        bytes_from_stdin().map_err(|e| format!("Failed to read response from stdin: {:?}", e))?;
+    let protobuf_response = ProtobufListClosedPanesResponse::decode(response_bytes.as_slice())
+        .map_err(|e| format!("Failed to decode protobuf response: {}", e))?;
+    let response = ListClosedPanesResponse::try_from(protobuf_response)
+        .map_err(|e| format!("Failed to convert protobuf response: {}", e))?;
+    match response {
+        ListClosedPanesResponse::Ok(closed_panes) => Ok(closed_panes),
+        ListClosedPanesResponse::Err(error_msg) => Err(error_msg),
+    }
+}
+
+/// Get the final scrollback of a recently closed terminal pane (by its `ClosedPaneInfo::id`)
+///
+/// # Returns
+/// * `Ok(Vec<String>)` - The scrollback and viewport of the pane as plain text lines
+/// * `Err(String)` - An error message if the pane is no longer in the history
+pub fn get_closed_pane_scrollback(id: u32) -> Result<Vec<String>, String> {
+    let plugin_command = PluginCommand::GetClosedPaneScrollback(id);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+
+    let response_bytes =
+        bytes_from_stdin().map_err(|e| format!("Failed to read response from stdin: {:?}", e))?;
+    let protobuf_response =
+        ProtobufGetClosedPaneScrollbackResponse::decode(response_bytes.as_slice())
+            .map_err(|e| format!("Failed to decode protobuf response: {}", e))?;
+    let response = GetClosedPaneScrollbackResponse::try_from(protobuf_response)
+        .map_err(|e| format!("Failed to convert protobuf response: {}", e))?;
+    match response {
+        GetClosedPaneScrollbackResponse::Ok(lines) => Ok(lines),
+        GetClosedPaneScrollbackResponse::Err(error_msg) => Err(error_msg),
+    }
+}
+
+/// Reopen a recently closed terminal pane (by its `ClosedPaneInfo::id`) next to the focused pane.
+/// The pane shows its final scrollback and is held until the user re-runs its command in place.
+pub fn reopen_closed_pane(id: u32) {
+    let plugin_command = PluginCommand::ReopenClosedPane(id);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Focus a pane and scroll a search match into view, highlighting the query in its viewport as
+/// with the built-in search. `line_index` is the `SearchMatch::line_index` returned by
+/// `search_all_panes`.
+pub fn reveal_search_match(pane_id: PaneId, query: &str, case_sensitive: bool, line_index: usize) {
+    let plugin_command = PluginCommand::RevealSearchMatch {
+        pane_id,
+        query: query.to_owned(),
+        case_sensitive,
+        line_index,
+    };
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
 /// Write bytes to the `STDIN` of the specified pane
 pub fn write_to_pane_id(bytes: Vec<u8>, pane_id: PaneId) {
     let plugin_command = PluginCommand::WriteToPaneId(bytes, pane_id);
@@ -2427,6 +2706,19 @@ pub fn set_pane_borderless(pane_id: PaneId, borderless: bool) {
     unsafe { host_run_plugin_command() };
 }
 
+/// Lock or unlock the input of a terminal pane
+///
+/// # Arguments
+/// * `pane_id` - The ID of the pane (only terminal panes can be locked)
+/// * `input_lock` - `Some(PaneInputLock::Drop)` to drop all input, `Some(PaneInputLock::Confirm)`
+///   to hold input until the user confirms it, or `None` to unlock the pane
+pub fn set_pane_input_lock(pane_id: PaneId, input_lock: Option<PaneInputLock>) {
+    let plugin_command = PluginCommand::SetPaneInputLock(pane_id, input_lock);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
 /// Set the default foreground and/or background color of a pane
 ///
 /// # Arguments
@@ -2490,6 +2782,15 @@ pub fn group_and_ungroup_panes(
     unsafe { host_run_plugin_command() };
 }
 
+/// Toggle writing the input of this plugin's client to all panes in its pane group (even across
+/// tabs) rather than only to the focused pane. Does nothing if the client has no grouped panes.
+pub fn toggle_group_broadcast() {
+    let plugin_command = PluginCommand::ToggleGroupBroadcast;
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
 pub fn highlight_and_unhighlight_panes(
     pane_ids_to_highlight: Vec<PaneId>,
     pane_ids_to_unhighlight: Vec<PaneId>,
@@ -2676,6 +2977,26 @@ pub fn override_layout<L: AsRef<LayoutInfo>>(
     retain_existing_plugin_panes: bool,
     apply_only_to_active_tab: bool,
     context: BTreeMap<String, String>,
+) {
+    override_layout_with_vars(
+        layout_info,
+        retain_existing_terminal_panes,
+        retain_existing_plugin_panes,
+        apply_only_to_active_tab,
+        context,
+        BTreeMap::new(),
+    )
+}
+
+/// Like `override_layout`, providing values for the params declared in the layout (params left
+/// out fall back to their defaults)
+pub fn override_layout_with_vars<L: AsRef<LayoutInfo>>(
+    layout_info: L,
+    retain_existing_terminal_panes: bool,
+    retain_existing_plugin_panes: bool,
+    apply_only_to_active_tab: bool,
+    context: BTreeMap<String, String>,
+    layout_vars: BTreeMap<String, String>,
 ) {
     let plugin_command = PluginCommand::OverrideLayout(
         layout_info.as_ref().clone(),
@@ -2683,6 +3004,7 @@ pub fn override_layout<L: AsRef<LayoutInfo>>(
         retain_existing_plugin_panes,
         apply_only_to_active_tab,
         context,
+        layout_vars,
     );
     let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
     object_to_stdout(&protobuf_plugin_command.encode_to_vec());
@@ -2820,6 +3142,19 @@ pub fn clear_pane_highlights(pane_id: PaneId) {
     unsafe { host_run_plugin_command() };
 }
 
+/// Move a terminal pane to another running session.
+///
+/// The pane's process keeps running and its scrollback is carried over; it is removed from the
+/// current session once the target session has taken it over.
+///
+/// Requires `ChangeApplicationState` permission.
+pub fn send_pane_to_session(pane_id: PaneId, session_name: &str) {
+    let plugin_command = PluginCommand::SendPaneToSession(pane_id, session_name.to_owned());
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
 #[link(wasm_import_module = "zellij")]
 extern "C" {
     fn host_run_plugin_command();
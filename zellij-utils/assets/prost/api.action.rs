@@ -157,6 +157,10 @@ pub struct TiledPaneLayout {
     pub hide_floating_panes: bool,
     #[prost(string, optional, tag="13")]
     pub pane_initial_contents: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(message, optional, tag="14")]
+    pub tiling_strategy: ::core::option::Option<TilingStrategy>,
+    #[prost(string, optional, tag="15")]
+    pub input_lock: ::core::option::Option<::prost::alloc::string::String>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -302,7 +306,7 @@ pub struct OverrideLayoutPayload {
 pub struct Action {
     #[prost(enumeration="ActionName", tag="1")]
     pub name: i32,
-    #[prost(oneof="action::OptionalPayload", tags="2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47, 48, 49, 50, 51, 52, 53, 54, 55, 56, 57, 58, 59, 60")]
+    #[prost(oneof="action::OptionalPayload", tags="2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47, 48, 49, 50, 51, 52, 53, 54, 55, 56, 57, 58, 59, 60, 61, 62, 63, 64, 65, 66")]
     pub optional_payload: ::core::option::Option<action::OptionalPayload>,
 }
 /// Nested message and enum types in `Action`.
@@ -422,6 +426,18 @@ pub mod action {
         HideFloatingPanesPayload(super::HideFloatingPanesPayload),
         #[prost(message, tag="60")]
         AreFloatingPanesVisiblePayload(super::AreFloatingPanesVisiblePayload),
+        #[prost(message, tag="61")]
+        SendPaneToSessionPayload(super::SendPaneToSessionPayload),
+        #[prost(message, tag="62")]
+        EqualizePanesPayload(super::EqualizePanesPayload),
+        #[prost(message, tag="63")]
+        SetTilingStrategyPayload(super::SetTilingStrategyPayload),
+        #[prost(string, tag="64")]
+        ToggleScratchpadPayload(::prost::alloc::string::String),
+        #[prost(string, tag="65")]
+        TogglePaneInputLockPayload(::prost::alloc::string::String),
+        #[prost(message, tag="66")]
+        ReopenClosedPanePayload(super::ReopenClosedPanePayload),
     }
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -472,6 +488,44 @@ pub struct AreFloatingPanesVisiblePayload {
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SendPaneToSessionPayload {
+    #[prost(string, tag="1")]
+    pub session_name: ::prost::alloc::string::String,
+    #[prost(message, optional, tag="2")]
+    pub pane_id: ::core::option::Option<PaneId>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct EqualizePanesPayload {
+    #[prost(enumeration="SplitDirection", optional, tag="1")]
+    pub direction: ::core::option::Option<i32>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ReopenClosedPanePayload {
+    /// the most recently closed pane is reopened if no id is given
+    #[prost(uint32, optional, tag="1")]
+    pub id: ::core::option::Option<u32>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetTilingStrategyPayload {
+    /// no strategy stops re-tiling the panes
+    #[prost(message, optional, tag="1")]
+    pub strategy: ::core::option::Option<TilingStrategy>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TilingStrategy {
+    #[prost(string, tag="1")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(uint32, optional, tag="2")]
+    pub master_ratio: ::core::option::Option<u32>,
+    #[prost(uint32, optional, tag="3")]
+    pub master_count: ::core::option::Option<u32>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct PaneIdAndShouldFloat {
     #[prost(uint32, tag="1")]
     pub pane_id: u32,
@@ -1117,6 +1171,13 @@ pub enum ActionName {
     ShowFloatingPanes = 98,
     HideFloatingPanes = 99,
     AreFloatingPanesVisible = 100,
+    SendPaneToSession = 101,
+    EqualizePanes = 102,
+    SetTilingStrategy = 103,
+    ToggleGroupBroadcast = 104,
+    ToggleScratchpad = 105,
+    TogglePaneInputLock = 106,
+    ReopenClosedPane = 107,
 }
 impl ActionName {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -1223,6 +1284,13 @@ impl ActionName {
             ActionName::ShowFloatingPanes => "ShowFloatingPanes",
             ActionName::HideFloatingPanes => "HideFloatingPanes",
             ActionName::AreFloatingPanesVisible => "AreFloatingPanesVisible",
+            ActionName::SendPaneToSession => "SendPaneToSession",
+            ActionName::EqualizePanes => "EqualizePanes",
+            ActionName::SetTilingStrategy => "SetTilingStrategy",
+            ActionName::ToggleGroupBroadcast => "ToggleGroupBroadcast",
+            ActionName::ToggleScratchpad => "ToggleScratchpad",
+            ActionName::TogglePaneInputLock => "TogglePaneInputLock",
+            ActionName::ReopenClosedPane => "ReopenClosedPane",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -1326,6 +1394,13 @@ impl ActionName {
             "ShowFloatingPanes" => Some(Self::ShowFloatingPanes),
             "HideFloatingPanes" => Some(Self::HideFloatingPanes),
             "AreFloatingPanesVisible" => Some(Self::AreFloatingPanesVisible),
+            "SendPaneToSession" => Some(Self::SendPaneToSession),
+            "EqualizePanes" => Some(Self::EqualizePanes),
+            "SetTilingStrategy" => Some(Self::SetTilingStrategy),
+            "ToggleGroupBroadcast" => Some(Self::ToggleGroupBroadcast),
+            "ToggleScratchpad" => Some(Self::ToggleScratchpad),
+            "TogglePaneInputLock" => Some(Self::TogglePaneInputLock),
+            "ReopenClosedPane" => Some(Self::ReopenClosedPane),
             _ => None,
         }
     }
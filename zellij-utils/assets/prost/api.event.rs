@@ -9,7 +9,7 @@ pub struct EventNameList {
 pub struct Event {
     #[prost(enumeration="EventType", tag="1")]
     pub name: i32,
-    #[prost(oneof="event::Payload", tags="2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38")]
+    #[prost(oneof="event::Payload", tags="2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41")]
     pub payload: ::core::option::Option<event::Payload>,
 }
 /// Nested message and enum types in `Event`.
@@ -91,6 +91,12 @@ pub mod event {
         PaneRenderReportWithAnsiPayload(super::PaneRenderReportPayload),
         #[prost(message, tag="38")]
         InitialKeybindsPayload(super::InitialKeybindsPayload),
+        #[prost(message, tag="39")]
+        SpawnedProcessStdoutPayload(super::SpawnedProcessOutputPayload),
+        #[prost(message, tag="40")]
+        SpawnedProcessStderrPayload(super::SpawnedProcessOutputPayload),
+        #[prost(message, tag="41")]
+        SpawnedProcessExitedPayload(super::SpawnedProcessExitedPayload),
     }
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -248,6 +254,26 @@ pub struct RunCommandResultPayload {
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SpawnedProcessOutputPayload {
+    #[prost(uint32, tag="1")]
+    pub process_id: u32,
+    #[prost(bytes="vec", tag="2")]
+    pub output: ::prost::alloc::vec::Vec<u8>,
+    #[prost(message, repeated, tag="3")]
+    pub context: ::prost::alloc::vec::Vec<ContextItem>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SpawnedProcessExitedPayload {
+    #[prost(uint32, tag="1")]
+    pub process_id: u32,
+    #[prost(int32, optional, tag="2")]
+    pub exit_code: ::core::option::Option<i32>,
+    #[prost(message, repeated, tag="3")]
+    pub context: ::prost::alloc::vec::Vec<ContextItem>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct WebRequestResultPayload {
     #[prost(int32, tag="1")]
     pub status: i32,
@@ -403,6 +429,8 @@ pub struct PluginInfo {
     pub plugin_url: ::prost::alloc::string::String,
     #[prost(message, repeated, tag="3")]
     pub plugin_config: ::prost::alloc::vec::Vec<ContextItem>,
+    #[prost(uint64, tag="4")]
+    pub cpu_time_ms: u64,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -475,6 +503,16 @@ pub struct LayoutMetadata {
     pub creation_time: ::prost::alloc::string::String,
     #[prost(string, tag="3")]
     pub update_time: ::prost::alloc::string::String,
+    #[prost(message, repeated, tag="4")]
+    pub params: ::prost::alloc::vec::Vec<LayoutParam>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct LayoutParam {
+    #[prost(string, tag="1")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub default_value: ::prost::alloc::string::String,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -555,6 +593,9 @@ pub struct PaneInfo {
     pub default_fg: ::core::option::Option<::prost::alloc::string::String>,
     #[prost(string, optional, tag="25")]
     pub default_bg: ::core::option::Option<::prost::alloc::string::String>,
+    /// "drop" or "confirm" if the input of this pane is locked
+    #[prost(string, optional, tag="26")]
+    pub input_lock: ::core::option::Option<::prost::alloc::string::String>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -639,6 +680,8 @@ pub struct ModeUpdatePayload {
     pub web_server_port: ::core::option::Option<u32>,
     #[prost(bool, optional, tag="15")]
     pub web_server_capability: ::core::option::Option<bool>,
+    #[prost(bool, optional, tag="16")]
+    pub broadcasting_to_pane_group: ::core::option::Option<bool>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -815,6 +858,10 @@ pub enum EventType {
     HighlightClicked = 42,
     PaneRenderReportWithAnsi = 43,
     InitialKeybinds = 44,
+    SpawnedProcessStdout = 45,
+    SpawnedProcessStderr = 46,
+    SpawnedProcessExited = 47,
+    HostFolderScanned = 48,
 }
 impl EventType {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -867,6 +914,10 @@ impl EventType {
             EventType::HighlightClicked => "HighlightClicked",
             EventType::PaneRenderReportWithAnsi => "PaneRenderReportWithAnsi",
             EventType::InitialKeybinds => "InitialKeybinds",
+            EventType::SpawnedProcessStdout => "SpawnedProcessStdout",
+            EventType::SpawnedProcessStderr => "SpawnedProcessStderr",
+            EventType::SpawnedProcessExited => "SpawnedProcessExited",
+            EventType::HostFolderScanned => "HostFolderScanned",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -916,6 +967,10 @@ impl EventType {
             "HighlightClicked" => Some(Self::HighlightClicked),
             "PaneRenderReportWithAnsi" => Some(Self::PaneRenderReportWithAnsi),
             "InitialKeybinds" => Some(Self::InitialKeybinds),
+            "SpawnedProcessStdout" => Some(Self::SpawnedProcessStdout),
+            "SpawnedProcessStderr" => Some(Self::SpawnedProcessStderr),
+            "SpawnedProcessExited" => Some(Self::SpawnedProcessExited),
+            "HostFolderScanned" => Some(Self::HostFolderScanned),
             _ => None,
         }
     }
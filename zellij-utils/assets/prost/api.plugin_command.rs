@@ -3,7 +3,7 @@
 pub struct PluginCommand {
     #[prost(enumeration="CommandName", tag="1")]
     pub name: i32,
-    #[prost(oneof="plugin_command::Payload", tags="2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47, 48, 49, 50, 60, 61, 62, 63, 64, 65, 66, 67, 68, 69, 70, 71, 72, 73, 74, 75, 76, 77, 78, 79, 80, 81, 82, 83, 84, 85, 86, 87, 88, 89, 90, 91, 92, 93, 94, 95, 96, 97, 98, 99, 100, 101, 102, 103, 104, 105, 106, 107, 108, 109, 110, 111, 112, 113, 114, 119, 120, 121, 122, 123, 124, 125, 126, 127, 128, 129, 130, 131, 132, 133, 134, 135, 136, 137, 138, 139, 140, 141, 142, 143, 144, 145, 146, 147, 148, 149, 150, 151, 152, 153, 154, 155, 156, 157, 158, 159, 160")]
+    #[prost(oneof="plugin_command::Payload", tags="2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47, 48, 49, 50, 60, 61, 62, 63, 64, 65, 66, 67, 68, 69, 70, 71, 72, 73, 74, 75, 76, 77, 78, 79, 80, 81, 82, 83, 84, 85, 86, 87, 88, 89, 90, 91, 92, 93, 94, 95, 96, 97, 98, 99, 100, 101, 102, 103, 104, 105, 106, 107, 108, 109, 110, 111, 112, 113, 114, 119, 120, 121, 122, 123, 124, 125, 126, 127, 128, 129, 130, 131, 132, 133, 134, 135, 136, 137, 138, 139, 140, 141, 142, 143, 144, 145, 146, 147, 148, 149, 150, 151, 152, 153, 154, 155, 156, 157, 158, 159, 160, 161, 162, 163, 164, 165, 166, 167, 168, 169, 170, 171, 172, 173, 174, 175")]
     pub payload: ::core::option::Option<plugin_command::Payload>,
 }
 /// Nested message and enum types in `PluginCommand`.
@@ -303,6 +303,36 @@ pub mod plugin_command {
         ClearPaneHighlightsPayload(super::ClearPaneHighlightsPayload),
         #[prost(message, tag="160")]
         OpenPluginPaneFloatingPayload(super::OpenPluginPaneFloatingPayload),
+        #[prost(message, tag="161")]
+        SendPaneToSessionPayload(super::SendPaneToSessionPayload),
+        #[prost(message, tag="162")]
+        EqualizePanesPayload(super::EqualizePanesPayload),
+        #[prost(message, tag="163")]
+        SpawnProcessPayload(super::RunCommandPayload),
+        #[prost(message, tag="164")]
+        WriteToProcessStdinPayload(super::WriteToProcessStdinPayload),
+        #[prost(message, tag="165")]
+        KillProcessPayload(super::KillProcessPayload),
+        #[prost(message, tag="166")]
+        SignalProcessPayload(super::SignalProcessPayload),
+        #[prost(message, tag="167")]
+        RevokePluginPermissionPayload(super::RevokePluginPermissionPayload),
+        #[prost(message, tag="168")]
+        GetResurrectableSessionLayoutPayload(super::GetResurrectableSessionLayoutPayload),
+        #[prost(message, tag="169")]
+        SearchAllPanesPayload(super::SearchAllPanesPayload),
+        #[prost(message, tag="170")]
+        RevealSearchMatchPayload(super::RevealSearchMatchPayload),
+        #[prost(message, tag="171")]
+        ListSessionSnapshotsPayload(super::ListSessionSnapshotsPayload),
+        #[prost(message, tag="172")]
+        SetPaneInputLockPayload(super::SetPaneInputLockPayload),
+        #[prost(message, tag="173")]
+        ReopenClosedPanePayload(super::ReopenClosedPanePayload),
+        #[prost(message, tag="174")]
+        GetClosedPaneScrollbackPayload(super::GetClosedPaneScrollbackPayload),
+        #[prost(message, tag="175")]
+        CloseProcessStdinPayload(super::CloseProcessStdinPayload),
     }
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -527,6 +557,15 @@ pub struct SetPaneBorderlessPayload {
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetPaneInputLockPayload {
+    #[prost(message, optional, tag="1")]
+    pub pane_id: ::core::option::Option<PaneId>,
+    /// "drop" or "confirm", no input lock unlocks the pane
+    #[prost(string, optional, tag="2")]
+    pub input_lock: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ChangeHostFolderPayload {
     #[prost(string, tag="1")]
     pub new_host_folder: ::prost::alloc::string::String,
@@ -736,6 +775,8 @@ pub struct ShowPaneWithIdPayload {
 pub struct NewTabsWithLayoutInfoPayload {
     #[prost(message, optional, tag="1")]
     pub layout_info: ::core::option::Option<super::event::LayoutInfo>,
+    #[prost(message, repeated, tag="2")]
+    pub layout_vars: ::prost::alloc::vec::Vec<ContextItem>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -1241,6 +1282,8 @@ pub struct OverrideLayoutPayload {
     pub retain_existing_plugin_panes: bool,
     #[prost(bool, tag="5")]
     pub apply_only_to_active_tab: bool,
+    #[prost(message, repeated, tag="6")]
+    pub layout_vars: ::prost::alloc::vec::Vec<ContextItem>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -1883,6 +1926,243 @@ pub struct ClearPaneHighlightsPayload {
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct EqualizePanesPayload {
+    #[prost(enumeration="super::action::SplitDirection", optional, tag="1")]
+    pub direction: ::core::option::Option<i32>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SpawnProcessResponse {
+    #[prost(uint32, tag="1")]
+    pub process_id: u32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WriteToProcessStdinPayload {
+    #[prost(uint32, tag="1")]
+    pub process_id: u32,
+    #[prost(bytes="vec", tag="2")]
+    pub bytes: ::prost::alloc::vec::Vec<u8>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct KillProcessPayload {
+    #[prost(uint32, tag="1")]
+    pub process_id: u32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CloseProcessStdinPayload {
+    #[prost(uint32, tag="1")]
+    pub process_id: u32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SignalProcessPayload {
+    #[prost(uint32, tag="1")]
+    pub process_id: u32,
+    #[prost(string, tag="2")]
+    pub signal: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RevokePluginPermissionPayload {
+    #[prost(string, tag="1")]
+    pub plugin_url: ::prost::alloc::string::String,
+    #[prost(enumeration="super::plugin_permission::PermissionType", tag="2")]
+    pub permission: i32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetResurrectableSessionLayoutPayload {
+    #[prost(string, tag="1")]
+    pub session_name: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SearchAllPanesPayload {
+    #[prost(string, tag="1")]
+    pub query: ::prost::alloc::string::String,
+    #[prost(bool, tag="2")]
+    pub case_sensitive: bool,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SearchAllPanesResponse {
+    #[prost(oneof="search_all_panes_response::Result", tags="1, 2")]
+    pub result: ::core::option::Option<search_all_panes_response::Result>,
+}
+/// Nested message and enum types in `SearchAllPanesResponse`.
+pub mod search_all_panes_response {
+    #[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Result {
+        #[prost(message, tag="1")]
+        Results(super::PaneSearchResults),
+        #[prost(string, tag="2")]
+        Error(::prost::alloc::string::String),
+    }
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PaneSearchResults {
+    #[prost(message, repeated, tag="1")]
+    pub results: ::prost::alloc::vec::Vec<PaneSearchResult>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PaneSearchResult {
+    #[prost(uint32, tag="1")]
+    pub tab_position: u32,
+    #[prost(string, tag="2")]
+    pub tab_name: ::prost::alloc::string::String,
+    #[prost(message, optional, tag="3")]
+    pub pane_id: ::core::option::Option<PaneId>,
+    #[prost(string, tag="4")]
+    pub pane_title: ::prost::alloc::string::String,
+    #[prost(message, repeated, tag="5")]
+    pub matches: ::prost::alloc::vec::Vec<SearchMatch>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SearchMatch {
+    #[prost(uint32, tag="1")]
+    pub line_index: u32,
+    #[prost(uint32, tag="2")]
+    pub column: u32,
+    #[prost(string, tag="3")]
+    pub line: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RevealSearchMatchPayload {
+    #[prost(message, optional, tag="1")]
+    pub pane_id: ::core::option::Option<PaneId>,
+    #[prost(string, tag="2")]
+    pub query: ::prost::alloc::string::String,
+    #[prost(bool, tag="3")]
+    pub case_sensitive: bool,
+    #[prost(uint32, tag="4")]
+    pub line_index: u32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListPluginPermissionsResponse {
+    #[prost(message, repeated, tag="1")]
+    pub grants: ::prost::alloc::vec::Vec<PluginPermissionGrant>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PluginPermissionGrant {
+    #[prost(string, tag="1")]
+    pub plugin_url: ::prost::alloc::string::String,
+    #[prost(enumeration="super::plugin_permission::PermissionType", repeated, tag="2")]
+    pub permissions: ::prost::alloc::vec::Vec<i32>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListSessionSnapshotsPayload {
+    #[prost(string, tag="1")]
+    pub session_name: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ReopenClosedPanePayload {
+    #[prost(uint32, tag="1")]
+    pub id: u32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetClosedPaneScrollbackPayload {
+    #[prost(uint32, tag="1")]
+    pub id: u32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListClosedPanesResponse {
+    #[prost(oneof="list_closed_panes_response::Result", tags="1, 2")]
+    pub result: ::core::option::Option<list_closed_panes_response::Result>,
+}
+/// Nested message and enum types in `ListClosedPanesResponse`.
+pub mod list_closed_panes_response {
+    #[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Result {
+        #[prost(message, tag="1")]
+        ClosedPanes(super::ClosedPanes),
+        #[prost(string, tag="2")]
+        Error(::prost::alloc::string::String),
+    }
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ClosedPanes {
+    #[prost(message, repeated, tag="1")]
+    pub closed_panes: ::prost::alloc::vec::Vec<ClosedPaneInfo>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ClosedPaneInfo {
+    #[prost(uint32, tag="1")]
+    pub id: u32,
+    #[prost(string, tag="2")]
+    pub title: ::prost::alloc::string::String,
+    #[prost(string, optional, tag="3")]
+    pub command: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag="4")]
+    pub cwd: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(int32, optional, tag="5")]
+    pub exit_status: ::core::option::Option<i32>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetClosedPaneScrollbackResponse {
+    #[prost(oneof="get_closed_pane_scrollback_response::Result", tags="1, 2")]
+    pub result: ::core::option::Option<get_closed_pane_scrollback_response::Result>,
+}
+/// Nested message and enum types in `GetClosedPaneScrollbackResponse`.
+pub mod get_closed_pane_scrollback_response {
+    #[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Result {
+        #[prost(message, tag="1")]
+        Scrollback(super::ClosedPaneScrollback),
+        #[prost(string, tag="2")]
+        Error(::prost::alloc::string::String),
+    }
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ClosedPaneScrollback {
+    #[prost(string, repeated, tag="1")]
+    pub lines: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListSessionSnapshotsResponse {
+    #[prost(message, repeated, tag="1")]
+    pub snapshots: ::prost::alloc::vec::Vec<SessionSnapshot>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SessionSnapshot {
+    #[prost(string, tag="1")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub layout_path: ::prost::alloc::string::String,
+    #[prost(uint64, tag="3")]
+    pub age_secs: u64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SendPaneToSessionPayload {
+    #[prost(message, optional, tag="1")]
+    pub pane_id: ::core::option::Option<PaneId>,
+    #[prost(string, tag="2")]
+    pub session_name: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct OpenPluginPaneFloatingPayload {
     #[prost(string, tag="1")]
     pub plugin_url: ::prost::alloc::string::String,
@@ -2092,6 +2372,24 @@ pub enum CommandName {
     ClearPaneHighlights = 208,
     OpenPluginPaneFloating = 209,
     ListWindowsVolumes = 210,
+    SendPaneToSession = 211,
+    EqualizePanes = 212,
+    SpawnProcess = 213,
+    WriteToProcessStdin = 214,
+    KillProcess = 215,
+    SignalProcess = 216,
+    ListPluginPermissions = 217,
+    RevokePluginPermission = 218,
+    GetResurrectableSessionLayout = 219,
+    SearchAllPanes = 220,
+    RevealSearchMatch = 221,
+    ToggleGroupBroadcast = 222,
+    ListSessionSnapshots = 223,
+    SetPaneInputLock = 224,
+    ListClosedPanes = 225,
+    ReopenClosedPane = 226,
+    GetClosedPaneScrollback = 227,
+    CloseProcessStdin = 228,
 }
 impl CommandName {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -2290,6 +2588,24 @@ impl CommandName {
             CommandName::ClearPaneHighlights => "ClearPaneHighlights",
             CommandName::OpenPluginPaneFloating => "OpenPluginPaneFloating",
             CommandName::ListWindowsVolumes => "ListWindowsVolumes",
+            CommandName::SendPaneToSession => "SendPaneToSession",
+            CommandName::EqualizePanes => "EqualizePanes",
+            CommandName::SpawnProcess => "SpawnProcess",
+            CommandName::WriteToProcessStdin => "WriteToProcessStdin",
+            CommandName::KillProcess => "KillProcess",
+            CommandName::SignalProcess => "SignalProcess",
+            CommandName::ListPluginPermissions => "ListPluginPermissions",
+            CommandName::RevokePluginPermission => "RevokePluginPermission",
+            CommandName::GetResurrectableSessionLayout => "GetResurrectableSessionLayout",
+            CommandName::SearchAllPanes => "SearchAllPanes",
+            CommandName::RevealSearchMatch => "RevealSearchMatch",
+            CommandName::ToggleGroupBroadcast => "ToggleGroupBroadcast",
+            CommandName::ListSessionSnapshots => "ListSessionSnapshots",
+            CommandName::SetPaneInputLock => "SetPaneInputLock",
+            CommandName::ListClosedPanes => "ListClosedPanes",
+            CommandName::ReopenClosedPane => "ReopenClosedPane",
+            CommandName::GetClosedPaneScrollback => "GetClosedPaneScrollback",
+            CommandName::CloseProcessStdin => "CloseProcessStdin",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -2485,6 +2801,24 @@ impl CommandName {
             "ClearPaneHighlights" => Some(Self::ClearPaneHighlights),
             "OpenPluginPaneFloating" => Some(Self::OpenPluginPaneFloating),
             "ListWindowsVolumes" => Some(Self::ListWindowsVolumes),
+            "SendPaneToSession" => Some(Self::SendPaneToSession),
+            "EqualizePanes" => Some(Self::EqualizePanes),
+            "SpawnProcess" => Some(Self::SpawnProcess),
+            "WriteToProcessStdin" => Some(Self::WriteToProcessStdin),
+            "KillProcess" => Some(Self::KillProcess),
+            "SignalProcess" => Some(Self::SignalProcess),
+            "ListPluginPermissions" => Some(Self::ListPluginPermissions),
+            "RevokePluginPermission" => Some(Self::RevokePluginPermission),
+            "GetResurrectableSessionLayout" => Some(Self::GetResurrectableSessionLayout),
+            "SearchAllPanes" => Some(Self::SearchAllPanes),
+            "RevealSearchMatch" => Some(Self::RevealSearchMatch),
+            "ToggleGroupBroadcast" => Some(Self::ToggleGroupBroadcast),
+            "ListSessionSnapshots" => Some(Self::ListSessionSnapshots),
+            "SetPaneInputLock" => Some(Self::SetPaneInputLock),
+            "ListClosedPanes" => Some(Self::ListClosedPanes),
+            "ReopenClosedPane" => Some(Self::ReopenClosedPane),
+            "GetClosedPaneScrollback" => Some(Self::GetClosedPaneScrollback),
+            "CloseProcessStdin" => Some(Self::CloseProcessStdin),
             _ => None,
         }
     }
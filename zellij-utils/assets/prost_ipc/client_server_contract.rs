@@ -119,7 +119,7 @@ pub struct RgbColor {
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct Action {
-    #[prost(oneof="action::ActionType", tags="1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47, 48, 49, 50, 51, 52, 53, 54, 55, 56, 57, 58, 59, 60, 61, 62, 63, 64, 65, 66, 67, 68, 69, 70, 71, 72, 73, 74, 75, 76, 77, 78, 79, 80, 81, 82, 83, 84, 85, 86, 87, 88, 89, 90, 91, 92, 93, 94, 95, 96, 97, 98, 99, 100, 101, 102, 103, 104, 105, 106, 107, 108, 109, 110, 111, 112, 113, 114, 115, 116, 117, 118, 119, 120, 121, 122, 123, 124, 125, 126, 127, 128, 129, 130, 131, 132, 133, 134, 135, 136")]
+    #[prost(oneof="action::ActionType", tags="1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47, 48, 49, 50, 51, 52, 53, 54, 55, 56, 57, 58, 59, 60, 61, 62, 63, 64, 65, 66, 67, 68, 69, 70, 71, 72, 73, 74, 75, 76, 77, 78, 79, 80, 81, 82, 83, 84, 85, 86, 87, 88, 89, 90, 91, 92, 93, 94, 95, 96, 97, 98, 99, 100, 101, 102, 103, 104, 105, 106, 107, 108, 109, 110, 111, 112, 113, 114, 115, 116, 117, 118, 119, 120, 121, 122, 123, 124, 125, 126, 127, 128, 129, 130, 131, 132, 133, 134, 135, 136, 137, 138, 139, 140, 141, 142, 143, 144, 145, 146, 147, 148")]
     pub action_type: ::core::option::Option<action::ActionType>,
 }
 /// Nested message and enum types in `Action`.
@@ -401,6 +401,30 @@ pub mod action {
         FocusPaneByPaneId(super::FocusPaneByPaneIdAction),
         #[prost(message, tag="136")]
         AreFloatingPanesVisible(super::AreFloatingPanesVisibleAction),
+        #[prost(message, tag="137")]
+        SendPaneToSession(super::SendPaneToSessionAction),
+        #[prost(message, tag="138")]
+        EqualizePanes(super::EqualizePanesAction),
+        #[prost(message, tag="139")]
+        SetTilingStrategy(super::SetTilingStrategyAction),
+        #[prost(message, tag="140")]
+        ToggleGroupBroadcast(super::ToggleGroupBroadcastAction),
+        #[prost(message, tag="141")]
+        ToggleScratchpad(super::ToggleScratchpadAction),
+        #[prost(message, tag="142")]
+        SaveSnapshot(super::SaveSnapshotAction),
+        #[prost(message, tag="143")]
+        ListSnapshots(super::ListSnapshotsAction),
+        #[prost(message, tag="144")]
+        DeleteSnapshot(super::DeleteSnapshotAction),
+        #[prost(message, tag="145")]
+        RestoreSnapshot(super::RestoreSnapshotAction),
+        #[prost(message, tag="146")]
+        TogglePaneInputLock(super::TogglePaneInputLockAction),
+        #[prost(message, tag="147")]
+        TogglePaneInputLockByPaneId(super::TogglePaneInputLockByPaneIdAction),
+        #[prost(message, tag="148")]
+        ReopenClosedPane(super::ReopenClosedPaneAction),
     }
 }
 // Action message definitions (all 92 variants)
@@ -672,6 +696,10 @@ pub struct TogglePaneInGroupAction {
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ToggleGroupMarkingAction {
 }
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ToggleGroupBroadcastAction {
+}
 /// Complex action types (with data)
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -753,6 +781,29 @@ pub struct MovePaneAction {
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct EqualizePanesAction {
+    #[prost(enumeration="SplitDirection", optional, tag="1")]
+    pub direction: ::core::option::Option<i32>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetTilingStrategyAction {
+    /// no strategy stops re-tiling the panes
+    #[prost(message, optional, tag="1")]
+    pub strategy: ::core::option::Option<TilingStrategy>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TilingStrategy {
+    #[prost(string, tag="1")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(uint32, optional, tag="2")]
+    pub master_ratio: ::core::option::Option<u32>,
+    #[prost(uint32, optional, tag="3")]
+    pub master_count: ::core::option::Option<u32>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct DumpScreenAction {
     #[prost(string, tag="1")]
     pub file_path: ::prost::alloc::string::String,
@@ -1147,6 +1198,59 @@ pub struct RenameSessionAction {
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SendPaneToSessionAction {
+    #[prost(string, tag="1")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(message, optional, tag="2")]
+    pub pane_id: ::core::option::Option<PaneId>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ToggleScratchpadAction {
+    #[prost(string, tag="1")]
+    pub name: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SaveSnapshotAction {
+    #[prost(string, tag="1")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(bool, tag="2")]
+    pub include_viewport: bool,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListSnapshotsAction {
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DeleteSnapshotAction {
+    #[prost(string, tag="1")]
+    pub name: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RestoreSnapshotAction {
+    #[prost(string, tag="1")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(bool, tag="2")]
+    pub replace: bool,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TogglePaneInputLockAction {
+    /// "drop" or "confirm"
+    #[prost(string, tag="1")]
+    pub input_lock: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ReopenClosedPaneAction {
+    #[prost(uint32, optional, tag="1")]
+    pub id: ::core::option::Option<u32>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct CliPipeAction {
     #[prost(string, tag="1")]
     pub pipe_id: ::prost::alloc::string::String,
@@ -1274,6 +1378,8 @@ pub struct CliAssets {
     pub force_run_layout_commands: bool,
     #[prost(string, optional, tag="11")]
     pub cwd: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(map="string, string", tag="12")]
+    pub layout_vars: ::std::collections::HashMap<::prost::alloc::string::String, ::prost::alloc::string::String>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -1307,6 +1413,16 @@ pub struct LayoutMetadata {
     pub creation_time: ::prost::alloc::string::String,
     #[prost(string, tag="3")]
     pub update_time: ::prost::alloc::string::String,
+    #[prost(message, repeated, tag="4")]
+    pub params: ::prost::alloc::vec::Vec<LayoutParam>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct LayoutParam {
+    #[prost(string, tag="1")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub default_value: ::prost::alloc::string::String,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -1530,6 +1646,10 @@ pub struct TiledPaneLayout {
     /// NOTE: run_instructions_to_ignore is not represented here because it's a field used only inside the server itself and not part of the server/client contract
     #[prost(string, optional, tag="15")]
     pub default_bg: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(message, optional, tag="16")]
+    pub tiling_strategy: ::core::option::Option<TilingStrategy>,
+    #[prost(string, optional, tag="17")]
+    pub input_lock: ::core::option::Option<::prost::alloc::string::String>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -1927,6 +2047,12 @@ pub struct Options {
     pub focus_follows_mouse: ::core::option::Option<bool>,
     #[prost(bool, optional, tag="45")]
     pub mouse_click_through: ::core::option::Option<bool>,
+    #[prost(uint64, optional, tag="46")]
+    pub plugin_cpu_budget: ::core::option::Option<u64>,
+    #[prost(string, optional, tag="47")]
+    pub plugin_bundle_dir: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(enumeration="LocalEcho", optional, tag="48")]
+    pub local_echo: ::core::option::Option<i32>,
 }
 /// Pane-targeting action messages
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -2053,6 +2179,14 @@ pub struct TogglePanePinnedByPaneIdAction {
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TogglePaneInputLockByPaneIdAction {
+    #[prost(message, optional, tag="1")]
+    pub pane_id: ::core::option::Option<PaneId>,
+    #[prost(string, tag="2")]
+    pub input_lock: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct FocusPaneByPaneIdAction {
     #[prost(message, optional, tag="1")]
     pub pane_id: ::core::option::Option<PaneId>,
@@ -2766,6 +2900,38 @@ impl OnForceClose {
 }
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
+pub enum LocalEcho {
+    Unspecified = 0,
+    Always = 1,
+    Adaptive = 2,
+    Never = 3,
+}
+impl LocalEcho {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            LocalEcho::Unspecified => "LOCAL_ECHO_UNSPECIFIED",
+            LocalEcho::Always => "LOCAL_ECHO_ALWAYS",
+            LocalEcho::Adaptive => "LOCAL_ECHO_ADAPTIVE",
+            LocalEcho::Never => "LOCAL_ECHO_NEVER",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "LOCAL_ECHO_UNSPECIFIED" => Some(Self::Unspecified),
+            "LOCAL_ECHO_ALWAYS" => Some(Self::Always),
+            "LOCAL_ECHO_ADAPTIVE" => Some(Self::Adaptive),
+            "LOCAL_ECHO_NEVER" => Some(Self::Never),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
 pub enum Clipboard {
     Unspecified = 0,
     System = 1,
@@ -2857,7 +3023,7 @@ impl WebSharing {
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ClientToServerMsg {
-    #[prost(oneof="client_to_server_msg::Message", tags="1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18")]
+    #[prost(oneof="client_to_server_msg::Message", tags="1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20")]
     pub message: ::core::option::Option<client_to_server_msg::Message>,
 }
 /// Nested message and enum types in `ClientToServerMsg`.
@@ -2901,6 +3067,10 @@ pub mod client_to_server_msg {
         SubscribeToPaneRenders(super::SubscribeToPaneRendersMsg),
         #[prost(message, tag="18")]
         DesktopNotificationResponse(super::DesktopNotificationResponseMsg),
+        #[prost(message, tag="19")]
+        AdoptPane(super::AdoptPaneMsg),
+        #[prost(message, tag="20")]
+        ClientReconnected(super::ClientReconnectedMsg),
     }
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -3034,8 +3204,25 @@ pub struct DesktopNotificationResponseMsg {
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AdoptPaneMsg {
+    #[prost(string, tag="1")]
+    pub transfer_socket: ::prost::alloc::string::String,
+    #[prost(string, optional, tag="2")]
+    pub pane_title: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, tag="3")]
+    pub scrollback: ::prost::alloc::string::String,
+    #[prost(uint32, optional, tag="4")]
+    pub child_pid: ::core::option::Option<u32>,
+}
+/// Empty message
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ClientReconnectedMsg {
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ServerToClientMsg {
-    #[prost(oneof="server_to_client_msg::Message", tags="1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15")]
+    #[prost(oneof="server_to_client_msg::Message", tags="1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16")]
     pub message: ::core::option::Option<server_to_client_msg::Message>,
 }
 /// Nested message and enum types in `ServerToClientMsg`.
@@ -3073,6 +3260,8 @@ pub mod server_to_client_msg {
         PaneRenderUpdate(super::PaneRenderUpdateMsg),
         #[prost(message, tag="15")]
         SubscribedPaneClosed(super::SubscribedPaneClosedMsg),
+        #[prost(message, tag="16")]
+        LocalEchoState(super::LocalEchoStateMsg),
     }
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -3172,3 +3361,13 @@ pub struct SubscribedPaneClosedMsg {
     #[prost(message, optional, tag="1")]
     pub pane_id: ::core::option::Option<PaneId>,
 }
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct LocalEchoStateMsg {
+    #[prost(bool, tag="1")]
+    pub predictable: bool,
+    #[prost(uint32, tag="2")]
+    pub first_column: u32,
+    #[prost(uint32, tag="3")]
+    pub last_column: u32,
+}
@@ -1,4 +1,4 @@
-use crate::data::{Direction, InputMode, Resize, UnblockCondition};
+use crate::data::{Direction, InputMode, Resize, SessionSnapshot, UnblockCondition};
 use crate::setup::Setup;
 use crate::{
     consts::{ZELLIJ_CONFIG_DIR_ENV, ZELLIJ_CONFIG_FILE_ENV},
@@ -34,6 +34,20 @@ fn validate_session(name: &str) -> Result<String, String> {
     Ok(name.to_owned())
 }
 
+fn parse_layout_var(var: &str) -> Result<(String, String), String> {
+    match var.split_once('=') {
+        Some((name, value)) if !name.is_empty() => Ok((name.to_owned(), value.to_owned())),
+        _ => Err(format!(
+            "layout variables should be in the form NAME=VALUE, found: {}",
+            var
+        )),
+    }
+}
+
+fn parse_snapshot_name(name: &str) -> Result<String, String> {
+    SessionSnapshot::validate_name(name).map(|_| name.to_owned())
+}
+
 #[derive(Parser, Default, Debug, Clone, Serialize, Deserialize)]
 #[clap(version, name = "zellij")]
 pub struct CliArgs {
@@ -70,6 +84,10 @@ pub struct CliArgs {
     #[clap(short, long, value_parser, overrides_with = "new_session_with_layout")]
     pub new_session_with_layout: Option<PathBuf>,
 
+    /// Set a parameter declared in the layout, eg. --var service=worker (can be repeated)
+    #[clap(long = "var", value_name = "NAME=VALUE", value_parser = parse_layout_var, multiple_occurrences(true))]
+    pub layout_vars: Vec<(String, String)>,
+
     /// Change where zellij looks for the configuration file
     #[clap(short, long, overrides_with = "config", env = ZELLIJ_CONFIG_FILE_ENV, value_parser)]
     pub config: Option<PathBuf>,
@@ -282,6 +300,51 @@ pub enum SessionCommand {
     Options(Options),
 }
 
+#[derive(Debug, Subcommand, Clone, Serialize, Deserialize)]
+pub enum PluginSubcommand {
+    /// Download every remote plugin referenced by the configuration and its layouts into a
+    /// local bundle, to be loaded with the `plugin_bundle_dir` option instead of downloading them
+    Vendor {
+        /// The bundle directory, defaults to the `plugin_bundle_dir` option or to a
+        /// "plugin-bundle" folder in the data directory
+        #[clap(short, long, value_parser)]
+        output: Option<PathBuf>,
+    },
+}
+
+#[derive(Debug, Subcommand, Clone, Serialize, Deserialize)]
+pub enum SnapshotSubcommand {
+    /// Save the current layout of the session as a named snapshot, replacing any snapshot by
+    /// the same name
+    Save {
+        /// Name of the snapshot
+        #[clap(value_parser = parse_snapshot_name)]
+        name: String,
+        /// Also store the viewport contents of the panes (and their scrollback, up to the
+        /// `scrollback_lines_to_serialize` option)
+        #[clap(long, value_parser, default_value("false"), takes_value(false))]
+        with_viewport: bool,
+    },
+    /// List the snapshots saved for this session, most recent first
+    #[clap(visible_alias = "ls")]
+    List,
+    /// Delete a named snapshot
+    Delete {
+        /// Name of the snapshot
+        #[clap(value_parser = parse_snapshot_name)]
+        name: String,
+    },
+    /// Restore a named snapshot into this session as new tabs
+    Restore {
+        /// Name of the snapshot
+        #[clap(value_parser = parse_snapshot_name)]
+        name: String,
+        /// Replace the current tabs with the snapshot instead of adding new ones
+        #[clap(long, value_parser, default_value("false"), takes_value(false))]
+        replace: bool,
+    },
+}
+
 #[derive(Debug, Subcommand, Clone, Serialize, Deserialize)]
 pub enum Sessions {
     /// List active sessions
@@ -532,11 +595,14 @@ pub enum Sessions {
     },
     /// Load a plugin
     /// Returns: Created pane ID (format: plugin_<id>)
-    #[clap(visible_alias = "p")]
+    #[clap(visible_alias = "p", subcommand_negates_reqs(true))]
     Plugin {
+        #[clap(subcommand)]
+        subcommand: Option<PluginSubcommand>,
+
         /// Plugin URL, can either start with http(s), file: or zellij:
         #[clap(last(true), required(true))]
-        url: String,
+        url: Option<String>,
 
         /// Plugin configuration
         #[clap(short, long, value_parser)]
@@ -788,6 +854,23 @@ pub enum CliAction {
         #[clap(short, long, value_parser)]
         pane_id: Option<String>,
     },
+    /// Give all tiled panes in the current tab equal sizes, or only the panes in the split
+    /// holding the focused pane if a direction is specified
+    /// [vertical|horizontal]
+    EqualizePanes {
+        direction: Option<String>,
+    },
+    /// Re-tile the tiled panes in the current tab automatically as panes open and close
+    /// [master-stack|bsp|spiral|grid|columns|none]
+    SetTilingStrategy {
+        strategy: String,
+        /// The percent of the tab width taken by the master panes (master-stack only)
+        #[clap(long, value_parser)]
+        master_ratio: Option<usize>,
+        /// The number of panes in the master column (master-stack only)
+        #[clap(long, value_parser)]
+        master_count: Option<usize>,
+    },
     /// Clear all buffers for a focused pane
     Clear {
         /// Target a specific pane by ID (eg. terminal_1, plugin_2, or 3)
@@ -816,6 +899,11 @@ pub enum CliAction {
     DumpLayout,
     /// Save the current session state to disk immediately
     SaveSession,
+    /// Save, list, delete or restore named snapshots of the session layout
+    Snapshot {
+        #[clap(subcommand)]
+        subcommand: SnapshotSubcommand,
+    },
     /// Open the pane scrollback in your default editor
     EditScrollback {
         /// Target a specific pane by ID (eg. terminal_1, plugin_2, or 3)
@@ -888,6 +976,25 @@ pub enum CliAction {
         #[clap(short, long, value_parser)]
         tab_id: Option<usize>,
     },
+    /// Toggle between sending text commands to all panes in your pane group (across tabs) and
+    /// normal mode.
+    ToggleGroupBroadcast,
+    /// Show or hide a scratchpad declared in the config or layout, opening it the first time
+    ToggleScratchpad {
+        /// The name of the scratchpad
+        name: String,
+    },
+    /// Lock the input of a pane so that keys and pastes do not reach it (or unlock it if it is
+    /// already locked). Locked panes are also excluded from sync and broadcast.
+    TogglePaneInputLock {
+        /// Target a specific pane by ID (eg. terminal_1, plugin_2, or 3)
+        #[clap(short, long, value_parser)]
+        pane_id: Option<String>,
+        /// Hold input until it is confirmed with ENTER (or discarded with ESC) instead of
+        /// dropping it
+        #[clap(short, long, value_parser)]
+        confirm: bool,
+    },
     /// Open a new pane in the specified direction [right|down]
     /// If no direction is specified, will try to use the biggest available space.
     /// Returns: Created pane ID (format: terminal_<id> or plugin_<id>)
@@ -1161,6 +1268,14 @@ pub enum CliAction {
         #[clap(short, long, value_parser)]
         pane_id: Option<String>,
     },
+    /// Reopen a recently closed terminal pane showing its final scrollback. The pane is held
+    /// until ENTER re-runs its command in place.
+    ReopenClosedPane {
+        /// The id of the closed pane (as listed to plugins), defaults to the most recently
+        /// closed pane
+        #[clap(short, long, value_parser)]
+        id: Option<u32>,
+    },
     /// Renames the focused pane
     RenamePane {
         name: String,
@@ -1239,6 +1354,10 @@ pub enum CliAction {
         #[clap(long, value_parser, requires("layout"))]
         layout_dir: Option<PathBuf>,
 
+        /// Set a parameter declared in the layout, eg. --var service=worker (can be repeated)
+        #[clap(long = "var", value_name = "NAME=VALUE", value_parser = parse_layout_var, multiple_occurrences(true))]
+        layout_vars: Vec<(String, String)>,
+
         /// Name of the new tab
         #[clap(short, long, value_parser)]
         name: Option<String>,
@@ -1352,6 +1471,10 @@ pub enum CliAction {
         #[clap(long, value_parser)]
         layout_dir: Option<PathBuf>,
 
+        /// Set a parameter declared in the layout, eg. --var service=worker (can be repeated)
+        #[clap(long = "var", value_name = "NAME=VALUE", value_parser = parse_layout_var, multiple_occurrences(true))]
+        layout_vars: Vec<(String, String)>,
+
         /// Retain existing terminal panes that do not fit in the layout (default: false)
         #[clap(long, value_parser, takes_value(false), default_value("false"))]
         retain_existing_terminal_panes: bool,
@@ -1425,6 +1548,15 @@ pub enum CliAction {
     RenameSession {
         name: String,
     },
+    /// Move a terminal pane to another running session, keeping its process and scrollback
+    SendPaneToSession {
+        /// The name of the session to send the pane to
+        name: String,
+
+        /// The pane_id of the pane, eg. terminal_1 or 3 (equivalent to terminal_3). If not specified, sends the focused pane.
+        #[clap(short, long, value_parser)]
+        pane_id: Option<String>,
+    },
     /// Send data to one or more plugins, launch them if they are not running.
     #[clap(override_usage(
 r#"
@@ -1733,4 +1865,49 @@ mod tests {
         let result = CliArgs::try_parse_from(["zellij", "subscribe"]);
         assert!(result.is_err());
     }
+
+    fn parse_snapshot(args: &[&str]) -> SnapshotSubcommand {
+        let mut full_args = vec!["zellij", "action", "snapshot"];
+        full_args.extend_from_slice(args);
+        let cli = CliArgs::try_parse_from(full_args).unwrap();
+        match cli.command {
+            Some(Command::Action(cli_action)) => match *cli_action {
+                CliAction::Snapshot { subcommand } => subcommand,
+                other => panic!("Expected Snapshot, got {:?}", other),
+            },
+            other => panic!("Expected Action, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn snapshot_save_with_viewport() {
+        match parse_snapshot(&["save", "before-refactor", "--with-viewport"]) {
+            SnapshotSubcommand::Save {
+                name,
+                with_viewport,
+            } => {
+                assert_eq!(name, "before-refactor");
+                assert!(with_viewport);
+            },
+            other => panic!("Expected Save, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn snapshot_restore_defaults_to_new_tabs() {
+        match parse_snapshot(&["restore", "before-refactor"]) {
+            SnapshotSubcommand::Restore { name, replace } => {
+                assert_eq!(name, "before-refactor");
+                assert!(!replace);
+            },
+            other => panic!("Expected Restore, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn snapshot_name_cannot_be_a_path() {
+        let result =
+            CliArgs::try_parse_from(["zellij", "action", "snapshot", "delete", "../other-session"]);
+        assert!(result.is_err());
+    }
 }
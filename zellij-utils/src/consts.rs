@@ -12,6 +12,7 @@ pub const ZELLIJ_CONFIG_DIR_ENV: &str = "ZELLIJ_CONFIG_DIR";
 pub const ZELLIJ_LAYOUT_DIR_ENV: &str = "ZELLIJ_LAYOUT_DIR";
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 pub const DEFAULT_SCROLL_BUFFER_SIZE: usize = 10_000;
+pub const DEFAULT_PLUGIN_CPU_BUDGET: u64 = 10_000_000_000; // fuel per call into a plugin
 pub static SCROLL_BUFFER_SIZE: OnceLock<usize> = OnceLock::new();
 pub static DEBUG_MODE: OnceLock<bool> = OnceLock::new();
 
@@ -33,6 +34,18 @@ pub fn session_layout_cache_file_name(session_name: &str) -> PathBuf {
     session_info_folder_for_session(session_name).join("session-layout.kdl")
 }
 
+pub fn session_snapshots_folder_for_session(session_name: &str) -> PathBuf {
+    session_info_folder_for_session(session_name).join("snapshots")
+}
+
+pub const SNAPSHOT_LAYOUT_FILE_NAME: &str = "session-layout.kdl";
+
+pub const SESSION_PLUGIN_STATES_FILE_NAME: &str = "plugin-states.kdl";
+
+pub fn session_plugin_states_cache_file_name(session_name: &str) -> PathBuf {
+    session_info_folder_for_session(session_name).join(SESSION_PLUGIN_STATES_FILE_NAME)
+}
+
 pub fn session_info_folder_for_session(session_name: &str) -> PathBuf {
     ZELLIJ_SESSION_INFO_CACHE_DIR.join(session_name)
 }
@@ -150,6 +163,8 @@ mod not_wasm {
             add_plugin!(assets, "multiple-select.wasm");
             add_plugin!(assets, "layout-manager.wasm");
             add_plugin!(assets, "link.wasm");
+            add_plugin!(assets, "pane-search.wasm");
+            add_plugin!(assets, "closed-panes.wasm");
             assets
         };
     }
@@ -3,7 +3,7 @@ use crate::input::actions::{Action, RunCommandAction};
 use crate::input::config::{ConversionError, KdlError};
 use crate::input::keybinds::Keybinds;
 use crate::input::layout::{
-    Layout, PercentOrFixed, Run, RunPlugin, RunPluginLocation, RunPluginOrAlias,
+    Layout, PercentOrFixed, Run, RunPlugin, RunPluginLocation, RunPluginOrAlias, SplitDirection,
 };
 use crate::pane_size::PaneGeom;
 use crate::position::Position;
@@ -1024,6 +1024,16 @@ pub enum Event {
     /// Plugins that subscribe to this event signal they cache keybindings
     /// and can handle lightweight ModeUpdate events without keybindings.
     InitialKeybinds(KeybindsVec),
+    /// A chunk of STDOUT from a process started with `spawn_process`
+    SpawnedProcessStdout(u32, Vec<u8>, BTreeMap<String, String>), // process_id, chunk, context
+    /// A chunk of STDERR from a process started with `spawn_process`
+    SpawnedProcessStderr(u32, Vec<u8>, BTreeMap<String, String>), // process_id, chunk, context
+    /// A process started with `spawn_process` exited, after all of its output was delivered
+    SpawnedProcessExited(u32, Option<i32>, BTreeMap<String, String>), // process_id, exit_code,
+    // context
+    /// The entries of a folder scanned with `scan_host_folder` (or the volumes listed with
+    /// `list_windows_volumes`), sent in place of `FileSystemUpdate` to plugins subscribed to it
+    HostFolderScanned(Vec<(PathBuf, Option<FileMetadata>)>),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, EnumDiscriminants, Display, Serialize, Deserialize)]
@@ -1723,6 +1733,7 @@ pub struct ModeInfo {
     pub web_clients_allowed: Option<bool>,
     pub web_sharing: Option<WebSharing>,
     pub currently_marking_pane_group: Option<bool>,
+    pub broadcasting_to_pane_group: Option<bool>,
     pub is_web_client: Option<bool>,
     // note: these are only the configured ip/port that will be bound if and when the server is up
     pub web_server_ip: Option<IpAddr>,
@@ -1786,10 +1797,35 @@ pub struct SessionInfo {
     pub creation_time: Duration,
 }
 
+/// A named snapshot of a session's serialized layout, saved with `zellij action snapshot save`
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct SessionSnapshot {
+    pub name: String,
+    pub layout_path: PathBuf,
+    pub age: Duration,
+}
+
+impl SessionSnapshot {
+    /// Snapshots are stored in a folder named after them, so their name can't be a path
+    pub fn validate_name(name: &str) -> Result<(), String> {
+        if name.trim().is_empty() {
+            return Err("Snapshot name cannot be empty.".to_string());
+        }
+        if name == "." || name == ".." {
+            return Err(format!("Invalid snapshot name: \"{}\".", name));
+        }
+        if name.contains('/') || name.contains('\\') {
+            return Err("Snapshot name cannot contain path separators.".to_string());
+        }
+        Ok(())
+    }
+}
+
 #[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
 pub struct PluginInfo {
     pub location: String,
     pub configuration: BTreeMap<String, String>,
+    pub cpu_time_ms: u64, // time spent running the plugin's code since it was loaded
 }
 
 impl From<RunPlugin> for PluginInfo {
@@ -1797,6 +1833,7 @@ impl From<RunPlugin> for PluginInfo {
         PluginInfo {
             location: run_plugin.location.display(),
             configuration: run_plugin.configuration.inner().clone(),
+            cpu_time_ms: 0,
         }
     }
 }
@@ -1836,6 +1873,7 @@ pub struct LayoutMetadata {
     pub tabs: Vec<TabMetadata>,
     pub creation_time: String,
     pub update_time: String,
+    pub params: Vec<LayoutParam>,
 }
 
 impl From<&PathBuf> for LayoutMetadata {
@@ -1864,10 +1902,14 @@ impl From<&PathBuf> for LayoutMetadata {
                         let (creation_time, update_time) =
                             LayoutMetadata::creation_and_update_times(&path);
 
+                        let params =
+                            Layout::params_from_kdl(&stringified_layout).unwrap_or_default();
+
                         LayoutMetadata {
                             tabs,
                             creation_time,
                             update_time,
+                            params,
                         }
                     },
                     Err(e) => {
@@ -1916,6 +1958,14 @@ impl LayoutMetadata {
     }
 }
 
+/// A parameter declared in a layout with `param "name" default="value"`, interpolated wherever
+/// `{{name}}` appears in the layout
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct LayoutParam {
+    pub name: String,
+    pub default: String,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 pub struct TabMetadata {
     pub panes: Vec<PaneMetadata>,
@@ -2220,6 +2270,14 @@ impl SessionInfo {
         }
         self.plugins = plugin_list;
     }
+    pub fn populate_plugin_cpu_usage(&mut self, cpu_usage: BTreeMap<u32, Duration>) {
+        // u32 - plugin_id
+        for (plugin_id, plugin_info) in self.plugins.iter_mut() {
+            if let Some(cpu_time) = cpu_usage.get(plugin_id) {
+                plugin_info.cpu_time_ms = cpu_time.as_millis() as u64;
+            }
+        }
+    }
 }
 
 /// Contains all the information for a currently opened tab.
@@ -2334,6 +2392,43 @@ pub struct PaneInfo {
     pub default_fg: Option<String>,
     /// The default background color of this pane, if set (e.g. "#001a3a")
     pub default_bg: Option<String>,
+    /// Whether input written to this pane is blocked, and how
+    pub input_lock: Option<PaneInputLock>,
+}
+
+/// Protects a pane from accidental input: keys, pastes and writes sent to a locked pane are
+/// either dropped or held until the user confirms them
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum PaneInputLock {
+    /// Input is silently dropped
+    #[default]
+    Drop,
+    /// Input is held until the user either confirms it with `Enter` or discards it with `Esc`,
+    /// the pane stays locked either way
+    Confirm,
+}
+
+impl PaneInputLock {
+    pub fn name(&self) -> &'static str {
+        match self {
+            PaneInputLock::Drop => "drop",
+            PaneInputLock::Confirm => "confirm",
+        }
+    }
+}
+
+impl FromStr for PaneInputLock {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "drop" | "Drop" => Ok(PaneInputLock::Drop),
+            "confirm" | "Confirm" => Ok(PaneInputLock::Confirm),
+            _ => Err(format!(
+                "Unknown input lock \"{}\", expected either \"drop\" or \"confirm\"",
+                s
+            )),
+        }
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
@@ -2568,6 +2663,57 @@ pub enum PaneScrollbackResponse {
     Err(String),
 }
 
+/// The matches of a session-wide search in a single terminal pane
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PaneSearchResult {
+    pub tab_position: usize,
+    pub tab_name: String,
+    pub pane_id: PaneId,
+    pub pane_title: String,
+    pub matches: Vec<SearchMatch>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SearchMatch {
+    /// The index of the line counting from the top of the pane's scrollback (the first line above
+    /// the viewport is 0)
+    pub line_index: usize,
+    /// The character offset of the match in the line
+    pub column: usize,
+    pub line: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum SearchAllPanesResponse {
+    Ok(Vec<PaneSearchResult>),
+    Err(String),
+}
+
+/// A terminal pane that was recently closed in this session and can be reopened with its
+/// scrollback
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ClosedPaneInfo {
+    /// Identifies the pane in the closed pane history, unrelated to the id it had while open
+    pub id: u32,
+    pub title: String,
+    /// The command that ran in the pane, `None` if it was a shell
+    pub command: Option<String>,
+    pub cwd: Option<PathBuf>,
+    pub exit_status: Option<i32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ListClosedPanesResponse {
+    Ok(Vec<ClosedPaneInfo>), // most recently closed first
+    Err(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum GetClosedPaneScrollbackResponse {
+    Ok(Vec<String>), // the final scrollback and viewport of the pane as plain text
+    Err(String),
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum GetPanePidResponse {
     Ok(i32),
@@ -3404,7 +3550,7 @@ pub enum PluginCommand {
         tab_index: Option<usize>,
     },
     CloseSelf,
-    NewTabsWithLayoutInfo(LayoutInfo),
+    NewTabsWithLayoutInfo(LayoutInfo, BTreeMap<String, String>), // BTreeMap -> layout vars
     Reconfigure(String, bool), // String -> stringified configuration, bool -> save configuration
     // file to disk
     HidePaneWithId(PaneId),
@@ -3513,6 +3659,7 @@ pub enum PluginCommand {
         bool,                     // retain_existing_plugin_panes
         bool,                     // apply_only_to_active_tab,
         BTreeMap<String, String>, // context
+        BTreeMap<String, String>, // layout vars
     ),
     SaveLayout {
         layout_name: String,
@@ -3566,6 +3713,37 @@ pub enum PluginCommand {
         context: BTreeMap<String, String>,
     },
     ListWindowsVolumes,
+    SendPaneToSession(PaneId, String), // String -> name of the target session
+    EqualizePanes(Option<SplitDirection>), // None -> equalize all tiled panes in the tab
+    SpawnProcess(
+        Vec<String>,              // command
+        BTreeMap<String, String>, // env_variables
+        PathBuf,                  // cwd
+        BTreeMap<String, String>, // context
+    ),
+    WriteToProcessStdin(u32, Vec<u8>), // u32 - process_id
+    CloseProcessStdin(u32),            // u32 - process_id
+    KillProcess(u32),                  // u32 - process_id
+    SignalProcess(u32, String),        // u32 - process_id, String - signal name (eg. SIGTERM)
+    ListPluginPermissions,
+    RevokePluginPermission(String, PermissionType), // String - plugin url
+    GetResurrectableSessionLayout(String),          // String - session name
+    SearchAllPanes {
+        query: String,
+        case_sensitive: bool,
+    },
+    RevealSearchMatch {
+        pane_id: PaneId,
+        query: String,
+        case_sensitive: bool,
+        line_index: usize,
+    },
+    ToggleGroupBroadcast,
+    ListSessionSnapshots(String), // String - session name
+    SetPaneInputLock(PaneId, Option<PaneInputLock>), // None -> unlock the pane
+    ListClosedPanes,
+    ReopenClosedPane(u32),        // u32 - the id of the closed pane
+    GetClosedPaneScrollback(u32), // u32 - the id of the closed pane
 }
 
 // Response type for plugin API methods that open a pane in a new tab
@@ -255,6 +255,8 @@ pub enum ScreenContext {
     MoveFocusRightOrNextTab,
     MovePane,
     MovePaneBackwards,
+    EqualizePanes,
+    SetTilingStrategy,
     MovePaneDown,
     MovePaneUp,
     MovePaneRight,
@@ -264,8 +266,12 @@ pub enum ScreenContext {
     DumpScreen,
     DumpLayout,
     SaveSession,
+    SaveSnapshot,
     EditScrollback,
     GetPaneScrollback,
+    ListSearchablePanes,
+    RevealSearchMatch,
+    RestoreClosedPane,
     ScrollUp,
     ScrollUpAt,
     ScrollDown,
@@ -363,6 +369,7 @@ pub enum ScreenContext {
     NewInPlacePluginPane,
     SerializeLayoutForResurrection,
     RenameSession,
+    SendPaneToSession,
     DumpLayoutToPlugin,
     GetFocusedPaneInfo,
     GetPaneInfo,
@@ -401,6 +408,9 @@ pub enum ScreenContext {
     ChangeFloatingPanesCoordinates,
     TogglePaneBorderless,
     SetPaneBorderless,
+    TogglePaneInputLock,
+    TogglePaneInputLockWithPaneId,
+    SetPaneInputLock,
     AddHighlightPaneFrameColorOverride,
     GroupAndUngroupPanes,
     HighlightAndUnhighlightPanes,
@@ -408,6 +418,9 @@ pub enum ScreenContext {
     EmbedMultiplePanes,
     TogglePaneInGroup,
     ToggleGroupMarking,
+    ToggleGroupBroadcast,
+    ToggleScratchpad,
+    ScratchpadOpened,
     SessionSharingStatusChange,
     SetMouseSelectionSupport,
     InterceptKeyPresses,
@@ -421,6 +434,7 @@ pub enum ScreenContext {
     SetPluginRegexHighlights,
     ClearPluginHighlights,
     DesktopNotificationResponse,
+    ClientReconnected,
     SubscribeToPaneRenders,
     NotifyPaneClosedToSubscribers,
     // Pane-targeting CLI variants
@@ -459,6 +473,7 @@ pub enum ScreenContext {
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum PtyContext {
     SpawnTerminal,
+    SpawnScratchpad,
     OpenInPlaceEditor,
     SpawnTerminalVertically,
     SpawnTerminalHorizontally,
@@ -474,6 +489,7 @@ pub enum PtyContext {
     DumpLayout,
     LogLayoutToHd,
     SaveSessionToDisk,
+    SaveSnapshotToDisk,
     FillPluginCwd,
     DumpLayoutToPlugin,
     ListClientsMetadata,
@@ -486,6 +502,13 @@ pub enum PtyContext {
     GetPaneRunningCommand,
     GetPaneCwd,
     UpdateAndReportCwds,
+    SendTerminalToSession,
+    TerminalSentToSession,
+    AdoptTerminal,
+    AddClosedPane,
+    ListClosedPanes,
+    GetClosedPaneScrollback,
+    ReopenClosedPane,
     Exit,
 }
 
@@ -536,6 +559,7 @@ pub enum PluginContext {
     GetLastSessionSaveTime,
     DetectPluginConfigChanges,
     HighlightClicked,
+    RevokePluginPermission,
 }
 
 /// Stack call representations corresponding to the different types of [`ClientInstruction`]s.
@@ -597,6 +621,7 @@ pub enum ServerContext {
     FailedToStartWebServer,
     SendWebClientsForbidden,
     ClearMouseHelpText,
+    LocalEchoState,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
@@ -616,7 +641,14 @@ pub enum BackgroundJobContext {
     ReadAllSessionInfosOnMachine,
     ReportSessionInfo,
     ReportLayoutInfo,
+    ReportPluginStates,
     RunCommand,
+    SpawnProcess,
+    WriteToProcessStdin,
+    CloseProcessStdin,
+    KillProcess,
+    SignalProcess,
+    KillPluginProcesses,
     WebRequest,
     ReportPluginList,
     ListWebSessions,
@@ -628,6 +660,9 @@ pub enum BackgroundJobContext {
     StopFlashPaneBell,
     FlashTabBell,
     StopFlashTabBell,
+    RunHooks,
+    SendTerminalToSession,
+    ReconfigureHooks,
     Exit,
 }
 
@@ -3,12 +3,13 @@
 pub use super::command::{OpenFilePayload, RunCommandAction};
 use super::layout::{
     FloatingPaneLayout, Layout, PluginAlias, RunPlugin, RunPluginLocation, RunPluginOrAlias,
-    SwapFloatingLayout, SwapTiledLayout, TabLayoutInfo, TiledPaneLayout,
+    SplitDirection, SwapFloatingLayout, SwapTiledLayout, TabLayoutInfo, TiledPaneLayout,
+    TilingStrategy,
 };
-use crate::cli::CliAction;
+use crate::cli::{CliAction, SnapshotSubcommand};
 use crate::data::{
     CommandOrPlugin, Direction, KeyWithModifier, LayoutInfo, NewPanePlacement, OriginatingPlugin,
-    PaneId, Resize, UnblockCondition,
+    PaneId, PaneInputLock, Resize, UnblockCondition,
 };
 use crate::data::{FloatingPaneCoordinates, InputMode};
 use crate::home::{find_default_config_dir, get_layout_dir};
@@ -172,6 +173,16 @@ pub enum Action {
         direction: Option<Direction>,
     },
     MovePaneBackwards,
+    /// Give the tiled panes in the tab equal sizes, or only those in the split of the given
+    /// direction that holds the focused pane
+    EqualizePanes {
+        direction: Option<SplitDirection>,
+    },
+    /// Re-tile the tiled panes of the tab with this strategy whenever panes open or close, None
+    /// stops re-tiling them
+    SetTilingStrategy {
+        strategy: Option<TilingStrategy>,
+    },
     /// Clear all buffers of a current screen
     ClearScreen,
     /// Dumps the screen to a file or STDOUT
@@ -185,6 +196,22 @@ pub enum Action {
     DumpLayout,
     /// Save the current session state to disk
     SaveSession,
+    /// Save the current layout as a named snapshot, optionally with the pane viewports
+    SaveSnapshot {
+        name: String,
+        include_viewport: bool,
+    },
+    /// List the named snapshots of the current session
+    ListSnapshots,
+    /// Delete a named snapshot of the current session
+    DeleteSnapshot {
+        name: String,
+    },
+    /// Open a named snapshot as new tabs, or in place of the current ones
+    RestoreSnapshot {
+        name: String,
+        replace: bool,
+    },
     EditScrollback {
         ansi: bool,
     },
@@ -301,6 +328,11 @@ pub enum Action {
     },
     /// Close the focus pane.
     CloseFocus,
+    /// Reopen a recently closed terminal pane with its scrollback, the most recently closed one
+    /// if no id is given
+    ReopenClosedPane {
+        id: Option<u32>,
+    },
     PaneNameInput {
         input: Vec<u8>,
     },
@@ -490,6 +522,11 @@ pub enum Action {
     RenameSession {
         name: String,
     },
+    /// Move a terminal pane (the focused one if no id is given) to another running session
+    SendPaneToSession {
+        name: String,
+        pane_id: Option<PaneId>,
+    },
     CliPipe {
         pipe_id: String,
         name: Option<String>,
@@ -555,6 +592,16 @@ pub enum Action {
     },
     TogglePaneInGroup,
     ToggleGroupMarking,
+    /// Write input to all panes in the client's pane group instead of the focused pane
+    ToggleGroupBroadcast,
+    /// Show or hide a named scratchpad, opening it the first time
+    ToggleScratchpad {
+        name: String,
+    },
+    /// Lock the input of the focused pane, or unlock it if it is already locked
+    TogglePaneInputLock {
+        input_lock: PaneInputLock,
+    },
     // Pane-targeting CLI-only variants
     ScrollUpByPaneId {
         pane_id: PaneId,
@@ -618,6 +665,10 @@ pub enum Action {
     TogglePanePinnedByPaneId {
         pane_id: PaneId,
     },
+    TogglePaneInputLockByPaneId {
+        pane_id: PaneId,
+        input_lock: PaneInputLock,
+    },
     FocusPaneByPaneId {
         pane_id: PaneId,
     },
@@ -845,6 +896,33 @@ impl Action {
                 },
                 None => Ok(vec![Action::MovePaneBackwards]),
             },
+            CliAction::EqualizePanes { direction } => match direction {
+                Some(direction_str) => {
+                    let direction = SplitDirection::from_str(&direction_str).map_err(|_| {
+                        format!(
+                            "Invalid split direction: {direction_str}, expecting either vertical or horizontal"
+                        )
+                    })?;
+                    Ok(vec![Action::EqualizePanes {
+                        direction: Some(direction),
+                    }])
+                },
+                None => Ok(vec![Action::EqualizePanes { direction: None }]),
+            },
+            CliAction::SetTilingStrategy {
+                strategy,
+                master_ratio,
+                master_count,
+            } => {
+                if strategy == "none" {
+                    return Ok(vec![Action::SetTilingStrategy { strategy: None }]);
+                }
+                let strategy = TilingStrategy::new(&strategy, master_ratio, master_count)
+                    .map_err(|e| format!("Invalid tiling strategy: {}", e))?;
+                Ok(vec![Action::SetTilingStrategy {
+                    strategy: Some(strategy),
+                }])
+            },
             CliAction::MoveTab { direction, tab_id } => match tab_id {
                 Some(id) => Ok(vec![Action::MoveTabByTabId {
                     id: id as u64,
@@ -896,6 +974,20 @@ impl Action {
             },
             CliAction::DumpLayout => Ok(vec![Action::DumpLayout]),
             CliAction::SaveSession => Ok(vec![Action::SaveSession]),
+            CliAction::Snapshot { subcommand } => match subcommand {
+                SnapshotSubcommand::Save {
+                    name,
+                    with_viewport,
+                } => Ok(vec![Action::SaveSnapshot {
+                    name,
+                    include_viewport: with_viewport,
+                }]),
+                SnapshotSubcommand::List => Ok(vec![Action::ListSnapshots]),
+                SnapshotSubcommand::Delete { name } => Ok(vec![Action::DeleteSnapshot { name }]),
+                SnapshotSubcommand::Restore { name, replace } => {
+                    Ok(vec![Action::RestoreSnapshot { name, replace }])
+                },
+            },
             CliAction::EditScrollback { pane_id, ansi } => match pane_id {
                 Some(pane_id_str) => {
                     let pane_id = PaneId::from_str(&pane_id_str)
@@ -1001,6 +1093,28 @@ impl Action {
                 Some(id) => Ok(vec![Action::ToggleActiveSyncTabByTabId { id: id as u64 }]),
                 None => Ok(vec![Action::ToggleActiveSyncTab]),
             },
+            CliAction::ToggleGroupBroadcast => Ok(vec![Action::ToggleGroupBroadcast]),
+            CliAction::ToggleScratchpad { name } => Ok(vec![Action::ToggleScratchpad { name }]),
+            CliAction::TogglePaneInputLock { pane_id, confirm } => {
+                let input_lock = if confirm {
+                    PaneInputLock::Confirm
+                } else {
+                    PaneInputLock::Drop
+                };
+                match pane_id {
+                    Some(pane_id_str) => {
+                        let pane_id = PaneId::from_str(&pane_id_str)
+                            .map_err(|_| format!(
+                                "Malformed pane id: {pane_id_str}, expecting either a bare integer (eg. 1), a terminal pane id (eg. terminal_1) or a plugin pane id (eg. plugin_1)"
+                            ))?;
+                        Ok(vec![Action::TogglePaneInputLockByPaneId {
+                            pane_id,
+                            input_lock,
+                        }])
+                    },
+                    None => Ok(vec![Action::TogglePaneInputLock { input_lock }]),
+                }
+            },
             CliAction::NewPane {
                 direction,
                 command,
@@ -1323,6 +1437,7 @@ impl Action {
                 },
                 None => Ok(vec![Action::CloseFocus]),
             },
+            CliAction::ReopenClosedPane { id } => Ok(vec![Action::ReopenClosedPane { id }]),
             CliAction::RenamePane { name, pane_id } => match pane_id {
                 Some(pane_id_str) => {
                     let pane_id = PaneId::from_str(&pane_id_str)
@@ -1385,6 +1500,7 @@ impl Action {
                 layout,
                 layout_string,
                 layout_dir,
+                layout_vars,
                 cwd,
                 initial_command,
                 initial_plugin,
@@ -1398,6 +1514,7 @@ impl Action {
                 let cwd = cwd
                     .map(|cwd| current_dir.join(cwd))
                     .or_else(|| Some(current_dir.clone()));
+                let layout_vars: BTreeMap<String, String> = layout_vars.into_iter().collect();
 
                 // Map CLI flags to UnblockCondition
                 let first_pane_unblock_condition = if block_until_exit_success {
@@ -1454,7 +1571,7 @@ impl Action {
                     let swap_layouts: Option<(String, String)> = None;
                     let should_start_layout_commands_suspended = false;
                     let raw_layout_for_error = raw_layout.clone();
-                    let mut layout = Layout::from_str(&raw_layout, path_to_raw_layout, swap_layouts.as_ref().map(|(f, p)| (f.as_str(), p.as_str())), cwd).map_err(|e| {
+                    let mut layout = Layout::from_str_with_vars(&raw_layout, path_to_raw_layout, swap_layouts.as_ref().map(|(f, p)| (f.as_str(), p.as_str())), cwd, &layout_vars).map_err(|e| {
                         let stringified_error = match e {
                             ConfigError::KdlError(kdl_error) => {
                                 let error = kdl_error.add_src(layout_source_name.clone(), raw_layout_for_error);
@@ -1570,7 +1687,7 @@ impl Action {
                         Layout::stringified_from_path_or_default(Some(&layout_path), layout_dir)
                             .map_err(|e| format!("Failed to load layout: {}", e))?
                     };
-                    let mut layout = Layout::from_str(&raw_layout, path_to_raw_layout, swap_layouts.as_ref().map(|(f, p)| (f.as_str(), p.as_str())), cwd).map_err(|e| {
+                    let mut layout = Layout::from_str_with_vars(&raw_layout, path_to_raw_layout, swap_layouts.as_ref().map(|(f, p)| (f.as_str(), p.as_str())), cwd, &layout_vars).map_err(|e| {
                         let stringified_error = match e {
                             ConfigError::KdlError(kdl_error) => {
                                 let error = kdl_error.add_src(layout_source_name.clone(), String::from(raw_layout));
@@ -1681,6 +1798,7 @@ impl Action {
                 layout,
                 layout_string,
                 layout_dir,
+                layout_vars,
                 retain_existing_terminal_panes,
                 retain_existing_plugin_panes,
                 apply_only_to_active_tab,
@@ -1726,11 +1844,13 @@ impl Action {
                 };
 
                 // Parse KDL layout
-                let layout = Layout::from_str(
+                let layout_vars: BTreeMap<String, String> = layout_vars.into_iter().collect();
+                let layout = Layout::from_str_with_vars(
                     &raw_layout,
                     path_to_raw_layout,
                     swap_layouts.as_ref().map(|(f, p)| (f.as_str(), p.as_str())),
                     None, // cwd
+                    &layout_vars,
                 )
                 .map_err(|e| {
                     let stringified_error = match e {
@@ -1853,6 +1973,22 @@ impl Action {
                 }])
             },
             CliAction::RenameSession { name } => Ok(vec![Action::RenameSession { name }]),
+            CliAction::SendPaneToSession { name, pane_id } => match pane_id {
+                Some(pane_id_str) => {
+                    let pane_id = PaneId::from_str(&pane_id_str).map_err(|_| format!(
+                        "Malformed pane id: {}, expecting either a bare integer (eg. 1) or a terminal pane id (eg. terminal_1)",
+                        pane_id_str
+                    ))?;
+                    Ok(vec![Action::SendPaneToSession {
+                        name,
+                        pane_id: Some(pane_id),
+                    }])
+                },
+                None => Ok(vec![Action::SendPaneToSession {
+                    name,
+                    pane_id: None,
+                }]),
+            },
             CliAction::Pipe {
                 name,
                 payload,
@@ -2886,6 +3022,16 @@ mod tests {
         assert!(matches!(actions[0], Action::CloseFocus));
     }
 
+    #[test]
+    fn test_reopen_closed_pane() {
+        let cli_action = CliAction::ReopenClosedPane { id: Some(3) };
+        let result = Action::actions_from_cli(cli_action, Box::new(|| PathBuf::from("/tmp")), None);
+        assert_eq!(
+            result.unwrap(),
+            vec![Action::ReopenClosedPane { id: Some(3) }]
+        );
+    }
+
     // 17. RenamePane
     #[test]
     fn test_rename_pane_with_pane_id() {
@@ -3401,6 +3547,7 @@ mod tests {
             layout: None,
             layout_string: Some("layout {\n    pane\n    pane\n}\n".into()),
             layout_dir: None,
+            layout_vars: vec![],
             cwd: None,
             initial_command: vec![],
             initial_plugin: None,
@@ -3430,6 +3577,37 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_new_tab_with_layout_vars() {
+        let cli_action = CliAction::NewTab {
+            name: None,
+            layout: None,
+            layout_string: Some(
+                "layout {\n    param \"service\" default=\"api\"\n    pane name=\"{{service}}\"\n}\n"
+                    .into(),
+            ),
+            layout_dir: None,
+            layout_vars: vec![("service".into(), "worker".into())],
+            cwd: None,
+            initial_command: vec![],
+            initial_plugin: None,
+            close_on_exit: Default::default(),
+            start_suspended: Default::default(),
+            block_until_exit: false,
+            block_until_exit_success: false,
+            block_until_exit_failure: false,
+        };
+        let result = Action::actions_from_cli(cli_action, Box::new(|| PathBuf::from("/tmp")), None);
+        let actions = result.unwrap();
+        match &actions[0] {
+            Action::NewTab { tiled_layout, .. } => {
+                let layout = tiled_layout.as_ref().unwrap();
+                assert_eq!(layout.children[0].name, Some("worker".to_owned()));
+            },
+            _ => panic!("Expected NewTab action"),
+        }
+    }
+
     #[test]
     fn test_new_tab_with_invalid_layout_string() {
         let cli_action = CliAction::NewTab {
@@ -3437,6 +3615,7 @@ mod tests {
             layout: None,
             layout_string: Some("invalid { kdl".into()),
             layout_dir: None,
+            layout_vars: vec![],
             cwd: None,
             initial_command: vec![],
             initial_plugin: None,
@@ -3456,6 +3635,7 @@ mod tests {
             layout: None,
             layout_string: Some("layout {\n    pane\n    pane\n}\n".into()),
             layout_dir: None,
+            layout_vars: vec![],
             retain_existing_terminal_panes: false,
             retain_existing_plugin_panes: false,
             apply_only_to_active_tab: false,
@@ -3870,4 +4050,236 @@ mod tests {
             _ => panic!("Expected NewFloatingPluginPane action"),
         }
     }
+
+    // SendPaneToSession
+
+    #[test]
+    fn test_send_pane_to_session_with_pane_id() {
+        let cli_action = CliAction::SendPaneToSession {
+            name: "other-session".to_string(),
+            pane_id: Some("terminal_3".to_string()),
+        };
+        let result = Action::actions_from_cli(cli_action, Box::new(|| PathBuf::from("/tmp")), None);
+        assert!(result.is_ok());
+        let actions = result.unwrap();
+        assert_eq!(actions.len(), 1);
+        match &actions[0] {
+            Action::SendPaneToSession { name, pane_id } => {
+                assert_eq!(name, "other-session");
+                assert!(matches!(pane_id, Some(PaneId::Terminal(3))));
+            },
+            _ => panic!("Expected SendPaneToSession action"),
+        }
+    }
+
+    #[test]
+    fn test_send_pane_to_session_without_pane_id() {
+        let cli_action = CliAction::SendPaneToSession {
+            name: "other-session".to_string(),
+            pane_id: None,
+        };
+        let result = Action::actions_from_cli(cli_action, Box::new(|| PathBuf::from("/tmp")), None);
+        assert!(result.is_ok());
+        let actions = result.unwrap();
+        assert_eq!(actions.len(), 1);
+        assert!(matches!(
+            actions[0],
+            Action::SendPaneToSession { pane_id: None, .. }
+        ));
+    }
+
+    // EqualizePanes
+
+    #[test]
+    fn test_equalize_panes_with_direction() {
+        let cli_action = CliAction::EqualizePanes {
+            direction: Some("vertical".to_string()),
+        };
+        let result = Action::actions_from_cli(cli_action, Box::new(|| PathBuf::from("/tmp")), None);
+        assert!(result.is_ok());
+        let actions = result.unwrap();
+        assert_eq!(actions.len(), 1);
+        assert!(matches!(
+            actions[0],
+            Action::EqualizePanes {
+                direction: Some(SplitDirection::Vertical)
+            }
+        ));
+    }
+
+    #[test]
+    fn test_equalize_panes_without_direction() {
+        let cli_action = CliAction::EqualizePanes { direction: None };
+        let result = Action::actions_from_cli(cli_action, Box::new(|| PathBuf::from("/tmp")), None);
+        assert!(result.is_ok());
+        let actions = result.unwrap();
+        assert_eq!(actions.len(), 1);
+        assert!(matches!(
+            actions[0],
+            Action::EqualizePanes { direction: None }
+        ));
+    }
+
+    #[test]
+    fn test_equalize_panes_with_invalid_direction() {
+        let cli_action = CliAction::EqualizePanes {
+            direction: Some("diagonal".to_string()),
+        };
+        let result = Action::actions_from_cli(cli_action, Box::new(|| PathBuf::from("/tmp")), None);
+        assert!(result.is_err());
+    }
+
+    // SetTilingStrategy
+
+    #[test]
+    fn test_set_tiling_strategy_master_stack() {
+        let cli_action = CliAction::SetTilingStrategy {
+            strategy: "master-stack".to_string(),
+            master_ratio: Some(60),
+            master_count: None,
+        };
+        let result = Action::actions_from_cli(cli_action, Box::new(|| PathBuf::from("/tmp")), None);
+        assert!(result.is_ok());
+        let actions = result.unwrap();
+        assert_eq!(actions.len(), 1);
+        assert_eq!(
+            actions[0],
+            Action::SetTilingStrategy {
+                strategy: Some(TilingStrategy::MasterStack {
+                    master_ratio: 60,
+                    master_count: 1,
+                })
+            }
+        );
+    }
+
+    #[test]
+    fn test_set_tiling_strategy_none() {
+        let cli_action = CliAction::SetTilingStrategy {
+            strategy: "none".to_string(),
+            master_ratio: None,
+            master_count: None,
+        };
+        let result = Action::actions_from_cli(cli_action, Box::new(|| PathBuf::from("/tmp")), None);
+        assert!(result.is_ok());
+        let actions = result.unwrap();
+        assert_eq!(actions, vec![Action::SetTilingStrategy { strategy: None }]);
+    }
+
+    #[test]
+    fn test_set_tiling_strategy_with_master_options_for_other_strategy() {
+        let cli_action = CliAction::SetTilingStrategy {
+            strategy: "grid".to_string(),
+            master_ratio: Some(60),
+            master_count: None,
+        };
+        let result = Action::actions_from_cli(cli_action, Box::new(|| PathBuf::from("/tmp")), None);
+        assert!(result.is_err());
+    }
+
+    // ToggleGroupBroadcast
+    #[test]
+    fn test_toggle_group_broadcast() {
+        let cli_action = CliAction::ToggleGroupBroadcast;
+        let result = Action::actions_from_cli(cli_action, Box::new(|| PathBuf::from("/tmp")), None);
+        assert!(result.is_ok());
+        let actions = result.unwrap();
+        assert_eq!(actions, vec![Action::ToggleGroupBroadcast]);
+    }
+
+    // ToggleScratchpad
+    #[test]
+    fn test_toggle_scratchpad() {
+        let cli_action = CliAction::ToggleScratchpad {
+            name: "notes".to_string(),
+        };
+        let result = Action::actions_from_cli(cli_action, Box::new(|| PathBuf::from("/tmp")), None);
+        assert!(result.is_ok());
+        let actions = result.unwrap();
+        assert_eq!(
+            actions,
+            vec![Action::ToggleScratchpad {
+                name: "notes".to_string()
+            }]
+        );
+    }
+
+    // TogglePaneInputLock
+    #[test]
+    fn test_toggle_pane_input_lock() {
+        let cli_action = CliAction::TogglePaneInputLock {
+            pane_id: None,
+            confirm: false,
+        };
+        let result = Action::actions_from_cli(cli_action, Box::new(|| PathBuf::from("/tmp")), None);
+        assert_eq!(
+            result.unwrap(),
+            vec![Action::TogglePaneInputLock {
+                input_lock: PaneInputLock::Drop
+            }]
+        );
+    }
+
+    #[test]
+    fn test_toggle_pane_input_lock_with_pane_id() {
+        let cli_action = CliAction::TogglePaneInputLock {
+            pane_id: Some("terminal_3".to_string()),
+            confirm: true,
+        };
+        let result = Action::actions_from_cli(cli_action, Box::new(|| PathBuf::from("/tmp")), None);
+        assert_eq!(
+            result.unwrap(),
+            vec![Action::TogglePaneInputLockByPaneId {
+                pane_id: PaneId::Terminal(3),
+                input_lock: PaneInputLock::Confirm,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_toggle_pane_input_lock_with_malformed_pane_id() {
+        let cli_action = CliAction::TogglePaneInputLock {
+            pane_id: Some("not_a_pane".to_string()),
+            confirm: false,
+        };
+        let result = Action::actions_from_cli(cli_action, Box::new(|| PathBuf::from("/tmp")), None);
+        assert!(result.is_err());
+    }
+
+    // Snapshot
+    #[test]
+    fn test_snapshot_save() {
+        let cli_action = CliAction::Snapshot {
+            subcommand: SnapshotSubcommand::Save {
+                name: "before-refactor".to_string(),
+                with_viewport: true,
+            },
+        };
+        let result = Action::actions_from_cli(cli_action, Box::new(|| PathBuf::from("/tmp")), None);
+        assert_eq!(
+            result.unwrap(),
+            vec![Action::SaveSnapshot {
+                name: "before-refactor".to_string(),
+                include_viewport: true,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_snapshot_restore_replacing_tabs() {
+        let cli_action = CliAction::Snapshot {
+            subcommand: SnapshotSubcommand::Restore {
+                name: "before-refactor".to_string(),
+                replace: true,
+            },
+        };
+        let result = Action::actions_from_cli(cli_action, Box::new(|| PathBuf::from("/tmp")), None);
+        assert_eq!(
+            result.unwrap(),
+            vec![Action::RestoreSnapshot {
+                name: "before-refactor".to_string(),
+                replace: true,
+            }]
+        );
+    }
 }
@@ -7,6 +7,7 @@ use crate::{
     setup::get_default_themes,
 };
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
@@ -16,6 +17,7 @@ pub struct CliAssets {
     pub should_ignore_config: bool,
     pub configuration_options: Option<Options>, // merged from everywhere: there are the source of truth
     pub layout: Option<LayoutInfo>,
+    pub layout_vars: BTreeMap<String, String>, // values for the params declared in the layout
     pub terminal_window_size: Size,
     pub data_dir: Option<PathBuf>,
     pub is_debug: bool,
@@ -52,8 +54,13 @@ impl CliAssets {
                         .map(|dir| dir.join("layouts"))
                 });
             self.layout.as_ref().and_then(|layout_info| {
-                Layout::from_layout_info_with_config(&layout_dir, layout_info, Some(config.clone()))
-                    .ok()
+                Layout::from_layout_info_with_config(
+                    &layout_dir,
+                    layout_info,
+                    Some(config.clone()),
+                    &self.layout_vars,
+                )
+                .ok()
             })
         }
         .map(|(layout, config)| (layout, config))
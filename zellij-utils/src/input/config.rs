@@ -13,10 +13,13 @@ use thiserror::Error;
 
 use std::convert::TryFrom;
 
+use super::hooks::Hooks;
 use super::keybinds::Keybinds;
 use super::layout::RunPluginOrAlias;
+use super::mousebinds::Mousebinds;
 use super::options::Options;
 use super::plugins::{PluginAliases, PluginsConfigError};
+use super::scratchpads::Scratchpads;
 use super::theme::{Themes, UiConfig};
 use super::web_client::WebClientConfig;
 use crate::cli::{CliArgs, Command};
@@ -38,6 +41,9 @@ pub struct Config {
     pub env: EnvironmentVariables,
     pub background_plugins: HashSet<RunPluginOrAlias>,
     pub web_client: WebClientConfig,
+    pub mousebinds: Mousebinds,
+    pub scratchpads: Scratchpads,
+    pub hooks: Hooks,
 }
 
 #[derive(Error, Debug, Serialize, Deserialize)]
@@ -264,8 +270,11 @@ impl Config {
     pub fn merge(&mut self, other: Config) -> Result<(), ConfigError> {
         self.options = self.options.merge(other.options);
         self.keybinds.merge(other.keybinds.clone());
+        self.mousebinds.merge(other.mousebinds);
         self.themes = self.themes.merge(other.themes);
         self.plugins.merge(other.plugins);
+        self.scratchpads.merge(other.scratchpads);
+        self.hooks.merge(other.hooks);
         self.ui = self.ui.merge(other.ui);
         self.env = self.env.merge(other.env);
         Ok(())
@@ -0,0 +1,147 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::data::{ClientId, InputMode};
+
+use serde::{Deserialize, Serialize};
+
+pub const DEFAULT_HOOK_TIMEOUT_SECS: u64 = 10;
+
+/// Shell commands the server runs when something happens in the session, declared in the `hooks`
+/// block of the config
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize, Default)]
+pub struct Hooks(pub Vec<Hook>);
+
+impl Hooks {
+    pub fn for_event(&self, event: HookEvent) -> impl Iterator<Item = &Hook> {
+        self.0.iter().filter(move |hook| hook.event == event)
+    }
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+    /// Several hooks can run on the same event, so rather than merging them by event a `hooks`
+    /// block replaces the one it is merged onto
+    pub fn merge(&mut self, other: Hooks) {
+        if !other.is_empty() {
+            *self = other;
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub struct Hook {
+    pub event: HookEvent,
+    /// Run with `sh -c` (`cmd /C` on Windows)
+    pub command: String,
+    /// The command is killed if it runs for longer than this
+    pub timeout_secs: Option<u64>,
+}
+
+impl Hook {
+    pub fn timeout(&self) -> Duration {
+        Duration::from_secs(self.timeout_secs.unwrap_or(DEFAULT_HOOK_TIMEOUT_SECS))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum HookEvent {
+    SessionCreated,
+    SessionResurrected,
+    ClientAttached,
+    ClientDetached,
+    TabCreated,
+    TabClosed,
+    PaneOpened,
+    CommandPaneExited,
+    CwdChanged,
+    ModeChanged,
+    /// Run before the server exits, which waits for it to finish or time out
+    SessionKilled,
+}
+
+impl HookEvent {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HookEvent::SessionCreated => "session_created",
+            HookEvent::SessionResurrected => "session_resurrected",
+            HookEvent::ClientAttached => "client_attached",
+            HookEvent::ClientDetached => "client_detached",
+            HookEvent::TabCreated => "tab_created",
+            HookEvent::TabClosed => "tab_closed",
+            HookEvent::PaneOpened => "pane_opened",
+            HookEvent::CommandPaneExited => "command_pane_exited",
+            HookEvent::CwdChanged => "cwd_changed",
+            HookEvent::ModeChanged => "mode_changed",
+            HookEvent::SessionKilled => "session_killed",
+        }
+    }
+}
+
+impl fmt::Display for HookEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for HookEvent {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "session_created" => Ok(HookEvent::SessionCreated),
+            "session_resurrected" => Ok(HookEvent::SessionResurrected),
+            "client_attached" => Ok(HookEvent::ClientAttached),
+            "client_detached" => Ok(HookEvent::ClientDetached),
+            "tab_created" => Ok(HookEvent::TabCreated),
+            "tab_closed" => Ok(HookEvent::TabClosed),
+            "pane_opened" => Ok(HookEvent::PaneOpened),
+            "command_pane_exited" => Ok(HookEvent::CommandPaneExited),
+            "cwd_changed" => Ok(HookEvent::CwdChanged),
+            "mode_changed" => Ok(HookEvent::ModeChanged),
+            "session_killed" => Ok(HookEvent::SessionKilled),
+            _ => Err(format!("Unknown hook event: {}", s)),
+        }
+    }
+}
+
+/// What a hook is told about the event that triggered it, each event fills in the fields that
+/// are relevant to it
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct HookContext {
+    pub client_id: Option<ClientId>,
+    pub tab_name: Option<String>,
+    pub tab_position: Option<usize>,
+    pub pane_id: Option<u32>, // terminal pane id, as in ZELLIJ_PANE_ID
+    pub command: Option<String>,
+    pub exit_code: Option<i32>,
+    pub cwd: Option<PathBuf>,
+    pub mode: Option<InputMode>,
+}
+
+impl HookContext {
+    pub fn env_variables(&self, event: HookEvent) -> BTreeMap<String, String> {
+        let mut env_variables = BTreeMap::new();
+        env_variables.insert("ZELLIJ_HOOK_EVENT".to_owned(), event.to_string());
+        let mut insert = |name: &str, value: Option<String>| {
+            if let Some(value) = value {
+                env_variables.insert(format!("ZELLIJ_HOOK_{}", name), value);
+            }
+        };
+        insert("CLIENT_ID", self.client_id.map(|c| c.to_string()));
+        insert("TAB_NAME", self.tab_name.clone());
+        insert("TAB_POSITION", self.tab_position.map(|p| p.to_string()));
+        insert("PANE_ID", self.pane_id.map(|p| p.to_string()));
+        insert("COMMAND", self.command.clone());
+        insert("EXIT_CODE", self.exit_code.map(|e| e.to_string()));
+        insert("CWD", self.cwd.as_ref().map(|c| c.display().to_string()));
+        insert("MODE", self.mode.map(|m| format!("{:?}", m).to_lowercase()));
+        env_variables
+    }
+}
+
+// The unit test location.
+#[cfg(test)]
+#[path = "./unit/hooks_test.rs"]
+mod hooks_test;
@@ -11,7 +11,9 @@
 #[cfg(not(target_family = "wasm"))]
 use crate::downloader::Downloader;
 use crate::{
-    data::{Direction, LayoutInfo, LayoutMetadata, LayoutParsingError, LayoutWithError},
+    data::{
+        Direction, LayoutInfo, LayoutMetadata, LayoutParsingError, LayoutWithError, PaneInputLock,
+    },
     home::{default_layout_dir, find_default_config_dir},
     input::{
         command::RunCommand,
@@ -26,6 +28,7 @@ use std::fmt::{Display, Formatter};
 use std::str::FromStr;
 
 use super::plugins::{PluginAliases, PluginTag, PluginsConfigError};
+use super::scratchpads::Scratchpads;
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 use std::vec::Vec;
@@ -63,6 +66,82 @@ impl From<Direction> for SplitDirection {
     }
 }
 
+pub const DEFAULT_MASTER_RATIO: usize = 50;
+pub const DEFAULT_MASTER_COUNT: usize = 1;
+
+/// A built-in arrangement that re-tiles the tiled panes of a tab whenever panes open or close
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone, Copy)]
+pub enum TilingStrategy {
+    /// The first `master_count` panes share a column taking `master_ratio` percent of the width,
+    /// the rest are stacked on top of each other in the remaining column
+    MasterStack {
+        master_ratio: usize,
+        master_count: usize,
+    },
+    /// The space is halved along its longer side, each half holding half of the panes
+    Bsp,
+    /// Every pane takes half of the space left by the previous one, turning clockwise
+    Spiral,
+    /// Rows of equally sized panes, as close to a square grid as possible
+    Grid,
+    /// Equally wide panes side by side
+    Columns,
+}
+
+impl TilingStrategy {
+    pub fn new(
+        name: &str,
+        master_ratio: Option<usize>,
+        master_count: Option<usize>,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let strategy = TilingStrategy::from_str(name)?;
+        match strategy {
+            TilingStrategy::MasterStack { .. } => {
+                let master_ratio = master_ratio.unwrap_or(DEFAULT_MASTER_RATIO);
+                if master_ratio == 0 || master_ratio >= 100 {
+                    return Err("master_ratio must be between 1 and 99".into());
+                }
+                Ok(TilingStrategy::MasterStack {
+                    master_ratio,
+                    master_count: master_count.unwrap_or(DEFAULT_MASTER_COUNT),
+                })
+            },
+            _ if master_ratio.is_some() || master_count.is_some() => {
+                Err("master_ratio and master_count are only supported by master-stack".into())
+            },
+            strategy => Ok(strategy),
+        }
+    }
+    pub fn name(&self) -> &'static str {
+        match self {
+            TilingStrategy::MasterStack { .. } => "master-stack",
+            TilingStrategy::Bsp => "bsp",
+            TilingStrategy::Spiral => "spiral",
+            TilingStrategy::Grid => "grid",
+            TilingStrategy::Columns => "columns",
+        }
+    }
+}
+
+impl FromStr for TilingStrategy {
+    type Err = Box<dyn std::error::Error>;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "master-stack" | "MasterStack" => Ok(TilingStrategy::MasterStack {
+                master_ratio: DEFAULT_MASTER_RATIO,
+                master_count: DEFAULT_MASTER_COUNT,
+            }),
+            "bsp" | "Bsp" => Ok(TilingStrategy::Bsp),
+            "spiral" | "Spiral" => Ok(TilingStrategy::Spiral),
+            "grid" | "Grid" => Ok(TilingStrategy::Grid),
+            "columns" | "Columns" => Ok(TilingStrategy::Columns),
+            _ => Err(
+                "tiling strategy must be one of master-stack, bsp, spiral, grid or columns".into(),
+            ),
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
 pub enum SplitSize {
     #[serde(alias = "percent")]
@@ -700,6 +779,7 @@ pub struct Layout {
     pub swap_layouts: Vec<(TiledPaneLayout, Vec<FloatingPaneLayout>)>,
     pub swap_tiled_layouts: Vec<SwapTiledLayout>,
     pub swap_floating_layouts: Vec<SwapFloatingLayout>,
+    pub scratchpads: Scratchpads,
 }
 
 /// Layout configuration for a single tab in multi-tab override
@@ -856,8 +936,10 @@ pub struct TiledPaneLayout {
     pub children_are_stacked: bool,
     pub is_expanded_in_stack: bool,
     pub exclude_from_sync: Option<bool>,
+    pub input_lock: Option<PaneInputLock>,
     pub run_instructions_to_ignore: Vec<Option<Run>>,
     pub hide_floating_panes: bool, // only relevant if this is the base layout
+    pub tiling_strategy: Option<TilingStrategy>, // only relevant if this is the base layout
     pub pane_initial_contents: Option<String>,
     pub default_fg: Option<String>,
     pub default_bg: Option<String>,
@@ -1109,6 +1191,14 @@ impl TiledPaneLayout {
             child.populate_plugin_aliases_in_layout(plugin_aliases);
         }
     }
+    pub fn run_plugins(&self) -> Vec<RunPlugin> {
+        let mut run_plugins: Vec<RunPlugin> =
+            self.run.iter().filter_map(|r| r.get_run_plugin()).collect();
+        for child in &self.children {
+            run_plugins.append(&mut child.run_plugins());
+        }
+        run_plugins
+    }
     pub fn deepest_depth(&self) -> usize {
         let mut deepest_child_depth = 0;
         for child in self.children.iter() {
@@ -1326,6 +1416,7 @@ impl Layout {
     pub fn from_layout_info(
         layout_dir: &Option<PathBuf>,
         layout_info: LayoutInfo,
+        layout_vars: &BTreeMap<String, String>,
     ) -> Result<Layout, ConfigError> {
         let mut should_start_layout_commands_suspended = false;
         let (path_to_raw_layout, raw_layout, raw_swap_layouts) = match layout_info {
@@ -1349,13 +1440,14 @@ impl Layout {
             },
             LayoutInfo::Stringified(stringified_layout) => (None, stringified_layout, None),
         };
-        let mut layout = Layout::from_kdl(
+        let mut layout = Layout::from_kdl_with_vars(
             &raw_layout,
             path_to_raw_layout,
             raw_swap_layouts
                 .as_ref()
                 .map(|(r, f)| (r.as_str(), f.as_str())),
             None,
+            layout_vars,
         );
         if should_start_layout_commands_suspended {
             layout
@@ -1369,6 +1461,7 @@ impl Layout {
         layout_dir: &Option<PathBuf>,
         layout_info: &LayoutInfo,
         config: Option<Config>,
+        layout_vars: &BTreeMap<String, String>,
     ) -> Result<(Layout, Config), ConfigError> {
         let mut should_start_layout_commands_suspended = false;
         let (path_to_raw_layout, raw_layout, raw_swap_layouts) = match layout_info {
@@ -1392,13 +1485,14 @@ impl Layout {
             },
             LayoutInfo::Stringified(stringified_layout) => (None, stringified_layout.clone(), None),
         };
-        let mut layout = Layout::from_kdl(
+        let mut layout = Layout::from_kdl_with_vars(
             &raw_layout,
             path_to_raw_layout,
             raw_swap_layouts
                 .as_ref()
                 .map(|(r, f)| (r.as_str(), f.as_str())),
             None,
+            layout_vars,
         );
         if should_start_layout_commands_suspended {
             layout
@@ -1461,25 +1555,32 @@ impl Layout {
         layout_path: Option<&PathBuf>,
         layout_dir: Option<PathBuf>,
         config: Config,
+        layout_vars: &BTreeMap<String, String>,
     ) -> Result<(Layout, Config), ConfigError> {
         let (path_to_raw_layout, raw_layout, raw_swap_layouts) =
             Layout::stringified_from_path_or_default(layout_path, layout_dir)?;
-        let layout = Layout::from_kdl(
+        let layout = Layout::from_kdl_with_vars(
             &raw_layout,
             Some(path_to_raw_layout),
             raw_swap_layouts
                 .as_ref()
                 .map(|(r, f)| (r.as_str(), f.as_str())),
             None,
+            layout_vars,
         )?;
         let config = Config::from_kdl(&raw_layout, Some(config))?; // this merges the two config, with
         Ok((layout, config))
     }
     #[cfg(not(target_family = "wasm"))]
-    pub fn from_url(url: &str, config: Config) -> Result<(Layout, Config), ConfigError> {
+    pub fn from_url(
+        url: &str,
+        config: Config,
+        layout_vars: &BTreeMap<String, String>,
+    ) -> Result<(Layout, Config), ConfigError> {
         let raw_layout = Downloader::download_without_cache_blocking(url)
             .map_err(|e| ConfigError::DownloadError(format!("{}", e)))?;
-        let mut layout = Layout::from_kdl(&raw_layout, Some(url.into()), None, None)?;
+        let mut layout =
+            Layout::from_kdl_with_vars(&raw_layout, Some(url.into()), None, None, layout_vars)?;
         layout.recursively_add_start_suspended_including_template(Some(true));
         let config = Config::from_kdl(&raw_layout, Some(config))?; // this merges the two config, with
         Ok((layout, config))
@@ -1487,13 +1588,18 @@ impl Layout {
     pub fn from_stringified_layout(
         stringified_layout: &str,
         config: Config,
+        layout_vars: &BTreeMap<String, String>,
     ) -> Result<(Layout, Config), ConfigError> {
-        let layout = Layout::from_kdl(&stringified_layout, None, None, None)?;
-        let config = Config::from_kdl(&stringified_layout, Some(config))?; // this merges the two config, with
+        let layout = Layout::from_kdl_with_vars(stringified_layout, None, None, None, layout_vars)?;
+        let config = Config::from_kdl(stringified_layout, Some(config))?; // this merges the two config, with
         Ok((layout, config))
     }
     #[cfg(target_family = "wasm")]
-    pub fn from_url(_url: &str, _config: Config) -> Result<(Layout, Config), ConfigError> {
+    pub fn from_url(
+        _url: &str,
+        _config: Config,
+        _layout_vars: &BTreeMap<String, String>,
+    ) -> Result<(Layout, Config), ConfigError> {
         Err(ConfigError::DownloadError(format!(
             "Unsupported platform, cannot download layout from the web"
         )))
@@ -1554,6 +1660,21 @@ impl Layout {
     ) -> Result<Layout, ConfigError> {
         Layout::from_kdl(raw, Some(path_to_raw_layout), swap_layouts, cwd)
     }
+    pub fn from_str_with_vars(
+        raw: &str,
+        path_to_raw_layout: String,
+        swap_layouts: Option<(&str, &str)>, // Option<path_to_swap_layout, stringified_swap_layout>
+        cwd: Option<PathBuf>,
+        layout_vars: &BTreeMap<String, String>,
+    ) -> Result<Layout, ConfigError> {
+        Layout::from_kdl_with_vars(
+            raw,
+            Some(path_to_raw_layout),
+            swap_layouts,
+            cwd,
+            layout_vars,
+        )
+    }
     pub fn stringified_from_dir(
         layout: &PathBuf,
         layout_dir: Option<&PathBuf>,
@@ -1791,6 +1912,36 @@ impl Layout {
             }
         }
     }
+    /// All the plugins in this layout, including those in its template and swap layouts (aliases
+    /// are only included if they were populated)
+    pub fn run_plugins(&self) -> Vec<RunPlugin> {
+        let floating_run_plugins = |floating_panes: &[FloatingPaneLayout]| -> Vec<RunPlugin> {
+            floating_panes
+                .iter()
+                .filter_map(|f| f.run.as_ref().and_then(|r| r.get_run_plugin()))
+                .collect()
+        };
+        let mut run_plugins = vec![];
+        for (_, tiled_pane_layout, floating_panes) in &self.tabs {
+            run_plugins.append(&mut tiled_pane_layout.run_plugins());
+            run_plugins.append(&mut floating_run_plugins(floating_panes));
+        }
+        if let Some((tiled_pane_layout, floating_panes)) = &self.template {
+            run_plugins.append(&mut tiled_pane_layout.run_plugins());
+            run_plugins.append(&mut floating_run_plugins(floating_panes));
+        }
+        for swap_tiled_layout in &self.swap_tiled_layouts {
+            for tiled_pane_layout in swap_tiled_layout.0.values() {
+                run_plugins.append(&mut tiled_pane_layout.run_plugins());
+            }
+        }
+        for swap_floating_layout in &self.swap_floating_layouts {
+            for floating_panes in swap_floating_layout.0.values() {
+                run_plugins.append(&mut floating_run_plugins(floating_panes));
+            }
+        }
+        run_plugins
+    }
     pub fn add_cwd_to_layout(&mut self, cwd: &PathBuf) {
         for (_, tiled_pane_layout, floating_panes) in self.tabs.iter_mut() {
             tiled_pane_layout.add_cwd_to_layout(&cwd);
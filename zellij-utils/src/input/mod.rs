@@ -2,12 +2,15 @@ pub mod actions;
 pub mod cli_assets;
 pub mod command;
 pub mod config;
+pub mod hooks;
 pub mod keybinds;
 pub mod layout;
 pub mod mouse;
+pub mod mousebinds;
 pub mod options;
 pub mod permission;
 pub mod plugins;
+pub mod scratchpads;
 pub mod theme;
 pub mod web_client;
 
@@ -50,6 +53,7 @@ mod not_wasm {
             web_clients_allowed: None,
             web_sharing: None,
             currently_marking_pane_group: None,
+            broadcasting_to_pane_group: None,
             is_web_client: None,
             web_server_ip: None,
             web_server_port: None,
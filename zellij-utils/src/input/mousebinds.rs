@@ -0,0 +1,240 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use super::actions::Action;
+use super::mouse::{MouseEvent, MouseEventType};
+use crate::data::KeyModifier;
+use crate::position::Position;
+
+use serde::{Deserialize, Serialize};
+
+/// Presses of the same button at the same position closer together than this are counted as a
+/// single multi-click
+const MULTI_CLICK_INTERVAL: Duration = Duration::from_millis(400);
+const MAX_CLICK_COUNT: usize = 3;
+
+/// Used in the config struct
+#[derive(Clone, PartialEq, Deserialize, Serialize, Default)]
+pub struct Mousebinds(pub HashMap<MouseBind, Vec<Action>>);
+
+impl fmt::Debug for Mousebinds {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let stable_sorted: BTreeMap<&MouseBind, &Vec<Action>> = self.0.iter().collect();
+        write!(f, "{:#?}", stable_sorted)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
+pub enum MouseButton {
+    Left,
+    Right,
+    Middle,
+    WheelUp,
+    WheelDown,
+}
+
+impl FromStr for MouseButton {
+    type Err = Box<dyn std::error::Error>;
+    fn from_str(button_str: &str) -> Result<Self, Self::Err> {
+        match button_str.to_ascii_lowercase().as_str() {
+            "left" => Ok(MouseButton::Left),
+            "right" => Ok(MouseButton::Right),
+            "middle" => Ok(MouseButton::Middle),
+            "wheelup" => Ok(MouseButton::WheelUp),
+            "wheeldown" => Ok(MouseButton::WheelDown),
+            _ => Err(format!("unsupported mouse button: {}", button_str).into()),
+        }
+    }
+}
+
+impl fmt::Display for MouseButton {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MouseButton::Left => write!(f, "Left"),
+            MouseButton::Right => write!(f, "Right"),
+            MouseButton::Middle => write!(f, "Middle"),
+            MouseButton::WheelUp => write!(f, "WheelUp"),
+            MouseButton::WheelDown => write!(f, "WheelDown"),
+        }
+    }
+}
+
+impl MouseButton {
+    /// The button pressed in this event, only press and wheel events are considered
+    pub fn from_event(event: &MouseEvent) -> Option<Self> {
+        if event.wheel_up {
+            Some(MouseButton::WheelUp)
+        } else if event.wheel_down {
+            Some(MouseButton::WheelDown)
+        } else if event.event_type != MouseEventType::Press {
+            None
+        } else if event.left {
+            Some(MouseButton::Left)
+        } else if event.right {
+            Some(MouseButton::Right)
+        } else if event.middle {
+            Some(MouseButton::Middle)
+        } else {
+            None
+        }
+    }
+}
+
+/// The part of the screen a mouse event happened on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
+pub enum MouseRegion {
+    PaneBody,
+    PaneFrame,
+    /// The bars the layout places around the panes (eg. the tab-bar or compact-bar), ie. its
+    /// non-selectable plugin panes outside of the viewport
+    TabBar,
+}
+
+impl FromStr for MouseRegion {
+    type Err = Box<dyn std::error::Error>;
+    fn from_str(region_str: &str) -> Result<Self, Self::Err> {
+        match region_str.to_ascii_lowercase().as_str() {
+            "pane_body" => Ok(MouseRegion::PaneBody),
+            "pane_frame" => Ok(MouseRegion::PaneFrame),
+            "tab_bar" => Ok(MouseRegion::TabBar),
+            _ => Err(format!("unsupported mouse region: {}", region_str).into()),
+        }
+    }
+}
+
+impl fmt::Display for MouseRegion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MouseRegion::PaneBody => write!(f, "pane_body"),
+            MouseRegion::PaneFrame => write!(f, "pane_frame"),
+            MouseRegion::TabBar => write!(f, "tab_bar"),
+        }
+    }
+}
+
+/// A mouse button press (or wheel scroll) with its modifiers, click count and the region it
+/// happened in. A `None` region matches everywhere.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
+pub struct MouseBind {
+    pub button: MouseButton,
+    pub modifiers: BTreeSet<KeyModifier>,
+    pub click_count: usize,
+    pub region: Option<MouseRegion>,
+}
+
+impl MouseBind {
+    pub fn new(button: MouseButton) -> Self {
+        MouseBind {
+            button,
+            modifiers: BTreeSet::new(),
+            click_count: 1,
+            region: None,
+        }
+    }
+    pub fn with_modifier(mut self, modifier: KeyModifier) -> Self {
+        self.modifiers.insert(modifier);
+        self
+    }
+    pub fn with_click_count(mut self, click_count: usize) -> Self {
+        self.click_count = click_count;
+        self
+    }
+    pub fn with_region(mut self, region: MouseRegion) -> Self {
+        self.region = Some(region);
+        self
+    }
+    pub fn from_event(
+        event: &MouseEvent,
+        click_count: usize,
+        region: Option<MouseRegion>,
+    ) -> Option<Self> {
+        let mut modifiers = BTreeSet::new();
+        if event.ctrl {
+            modifiers.insert(KeyModifier::Ctrl);
+        }
+        if event.alt {
+            modifiers.insert(KeyModifier::Alt);
+        }
+        if event.shift {
+            modifiers.insert(KeyModifier::Shift);
+        }
+        MouseButton::from_event(event).map(|button| MouseBind {
+            button,
+            modifiers,
+            click_count,
+            region,
+        })
+    }
+    /// The button and its modifiers, eg. "Ctrl Left"
+    pub fn button_to_string(&self) -> String {
+        let mut parts: Vec<String> = self.modifiers.iter().map(|m| m.to_string()).collect();
+        parts.push(self.button.to_string());
+        parts.join(" ")
+    }
+}
+
+impl Mousebinds {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+    /// The actions bound to this event, a bind for the specific region the event happened in
+    /// takes precedence over one that matches everywhere
+    pub fn get_actions_for_event(
+        &self,
+        event: &MouseEvent,
+        click_count: usize,
+        region: Option<MouseRegion>,
+    ) -> Option<&Vec<Action>> {
+        let region_bind = region.and_then(|r| MouseBind::from_event(event, click_count, Some(r)));
+        region_bind.and_then(|b| self.0.get(&b)).or_else(|| {
+            MouseBind::from_event(event, click_count, None).and_then(|b| self.0.get(&b))
+        })
+    }
+    pub fn merge(&mut self, other: Mousebinds) {
+        self.0.extend(other.0);
+    }
+}
+
+/// Counts consecutive presses of the same button at the same position to tell apart single,
+/// double and triple clicks
+#[derive(Debug, Default)]
+pub struct MouseClickCounter {
+    last_press: Option<(MouseButton, Position, Instant)>,
+    click_count: usize,
+}
+
+impl MouseClickCounter {
+    /// Register this event and return its click count, wheel events always count as a single
+    /// click
+    pub fn register(&mut self, event: &MouseEvent) -> usize {
+        self.register_at(event, Instant::now())
+    }
+    fn register_at(&mut self, event: &MouseEvent, now: Instant) -> usize {
+        let button = match MouseButton::from_event(event) {
+            Some(MouseButton::WheelUp) | Some(MouseButton::WheelDown) | None => return 1,
+            Some(button) => button,
+        };
+        let is_consecutive = match self.last_press {
+            Some((last_button, last_position, last_time)) => {
+                last_button == button
+                    && last_position == event.position
+                    && now.duration_since(last_time) <= MULTI_CLICK_INTERVAL
+            },
+            None => false,
+        };
+        self.click_count = if is_consecutive && self.click_count < MAX_CLICK_COUNT {
+            self.click_count + 1
+        } else {
+            1
+        };
+        self.last_press = Some((button, event.position, now));
+        self.click_count
+    }
+}
+
+// The unit test location.
+#[cfg(test)]
+#[path = "./unit/mousebinds_test.rs"]
+mod mousebinds_test;
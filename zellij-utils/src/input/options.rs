@@ -34,6 +34,30 @@ impl FromStr for OnForceClose {
     }
 }
 
+#[derive(Copy, Clone, Debug, Default, PartialEq, Deserialize, Serialize, ArgEnum)]
+pub enum LocalEcho {
+    #[serde(alias = "always")]
+    Always,
+    #[default]
+    #[serde(alias = "adaptive")]
+    Adaptive,
+    #[serde(alias = "never")]
+    Never,
+}
+
+impl FromStr for LocalEcho {
+    type Err = Box<dyn std::error::Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "always" => Ok(Self::Always),
+            "adaptive" => Ok(Self::Adaptive),
+            "never" => Ok(Self::Never),
+            e => Err(e.to_string().into()),
+        }
+    }
+}
+
 #[derive(Clone, Default, Debug, PartialEq, Deserialize, Serialize, Args)]
 /// Options that can be set either through the config file,
 /// or cli flags - cli flags should take precedence over the config file
@@ -268,6 +292,23 @@ pub struct Options {
     /// NOTE: This only applies to web clients at the moment.
     #[clap(long)]
     pub client_async_worker_tasks: Option<usize>,
+
+    /// The amount of fuel (roughly, WebAssembly instructions) a plugin may consume in a single
+    /// call (eg. while handling an event or rendering) before it is stopped. Set to 0 to disable
+    /// the limit.
+    #[clap(long, value_parser)]
+    pub plugin_cpu_budget: Option<u64>,
+
+    /// Load remote plugins from the bundle created by `zellij plugin vendor` in this directory,
+    /// verifying their recorded hash instead of downloading them
+    #[clap(long, value_parser)]
+    pub plugin_bundle_dir: Option<PathBuf>,
+
+    /// Whether clients attached to a remote session echo typed characters locally before the
+    /// server confirms them (always, adaptive or never), adaptive only does so over slow
+    /// connections
+    #[clap(long, arg_enum, hide_possible_values = true, value_parser)]
+    pub local_echo: Option<LocalEcho>,
 }
 
 #[derive(ArgEnum, Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
@@ -372,6 +413,11 @@ impl Options {
         let client_async_worker_tasks = other
             .client_async_worker_tasks
             .or(self.client_async_worker_tasks);
+        let plugin_cpu_budget = other.plugin_cpu_budget.or(self.plugin_cpu_budget);
+        let plugin_bundle_dir = other
+            .plugin_bundle_dir
+            .or_else(|| self.plugin_bundle_dir.clone());
+        let local_echo = other.local_echo.or(self.local_echo);
 
         Options {
             simplified_ui,
@@ -419,6 +465,9 @@ impl Options {
             enforce_https_for_localhost,
             post_command_discovery_hook,
             client_async_worker_tasks,
+            plugin_cpu_budget,
+            plugin_bundle_dir,
+            local_echo,
         }
     }
 
@@ -503,6 +552,11 @@ impl Options {
         let client_async_worker_tasks = other
             .client_async_worker_tasks
             .or(self.client_async_worker_tasks);
+        let plugin_cpu_budget = other.plugin_cpu_budget.or(self.plugin_cpu_budget);
+        let plugin_bundle_dir = other
+            .plugin_bundle_dir
+            .or_else(|| self.plugin_bundle_dir.clone());
+        let local_echo = other.local_echo.or(self.local_echo);
 
         Options {
             simplified_ui,
@@ -550,6 +604,9 @@ impl Options {
             enforce_https_for_localhost,
             post_command_discovery_hook,
             client_async_worker_tasks,
+            plugin_cpu_budget,
+            plugin_bundle_dir,
+            local_echo,
         }
     }
 
@@ -5,9 +5,27 @@ use std::{
     path::PathBuf,
 };
 
+use sha2::{Digest, Sha256};
+
 use crate::{consts::ZELLIJ_PLUGIN_PERMISSIONS_CACHE, data::PermissionType};
 
-pub type GrantedPermission = HashMap<String, Vec<PermissionType>>;
+pub type GrantedPermission = HashMap<String, PluginGrant>;
+
+/// The permissions granted to a plugin, pinned to the contents of the wasm file they were
+/// granted to
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct PluginGrant {
+    // None for grants cached before they were pinned, the user is asked to grant these again
+    pub content_hash: Option<String>,
+    pub permissions: Vec<PermissionType>,
+}
+
+/// The hex encoded SHA-256 of a plugin's wasm bytes
+pub fn plugin_content_hash(wasm_bytes: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(wasm_bytes);
+    format!("{:x}", hasher.finalize())
+}
 
 #[derive(Default, Debug)]
 pub struct PermissionCache {
@@ -16,23 +34,53 @@ pub struct PermissionCache {
 }
 
 impl PermissionCache {
-    pub fn cache(&mut self, plugin_name: String, permissions: Vec<PermissionType>) {
-        self.granted.insert(plugin_name, permissions);
+    pub fn cache(
+        &mut self,
+        plugin_name: String,
+        content_hash: Option<String>,
+        permissions: Vec<PermissionType>,
+    ) {
+        self.granted.insert(
+            plugin_name,
+            PluginGrant {
+                content_hash,
+                permissions,
+            },
+        );
     }
 
     pub fn get_permissions(&self, plugin_name: String) -> Option<&Vec<PermissionType>> {
-        self.granted.get(&plugin_name)
+        self.granted.get(&plugin_name).map(|g| &g.permissions)
+    }
+
+    pub fn granted(&self) -> &GrantedPermission {
+        &self.granted
     }
 
+    /// Returns true if all the permissions were granted to this exact plugin (as identified by
+    /// its content hash)
     pub fn check_permissions(
         &self,
         plugin_name: String,
+        content_hash: Option<&str>,
         permissions_to_check: &Vec<PermissionType>,
     ) -> bool {
         if let Some(target) = self.granted.get(&plugin_name) {
+            match (&target.content_hash, content_hash) {
+                (Some(granted_hash), Some(content_hash)) if granted_hash != content_hash => {
+                    // the plugin changed since the permissions were granted
+                    return false;
+                },
+                (None, Some(_content_hash)) => {
+                    // granted before grants were pinned, so we can't tell whether this is the
+                    // plugin they were granted to
+                    return false;
+                },
+                _ => {},
+            }
             let mut all_granted = true;
             for permission in permissions_to_check {
-                if !target.contains(permission) {
+                if !target.permissions.contains(permission) {
                     all_granted = false;
                 }
             }
@@ -42,6 +90,18 @@ impl PermissionCache {
         false
     }
 
+    /// Removes a single permission from a plugin's grant, returns true if it was granted
+    pub fn revoke(&mut self, plugin_name: &str, permission: &PermissionType) -> bool {
+        match self.granted.get_mut(plugin_name) {
+            Some(grant) => {
+                let granted_count = grant.permissions.len();
+                grant.permissions.retain(|p| p != permission);
+                grant.permissions.len() != granted_count
+            },
+            None => false,
+        }
+    }
+
     pub fn from_path_or_default(cache_path: Option<PathBuf>) -> Self {
         let cache_path = cache_path.unwrap_or(ZELLIJ_PLUGIN_PERMISSIONS_CACHE.to_path_buf());
 
@@ -65,3 +125,65 @@ impl PermissionCache {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn permissions_are_pinned_to_content_hash() {
+        let temp_folder = tempfile::tempdir().unwrap();
+        let cache_path = temp_folder.path().join("permissions.kdl");
+        let mut permission_cache = PermissionCache::from_path_or_default(Some(cache_path.clone()));
+        let permissions = vec![PermissionType::ReadApplicationState];
+        permission_cache.cache(
+            "file:/path/to/plugin.wasm".to_owned(),
+            Some(plugin_content_hash(b"original")),
+            permissions.clone(),
+        );
+        permission_cache.write_to_file().unwrap();
+
+        let permission_cache = PermissionCache::from_path_or_default(Some(cache_path));
+        assert!(permission_cache.check_permissions(
+            "file:/path/to/plugin.wasm".to_owned(),
+            Some(&plugin_content_hash(b"original")),
+            &permissions
+        ));
+        assert!(!permission_cache.check_permissions(
+            "file:/path/to/plugin.wasm".to_owned(),
+            Some(&plugin_content_hash(b"changed")),
+            &permissions
+        ));
+    }
+
+    #[test]
+    fn legacy_permissions_are_not_trusted() {
+        let temp_folder = tempfile::tempdir().unwrap();
+        let cache_path = temp_folder.path().join("permissions.kdl");
+        fs::write(
+            &cache_path,
+            "\"file:/path/to/plugin.wasm\" {\n    ReadApplicationState\n}\n",
+        )
+        .unwrap();
+        let permissions = vec![PermissionType::ReadApplicationState];
+        let permission_cache = PermissionCache::from_path_or_default(Some(cache_path.clone()));
+        assert!(
+            !permission_cache.check_permissions(
+                "file:/path/to/plugin.wasm".to_owned(),
+                Some(&plugin_content_hash(b"original")),
+                &permissions
+            ),
+            "grant without a content hash must be granted again"
+        );
+
+        // the legacy grant is left as is until the user grants the permissions again
+        let permission_cache = PermissionCache::from_path_or_default(Some(cache_path));
+        assert_eq!(
+            permission_cache
+                .granted()
+                .get("file:/path/to/plugin.wasm")
+                .map(|grant| grant.content_hash.clone()),
+            Some(None)
+        );
+    }
+}
@@ -69,6 +69,8 @@ impl PluginConfig {
                     || tag == "multiple-select"
                     || tag == "layout-manager"
                     || tag == "link"
+                    || tag == "pane-search"
+                    || tag == "closed-panes"
                 {
                     Some(PluginConfig {
                         path: PathBuf::from(&tag),
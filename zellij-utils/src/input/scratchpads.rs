@@ -0,0 +1,67 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use super::command::RunCommand;
+use crate::data::FloatingPaneCoordinates;
+
+use serde::{Deserialize, Serialize};
+
+/// Named scratchpads, declared in the config or in a layout and toggled with `ToggleScratchpad`
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, Default)]
+pub struct Scratchpads(pub BTreeMap<String, Scratchpad>);
+
+impl Scratchpads {
+    pub fn get(&self, name: &str) -> Option<&Scratchpad> {
+        self.0.get(name)
+    }
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+    /// Scratchpads in `other` replace those with the same name
+    pub fn merge(&mut self, other: Scratchpads) {
+        self.0.extend(other.0);
+    }
+}
+
+/// A floating terminal that is created the first time it is toggled and then hidden and shown
+/// without affecting the other floating panes
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, Default)]
+pub struct Scratchpad {
+    /// The default shell is opened if this is empty
+    pub command: Option<PathBuf>,
+    pub args: Vec<String>,
+    pub cwd: Option<PathBuf>,
+    pub coordinates: Option<FloatingPaneCoordinates>,
+    /// Whether showing the scratchpad from another tab brings it to the focused tab rather than
+    /// going to the tab it is in
+    pub move_to_focused_tab: bool,
+}
+
+impl Scratchpad {
+    /// The command to open this scratchpad with, a command that exits is held so that its output
+    /// can be read, the shell closes with its scratchpad
+    pub fn to_run_command(&self, default_shell: &Path) -> RunCommand {
+        RunCommand {
+            command: self
+                .command
+                .clone()
+                .unwrap_or_else(|| default_shell.to_path_buf()),
+            args: self.args.clone(),
+            cwd: self.cwd.clone(),
+            hold_on_close: self.command.is_some(),
+            ..Default::default()
+        }
+    }
+    pub fn with_cwd_prefix(mut self, cwd_prefix: &Path) -> Self {
+        self.cwd = match self.cwd.take() {
+            Some(cwd) => Some(cwd_prefix.join(cwd)),
+            None => Some(cwd_prefix.to_path_buf()),
+        };
+        self
+    }
+}
+
+// The unit test location.
+#[cfg(test)]
+#[path = "./unit/scratchpads_test.rs"]
+mod scratchpads_test;
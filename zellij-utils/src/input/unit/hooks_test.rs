@@ -0,0 +1,133 @@
+use super::super::config::Config;
+use super::super::hooks::*;
+use crate::data::InputMode;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+#[test]
+fn can_define_hooks_in_configfile() {
+    let config_contents = r#"
+        hooks {
+            client_attached "echo attached >> /tmp/log"
+            command_pane_exited "notify $ZELLIJ_HOOK_EXIT_CODE" timeout=30
+            client_attached "notify attached"
+        }
+    "#;
+    let config = Config::from_kdl(config_contents, None).unwrap();
+    assert_eq!(
+        config
+            .hooks
+            .for_event(HookEvent::ClientAttached)
+            .collect::<Vec<_>>(),
+        vec![
+            &Hook {
+                event: HookEvent::ClientAttached,
+                command: "echo attached >> /tmp/log".to_owned(),
+                timeout_secs: None,
+            },
+            &Hook {
+                event: HookEvent::ClientAttached,
+                command: "notify attached".to_owned(),
+                timeout_secs: None,
+            },
+        ],
+        "Several hooks for the same event run in order"
+    );
+    let command_pane_exited_hook = config
+        .hooks
+        .for_event(HookEvent::CommandPaneExited)
+        .next()
+        .unwrap();
+    assert_eq!(command_pane_exited_hook.timeout_secs, Some(30));
+    assert_eq!(
+        command_pane_exited_hook.timeout(),
+        std::time::Duration::from_secs(30)
+    );
+    assert_eq!(config.hooks.for_event(HookEvent::TabClosed).count(), 0);
+}
+
+#[test]
+fn hooks_with_unknown_events_or_without_commands_are_rejected() {
+    let unknown_event = r#"
+        hooks {
+            client_exploded "echo"
+        }
+    "#;
+    assert!(Config::from_kdl(unknown_event, None).is_err());
+    let without_command = r#"
+        hooks {
+            client_attached
+        }
+    "#;
+    assert!(Config::from_kdl(without_command, None).is_err());
+}
+
+#[test]
+fn hooks_survive_config_serialization() {
+    let config_contents = r#"
+        hooks {
+            cwd_changed "direnv export bash > /tmp/env" timeout=5
+            session_killed "echo \"bye\""
+        }
+    "#;
+    let config = Config::from_kdl(config_contents, None).unwrap();
+    let serialized = config.to_string(false);
+    let deserialized = Config::from_kdl(&serialized, None).unwrap();
+    assert_eq!(config.hooks, deserialized.hooks);
+}
+
+#[test]
+fn a_hooks_block_replaces_the_hooks_of_its_base_config() {
+    let base_config = Config::from_kdl(
+        r#"
+        hooks {
+            tab_created "echo one"
+        }
+    "#,
+        None,
+    )
+    .unwrap();
+    let config = Config::from_kdl(
+        r#"
+        hooks {
+            tab_closed "echo two"
+        }
+    "#,
+        Some(base_config.clone()),
+    )
+    .unwrap();
+    assert_eq!(config.hooks.0.len(), 1);
+    assert_eq!(config.hooks.0[0].event, HookEvent::TabClosed);
+    let config_without_hooks = Config::from_kdl("", Some(base_config.clone())).unwrap();
+    assert_eq!(config_without_hooks.hooks, base_config.hooks);
+}
+
+#[test]
+fn hook_context_is_passed_through_environment_variables() {
+    let context = HookContext {
+        client_id: Some(2),
+        pane_id: Some(4),
+        command: Some("cargo test".to_owned()),
+        exit_code: Some(101),
+        cwd: Some(PathBuf::from("/tmp/project")),
+        mode: Some(InputMode::Locked),
+        ..Default::default()
+    };
+    let expected: BTreeMap<String, String> = [
+        ("ZELLIJ_HOOK_EVENT", "command_pane_exited"),
+        ("ZELLIJ_HOOK_CLIENT_ID", "2"),
+        ("ZELLIJ_HOOK_PANE_ID", "4"),
+        ("ZELLIJ_HOOK_COMMAND", "cargo test"),
+        ("ZELLIJ_HOOK_EXIT_CODE", "101"),
+        ("ZELLIJ_HOOK_CWD", "/tmp/project"),
+        ("ZELLIJ_HOOK_MODE", "locked"),
+    ]
+    .iter()
+    .map(|(k, v)| (k.to_string(), v.to_string()))
+    .collect();
+    assert_eq!(
+        context.env_variables(HookEvent::CommandPaneExited),
+        expected,
+        "Only the fields set for the event are passed"
+    );
+}
@@ -1,4 +1,5 @@
 use super::super::layout::*;
+use crate::data::PaneInputLock;
 use insta::assert_snapshot;
 
 #[cfg(not(windows))]
@@ -216,6 +217,49 @@ fn layout_with_hidden_floating_panes() {
     assert_eq!(layout, expected_layout);
 }
 
+#[test]
+fn layout_with_tab_tiling_strategy() {
+    let kdl_layout = r#"
+        layout {
+            tab tiling="master-stack" master_ratio=60 {
+                pane
+                pane
+            }
+            tab tiling="spiral" {
+                pane
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None).unwrap();
+    assert_eq!(
+        layout.tabs[0].1.tiling_strategy,
+        Some(TilingStrategy::MasterStack {
+            master_ratio: 60,
+            master_count: 1,
+        })
+    );
+    assert_eq!(
+        layout.tabs[1].1.tiling_strategy,
+        Some(TilingStrategy::Spiral)
+    );
+}
+
+#[test]
+fn layout_with_invalid_tab_tiling_strategy() {
+    let kdl_layout = r#"
+        layout {
+            tab tiling="grid" master_count=2 {
+                pane
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None);
+    assert!(
+        layout.is_err(),
+        "master options are only valid for master-stack"
+    );
+}
+
 #[test]
 fn layout_with_floating_panes_template() {
     let kdl_layout = r#"
@@ -1109,6 +1153,48 @@ fn layout_with_pane_excluded_from_sync() {
     assert_snapshot!(normalize_layout_debug(format!("{:#?}", layout)));
 }
 
+#[test]
+fn layout_with_input_locked_panes() {
+    let kdl_layout = r#"
+        layout {
+            pane_template name="shell" {
+                command "bash"
+            }
+            pane input_lock="confirm"
+            shell
+            shell input_lock="drop"
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None).unwrap();
+    let input_locks: Vec<Option<PaneInputLock>> = layout
+        .template
+        .unwrap()
+        .0
+        .children
+        .iter()
+        .map(|p| p.input_lock)
+        .collect();
+    assert_eq!(
+        input_locks,
+        vec![
+            Some(PaneInputLock::Confirm),
+            None,
+            Some(PaneInputLock::Drop)
+        ]
+    );
+}
+
+#[test]
+fn cannot_define_an_unknown_input_lock() {
+    let kdl_layout = r#"
+        layout {
+            pane input_lock="sometimes"
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None);
+    assert!(layout.is_err(), "error provided for unknown input lock");
+}
+
 #[test]
 fn cannot_define_tab_template_name_with_space() {
     let kdl_layout = r#"
@@ -2535,3 +2621,193 @@ fn tiled_pane_still_rejects_zero_percent() {
     let result = SplitSize::from_str("1%");
     assert!(result.is_ok());
 }
+
+#[test]
+fn layout_params_are_interpolated_with_their_defaults() {
+    let kdl_layout = r#"
+        layout {
+            param "service" default="api"
+            pane name="{{service}}" command="cargo" cwd="/srv/{{service}}" {
+                args "run" "--bin" "{{service}}"
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None).unwrap();
+    let expected_layout = Layout {
+        template: Some((
+            TiledPaneLayout {
+                children: vec![TiledPaneLayout {
+                    name: Some("api".into()),
+                    run: Some(Run::Command(RunCommand {
+                        command: PathBuf::from("cargo"),
+                        args: vec!["run".into(), "--bin".into(), "api".into()],
+                        cwd: Some(PathBuf::from("/srv/api")),
+                        hold_on_close: true,
+                        ..Default::default()
+                    })),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+            vec![],
+        )),
+        ..Default::default()
+    };
+    assert_eq!(layout, expected_layout);
+}
+
+#[test]
+fn layout_vars_override_param_defaults() {
+    let kdl_layout = r#"
+        layout {
+            param "service" default="api"
+            pane name="{{service}}" command="cargo" cwd="/srv/{{service}}" {
+                args "run" "--bin" "{{service}}"
+            }
+        }
+    "#;
+    let mut layout_vars = BTreeMap::new();
+    layout_vars.insert("service".to_owned(), "worker".to_owned());
+    let layout = Layout::from_kdl_with_vars(
+        kdl_layout,
+        Some("layout_file_name".into()),
+        None,
+        None,
+        &layout_vars,
+    )
+    .unwrap();
+    let expected_layout = Layout {
+        template: Some((
+            TiledPaneLayout {
+                children: vec![TiledPaneLayout {
+                    name: Some("worker".into()),
+                    run: Some(Run::Command(RunCommand {
+                        command: PathBuf::from("cargo"),
+                        args: vec!["run".into(), "--bin".into(), "worker".into()],
+                        cwd: Some(PathBuf::from("/srv/worker")),
+                        hold_on_close: true,
+                        ..Default::default()
+                    })),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+            vec![],
+        )),
+        ..Default::default()
+    };
+    assert_eq!(layout, expected_layout);
+}
+
+#[test]
+fn layout_vars_are_interpolated_into_plugin_configuration() {
+    let kdl_layout = r#"
+        layout {
+            param "root" default="/tmp"
+            pane {
+                plugin location="zellij:strider" {
+                    start_folder "{{root}}"
+                }
+            }
+        }
+    "#;
+    let mut layout_vars = BTreeMap::new();
+    layout_vars.insert("root".to_owned(), "/srv".to_owned());
+    let layout = Layout::from_kdl_with_vars(
+        kdl_layout,
+        Some("layout_file_name".into()),
+        None,
+        None,
+        &layout_vars,
+    )
+    .unwrap();
+    let run_plugins = layout.run_plugins();
+    assert_eq!(run_plugins.len(), 1);
+    assert_eq!(
+        run_plugins[0].configuration.inner().get("start_folder"),
+        Some(&"/srv".to_owned())
+    );
+}
+
+#[test]
+fn layout_text_in_double_braces_that_is_not_a_param_is_left_alone() {
+    let kdl_layout = r#"
+        layout {
+            param "service" default="api"
+            pane command="echo" {
+                args "{{service}}" "{{not_a_param}}"
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None).unwrap();
+    let (tiled_layout, _) = layout.new_tab();
+    match &tiled_layout.children[0].run {
+        Some(Run::Command(run_command)) => {
+            assert_eq!(
+                run_command.args,
+                vec!["api".to_owned(), "{{not_a_param}}".to_owned()]
+            );
+        },
+        run => panic!("Expected a command pane, found: {:?}", run),
+    }
+}
+
+#[test]
+fn layout_var_for_undeclared_param_is_an_error() {
+    let kdl_layout = r#"
+        layout {
+            param "service" default="api"
+            pane
+        }
+    "#;
+    let mut layout_vars = BTreeMap::new();
+    layout_vars.insert("srvice".to_owned(), "worker".to_owned());
+    let layout = Layout::from_kdl_with_vars(
+        kdl_layout,
+        Some("layout_file_name".into()),
+        None,
+        None,
+        &layout_vars,
+    );
+    assert!(layout.is_err(), "undeclared layout var is an error");
+}
+
+#[test]
+fn layout_param_without_default_is_an_error() {
+    let kdl_layout = r#"
+        layout {
+            param "service"
+            pane
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None);
+    assert!(
+        layout.is_err(),
+        "layout param without a default is an error"
+    );
+}
+
+#[test]
+fn layout_params_can_be_listed_without_parsing_the_layout() {
+    let kdl_layout = r#"
+        layout {
+            param "service" default="api"
+            param "port" default="8080"
+            pane
+        }
+    "#;
+    let params = Layout::params_from_kdl(kdl_layout).unwrap();
+    assert_eq!(
+        params,
+        vec![
+            crate::data::LayoutParam {
+                name: "service".to_owned(),
+                default: "api".to_owned(),
+            },
+            crate::data::LayoutParam {
+                name: "port".to_owned(),
+                default: "8080".to_owned(),
+            },
+        ]
+    );
+}
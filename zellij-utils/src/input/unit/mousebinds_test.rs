@@ -0,0 +1,208 @@
+use super::super::actions::*;
+use super::super::mouse::MouseEvent;
+use super::super::mousebinds::*;
+use crate::data::{Direction, KeyModifier};
+use crate::input::config::Config;
+use crate::position::Position;
+use std::time::{Duration, Instant};
+
+#[test]
+fn can_define_mousebinds_in_configfile() {
+    let config_contents = r#"
+        mousebinds {
+            bind "Middle" { Write 10; }
+            bind "Ctrl Left" region="pane_body" { EditScrollback; }
+            bind "Left" clicks=2 region="pane_frame" { ToggleFocusFullscreen; }
+        }
+    "#;
+    let config = Config::from_kdl(config_contents, None).unwrap();
+    assert_eq!(
+        config
+            .mousebinds
+            .0
+            .get(&MouseBind::new(MouseButton::Middle)),
+        Some(&vec![Action::Write {
+            key_with_modifier: None,
+            bytes: vec![10],
+            is_kitty_keyboard_protocol: false,
+        }]),
+        "Plain mousebind defined"
+    );
+    assert_eq!(
+        config.mousebinds.0.get(
+            &MouseBind::new(MouseButton::Left)
+                .with_modifier(KeyModifier::Ctrl)
+                .with_region(MouseRegion::PaneBody)
+        ),
+        Some(&vec![Action::EditScrollback { ansi: false }]),
+        "Mousebind with modifier and region defined"
+    );
+    assert_eq!(
+        config.mousebinds.0.get(
+            &MouseBind::new(MouseButton::Left)
+                .with_click_count(2)
+                .with_region(MouseRegion::PaneFrame)
+        ),
+        Some(&vec![Action::ToggleFocusFullscreen]),
+        "Double click mousebind defined"
+    );
+}
+
+#[test]
+fn can_unbind_mousebinds() {
+    let config_contents = r#"
+        mousebinds {
+            bind "Middle" { FocusNextPane; }
+            bind "WheelUp" { MoveFocus "Up"; }
+        }
+    "#;
+    let config = Config::from_kdl(config_contents, None).unwrap();
+    let unbind_contents = r#"
+        mousebinds {
+            unbind "Middle"
+        }
+    "#;
+    let config = Config::from_kdl(unbind_contents, Some(config)).unwrap();
+    assert_eq!(
+        config
+            .mousebinds
+            .0
+            .get(&MouseBind::new(MouseButton::Middle)),
+        None,
+        "Mousebind removed"
+    );
+    assert_eq!(
+        config
+            .mousebinds
+            .0
+            .get(&MouseBind::new(MouseButton::WheelUp)),
+        Some(&vec![Action::MoveFocus {
+            direction: Direction::Up
+        }]),
+        "Other mousebind untouched"
+    );
+}
+
+#[test]
+fn invalid_mousebinds_are_reported() {
+    let invalid_button = r#"
+        mousebinds {
+            bind "Ctrl Sideways" { FocusNextPane; }
+        }
+    "#;
+    assert!(Config::from_kdl(invalid_button, None).is_err());
+    let invalid_region = r#"
+        mousebinds {
+            bind "Left" region="status_bar" { FocusNextPane; }
+        }
+    "#;
+    assert!(Config::from_kdl(invalid_region, None).is_err());
+    let invalid_click_count = r#"
+        mousebinds {
+            bind "Left" clicks=4 { FocusNextPane; }
+        }
+    "#;
+    assert!(Config::from_kdl(invalid_click_count, None).is_err());
+}
+
+#[test]
+fn mousebinds_survive_config_serialization() {
+    let config_contents = r#"
+        mousebinds {
+            bind "Ctrl Left" clicks=2 region="tab_bar" { NewTab; }
+            bind "Middle" { FocusNextPane; }
+        }
+    "#;
+    let config = Config::from_kdl(config_contents, None).unwrap();
+    let serialized = config.to_string(false);
+    let deserialized = Config::from_kdl(&serialized, None).unwrap();
+    assert_eq!(config.mousebinds, deserialized.mousebinds);
+}
+
+#[test]
+fn region_specific_mousebind_takes_precedence() {
+    let config_contents = r#"
+        mousebinds {
+            bind "Middle" { FocusNextPane; }
+            bind "Middle" region="pane_frame" { FocusPreviousPane; }
+        }
+    "#;
+    let config = Config::from_kdl(config_contents, None).unwrap();
+    let event = MouseEvent::new_middle_press_event(Position::new(0, 0));
+    assert_eq!(
+        config
+            .mousebinds
+            .get_actions_for_event(&event, 1, Some(MouseRegion::PaneFrame)),
+        Some(&vec![Action::FocusPreviousPane])
+    );
+    assert_eq!(
+        config
+            .mousebinds
+            .get_actions_for_event(&event, 1, Some(MouseRegion::PaneBody)),
+        Some(&vec![Action::FocusNextPane])
+    );
+    assert_eq!(
+        config.mousebinds.get_actions_for_event(&event, 2, None),
+        None,
+        "Double click does not match a single click bind"
+    );
+    let release = MouseEvent::new_middle_release_event(Position::new(0, 0));
+    assert_eq!(
+        config.mousebinds.get_actions_for_event(&release, 1, None),
+        None,
+        "Releases never match"
+    );
+}
+
+#[test]
+fn mousebind_matches_event_modifiers() {
+    let config_contents = r#"
+        mousebinds {
+            bind "Ctrl Alt Right" { FocusNextPane; }
+        }
+    "#;
+    let config = Config::from_kdl(config_contents, None).unwrap();
+    let mut event = MouseEvent::new_right_press_event(Position::new(0, 0));
+    assert_eq!(
+        config.mousebinds.get_actions_for_event(&event, 1, None),
+        None
+    );
+    event.ctrl = true;
+    event.alt = true;
+    assert_eq!(
+        config.mousebinds.get_actions_for_event(&event, 1, None),
+        Some(&vec![Action::FocusNextPane])
+    );
+}
+
+#[test]
+fn click_counter_counts_consecutive_presses() {
+    let mut click_counter = MouseClickCounter::default();
+    let press = MouseEvent::new_left_press_event(Position::new(1, 1));
+    let start = Instant::now();
+    assert_eq!(click_counter.register_at(&press, start), 1);
+    assert_eq!(
+        click_counter.register_at(&press, start + Duration::from_millis(100)),
+        2
+    );
+    assert_eq!(
+        click_counter.register_at(&press, start + Duration::from_millis(200)),
+        3
+    );
+    assert_eq!(
+        click_counter.register_at(&press, start + Duration::from_millis(300)),
+        1,
+        "Wraps around after a triple click"
+    );
+    assert_eq!(
+        click_counter.register_at(&press, start + Duration::from_secs(2)),
+        1,
+        "Slow presses are separate clicks"
+    );
+    let elsewhere = MouseEvent::new_left_press_event(Position::new(5, 5));
+    assert_eq!(
+        click_counter.register_at(&elsewhere, start + Duration::from_millis(2100)),
+        1,
+        "Presses at a different position are separate clicks"
+    );
+}
@@ -0,0 +1,96 @@
+use super::super::config::Config;
+use super::super::layout::{Layout, PercentOrFixed};
+use super::super::scratchpads::*;
+use crate::data::FloatingPaneCoordinates;
+use std::path::PathBuf;
+
+#[test]
+fn can_define_scratchpads_in_configfile() {
+    let config_contents = r#"
+        scratchpads {
+            notes command="vim" cwd="/tmp/notes" width="80%" height=20 x="10%" y=2 move_to_focused_tab=true {
+                args "todo.md"
+            }
+            shell
+        }
+    "#;
+    let config = Config::from_kdl(config_contents, None).unwrap();
+    assert_eq!(
+        config.scratchpads.get("notes"),
+        Some(&Scratchpad {
+            command: Some(PathBuf::from("vim")),
+            args: vec!["todo.md".to_owned()],
+            cwd: Some(PathBuf::from("/tmp/notes")),
+            coordinates: Some(FloatingPaneCoordinates {
+                x: Some(PercentOrFixed::Percent(10)),
+                y: Some(PercentOrFixed::Fixed(2)),
+                width: Some(PercentOrFixed::Percent(80)),
+                height: Some(PercentOrFixed::Fixed(20)),
+                pinned: None,
+                borderless: None,
+            }),
+            move_to_focused_tab: true,
+        }),
+        "Scratchpad with a command, size and position"
+    );
+    assert_eq!(
+        config.scratchpads.get("shell"),
+        Some(&Scratchpad::default()),
+        "Scratchpad with the default shell"
+    );
+}
+
+#[test]
+fn scratchpads_survive_config_serialization() {
+    let config_contents = r#"
+        scratchpads {
+            notes command="vim" width="80%" height=20 move_to_focused_tab=true {
+                args "todo.md"
+            }
+        }
+    "#;
+    let config = Config::from_kdl(config_contents, None).unwrap();
+    let serialized = config.to_string(false);
+    let deserialized = Config::from_kdl(&serialized, None).unwrap();
+    assert_eq!(config.scratchpads, deserialized.scratchpads);
+}
+
+#[test]
+fn can_define_scratchpads_in_layout() {
+    let kdl_layout = r#"
+        layout cwd="/home" {
+            pane
+            scratchpads {
+                logs command="tail" cwd="logs" {
+                    args "-f" "zellij.log"
+                }
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, Some("layout_file_name".into()), None, None).unwrap();
+    assert_eq!(
+        layout.scratchpads.get("logs"),
+        Some(&Scratchpad {
+            command: Some(PathBuf::from("tail")),
+            args: vec!["-f".to_owned(), "zellij.log".to_owned()],
+            cwd: Some(PathBuf::from("/home/logs")),
+            ..Default::default()
+        })
+    );
+}
+
+#[test]
+fn scratchpads_run_their_command_or_the_default_shell() {
+    let default_shell = PathBuf::from("/bin/bash");
+    let command_scratchpad = Scratchpad {
+        command: Some(PathBuf::from("htop")),
+        ..Default::default()
+    };
+    let run_command = command_scratchpad.to_run_command(&default_shell);
+    assert_eq!(run_command.command, PathBuf::from("htop"));
+    assert!(run_command.hold_on_close, "Command output is held on exit");
+    let shell_scratchpad = Scratchpad::default();
+    let run_command = shell_scratchpad.to_run_command(&default_shell);
+    assert_eq!(run_command.command, default_shell);
+    assert!(!run_command.hold_on_close, "Shell closes on exit");
+}
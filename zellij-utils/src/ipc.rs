@@ -12,6 +12,7 @@ use std::{
     fmt::{Display, Error, Formatter},
     io::{self, Read, Write},
     marker::PhantomData,
+    path::PathBuf,
 };
 
 // Protobuf imports
@@ -156,6 +157,17 @@ pub enum ClientToServerMsg {
     DesktopNotificationResponse {
         raw_bytes: Vec<u8>,
     },
+    // sent from one session server to another, the pane's PTY fd is then passed over the
+    // transfer socket
+    AdoptPane {
+        transfer_socket: PathBuf,
+        pane_title: Option<String>,
+        scrollback: String,
+        child_pid: Option<u32>,
+    },
+    // sent by the web server when a remote client reattaches to its existing client slot, the
+    // client's terminal has missed renders while it was disconnected
+    ClientReconnected,
 }
 
 // Types of messages sent from the server to the client
@@ -200,6 +212,14 @@ pub enum ServerToClientMsg {
     SubscribedPaneClosed {
         pane_id: PaneId,
     },
+    // sent to web clients when the focused pane changes between a state in which they can echo
+    // typed characters locally (eg. a shell prompt) and one in which they cannot, the columns
+    // bound the line the cursor is on
+    LocalEchoState {
+        predictable: bool,
+        first_column: usize,
+        last_column: usize,
+    },
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
@@ -1,17 +1,18 @@
 use crate::{
     client_server_contract::client_server_contract::{
-        client_to_server_msg, server_to_client_msg, ActionMsg, AttachClientMsg,
+        client_to_server_msg, server_to_client_msg, ActionMsg, AdoptPaneMsg, AttachClientMsg,
         AttachWatcherClientMsg, BackgroundColorMsg, CliPipeOutputMsg, ClientExitedMsg,
-        ClientToServerMsg as ProtoClientToServerMsg, ColorRegistersMsg, ConfigFileUpdatedMsg,
-        ConnStatusMsg, ConnectedMsg, DesktopNotificationResponseMsg, DetachSessionMsg, ExitMsg,
-        ExitReason as ProtoExitReason, FailedToStartWebServerMsg, FirstClientConnectedMsg,
-        ForegroundColorMsg, InputMode as ProtoInputMode, KeyMsg, KillSessionMsg,
-        LayoutMetadata as ProtoLayoutMetadata, LogErrorMsg, LogMsg,
-        PaneMetadata as ProtoPaneMetadata, PaneRenderUpdateMsg, QueryTerminalSizeMsg,
-        RenamedSessionMsg, RenderMsg, ServerToClientMsg as ProtoServerToClientMsg,
-        StartWebServerMsg, SubscribeToPaneRendersMsg, SubscribedPaneClosedMsg, SwitchSessionMsg,
-        TabMetadata as ProtoTabMetadata, TerminalPixelDimensionsMsg, TerminalResizeMsg,
-        UnblockCliPipeInputMsg, UnblockInputThreadMsg, WebServerStartedMsg,
+        ClientReconnectedMsg, ClientToServerMsg as ProtoClientToServerMsg, ColorRegistersMsg,
+        ConfigFileUpdatedMsg, ConnStatusMsg, ConnectedMsg, DesktopNotificationResponseMsg,
+        DetachSessionMsg, ExitMsg, ExitReason as ProtoExitReason, FailedToStartWebServerMsg,
+        FirstClientConnectedMsg, ForegroundColorMsg, InputMode as ProtoInputMode, KeyMsg,
+        KillSessionMsg, LayoutMetadata as ProtoLayoutMetadata, LayoutParam as ProtoLayoutParam,
+        LocalEchoStateMsg, LogErrorMsg, LogMsg, PaneMetadata as ProtoPaneMetadata,
+        PaneRenderUpdateMsg, QueryTerminalSizeMsg, RenamedSessionMsg, RenderMsg,
+        ServerToClientMsg as ProtoServerToClientMsg, StartWebServerMsg, SubscribeToPaneRendersMsg,
+        SubscribedPaneClosedMsg, SwitchSessionMsg, TabMetadata as ProtoTabMetadata,
+        TerminalPixelDimensionsMsg, TerminalResizeMsg, UnblockCliPipeInputMsg,
+        UnblockInputThreadMsg, WebServerStartedMsg,
     },
     data::{InputMode, PaneId},
     errors::prelude::*,
@@ -129,6 +130,20 @@ impl From<ClientToServerMsg> for ProtoClientToServerMsg {
                     DesktopNotificationResponseMsg { raw_bytes },
                 )
             },
+            ClientToServerMsg::AdoptPane {
+                transfer_socket,
+                pane_title,
+                scrollback,
+                child_pid,
+            } => client_to_server_msg::Message::AdoptPane(AdoptPaneMsg {
+                transfer_socket: transfer_socket.to_string_lossy().to_string(),
+                pane_title,
+                scrollback,
+                child_pid,
+            }),
+            ClientToServerMsg::ClientReconnected => {
+                client_to_server_msg::Message::ClientReconnected(ClientReconnectedMsg {})
+            },
         };
 
         ProtoClientToServerMsg {
@@ -257,6 +272,17 @@ impl TryFrom<ProtoClientToServerMsg> for ClientToServerMsg {
                     raw_bytes: msg.raw_bytes,
                 })
             },
+            Some(client_to_server_msg::Message::AdoptPane(msg)) => {
+                Ok(ClientToServerMsg::AdoptPane {
+                    transfer_socket: PathBuf::from(msg.transfer_socket),
+                    pane_title: msg.pane_title,
+                    scrollback: msg.scrollback,
+                    child_pid: msg.child_pid,
+                })
+            },
+            Some(client_to_server_msg::Message::ClientReconnected(_)) => {
+                Ok(ClientToServerMsg::ClientReconnected)
+            },
             None => Err(anyhow!("Empty ClientToServerMsg message")),
         }
     }
@@ -336,6 +362,15 @@ impl From<ServerToClientMsg> for ProtoServerToClientMsg {
                     pane_id: Some(pane_id.into()),
                 })
             },
+            ServerToClientMsg::LocalEchoState {
+                predictable,
+                first_column,
+                last_column,
+            } => server_to_client_msg::Message::LocalEchoState(LocalEchoStateMsg {
+                predictable,
+                first_column: first_column as u32,
+                last_column: last_column as u32,
+            }),
         };
 
         ProtoServerToClientMsg {
@@ -442,6 +477,13 @@ impl TryFrom<ProtoServerToClientMsg> for ServerToClientMsg {
                     .try_into()?;
                 Ok(ServerToClientMsg::SubscribedPaneClosed { pane_id })
             },
+            Some(server_to_client_msg::Message::LocalEchoState(msg)) => {
+                Ok(ServerToClientMsg::LocalEchoState {
+                    predictable: msg.predictable,
+                    first_column: msg.first_column as usize,
+                    last_column: msg.last_column as usize,
+                })
+            },
             None => Err(anyhow!("Empty ServerToClientMsg message")),
         }
     }
@@ -574,6 +616,7 @@ impl From<crate::input::cli_assets::CliAssets>
             should_ignore_config: cli_assets.should_ignore_config,
             configuration_options: cli_assets.configuration_options.map(|o| o.into()),
             layout: cli_assets.layout.map(|l| l.into()),
+            layout_vars: cli_assets.layout_vars.into_iter().collect(),
             terminal_window_size: Some(cli_assets.terminal_window_size.into()),
             data_dir: cli_assets.data_dir.map(|p| p.to_string_lossy().to_string()),
             is_debug: cli_assets.is_debug,
@@ -600,6 +643,7 @@ impl TryFrom<crate::client_server_contract::client_server_contract::CliAssets>
                 .map(|o| o.try_into())
                 .transpose()?,
             layout: cli_assets.layout.map(|l| l.try_into()).transpose()?,
+            layout_vars: cli_assets.layout_vars.into_iter().collect(),
             terminal_window_size: cli_assets
                 .terminal_window_size
                 .ok_or_else(|| anyhow!("CliAssets missing terminal_window_size"))?
@@ -618,8 +662,8 @@ impl From<crate::input::options::Options>
 {
     fn from(options: crate::input::options::Options) -> Self {
         use crate::client_server_contract::client_server_contract::{
-            Clipboard as ProtoClipboard, OnForceClose as ProtoOnForceClose,
-            WebSharing as ProtoWebSharing,
+            Clipboard as ProtoClipboard, LocalEcho as ProtoLocalEcho,
+            OnForceClose as ProtoOnForceClose, WebSharing as ProtoWebSharing,
         };
 
         Self {
@@ -688,6 +732,15 @@ impl From<crate::input::options::Options>
             visual_bell: options.visual_bell,
             focus_follows_mouse: options.focus_follows_mouse,
             mouse_click_through: options.mouse_click_through,
+            plugin_cpu_budget: options.plugin_cpu_budget,
+            plugin_bundle_dir: options
+                .plugin_bundle_dir
+                .map(|p| p.to_string_lossy().to_string()),
+            local_echo: options.local_echo.map(|l| match l {
+                crate::input::options::LocalEcho::Always => ProtoLocalEcho::Always as i32,
+                crate::input::options::LocalEcho::Adaptive => ProtoLocalEcho::Adaptive as i32,
+                crate::input::options::LocalEcho::Never => ProtoLocalEcho::Never as i32,
+            }),
         }
     }
 }
@@ -700,8 +753,8 @@ impl TryFrom<crate::client_server_contract::client_server_contract::Options>
         options: crate::client_server_contract::client_server_contract::Options,
     ) -> Result<Self> {
         use crate::client_server_contract::client_server_contract::{
-            Clipboard as ProtoClipboard, OnForceClose as ProtoOnForceClose,
-            WebSharing as ProtoWebSharing,
+            Clipboard as ProtoClipboard, LocalEcho as ProtoLocalEcho,
+            OnForceClose as ProtoOnForceClose, WebSharing as ProtoWebSharing,
         };
 
         Ok(Self {
@@ -783,6 +836,19 @@ impl TryFrom<crate::client_server_contract::client_server_contract::Options>
             visual_bell: options.visual_bell,
             focus_follows_mouse: options.focus_follows_mouse,
             mouse_click_through: options.mouse_click_through,
+            plugin_cpu_budget: options.plugin_cpu_budget,
+            plugin_bundle_dir: options.plugin_bundle_dir.map(std::path::PathBuf::from),
+            local_echo: options
+                .local_echo
+                .map(|l| match ProtoLocalEcho::from_i32(l) {
+                    Some(ProtoLocalEcho::Always) => Ok(crate::input::options::LocalEcho::Always),
+                    Some(ProtoLocalEcho::Adaptive) => {
+                        Ok(crate::input::options::LocalEcho::Adaptive)
+                    },
+                    Some(ProtoLocalEcho::Never) => Ok(crate::input::options::LocalEcho::Never),
+                    _ => Err(anyhow!("Invalid LocalEcho value: {}", l)),
+                })
+                .transpose()?,
         })
     }
 }
@@ -811,6 +877,7 @@ impl From<crate::input::actions::Action>
             ConfirmAction,
             CopyAction,
             CurrentTabInfoAction,
+            DeleteSnapshotAction,
             DenyAction,
             DetachAction,
             DumpLayoutAction,
@@ -818,6 +885,7 @@ impl From<crate::input::actions::Action>
             EditFileAction,
             EditScrollbackAction,
             EditScrollbackByPaneIdAction,
+            EqualizePanesAction,
             FocusNextPaneAction,
             FocusPaneByPaneIdAction,
             FocusPluginPaneWithIdAction,
@@ -838,6 +906,7 @@ impl From<crate::input::actions::Action>
             LaunchPluginAction,
             ListClientsAction,
             ListPanesAction,
+            ListSnapshotsAction,
             ListTabsAction,
             MouseEventAction,
             MoveFocusAction,
@@ -879,10 +948,13 @@ impl From<crate::input::actions::Action>
             RenameTabAction,
             RenameTabByIdAction,
             RenameTerminalPaneAction,
+            ReopenClosedPaneAction,
             ResizeAction,
             ResizeByPaneIdAction,
+            RestoreSnapshotAction,
             RunAction,
             SaveSessionAction,
+            SaveSnapshotAction,
             ScrollDownAction,
             ScrollDownAtAction,
             ScrollDownByPaneIdAction,
@@ -897,8 +969,10 @@ impl From<crate::input::actions::Action>
             SearchAction,
             SearchInputAction,
             SearchToggleOptionAction,
+            SendPaneToSessionAction,
             SetPaneBorderlessAction,
             SetPaneColorAction,
+            SetTilingStrategyAction,
             ShowFloatingPanesAction,
             SkipConfirmAction,
             StackPanesAction,
@@ -914,6 +988,7 @@ impl From<crate::input::actions::Action>
             ToggleFloatingPanesByTabIdAction,
             ToggleFocusFullscreenAction,
             ToggleFullscreenByPaneIdAction,
+            ToggleGroupBroadcastAction,
             ToggleGroupMarkingAction,
             ToggleMouseModeAction,
             TogglePaneBorderlessAction,
@@ -921,8 +996,11 @@ impl From<crate::input::actions::Action>
             TogglePaneEmbedOrFloatingByPaneIdAction,
             TogglePaneFramesAction,
             TogglePaneInGroupAction,
+            TogglePaneInputLockAction,
+            TogglePaneInputLockByPaneIdAction,
             TogglePanePinnedAction,
             TogglePanePinnedByPaneIdAction,
+            ToggleScratchpadAction,
             ToggleTabAction,
             UndoRenamePaneAction,
             UndoRenamePaneByPaneIdAction,
@@ -1011,6 +1089,16 @@ impl From<crate::input::actions::Action>
             crate::input::actions::Action::MovePaneBackwards => {
                 ActionType::MovePaneBackwards(MovePaneBackwardsAction {})
             },
+            crate::input::actions::Action::EqualizePanes { direction } => {
+                ActionType::EqualizePanes(EqualizePanesAction {
+                    direction: direction.map(split_direction_to_proto_i32),
+                })
+            },
+            crate::input::actions::Action::SetTilingStrategy { strategy } => {
+                ActionType::SetTilingStrategy(SetTilingStrategyAction {
+                    strategy: strategy.map(|s| s.into()),
+                })
+            },
             crate::input::actions::Action::ClearScreen => {
                 ActionType::ClearScreen(ClearScreenAction {})
             },
@@ -1206,6 +1294,9 @@ impl From<crate::input::actions::Action>
             crate::input::actions::Action::CloseFocus => {
                 ActionType::CloseFocus(CloseFocusAction {})
             },
+            crate::input::actions::Action::ReopenClosedPane { id } => {
+                ActionType::ReopenClosedPane(ReopenClosedPaneAction { id })
+            },
             crate::input::actions::Action::PaneNameInput { input } => {
                 ActionType::PaneNameInput(PaneNameInputAction {
                     input: input.into_iter().map(|b| b as u32).collect(),
@@ -1491,6 +1582,12 @@ impl From<crate::input::actions::Action>
             crate::input::actions::Action::RenameSession { name } => {
                 ActionType::RenameSession(RenameSessionAction { name })
             },
+            crate::input::actions::Action::SendPaneToSession { name, pane_id } => {
+                ActionType::SendPaneToSession(SendPaneToSessionAction {
+                    name,
+                    pane_id: pane_id.map(|p| p.into()),
+                })
+            },
             crate::input::actions::Action::CliPipe {
                 pipe_id,
                 name,
@@ -1604,9 +1701,36 @@ impl From<crate::input::actions::Action>
             crate::input::actions::Action::ToggleGroupMarking => {
                 ActionType::ToggleGroupMarking(ToggleGroupMarkingAction {})
             },
+            crate::input::actions::Action::ToggleGroupBroadcast => {
+                ActionType::ToggleGroupBroadcast(ToggleGroupBroadcastAction {})
+            },
+            crate::input::actions::Action::ToggleScratchpad { name } => {
+                ActionType::ToggleScratchpad(ToggleScratchpadAction { name })
+            },
             crate::input::actions::Action::SaveSession => {
                 ActionType::SaveSession(SaveSessionAction {})
             },
+            crate::input::actions::Action::SaveSnapshot {
+                name,
+                include_viewport,
+            } => ActionType::SaveSnapshot(SaveSnapshotAction {
+                name,
+                include_viewport,
+            }),
+            crate::input::actions::Action::ListSnapshots => {
+                ActionType::ListSnapshots(ListSnapshotsAction {})
+            },
+            crate::input::actions::Action::DeleteSnapshot { name } => {
+                ActionType::DeleteSnapshot(DeleteSnapshotAction { name })
+            },
+            crate::input::actions::Action::RestoreSnapshot { name, replace } => {
+                ActionType::RestoreSnapshot(RestoreSnapshotAction { name, replace })
+            },
+            crate::input::actions::Action::TogglePaneInputLock { input_lock } => {
+                ActionType::TogglePaneInputLock(TogglePaneInputLockAction {
+                    input_lock: input_lock.name().to_owned(),
+                })
+            },
             crate::input::actions::Action::ListTabs {
                 show_state,
                 show_dimensions,
@@ -1739,6 +1863,13 @@ impl From<crate::input::actions::Action>
                     pane_id: Some(pane_id.into()),
                 })
             },
+            crate::input::actions::Action::TogglePaneInputLockByPaneId {
+                pane_id,
+                input_lock,
+            } => ActionType::TogglePaneInputLockByPaneId(TogglePaneInputLockByPaneIdAction {
+                pane_id: Some(pane_id.into()),
+                input_lock: input_lock.name().to_owned(),
+            }),
             crate::input::actions::Action::FocusPaneByPaneId { pane_id } => {
                 ActionType::FocusPaneByPaneId(FocusPaneByPaneIdAction {
                     pane_id: Some(pane_id.into()),
@@ -1869,6 +2000,22 @@ impl TryFrom<crate::client_server_contract::client_server_contract::Action>
             ActionType::MovePaneBackwards(_) => {
                 Ok(crate::input::actions::Action::MovePaneBackwards)
             },
+            ActionType::EqualizePanes(equalize_panes_action) => {
+                Ok(crate::input::actions::Action::EqualizePanes {
+                    direction: equalize_panes_action
+                        .direction
+                        .map(proto_i32_to_split_direction)
+                        .transpose()?,
+                })
+            },
+            ActionType::SetTilingStrategy(set_tiling_strategy_action) => {
+                Ok(crate::input::actions::Action::SetTilingStrategy {
+                    strategy: set_tiling_strategy_action
+                        .strategy
+                        .map(|s| s.try_into())
+                        .transpose()?,
+                })
+            },
             ActionType::ClearScreen(_) => Ok(crate::input::actions::Action::ClearScreen),
             ActionType::DumpScreen(dump_screen_action) => {
                 let file_path = if dump_screen_action.dump_to_stdout {
@@ -1885,6 +2032,32 @@ impl TryFrom<crate::client_server_contract::client_server_contract::Action>
             },
             ActionType::DumpLayout(_) => Ok(crate::input::actions::Action::DumpLayout),
             ActionType::SaveSession(_) => Ok(crate::input::actions::Action::SaveSession),
+            ActionType::SaveSnapshot(save_snapshot_action) => {
+                Ok(crate::input::actions::Action::SaveSnapshot {
+                    name: save_snapshot_action.name,
+                    include_viewport: save_snapshot_action.include_viewport,
+                })
+            },
+            ActionType::ListSnapshots(_) => Ok(crate::input::actions::Action::ListSnapshots),
+            ActionType::DeleteSnapshot(delete_snapshot_action) => {
+                Ok(crate::input::actions::Action::DeleteSnapshot {
+                    name: delete_snapshot_action.name,
+                })
+            },
+            ActionType::RestoreSnapshot(restore_snapshot_action) => {
+                Ok(crate::input::actions::Action::RestoreSnapshot {
+                    name: restore_snapshot_action.name,
+                    replace: restore_snapshot_action.replace,
+                })
+            },
+            ActionType::TogglePaneInputLock(toggle_pane_input_lock_action) => {
+                Ok(crate::input::actions::Action::TogglePaneInputLock {
+                    input_lock: toggle_pane_input_lock_action
+                        .input_lock
+                        .parse()
+                        .map_err(|e| anyhow!("Invalid TogglePaneInputLock: {}", e))?,
+                })
+            },
             ActionType::EditScrollback(edit_scrollback_action) => {
                 Ok(crate::input::actions::Action::EditScrollback {
                     ansi: edit_scrollback_action.ansi,
@@ -2046,6 +2219,11 @@ impl TryFrom<crate::client_server_contract::client_server_contract::Action>
                 })
             },
             ActionType::CloseFocus(_) => Ok(crate::input::actions::Action::CloseFocus),
+            ActionType::ReopenClosedPane(reopen_closed_pane_action) => {
+                Ok(crate::input::actions::Action::ReopenClosedPane {
+                    id: reopen_closed_pane_action.id,
+                })
+            },
             ActionType::PaneNameInput(pane_name_action) => {
                 Ok(crate::input::actions::Action::PaneNameInput {
                     input: pane_name_action
@@ -2368,6 +2546,14 @@ impl TryFrom<crate::client_server_contract::client_server_contract::Action>
                     name: rename_session_action.name,
                 })
             },
+            ActionType::SendPaneToSession(send_pane_to_session_action) => {
+                Ok(crate::input::actions::Action::SendPaneToSession {
+                    name: send_pane_to_session_action.name,
+                    pane_id: send_pane_to_session_action
+                        .pane_id
+                        .and_then(|p| p.try_into().ok()),
+                })
+            },
             ActionType::CliPipe(cli_pipe_action) => Ok(crate::input::actions::Action::CliPipe {
                 pipe_id: cli_pipe_action.pipe_id,
                 name: cli_pipe_action.name,
@@ -2485,6 +2671,14 @@ impl TryFrom<crate::client_server_contract::client_server_contract::Action>
             ActionType::ToggleGroupMarking(_) => {
                 Ok(crate::input::actions::Action::ToggleGroupMarking)
             },
+            ActionType::ToggleGroupBroadcast(_) => {
+                Ok(crate::input::actions::Action::ToggleGroupBroadcast)
+            },
+            ActionType::ToggleScratchpad(toggle_scratchpad_action) => {
+                Ok(crate::input::actions::Action::ToggleScratchpad {
+                    name: toggle_scratchpad_action.name,
+                })
+            },
             ActionType::SetPaneColor(set_pane_color_action) => {
                 Ok(crate::input::actions::Action::SetPaneColor {
                     pane_id: set_pane_color_action
@@ -2664,6 +2858,18 @@ impl TryFrom<crate::client_server_contract::client_server_contract::Action>
                         .try_into()?,
                 })
             },
+            ActionType::TogglePaneInputLockByPaneId(a) => {
+                Ok(crate::input::actions::Action::TogglePaneInputLockByPaneId {
+                    pane_id: a
+                        .pane_id
+                        .ok_or_else(|| anyhow!("TogglePaneInputLockByPaneId missing pane_id"))?
+                        .try_into()?,
+                    input_lock: a
+                        .input_lock
+                        .parse()
+                        .map_err(|e| anyhow!("Invalid TogglePaneInputLockByPaneId: {}", e))?,
+                })
+            },
             ActionType::FocusPaneByPaneId(a) => {
                 Ok(crate::input::actions::Action::FocusPaneByPaneId {
                     pane_id: a
@@ -2856,6 +3062,7 @@ impl From<crate::data::LayoutMetadata> for ProtoLayoutMetadata {
             tabs: metadata.tabs.into_iter().map(|t| t.into()).collect(),
             creation_time: metadata.creation_time,
             update_time: metadata.update_time,
+            params: metadata.params.into_iter().map(|p| p.into()).collect(),
         }
     }
 }
@@ -2872,10 +3079,33 @@ impl TryFrom<ProtoLayoutMetadata> for crate::data::LayoutMetadata {
             tabs,
             creation_time: proto_metadata.creation_time,
             update_time: proto_metadata.update_time,
+            params: proto_metadata
+                .params
+                .into_iter()
+                .map(|p| p.into())
+                .collect(),
         })
     }
 }
 
+impl From<crate::data::LayoutParam> for ProtoLayoutParam {
+    fn from(param: crate::data::LayoutParam) -> Self {
+        ProtoLayoutParam {
+            name: param.name,
+            default_value: param.default,
+        }
+    }
+}
+
+impl From<ProtoLayoutParam> for crate::data::LayoutParam {
+    fn from(proto_param: ProtoLayoutParam) -> Self {
+        crate::data::LayoutParam {
+            name: proto_param.name,
+            default: proto_param.default_value,
+        }
+    }
+}
+
 impl From<crate::data::TabMetadata> for ProtoTabMetadata {
     fn from(metadata: crate::data::TabMetadata) -> Self {
         ProtoTabMetadata {
@@ -3576,6 +3806,56 @@ fn split_direction_to_proto_i32(direction: crate::input::layout::SplitDirection)
     }
 }
 
+fn proto_i32_to_split_direction(direction: i32) -> Result<crate::input::layout::SplitDirection> {
+    use crate::client_server_contract::client_server_contract::SplitDirection as ProtoSplitDirection;
+    match direction {
+        x if x == ProtoSplitDirection::Horizontal as i32 => {
+            Ok(crate::input::layout::SplitDirection::Horizontal)
+        },
+        x if x == ProtoSplitDirection::Vertical as i32 => {
+            Ok(crate::input::layout::SplitDirection::Vertical)
+        },
+        _ => Err(anyhow!("Invalid SplitDirection: {}", direction)),
+    }
+}
+
+// TilingStrategy conversion
+impl From<crate::input::layout::TilingStrategy>
+    for crate::client_server_contract::client_server_contract::TilingStrategy
+{
+    fn from(strategy: crate::input::layout::TilingStrategy) -> Self {
+        let (master_ratio, master_count) = match strategy {
+            crate::input::layout::TilingStrategy::MasterStack {
+                master_ratio,
+                master_count,
+            } => (Some(master_ratio as u32), Some(master_count as u32)),
+            _ => (None, None),
+        };
+        Self {
+            name: strategy.name().to_owned(),
+            master_ratio,
+            master_count,
+        }
+    }
+}
+
+impl TryFrom<crate::client_server_contract::client_server_contract::TilingStrategy>
+    for crate::input::layout::TilingStrategy
+{
+    type Error = anyhow::Error;
+
+    fn try_from(
+        strategy: crate::client_server_contract::client_server_contract::TilingStrategy,
+    ) -> Result<Self> {
+        crate::input::layout::TilingStrategy::new(
+            &strategy.name,
+            strategy.master_ratio.map(|r| r as usize),
+            strategy.master_count.map(|c| c as usize),
+        )
+        .map_err(|e| anyhow!("Invalid TilingStrategy: {}", e))
+    }
+}
+
 // SplitSize conversion
 impl From<crate::input::layout::SplitSize>
     for crate::client_server_contract::client_server_contract::SplitSize
@@ -3747,6 +4027,8 @@ impl From<crate::input::layout::TiledPaneLayout>
             pane_initial_contents: layout.pane_initial_contents,
             default_fg: layout.default_fg,
             default_bg: layout.default_bg,
+            tiling_strategy: layout.tiling_strategy.map(|s| s.into()),
+            input_lock: layout.input_lock.map(|l| l.name().to_owned()),
         }
     }
 }
@@ -4175,8 +4457,14 @@ impl TryFrom<crate::client_server_contract::client_server_contract::TiledPaneLay
             children_are_stacked: layout.children_are_stacked,
             is_expanded_in_stack: layout.is_expanded_in_stack,
             exclude_from_sync: layout.exclude_from_sync,
+            input_lock: layout
+                .input_lock
+                .map(|l| l.parse())
+                .transpose()
+                .map_err(|e: String| anyhow!(e))?,
             run_instructions_to_ignore: vec![], // not represented in protobuf
             hide_floating_panes: layout.hide_floating_panes,
+            tiling_strategy: layout.tiling_strategy.map(|s| s.try_into()).transpose()?,
             pane_initial_contents: layout.pane_initial_contents,
             default_fg: layout.default_fg,
             default_bg: layout.default_bg,
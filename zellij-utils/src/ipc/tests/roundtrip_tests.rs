@@ -1,8 +1,8 @@
 use super::test_framework::*;
 use crate::data::{
     BareKey, CommandOrPlugin, ConnectToSession, Direction, FloatingPaneCoordinates, InputMode,
-    KeyModifier, KeyWithModifier, LayoutInfo, LayoutMetadata, NewPanePlacement, OriginatingPlugin,
-    PaneId, PluginTag, Resize, WebSharing,
+    KeyModifier, KeyWithModifier, LayoutInfo, LayoutMetadata, LayoutParam, NewPanePlacement,
+    OriginatingPlugin, PaneId, PaneInputLock, PluginTag, Resize, WebSharing,
 };
 use crate::input::actions::{Action, SearchDirection, SearchOption};
 use crate::input::cli_assets::CliAssets;
@@ -10,10 +10,10 @@ use crate::input::command::{OpenFilePayload, RunCommand, RunCommandAction};
 use crate::input::layout::{
     FloatingPaneLayout, LayoutConstraint, PercentOrFixed, PluginAlias, PluginUserConfiguration,
     Run, RunPlugin, RunPluginLocation, RunPluginOrAlias, SplitDirection, SplitSize,
-    TiledPaneLayout,
+    TiledPaneLayout, TilingStrategy,
 };
 use crate::input::mouse::{MouseEvent, MouseEventType};
-use crate::input::options::{Clipboard, OnForceClose, Options};
+use crate::input::options::{Clipboard, LocalEcho, OnForceClose, Options};
 use crate::ipc::{
     ClientToServerMsg, ColorRegister, ExitReason, PaneReference, PixelDimensions, ServerToClientMsg,
 };
@@ -400,6 +400,7 @@ fn test_client_messages() {
             should_ignore_config: true,
             configuration_options: None,
             layout: None,
+            layout_vars: Default::default(),
             terminal_window_size: Size { rows: 80, cols: 42 },
             data_dir: Some(PathBuf::from("/path/to/data/dir")),
             is_debug: true,
@@ -416,6 +417,7 @@ fn test_client_messages() {
             should_ignore_config: true,
             configuration_options: Some(Options::default()),
             layout: None,
+            layout_vars: Default::default(),
             terminal_window_size: Size { rows: 80, cols: 42 },
             data_dir: Some(PathBuf::from("/path/to/data/dir")),
             is_debug: true,
@@ -476,8 +478,12 @@ fn test_client_messages() {
                 visual_bell: Some(true),
                 focus_follows_mouse: Some(false),
                 mouse_click_through: Some(false),
+                plugin_cpu_budget: Some(1000),
+                plugin_bundle_dir: Some(PathBuf::from("/tmp/plugin-bundle")),
+                local_echo: Some(LocalEcho::Always),
             }),
             layout: None,
+            layout_vars: Default::default(),
             terminal_window_size: Size { rows: 80, cols: 42 },
             data_dir: Some(PathBuf::from("/path/to/data/dir")),
             is_debug: true,
@@ -867,6 +873,45 @@ fn test_client_messages() {
         client_id: Some(100),
         is_cli_client: true,
     });
+    test_client_roundtrip!(ClientToServerMsg::Action {
+        action: Action::EqualizePanes { direction: None },
+        terminal_id: Some(1),
+        client_id: Some(100),
+        is_cli_client: true,
+    });
+    test_client_roundtrip!(ClientToServerMsg::Action {
+        action: Action::EqualizePanes {
+            direction: Some(SplitDirection::Vertical),
+        },
+        terminal_id: Some(1),
+        client_id: Some(100),
+        is_cli_client: true,
+    });
+    test_client_roundtrip!(ClientToServerMsg::Action {
+        action: Action::SetTilingStrategy {
+            strategy: Some(TilingStrategy::MasterStack {
+                master_ratio: 60,
+                master_count: 2,
+            }),
+        },
+        terminal_id: Some(1),
+        client_id: Some(100),
+        is_cli_client: true,
+    });
+    test_client_roundtrip!(ClientToServerMsg::Action {
+        action: Action::SetTilingStrategy {
+            strategy: Some(TilingStrategy::Spiral),
+        },
+        terminal_id: Some(1),
+        client_id: Some(100),
+        is_cli_client: true,
+    });
+    test_client_roundtrip!(ClientToServerMsg::Action {
+        action: Action::SetTilingStrategy { strategy: None },
+        terminal_id: Some(1),
+        client_id: Some(100),
+        is_cli_client: true,
+    });
     test_client_roundtrip!(ClientToServerMsg::Action {
         action: Action::ClearScreen,
         terminal_id: Some(1),
@@ -1466,6 +1511,12 @@ fn test_client_messages() {
         client_id: Some(100),
         is_cli_client: true,
     });
+    test_client_roundtrip!(ClientToServerMsg::Action {
+        action: Action::ReopenClosedPane { id: Some(2) },
+        terminal_id: Some(1),
+        client_id: Some(100),
+        is_cli_client: true,
+    });
     test_client_roundtrip!(ClientToServerMsg::Action {
         action: Action::PaneNameInput {
             input: "name input".as_bytes().to_vec(),
@@ -2775,6 +2826,24 @@ fn test_client_messages() {
         client_id: Some(100),
         is_cli_client: true,
     });
+    test_client_roundtrip!(ClientToServerMsg::Action {
+        action: Action::SendPaneToSession {
+            name: "other_session_name".to_owned(),
+            pane_id: Some(PaneId::Terminal(2)),
+        },
+        terminal_id: Some(1),
+        client_id: Some(100),
+        is_cli_client: true,
+    });
+    test_client_roundtrip!(ClientToServerMsg::Action {
+        action: Action::SendPaneToSession {
+            name: "other_session_name".to_owned(),
+            pane_id: None,
+        },
+        terminal_id: None,
+        client_id: None,
+        is_cli_client: false,
+    });
     test_client_roundtrip!(ClientToServerMsg::Action {
         action: Action::CliPipe {
             pipe_id: "pipe_id_name".to_owned(),
@@ -2928,6 +2997,60 @@ fn test_client_messages() {
         client_id: Some(100),
         is_cli_client: true,
     });
+    test_client_roundtrip!(ClientToServerMsg::Action {
+        action: Action::ToggleGroupBroadcast,
+        terminal_id: Some(1),
+        client_id: Some(100),
+        is_cli_client: true,
+    });
+    test_client_roundtrip!(ClientToServerMsg::Action {
+        action: Action::ToggleScratchpad {
+            name: "notes".to_owned(),
+        },
+        terminal_id: Some(1),
+        client_id: Some(100),
+        is_cli_client: true,
+    });
+    test_client_roundtrip!(ClientToServerMsg::Action {
+        action: Action::SaveSnapshot {
+            name: "before-refactor".to_owned(),
+            include_viewport: true,
+        },
+        terminal_id: Some(1),
+        client_id: Some(100),
+        is_cli_client: true,
+    });
+    test_client_roundtrip!(ClientToServerMsg::Action {
+        action: Action::ListSnapshots,
+        terminal_id: Some(1),
+        client_id: Some(100),
+        is_cli_client: true,
+    });
+    test_client_roundtrip!(ClientToServerMsg::Action {
+        action: Action::DeleteSnapshot {
+            name: "before-refactor".to_owned(),
+        },
+        terminal_id: Some(1),
+        client_id: Some(100),
+        is_cli_client: true,
+    });
+    test_client_roundtrip!(ClientToServerMsg::Action {
+        action: Action::RestoreSnapshot {
+            name: "before-refactor".to_owned(),
+            replace: true,
+        },
+        terminal_id: Some(1),
+        client_id: Some(100),
+        is_cli_client: true,
+    });
+    test_client_roundtrip!(ClientToServerMsg::Action {
+        action: Action::TogglePaneInputLock {
+            input_lock: PaneInputLock::Confirm,
+        },
+        terminal_id: Some(1),
+        client_id: Some(100),
+        is_cli_client: true,
+    });
     test_client_roundtrip!(ClientToServerMsg::Action {
         action: Action::SetPaneColor {
             pane_id: PaneId::Terminal(0),
@@ -3239,6 +3362,19 @@ fn test_client_messages() {
     test_client_roundtrip!(ClientToServerMsg::ClientExited);
     test_client_roundtrip!(ClientToServerMsg::KillSession);
     test_client_roundtrip!(ClientToServerMsg::ConnStatus);
+    test_client_roundtrip!(ClientToServerMsg::AdoptPane {
+        transfer_socket: PathBuf::from("/tmp/zellij-1000/pane-transfer"),
+        pane_title: Some("my pane".to_owned()),
+        scrollback: "\u{1b}[31mhi\u{1b}[m\n".to_owned(),
+        child_pid: Some(1234),
+    });
+    test_client_roundtrip!(ClientToServerMsg::AdoptPane {
+        transfer_socket: PathBuf::from("/tmp/zellij-1000/pane-transfer"),
+        pane_title: None,
+        scrollback: String::new(),
+        child_pid: None,
+    });
+    test_client_roundtrip!(ClientToServerMsg::ClientReconnected);
     test_client_roundtrip!(ClientToServerMsg::WebServerStarted {
         base_url: "http://localhost:8080".to_string(),
     });
@@ -3472,6 +3608,15 @@ fn test_client_messages() {
         client_id: Some(100),
         is_cli_client: true,
     });
+    test_client_roundtrip!(ClientToServerMsg::Action {
+        action: Action::TogglePaneInputLockByPaneId {
+            pane_id: PaneId::Terminal(1),
+            input_lock: PaneInputLock::Drop,
+        },
+        terminal_id: Some(1),
+        client_id: Some(100),
+        is_cli_client: true,
+    });
     test_client_roundtrip!(ClientToServerMsg::Action {
         action: Action::FocusPaneByPaneId {
             pane_id: PaneId::Terminal(1),
@@ -3605,7 +3750,11 @@ fn test_server_messages() {
                 LayoutMetadata {
                     tabs: vec![],
                     creation_time: "0".to_owned(),
-                    update_time: "0".to_owned()
+                    update_time: "0".to_owned(),
+                    params: vec![LayoutParam {
+                        name: "service".to_owned(),
+                        default: "api".to_owned(),
+                    }],
                 }
             )),
             cwd: Some(PathBuf::from("/path/to/cwd")),
@@ -3655,6 +3804,16 @@ fn test_server_messages() {
     test_server_roundtrip!(ServerToClientMsg::SubscribedPaneClosed {
         pane_id: PaneId::Plugin(3),
     });
+    test_server_roundtrip!(ServerToClientMsg::LocalEchoState {
+        predictable: true,
+        first_column: 2,
+        last_column: 80,
+    });
+    test_server_roundtrip!(ServerToClientMsg::LocalEchoState {
+        predictable: false,
+        first_column: 0,
+        last_column: 0,
+    });
 }
 
 #[test]
@@ -1,11 +1,13 @@
+use crate::data::{LayoutParam, PaneInputLock};
 use crate::input::{
     command::RunCommand,
     config::ConfigError,
     layout::{
         FloatingPaneLayout, Layout, LayoutConstraint, PercentOrFixed, PluginUserConfiguration, Run,
         RunPluginOrAlias, SplitDirection, SplitSize, SwapFloatingLayout, SwapTiledLayout,
-        TiledPaneLayout,
+        TiledPaneLayout, TilingStrategy,
     },
+    scratchpads::Scratchpads,
 };
 
 use kdl::*;
@@ -41,6 +43,8 @@ pub struct KdlLayoutParser<'a> {
     default_tab_template: Option<(TiledPaneLayout, Vec<FloatingPaneLayout>, KdlNode)>,
     new_tab_template: Option<(TiledPaneLayout, Vec<FloatingPaneLayout>)>,
     file_name: Option<PathBuf>,
+    layout_vars: BTreeMap<String, String>, // values given for the layout params by the user
+    param_values: BTreeMap<String, String>, // every layout param, resolved from the above or its default
 }
 
 impl<'a> KdlLayoutParser<'a> {
@@ -48,6 +52,7 @@ impl<'a> KdlLayoutParser<'a> {
         raw_layout: &'a str,
         global_cwd: Option<PathBuf>,
         file_name: Option<String>,
+        layout_vars: BTreeMap<String, String>,
     ) -> Self {
         KdlLayoutParser {
             raw_layout,
@@ -57,6 +62,8 @@ impl<'a> KdlLayoutParser<'a> {
             new_tab_template: None,
             global_cwd,
             file_name: file_name.map(|f| PathBuf::from(f)),
+            layout_vars,
+            param_values: BTreeMap::new(),
         }
     }
     fn is_a_reserved_word(&self, word: &str) -> bool {
@@ -85,7 +92,12 @@ impl<'a> KdlLayoutParser<'a> {
             || word == "swap_tiled_layout"
             || word == "swap_floating_layout"
             || word == "hide_floating_panes"
+            || word == "tiling"
+            || word == "master_ratio"
+            || word == "master_count"
             || word == "contents_file"
+            || word == "scratchpads"
+            || word == "param"
     }
     fn is_a_valid_pane_property(&self, property_name: &str) -> bool {
         property_name == "borderless"
@@ -105,6 +117,7 @@ impl<'a> KdlLayoutParser<'a> {
             || property_name == "stacked"
             || property_name == "expanded"
             || property_name == "exclude_from_sync"
+            || property_name == "input_lock"
             || property_name == "contents_file"
             || property_name == "default_fg"
             || property_name == "default_bg"
@@ -140,6 +153,9 @@ impl<'a> KdlLayoutParser<'a> {
             || property_name == "min_panes"
             || property_name == "exact_panes"
             || property_name == "hide_floating_panes"
+            || property_name == "tiling"
+            || property_name == "master_ratio"
+            || property_name == "master_count"
     }
     pub fn is_a_reserved_plugin_property(property_name: &str) -> bool {
         property_name == "location"
@@ -536,6 +552,7 @@ impl<'a> KdlLayoutParser<'a> {
             .map(|name| name.to_string());
         let exclude_from_sync =
             kdl_get_bool_property_or_child_value_with_error!(kdl_node, "exclude_from_sync");
+        let input_lock = self.parse_input_lock(kdl_node)?;
         let contents_file =
             kdl_get_string_property_or_child_value_with_error!(kdl_node, "contents_file");
         let split_size = self.parse_split_size(kdl_node)?;
@@ -589,6 +606,7 @@ impl<'a> KdlLayoutParser<'a> {
             children_split_direction,
             external_children_index,
             exclude_from_sync,
+            input_lock,
             children,
             children_are_stacked,
             is_expanded_in_stack,
@@ -737,6 +755,7 @@ impl<'a> KdlLayoutParser<'a> {
                 let run = self.parse_command_plugin_or_edit_block_for_template(kdl_node)?;
                 let exclude_from_sync =
                     kdl_get_bool_property_or_child_value_with_error!(kdl_node, "exclude_from_sync");
+                let input_lock = self.parse_input_lock(kdl_node)?;
 
                 let external_children_index = if should_mark_external_children_index {
                     self.populate_external_children_index(kdl_node)?
@@ -776,6 +795,9 @@ impl<'a> KdlLayoutParser<'a> {
                 if let Some(exclude_from_sync) = exclude_from_sync {
                     pane_template.exclude_from_sync = Some(exclude_from_sync);
                 }
+                if let Some(input_lock) = input_lock {
+                    pane_template.input_lock = Some(input_lock);
+                }
                 if let Some(split_size) = split_size {
                     pane_template.split_size = Some(split_size);
                 }
@@ -956,6 +978,40 @@ impl<'a> KdlLayoutParser<'a> {
             None => Ok(SplitDirection::default()),
         }
     }
+    fn parse_input_lock(&self, kdl_node: &KdlNode) -> Result<Option<PaneInputLock>, ConfigError> {
+        match kdl_get_string_property_or_child_value_with_error!(kdl_node, "input_lock") {
+            Some(input_lock) => match PaneInputLock::from_str(input_lock) {
+                Ok(input_lock) => Ok(Some(input_lock)),
+                Err(_e) => Err(kdl_parsing_error!(
+                    format!(
+                        "input_lock should be either \"drop\" or \"confirm\" found: {}",
+                        input_lock
+                    ),
+                    kdl_node
+                )),
+            },
+            None => Ok(None),
+        }
+    }
+    fn parse_tiling_strategy(
+        &self,
+        kdl_node: &KdlNode,
+    ) -> Result<Option<TilingStrategy>, ConfigError> {
+        let master_ratio =
+            kdl_get_int_property_or_child_value!(kdl_node, "master_ratio").map(|r| r as usize);
+        let master_count =
+            kdl_get_int_property_or_child_value!(kdl_node, "master_count").map(|c| c as usize);
+        match kdl_get_string_property_or_child_value_with_error!(kdl_node, "tiling") {
+            Some(tiling) => TilingStrategy::new(tiling, master_ratio, master_count)
+                .map(Some)
+                .map_err(|e| kdl_parsing_error!(format!("Invalid tiling: {}", e), kdl_node)),
+            None if master_ratio.is_some() || master_count.is_some() => Err(kdl_parsing_error!(
+                "master_ratio and master_count require tiling=\"master-stack\"".into(),
+                kdl_node
+            )),
+            None => Ok(None),
+        }
+    }
     fn has_only_neutral_pane_template_properties(
         &self,
         kdl_node: &KdlNode,
@@ -1191,6 +1247,7 @@ impl<'a> KdlLayoutParser<'a> {
         let is_focused = kdl_get_bool_property_or_child_value!(kdl_node, "focus").unwrap_or(false);
         let hide_floating_panes =
             kdl_get_bool_property_or_child_value!(kdl_node, "hide_floating_panes").unwrap_or(false);
+        let tiling_strategy = self.parse_tiling_strategy(kdl_node)?;
         let children_split_direction = self.parse_split_direction(kdl_node)?;
         let mut child_floating_panes = vec![];
         let children = match kdl_children_nodes!(kdl_node) {
@@ -1208,6 +1265,7 @@ impl<'a> KdlLayoutParser<'a> {
             children_split_direction,
             children,
             hide_floating_panes,
+            tiling_strategy,
             ..Default::default()
         };
         if let Some(cwd_prefix) = &self.cwd_prefix(tab_cwd.as_ref())? {
@@ -2392,12 +2450,31 @@ impl<'a> KdlLayoutParser<'a> {
         };
         Ok(())
     }
+    fn populate_scratchpads(
+        &self,
+        layout_children: &[KdlNode],
+        scratchpads: &mut Scratchpads,
+    ) -> Result<(), ConfigError> {
+        for child in layout_children {
+            if kdl_name!(child) == "scratchpads" {
+                let mut layout_scratchpads = Scratchpads::from_kdl(child)?;
+                if let Some(global_cwd) = &self.global_cwd {
+                    for scratchpad in layout_scratchpads.0.values_mut() {
+                        *scratchpad = scratchpad.clone().with_cwd_prefix(global_cwd);
+                    }
+                }
+                scratchpads.merge(layout_scratchpads);
+            }
+        }
+        Ok(())
+    }
     pub fn parse_external_swap_layouts(
         &mut self,
         raw_swap_layouts: &str,
         mut existing_layout: Layout,
     ) -> Result<Layout, ConfigError> {
-        let kdl_swap_layout: KdlDocument = raw_swap_layouts.parse()?;
+        let mut kdl_swap_layout: KdlDocument = raw_swap_layouts.parse()?;
+        self.interpolate_param_values(kdl_swap_layout.nodes_mut());
         let mut swap_tiled_layouts = vec![];
         let mut swap_floating_layouts = vec![];
 
@@ -2440,8 +2517,105 @@ impl<'a> KdlLayoutParser<'a> {
             .append(&mut swap_floating_layouts);
         Ok(existing_layout)
     }
+    pub fn parse_params(layout_node: &KdlNode) -> Result<Vec<LayoutParam>, ConfigError> {
+        let mut params: Vec<LayoutParam> = vec![];
+        for param_node in kdl_children_nodes!(layout_node)
+            .unwrap_or(&[])
+            .iter()
+            .filter(|n| kdl_name!(n) == "param")
+        {
+            let name = param_node
+                .entries()
+                .iter()
+                .find(|e| e.name().is_none())
+                .and_then(|e| e.value().as_string())
+                .ok_or(ConfigError::new_layout_kdl_error(
+                    "Layout parameters need a name, eg. param \"service\" default=\"api\"".into(),
+                    param_node.span().offset(),
+                    param_node.span().len(),
+                ))?;
+            let default = kdl_get_string_property_or_child_value!(param_node, "default").ok_or(
+                ConfigError::new_layout_kdl_error(
+                    format!("Layout parameter '{}' needs a default value", name),
+                    param_node.span().offset(),
+                    param_node.span().len(),
+                ),
+            )?;
+            if params.iter().any(|p| p.name == name) {
+                return Err(ConfigError::new_layout_kdl_error(
+                    format!("Layout parameter '{}' is declared more than once", name),
+                    param_node.span().offset(),
+                    param_node.span().len(),
+                ));
+            }
+            params.push(LayoutParam {
+                name: name.to_owned(),
+                default: default.to_owned(),
+            });
+        }
+        Ok(params)
+    }
+    fn populate_param_values(&mut self, layout_node: &KdlNode) -> Result<(), ConfigError> {
+        let params = KdlLayoutParser::parse_params(layout_node)?;
+        if let Some(unknown_var) = self
+            .layout_vars
+            .keys()
+            .find(|name| !params.iter().any(|p| &&p.name == name))
+        {
+            return Err(ConfigError::new_layout_kdl_error(
+                format!("This layout has no parameter named '{}'", unknown_var),
+                layout_node.span().offset(),
+                layout_node.span().len(),
+            ));
+        }
+        for param in params {
+            let value = self
+                .layout_vars
+                .get(&param.name)
+                .cloned()
+                .unwrap_or(param.default);
+            self.param_values.insert(param.name, value);
+        }
+        Ok(())
+    }
+    fn interpolate_param_values(&self, nodes: &mut [KdlNode]) {
+        // we only replace the declared params, so that other text in double braces (eg. in a
+        // command's arguments) is left alone
+        if self.param_values.is_empty() {
+            return;
+        }
+        for node in nodes {
+            if kdl_name!(node) == "param" {
+                continue;
+            }
+            for entry in node.entries_mut() {
+                let interpolated = entry.value().as_string().and_then(|value| {
+                    let mut interpolated = value.to_owned();
+                    for (name, param_value) in &self.param_values {
+                        interpolated =
+                            interpolated.replace(&format!("{{{{{}}}}}", name), param_value);
+                    }
+                    if interpolated != value {
+                        Some(interpolated)
+                    } else {
+                        None
+                    }
+                });
+                if let Some(interpolated) = interpolated {
+                    entry.set_value(interpolated);
+                }
+            }
+            if let Some(children) = node.children_mut() {
+                self.interpolate_param_values(children.nodes_mut());
+            }
+        }
+    }
     pub fn parse(&mut self) -> Result<Layout, ConfigError> {
-        let kdl_layout: KdlDocument = self.raw_layout.parse()?;
+        let mut kdl_layout: KdlDocument = self.raw_layout.parse()?;
+        if let Some(layout_node) = kdl_layout.nodes().iter().find(|n| kdl_name!(n) == "layout") {
+            self.populate_param_values(layout_node)?;
+            self.interpolate_param_values(kdl_layout.nodes_mut());
+        }
         let layout_node = kdl_layout
             .nodes()
             .iter()
@@ -2480,8 +2654,10 @@ impl<'a> KdlLayoutParser<'a> {
         let mut child_floating_panes = vec![];
         let mut swap_tiled_layouts = vec![];
         let mut swap_floating_layouts = vec![];
+        let mut scratchpads = Scratchpads::default();
         if let Some(children) = kdl_children_nodes!(layout_node) {
             self.populate_global_cwd(layout_node)?;
+            self.populate_scratchpads(children, &mut scratchpads)?;
             self.populate_pane_templates(children, &kdl_layout)?;
             self.populate_tab_templates(children)?;
             self.populate_swap_tiled_layouts(children, &mut swap_tiled_layouts)?;
@@ -2495,7 +2671,7 @@ impl<'a> KdlLayoutParser<'a> {
                 )?;
             }
         }
-        if !child_tabs.is_empty() {
+        let layout = if !child_tabs.is_empty() {
             // Check if layout_node has tab properties when there are explicit tabs
             let layout_has_tab_name =
                 kdl_get_string_property_or_child_value!(layout_node, "name").is_some();
@@ -2596,6 +2772,10 @@ impl<'a> KdlLayoutParser<'a> {
                 hide_floating_panes,
                 tab_cwd,
             )
-        }
+        }?;
+        Ok(Layout {
+            scratchpads,
+            ..layout
+        })
     }
 }
@@ -1,22 +1,28 @@
 mod kdl_layout_parser;
 use crate::data::{
-    BareKey, Direction, FloatingPaneCoordinates, InputMode, KeyWithModifier, LayoutInfo,
-    LayoutMetadata, MultiplayerColors, Palette, PaletteColor, PaneId, PaneInfo, PaneManifest,
-    PermissionType, Resize, SessionInfo, StyleDeclaration, Styling, TabInfo, WebSharing,
-    DEFAULT_STYLES,
+    BareKey, Direction, FloatingPaneCoordinates, InputMode, KeyModifier, KeyWithModifier,
+    LayoutInfo, LayoutMetadata, LayoutParam, MultiplayerColors, Palette, PaletteColor, PaneId,
+    PaneInfo, PaneInputLock, PaneManifest, PermissionType, Resize, SessionInfo, StyleDeclaration,
+    Styling, TabInfo, WebSharing, DEFAULT_STYLES,
 };
 use crate::envs::EnvironmentVariables;
 use crate::home::{find_default_config_dir, get_layout_dir};
 use crate::input::config::{Config, ConfigError, KdlError};
+use crate::input::hooks::{Hook, HookEvent, Hooks};
 use crate::input::keybinds::Keybinds;
 use crate::input::layout::{
-    Layout, PercentOrFixed, PluginUserConfiguration, RunPlugin, RunPluginOrAlias, TabLayoutInfo,
+    Layout, PercentOrFixed, PluginUserConfiguration, RunPlugin, RunPluginOrAlias, SplitDirection,
+    TabLayoutInfo, TilingStrategy,
 };
-use crate::input::options::{Clipboard, OnForceClose, Options};
-use crate::input::permission::{GrantedPermission, PermissionCache};
+use crate::input::mousebinds::{MouseBind, MouseButton, MouseRegion, Mousebinds};
+use crate::input::options::{Clipboard, LocalEcho, OnForceClose, Options};
+use crate::input::permission::{GrantedPermission, PermissionCache, PluginGrant};
 use crate::input::plugins::PluginAliases;
+use crate::input::scratchpads::{Scratchpad, Scratchpads};
 use crate::input::theme::{FrameConfig, Theme, Themes, UiConfig};
 use crate::input::web_client::WebClientConfig;
+#[cfg(not(target_family = "wasm"))]
+use crate::plugin_bundle::{BundledPlugin, PluginBundle};
 use kdl_layout_parser::KdlLayoutParser;
 use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::net::{IpAddr, Ipv4Addr};
@@ -66,6 +72,7 @@ macro_rules! parse_kdl_action_arguments {
                 "ShowFloatingPanes" => Ok(Action::ShowFloatingPanes { tab_id: None }),
                 "HideFloatingPanes" => Ok(Action::HideFloatingPanes { tab_id: None }),
                 "CloseFocus" => Ok(Action::CloseFocus),
+                "ReopenClosedPane" => Ok(Action::ReopenClosedPane { id: None }),
                 "UndoRenamePane" => Ok(Action::UndoRenamePane),
                 "NoOp" => Ok(Action::NoOp),
                 "GoToNextTab" => Ok(Action::GoToNextTab),
@@ -539,6 +546,22 @@ impl Action {
                 }
             },
             "MovePaneBackwards" => Ok(Action::MovePaneBackwards),
+            "EqualizePanes" => {
+                if string.is_empty() {
+                    Ok(Action::EqualizePanes { direction: None })
+                } else {
+                    let direction = SplitDirection::from_str(string.as_str()).map_err(|_| {
+                        ConfigError::new_kdl_error(
+                            format!("Invalid split direction: '{}'", string),
+                            action_node.span().offset(),
+                            action_node.span().len(),
+                        )
+                    })?;
+                    Ok(Action::EqualizePanes {
+                        direction: Some(direction),
+                    })
+                }
+            },
             "DumpScreen" => Ok(Action::DumpScreen {
                 file_path: Some(string),
                 include_scrollback: false,
@@ -601,6 +624,23 @@ impl Action {
                 })
             },
             "RenameSession" => Ok(Action::RenameSession { name: string }),
+            "ToggleScratchpad" => Ok(Action::ToggleScratchpad { name: string }),
+            "TogglePaneInputLock" => {
+                if string.is_empty() {
+                    Ok(Action::TogglePaneInputLock {
+                        input_lock: PaneInputLock::Drop,
+                    })
+                } else {
+                    let input_lock = PaneInputLock::from_str(string.as_str()).map_err(|_| {
+                        ConfigError::new_kdl_error(
+                            format!("Invalid input lock: '{}'", string),
+                            action_node.span().offset(),
+                            action_node.span().len(),
+                        )
+                    })?;
+                    Ok(Action::TogglePaneInputLock { input_lock })
+                }
+            },
             _ => Err(ConfigError::new_kdl_error(
                 format!("Unsupported action: {}", action_name),
                 action_node.span().offset(),
@@ -693,6 +733,35 @@ impl Action {
                 Some(node)
             },
             Action::MovePaneBackwards => Some(KdlNode::new("MovePaneBackwards")),
+            Action::EqualizePanes { direction } => {
+                let mut node = KdlNode::new("EqualizePanes");
+                if let Some(direction) = direction {
+                    let direction = match direction {
+                        SplitDirection::Vertical => "vertical",
+                        SplitDirection::Horizontal => "horizontal",
+                    };
+                    node.push(direction);
+                }
+                Some(node)
+            },
+            Action::SetTilingStrategy { strategy } => {
+                let mut node = KdlNode::new("SetTilingStrategy");
+                match strategy {
+                    Some(strategy) => {
+                        node.push(strategy.name());
+                        if let TilingStrategy::MasterStack {
+                            master_ratio,
+                            master_count,
+                        } = strategy
+                        {
+                            node.push(KdlEntry::new_prop("master_ratio", *master_ratio as i64));
+                            node.push(KdlEntry::new_prop("master_count", *master_count as i64));
+                        }
+                    },
+                    None => node.push("none"),
+                }
+                Some(node)
+            },
             Action::DumpScreen {
                 file_path: Some(file),
                 include_scrollback: _,
@@ -763,6 +832,8 @@ impl Action {
                 Some(node)
             },
             Action::CloseFocus => Some(KdlNode::new("CloseFocus")),
+            // the ids of closed panes only live as long as the session, so they are not bound
+            Action::ReopenClosedPane { id: None } => Some(KdlNode::new("ReopenClosedPane")),
             Action::PaneNameInput { input: bytes } => {
                 let mut node = KdlNode::new("PaneNameInput");
                 for byte in bytes {
@@ -1317,8 +1388,29 @@ impl Action {
                 Some(node)
             },
             Action::TogglePanePinned => Some(KdlNode::new("TogglePanePinned")),
+            Action::TogglePaneInputLock { input_lock } => {
+                let mut node = KdlNode::new("TogglePaneInputLock");
+                if *input_lock != PaneInputLock::Drop {
+                    node.push(input_lock.name());
+                }
+                Some(node)
+            },
             Action::TogglePaneInGroup => Some(KdlNode::new("TogglePaneInGroup")),
             Action::ToggleGroupMarking => Some(KdlNode::new("ToggleGroupMarking")),
+            Action::ToggleGroupBroadcast => Some(KdlNode::new("ToggleGroupBroadcast")),
+            Action::SendPaneToSession { name, pane_id } => {
+                let mut node = KdlNode::new("SendPaneToSession");
+                node.push(name.clone());
+                if let Some(pane_id) = pane_id {
+                    node.push(KdlEntry::new_prop("pane_id", pane_id.to_string()));
+                }
+                Some(node)
+            },
+            Action::ToggleScratchpad { name } => {
+                let mut node = KdlNode::new("ToggleScratchpad");
+                node.push(name.clone());
+                Some(node)
+            },
             _ => None,
         }
     }
@@ -1563,6 +1655,9 @@ impl TryFrom<(&KdlNode, &Options)> for Action {
                 Ok(Action::HideFloatingPanes { tab_id })
             },
             "CloseFocus" => parse_kdl_action_arguments!(action_name, action_arguments, kdl_action),
+            "ReopenClosedPane" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
             "UndoRenamePane" => {
                 parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
             },
@@ -1679,6 +1774,41 @@ impl TryFrom<(&KdlNode, &Options)> for Action {
                 action_arguments,
                 kdl_action
             ),
+            "EqualizePanes" => parse_kdl_action_char_or_string_arguments!(
+                action_name,
+                action_arguments,
+                kdl_action
+            ),
+            "SetTilingStrategy" => {
+                let strategy = action_arguments
+                    .first()
+                    .and_then(|a| a.value().as_string())
+                    .ok_or(ConfigError::new_kdl_error(
+                        "SetTilingStrategy action requires a strategy name or \"none\"".into(),
+                        kdl_action.span().offset(),
+                        kdl_action.span().len(),
+                    ))?;
+                if strategy == "none" {
+                    return Ok(Action::SetTilingStrategy { strategy: None });
+                }
+                let master_ratio =
+                    crate::kdl_get_int_property_or_child_value!(kdl_action, "master_ratio")
+                        .map(|r| r as usize);
+                let master_count =
+                    crate::kdl_get_int_property_or_child_value!(kdl_action, "master_count")
+                        .map(|c| c as usize);
+                let strategy =
+                    TilingStrategy::new(strategy, master_ratio, master_count).map_err(|e| {
+                        ConfigError::new_kdl_error(
+                            format!("Invalid tiling strategy: {}", e),
+                            kdl_action.span().offset(),
+                            kdl_action.span().len(),
+                        )
+                    })?;
+                Ok(Action::SetTilingStrategy {
+                    strategy: Some(strategy),
+                })
+            },
             "DumpScreen" => parse_kdl_action_char_or_string_arguments!(
                 action_name,
                 action_arguments,
@@ -2146,6 +2276,39 @@ impl TryFrom<(&KdlNode, &Options)> for Action {
                 action_arguments,
                 kdl_action
             ),
+            "SendPaneToSession" => {
+                let name = action_arguments
+                    .iter()
+                    .find(|a| a.name().is_none())
+                    .and_then(|a| a.value().as_string())
+                    .ok_or(ConfigError::new_kdl_error(
+                        "SendPaneToSession action requires a session name".into(),
+                        kdl_action.span().offset(),
+                        kdl_action.span().len(),
+                    ))?
+                    .to_owned();
+                let pane_id = match kdl_get_string_property_or_child_value!(kdl_action, "pane_id") {
+                    Some(pane_id) => Some(PaneId::from_str(pane_id).map_err(|_| {
+                        ConfigError::new_kdl_error(
+                            format!("Malformed pane id: {}", pane_id),
+                            kdl_action.span().offset(),
+                            kdl_action.span().len(),
+                        )
+                    })?),
+                    None => None,
+                };
+                Ok(Action::SendPaneToSession { name, pane_id })
+            },
+            "ToggleScratchpad" => parse_kdl_action_char_or_string_arguments!(
+                action_name,
+                action_arguments,
+                kdl_action
+            ),
+            "TogglePaneInputLock" => parse_kdl_action_char_or_string_arguments!(
+                action_name,
+                action_arguments,
+                kdl_action
+            ),
             "MessagePlugin" => {
                 let arguments = action_arguments.iter().copied();
                 let mut args = kdl_arguments_that_are_strings(arguments)?;
@@ -2247,6 +2410,7 @@ impl TryFrom<(&KdlNode, &Options)> for Action {
             "TogglePanePinned" => Ok(Action::TogglePanePinned),
             "TogglePaneInGroup" => Ok(Action::TogglePaneInGroup),
             "ToggleGroupMarking" => Ok(Action::ToggleGroupMarking),
+            "ToggleGroupBroadcast" => Ok(Action::ToggleGroupBroadcast),
             _ => Err(ConfigError::new_kdl_error(
                 format!("Unsupported action: {}", action_name).into(),
                 kdl_action.span().offset(),
@@ -2817,6 +2981,27 @@ impl Options {
         let mouse_click_through =
             kdl_property_first_arg_as_bool_or_error!(kdl_options, "mouse_click_through")
                 .map(|(v, _)| v);
+        let plugin_cpu_budget =
+            match kdl_property_first_arg_as_i64_or_error!(kdl_options, "plugin_cpu_budget") {
+                Some((value, _)) if value >= 0 => Some(value as u64),
+                Some((value, entry)) => {
+                    return Err(kdl_parsing_error!(
+                        format!("Plugin CPU budget cannot be negative, found '{}'", value),
+                        entry
+                    ));
+                },
+                None => None,
+            };
+        let plugin_bundle_dir =
+            kdl_property_first_arg_as_string_or_error!(kdl_options, "plugin_bundle_dir")
+                .map(|(string, _entry)| PathBuf::from(string));
+        let local_echo = match kdl_property_first_arg_as_string_or_error!(kdl_options, "local_echo")
+        {
+            Some((string, entry)) => Some(LocalEcho::from_str(string).map_err(|_| {
+                kdl_parsing_error!(format!("Invalid value for local_echo: '{}'", string), entry)
+            })?),
+            None => None,
+        };
 
         Ok(Options {
             simplified_ui,
@@ -2864,6 +3049,9 @@ impl Options {
             enforce_https_for_localhost,
             post_command_discovery_hook,
             client_async_worker_tasks,
+            plugin_cpu_budget,
+            plugin_bundle_dir,
+            local_echo,
         })
     }
     pub fn from_string(stringified_keybindings: &String) -> Result<Self, ConfigError> {
@@ -4153,6 +4341,88 @@ impl Options {
             None
         }
     }
+    fn plugin_cpu_budget_to_kdl(&self, add_comments: bool) -> Option<KdlNode> {
+        let comment_text = r#"
+// The amount of fuel (roughly, WebAssembly instructions) a plugin may consume in a single call
+// (eg. while handling an event or rendering) before it is stopped and marked as errored.
+// Set to 0 to disable the limit.
+// Default: 10000000000"#;
+        let create_node = |node_value: u64| -> KdlNode {
+            let mut node = KdlNode::new("plugin_cpu_budget");
+            node.push(KdlValue::Base10(node_value as i64));
+            node
+        };
+        if let Some(plugin_cpu_budget) = self.plugin_cpu_budget {
+            let mut node = create_node(plugin_cpu_budget);
+            if add_comments {
+                node.set_leading(format!("{}\n", comment_text));
+            }
+            Some(node)
+        } else if add_comments {
+            let mut node = create_node(10_000_000_000);
+            node.set_leading(format!("{}\n// ", comment_text));
+            Some(node)
+        } else {
+            None
+        }
+    }
+    fn plugin_bundle_dir_to_kdl(&self, add_comments: bool) -> Option<KdlNode> {
+        let comment_text = r#"
+// Load remote (http/https) plugins from the bundle created by `zellij plugin vendor` in this
+// directory rather than downloading them, refusing to load any whose hash does not match the one
+// recorded in the bundle's lockfile
+// Default: None (download remote plugins when they are loaded)"#;
+        let create_node = |node_value: &str| -> KdlNode {
+            let mut node = KdlNode::new("plugin_bundle_dir");
+            node.push(node_value.to_owned());
+            node
+        };
+        if let Some(plugin_bundle_dir) = &self.plugin_bundle_dir {
+            let mut node = create_node(&plugin_bundle_dir.display().to_string());
+            if add_comments {
+                node.set_leading(format!("{}\n", comment_text));
+            }
+            Some(node)
+        } else if add_comments {
+            let mut node = create_node("/path/to/plugin/bundle");
+            node.set_leading(format!("{}\n// ", comment_text));
+            Some(node)
+        } else {
+            None
+        }
+    }
+    fn local_echo_to_kdl(&self, add_comments: bool) -> Option<KdlNode> {
+        let comment_text = r#"
+// Choose whether clients attached to a remote session (`zellij attach https://...`) echo typed
+// characters and cursor moves locally, underlined until the server confirms them, when the
+// focused pane is at a shell-like prompt
+// Options:
+//   - adaptive (Default, only when the connection is slow)
+//   - always
+//   - never"#;
+        let create_node = |node_value: &str| -> KdlNode {
+            let mut node = KdlNode::new("local_echo");
+            node.push(node_value.to_owned());
+            node
+        };
+        if let Some(local_echo) = &self.local_echo {
+            let mut node = match local_echo {
+                LocalEcho::Always => create_node("always"),
+                LocalEcho::Adaptive => create_node("adaptive"),
+                LocalEcho::Never => create_node("never"),
+            };
+            if add_comments {
+                node.set_leading(format!("{}\n", comment_text));
+            }
+            Some(node)
+        } else if add_comments {
+            let mut node = create_node("adaptive");
+            node.set_leading(format!("{}\n// ", comment_text));
+            Some(node)
+        } else {
+            None
+        }
+    }
     fn client_async_worker_tasks_to_kdl(&self, add_comments: bool) -> Option<KdlNode> {
         let comment_text = r#"
 // Number of async worker tasks to spawn per active client.
@@ -4325,6 +4595,15 @@ impl Options {
         {
             nodes.push(client_async_worker_tasks);
         }
+        if let Some(plugin_cpu_budget) = self.plugin_cpu_budget_to_kdl(add_comments) {
+            nodes.push(plugin_cpu_budget);
+        }
+        if let Some(plugin_bundle_dir) = self.plugin_bundle_dir_to_kdl(add_comments) {
+            nodes.push(plugin_bundle_dir);
+        }
+        if let Some(local_echo) = self.local_echo_to_kdl(add_comments) {
+            nodes.push(local_echo);
+        }
         nodes
     }
 }
@@ -4336,7 +4615,23 @@ impl Layout {
         raw_swap_layouts: Option<(&str, &str)>, // raw_swap_layouts swap_layouts_file_name
         cwd: Option<PathBuf>,
     ) -> Result<Self, ConfigError> {
-        let mut kdl_layout_parser = KdlLayoutParser::new(raw_layout, cwd, file_name.clone());
+        Layout::from_kdl_with_vars(
+            raw_layout,
+            file_name,
+            raw_swap_layouts,
+            cwd,
+            &BTreeMap::new(),
+        )
+    }
+    pub fn from_kdl_with_vars(
+        raw_layout: &str,
+        file_name: Option<String>,
+        raw_swap_layouts: Option<(&str, &str)>, // raw_swap_layouts swap_layouts_file_name
+        cwd: Option<PathBuf>,
+        layout_vars: &BTreeMap<String, String>, // values for the params declared in the layout
+    ) -> Result<Self, ConfigError> {
+        let mut kdl_layout_parser =
+            KdlLayoutParser::new(raw_layout, cwd, file_name.clone(), layout_vars.clone());
         let layout = kdl_layout_parser.parse().map_err(|e| match e {
             ConfigError::KdlError(kdl_error) => ConfigError::KdlError(kdl_error.add_src(
                 file_name.unwrap_or_else(|| "N/A".to_owned()),
@@ -4373,6 +4668,13 @@ impl Layout {
             None => Ok(layout),
         }
     }
+    pub fn params_from_kdl(raw_layout: &str) -> Result<Vec<LayoutParam>, ConfigError> {
+        let kdl_layout: KdlDocument = raw_layout.parse()?;
+        match kdl_layout.nodes().iter().find(|n| kdl_name!(n) == "layout") {
+            Some(layout_node) => KdlLayoutParser::parse_params(layout_node),
+            None => Ok(vec![]),
+        }
+    }
 }
 
 fn kdl_layout_error(kdl_error: kdl::KdlError, file_name: String, raw_layout: &str) -> ConfigError {
@@ -4732,6 +5034,319 @@ impl Keybinds {
     }
 }
 
+impl Mousebinds {
+    pub fn from_kdl(
+        kdl_mousebinds: &KdlNode,
+        base_mousebinds: Mousebinds,
+        config_options: &Options,
+    ) -> Result<Self, ConfigError> {
+        let mut mousebinds_from_config = base_mousebinds;
+        let all_nodes = kdl_children_nodes_or_error!(kdl_mousebinds, "mousebinds with no children");
+        for bind_node in all_nodes {
+            match kdl_name!(bind_node) {
+                "bind" => {
+                    let actions: Vec<Action> = actions_from_kdl!(bind_node, config_options);
+                    for mouse_bind in Mousebinds::mouse_binds_from_kdl(bind_node)? {
+                        mousebinds_from_config.0.insert(mouse_bind, actions.clone());
+                    }
+                },
+                "unbind" => {
+                    for mouse_bind in Mousebinds::mouse_binds_from_kdl(bind_node)? {
+                        mousebinds_from_config.0.remove(&mouse_bind);
+                    }
+                },
+                unknown => {
+                    return Err(ConfigError::new_kdl_error(
+                        format!("Unknown mousebind instruction: '{}'", unknown),
+                        bind_node.span().offset(),
+                        bind_node.span().len(),
+                    ));
+                },
+            }
+        }
+        Ok(mousebinds_from_config)
+    }
+    fn mouse_binds_from_kdl(bind_node: &KdlNode) -> Result<Vec<MouseBind>, ConfigError> {
+        let region = match bind_node.get("region") {
+            Some(entry) => Some(
+                entry
+                    .value()
+                    .as_string()
+                    .and_then(|r| MouseRegion::from_str(r).ok())
+                    .ok_or_else(|| {
+                        ConfigError::new_kdl_error(
+                            format!(
+                                "Invalid mouse region: '{}', expected one of: pane_body, pane_frame, tab_bar",
+                                entry.value()
+                            ),
+                            entry.span().offset(),
+                            entry.span().len(),
+                        )
+                    })?,
+            ),
+            None => None,
+        };
+        let click_count = match bind_node.get("clicks") {
+            Some(entry) => entry
+                .value()
+                .as_i64()
+                .filter(|c| (1..=3).contains(c))
+                .map(|c| c as usize)
+                .ok_or_else(|| {
+                    ConfigError::new_kdl_error(
+                        format!(
+                            "Invalid click count: '{}', expected 1, 2 or 3",
+                            entry.value()
+                        ),
+                        entry.span().offset(),
+                        entry.span().len(),
+                    )
+                })?,
+            None => 1,
+        };
+        let mut mouse_binds = vec![];
+        for entry in bind_node.entries().iter().filter(|e| e.name().is_none()) {
+            let invalid_bind = || {
+                ConfigError::new_kdl_error(
+                    format!("Invalid mouse button: '{}'", entry.value()),
+                    entry.span().offset(),
+                    entry.span().len(),
+                )
+            };
+            let bind_str = entry.value().as_string().ok_or_else(invalid_bind)?;
+            let mut parts: Vec<&str> = bind_str.split_whitespace().collect();
+            let button = parts
+                .pop()
+                .and_then(|b| MouseButton::from_str(b).ok())
+                .ok_or_else(invalid_bind)?;
+            let modifiers = parts
+                .iter()
+                .map(|m| KeyModifier::from_str(m).map_err(|_| invalid_bind()))
+                .collect::<Result<BTreeSet<_>, _>>()?;
+            mouse_binds.push(MouseBind {
+                button,
+                modifiers,
+                click_count,
+                region,
+            });
+        }
+        Ok(mouse_binds)
+    }
+    pub fn to_kdl(&self) -> Option<KdlNode> {
+        if self.is_empty() {
+            return None;
+        }
+        let mut mousebinds_node = KdlNode::new("mousebinds");
+        let mut mousebinds_children = KdlDocument::new();
+        let stable_sorted: BTreeMap<&MouseBind, &Vec<Action>> = self.0.iter().collect();
+        for (mouse_bind, bound_actions) in stable_sorted {
+            let mut bind_node = KdlNode::new("bind");
+            bind_node.push(mouse_bind.button_to_string());
+            if mouse_bind.click_count != 1 {
+                bind_node.insert("clicks", mouse_bind.click_count as i64);
+            }
+            if let Some(region) = mouse_bind.region {
+                bind_node.insert("region", region.to_string());
+            }
+            let mut actions = KdlDocument::new();
+            let mut actions_have_children = false;
+            for action in bound_actions {
+                if let Some(kdl_action) = action.to_kdl() {
+                    if kdl_action.children().is_some() {
+                        actions_have_children = true;
+                    }
+                    actions.nodes_mut().push(kdl_action);
+                }
+            }
+            if !actions_have_children {
+                for action in actions.nodes_mut() {
+                    action.set_leading("");
+                    action.set_trailing("; ");
+                }
+                actions.set_leading(" ");
+                actions.set_trailing("");
+            }
+            bind_node.set_children(actions);
+            mousebinds_children.nodes_mut().push(bind_node);
+        }
+        mousebinds_node.set_children(mousebinds_children);
+        Some(mousebinds_node)
+    }
+}
+
+impl Scratchpads {
+    pub fn from_kdl(kdl_scratchpads: &KdlNode) -> Result<Scratchpads, ConfigError> {
+        let mut scratchpads: BTreeMap<String, Scratchpad> = BTreeMap::new();
+        if let Some(kdl_scratchpads) = kdl_children_nodes!(kdl_scratchpads) {
+            for scratchpad_definition in kdl_scratchpads {
+                let scratchpad_name = kdl_name!(scratchpad_definition);
+                let command =
+                    kdl_get_string_property_or_child_value!(scratchpad_definition, "command")
+                        .map(PathBuf::from);
+                let args = match kdl_get_child!(scratchpad_definition, "args") {
+                    Some(kdl_args) => kdl_string_arguments!(kdl_args)
+                        .iter()
+                        .map(|s| String::from(*s))
+                        .collect(),
+                    None => vec![],
+                };
+                let cwd =
+                    match kdl_get_string_property_or_child_value!(scratchpad_definition, "cwd") {
+                        Some(cwd) => match shellexpand::full(cwd) {
+                            Ok(cwd) => Some(PathBuf::from(cwd.as_ref())),
+                            Err(e) => {
+                                return Err(kdl_parsing_error!(
+                                    e.to_string(),
+                                    scratchpad_definition
+                                ))
+                            },
+                        },
+                        None => None,
+                    };
+                let coordinate = |name: &str| {
+                    kdl_get_string_property_or_child_value!(scratchpad_definition, name)
+                        .map(|s| s.to_owned())
+                        .or_else(|| {
+                            kdl_get_int_property_or_child_value!(scratchpad_definition, name)
+                                .map(|i| i.to_string())
+                        })
+                };
+                let pinned = kdl_get_bool_property_or_child_value_with_error!(
+                    scratchpad_definition,
+                    "pinned"
+                );
+                let borderless = kdl_get_bool_property_or_child_value_with_error!(
+                    scratchpad_definition,
+                    "borderless"
+                );
+                let move_to_focused_tab = kdl_get_bool_property_or_child_value_with_error!(
+                    scratchpad_definition,
+                    "move_to_focused_tab"
+                )
+                .unwrap_or(false);
+                scratchpads.insert(
+                    scratchpad_name.to_owned(),
+                    Scratchpad {
+                        command,
+                        args,
+                        cwd,
+                        coordinates: FloatingPaneCoordinates::new(
+                            coordinate("x"),
+                            coordinate("y"),
+                            coordinate("width"),
+                            coordinate("height"),
+                            pinned,
+                            borderless,
+                        ),
+                        move_to_focused_tab,
+                    },
+                );
+            }
+        }
+        Ok(Scratchpads(scratchpads))
+    }
+    pub fn to_kdl(&self) -> Option<KdlNode> {
+        if self.is_empty() {
+            return None;
+        }
+        let percent_or_fixed_to_kdl = |value: &PercentOrFixed| match value {
+            PercentOrFixed::Percent(percent) => KdlValue::String(format!("{}%", percent)),
+            PercentOrFixed::Fixed(fixed) => KdlValue::Base10(*fixed as i64),
+        };
+        let mut scratchpads_node = KdlNode::new("scratchpads");
+        let mut scratchpads_children = KdlDocument::new();
+        for (scratchpad_name, scratchpad) in self.0.iter() {
+            let mut scratchpad_node = KdlNode::new(scratchpad_name.clone());
+            if let Some(command) = &scratchpad.command {
+                scratchpad_node.insert("command", command.display().to_string());
+            }
+            if let Some(cwd) = &scratchpad.cwd {
+                scratchpad_node.insert("cwd", cwd.display().to_string());
+            }
+            if let Some(coordinates) = &scratchpad.coordinates {
+                if let Some(x) = &coordinates.x {
+                    scratchpad_node.insert("x", percent_or_fixed_to_kdl(x));
+                }
+                if let Some(y) = &coordinates.y {
+                    scratchpad_node.insert("y", percent_or_fixed_to_kdl(y));
+                }
+                if let Some(width) = &coordinates.width {
+                    scratchpad_node.insert("width", percent_or_fixed_to_kdl(width));
+                }
+                if let Some(height) = &coordinates.height {
+                    scratchpad_node.insert("height", percent_or_fixed_to_kdl(height));
+                }
+                if let Some(pinned) = coordinates.pinned {
+                    scratchpad_node.insert("pinned", pinned);
+                }
+                if let Some(borderless) = coordinates.borderless {
+                    scratchpad_node.insert("borderless", borderless);
+                }
+            }
+            if scratchpad.move_to_focused_tab {
+                scratchpad_node.insert("move_to_focused_tab", true);
+            }
+            if !scratchpad.args.is_empty() {
+                let mut args_node = KdlNode::new("args");
+                for arg in &scratchpad.args {
+                    args_node.push(arg.clone());
+                }
+                let mut scratchpad_children = KdlDocument::new();
+                scratchpad_children.nodes_mut().push(args_node);
+                scratchpad_node.set_children(scratchpad_children);
+            }
+            scratchpads_children.nodes_mut().push(scratchpad_node);
+        }
+        scratchpads_node.set_children(scratchpads_children);
+        Some(scratchpads_node)
+    }
+}
+
+impl Hooks {
+    pub fn from_kdl(kdl_hooks: &KdlNode) -> Result<Hooks, ConfigError> {
+        let mut hooks = vec![];
+        if let Some(kdl_hooks) = kdl_children_nodes!(kdl_hooks) {
+            for hook_definition in kdl_hooks {
+                let event = HookEvent::from_str(kdl_name!(hook_definition))
+                    .map_err(|e| kdl_parsing_error!(e, hook_definition))?;
+                let command = kdl_first_entry_as_string!(hook_definition)
+                    .ok_or_else(|| {
+                        kdl_parsing_error!(
+                            format!("Hook for {} must have a command", event),
+                            hook_definition
+                        )
+                    })?
+                    .to_owned();
+                let timeout_secs = kdl_get_int_property_or_child_value!(hook_definition, "timeout")
+                    .map(|t| t as u64);
+                hooks.push(Hook {
+                    event,
+                    command,
+                    timeout_secs,
+                });
+            }
+        }
+        Ok(Hooks(hooks))
+    }
+    pub fn to_kdl(&self) -> Option<KdlNode> {
+        if self.is_empty() {
+            return None;
+        }
+        let mut hooks_node = KdlNode::new("hooks");
+        let mut hooks_children = KdlDocument::new();
+        for hook in &self.0 {
+            let mut hook_node = KdlNode::new(hook.event.as_str());
+            hook_node.push(hook.command.clone());
+            if let Some(timeout_secs) = hook.timeout_secs {
+                hook_node.insert("timeout", timeout_secs as i64);
+            }
+            hooks_children.nodes_mut().push(hook_node);
+        }
+        hooks_node.set_children(hooks_children);
+        Some(hooks_node)
+    }
+}
+
 impl KeyWithModifier {
     pub fn to_kdl(&self) -> String {
         if self.key_modifiers.is_empty() {
@@ -4793,6 +5408,10 @@ impl Config {
         if let Some(kdl_keybinds) = kdl_config.get("keybinds") {
             config.keybinds = Keybinds::from_kdl(&kdl_keybinds, config.keybinds, &config.options)?;
         }
+        if let Some(kdl_mousebinds) = kdl_config.get("mousebinds") {
+            config.mousebinds =
+                Mousebinds::from_kdl(kdl_mousebinds, config.mousebinds, &config.options)?;
+        }
         if let Some(kdl_themes) = kdl_config.get("themes") {
             let sourced_from_external_file = false;
             let config_themes = Themes::from_kdl(kdl_themes, sourced_from_external_file)?;
@@ -4802,6 +5421,14 @@ impl Config {
             let config_plugins = PluginAliases::from_kdl(kdl_plugin_aliases)?;
             config.plugins.merge(config_plugins);
         }
+        if let Some(kdl_scratchpads) = kdl_config.get("scratchpads") {
+            let config_scratchpads = Scratchpads::from_kdl(kdl_scratchpads)?;
+            config.scratchpads.merge(config_scratchpads);
+        }
+        if let Some(kdl_hooks) = kdl_config.get("hooks") {
+            let config_hooks = Hooks::from_kdl(kdl_hooks)?;
+            config.hooks.merge(config_hooks);
+        }
         if let Some(kdl_load_plugins) = kdl_config.get("load_plugins") {
             let load_plugins = load_plugins_from_kdl(kdl_load_plugins)?;
             config.background_plugins = load_plugins;
@@ -4827,6 +5454,10 @@ impl Config {
         let keybinds = self.keybinds.to_kdl(clear_defaults);
         document.nodes_mut().push(keybinds);
 
+        if let Some(mousebinds) = self.mousebinds.to_kdl() {
+            document.nodes_mut().push(mousebinds);
+        }
+
         if let Some(themes) = self.themes.to_kdl() {
             document.nodes_mut().push(themes);
         }
@@ -4837,6 +5468,14 @@ impl Config {
         let load_plugins = load_plugins_to_kdl(&self.background_plugins, add_comments);
         document.nodes_mut().push(load_plugins);
 
+        if let Some(scratchpads) = self.scratchpads.to_kdl() {
+            document.nodes_mut().push(scratchpads);
+        }
+
+        if let Some(hooks) = self.hooks.to_kdl() {
+            document.nodes_mut().push(hooks);
+        }
+
         if let Some(ui_config) = self.ui.to_kdl() {
             document.nodes_mut().push(ui_config);
         }
@@ -5391,6 +6030,10 @@ impl PermissionCache {
         for node in kdl_document.nodes() {
             if let Some(children) = node.children() {
                 let key = kdl_name!(node);
+                let content_hash = node
+                    .get("hash")
+                    .and_then(|e| e.value().as_string())
+                    .map(|h| h.to_owned());
                 let permissions: Vec<PermissionType> = children
                     .nodes()
                     .iter()
@@ -5400,7 +6043,13 @@ impl PermissionCache {
                     })
                     .collect();
 
-                granted_permission.insert(key.into(), permissions);
+                granted_permission.insert(
+                    key.into(),
+                    PluginGrant {
+                        content_hash,
+                        permissions,
+                    },
+                );
             }
         }
 
@@ -5412,9 +6061,12 @@ impl PermissionCache {
 
         granted.iter().for_each(|(k, v)| {
             let mut node = KdlNode::new(k.as_str());
+            if let Some(content_hash) = &v.content_hash {
+                node.insert("hash", content_hash.as_str());
+            }
             let mut children = KdlDocument::new();
 
-            let permissions: HashSet<PermissionType> = v.clone().into_iter().collect();
+            let permissions: HashSet<PermissionType> = v.permissions.clone().into_iter().collect();
             permissions.iter().for_each(|f| {
                 let n = KdlNode::new(f.to_string().as_str());
                 children.nodes_mut().push(n);
@@ -5429,6 +6081,67 @@ impl PermissionCache {
     }
 }
 
+#[cfg(not(target_family = "wasm"))]
+impl PluginBundle {
+    pub fn lockfile_from_string(
+        raw_string: &str,
+    ) -> Result<BTreeMap<String, BundledPlugin>, ConfigError> {
+        let kdl_document: KdlDocument = raw_string.parse()?;
+        let mut plugins = BTreeMap::new();
+        for node in kdl_document.nodes() {
+            if kdl_name!(node) != "plugin" {
+                continue;
+            }
+            let url = kdl_first_entry_as_string!(node)
+                .ok_or(ConfigError::new_kdl_error(
+                    "Plugin must have a url".into(),
+                    node.span().offset(),
+                    node.span().len(),
+                ))?
+                .to_owned();
+            let child_as_string = |name: &str| -> Result<String, ConfigError> {
+                kdl_children_nodes!(node)
+                    .and_then(|c| c.iter().find(|n| kdl_name!(n) == name))
+                    .and_then(|n| kdl_first_entry_as_string!(n))
+                    .map(|s| s.to_owned())
+                    .ok_or(ConfigError::new_kdl_error(
+                        format!("Plugin {} is missing a {}", url, name),
+                        node.span().offset(),
+                        node.span().len(),
+                    ))
+            };
+            let bundled_plugin = BundledPlugin {
+                file_name: child_as_string("file")?,
+                hash: child_as_string("hash")?,
+                fetched_at: child_as_string("fetched_at")?,
+            };
+            plugins.insert(url, bundled_plugin);
+        }
+        Ok(plugins)
+    }
+    pub fn lockfile_to_string(plugins: &BTreeMap<String, BundledPlugin>) -> String {
+        let mut kdl_document = KdlDocument::new();
+        for (url, bundled_plugin) in plugins {
+            let mut node = KdlNode::new("plugin");
+            node.push(url.as_str());
+            let mut children = KdlDocument::new();
+            for (name, value) in [
+                ("file", &bundled_plugin.file_name),
+                ("hash", &bundled_plugin.hash),
+                ("fetched_at", &bundled_plugin.fetched_at),
+            ] {
+                let mut child = KdlNode::new(name);
+                child.push(value.as_str());
+                children.nodes_mut().push(child);
+            }
+            node.set_children(children);
+            kdl_document.nodes_mut().push(node);
+        }
+        kdl_document.fmt();
+        kdl_document.to_string()
+    }
+}
+
 impl SessionInfo {
     pub fn from_string(raw_session_info: &str, current_session_name: &str) -> Result<Self, String> {
         let kdl_document: KdlDocument = raw_session_info
@@ -6035,6 +6748,7 @@ impl PaneInfo {
             index_in_pane_group: Default::default(), // we don't serialize this
             default_fg: None,
             default_bg: None,
+            input_lock: None,
         };
         Ok((tab_position, pane_info))
     }
@@ -6185,6 +6899,7 @@ fn serialize_and_deserialize_session_info_with_data() {
             index_in_pane_group: Default::default(), // we don't serialize this
             default_fg: None,
             default_bg: None,
+            input_lock: None,
         },
         PaneInfo {
             id: 1,
@@ -6212,6 +6927,7 @@ fn serialize_and_deserialize_session_info_with_data() {
             index_in_pane_group: Default::default(), // we don't serialize this
             default_fg: None,
             default_bg: None,
+            input_lock: None,
         },
     ];
     let mut panes = HashMap::new();
@@ -6520,6 +7236,17 @@ fn keybinds_to_string_with_all_actions() {
                         config_key_2 "config_value_2";
                     };
                 }
+                bind "Ctrl Alt k" { EqualizePanes; }
+                bind "Ctrl Alt l" { EqualizePanes "vertical"; }
+                bind "Ctrl Alt m" { SetTilingStrategy "master-stack" master_ratio=60 master_count=2; }
+                bind "Ctrl Alt n" { SetTilingStrategy "none"; }
+                bind "Ctrl Alt o" { ToggleGroupBroadcast; }
+                bind "Ctrl Alt p" { ToggleScratchpad "notes"; }
+                bind "Ctrl Alt q" { TogglePaneInputLock; }
+                bind "Ctrl Alt r" { TogglePaneInputLock "confirm"; }
+                bind "Ctrl Alt s" { ReopenClosedPane; }
+                bind "Ctrl Alt t" { SendPaneToSession "other-session"; }
+                bind "Ctrl Alt u" { SendPaneToSession "other-session" pane_id="terminal_3"; }
             }
         }"#;
     let document: KdlDocument = fake_config.parse().unwrap();
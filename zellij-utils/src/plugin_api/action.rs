@@ -13,6 +13,7 @@ pub use super::generated_api::api::{
         CommandOrPlugin as ProtobufCommandOrPlugin,
         DumpScreenPayload,
         EditFilePayload,
+        EqualizePanesPayload,
         FloatingPaneCoordinates as ProtobufFloatingPaneCoordinates,
         FloatingPaneLayout as ProtobufFloatingPaneLayout,
         FloatingPlacement as ProtobufFloatingPlacement,
@@ -49,6 +50,7 @@ pub use super::generated_api::api::{
         PluginTag as ProtobufPluginTag,
         PluginUserConfiguration as ProtobufPluginUserConfiguration,
         Position as ProtobufPosition,
+        ReopenClosedPanePayload,
         RunCommandAction as ProtobufRunCommandAction,
         RunEditFileAction as ProtobufRunEditFileAction,
         RunPlugin as ProtobufRunPlugin,
@@ -58,6 +60,8 @@ pub use super::generated_api::api::{
         ScrollAtPayload,
         SearchDirection as ProtobufSearchDirection,
         SearchOption as ProtobufSearchOption,
+        SendPaneToSessionPayload,
+        SetTilingStrategyPayload,
         ShowFloatingPanesPayload,
         SplitDirection as ProtobufSplitDirection,
         SplitSize as ProtobufSplitSize,
@@ -69,6 +73,7 @@ pub use super::generated_api::api::{
         TabLayoutInfo as ProtobufTabLayoutInfo,
         TiledPaneLayout as ProtobufTiledPaneLayout,
         TiledPlacement as ProtobufTiledPlacement,
+        TilingStrategy as ProtobufTilingStrategy,
         UnblockCondition as ProtobufUnblockCondition,
         WriteCharsPayload,
         WritePayload,
@@ -88,7 +93,7 @@ use crate::input::layout::SplitSize;
 use crate::input::layout::{
     FloatingPaneLayout, LayoutConstraint, PercentOrFixed, PluginAlias, PluginUserConfiguration,
     Run, RunPlugin, RunPluginLocation, RunPluginOrAlias, SplitDirection, SwapFloatingLayout,
-    SwapTiledLayout, TabLayoutInfo, TiledPaneLayout,
+    SwapTiledLayout, TabLayoutInfo, TiledPaneLayout, TilingStrategy,
 };
 use crate::input::mouse::{MouseEvent, MouseEventType};
 use crate::position::Position;
@@ -206,6 +211,23 @@ impl TryFrom<ProtobufAction> for Action {
                 Some(_) => Err("MovePaneBackwards should not have a payload"),
                 None => Ok(Action::MovePaneBackwards),
             },
+            Some(ProtobufActionName::EqualizePanes) => match protobuf_action.optional_payload {
+                Some(OptionalPayload::EqualizePanesPayload(payload)) => {
+                    let direction: Option<SplitDirection> = payload
+                        .direction
+                        .and_then(ProtobufSplitDirection::from_i32)
+                        .and_then(|d| d.try_into().ok());
+                    Ok(Action::EqualizePanes { direction })
+                },
+                _ => Err("Wrong payload for Action::EqualizePanes"),
+            },
+            Some(ProtobufActionName::SetTilingStrategy) => match protobuf_action.optional_payload {
+                Some(OptionalPayload::SetTilingStrategyPayload(payload)) => {
+                    let strategy = payload.strategy.map(|s| s.try_into()).transpose()?;
+                    Ok(Action::SetTilingStrategy { strategy })
+                },
+                _ => Err("Wrong payload for Action::SetTilingStrategy"),
+            },
             Some(ProtobufActionName::ClearScreen) => match protobuf_action.optional_payload {
                 Some(_) => Err("ClearScreen should not have a payload"),
                 None => Ok(Action::ClearScreen),
@@ -955,6 +977,15 @@ impl TryFrom<ProtobufAction> for Action {
                 },
                 _ => Err("Wrong payload for Action::RenameSession"),
             },
+            Some(ProtobufActionName::SendPaneToSession) => match protobuf_action.optional_payload {
+                Some(OptionalPayload::SendPaneToSessionPayload(payload)) => {
+                    Ok(Action::SendPaneToSession {
+                        name: payload.session_name,
+                        pane_id: payload.pane_id.and_then(|p| p.try_into().ok()),
+                    })
+                },
+                _ => Err("Wrong payload for Action::SendPaneToSession"),
+            },
             Some(ProtobufActionName::TogglePanePinned) => match protobuf_action.optional_payload {
                 Some(_) => Err("TogglePanePinned should not have a payload"),
                 None => Ok(Action::TogglePanePinned),
@@ -969,6 +1000,36 @@ impl TryFrom<ProtobufAction> for Action {
                     None => Ok(Action::ToggleGroupMarking),
                 }
             },
+            Some(ProtobufActionName::ToggleGroupBroadcast) => {
+                match protobuf_action.optional_payload {
+                    Some(_) => Err("ToggleGroupBroadcast should not have a payload"),
+                    None => Ok(Action::ToggleGroupBroadcast),
+                }
+            },
+            Some(ProtobufActionName::ToggleScratchpad) => match protobuf_action.optional_payload {
+                Some(OptionalPayload::ToggleScratchpadPayload(name)) => {
+                    Ok(Action::ToggleScratchpad { name })
+                },
+                _ => Err("Wrong payload for Action::ToggleScratchpad"),
+            },
+            Some(ProtobufActionName::TogglePaneInputLock) => {
+                match protobuf_action.optional_payload {
+                    Some(OptionalPayload::TogglePaneInputLockPayload(input_lock)) => {
+                        Ok(Action::TogglePaneInputLock {
+                            input_lock: input_lock
+                                .parse()
+                                .map_err(|_| "Malformed input lock for TogglePaneInputLock")?,
+                        })
+                    },
+                    _ => Err("Wrong payload for Action::TogglePaneInputLock"),
+                }
+            },
+            Some(ProtobufActionName::ReopenClosedPane) => match protobuf_action.optional_payload {
+                Some(OptionalPayload::ReopenClosedPanePayload(payload)) => {
+                    Ok(Action::ReopenClosedPane { id: payload.id })
+                },
+                _ => Err("Wrong payload for Action::ReopenClosedPane"),
+            },
             Some(ProtobufActionName::KeybindPipe) => match protobuf_action.optional_payload {
                 Some(_) => Err("KeybindPipe should not have a payload"),
                 // TODO: at some point we might want to support a payload here
@@ -1111,6 +1172,7 @@ impl TryFrom<Action> for ProtobufAction {
             | Action::RenamePaneByPaneId { .. }
             | Action::UndoRenamePaneByPaneId { .. }
             | Action::TogglePanePinnedByPaneId { .. }
+            | Action::TogglePaneInputLockByPaneId { .. }
             | Action::FocusPaneByPaneId { .. }
             | Action::UndoRenameTabByTabId { .. }
             | Action::ToggleActiveSyncTabByTabId { .. }
@@ -1197,6 +1259,26 @@ impl TryFrom<Action> for ProtobufAction {
                 name: ProtobufActionName::MovePaneBackwards as i32,
                 optional_payload: None,
             }),
+            Action::EqualizePanes { direction } => {
+                let direction = direction.and_then(|direction| {
+                    let protobuf_direction: ProtobufSplitDirection = direction.try_into().ok()?;
+                    Some(protobuf_direction as i32)
+                });
+                Ok(ProtobufAction {
+                    name: ProtobufActionName::EqualizePanes as i32,
+                    optional_payload: Some(OptionalPayload::EqualizePanesPayload(
+                        EqualizePanesPayload { direction },
+                    )),
+                })
+            },
+            Action::SetTilingStrategy { strategy } => Ok(ProtobufAction {
+                name: ProtobufActionName::SetTilingStrategy as i32,
+                optional_payload: Some(OptionalPayload::SetTilingStrategyPayload(
+                    SetTilingStrategyPayload {
+                        strategy: strategy.map(|s| s.into()),
+                    },
+                )),
+            }),
             Action::ClearScreen => Ok(ProtobufAction {
                 name: ProtobufActionName::ClearScreen as i32,
                 optional_payload: None,
@@ -1806,6 +1888,15 @@ impl TryFrom<Action> for ProtobufAction {
                 name: ProtobufActionName::RenameSession as i32,
                 optional_payload: Some(OptionalPayload::RenameSessionPayload(session_name)),
             }),
+            Action::SendPaneToSession { name, pane_id } => Ok(ProtobufAction {
+                name: ProtobufActionName::SendPaneToSession as i32,
+                optional_payload: Some(OptionalPayload::SendPaneToSessionPayload(
+                    SendPaneToSessionPayload {
+                        session_name: name,
+                        pane_id: pane_id.and_then(|p| p.try_into().ok()),
+                    },
+                )),
+            }),
             Action::KeybindPipe { .. } => Ok(ProtobufAction {
                 name: ProtobufActionName::KeybindPipe as i32,
                 optional_payload: None,
@@ -1822,6 +1913,26 @@ impl TryFrom<Action> for ProtobufAction {
                 name: ProtobufActionName::ToggleGroupMarking as i32,
                 optional_payload: None,
             }),
+            Action::ToggleGroupBroadcast => Ok(ProtobufAction {
+                name: ProtobufActionName::ToggleGroupBroadcast as i32,
+                optional_payload: None,
+            }),
+            Action::ToggleScratchpad { name } => Ok(ProtobufAction {
+                name: ProtobufActionName::ToggleScratchpad as i32,
+                optional_payload: Some(OptionalPayload::ToggleScratchpadPayload(name)),
+            }),
+            Action::TogglePaneInputLock { input_lock } => Ok(ProtobufAction {
+                name: ProtobufActionName::TogglePaneInputLock as i32,
+                optional_payload: Some(OptionalPayload::TogglePaneInputLockPayload(
+                    input_lock.name().to_owned(),
+                )),
+            }),
+            Action::ReopenClosedPane { id } => Ok(ProtobufAction {
+                name: ProtobufActionName::ReopenClosedPane as i32,
+                optional_payload: Some(OptionalPayload::ReopenClosedPanePayload(
+                    ReopenClosedPanePayload { id },
+                )),
+            }),
             Action::NewStackedPane {
                 command: _,
                 pane_name: _,
@@ -1917,6 +2028,10 @@ impl TryFrom<Action> for ProtobufAction {
             | Action::SkipConfirm { action: _ }
             | Action::SwitchSession { .. }
             | Action::SaveSession
+            | Action::SaveSnapshot { .. }
+            | Action::ListSnapshots
+            | Action::DeleteSnapshot { .. }
+            | Action::RestoreSnapshot { .. }
             | Action::ListTabs { .. }
             | Action::CurrentTabInfo { .. }
             | Action::SetPaneColor { .. } => Err("Unsupported action"),
@@ -2567,6 +2682,35 @@ impl TryFrom<SplitDirection> for ProtobufSplitDirection {
     }
 }
 
+impl TryFrom<ProtobufTilingStrategy> for TilingStrategy {
+    type Error = &'static str;
+    fn try_from(protobuf: ProtobufTilingStrategy) -> Result<Self, Self::Error> {
+        TilingStrategy::new(
+            &protobuf.name,
+            protobuf.master_ratio.map(|r| r as usize),
+            protobuf.master_count.map(|c| c as usize),
+        )
+        .map_err(|_| "Invalid TilingStrategy")
+    }
+}
+
+impl From<TilingStrategy> for ProtobufTilingStrategy {
+    fn from(internal: TilingStrategy) -> Self {
+        let (master_ratio, master_count) = match internal {
+            TilingStrategy::MasterStack {
+                master_ratio,
+                master_count,
+            } => (Some(master_ratio as u32), Some(master_count as u32)),
+            _ => (None, None),
+        };
+        ProtobufTilingStrategy {
+            name: internal.name().to_owned(),
+            master_ratio,
+            master_count,
+        }
+    }
+}
+
 impl TryFrom<ProtobufLayoutConstraint> for LayoutConstraint {
     type Error = &'static str;
     fn try_from(protobuf: ProtobufLayoutConstraint) -> Result<Self, Self::Error> {
@@ -2996,8 +3140,14 @@ impl TryFrom<ProtobufTiledPaneLayout> for TiledPaneLayout {
             children_are_stacked: protobuf.children_are_stacked,
             is_expanded_in_stack: protobuf.is_expanded_in_stack,
             exclude_from_sync: protobuf.exclude_from_sync,
+            input_lock: protobuf
+                .input_lock
+                .map(|l| l.parse())
+                .transpose()
+                .map_err(|_| "Malformed input lock for TiledPaneLayout")?,
             run_instructions_to_ignore,
             hide_floating_panes: protobuf.hide_floating_panes,
+            tiling_strategy: protobuf.tiling_strategy.map(|s| s.try_into()).transpose()?,
             pane_initial_contents: protobuf.pane_initial_contents,
             default_fg: None,
             default_bg: None,
@@ -3032,6 +3182,8 @@ impl TryFrom<TiledPaneLayout> for ProtobufTiledPaneLayout {
             exclude_from_sync: internal.exclude_from_sync,
             hide_floating_panes: internal.hide_floating_panes,
             pane_initial_contents: internal.pane_initial_contents,
+            tiling_strategy: internal.tiling_strategy.map(|s| s.into()),
+            input_lock: internal.input_lock.map(|l| l.name().to_owned()),
         })
     }
 }
@@ -13,7 +13,7 @@ pub use super::generated_api::api::{
         InputModeKeybinds as ProtobufInputModeKeybinds, KdlError as ProtobufKdlError,
         KdlErrorVariant as ProtobufKdlErrorVariant, KeyBind as ProtobufKeyBind,
         LayoutInfo as ProtobufLayoutInfo, LayoutMetadata as ProtobufLayoutMetadata,
-        LayoutParsingError as ProtobufLayoutParsingError,
+        LayoutParam as ProtobufLayoutParam, LayoutParsingError as ProtobufLayoutParsingError,
         LayoutWithError as ProtobufLayoutWithError, ModeUpdatePayload as ProtobufModeUpdatePayload,
         PaneContents as ProtobufPaneContents, PaneContentsEntry as ProtobufPaneContentsEntry,
         PaneId as ProtobufPaneId, PaneInfo as ProtobufPaneInfo,
@@ -35,10 +35,10 @@ pub use super::generated_api::api::{
 #[allow(hidden_glob_reexports)]
 use crate::data::{
     ClientId, ClientInfo, CopyDestination, Event, EventType, FileMetadata, InputMode,
-    KeyWithModifier, LayoutInfo, LayoutMetadata, ModeInfo, Mouse, PaneContents, PaneId, PaneInfo,
-    PaneManifest, PaneMetadata, PaneScrollbackResponse, PermissionStatus, PluginCapabilities,
-    PluginInfo, SelectedText, SessionInfo, Style, TabInfo, TabMetadata, WebServerStatus,
-    WebSharing,
+    KeyWithModifier, LayoutInfo, LayoutMetadata, LayoutParam, ModeInfo, Mouse, PaneContents,
+    PaneId, PaneInfo, PaneInputLock, PaneManifest, PaneMetadata, PaneScrollbackResponse,
+    PermissionStatus, PluginCapabilities, PluginInfo, SelectedText, SessionInfo, Style, TabInfo,
+    TabMetadata, WebServerStatus, WebSharing,
 };
 
 use crate::errors::prelude::*;
@@ -556,6 +556,60 @@ impl TryFrom<ProtobufEvent> for Event {
                 },
                 _ => Err("Malformed payload for InitialKeybinds Event"),
             },
+            Some(ProtobufEventType::SpawnedProcessStdout) => match protobuf_event.payload {
+                Some(ProtobufEventPayload::SpawnedProcessStdoutPayload(payload)) => {
+                    Ok(Event::SpawnedProcessStdout(
+                        payload.process_id,
+                        payload.output,
+                        payload
+                            .context
+                            .into_iter()
+                            .map(|c_i| (c_i.name, c_i.value))
+                            .collect(),
+                    ))
+                },
+                _ => Err("Malformed payload for the SpawnedProcessStdout Event"),
+            },
+            Some(ProtobufEventType::SpawnedProcessStderr) => match protobuf_event.payload {
+                Some(ProtobufEventPayload::SpawnedProcessStderrPayload(payload)) => {
+                    Ok(Event::SpawnedProcessStderr(
+                        payload.process_id,
+                        payload.output,
+                        payload
+                            .context
+                            .into_iter()
+                            .map(|c_i| (c_i.name, c_i.value))
+                            .collect(),
+                    ))
+                },
+                _ => Err("Malformed payload for the SpawnedProcessStderr Event"),
+            },
+            Some(ProtobufEventType::SpawnedProcessExited) => match protobuf_event.payload {
+                Some(ProtobufEventPayload::SpawnedProcessExitedPayload(payload)) => {
+                    Ok(Event::SpawnedProcessExited(
+                        payload.process_id,
+                        payload.exit_code,
+                        payload
+                            .context
+                            .into_iter()
+                            .map(|c_i| (c_i.name, c_i.value))
+                            .collect(),
+                    ))
+                },
+                _ => Err("Malformed payload for the SpawnedProcessExited Event"),
+            },
+            Some(ProtobufEventType::HostFolderScanned) => match protobuf_event.payload {
+                Some(ProtobufEventPayload::FileListPayload(file_list_payload)) => {
+                    let file_paths = file_list_payload
+                        .paths
+                        .iter()
+                        .zip(file_list_payload.paths_metadata.iter())
+                        .map(|(p, m)| (PathBuf::from(p), m.into()))
+                        .collect();
+                    Ok(Event::HostFolderScanned(file_paths))
+                },
+                _ => Err("Malformed payload for the host folder scanned Event"),
+            },
             None => Err("Unknown Protobuf Event"),
         }
     }
@@ -1104,6 +1158,61 @@ impl TryFrom<Event> for ProtobufEvent {
                     )),
                 })
             },
+            Event::SpawnedProcessStdout(process_id, output, context) => Ok(ProtobufEvent {
+                name: ProtobufEventType::SpawnedProcessStdout as i32,
+                payload: Some(event::Payload::SpawnedProcessStdoutPayload(
+                    SpawnedProcessOutputPayload {
+                        process_id,
+                        output,
+                        context: context
+                            .into_iter()
+                            .map(|(name, value)| ContextItem { name, value })
+                            .collect(),
+                    },
+                )),
+            }),
+            Event::SpawnedProcessStderr(process_id, output, context) => Ok(ProtobufEvent {
+                name: ProtobufEventType::SpawnedProcessStderr as i32,
+                payload: Some(event::Payload::SpawnedProcessStderrPayload(
+                    SpawnedProcessOutputPayload {
+                        process_id,
+                        output,
+                        context: context
+                            .into_iter()
+                            .map(|(name, value)| ContextItem { name, value })
+                            .collect(),
+                    },
+                )),
+            }),
+            Event::SpawnedProcessExited(process_id, exit_code, context) => Ok(ProtobufEvent {
+                name: ProtobufEventType::SpawnedProcessExited as i32,
+                payload: Some(event::Payload::SpawnedProcessExitedPayload(
+                    SpawnedProcessExitedPayload {
+                        process_id,
+                        exit_code,
+                        context: context
+                            .into_iter()
+                            .map(|(name, value)| ContextItem { name, value })
+                            .collect(),
+                    },
+                )),
+            }),
+            Event::HostFolderScanned(event_paths) => {
+                let mut paths = vec![];
+                let mut paths_metadata = vec![];
+                for (path, path_metadata) in event_paths {
+                    paths.push(path.display().to_string());
+                    paths_metadata.push(path_metadata.into());
+                }
+                let file_list_payload = FileListPayload {
+                    paths,
+                    paths_metadata,
+                };
+                Ok(ProtobufEvent {
+                    name: ProtobufEventType::HostFolderScanned as i32,
+                    payload: Some(event::Payload::FileListPayload(file_list_payload)),
+                })
+            },
         }
     }
 }
@@ -1189,6 +1298,7 @@ impl From<(u32, PluginInfo)> for ProtobufPluginInfo {
                 .into_iter()
                 .map(|(name, value)| ContextItem { name, value })
                 .collect(),
+            cpu_time_ms: plugin_info.cpu_time_ms,
         }
     }
 }
@@ -1222,6 +1332,7 @@ impl TryFrom<ProtobufSessionManifest> for SessionInfo {
                 PluginInfo {
                     location: plugin_info.plugin_url,
                     configuration,
+                    cpu_time_ms: plugin_info.cpu_time_ms,
                 },
             );
         }
@@ -1330,6 +1441,14 @@ impl TryFrom<ProtobufLayoutMetadata> for LayoutMetadata {
             tabs,
             creation_time: protobuf_metadata.creation_time,
             update_time: protobuf_metadata.update_time,
+            params: protobuf_metadata
+                .params
+                .into_iter()
+                .map(|p| LayoutParam {
+                    name: p.name,
+                    default: p.default_value,
+                })
+                .collect(),
         })
     }
 }
@@ -1346,6 +1465,14 @@ impl TryFrom<LayoutMetadata> for ProtobufLayoutMetadata {
             tabs,
             creation_time: metadata.creation_time,
             update_time: metadata.update_time,
+            params: metadata
+                .params
+                .into_iter()
+                .map(|p| ProtobufLayoutParam {
+                    name: p.name,
+                    default_value: p.default,
+                })
+                .collect(),
         })
     }
 }
@@ -1677,6 +1804,11 @@ impl TryFrom<ProtobufPaneInfo> for PaneInfo {
                 .collect(),
             default_fg: protobuf_pane_info.default_fg,
             default_bg: protobuf_pane_info.default_bg,
+            input_lock: protobuf_pane_info
+                .input_lock
+                .map(|input_lock| PaneInputLock::from_str(&input_lock))
+                .transpose()
+                .map_err(|_| "Malformed input_lock in PaneInfo")?,
         })
     }
 }
@@ -1722,6 +1854,7 @@ impl TryFrom<PaneInfo> for ProtobufPaneInfo {
                 .collect(),
             default_fg: pane_info.default_fg,
             default_bg: pane_info.default_bg,
+            input_lock: pane_info.input_lock.map(|l| l.name().to_owned()),
         })
     }
 }
@@ -1844,6 +1977,7 @@ impl TryFrom<ProtobufModeUpdatePayload> for ModeInfo {
         };
         let currently_marking_pane_group =
             protobuf_mode_update_payload.currently_marking_pane_group;
+        let broadcasting_to_pane_group = protobuf_mode_update_payload.broadcasting_to_pane_group;
         let is_web_client = protobuf_mode_update_payload.is_web_client;
 
         let web_server_ip = protobuf_mode_update_payload
@@ -1869,6 +2003,7 @@ impl TryFrom<ProtobufModeUpdatePayload> for ModeInfo {
             web_clients_allowed,
             web_sharing,
             currently_marking_pane_group,
+            broadcasting_to_pane_group,
             is_web_client,
             web_server_ip,
             web_server_port,
@@ -1893,6 +2028,7 @@ impl TryFrom<ModeInfo> for ProtobufModeUpdatePayload {
         let web_clients_allowed = mode_info.web_clients_allowed;
         let web_sharing = mode_info.web_sharing.map(|w| w as i32);
         let currently_marking_pane_group = mode_info.currently_marking_pane_group;
+        let broadcasting_to_pane_group = mode_info.broadcasting_to_pane_group;
         let is_web_client = mode_info.is_web_client;
         let web_server_ip = mode_info.web_server_ip.map(|i| format!("{}", i));
         let web_server_port = mode_info.web_server_port.map(|p| p as u32);
@@ -1933,6 +2069,7 @@ impl TryFrom<ModeInfo> for ProtobufModeUpdatePayload {
             web_clients_allowed,
             web_sharing,
             currently_marking_pane_group,
+            broadcasting_to_pane_group,
             is_web_client,
             web_server_ip,
             web_server_port,
@@ -2019,6 +2156,10 @@ impl TryFrom<ProtobufEventType> for EventType {
             ProtobufEventType::PluginConfigurationChanged => EventType::PluginConfigurationChanged,
             ProtobufEventType::HighlightClicked => EventType::HighlightClicked,
             ProtobufEventType::InitialKeybinds => EventType::InitialKeybinds,
+            ProtobufEventType::SpawnedProcessStdout => EventType::SpawnedProcessStdout,
+            ProtobufEventType::SpawnedProcessStderr => EventType::SpawnedProcessStderr,
+            ProtobufEventType::SpawnedProcessExited => EventType::SpawnedProcessExited,
+            ProtobufEventType::HostFolderScanned => EventType::HostFolderScanned,
         })
     }
 }
@@ -2071,6 +2212,10 @@ impl TryFrom<EventType> for ProtobufEventType {
             EventType::PluginConfigurationChanged => ProtobufEventType::PluginConfigurationChanged,
             EventType::HighlightClicked => ProtobufEventType::HighlightClicked,
             EventType::InitialKeybinds => ProtobufEventType::InitialKeybinds,
+            EventType::SpawnedProcessStdout => ProtobufEventType::SpawnedProcessStdout,
+            EventType::SpawnedProcessStderr => ProtobufEventType::SpawnedProcessStderr,
+            EventType::SpawnedProcessExited => ProtobufEventType::SpawnedProcessExited,
+            EventType::HostFolderScanned => ProtobufEventType::HostFolderScanned,
         })
     }
 }
@@ -2226,6 +2371,7 @@ fn serialize_mode_update_event_with_non_default_values() {
         web_clients_allowed: Some(true),
         web_sharing: Some(WebSharing::default()),
         currently_marking_pane_group: Some(false),
+        broadcasting_to_pane_group: Some(false),
         is_web_client: Some(false),
         web_server_ip: IpAddr::from_str("127.0.0.1").ok(),
         web_server_port: Some(8082),
@@ -2521,6 +2667,24 @@ fn serialize_file_system_update_event() {
     );
 }
 
+#[test]
+fn serialize_host_folder_scanned_event() {
+    use prost::Message;
+    let host_folder_scanned_event = Event::HostFolderScanned(vec![
+        ("/host/file".into(), Some(Default::default())),
+        ("/host/unreadable_file".into(), None),
+    ]);
+    let protobuf_event: ProtobufEvent = host_folder_scanned_event.clone().try_into().unwrap();
+    let serialized_protobuf_event = protobuf_event.encode_to_vec();
+    let deserialized_protobuf_event: ProtobufEvent =
+        Message::decode(serialized_protobuf_event.as_slice()).unwrap();
+    let deserialized_event: Event = deserialized_protobuf_event.try_into().unwrap();
+    assert_eq!(
+        host_folder_scanned_event, deserialized_event,
+        "Event properly serialized/deserialized without change"
+    );
+}
+
 #[test]
 fn serialize_file_system_delete_event() {
     use prost::Message;
@@ -2638,6 +2802,7 @@ fn serialize_session_update_event_with_non_default_values() {
             index_in_pane_group: index_in_pane_group_1,
             default_fg: None,
             default_bg: None,
+            input_lock: Some(PaneInputLock::Confirm),
         },
         PaneInfo {
             id: 1,
@@ -2665,6 +2830,7 @@ fn serialize_session_update_event_with_non_default_values() {
             index_in_pane_group: index_in_pane_group_2,
             default_fg: None,
             default_bg: None,
+            input_lock: None,
         },
     ];
     panes.insert(0, panes_list);
@@ -2676,6 +2842,7 @@ fn serialize_session_update_event_with_non_default_values() {
         PluginInfo {
             location: "https://example.com/my-plugin.wasm".to_owned(),
             configuration: plugin_configuration,
+            cpu_time_ms: 42,
         },
     );
     let mut tab_history = BTreeMap::new();
@@ -2694,6 +2861,7 @@ fn serialize_session_update_event_with_non_default_values() {
                     tabs: vec![],
                     creation_time: "0".to_owned(),
                     update_time: "0".to_owned(),
+                    params: vec![],
                 },
             ),
             LayoutInfo::BuiltIn("layout2".to_owned()),
@@ -2703,6 +2871,7 @@ fn serialize_session_update_event_with_non_default_values() {
                     tabs: vec![],
                     creation_time: "0".to_owned(),
                     update_time: "0".to_owned(),
+                    params: vec![],
                 },
             ),
         ],
@@ -2728,6 +2897,7 @@ fn serialize_session_update_event_with_non_default_values() {
                     tabs: vec![],
                     creation_time: "0".to_owned(),
                     update_time: "0".to_owned(),
+                    params: vec![],
                 },
             ),
             LayoutInfo::BuiltIn("layout2".to_owned()),
@@ -2737,6 +2907,7 @@ fn serialize_session_update_event_with_non_default_values() {
                     tabs: vec![],
                     creation_time: "0".to_owned(),
                     update_time: "0".to_owned(),
+                    params: vec![],
                 },
             ),
         ],
@@ -3038,3 +3209,27 @@ fn serialize_pane_render_report_with_ansi_event_with_data() {
         "PaneRenderReportWithAnsi event with ANSI data properly serialized/deserialized"
     );
 }
+
+#[test]
+fn serialize_spawned_process_events() {
+    use prost::Message;
+    let mut context = BTreeMap::new();
+    context.insert("watcher".to_owned(), "cargo".to_owned());
+    let events = vec![
+        Event::SpawnedProcessStdout(1, b"Compiling\n".to_vec(), context.clone()),
+        Event::SpawnedProcessStderr(1, b"warning\n".to_vec(), context.clone()),
+        Event::SpawnedProcessExited(1, Some(0), context.clone()),
+        Event::SpawnedProcessExited(2, None, BTreeMap::new()),
+    ];
+    for event in events {
+        let protobuf_event: ProtobufEvent = event.clone().try_into().unwrap();
+        let serialized_protobuf_event = protobuf_event.encode_to_vec();
+        let deserialized_protobuf_event: ProtobufEvent =
+            Message::decode(serialized_protobuf_event.as_slice()).unwrap();
+        let deserialized_event: Event = deserialized_protobuf_event.try_into().unwrap();
+        assert_eq!(
+            event, deserialized_event,
+            "Event properly serialized/deserialized without change"
+        );
+    }
+}
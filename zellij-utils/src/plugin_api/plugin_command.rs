@@ -1,17 +1,20 @@
 pub use super::generated_api::api::{
-    action::{Action as ProtobufAction, PaneIdAndShouldFloat, SwitchToModePayload},
+    action::{
+        Action as ProtobufAction, PaneIdAndShouldFloat, SplitDirection as ProtobufSplitDirection,
+        SwitchToModePayload,
+    },
     event::{EventNameList as ProtobufEventNameList, Header},
     input_mode::InputMode as ProtobufInputMode,
     plugin_command::{
         break_panes_to_new_tab_response, break_panes_to_tab_with_id_response,
         break_panes_to_tab_with_index_response, delete_layout_response, dump_layout_response,
         dump_session_layout_response, edit_layout_response, focus_or_create_tab_response,
-        get_focused_pane_info_response, get_pane_cwd_response, get_pane_pid_response,
-        get_pane_running_command_response, hide_floating_panes_response,
-        highlight_style::Style as ProtobufHighlightStyleVariant, new_tab_response,
-        parse_layout_response, plugin_command::Payload, rename_layout_response,
-        save_layout_response, save_session_response, show_floating_panes_response,
-        BreakPanesToNewTabPayload,
+        get_closed_pane_scrollback_response, get_focused_pane_info_response, get_pane_cwd_response,
+        get_pane_pid_response, get_pane_running_command_response, hide_floating_panes_response,
+        highlight_style::Style as ProtobufHighlightStyleVariant, list_closed_panes_response,
+        new_tab_response, parse_layout_response, plugin_command::Payload, rename_layout_response,
+        save_layout_response, save_session_response, search_all_panes_response,
+        show_floating_panes_response, BreakPanesToNewTabPayload,
         BreakPanesToNewTabResponse as ProtobufBreakPanesToNewTabResponse,
         BreakPanesToTabWithIdPayload,
         BreakPanesToTabWithIdResponse as ProtobufBreakPanesToTabWithIdResponse,
@@ -19,9 +22,12 @@ pub use super::generated_api::api::{
         BreakPanesToTabWithIndexResponse as ProtobufBreakPanesToTabWithIndexResponse,
         ChangeFloatingPanesCoordinatesPayload, ChangeHostFolderPayload, ClearPaneHighlightsPayload,
         ClearScreenForPaneIdPayload, CliPipeOutputPayload, CloseMultiplePanesPayload,
-        CloseTabWithIdPayload, CloseTabWithIndexPayload, CommandName, ContextItem,
-        CopyToClipboardPayload, CreateTokenResponse as ProtobufCreateTokenResponse,
-        CreateTokenResponse, CurrentSessionLastSavedTimePayload,
+        CloseProcessStdinPayload, CloseTabWithIdPayload, CloseTabWithIndexPayload,
+        ClosedPaneInfo as ProtobufClosedPaneInfo,
+        ClosedPaneScrollback as ProtobufClosedPaneScrollback, ClosedPanes as ProtobufClosedPanes,
+        CommandName, ContextItem, CopyToClipboardPayload,
+        CreateTokenResponse as ProtobufCreateTokenResponse, CreateTokenResponse,
+        CurrentSessionLastSavedTimePayload,
         CurrentSessionLastSavedTimeResponse as ProtobufCurrentSessionLastSavedTimeResponse,
         CursorPosition, CustomIndexHighlight as ProtobufCustomIndexHighlight,
         CustomRgbHighlight as ProtobufCustomRgbHighlight, DeleteLayoutPayload,
@@ -29,14 +35,16 @@ pub use super::generated_api::api::{
         DumpLayoutResponse as ProtobufDumpLayoutResponse, DumpSessionLayoutPayload,
         DumpSessionLayoutResponse as ProtobufDumpSessionLayoutResponse, EditLayoutPayload,
         EditLayoutResponse as ProtobufEditLayoutResponse, EditScrollbackForPaneWithIdPayload,
-        EmbedMultiplePanesPayload, EnvVariable, ExecCmdPayload,
+        EmbedMultiplePanesPayload, EnvVariable, EqualizePanesPayload, ExecCmdPayload,
         FixedOrPercent as ProtobufFixedOrPercent,
         FixedOrPercentValue as ProtobufFixedOrPercentValue, FloatMultiplePanesPayload,
         FloatingPaneCoordinates as ProtobufFloatingPaneCoordinates,
         FocusOrCreateTabResponse as ProtobufFocusOrCreateTabResponse, FocusedPaneInfo,
         GenerateRandomNamePayload,
         GenerateRandomNameResponse as ProtobufGenerateRandomNameResponse,
-        GenerateWebLoginTokenPayload, GetFocusedPaneInfoPayload,
+        GenerateWebLoginTokenPayload, GetClosedPaneScrollbackPayload,
+        GetClosedPaneScrollbackResponse as ProtobufGetClosedPaneScrollbackResponse,
+        GetFocusedPaneInfoPayload,
         GetFocusedPaneInfoResponse as ProtobufGetFocusedPaneInfoResponse, GetLayoutDirPayload,
         GetLayoutDirResponse as ProtobufGetLayoutDirResponse,
         GetPaneCwdPayload as ProtobufGetPaneCwdPayload,
@@ -45,7 +53,7 @@ pub use super::generated_api::api::{
         GetPanePidResponse as ProtobufGetPanePidResponse,
         GetPaneRunningCommandPayload as ProtobufGetPaneRunningCommandPayload,
         GetPaneRunningCommandResponse as ProtobufGetPaneRunningCommandResponse,
-        GetPaneScrollbackPayload,
+        GetPaneScrollbackPayload, GetResurrectableSessionLayoutPayload,
         GetSessionEnvironmentVariablesPayload as ProtobufGetSessionEnvironmentVariablesPayload,
         GetSessionEnvironmentVariablesResponse as ProtobufGetSessionEnvironmentVariablesResponse,
         GetTabInfoPayload, GetTabInfoResponse as ProtobufGetTabInfoResponse, GoToTabWithIdPayload,
@@ -53,11 +61,15 @@ pub use super::generated_api::api::{
         HideFloatingPanesResponse as ProtobufHideFloatingPanesResponse, HidePaneWithIdPayload,
         HighlightAndUnhighlightPanesPayload, HighlightLayer as ProtobufHighlightLayer,
         HighlightStyle as ProtobufHighlightStyle, HttpVerb as ProtobufHttpVerb, IdAndNewName,
-        KeyToRebind, KeyToUnbind, KillSessionsPayload, ListTokensResponse, LoadNewPluginPayload,
-        MessageToPluginPayload, MovePaneWithPaneIdInDirectionPayload, MovePaneWithPaneIdPayload,
-        MovePayload, NewPluginArgs as ProtobufNewPluginArgs, NewTabPayload,
-        NewTabResponse as ProtobufNewTabResponse, NewTabsResponse as ProtobufNewTabsResponse,
-        NewTabsWithLayoutInfoPayload,
+        KeyToRebind, KeyToUnbind, KillProcessPayload, KillSessionsPayload,
+        ListClosedPanesResponse as ProtobufListClosedPanesResponse,
+        ListPluginPermissionsResponse as ProtobufListPluginPermissionsResponse,
+        ListSessionSnapshotsPayload,
+        ListSessionSnapshotsResponse as ProtobufListSessionSnapshotsResponse, ListTokensResponse,
+        LoadNewPluginPayload, MessageToPluginPayload, MovePaneWithPaneIdInDirectionPayload,
+        MovePaneWithPaneIdPayload, MovePayload, NewPluginArgs as ProtobufNewPluginArgs,
+        NewTabPayload, NewTabResponse as ProtobufNewTabResponse,
+        NewTabsResponse as ProtobufNewTabsResponse, NewTabsWithLayoutInfoPayload,
         OpenCommandPaneBackgroundResponse as ProtobufOpenCommandPaneBackgroundResponse,
         OpenCommandPaneFloatingNearPluginPayload,
         OpenCommandPaneFloatingNearPluginResponse as ProtobufOpenCommandPaneFloatingNearPluginResponse,
@@ -96,44 +108,56 @@ pub use super::generated_api::api::{
         OpenTerminalPaneInPlaceOfPaneIdResponse as ProtobufOpenTerminalPaneInPlaceOfPaneIdResponse,
         OpenTerminalResponse as ProtobufOpenTerminalResponse, OverrideLayoutPayload,
         PageScrollDownInPaneIdPayload, PageScrollUpInPaneIdPayload, PaneId as ProtobufPaneId,
-        PaneIdAndFloatingPaneCoordinates, PaneType as ProtobufPaneType, ParseLayoutPayload,
-        ParseLayoutResponse as ProtobufParseLayoutResponse, PluginCommand as ProtobufPluginCommand,
-        PluginMessagePayload, RebindKeysPayload, ReconfigurePayload,
-        RegexHighlight as ProtobufRegexHighlight, ReloadPluginPayload, RenameLayoutPayload,
-        RenameLayoutResponse as ProtobufRenameLayoutResponse, RenameTabWithIdPayload,
-        RenameWebLoginTokenPayload, RenameWebTokenResponse, ReplacePaneWithExistingPanePayload,
+        PaneIdAndFloatingPaneCoordinates, PaneSearchResult as ProtobufPaneSearchResult,
+        PaneSearchResults as ProtobufPaneSearchResults, PaneType as ProtobufPaneType,
+        ParseLayoutPayload, ParseLayoutResponse as ProtobufParseLayoutResponse,
+        PluginCommand as ProtobufPluginCommand, PluginMessagePayload,
+        PluginPermissionGrant as ProtobufPluginPermissionGrant, RebindKeysPayload,
+        ReconfigurePayload, RegexHighlight as ProtobufRegexHighlight, ReloadPluginPayload,
+        RenameLayoutPayload, RenameLayoutResponse as ProtobufRenameLayoutResponse,
+        RenameTabWithIdPayload, RenameWebLoginTokenPayload, RenameWebTokenResponse,
+        ReopenClosedPanePayload, ReplacePaneWithExistingPanePayload,
         RequestPluginPermissionPayload, RerunCommandPanePayload, ResizePaneIdWithDirectionPayload,
-        ResizePayload, RevokeAllWebTokensResponse, RevokeTokenResponse, RevokeWebLoginTokenPayload,
+        ResizePayload, RevealSearchMatchPayload, RevokeAllWebTokensResponse,
+        RevokePluginPermissionPayload, RevokeTokenResponse, RevokeWebLoginTokenPayload,
         RunActionPayload, RunCommandPayload, RunningCommand as ProtobufRunningCommand,
         SaveLayoutPayload, SaveLayoutResponse as ProtobufSaveLayoutResponse, SaveSessionPayload,
         SaveSessionResponse as ProtobufSaveSessionResponse, ScrollDownInPaneIdPayload,
         ScrollToBottomInPaneIdPayload, ScrollToTopInPaneIdPayload, ScrollUpInPaneIdPayload,
-        SetFloatingPanePinnedPayload, SetPaneBorderlessPayload, SetPaneColorPayload,
+        SearchAllPanesPayload, SearchAllPanesResponse as ProtobufSearchAllPanesResponse,
+        SearchMatch as ProtobufSearchMatch, SendPaneToSessionPayload,
+        SessionSnapshot as ProtobufSessionSnapshot, SetFloatingPanePinnedPayload,
+        SetPaneBorderlessPayload, SetPaneColorPayload, SetPaneInputLockPayload,
         SetPaneRegexHighlightsPayload, SetSelfMouseSelectionSupportPayload, SetTimeoutPayload,
         ShowCursorPayload, ShowFloatingPanesPayload as ProtobufShowFloatingPanesPayload,
         ShowFloatingPanesResponse as ProtobufShowFloatingPanesResponse, ShowPaneWithIdPayload,
+        SignalProcessPayload, SpawnProcessResponse as ProtobufSpawnProcessResponse,
         StackPanesPayload, SubscribePayload, SwitchSessionPayload, SwitchTabToIdPayload,
         SwitchTabToPayload, TogglePaneBorderlessPayload, TogglePaneEmbedOrEjectForPaneIdPayload,
         TogglePaneIdFullscreenPayload, UnsubscribePayload, WebRequestPayload,
-        WriteCharsToPaneIdPayload, WriteToPaneIdPayload,
+        WriteCharsToPaneIdPayload, WriteToPaneIdPayload, WriteToProcessStdinPayload,
     },
     plugin_permission::PermissionType as ProtobufPermissionType,
     resize::ResizeAction as ProtobufResizeAction,
 };
 
 use crate::data::{
-    ConnectToSession, DeleteLayoutResponse, EditLayoutResponse, FloatingPaneCoordinates,
-    GetFocusedPaneInfoResponse, GetPaneCwdResponse, GetPanePidResponse,
-    GetPaneRunningCommandResponse, HighlightLayer, HighlightStyle, HttpVerb, InputMode,
-    KeyWithModifier, MessageToPlugin, NewPluginArgs, PaneId, PermissionType, PluginCommand,
-    RegexHighlight, RenameLayoutResponse, SaveLayoutResponse,
+    ClosedPaneInfo, ConnectToSession, DeleteLayoutResponse, EditLayoutResponse,
+    FloatingPaneCoordinates, GetClosedPaneScrollbackResponse, GetFocusedPaneInfoResponse,
+    GetPaneCwdResponse, GetPanePidResponse, GetPaneRunningCommandResponse, HighlightLayer,
+    HighlightStyle, HttpVerb, InputMode, KeyWithModifier, ListClosedPanesResponse, MessageToPlugin,
+    NewPluginArgs, PaneId, PaneInputLock, PaneSearchResult, PermissionType, PluginCommand,
+    RegexHighlight, RenameLayoutResponse, SaveLayoutResponse, SearchAllPanesResponse, SearchMatch,
+    SessionSnapshot,
 };
 use crate::input::actions::Action;
-use crate::input::layout::PercentOrFixed;
+use crate::input::layout::{PercentOrFixed, SplitDirection};
 
 use std::collections::BTreeMap;
 use std::convert::TryFrom;
 use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
 
 impl Into<FloatingPaneCoordinates> for ProtobufFloatingPaneCoordinates {
     fn into(self) -> FloatingPaneCoordinates {
@@ -314,6 +338,217 @@ impl From<GetPanePidResponse> for ProtobufGetPanePidResponse {
     }
 }
 
+impl TryFrom<ProtobufSearchMatch> for SearchMatch {
+    type Error = &'static str;
+    fn try_from(protobuf_search_match: ProtobufSearchMatch) -> Result<Self, &'static str> {
+        Ok(SearchMatch {
+            line_index: protobuf_search_match.line_index as usize,
+            column: protobuf_search_match.column as usize,
+            line: protobuf_search_match.line,
+        })
+    }
+}
+
+impl From<SearchMatch> for ProtobufSearchMatch {
+    fn from(search_match: SearchMatch) -> Self {
+        ProtobufSearchMatch {
+            line_index: search_match.line_index as u32,
+            column: search_match.column as u32,
+            line: search_match.line,
+        }
+    }
+}
+
+impl From<ProtobufSessionSnapshot> for SessionSnapshot {
+    fn from(protobuf_session_snapshot: ProtobufSessionSnapshot) -> Self {
+        SessionSnapshot {
+            name: protobuf_session_snapshot.name,
+            layout_path: PathBuf::from(protobuf_session_snapshot.layout_path),
+            age: Duration::from_secs(protobuf_session_snapshot.age_secs),
+        }
+    }
+}
+
+impl From<SessionSnapshot> for ProtobufSessionSnapshot {
+    fn from(session_snapshot: SessionSnapshot) -> Self {
+        ProtobufSessionSnapshot {
+            name: session_snapshot.name,
+            layout_path: session_snapshot.layout_path.display().to_string(),
+            age_secs: session_snapshot.age.as_secs(),
+        }
+    }
+}
+
+impl TryFrom<ProtobufPaneSearchResult> for PaneSearchResult {
+    type Error = &'static str;
+    fn try_from(protobuf_result: ProtobufPaneSearchResult) -> Result<Self, &'static str> {
+        Ok(PaneSearchResult {
+            tab_position: protobuf_result.tab_position as usize,
+            tab_name: protobuf_result.tab_name,
+            pane_id: protobuf_result
+                .pane_id
+                .ok_or("PaneSearchResult missing pane_id")?
+                .try_into()?,
+            pane_title: protobuf_result.pane_title,
+            matches: protobuf_result
+                .matches
+                .into_iter()
+                .map(|m| m.try_into())
+                .collect::<Result<Vec<_>, _>>()?,
+        })
+    }
+}
+
+impl TryFrom<PaneSearchResult> for ProtobufPaneSearchResult {
+    type Error = &'static str;
+    fn try_from(result: PaneSearchResult) -> Result<Self, &'static str> {
+        Ok(ProtobufPaneSearchResult {
+            tab_position: result.tab_position as u32,
+            tab_name: result.tab_name,
+            pane_id: Some(result.pane_id.try_into()?),
+            pane_title: result.pane_title,
+            matches: result.matches.into_iter().map(|m| m.into()).collect(),
+        })
+    }
+}
+
+impl TryFrom<ProtobufSearchAllPanesResponse> for SearchAllPanesResponse {
+    type Error = &'static str;
+    fn try_from(protobuf_response: ProtobufSearchAllPanesResponse) -> Result<Self, &'static str> {
+        match protobuf_response.result {
+            Some(search_all_panes_response::Result::Results(results)) => {
+                Ok(SearchAllPanesResponse::Ok(
+                    results
+                        .results
+                        .into_iter()
+                        .map(|r| r.try_into())
+                        .collect::<Result<Vec<_>, _>>()?,
+                ))
+            },
+            Some(search_all_panes_response::Result::Error(error)) => {
+                Ok(SearchAllPanesResponse::Err(error))
+            },
+            None => Err("Empty SearchAllPanesResponse"),
+        }
+    }
+}
+
+impl TryFrom<SearchAllPanesResponse> for ProtobufSearchAllPanesResponse {
+    type Error = &'static str;
+    fn try_from(response: SearchAllPanesResponse) -> Result<Self, &'static str> {
+        let result = match response {
+            SearchAllPanesResponse::Ok(results) => {
+                search_all_panes_response::Result::Results(ProtobufPaneSearchResults {
+                    results: results
+                        .into_iter()
+                        .map(|r| r.try_into())
+                        .collect::<Result<Vec<_>, _>>()?,
+                })
+            },
+            SearchAllPanesResponse::Err(error) => search_all_panes_response::Result::Error(error),
+        };
+        Ok(ProtobufSearchAllPanesResponse {
+            result: Some(result),
+        })
+    }
+}
+
+impl From<ProtobufClosedPaneInfo> for ClosedPaneInfo {
+    fn from(protobuf_closed_pane: ProtobufClosedPaneInfo) -> Self {
+        ClosedPaneInfo {
+            id: protobuf_closed_pane.id,
+            title: protobuf_closed_pane.title,
+            command: protobuf_closed_pane.command,
+            cwd: protobuf_closed_pane.cwd.map(PathBuf::from),
+            exit_status: protobuf_closed_pane.exit_status,
+        }
+    }
+}
+
+impl From<ClosedPaneInfo> for ProtobufClosedPaneInfo {
+    fn from(closed_pane: ClosedPaneInfo) -> Self {
+        ProtobufClosedPaneInfo {
+            id: closed_pane.id,
+            title: closed_pane.title,
+            command: closed_pane.command,
+            cwd: closed_pane.cwd.map(|cwd| cwd.display().to_string()),
+            exit_status: closed_pane.exit_status,
+        }
+    }
+}
+
+impl TryFrom<ProtobufListClosedPanesResponse> for ListClosedPanesResponse {
+    type Error = &'static str;
+    fn try_from(protobuf_response: ProtobufListClosedPanesResponse) -> Result<Self, &'static str> {
+        match protobuf_response.result {
+            Some(list_closed_panes_response::Result::ClosedPanes(closed_panes)) => {
+                Ok(ListClosedPanesResponse::Ok(
+                    closed_panes
+                        .closed_panes
+                        .into_iter()
+                        .map(|c| c.into())
+                        .collect(),
+                ))
+            },
+            Some(list_closed_panes_response::Result::Error(error)) => {
+                Ok(ListClosedPanesResponse::Err(error))
+            },
+            None => Err("Empty ListClosedPanesResponse"),
+        }
+    }
+}
+
+impl From<ListClosedPanesResponse> for ProtobufListClosedPanesResponse {
+    fn from(response: ListClosedPanesResponse) -> Self {
+        let result = match response {
+            ListClosedPanesResponse::Ok(closed_panes) => {
+                list_closed_panes_response::Result::ClosedPanes(ProtobufClosedPanes {
+                    closed_panes: closed_panes.into_iter().map(|c| c.into()).collect(),
+                })
+            },
+            ListClosedPanesResponse::Err(error) => list_closed_panes_response::Result::Error(error),
+        };
+        ProtobufListClosedPanesResponse {
+            result: Some(result),
+        }
+    }
+}
+
+impl TryFrom<ProtobufGetClosedPaneScrollbackResponse> for GetClosedPaneScrollbackResponse {
+    type Error = &'static str;
+    fn try_from(
+        protobuf_response: ProtobufGetClosedPaneScrollbackResponse,
+    ) -> Result<Self, &'static str> {
+        match protobuf_response.result {
+            Some(get_closed_pane_scrollback_response::Result::Scrollback(scrollback)) => {
+                Ok(GetClosedPaneScrollbackResponse::Ok(scrollback.lines))
+            },
+            Some(get_closed_pane_scrollback_response::Result::Error(error)) => {
+                Ok(GetClosedPaneScrollbackResponse::Err(error))
+            },
+            None => Err("Empty GetClosedPaneScrollbackResponse"),
+        }
+    }
+}
+
+impl From<GetClosedPaneScrollbackResponse> for ProtobufGetClosedPaneScrollbackResponse {
+    fn from(response: GetClosedPaneScrollbackResponse) -> Self {
+        let result = match response {
+            GetClosedPaneScrollbackResponse::Ok(lines) => {
+                get_closed_pane_scrollback_response::Result::Scrollback(
+                    ProtobufClosedPaneScrollback { lines },
+                )
+            },
+            GetClosedPaneScrollbackResponse::Err(error) => {
+                get_closed_pane_scrollback_response::Result::Error(error)
+            },
+        };
+        ProtobufGetClosedPaneScrollbackResponse {
+            result: Some(result),
+        }
+    }
+}
+
 impl From<GetPaneRunningCommandResponse> for ProtobufGetPaneRunningCommandResponse {
     fn from(response: GetPaneRunningCommandResponse) -> Self {
         match response {
@@ -1315,11 +1550,17 @@ impl TryFrom<ProtobufPluginCommand> for PluginCommand {
             },
             Some(CommandName::NewTabsWithLayoutInfo) => match protobuf_plugin_command.payload {
                 Some(Payload::NewTabsWithLayoutInfoPayload(new_tabs_with_layout_info_payload)) => {
+                    let layout_vars = new_tabs_with_layout_info_payload
+                        .layout_vars
+                        .into_iter()
+                        .map(|v| (v.name, v.value))
+                        .collect();
                     new_tabs_with_layout_info_payload
                         .layout_info
                         .and_then(|layout_info| {
                             Some(PluginCommand::NewTabsWithLayoutInfo(
                                 layout_info.try_into().ok()?,
+                                layout_vars,
                             ))
                         })
                         .ok_or("Failed to parse NewTabsWithLayoutInfo command")
@@ -1506,12 +1747,18 @@ impl TryFrom<ProtobufPluginCommand> for PluginCommand {
                         .into_iter()
                         .map(|c| (c.name, c.value))
                         .collect();
+                    let layout_vars = override_layout_payload
+                        .layout_vars
+                        .into_iter()
+                        .map(|v| (v.name, v.value))
+                        .collect();
                     Ok(PluginCommand::OverrideLayout(
                         layout_info,
                         override_layout_payload.retain_existing_terminal_panes,
                         override_layout_payload.retain_existing_plugin_panes,
                         override_layout_payload.apply_only_to_active_tab,
                         context,
+                        layout_vars,
                     ))
                 },
                 _ => Err("Mismatched payload for OverrideLayout"),
@@ -2549,6 +2796,159 @@ impl TryFrom<ProtobufPluginCommand> for PluginCommand {
                 },
                 _ => Err("Mismatched payload for ClearPaneHighlights"),
             },
+            Some(CommandName::SendPaneToSession) => match protobuf_plugin_command.payload {
+                Some(Payload::SendPaneToSessionPayload(p)) => {
+                    let pane_id: PaneId = p
+                        .pane_id
+                        .ok_or("Missing pane_id in SendPaneToSession")?
+                        .try_into()?;
+                    Ok(PluginCommand::SendPaneToSession(pane_id, p.session_name))
+                },
+                _ => Err("Mismatched payload for SendPaneToSession"),
+            },
+            Some(CommandName::EqualizePanes) => match protobuf_plugin_command.payload {
+                Some(Payload::EqualizePanesPayload(p)) => {
+                    let direction: Option<SplitDirection> = p
+                        .direction
+                        .and_then(ProtobufSplitDirection::from_i32)
+                        .and_then(|d| d.try_into().ok());
+                    Ok(PluginCommand::EqualizePanes(direction))
+                },
+                _ => Err("Mismatched payload for EqualizePanes"),
+            },
+            Some(CommandName::SpawnProcess) => match protobuf_plugin_command.payload {
+                Some(Payload::SpawnProcessPayload(spawn_process_payload)) => {
+                    let env_variables: BTreeMap<String, String> = spawn_process_payload
+                        .env_variables
+                        .into_iter()
+                        .map(|e| (e.name, e.value))
+                        .collect();
+                    let context: BTreeMap<String, String> = spawn_process_payload
+                        .context
+                        .into_iter()
+                        .map(|e| (e.name, e.value))
+                        .collect();
+                    Ok(PluginCommand::SpawnProcess(
+                        spawn_process_payload.command_line,
+                        env_variables,
+                        PathBuf::from(spawn_process_payload.cwd),
+                        context,
+                    ))
+                },
+                _ => Err("Mismatched payload for SpawnProcess"),
+            },
+            Some(CommandName::WriteToProcessStdin) => match protobuf_plugin_command.payload {
+                Some(Payload::WriteToProcessStdinPayload(p)) => {
+                    Ok(PluginCommand::WriteToProcessStdin(p.process_id, p.bytes))
+                },
+                _ => Err("Mismatched payload for WriteToProcessStdin"),
+            },
+            Some(CommandName::CloseProcessStdin) => match protobuf_plugin_command.payload {
+                Some(Payload::CloseProcessStdinPayload(p)) => {
+                    Ok(PluginCommand::CloseProcessStdin(p.process_id))
+                },
+                _ => Err("Mismatched payload for CloseProcessStdin"),
+            },
+            Some(CommandName::KillProcess) => match protobuf_plugin_command.payload {
+                Some(Payload::KillProcessPayload(p)) => {
+                    Ok(PluginCommand::KillProcess(p.process_id))
+                },
+                _ => Err("Mismatched payload for KillProcess"),
+            },
+            Some(CommandName::SignalProcess) => match protobuf_plugin_command.payload {
+                Some(Payload::SignalProcessPayload(p)) => {
+                    Ok(PluginCommand::SignalProcess(p.process_id, p.signal))
+                },
+                _ => Err("Mismatched payload for SignalProcess"),
+            },
+            Some(CommandName::ListPluginPermissions) => match protobuf_plugin_command.payload {
+                Some(_) => Err("ListPluginPermissions should have no payload, found a payload"),
+                None => Ok(PluginCommand::ListPluginPermissions),
+            },
+            Some(CommandName::RevokePluginPermission) => match protobuf_plugin_command.payload {
+                Some(Payload::RevokePluginPermissionPayload(payload)) => {
+                    let permission = ProtobufPermissionType::from_i32(payload.permission)
+                        .and_then(|p| PermissionType::try_from(p).ok())
+                        .ok_or("Unknown permission type for RevokePluginPermission")?;
+                    Ok(PluginCommand::RevokePluginPermission(
+                        payload.plugin_url,
+                        permission,
+                    ))
+                },
+                _ => Err("Mismatched payload for RevokePluginPermission"),
+            },
+            Some(CommandName::GetResurrectableSessionLayout) => {
+                match protobuf_plugin_command.payload {
+                    Some(Payload::GetResurrectableSessionLayoutPayload(payload)) => Ok(
+                        PluginCommand::GetResurrectableSessionLayout(payload.session_name),
+                    ),
+                    _ => Err("Mismatched payload for GetResurrectableSessionLayout"),
+                }
+            },
+            Some(CommandName::SearchAllPanes) => match protobuf_plugin_command.payload {
+                Some(Payload::SearchAllPanesPayload(payload)) => {
+                    Ok(PluginCommand::SearchAllPanes {
+                        query: payload.query,
+                        case_sensitive: payload.case_sensitive,
+                    })
+                },
+                _ => Err("Mismatched payload for SearchAllPanes"),
+            },
+            Some(CommandName::RevealSearchMatch) => match protobuf_plugin_command.payload {
+                Some(Payload::RevealSearchMatchPayload(payload)) => match payload.pane_id {
+                    Some(pane_id) => Ok(PluginCommand::RevealSearchMatch {
+                        pane_id: pane_id.try_into()?,
+                        query: payload.query,
+                        case_sensitive: payload.case_sensitive,
+                        line_index: payload.line_index as usize,
+                    }),
+                    None => Err("Malformed reveal_search_match_payload"),
+                },
+                _ => Err("Mismatched payload for RevealSearchMatch"),
+            },
+            Some(CommandName::ToggleGroupBroadcast) => match protobuf_plugin_command.payload {
+                Some(_) => Err("ToggleGroupBroadcast should have no payload, found a payload"),
+                None => Ok(PluginCommand::ToggleGroupBroadcast),
+            },
+            Some(CommandName::ListSessionSnapshots) => match protobuf_plugin_command.payload {
+                Some(Payload::ListSessionSnapshotsPayload(payload)) => {
+                    Ok(PluginCommand::ListSessionSnapshots(payload.session_name))
+                },
+                _ => Err("Mismatched payload for ListSessionSnapshots"),
+            },
+            Some(CommandName::SetPaneInputLock) => match protobuf_plugin_command.payload {
+                Some(Payload::SetPaneInputLockPayload(payload)) => {
+                    let pane_id = payload
+                        .pane_id
+                        .ok_or("Malformed SetPaneInputLock payload")?
+                        .try_into()?;
+                    let input_lock = match payload.input_lock {
+                        Some(input_lock) => Some(
+                            PaneInputLock::from_str(&input_lock)
+                                .map_err(|_| "Malformed SetPaneInputLock payload")?,
+                        ),
+                        None => None,
+                    };
+                    Ok(PluginCommand::SetPaneInputLock(pane_id, input_lock))
+                },
+                _ => Err("Mismatched payload for SetPaneInputLock"),
+            },
+            Some(CommandName::ListClosedPanes) => match protobuf_plugin_command.payload {
+                Some(_) => Err("ListClosedPanes should have no payload, found a payload"),
+                None => Ok(PluginCommand::ListClosedPanes),
+            },
+            Some(CommandName::ReopenClosedPane) => match protobuf_plugin_command.payload {
+                Some(Payload::ReopenClosedPanePayload(payload)) => {
+                    Ok(PluginCommand::ReopenClosedPane(payload.id))
+                },
+                _ => Err("Mismatched payload for ReopenClosedPane"),
+            },
+            Some(CommandName::GetClosedPaneScrollback) => match protobuf_plugin_command.payload {
+                Some(Payload::GetClosedPaneScrollbackPayload(payload)) => {
+                    Ok(PluginCommand::GetClosedPaneScrollback(payload.id))
+                },
+                _ => Err("Mismatched payload for GetClosedPaneScrollback"),
+            },
             Some(CommandName::OpenPluginPaneFloating) => match protobuf_plugin_command.payload {
                 Some(Payload::OpenPluginPaneFloatingPayload(payload)) => {
                     let configuration: BTreeMap<String, String> =
@@ -3157,12 +3557,16 @@ impl TryFrom<PluginCommand> for ProtobufPluginCommand {
                 name: CommandName::CloseSelf as i32,
                 payload: None,
             }),
-            PluginCommand::NewTabsWithLayoutInfo(new_tabs_with_layout_info_payload) => {
+            PluginCommand::NewTabsWithLayoutInfo(layout_info, layout_vars) => {
                 Ok(ProtobufPluginCommand {
                     name: CommandName::NewTabsWithLayoutInfo as i32,
                     payload: Some(Payload::NewTabsWithLayoutInfoPayload(
                         NewTabsWithLayoutInfoPayload {
-                            layout_info: new_tabs_with_layout_info_payload.try_into().ok(),
+                            layout_info: layout_info.try_into().ok(),
+                            layout_vars: layout_vars
+                                .into_iter()
+                                .map(|(name, value)| ContextItem { name, value })
+                                .collect(),
                         },
                     )),
                 })
@@ -3297,6 +3701,7 @@ impl TryFrom<PluginCommand> for ProtobufPluginCommand {
                 retain_existing_plugin_panes,
                 apply_only_to_active_tab,
                 context,
+                layout_vars,
             ) => Ok(ProtobufPluginCommand {
                 name: CommandName::OverrideLayout as i32,
                 payload: Some(Payload::OverrideLayoutPayload(OverrideLayoutPayload {
@@ -3308,6 +3713,10 @@ impl TryFrom<PluginCommand> for ProtobufPluginCommand {
                     retain_existing_terminal_panes,
                     retain_existing_plugin_panes,
                     apply_only_to_active_tab,
+                    layout_vars: layout_vars
+                        .into_iter()
+                        .map(|(name, value)| ContextItem { name, value })
+                        .collect(),
                 })),
             }),
             PluginCommand::SaveLayout {
@@ -4232,6 +4641,151 @@ impl TryFrom<PluginCommand> for ProtobufPluginCommand {
                     },
                 )),
             }),
+            PluginCommand::SendPaneToSession(pane_id, session_name) => Ok(ProtobufPluginCommand {
+                name: CommandName::SendPaneToSession as i32,
+                payload: Some(Payload::SendPaneToSessionPayload(
+                    SendPaneToSessionPayload {
+                        pane_id: pane_id.try_into().ok(),
+                        session_name,
+                    },
+                )),
+            }),
+            PluginCommand::EqualizePanes(direction) => Ok(ProtobufPluginCommand {
+                name: CommandName::EqualizePanes as i32,
+                payload: Some(Payload::EqualizePanesPayload(EqualizePanesPayload {
+                    direction: direction.and_then(|d| {
+                        let protobuf_direction: ProtobufSplitDirection = d.try_into().ok()?;
+                        Some(protobuf_direction as i32)
+                    }),
+                })),
+            }),
+            PluginCommand::SpawnProcess(command_line, env_variables, cwd, context) => {
+                let env_variables: Vec<_> = env_variables
+                    .into_iter()
+                    .map(|(name, value)| EnvVariable { name, value })
+                    .collect();
+                let context: Vec<_> = context
+                    .into_iter()
+                    .map(|(name, value)| ContextItem { name, value })
+                    .collect();
+                let cwd = cwd.display().to_string();
+                Ok(ProtobufPluginCommand {
+                    name: CommandName::SpawnProcess as i32,
+                    payload: Some(Payload::SpawnProcessPayload(RunCommandPayload {
+                        command_line,
+                        env_variables,
+                        cwd,
+                        context,
+                    })),
+                })
+            },
+            PluginCommand::WriteToProcessStdin(process_id, bytes) => Ok(ProtobufPluginCommand {
+                name: CommandName::WriteToProcessStdin as i32,
+                payload: Some(Payload::WriteToProcessStdinPayload(
+                    WriteToProcessStdinPayload { process_id, bytes },
+                )),
+            }),
+            PluginCommand::CloseProcessStdin(process_id) => Ok(ProtobufPluginCommand {
+                name: CommandName::CloseProcessStdin as i32,
+                payload: Some(Payload::CloseProcessStdinPayload(
+                    CloseProcessStdinPayload { process_id },
+                )),
+            }),
+            PluginCommand::KillProcess(process_id) => Ok(ProtobufPluginCommand {
+                name: CommandName::KillProcess as i32,
+                payload: Some(Payload::KillProcessPayload(KillProcessPayload {
+                    process_id,
+                })),
+            }),
+            PluginCommand::SignalProcess(process_id, signal) => Ok(ProtobufPluginCommand {
+                name: CommandName::SignalProcess as i32,
+                payload: Some(Payload::SignalProcessPayload(SignalProcessPayload {
+                    process_id,
+                    signal,
+                })),
+            }),
+            PluginCommand::ListPluginPermissions => Ok(ProtobufPluginCommand {
+                name: CommandName::ListPluginPermissions as i32,
+                payload: None,
+            }),
+            PluginCommand::RevokePluginPermission(plugin_url, permission) => {
+                Ok(ProtobufPluginCommand {
+                    name: CommandName::RevokePluginPermission as i32,
+                    payload: Some(Payload::RevokePluginPermissionPayload(
+                        RevokePluginPermissionPayload {
+                            plugin_url,
+                            permission: ProtobufPermissionType::try_from(permission)? as i32,
+                        },
+                    )),
+                })
+            },
+            PluginCommand::GetResurrectableSessionLayout(session_name) => {
+                Ok(ProtobufPluginCommand {
+                    name: CommandName::GetResurrectableSessionLayout as i32,
+                    payload: Some(Payload::GetResurrectableSessionLayoutPayload(
+                        GetResurrectableSessionLayoutPayload { session_name },
+                    )),
+                })
+            },
+            PluginCommand::SearchAllPanes {
+                query,
+                case_sensitive,
+            } => Ok(ProtobufPluginCommand {
+                name: CommandName::SearchAllPanes as i32,
+                payload: Some(Payload::SearchAllPanesPayload(SearchAllPanesPayload {
+                    query,
+                    case_sensitive,
+                })),
+            }),
+            PluginCommand::RevealSearchMatch {
+                pane_id,
+                query,
+                case_sensitive,
+                line_index,
+            } => Ok(ProtobufPluginCommand {
+                name: CommandName::RevealSearchMatch as i32,
+                payload: Some(Payload::RevealSearchMatchPayload(
+                    RevealSearchMatchPayload {
+                        pane_id: Some(pane_id.try_into()?),
+                        query,
+                        case_sensitive,
+                        line_index: line_index as u32,
+                    },
+                )),
+            }),
+            PluginCommand::ToggleGroupBroadcast => Ok(ProtobufPluginCommand {
+                name: CommandName::ToggleGroupBroadcast as i32,
+                payload: None,
+            }),
+            PluginCommand::ListSessionSnapshots(session_name) => Ok(ProtobufPluginCommand {
+                name: CommandName::ListSessionSnapshots as i32,
+                payload: Some(Payload::ListSessionSnapshotsPayload(
+                    ListSessionSnapshotsPayload { session_name },
+                )),
+            }),
+            PluginCommand::SetPaneInputLock(pane_id, input_lock) => Ok(ProtobufPluginCommand {
+                name: CommandName::SetPaneInputLock as i32,
+                payload: Some(Payload::SetPaneInputLockPayload(SetPaneInputLockPayload {
+                    pane_id: Some(pane_id.try_into()?),
+                    input_lock: input_lock.map(|l| l.name().to_owned()),
+                })),
+            }),
+            PluginCommand::ListClosedPanes => Ok(ProtobufPluginCommand {
+                name: CommandName::ListClosedPanes as i32,
+                payload: None,
+            }),
+            PluginCommand::ReopenClosedPane(id) => Ok(ProtobufPluginCommand {
+                name: CommandName::ReopenClosedPane as i32,
+                payload: Some(Payload::ReopenClosedPanePayload(ReopenClosedPanePayload {
+                    id,
+                })),
+            }),
+            PluginCommand::GetClosedPaneScrollback(id) => Ok(ProtobufPluginCommand {
+                name: CommandName::GetClosedPaneScrollback as i32,
+                payload: Some(Payload::GetClosedPaneScrollbackPayload(
+                    GetClosedPaneScrollbackPayload { id },
+                )),
+            }),
             PluginCommand::OpenPluginPaneFloating {
                 plugin_url,
                 configuration,
@@ -0,0 +1,165 @@
+//! A local bundle of remote plugins, so that sessions can load them without network access.
+//!
+//! `zellij plugin vendor` downloads every remote plugin referenced by the configuration and its
+//! layouts into a directory, recording the url, hash and fetch time of each in a lockfile. When
+//! the `plugin_bundle_dir` option points at such a directory, remote plugins are loaded from it
+//! (after their hash is verified) instead of being downloaded.
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use thiserror::Error;
+
+use crate::downloader::{Downloader, DownloaderError};
+use crate::input::permission::plugin_content_hash;
+
+pub const PLUGIN_BUNDLE_LOCKFILE_NAME: &str = "plugins.lock.kdl";
+
+#[derive(Error, Debug)]
+pub enum PluginBundleError {
+    #[error("IoError: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Failed to download plugin: {0}")]
+    Download(#[from] DownloaderError),
+    #[error("Failed to parse plugin bundle lockfile: {0}")]
+    Lockfile(String),
+    #[error("Plugin {0} is not in the plugin bundle, add it with: zellij plugin vendor")]
+    NotBundled(String),
+    #[error("Plugin {url} does not match the plugin bundle lockfile (expected hash {expected}, found {found})")]
+    HashMismatch {
+        url: String,
+        expected: String,
+        found: String,
+    },
+}
+
+/// A single plugin in the bundle, as recorded in its lockfile
+#[derive(Debug, Clone, PartialEq)]
+pub struct BundledPlugin {
+    pub file_name: String,
+    pub hash: String,       // hex encoded SHA-256 of the wasm bytes
+    pub fetched_at: String, // RFC 3339
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct PluginBundle {
+    dir: PathBuf,
+    plugins: BTreeMap<String, BundledPlugin>, // url -> plugin
+}
+
+impl PluginBundle {
+    /// Reads the bundle in this directory, an empty bundle is returned if it has no lockfile
+    pub fn from_dir(dir: &Path) -> Result<Self, PluginBundleError> {
+        let lockfile_path = dir.join(PLUGIN_BUNDLE_LOCKFILE_NAME);
+        let plugins = if lockfile_path.exists() {
+            let raw_lockfile = std::fs::read_to_string(&lockfile_path)?;
+            PluginBundle::lockfile_from_string(&raw_lockfile)
+                .map_err(|e| PluginBundleError::Lockfile(e.to_string()))?
+        } else {
+            BTreeMap::new()
+        };
+        Ok(PluginBundle {
+            dir: dir.to_path_buf(),
+            plugins,
+        })
+    }
+    pub fn plugins(&self) -> &BTreeMap<String, BundledPlugin> {
+        &self.plugins
+    }
+    /// Returns the path of the bundled plugin for this url, making sure its contents still match
+    /// the hash recorded when it was vendored
+    pub fn resolve(&self, url: &str) -> Result<PathBuf, PluginBundleError> {
+        let bundled_plugin = self
+            .plugins
+            .get(url)
+            .ok_or_else(|| PluginBundleError::NotBundled(url.to_owned()))?;
+        let path = self.dir.join(&bundled_plugin.file_name);
+        let found = plugin_content_hash(&std::fs::read(&path)?);
+        if found != bundled_plugin.hash {
+            return Err(PluginBundleError::HashMismatch {
+                url: url.to_owned(),
+                expected: bundled_plugin.hash.clone(),
+                found,
+            });
+        }
+        Ok(path)
+    }
+    /// Downloads the plugin at this url into the bundle, replacing any previous version of it
+    pub async fn vendor(&mut self, url: &str) -> Result<(), PluginBundleError> {
+        let file_name = format!("{}.wasm", &plugin_content_hash(url.as_bytes())[..16]);
+        let path = self.dir.join(&file_name);
+        if path.exists() {
+            // the downloader would otherwise consider this plugin already downloaded
+            tokio::fs::remove_file(&path).await?;
+        }
+        Downloader::new(self.dir.clone())
+            .download(url, Some(&file_name))
+            .await?;
+        let hash = plugin_content_hash(&tokio::fs::read(&path).await?);
+        self.plugins.insert(
+            url.to_owned(),
+            BundledPlugin {
+                file_name,
+                hash,
+                fetched_at: humantime::format_rfc3339_seconds(SystemTime::now()).to_string(),
+            },
+        );
+        Ok(())
+    }
+    /// Like [`PluginBundle::vendor`], for use outside of an async runtime (eg. from the cli)
+    pub fn vendor_blocking(&mut self, url: &str) -> Result<(), PluginBundleError> {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .thread_name("plugin bundle vendoring")
+            .build()?;
+        runtime.block_on(self.vendor(url))
+    }
+    pub fn write_lockfile(&self) -> Result<(), PluginBundleError> {
+        std::fs::write(
+            self.dir.join(PLUGIN_BUNDLE_LOCKFILE_NAME),
+            PluginBundle::lockfile_to_string(&self.plugins),
+        )?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve_verifies_recorded_hash() {
+        let bundle_dir = tempfile::tempdir().unwrap();
+        let url = "https://example.com/plugin.wasm";
+        std::fs::write(bundle_dir.path().join("plugin.wasm"), b"original").unwrap();
+        let mut plugins = BTreeMap::new();
+        plugins.insert(
+            url.to_owned(),
+            BundledPlugin {
+                file_name: "plugin.wasm".to_owned(),
+                hash: plugin_content_hash(b"original"),
+                fetched_at: "2024-01-01T00:00:00Z".to_owned(),
+            },
+        );
+        PluginBundle {
+            dir: bundle_dir.path().to_path_buf(),
+            plugins,
+        }
+        .write_lockfile()
+        .unwrap();
+
+        let plugin_bundle = PluginBundle::from_dir(bundle_dir.path()).unwrap();
+        assert_eq!(
+            plugin_bundle.resolve(url).unwrap(),
+            bundle_dir.path().join("plugin.wasm")
+        );
+        assert!(matches!(
+            plugin_bundle.resolve("https://example.com/other.wasm"),
+            Err(PluginBundleError::NotBundled(_))
+        ));
+
+        std::fs::write(bundle_dir.path().join("plugin.wasm"), b"changed").unwrap();
+        assert!(matches!(
+            plugin_bundle.resolve(url),
+            Err(PluginBundleError::HashMismatch { .. })
+        ));
+    }
+}
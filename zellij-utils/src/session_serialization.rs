@@ -1,5 +1,5 @@
 use kdl::{KdlDocument, KdlEntry, KdlNode, KdlValue};
-use std::collections::{BTreeMap, HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 use std::path::PathBuf;
 
 use crate::{
@@ -7,6 +7,7 @@ use crate::{
     input::layout::{
         FloatingPaneLayout, Layout, LayoutConstraint, PercentOrFixed, Run, RunPluginOrAlias,
         SplitDirection, SplitSize, SwapFloatingLayout, SwapTiledLayout, TiledPaneLayout,
+        TilingStrategy,
     },
     pane_size::{Constraint, PaneGeom},
 };
@@ -25,6 +26,7 @@ pub struct TabLayoutManifest {
     pub floating_panes: Vec<PaneLayoutManifest>,
     pub is_focused: bool,
     pub hide_floating_panes: bool,
+    pub tiling_strategy: Option<TilingStrategy>,
 }
 
 #[derive(Default, Debug, Clone)]
@@ -82,10 +84,148 @@ pub fn serialize_session_layout(
     Ok((document.to_string(), pane_contents))
 }
 
+/// An opaque plugin state (as returned from a plugin's `save_state` method), along with the
+/// location and configuration of the plugin that saved it
+#[derive(Debug, Clone, PartialEq)]
+pub struct SavedPluginState {
+    pub location: String,
+    pub configuration: PluginUserConfiguration,
+    pub state: Vec<u8>,
+}
+
+/// The states saved by the running plugins, keyed by their plugin id
+pub type PluginStates = BTreeMap<u32, SavedPluginState>;
+
+/// Plugin states read back when resurrecting a session. Plugin ids are not kept across
+/// resurrections, so each plugin that loads takes the first state left by an instance with the
+/// same location and configuration, in the order those instances were loaded.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct PluginStatesToRestore(HashMap<(String, PluginUserConfiguration), VecDeque<Vec<u8>>>);
+
+impl PluginStatesToRestore {
+    pub fn take(
+        &mut self,
+        location: &str,
+        configuration: &PluginUserConfiguration,
+    ) -> Option<Vec<u8>> {
+        let key = (location.to_owned(), configuration.clone());
+        let states = self.0.get_mut(&key)?;
+        let state = states.pop_front();
+        if states.is_empty() {
+            self.0.remove(&key);
+        }
+        state
+    }
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+pub fn serialize_plugin_states(plugin_states: &PluginStates) -> String {
+    let mut document = KdlDocument::new();
+    let mut plugin_states_node = KdlNode::new("plugin_states");
+    let mut plugin_states_node_children = KdlDocument::new();
+    // plugin ids are given out in load order, which is the order the states are restored in
+    for saved_plugin_state in plugin_states.values() {
+        if saved_plugin_state.state.is_empty() {
+            continue;
+        }
+        let mut plugin_node = KdlNode::new("plugin");
+        plugin_node.entries_mut().push(KdlEntry::new_prop(
+            "location",
+            saved_plugin_state.location.to_owned(),
+        ));
+        let encoded_state: String = saved_plugin_state
+            .state
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect();
+        plugin_node
+            .entries_mut()
+            .push(KdlEntry::new_prop("state", encoded_state));
+        if !saved_plugin_state.configuration.inner().is_empty() {
+            let mut plugin_node_children = KdlDocument::new();
+            for (config_key, config_value) in saved_plugin_state.configuration.inner() {
+                let mut config_node = KdlNode::new(config_key.to_owned());
+                config_node
+                    .entries_mut()
+                    .push(KdlEntry::new(config_value.to_owned()));
+                plugin_node_children.nodes_mut().push(config_node);
+            }
+            plugin_node.set_children(plugin_node_children);
+        }
+        plugin_states_node_children.nodes_mut().push(plugin_node);
+    }
+    plugin_states_node.set_children(plugin_states_node_children);
+    document.nodes_mut().push(plugin_states_node);
+    document.to_string()
+}
+
+pub fn deserialize_plugin_states(
+    raw_plugin_states: &str,
+) -> Result<PluginStatesToRestore, &'static str> {
+    let document: KdlDocument = raw_plugin_states
+        .parse()
+        .map_err(|_| "Failed to parse plugin states")?;
+    let mut plugin_states = PluginStatesToRestore::default();
+    let plugin_nodes = document
+        .get("plugin_states")
+        .and_then(|n| n.children())
+        .map(|c| c.nodes())
+        .unwrap_or(&[]);
+    for plugin_node in plugin_nodes {
+        let location = plugin_node
+            .get("location")
+            .and_then(|e| e.value().as_string())
+            .ok_or("Plugin state is missing a location")?;
+        let encoded_state = plugin_node
+            .get("state")
+            .and_then(|e| e.value().as_string())
+            .ok_or("Plugin state is missing its state")?;
+        let state = decode_plugin_state(encoded_state).ok_or("Malformed plugin state")?;
+        let mut configuration = BTreeMap::new();
+        if let Some(children) = plugin_node.children() {
+            for config_node in children.nodes() {
+                if let Some(config_value) = config_node
+                    .entries()
+                    .iter()
+                    .next()
+                    .and_then(|e| e.value().as_string())
+                {
+                    configuration.insert(
+                        config_node.name().value().to_owned(),
+                        config_value.to_owned(),
+                    );
+                }
+            }
+        }
+        plugin_states
+            .0
+            .entry((
+                location.to_owned(),
+                PluginUserConfiguration::new(configuration),
+            ))
+            .or_default()
+            .push_back(state);
+    }
+    Ok(plugin_states)
+}
+
+fn decode_plugin_state(encoded_state: &str) -> Option<Vec<u8>> {
+    if !encoded_state.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..encoded_state.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(encoded_state.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
 fn serialize_tab(
     tab_name: String,
     is_focused: bool,
     hide_floating_panes: bool,
+    tiling_strategy: Option<TilingStrategy>,
     tiled_panes: &Vec<PaneLayoutManifest>,
     floating_panes: &Vec<PaneLayoutManifest>,
     pane_contents: &mut BTreeMap<String, String>,
@@ -117,6 +257,23 @@ fn serialize_tab(
                     KdlValue::Bool(true),
                 ));
             }
+            if let Some(tiling_strategy) = tiling_strategy {
+                serialized_tab
+                    .entries_mut()
+                    .push(KdlEntry::new_prop("tiling", tiling_strategy.name()));
+                if let TilingStrategy::MasterStack {
+                    master_ratio,
+                    master_count,
+                } = tiling_strategy
+                {
+                    serialized_tab
+                        .entries_mut()
+                        .push(KdlEntry::new_prop("master_ratio", master_ratio as i64));
+                    serialized_tab
+                        .entries_mut()
+                        .push(KdlEntry::new_prop("master_count", master_count as i64));
+                }
+            }
 
             serialize_tiled_and_floating_panes(
                 &tiled_panes,
@@ -636,6 +793,7 @@ fn serialize_multiple_tabs(
             tab_name.clone(),
             tab_layout_manifest.is_focused,
             hide_floating_panes,
+            tab_layout_manifest.tiling_strategy,
             &tiled_panes,
             &floating_panes,
             pane_contents,
@@ -1347,6 +1505,90 @@ mod tests {
         .assert_eq(&kdl.0);
     }
 
+    #[test]
+    fn can_serialize_and_deserialize_plugin_states() {
+        let mut configuration = BTreeMap::new();
+        configuration.insert("filter".to_owned(), "foo".to_owned());
+        let configuration = PluginUserConfiguration::new(configuration);
+        let mut plugin_states = PluginStates::new();
+        plugin_states.insert(
+            1,
+            SavedPluginState {
+                location: "file:/path/to/plugin.wasm".to_owned(),
+                configuration: configuration.clone(),
+                state: vec![0, 1, 254, 255],
+            },
+        );
+        plugin_states.insert(
+            2,
+            SavedPluginState {
+                location: "zellij:strider".to_owned(),
+                configuration: Default::default(),
+                state: b"state".to_vec(),
+            },
+        );
+        plugin_states.insert(
+            3,
+            SavedPluginState {
+                location: "zellij:tab-bar".to_owned(),
+                configuration: Default::default(),
+                state: vec![],
+            },
+        );
+        let kdl = serialize_plugin_states(&plugin_states);
+        expect![[r#"
+            plugin_states {
+                plugin location="file:/path/to/plugin.wasm" state="0001feff" {
+                    filter "foo"
+                }
+                plugin location="zellij:strider" state="7374617465"
+            }
+        "#]]
+        .assert_eq(&kdl);
+        let mut plugin_states_to_restore = deserialize_plugin_states(&kdl).unwrap();
+        assert_eq!(
+            plugin_states_to_restore.take("file:/path/to/plugin.wasm", &configuration),
+            Some(vec![0, 1, 254, 255])
+        );
+        assert_eq!(
+            plugin_states_to_restore.take("zellij:strider", &Default::default()),
+            Some(b"state".to_vec())
+        );
+        assert!(plugin_states_to_restore.is_empty());
+    }
+    #[test]
+    fn plugin_states_of_identical_plugins_are_kept_apart() {
+        let configuration = PluginUserConfiguration::new(BTreeMap::from([(
+            "filter".to_owned(),
+            "todo".to_owned(),
+        )]));
+        let saved_plugin_state = |state: &[u8]| SavedPluginState {
+            location: "file:/path/to/plugin.wasm".to_owned(),
+            configuration: configuration.clone(),
+            state: state.to_vec(),
+        };
+        let mut plugin_states = PluginStates::new();
+        plugin_states.insert(7, saved_plugin_state(b"second"));
+        plugin_states.insert(3, saved_plugin_state(b"first"));
+        plugin_states.insert(9, saved_plugin_state(b""));
+        let mut plugin_states_to_restore =
+            deserialize_plugin_states(&serialize_plugin_states(&plugin_states)).unwrap();
+        let location = "file:/path/to/plugin.wasm";
+        assert_eq!(
+            plugin_states_to_restore.take(location, &configuration),
+            Some(b"first".to_vec())
+        );
+        assert_eq!(
+            plugin_states_to_restore.take(location, &configuration),
+            Some(b"second".to_vec())
+        );
+        assert_eq!(
+            plugin_states_to_restore.take(location, &configuration),
+            None,
+            "Empty states are not saved"
+        );
+        assert!(plugin_states_to_restore.is_empty());
+    }
     #[test]
     fn global_cwd() {
         let global_layout_manifest = GlobalLayoutManifest {
@@ -1393,6 +1635,22 @@ mod tests {
         assert_snapshot!(kdl.0);
     }
     #[test]
+    fn can_serialize_tab_tiling_strategy() {
+        let tab_layout_manifest = TabLayoutManifest {
+            tiling_strategy: Some(TilingStrategy::MasterStack {
+                master_ratio: 60,
+                master_count: 2,
+            }),
+            ..Default::default()
+        };
+        let global_layout_manifest = GlobalLayoutManifest {
+            tabs: vec![("Tab #1".to_owned(), tab_layout_manifest)],
+            ..Default::default()
+        };
+        let kdl = serialize_session_layout(global_layout_manifest).unwrap();
+        assert_snapshot!(kdl.0);
+    }
+    #[test]
     fn can_serialize_tab_with_tiled_panes() {
         use crate::input::command::RunCommand;
         use crate::input::layout::RunPlugin;
@@ -1,8 +1,9 @@
 use crate::{
     consts::{
         is_ipc_socket, session_info_folder_for_session, session_layout_cache_file_name,
-        ZELLIJ_SESSION_INFO_CACHE_DIR, ZELLIJ_SOCK_DIR,
+        SNAPSHOT_LAYOUT_FILE_NAME, ZELLIJ_SESSION_INFO_CACHE_DIR, ZELLIJ_SOCK_DIR,
     },
+    data::SessionSnapshot,
     envs,
     input::layout::Layout,
     ipc::{ClientToServerMsg, IpcReceiverWithContext, IpcSenderWithContext, ServerToClientMsg},
@@ -10,6 +11,7 @@ use crate::{
 use anyhow;
 use humantime::format_duration;
 use std::collections::HashMap;
+use std::path::Path;
 use std::time::{Duration, SystemTime};
 use std::{fs, io, process};
 use suggest::Suggest;
@@ -469,6 +471,97 @@ pub fn resurrection_layout(session_name_to_resurrect: &str) -> Result<Option<Lay
     }
 }
 
+/// The raw serialized layout of a resurrectable session, for plugins to inspect
+pub fn resurrectable_session_layout(session_name: &str) -> Result<String, String> {
+    // the name comes from plugins, and is joined to the session info folder
+    validate_session_name(session_name)?;
+    if session_name.contains('\\') {
+        return Err("Session name cannot contain path separators.".to_string());
+    }
+    let layout_file_name = session_layout_cache_file_name(session_name);
+    std::fs::read_to_string(&layout_file_name).map_err(|e| {
+        format!(
+            "Failed to read the layout of session {}: {}",
+            session_name, e
+        )
+    })
+}
+
+/// The snapshots saved in the `snapshots_folder` of a session (see
+/// `session_snapshots_folder_for_session`), most recent first
+pub fn get_session_snapshots(snapshots_folder: &Path) -> Vec<SessionSnapshot> {
+    let snapshot_folders = match fs::read_dir(snapshots_folder) {
+        Ok(snapshot_folders) => snapshot_folders,
+        Err(_) => return vec![],
+    };
+    let mut snapshots: Vec<SessionSnapshot> = snapshot_folders
+        .filter_map(|f| f.ok())
+        .filter_map(|snapshot_folder| {
+            let name = snapshot_folder.file_name().into_string().ok()?;
+            let layout_path = snapshot_folder.path().join(SNAPSHOT_LAYOUT_FILE_NAME);
+            let mtime = std::fs::metadata(&layout_path).ok()?.modified().ok();
+            let age = mtime
+                .and_then(|mtime| mtime.elapsed().ok())
+                .map(|elapsed| Duration::from_secs(elapsed.as_secs()))
+                .unwrap_or_default();
+            Some(SessionSnapshot {
+                name,
+                layout_path,
+                age,
+            })
+        })
+        .collect();
+    snapshots.sort_by(|a, b| a.age.cmp(&b.age).then_with(|| a.name.cmp(&b.name)));
+    snapshots
+}
+
+pub fn format_session_snapshots(snapshots: &[SessionSnapshot]) -> Vec<String> {
+    snapshots
+        .iter()
+        .map(|snapshot| {
+            format!(
+                "{} [Saved {} ago]",
+                snapshot.name,
+                format_duration(snapshot.age)
+            )
+        })
+        .collect()
+}
+
+pub fn delete_session_snapshot(snapshots_folder: &Path, snapshot_name: &str) -> Result<(), String> {
+    SessionSnapshot::validate_name(snapshot_name)?;
+    std::fs::remove_dir_all(snapshots_folder.join(snapshot_name)).map_err(|e| {
+        if e.kind() == io::ErrorKind::NotFound {
+            format!("No snapshot named {:?} found.", snapshot_name)
+        } else {
+            format!("Failed to delete snapshot {:?}: {}", snapshot_name, e)
+        }
+    })
+}
+
+// the layout a snapshot was saved with, with its pane contents files read relative to it
+pub fn snapshot_layout(snapshots_folder: &Path, snapshot_name: &str) -> Result<Layout, String> {
+    SessionSnapshot::validate_name(snapshot_name)?;
+    let layout_file_name = snapshots_folder
+        .join(snapshot_name)
+        .join(SNAPSHOT_LAYOUT_FILE_NAME);
+    let raw_layout = std::fs::read_to_string(&layout_file_name)
+        .map_err(|_| format!("No snapshot named {:?} found.", snapshot_name))?;
+    Layout::from_kdl(
+        &raw_layout,
+        Some(layout_file_name.display().to_string()),
+        None,
+        None,
+    )
+    .map_err(|e| {
+        format!(
+            "Failed to parse snapshot layout file {}: {}",
+            layout_file_name.display(),
+            e
+        )
+    })
+}
+
 pub fn assert_session(name: &str) {
     match session_exists(name) {
         Ok(result) => {
@@ -729,3 +822,38 @@ const NOUNS: &[&'static str] = &[
     "yak",
     "zebra",
 ];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resurrectable_session_layout_rejects_names_that_are_paths() {
+        for session_name in [
+            "..",
+            ".",
+            "../other-session",
+            "nested/../../session",
+            "/absolute",
+            "back\\slash",
+            "..\\other-session",
+            "",
+        ] {
+            assert!(
+                resurrectable_session_layout(session_name).is_err(),
+                "{:?} was not rejected",
+                session_name
+            );
+        }
+    }
+
+    #[test]
+    fn resurrectable_session_layout_of_missing_session_is_an_error() {
+        let error = resurrectable_session_layout("no-such-session-ever-existed").unwrap_err();
+        assert!(
+            error.starts_with("Failed to read the layout of session no-such-session-ever-existed"),
+            "{}",
+            error
+        );
+    }
+}
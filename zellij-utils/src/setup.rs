@@ -18,7 +18,10 @@ use clap::{Args, IntoApp};
 use clap_complete::Shell;
 use log::info;
 use serde::{Deserialize, Serialize};
-use std::{convert::TryFrom, fmt::Write as FmtWrite, fs, io::Write, path::PathBuf, process};
+use std::{
+    collections::BTreeMap, convert::TryFrom, fmt::Write as FmtWrite, fs, io::Write, path::PathBuf,
+    process,
+};
 
 const CONFIG_NAME: &str = "config.kdl";
 static ARROW_SEPARATOR: &str = "";
@@ -629,16 +632,23 @@ impl Setup {
             let layout_info = LayoutInfo::from_config(&layout_dir, &chosen_layout);
             (layout_info, chosen_layout)
         };
+        let layout_vars: BTreeMap<String, String> = cli_args.layout_vars.iter().cloned().collect();
         match layout_info {
             Some(LayoutInfo::Url(ref layout_url)) => {
-                Layout::from_url(layout_url, config).map(|(_layout, config)| (layout_info, config))
+                Layout::from_url(layout_url, config, &layout_vars)
+                    .map(|(_layout, config)| (layout_info, config))
             },
             Some(LayoutInfo::Stringified(ref raw_layout)) => {
-                Layout::from_stringified_layout(raw_layout, config)
+                Layout::from_stringified_layout(raw_layout, config, &layout_vars)
                     .map(|(_layout, config)| (layout_info, config))
             },
-            _ => Layout::from_path_or_default(chosen_layout.as_ref(), layout_dir.clone(), config)
-                .map(|(_layout, config)| (layout_info, config)),
+            _ => Layout::from_path_or_default(
+                chosen_layout.as_ref(),
+                layout_dir.clone(),
+                config,
+                &layout_vars,
+            )
+            .map(|(_layout, config)| (layout_info, config)),
         }
     }
     fn handle_setup_commands(cli_args: &CliArgs) {
@@ -36,6 +36,12 @@ pub fn ansi_len(s: &str) -> usize {
     from_utf8(&strip(s).unwrap()).unwrap().width()
 }
 
+pub fn strip_ansi_codes(s: &str) -> String {
+    strip(s)
+        .map(|stripped| String::from_utf8_lossy(&stripped).into_owned())
+        .unwrap_or_default()
+}
+
 pub fn clean_string_from_control_and_linebreak(input: &str) -> String {
     input
         .chars()